@@ -0,0 +1,237 @@
+//! Security advisories and yanked versions for `unrealpm audit`
+//!
+//! Registries publish advisories for vulnerable package versions. Each one
+//! names the affected versions as a semver range, and the patched range when
+//! a fix exists:
+//!
+//! ```json
+//! {
+//!   "id": "UPMSA-2026-0004",
+//!   "package": "awesome-plugin",
+//!   "severity": "high",
+//!   "title": "Path traversal when importing .uasset bundles",
+//!   "affected": ">=1.0.0, <1.2.3",
+//!   "patched": ">=1.2.3",
+//!   "url": "https://registry.unreal.dev/advisories/UPMSA-2026-0004"
+//! }
+//! ```
+//!
+//! [`findings`] matches them (and yank status) against the locked versions
+//! and ranks the result by [`Severity`]. Yanked versions rank lowest, as
+//! [`Severity::Warning`].
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::advisory::{findings, Advisory, Severity};
+//!
+//! let advisory = Advisory {
+//!     id: "UPMSA-2026-0004".to_string(),
+//!     package: "awesome-plugin".to_string(),
+//!     severity: Severity::High,
+//!     title: "Path traversal when importing .uasset bundles".to_string(),
+//!     affected: ">=1.0.0, <1.2.3".to_string(),
+//!     patched: Some(">=1.2.3".to_string()),
+//!     url: None,
+//! };
+//! assert!(advisory.affects("1.2.0"));
+//! assert!(!advisory.affects("1.2.3"));
+//!
+//! let locked = [("awesome-plugin".to_string(), "1.2.0".to_string())];
+//! let found = findings(&locked, &[advisory], &[]);
+//! assert_eq!(found[0].severity, Severity::High);
+//! ```
+
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How serious a finding is, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Not a vulnerability: a yanked version
+    Warning,
+    Low,
+    #[serde(alias = "moderate")]
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Severity::Warning => "warning",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        };
+        f.write_str(label)
+    }
+}
+
+/// A vulnerability in some versions of a package, as published by a registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Advisory identifier, e.g. `UPMSA-2026-0004`
+    pub id: String,
+    pub package: String,
+    pub severity: Severity,
+    pub title: String,
+    /// Semver range of affected versions
+    pub affected: String,
+    /// Semver range of fixed versions, if a fix was released
+    #[serde(default)]
+    pub patched: Option<String>,
+    /// Page with the full advisory
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl Advisory {
+    /// Whether `version` is in the affected range
+    ///
+    /// An advisory whose range doesn't parse affects every version, so a
+    /// malformed entry is reported rather than dropped.
+    pub fn affects(&self, version: &str) -> bool {
+        let Ok(version) = Version::parse(version) else {
+            return false;
+        };
+        match VersionReq::parse(&self.affected) {
+            Ok(req) => req.matches(&version),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Why a locked package is reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FindingKind {
+    Advisory(Advisory),
+    Yanked,
+}
+
+/// One problem with one locked package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub package: String,
+    pub version: String,
+    pub severity: Severity,
+    pub kind: FindingKind,
+}
+
+/// Advisories affecting the `locked` (name, version) pairs, and the `yanked`
+/// package names, most severe first
+pub fn findings(
+    locked: &[(String, String)],
+    advisories: &[Advisory],
+    yanked: &[String],
+) -> Vec<Finding> {
+    let mut found: Vec<Finding> = Vec::new();
+    for (name, version) in locked {
+        for advisory in advisories
+            .iter()
+            .filter(|advisory| &advisory.package == name && advisory.affects(version))
+        {
+            if found.iter().any(|finding| {
+                matches!(&finding.kind, FindingKind::Advisory(seen) if seen.id == advisory.id)
+                    && &finding.package == name
+            }) {
+                continue;
+            }
+            found.push(Finding {
+                package: name.clone(),
+                version: version.clone(),
+                severity: advisory.severity,
+                kind: FindingKind::Advisory(advisory.clone()),
+            });
+        }
+        if yanked.contains(name) {
+            found.push(Finding {
+                package: name.clone(),
+                version: version.clone(),
+                severity: Severity::Warning,
+                kind: FindingKind::Yanked,
+            });
+        }
+    }
+    found.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.package.cmp(&b.package))
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(id: &str, package: &str, severity: Severity, affected: &str) -> Advisory {
+        Advisory {
+            id: id.to_string(),
+            package: package.to_string(),
+            severity,
+            title: "Example".to_string(),
+            affected: affected.to_string(),
+            patched: None,
+            url: None,
+        }
+    }
+
+    #[test]
+    fn test_affects() {
+        let range = advisory("A-1", "core", Severity::Low, ">=1.0.0, <1.2.3");
+        assert!(range.affects("1.0.0"));
+        assert!(range.affects("1.2.2"));
+        assert!(!range.affects("1.2.3"));
+        assert!(!range.affects("0.9.0"));
+        assert!(!range.affects("not-a-version"));
+
+        // Malformed ranges are reported, not dropped
+        assert!(advisory("A-2", "core", Severity::Low, "all of them").affects("1.0.0"));
+    }
+
+    #[test]
+    fn test_findings_ranked() {
+        let locked = vec![
+            ("alpha".to_string(), "1.0.0".to_string()),
+            ("beta".to_string(), "2.0.0".to_string()),
+            ("gamma".to_string(), "3.0.0".to_string()),
+        ];
+        let advisories = vec![
+            advisory("A-1", "alpha", Severity::Medium, "<2.0.0"),
+            advisory("A-2", "beta", Severity::Critical, "^2"),
+            advisory("A-3", "gamma", Severity::High, "<3.0.0"),
+            advisory("A-1", "alpha", Severity::Medium, "<2.0.0"),
+        ];
+        let found = findings(&locked, &advisories, &["gamma".to_string()]);
+        let summary: Vec<(&str, Severity)> = found
+            .iter()
+            .map(|finding| (finding.package.as_str(), finding.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("beta", Severity::Critical),
+                ("alpha", Severity::Medium),
+                ("gamma", Severity::Warning),
+            ]
+        );
+        assert_eq!(found[2].kind, FindingKind::Yanked);
+    }
+
+    #[test]
+    fn test_severity_from_registry() {
+        let parsed: Advisory = serde_json::from_str(
+            r#"{"id": "A-1", "package": "core", "severity": "moderate",
+                "title": "Example", "affected": "<1.0.0"}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.severity, Severity::Medium);
+        assert!(Severity::Critical > Severity::High);
+        assert!(Severity::Low > Severity::Warning);
+    }
+}
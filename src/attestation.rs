@@ -0,0 +1,324 @@
+//! Signed build attestations
+//!
+//! `unrealpm attest` records exactly which packages, checksums, and engine a
+//! build was made with, signed with the user's Ed25519 key, so the file can be
+//! archived next to a shipped game build and checked later with
+//! `unrealpm attest verify`.
+//!
+//! # Format (version 1)
+//!
+//! ```json
+//! {
+//!   "format": "unrealpm-attestation/v1",
+//!   "statement": {
+//!     "created_at": "2025-01-01T12:00:00+00:00",
+//!     "unrealpm_version": "0.4.7",
+//!     "project": "MyGame",
+//!     "build_id": "ci-1234",
+//!     "platform": "Win64",
+//!     "engine_version": "5.4",
+//!     "engine_build": "5.4.2-33871570+++UE5+Release-5.4",
+//!     "lockfile_sha256": "9f86d081884c7d65...",
+//!     "packages": [
+//!       { "name": "awesome-plugin", "version": "1.2.0", "checksum": "sha256:..." }
+//!     ]
+//!   },
+//!   "public_key": "<hex Ed25519 public key>",
+//!   "signature": "<hex Ed25519 signature>"
+//! }
+//! ```
+//!
+//! The signature covers the compact JSON serialization of `statement`, whose
+//! fields are written in a fixed order and whose packages are sorted by name.
+//! A valid signature only proves the file is unchanged since signing; check
+//! `public_key` against a key you trust to know who signed it.
+
+use crate::{Error, LockedPackage, Lockfile, PackageSigningKey, PrebuiltBinary, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Format identifier written to every attestation
+pub const ATTESTATION_FORMAT: &str = "unrealpm-attestation/v1";
+
+/// Default attestation filename
+pub const ATTESTATION_FILE_NAME: &str = "unrealpm-attestation.json";
+
+/// A signed attestation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    pub format: String,
+    pub statement: AttestationStatement,
+
+    /// Hex-encoded Ed25519 public key of the signer
+    pub public_key: String,
+
+    /// Hex-encoded Ed25519 signature over the statement
+    pub signature: String,
+}
+
+/// What was attested: the dependency set and engine used for one build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestationStatement {
+    /// When the attestation was created (RFC 3339)
+    pub created_at: String,
+    pub unrealpm_version: String,
+
+    /// Project name (.uproject file stem)
+    pub project: String,
+
+    /// Caller-supplied build identifier (e.g., CI build number or changelist)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_id: Option<String>,
+
+    /// Host platform the attestation was created on
+    pub platform: String,
+
+    /// Engine version packages were resolved for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_version: Option<String>,
+
+    /// Full engine Build.version string, when the engine was found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_build: Option<String>,
+
+    /// SHA256 of the unrealpm.lock contents
+    pub lockfile_sha256: String,
+
+    /// Locked packages, sorted by name
+    pub packages: Vec<AttestedPackage>,
+}
+
+/// One locked package in an attestation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttestedPackage {
+    pub name: String,
+    pub version: String,
+    pub checksum: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patched_hash: Option<String>,
+
+    /// Pre-built binary installed instead of the source tarball, with its
+    /// own checksum; `None` for source installs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<PrebuiltBinary>,
+}
+
+impl AttestationStatement {
+    /// Build a statement from a lockfile's contents
+    ///
+    /// The engine and build fields start empty; fill them in before signing.
+    pub fn from_lockfile(project: &str, lockfile_contents: &str) -> Result<Self> {
+        let lockfile = Lockfile::parse(lockfile_contents)?;
+
+        let mut packages: Vec<AttestedPackage> = lockfile
+            .packages
+            .into_iter()
+            .map(|(name, package)| AttestedPackage {
+                name,
+                version: package.version,
+                checksum: package.checksum,
+                source: package.source,
+                patched_hash: package.patched_hash,
+                binary: package.binary,
+            })
+            .collect();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            created_at: chrono::Utc::now().to_rfc3339(),
+            unrealpm_version: env!("CARGO_PKG_VERSION").to_string(),
+            project: project.to_string(),
+            build_id: None,
+            platform: crate::detect_platform(),
+            engine_version: None,
+            engine_build: None,
+            lockfile_sha256: hex::encode(Sha256::digest(lockfile_contents.as_bytes())),
+            packages,
+        })
+    }
+
+    /// Bytes covered by the signature
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize attestation: {}", e)))
+    }
+
+    /// Sign the statement
+    pub fn sign(self, key: &PackageSigningKey) -> Result<Attestation> {
+        let signature = key.sign(&self.signing_bytes()?);
+        Ok(Attestation {
+            format: ATTESTATION_FORMAT.to_string(),
+            statement: self,
+            public_key: key.public_key_hex(),
+            signature: hex::encode(signature.to_bytes()),
+        })
+    }
+
+    /// The attested packages as a lockfile, for diffing against a project
+    pub fn to_lockfile(&self) -> Lockfile {
+        let mut lockfile = Lockfile::new();
+        lockfile.packages = self
+            .packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.clone(),
+                    LockedPackage {
+                        version: p.version.clone(),
+                        checksum: p.checksum.clone(),
+                        dependencies: None,
+                        patched_hash: p.patched_hash.clone(),
+                        source: p.source.clone(),
+                        binary: p.binary.clone(),
+                        dev: false,
+                        features: Vec::new(),
+                        partial: false,
+                        registry: None,
+                        license: None,
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        lockfile
+    }
+}
+
+impl Attestation {
+    /// Load an attestation file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = fs::read_to_string(path.as_ref())?;
+        let attestation: Self = serde_json::from_str(&contents).map_err(|e| {
+            Error::Other(format!(
+                "Failed to parse attestation {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+
+        if attestation.format != ATTESTATION_FORMAT {
+            return Err(Error::Other(format!(
+                "Unsupported attestation format '{}' (expected '{}')",
+                attestation.format, ATTESTATION_FORMAT
+            )));
+        }
+        Ok(attestation)
+    }
+
+    /// Write the attestation as pretty-printed JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize attestation: {}", e)))?;
+        fs::write(path.as_ref(), json + "\n")?;
+        Ok(())
+    }
+
+    /// Whether the signature matches the statement and embedded public key
+    pub fn verify_signature(&self) -> Result<bool> {
+        let signature = hex::decode(&self.signature)
+            .map_err(|e| Error::Other(format!("Invalid attestation signature: {}", e)))?;
+        crate::verify_signature(
+            &self.statement.signing_bytes()?,
+            &signature,
+            &self.public_key,
+        )
+        .map_err(|e| Error::Other(format!("Failed to verify attestation: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lockfile_contents() -> String {
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package("b-plugin".into(), "2.0.0".into(), "sha256:bbb".into(), None);
+        lockfile.update_package("a-plugin".into(), "1.0.0".into(), "sha256:aaa".into(), None);
+        lockfile.set_binary(
+            "b-plugin",
+            Some(PrebuiltBinary {
+                platform: "Win64".to_string(),
+                engine: "5.4".to_string(),
+                tarball: "2.0.0-Win64-5.4".to_string(),
+                checksum: "sha256:bin".to_string(),
+                configuration: Some("Shipping".to_string()),
+            }),
+        );
+        toml::to_string_pretty(&lockfile).unwrap()
+    }
+
+    #[test]
+    fn test_statement_from_lockfile() {
+        let contents = lockfile_contents();
+        let statement = AttestationStatement::from_lockfile("MyGame", &contents).unwrap();
+
+        let names: Vec<&str> = statement.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["a-plugin", "b-plugin"]);
+        assert_eq!(statement.project, "MyGame");
+        assert_eq!(statement.lockfile_sha256.len(), 64);
+        assert_eq!(
+            statement.packages[1].binary.as_ref().unwrap().checksum,
+            "sha256:bin"
+        );
+        assert!(statement
+            .to_lockfile()
+            .diff(&Lockfile::parse(&contents).unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sign_and_verify_roundtrip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join(ATTESTATION_FILE_NAME);
+        let key = PackageSigningKey::generate().unwrap();
+
+        let mut statement =
+            AttestationStatement::from_lockfile("MyGame", &lockfile_contents()).unwrap();
+        statement.engine_version = Some("5.4".to_string());
+        statement.build_id = Some("ci-1234".to_string());
+        statement.sign(&key).unwrap().save(&path).unwrap();
+
+        let loaded = Attestation::load(&path).unwrap();
+        assert!(loaded.verify_signature().unwrap());
+        assert_eq!(loaded.public_key, key.public_key_hex());
+
+        // Any edit to the statement invalidates the signature
+        let mut tampered = loaded.clone();
+        tampered.statement.packages[0].checksum = "sha256:evil".to_string();
+        assert!(!tampered.verify_signature().unwrap());
+
+        let mut tampered = loaded.clone();
+        tampered.statement.packages[1]
+            .binary
+            .as_mut()
+            .unwrap()
+            .checksum = "sha256:evil".to_string();
+        assert!(!tampered.verify_signature().unwrap());
+
+        let mut tampered = loaded;
+        tampered.statement.engine_version = None;
+        assert!(!tampered.verify_signature().unwrap());
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_format() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join(ATTESTATION_FILE_NAME);
+        let key = PackageSigningKey::generate().unwrap();
+
+        let mut attestation = AttestationStatement::from_lockfile("MyGame", &lockfile_contents())
+            .unwrap()
+            .sign(&key)
+            .unwrap();
+        attestation.format = "unrealpm-attestation/v99".to_string();
+        attestation.save(&path).unwrap();
+
+        assert!(Attestation::load(&path).is_err());
+    }
+}
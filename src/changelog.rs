@@ -0,0 +1,334 @@
+//! Release notes between two versions of a package
+//!
+//! `update --dry-run` shows what changed in each bumped package. Notes come
+//! from the package's changelog (uploaded at publish time, or a
+//! `CHANGELOG.md`/`CHANGES`/`HISTORY` file in the tarball), split into one
+//! [`ChangelogSection`] per version heading, such as `## [1.4.1] - 2024-05-02`,
+//! `## v1.4.1` or `# 1.4.1 (May 2024)`. A package without a changelog but
+//! with a git repository gets the commit subjects between its version tags
+//! instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::changelog::between;
+//!
+//! let changelog = "# Changelog\n\n## [1.4.1]\n- Fix crash\n\n## [1.3.0]\n- Add API\n\n## [1.2.0]\n- Initial\n";
+//! let sections = between(changelog, "1.2.0", "1.4.1");
+//! let versions: Vec<&str> = sections.iter().map(|s| s.version.as_str()).collect();
+//! assert_eq!(versions, vec!["1.4.1", "1.3.0"]);
+//! assert_eq!(sections[0].body, "- Fix crash");
+//! ```
+
+use crate::{Error, Result};
+use semver::Version;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Changelog file stems looked for in package tarballs
+pub const CHANGELOG_STEMS: &[&str] = &["changelog", "changes", "history", "release-notes"];
+
+/// Where a version's release notes can come from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangelogSource {
+    /// Changelog text from the registry or the package tarball
+    pub changelog: Option<String>,
+    /// Repository to read commit history from when there is no changelog
+    pub git_repository: Option<String>,
+}
+
+/// The notes for one version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogSection {
+    /// Version the heading names, as written (without a leading `v`)
+    pub version: String,
+    /// Heading text, e.g. `[1.4.1] - 2024-05-02`
+    pub title: String,
+    /// Markdown under the heading, trimmed
+    pub body: String,
+}
+
+/// Split a changelog into one section per version heading, in document order
+///
+/// Headings without a version (like `# Changelog` or `## Unreleased`) end
+/// the previous section but don't start one.
+pub fn parse(text: &str) -> Vec<ChangelogSection> {
+    let mut sections = Vec::new();
+    let mut current: Option<(ChangelogSection, usize, Vec<&str>)> = None;
+
+    for line in text.lines() {
+        let level = line.chars().take_while(|&c| c == '#').count();
+        let is_heading = level > 0 && line[level..].starts_with(' ');
+        if is_heading {
+            let ends_section = current
+                .as_ref()
+                .is_some_and(|(_, current_level, _)| level <= *current_level);
+            let title = line[level..].trim().trim_end_matches('#').trim();
+            let version = heading_version(title);
+            if ends_section || version.is_some() {
+                if let Some((section, _, body)) = current.take() {
+                    sections.push(finish(section, body));
+                }
+            }
+            if let Some(version) = version {
+                let section = ChangelogSection {
+                    version,
+                    title: title.to_string(),
+                    body: String::new(),
+                };
+                current = Some((section, level, Vec::new()));
+                continue;
+            }
+        }
+        if let Some((_, _, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    if let Some((section, _, body)) = current {
+        sections.push(finish(section, body));
+    }
+    sections
+}
+
+/// Sections for versions after `from` up to and including `to`
+pub fn between(text: &str, from: &str, to: &str) -> Vec<ChangelogSection> {
+    let (Some(from), Some(to)) = (parse_version(from), parse_version(to)) else {
+        return Vec::new();
+    };
+    parse(text)
+        .into_iter()
+        .filter(|section| parse_version(&section.version).is_some_and(|v| v > from && v <= to))
+        .collect()
+}
+
+/// Where [`git_log_between`] keeps its clones (`~/.unrealpm/git`)
+pub fn default_git_cache_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| Error::Other("Could not find home directory".to_string()))?;
+    Ok(home.join(".unrealpm").join("git"))
+}
+
+/// Commit subjects between two version tags of a git repository
+///
+/// Keeps a blobless bare clone per repository under `cache_dir`, fetching
+/// tags on later calls. Tags are matched as `v1.2.0` or `1.2.0`. Returns
+/// `None` when either version has no tag.
+pub fn git_log_between(
+    repository: &str,
+    cache_dir: &Path,
+    from: &str,
+    to: &str,
+) -> Result<Option<ChangelogSection>> {
+    // The URL comes from registry metadata; never let it read as an option
+    if repository.starts_with('-') {
+        return Err(Error::Other(format!(
+            "Invalid git repository URL: {}",
+            repository
+        )));
+    }
+    let clone = git_cache_path(cache_dir, repository);
+    let mut command = Command::new("git");
+    if clone.exists() {
+        command
+            .arg("--git-dir")
+            .arg(&clone)
+            .args(["fetch", "--quiet", "--tags", "--force", "origin"]);
+    } else {
+        std::fs::create_dir_all(cache_dir)?;
+        command
+            .args([
+                "clone",
+                "--quiet",
+                "--bare",
+                "--filter=blob:none",
+                "--",
+                repository,
+            ])
+            .arg(&clone);
+    }
+    // A private or missing repository must fail rather than prompt for
+    // credentials in the middle of `update`
+    let status = command
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .status()
+        .map_err(|e| Error::Other(format!("Failed to run git: {}", e)))?;
+    if !status.success() {
+        return Err(Error::Other(format!("Failed to fetch {}", repository)));
+    }
+
+    let (Some(from_tag), Some(to_tag)) = (find_tag(&clone, from), find_tag(&clone, to)) else {
+        return Ok(None);
+    };
+    let output = Command::new("git")
+        .arg("--git-dir")
+        .arg(&clone)
+        .args(["log", "--no-merges", "--format=- %s"])
+        .arg(format!("{}..{}", from_tag, to_tag))
+        .output()
+        .map_err(|e| Error::Other(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(ChangelogSection {
+        version: to.to_string(),
+        title: format!("{}..{} (git history)", from_tag, to_tag),
+        body: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    }))
+}
+
+fn finish(mut section: ChangelogSection, body: Vec<&str>) -> ChangelogSection {
+    section.body = body.join("\n").trim().to_string();
+    section
+}
+
+/// Version named by a heading: its first word that parses as a version
+fn heading_version(title: &str) -> Option<String> {
+    title
+        .split(|c: char| c.is_whitespace() || matches!(c, '[' | ']' | '(' | ')' | ','))
+        .map(|word| word.trim_start_matches(['v', 'V']))
+        .find(|word| parse_version(word).is_some())
+        .map(str::to_string)
+}
+
+/// Parse a version, accepting `1.4` for `1.4.0`
+fn parse_version(version: &str) -> Option<Version> {
+    let version = version.trim_start_matches(['v', 'V']);
+    Version::parse(version).ok().or_else(|| {
+        let parts = version.split('.').count();
+        if parts == 2 && version.split('.').all(|p| p.parse::<u64>().is_ok()) {
+            Version::parse(&format!("{}.0", version)).ok()
+        } else {
+            None
+        }
+    })
+}
+
+fn git_cache_path(cache_dir: &Path, repository: &str) -> PathBuf {
+    let name: String = repository
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    cache_dir.join(format!("{}.git", name))
+}
+
+fn find_tag(clone: &Path, version: &str) -> Option<String> {
+    [format!("v{}", version), version.to_string()]
+        .into_iter()
+        .find(|tag| {
+            Command::new("git")
+                .arg("--git-dir")
+                .arg(clone)
+                .args(["rev-parse", "--verify", "--quiet"])
+                .arg(format!("refs/tags/{}", tag))
+                .output()
+                .is_ok_and(|output| output.status.success())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGELOG: &str = "\
+# Changelog
+
+All notable changes.
+
+## [Unreleased]
+- Work in progress
+
+## [1.4.1] - 2024-05-02
+### Fixed
+- Crash on shutdown
+
+## v1.4.0
+- Add `UAlphaComponent`
+
+# 1.3 (March 2024)
+- Faster replication
+
+## 1.2.0
+- Initial release
+";
+
+    #[test]
+    fn test_parse_sections() {
+        let sections = parse(CHANGELOG);
+        let versions: Vec<&str> = sections.iter().map(|s| s.version.as_str()).collect();
+        assert_eq!(versions, vec!["1.4.1", "1.4.0", "1.3", "1.2.0"]);
+
+        // Sub-headings stay in their version's section; Unreleased is dropped
+        assert_eq!(sections[0].title, "[1.4.1] - 2024-05-02");
+        assert_eq!(sections[0].body, "### Fixed\n- Crash on shutdown");
+        assert!(!sections.iter().any(|s| s.body.contains("Work in progress")));
+    }
+
+    #[test]
+    fn test_between() {
+        let versions = |from, to| -> Vec<String> {
+            between(CHANGELOG, from, to)
+                .into_iter()
+                .map(|s| s.version)
+                .collect()
+        };
+        assert_eq!(versions("1.2.0", "1.4.1"), vec!["1.4.1", "1.4.0", "1.3"]);
+        assert_eq!(versions("1.3.0", "1.4.0"), vec!["1.4.0"]);
+        assert!(versions("1.4.1", "1.4.1").is_empty());
+        assert!(versions("none", "1.4.1").is_empty());
+    }
+
+    #[test]
+    fn test_git_log_between() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t", "-C"])
+                .arg(&repo)
+                .args(args)
+                .output()
+                .is_ok_and(|o| o.status.success())
+        };
+        std::fs::create_dir_all(&repo).unwrap();
+        if !git(&["init", "-q"]) {
+            return; // git not installed
+        }
+        for (message, tag) in [
+            ("Initial", Some("v1.0.0")),
+            ("Fix crash", None),
+            ("Add API", Some("v1.1.0")),
+        ] {
+            assert!(git(&["commit", "-q", "--allow-empty", "-m", message]));
+            if let Some(tag) = tag {
+                assert!(git(&["tag", tag]));
+            }
+        }
+
+        let cache = temp.path().join("cache");
+        let url = format!("file://{}", repo.display());
+        let section = git_log_between(&url, &cache, "1.0.0", "1.1.0")
+            .unwrap()
+            .unwrap();
+        assert_eq!(section.title, "v1.0.0..v1.1.0 (git history)");
+        assert_eq!(section.body, "- Add API\n- Fix crash");
+
+        // The second call fetches into the existing clone
+        assert!(git_log_between(&url, &cache, "1.0.0", "2.0.0")
+            .unwrap()
+            .is_none());
+        assert!(git_log_between("--upload-pack=x", &cache, "1.0.0", "1.1.0").is_err());
+    }
+
+    #[test]
+    fn test_git_cache_path() {
+        let dir = Path::new("/cache");
+        assert_eq!(
+            git_cache_path(dir, "https://github.com/acme/alpha.git"),
+            dir.join("https___github_com_acme_alpha.git")
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Attest command - signed record of the dependencies used for a build
+//!
+//! Subcommands:
+//! - (none): Sign the project's lockfile, engine version, and checksums into
+//!   an attestation file to archive with the build
+//! - verify: Check an attestation's signature, signer, and (optionally) that
+//!   a lockfile still matches it
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use unrealpm::{
+    load_public_key_hex, read_engine_build_version, Attestation, AttestationStatement, Config,
+    Lockfile, Manifest, PackageSigningKey, UProject, ATTESTATION_FILE_NAME, LOCKFILE_NAME,
+};
+
+use crate::AttestAction;
+
+pub fn run(
+    action: &Option<AttestAction>,
+    output: Option<&str>,
+    build_id: Option<&str>,
+) -> Result<()> {
+    match action {
+        None => create(output, build_id),
+        Some(AttestAction::Verify {
+            file,
+            key,
+            lockfile,
+        }) => verify(file, key.as_deref(), lockfile.as_deref()),
+    }
+}
+
+fn create(output: Option<&str>, build_id: Option<&str>) -> Result<()> {
+    let project_dir = env::current_dir()?;
+    let config = Config::load()?;
+
+    let lockfile_path = project_dir.join(LOCKFILE_NAME);
+    if !lockfile_path.exists() {
+        anyhow::bail!(
+            "No lockfile found (unrealpm.lock) in {}\n\n\
+            Run 'unrealpm install' first so the attestation records exact versions.",
+            project_dir.display()
+        );
+    }
+    let lockfile_contents = fs::read_to_string(&lockfile_path)?;
+
+    let manifest = Manifest::load(&project_dir).ok();
+    let uproject = match &manifest {
+        Some(manifest) => manifest.uproject_path(&project_dir).ok(),
+        None => UProject::find(&project_dir).ok(),
+    };
+    let project = uproject
+        .and_then(|path| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .or_else(|| {
+            project_dir
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+        })
+        .unwrap_or_default();
+
+    let mut statement = AttestationStatement::from_lockfile(&project, &lockfile_contents)?;
+    statement.build_id = build_id.map(str::to_string);
+
+    if let Some(association) = manifest.and_then(|m| m.engine_version) {
+        statement.engine_version = Some(config.logical_engine_version(&association));
+        statement.engine_build = config
+            .find_engine(&association)
+            .and_then(|engine| read_engine_build_version(&engine.path))
+            .map(|build| build.full());
+    }
+
+    let (private_key_path, public_key_path) = config.signing.key_paths();
+    if !private_key_path.exists() || !public_key_path.exists() {
+        anyhow::bail!(
+            "No signing keys found at {}\n\n\
+            Generate them with:\n\
+              unrealpm keys generate",
+            private_key_path.display()
+        );
+    }
+    let key = PackageSigningKey::load_from_files(&private_key_path, &public_key_path)?;
+
+    let output_path = PathBuf::from(output.unwrap_or(ATTESTATION_FILE_NAME));
+    let attestation = statement.sign(&key)?;
+    attestation.save(&output_path)?;
+
+    let statement = &attestation.statement;
+    println!("✓ Wrote attestation to {}", output_path.display());
+    println!("  Project: {}", statement.project);
+    if let Some(build_id) = &statement.build_id {
+        println!("  Build: {}", build_id);
+    }
+    if let Some(engine) = &statement.engine_version {
+        match &statement.engine_build {
+            Some(build) => println!("  Engine: {} ({})", engine, build),
+            None => println!("  Engine: {}", engine),
+        }
+    }
+    println!("  Packages: {}", statement.packages.len());
+    println!("  Signed by: {}", attestation.public_key);
+    println!();
+    println!("Archive it with the build; check it later with:");
+    println!("  unrealpm attest verify {}", output_path.display());
+
+    Ok(())
+}
+
+fn verify(file: &str, trusted_key: Option<&str>, lockfile: Option<&str>) -> Result<()> {
+    let attestation = Attestation::load(file)?;
+    let statement = &attestation.statement;
+
+    println!("Verifying attestation {}...", file);
+    println!();
+
+    if !attestation.verify_signature()? {
+        anyhow::bail!(
+            "Attestation signature is INVALID\n\n\
+            The file was modified after it was signed. Do not trust its contents."
+        );
+    }
+    println!("  ✓ Signature valid");
+
+    match trusted_key {
+        Some(key) => {
+            let expected = read_key(key)?;
+            if !expected.eq_ignore_ascii_case(&attestation.public_key) {
+                anyhow::bail!(
+                    "Attestation was signed by a different key\n\n\
+                    Expected: {}\n\
+                    Signed by: {}",
+                    expected,
+                    attestation.public_key
+                );
+            }
+            println!("  ✓ Signed by trusted key");
+        }
+        None => match own_public_key() {
+            Some(own) if own.eq_ignore_ascii_case(&attestation.public_key) => {
+                println!("  ✓ Signed by your key")
+            }
+            _ => {
+                println!("  ⚠ Signer not checked against a trusted key");
+                println!("    Signed by: {}", attestation.public_key);
+                println!("    Pass --key <hex or public key file> to pin the signer");
+            }
+        },
+    }
+
+    println!();
+    println!("  Created: {}", statement.created_at);
+    println!("  Project: {}", statement.project);
+    if let Some(build_id) = &statement.build_id {
+        println!("  Build: {}", build_id);
+    }
+    if let Some(engine) = &statement.engine_version {
+        match &statement.engine_build {
+            Some(build) => println!("  Engine: {} ({})", engine, build),
+            None => println!("  Engine: {}", engine),
+        }
+    }
+    println!("  Platform: {}", statement.platform);
+    println!("  Packages ({}):", statement.packages.len());
+    for package in &statement.packages {
+        match &package.binary {
+            Some(binary) => println!(
+                "    {}@{} {} ({})",
+                package.name,
+                package.version,
+                binary.target(),
+                binary.checksum
+            ),
+            None => println!(
+                "    {}@{} ({})",
+                package.name, package.version, package.checksum
+            ),
+        }
+    }
+
+    if let Some(lockfile_path) = lockfile {
+        println!();
+        compare_lockfile(&attestation, Path::new(lockfile_path))?;
+    }
+
+    println!();
+    println!("✓ Attestation verified");
+    Ok(())
+}
+
+/// Check that a lockfile still matches the attested dependency set
+fn compare_lockfile(attestation: &Attestation, path: &Path) -> Result<()> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read lockfile {}", path.display()))?;
+
+    if hex::encode(Sha256::digest(contents.as_bytes())) == attestation.statement.lockfile_sha256 {
+        println!(
+            "  ✓ {} is identical to the attested lockfile",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let changes = attestation
+        .statement
+        .to_lockfile()
+        .diff(&Lockfile::parse(&contents)?);
+    if changes.is_empty() {
+        println!(
+            "  ✓ {} locks the attested packages (file contents differ)",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    println!("  ✗ {} differs from the attestation:", path.display());
+    for change in &changes {
+        let version = |p: &Option<unrealpm::LockedPackage>| {
+            p.as_ref()
+                .map(|p| p.version.clone())
+                .unwrap_or_else(|| "-".to_string())
+        };
+        println!(
+            "    {} {}: {} -> {}",
+            change.kind,
+            change.name,
+            version(&change.old),
+            version(&change.new)
+        );
+    }
+    anyhow::bail!(
+        "Lockfile does not match the attestation ({} package change{})",
+        changes.len(),
+        if changes.len() == 1 { "" } else { "s" }
+    )
+}
+
+/// A public key given as hex or as a path to a PEM file
+fn read_key(key: &str) -> Result<String> {
+    let path = PathBuf::from(shellexpand::tilde(key).to_string());
+    if path.is_file() {
+        return load_public_key_hex(&path);
+    }
+    if key.len() == 64 && key.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(key.to_string());
+    }
+    anyhow::bail!(
+        "Invalid --key '{}' (expected a hex public key or a public key file)",
+        key
+    )
+}
+
+/// The user's configured public key, if one exists
+fn own_public_key() -> Option<String> {
+    let config = Config::load().ok()?;
+    let (_, path) = config.signing.key_paths();
+    load_public_key_hex(&path).ok()
+}
@@ -0,0 +1,157 @@
+//! Audit command - report locked versions with security advisories or yanks
+//!
+//! Asks the registry's advisory database about every locked registry package
+//! and checks whether its version was yanked. Findings are listed most
+//! severe first; `--deny <level>` exits non-zero when any finding is at that
+//! level or above (`--deny warnings` fails on anything).
+//!
+//! Checksum incidents recorded for locked packages in the last
+//! [`INCIDENT_DAYS`] days are listed too, as warnings.
+
+use anyhow::Result;
+use unrealpm::advisory::{findings, FindingKind, Severity};
+use unrealpm::integrity::{Incident, IncidentLog};
+use unrealpm::{Config, Lockfile, RegistryClient};
+
+/// How far back checksum incidents are reported
+const INCIDENT_DAYS: i64 = 30;
+
+pub fn run(deny: Option<Severity>) -> Result<()> {
+    let lockfile = Lockfile::load()?.ok_or_else(|| {
+        anyhow::anyhow!("No lockfile found (unrealpm.lock). Run `unrealpm install` first.")
+    })?;
+
+    // Local and externally managed packages have no registry advisories
+    let mut locked: Vec<(String, String)> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, package)| package.source.is_none())
+        .map(|(name, package)| (name.clone(), package.version.clone()))
+        .collect();
+    locked.sort();
+
+    println!("Auditing {} locked packages...", locked.len());
+    println!();
+
+    let config = Config::load()?;
+    let registry =
+        RegistryClient::from_config(&config)?.with_locked_registries(&lockfile, &config)?;
+
+    let (advisories, yanked) = match &registry {
+        RegistryClient::Http(http_client) => {
+            let advisories = http_client.advisories(&locked)?;
+            if advisories.is_none() {
+                println!("  ⚠ The registry does not publish advisories; only checking for yanked versions");
+            }
+            let mut yanked = Vec::new();
+            for (name, version) in &locked {
+                match http_client.list_versions(name) {
+                    Ok(versions) => {
+                        if versions.iter().any(|v| &v.version == version && v.yanked) {
+                            yanked.push(name.clone());
+                        }
+                    }
+                    Err(e) => println!("  ⚠ Could not check {}: {}", name, e),
+                }
+            }
+            (advisories.unwrap_or_default(), yanked)
+        }
+        // File registries have neither advisories nor yanks
+        RegistryClient::File(_) => {
+            println!("  ⚠ File registries have no advisories or yanked versions to check");
+            (Vec::new(), Vec::new())
+        }
+    };
+
+    let found = findings(&locked, &advisories, &yanked);
+    let incidents = recent_incidents(&lockfile);
+    if found.is_empty() && incidents.is_empty() {
+        println!("✓ No known vulnerabilities or yanked versions");
+        return Ok(());
+    }
+
+    for finding in &found {
+        let label = finding.severity.to_string().to_uppercase();
+        let spec = format!("{}@{}", finding.package, finding.version);
+        match &finding.kind {
+            FindingKind::Advisory(advisory) => {
+                println!(
+                    "  {:<9} {:<30} {}  {}",
+                    label, spec, advisory.id, advisory.title
+                );
+                match &advisory.patched {
+                    Some(patched) => println!("  {:<9} Fixed in: {}", "", patched),
+                    None => println!("  {:<9} No fixed version yet", ""),
+                }
+                if let Some(url) = &advisory.url {
+                    println!("  {:<9} {}", "", url);
+                }
+            }
+            FindingKind::Yanked => {
+                println!("  {:<9} {:<30} yanked by its publisher", label, spec)
+            }
+        }
+    }
+    let warning = Severity::Warning.to_string().to_uppercase();
+    for incident in &incidents {
+        let spec = format!("{}@{}", incident.package, incident.version);
+        println!(
+            "  {:<9} {:<30} checksum mismatch ({} layer) on {}",
+            warning,
+            spec,
+            incident.layer,
+            incident.timestamp.get(..10).unwrap_or(&incident.timestamp)
+        );
+    }
+    println!();
+
+    let mut counts: Vec<String> = Vec::new();
+    for severity in [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+    ] {
+        let count = found.iter().filter(|f| f.severity == severity).count();
+        if count > 0 {
+            counts.push(format!("{} {}", count, severity));
+        }
+    }
+    let yanked_count = found
+        .iter()
+        .filter(|f| f.kind == FindingKind::Yanked)
+        .count();
+    if yanked_count > 0 {
+        counts.push(format!("{} yanked", yanked_count));
+    }
+    if !incidents.is_empty() {
+        counts.push(format!("{} checksum incident(s)", incidents.len()));
+    }
+    println!(
+        "Found {} problem(s): {}",
+        found.len() + incidents.len(),
+        counts.join(", ")
+    );
+
+    // Incidents are warnings, so only `--deny warnings` fails on them
+    let denied = deny.is_some_and(|level| {
+        found.iter().any(|f| f.severity >= level)
+            || (level <= Severity::Warning && !incidents.is_empty())
+    });
+    if denied {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Checksum incidents from the last [`INCIDENT_DAYS`] days for packages in
+/// the lockfile
+fn recent_incidents(lockfile: &Lockfile) -> Vec<Incident> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(INCIDENT_DAYS);
+    IncidentLog::open_default()
+        .and_then(|log| log.since(cutoff))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|incident| lockfile.packages.contains_key(&incident.package))
+        .collect()
+}
@@ -0,0 +1,300 @@
+//! Auth command - manage a registry account from the terminal
+//!
+//! Subcommands:
+//! - verify-email: Show verification status, submit the code from the
+//!   verification email, or (with --resend) send the email again
+//! - reset-password: Request a reset code by email and set a new password
+//!
+//! Everything works without a browser, for WSL and remote machines.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::io::{self, Write};
+use unrealpm::{config::AuthConfig, Config};
+
+use crate::AuthAction;
+
+#[derive(Debug, Deserialize)]
+struct UserInfoResponse {
+    email: String,
+    email_verified: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+pub fn run(action: &AuthAction) -> Result<()> {
+    match action {
+        AuthAction::VerifyEmail { code, resend } => run_verify_email(code.as_deref(), *resend),
+        AuthAction::ResetPassword { code } => run_reset_password(code.as_deref()),
+    }
+}
+
+fn run_verify_email(code: Option<&str>, resend: bool) -> Result<()> {
+    let config = Config::load().context("Failed to load config")?;
+    let registry_url = http_registry_url(&config, "Email verification")?;
+    let client = reqwest::blocking::Client::new();
+    let token = config.auth_token(std::env::current_dir()?);
+    let token = token.as_deref();
+
+    if resend {
+        let email = match token {
+            Some(token) => user_info(&client, &registry_url, token)?.email,
+            None => prompt("Email: ")?,
+        };
+        resend_verification(&client, &registry_url, &email, token)?;
+        println!("✓ Verification email sent to {}", email);
+        println!();
+        println!("Then submit the code from the email with:");
+        println!("  unrealpm auth verify-email <CODE>");
+        return Ok(());
+    }
+
+    let code = match code {
+        Some(code) => code.to_string(),
+        None => {
+            // Without a code, report the current status first
+            if let Some(token) = token {
+                let user = user_info(&client, &registry_url, token)?;
+                if user.email_verified {
+                    println!("✓ {} is already verified", user.email);
+                    return Ok(());
+                }
+                println!("{} is not verified yet.", user.email);
+                println!();
+            }
+            println!("Paste the code (or the whole link) from the verification email.");
+            println!("Didn't get it? Run: unrealpm auth verify-email --resend");
+            println!();
+            prompt("Verification code: ")?
+        }
+    };
+
+    let response = client
+        .post(format!("{}/api/v1/auth/verify-email", registry_url))
+        .json(&serde_json::json!({ "token": extract_code(&code) }))
+        .send()
+        .context("Failed to send verification request")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_msg = error_message(response, status);
+        println!("✗ Verification failed: {}", error_msg);
+        println!();
+        if status.as_u16() == 400 || status.as_u16() == 404 || status.as_u16() == 410 {
+            println!("The code may have expired. Request a new one with:");
+            println!("  unrealpm auth verify-email --resend");
+        }
+        anyhow::bail!("Email verification failed");
+    }
+
+    println!("✓ Email verified");
+    println!();
+    println!("You can now publish packages with: unrealpm publish");
+    Ok(())
+}
+
+fn run_reset_password(code: Option<&str>) -> Result<()> {
+    let config = Config::load().context("Failed to load config")?;
+    let registry_url = http_registry_url(&config, "Password reset")?;
+    let client = reqwest::blocking::Client::new();
+
+    let code = match code {
+        Some(code) => code.to_string(),
+        None => {
+            let email = prompt("Email: ")?;
+            if email.is_empty() {
+                anyhow::bail!("Email cannot be empty");
+            }
+
+            let response = client
+                .post(format!("{}/api/v1/auth/forgot-password", registry_url))
+                .json(&serde_json::json!({ "email": email }))
+                .send()
+                .context("Failed to request password reset")?;
+            let status = response.status();
+            if !status.is_success() {
+                let error_msg = error_message(response, status);
+                println!("✗ Password reset request failed: {}", error_msg);
+                anyhow::bail!("Password reset failed");
+            }
+
+            // The registry answers the same way for unknown addresses
+            println!();
+            println!(
+                "If an account exists for {}, a reset code is on its way.",
+                email
+            );
+            println!("Paste the code (or the whole link) from the email.");
+            println!("You can also finish later with: unrealpm auth reset-password --code <CODE>");
+            println!();
+            prompt("Reset code: ")?
+        }
+    };
+    if code.is_empty() {
+        anyhow::bail!("Reset code cannot be empty");
+    }
+
+    let password =
+        rpassword::prompt_password("New password: ").context("Failed to read password")?;
+    if password.len() < 8 {
+        anyhow::bail!("Password must be at least 8 characters");
+    }
+    let password_confirm = rpassword::prompt_password("Confirm new password: ")
+        .context("Failed to read password confirmation")?;
+    if password != password_confirm {
+        anyhow::bail!("Passwords do not match");
+    }
+
+    println!();
+    println!("Resetting password...");
+
+    let response = client
+        .post(format!("{}/api/v1/auth/reset-password", registry_url))
+        .json(&serde_json::json!({
+            "token": extract_code(&code),
+            "new_password": password,
+        }))
+        .send()
+        .context("Failed to send password reset")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_msg = error_message(response, status);
+        println!("✗ Password reset failed: {}", error_msg);
+        println!();
+        if status.as_u16() == 400 || status.as_u16() == 404 || status.as_u16() == 410 {
+            println!("The code may have expired. Start over with:");
+            println!("  unrealpm auth reset-password");
+        }
+        anyhow::bail!("Password reset failed");
+    }
+
+    println!("✓ Password changed");
+    println!();
+    println!("Log in with your new password:");
+    println!("  unrealpm login --email");
+    Ok(())
+}
+
+/// Registry URL for account operations, which need an HTTP registry over HTTPS
+fn http_registry_url(config: &Config, operation: &str) -> Result<String> {
+    if config.registry.registry_type != "http" {
+        println!("ERROR: You are using a file-based registry.");
+        println!("{} is only supported for HTTP registries.", operation);
+        println!();
+        println!("To switch to HTTP registry, run:");
+        println!("  unrealpm config set registry.registry_type http");
+        println!("  unrealpm config set registry.url https://registry.unreal.dev");
+        anyhow::bail!("File-based registry does not support authentication");
+    }
+
+    let registry_url = config.registry.url.trim_end_matches('/').to_string();
+
+    // Security: Require HTTPS for credentials (except localhost for development)
+    let is_localhost = registry_url.contains("localhost") || registry_url.contains("127.0.0.1");
+    if !registry_url.starts_with("https://") && !is_localhost {
+        println!("ERROR: {} requires HTTPS for security.", operation);
+        println!();
+        println!("Your current registry URL: {}", registry_url);
+        println!();
+        println!("Use HTTPS: unrealpm config set registry.url https://registry.unreal.dev");
+        anyhow::bail!("Refusing to send credentials over unencrypted connection");
+    }
+
+    Ok(registry_url)
+}
+
+/// Whether the logged-in account's email address is verified
+pub fn email_verified(
+    client: &reqwest::blocking::Client,
+    registry_url: &str,
+    token: &str,
+) -> Result<bool> {
+    Ok(user_info(client, registry_url, token)?.email_verified)
+}
+
+fn user_info(
+    client: &reqwest::blocking::Client,
+    registry_url: &str,
+    token: &str,
+) -> Result<UserInfoResponse> {
+    let response = client
+        .get(format!("{}/api/v1/auth/me", registry_url))
+        .header("Authorization", AuthConfig::format_auth_header(token))
+        .send()
+        .context("Failed to get user info")?;
+
+    if !response.status().is_success() {
+        if response.status().as_u16() == 401 {
+            anyhow::bail!("Session expired or invalid. Run: unrealpm login");
+        }
+        anyhow::bail!(
+            "Failed to get user info: HTTP {}",
+            response.status().as_u16()
+        );
+    }
+
+    response.json().context("Failed to parse response")
+}
+
+/// Ask the registry to send another verification email
+pub fn resend_verification(
+    client: &reqwest::blocking::Client,
+    registry_url: &str,
+    email: &str,
+    token: Option<&str>,
+) -> Result<()> {
+    let mut request = client
+        .post(format!("{}/api/v1/auth/resend-verification", registry_url))
+        .json(&serde_json::json!({ "email": email }));
+    if let Some(token) = token {
+        request = request.header("Authorization", AuthConfig::format_auth_header(token));
+    }
+
+    let response = request
+        .send()
+        .context("Failed to request verification email")?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("{}", error_message(response, status));
+    }
+    Ok(())
+}
+
+/// Error message from a failed registry response
+pub fn error_message(response: reqwest::blocking::Response, status: reqwest::StatusCode) -> String {
+    if let Ok(error_response) = response.json::<ErrorResponse>() {
+        error_response.error
+    } else {
+        format!(
+            "HTTP {}: {}",
+            status.as_u16(),
+            status.canonical_reason().unwrap_or("Unknown error")
+        )
+    }
+}
+
+/// The code from a pasted value, which may be the whole link from the email
+fn extract_code(input: &str) -> String {
+    let input = input.trim();
+    input
+        .split(['?', '&'])
+        .find_map(|part| part.strip_prefix("token="))
+        .map(|code| {
+            urlencoding::decode(code)
+                .map(|c| c.into_owned())
+                .unwrap_or_else(|_| code.to_string())
+        })
+        .unwrap_or_else(|| input.to_string())
+}
+
+fn prompt(label: &str) -> Result<String> {
+    print!("{}", label);
+    io::stdout().flush()?;
+    let mut value = String::new();
+    io::stdin().read_line(&mut value)?;
+    Ok(value.trim().to_string())
+}
@@ -12,6 +12,7 @@ pub fn run(
     engine: Option<String>,
     platform: Option<String>,
     all_platforms: bool,
+    configurations: Vec<String>,
 ) -> Result<()> {
     println!("Building plugin binaries...");
     println!();
@@ -79,57 +80,86 @@ pub fn run(
 
     // Determine platforms to build
     let platforms = if all_platforms {
-        config.build.platforms.clone()
+        config.platforms_for_engine(&engine_version)
     } else if let Some(p) = platform {
         vec![p]
     } else {
         vec![unrealpm::detect_platform()]
     };
 
+    // Determine configurations to build
+    let configurations = if configurations.is_empty() {
+        vec![config.build.configuration.clone()]
+    } else {
+        configurations
+    };
+
     println!("  Building for platforms: {}", platforms.join(", "));
+    println!("  Configurations: {}", configurations.join(", "));
     println!();
 
-    // Build for each platform
+    // Build each platform/configuration combination
     for target_platform in &platforms {
-        println!("Building for {}...", target_platform);
-        build_for_platform(
-            &plugin_dir,
-            &plugin_name,
-            &engine_version,
-            target_platform,
-            &config,
-        )?;
-        println!("  ✓ Built for {}", target_platform);
-        println!();
+        for configuration in &configurations {
+            println!("Building for {} ({})...", target_platform, configuration);
+            build_for_platform(
+                &plugin_dir,
+                &plugin_name,
+                &engine_version,
+                target_platform,
+                configuration,
+                &config,
+            )?;
+            println!("  ✓ Built for {} ({})", target_platform, configuration);
+            println!();
+        }
     }
 
     println!(
-        "✓ Successfully built {} for {} platform{}",
+        "✓ Successfully built {} for {} platform{} × {} configuration{}",
         plugin_name,
         platforms.len(),
-        if platforms.len() == 1 { "" } else { "s" }
+        if platforms.len() == 1 { "" } else { "s" },
+        configurations.len(),
+        if configurations.len() == 1 { "" } else { "s" }
     );
     println!();
 
     // Show where binaries are
-    let binaries_dir = plugin_dir.join("Binaries");
-    if binaries_dir.exists() {
-        println!("Binaries location: {}", binaries_dir.display());
+    let package_dirs: Vec<PathBuf> = platforms
+        .iter()
+        .flat_map(|platform| {
+            configurations
+                .iter()
+                .map(|configuration| package_dir(&plugin_dir, platform, configuration))
+        })
+        .filter(|dir| dir.exists())
+        .collect();
+    if !package_dirs.is_empty() {
+        println!("Packaged builds:");
+        for dir in &package_dirs {
+            println!("  {}", dir.display());
+        }
         println!();
         println!("Next steps:");
         println!("  • Test the plugin in Unreal Editor");
         println!("  • Publish with binaries: unrealpm publish --include-binaries");
+        println!(
+            "  • Attach to a published version: unrealpm publish-binary --platform <P> --engine {} --config <C>",
+            engine_version
+        );
     }
 
     Ok(())
 }
 
-/// Build plugin for a specific platform (public function for use by publish)
+/// Build plugin for a specific platform and configuration (public function for use by publish)
 pub fn build_for_platform(
     plugin_dir: &Path,
     plugin_name: &str,
     engine_version: &str,
     platform: &str,
+    configuration: &str,
     config: &Config,
 ) -> Result<()> {
     // Find engine installation
@@ -148,7 +178,7 @@ pub fn build_for_platform(
         plugin_name,
         &engine_install.path,
         platform,
-        &config.build.configuration,
+        configuration,
     )
 }
 
@@ -162,6 +192,15 @@ fn build_plugin(
     println!("  Preparing build...");
     println!("  Platform: {}, Configuration: {}", platform, configuration);
 
+    // Intermediate/ and the packaged output land next to the plugin
+    let required =
+        unrealpm::disk::estimate_build_space(crate::commands::cache::dir_size(plugin_dir));
+    unrealpm::disk::ensure_space(&[unrealpm::disk::SpaceRequirement::new(
+        plugin_dir,
+        required,
+        &format!("building {} {}", platform, configuration),
+    )])?;
+
     // Check if we're on WSL and need to convert paths
     let is_wsl = env::var("WSL_DISTRO_NAME").is_ok();
 
@@ -200,12 +239,19 @@ fn build_plugin(
         Command::new(&run_uat)
     };
 
+    // Each platform/configuration packages into a folder of its own, so one
+    // build doesn't overwrite another's binaries
+    let package_dir = package_dir(plugin_dir, platform, configuration);
+    let package_dir_arg = if is_wsl && platform == "Win64" {
+        unrealpm::platform::wsl_to_windows_path(&package_dir)
+            .unwrap_or_else(|| package_dir.display().to_string())
+    } else {
+        package_dir.display().to_string()
+    };
+
     cmd.arg("BuildPlugin");
     cmd.arg(format!("-Plugin={}", plugin_path_arg));
-    cmd.arg(format!(
-        "-Package={}",
-        plugin_path_arg.replace(".uplugin", "")
-    ));
+    cmd.arg(format!("-Package={}", package_dir_arg));
     cmd.arg(format!("-TargetPlatforms={}", platform));
     cmd.arg(format!("-TargetConfigurations={}", configuration));
 
@@ -289,10 +335,23 @@ fn build_plugin(
     let elapsed = start_time.elapsed();
     pb.finish_with_message(format!("Build completed in {:.1}s", elapsed.as_secs_f32()));
     println!();
+    println!("  Packaged to {}", package_dir.display());
 
     Ok(())
 }
 
+/// Folder `build` packages one platform/configuration into:
+/// `Saved/Packages/<Platform>-<Configuration>/` in the plugin folder
+///
+/// Saved/ is never published, so the packaged copies stay out of source
+/// tarballs.
+pub fn package_dir(plugin_dir: &Path, platform: &str, configuration: &str) -> PathBuf {
+    plugin_dir
+        .join("Saved")
+        .join("Packages")
+        .join(format!("{}-{}", platform, configuration))
+}
+
 /// Parse build progress from UBT output (e.g., "[32/63]" -> Some((32, 63)))
 fn parse_build_progress(line: &str) -> Option<(u64, u64)> {
     // Look for patterns like [32/63]
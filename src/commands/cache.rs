@@ -9,11 +9,11 @@
 use anyhow::Result;
 use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
-use unrealpm::{get_store_dir, get_store_stats, Lockfile};
+use std::path::{Path, PathBuf};
+use unrealpm::{get_store_dir, get_store_stats, DryRun, Lockfile, ResolutionCache};
 
 /// Format bytes as human-readable size
-fn format_size(bytes: u64) -> String {
+pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -30,7 +30,7 @@ fn format_size(bytes: u64) -> String {
 }
 
 /// Calculate directory size recursively
-fn dir_size(path: &PathBuf) -> u64 {
+pub fn dir_size(path: &Path) -> u64 {
     let mut total = 0;
     if let Ok(entries) = fs::read_dir(path) {
         for entry in entries.flatten() {
@@ -161,6 +161,9 @@ pub fn run_info() -> Result<()> {
     println!("Store location: {}", store_dir.display());
     println!("Packages cached: {}", stats.package_count);
     println!("Total size: {}", format_size(stats.total_size));
+    if let Some(saved) = stats.saved_size {
+        println!("Saved by hard links: {}", format_size(saved));
+    }
     println!();
 
     // Show store structure
@@ -186,26 +189,16 @@ pub fn run_path() -> Result<()> {
 }
 
 /// Clean unused packages from the cache
-pub fn run_clean(all: bool, dry_run: bool) -> Result<()> {
+pub fn run_clean(all: bool, dry_run: DryRun) -> Result<()> {
     let store_dir = get_store_dir()?;
 
     if all {
         // Remove ALL cached packages
-        if dry_run {
-            println!("[DRY RUN] Would remove all cached packages from:");
-            println!("  {}", store_dir.display());
-
-            let stats = get_store_stats()?;
-            println!();
-            println!(
-                "Would free {} ({} packages)",
-                format_size(stats.total_size),
-                stats.package_count
-            );
-            return Ok(());
-        }
-
-        println!("Removing all cached packages...");
+        println!(
+            "{}Removing all cached packages from {}...",
+            dry_run.prefix(),
+            store_dir.display()
+        );
 
         let mut removed_count = 0;
         let mut freed_size: u64 = 0;
@@ -215,7 +208,7 @@ pub fn run_clean(all: bool, dry_run: bool) -> Result<()> {
                 let path = entry.path();
                 if path.is_dir() {
                     let size = dir_size(&path);
-                    if fs::remove_dir_all(&path).is_ok() {
+                    if dry_run.apply(|| Ok(fs::remove_dir_all(&path)?)).is_ok() {
                         removed_count += 1;
                         freed_size += size;
                     }
@@ -223,12 +216,23 @@ pub fn run_clean(all: bool, dry_run: bool) -> Result<()> {
             }
         }
 
+        let resolutions = ResolutionCache::open_default()?.clear(dry_run)?;
+
         println!();
         println!(
-            "Removed {} packages, freed {}",
+            "{} {} packages, {} {}",
+            dry_run.verb("Removed", "Would remove"),
             removed_count,
+            dry_run.verb("freed", "freeing"),
             format_size(freed_size)
         );
+        if resolutions > 0 {
+            println!(
+                "{} {} cached resolutions",
+                dry_run.verb("Removed", "Would remove"),
+                resolutions
+            );
+        }
         return Ok(());
     }
 
@@ -241,7 +245,7 @@ pub fn run_clean(all: bool, dry_run: bool) -> Result<()> {
 
     if let Ok(Some(lockfile)) = Lockfile::load() {
         for pkg in lockfile.packages.values() {
-            used_checksums.insert(pkg.checksum.clone());
+            used_checksums.insert(pkg.artifact_checksum().to_string());
         }
     }
 
@@ -303,19 +307,13 @@ pub fn run_clean(all: bool, dry_run: bool) -> Result<()> {
     println!("Total: {}", format_size(unused_size));
     println!();
 
-    if dry_run {
-        println!("[DRY RUN] Would remove {} packages", unused_packages.len());
-        return Ok(());
-    }
-
-    // Actually remove
-    println!("Removing unused packages...");
+    println!("{}Removing unused packages...", dry_run.prefix());
 
     let mut removed_count = 0;
     let mut freed_size: u64 = 0;
 
     for (path, _, size, _) in unused_packages {
-        if fs::remove_dir_all(&path).is_ok() {
+        if dry_run.apply(|| Ok(fs::remove_dir_all(&path)?)).is_ok() {
             removed_count += 1;
             freed_size += size;
         }
@@ -323,8 +321,10 @@ pub fn run_clean(all: bool, dry_run: bool) -> Result<()> {
 
     println!();
     println!(
-        "Removed {} packages, freed {}",
+        "{} {} packages, {} {}",
+        dry_run.verb("Removed", "Would remove"),
         removed_count,
+        dry_run.verb("freed", "freeing"),
         format_size(freed_size)
     );
 
@@ -0,0 +1,38 @@
+//! Categories command - list the categories packages are published under
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use unrealpm::{Config, RegistryClient};
+
+pub fn run() -> Result<()> {
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+    let packages = registry.search_ranked("", None)?;
+
+    // Group case variants ("AI" / "ai") under the first spelling seen
+    let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for category in packages.iter().flat_map(|hit| &hit.categories) {
+        counts
+            .entry(category.to_lowercase())
+            .or_insert_with(|| (category.clone(), 0))
+            .1 += 1;
+    }
+
+    if counts.is_empty() {
+        println!("No categorized packages found.");
+        println!();
+        println!("Packages get a category from the .uplugin's Category field,");
+        println!("or from \"categories\" in unrealpm.json when publishing.");
+        return Ok(());
+    }
+
+    println!("{:<30} Packages", "Category");
+    println!("{}", "-".repeat(40));
+    for (name, count) in counts.values() {
+        println!("{:<30} {}", name, count);
+    }
+    println!();
+    println!("Browse a category with: unrealpm search --category <name>");
+
+    Ok(())
+}
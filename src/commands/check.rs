@@ -0,0 +1,105 @@
+//! Check command - verify installed packages match the lockfile
+//!
+//! Checks:
+//! - Every locked package is present under Plugins/
+//! - Patched packages still match the tree hash recorded in the lockfile
+
+use anyhow::Result;
+use std::env;
+use unrealpm::patch::hash_directory;
+use unrealpm::{Lockfile, Manifest};
+
+pub fn run() -> Result<()> {
+    let project_dir = env::current_dir()?;
+
+    let manifest = Manifest::load(&project_dir)?;
+    let lockfile = Lockfile::load()?.ok_or_else(|| {
+        anyhow::anyhow!("No lockfile found (unrealpm.lock). Run `unrealpm install` first.")
+    })?;
+
+    println!("Checking installed packages...");
+    println!();
+
+    let mut names: Vec<_> = lockfile.packages.keys().collect();
+    names.sort();
+
+    let mut errors = 0;
+    let mut warnings = 0;
+
+    for name in names {
+        let locked = &lockfile.packages[name];
+        if locked.is_externally_managed() {
+            println!("  ✓ {}@{} (externally managed)", name, locked.version);
+            continue;
+        }
+
+        let Some(plugin_dir) =
+            unrealpm::installed_plugin_dir(&project_dir, name, locked.artifact_checksum())
+        else {
+            println!("  ✗ {}@{} - not installed", name, locked.version);
+            errors += 1;
+            continue;
+        };
+
+        let has_patches = manifest.patches.contains_key(name);
+        match (&locked.patched_hash, has_patches) {
+            (Some(expected), _) => {
+                let actual = hash_directory(&plugin_dir)?;
+                if &actual == expected {
+                    println!("  ✓ {}@{} (patched)", name, locked.version);
+                } else {
+                    println!(
+                        "  ✗ {}@{} - patched tree does not match lockfile",
+                        name, locked.version
+                    );
+                    println!("      Expected: {}", expected);
+                    println!("      Actual:   {}", actual);
+                    errors += 1;
+                }
+                if !has_patches {
+                    println!("      ⚠ No patches configured in unrealpm.json");
+                    warnings += 1;
+                }
+            }
+            (None, true) => {
+                println!(
+                    "  ✗ {}@{} - patches configured but not applied",
+                    name, locked.version
+                );
+                errors += 1;
+            }
+            (None, false) => {
+                println!("  ✓ {}@{}", name, locked.version);
+            }
+        }
+    }
+
+    for name in manifest.patches.keys() {
+        if !lockfile.has_package(name) {
+            println!(
+                "  ⚠ Patches configured for {}, which is not installed",
+                name
+            );
+            warnings += 1;
+        }
+    }
+
+    println!();
+
+    if errors > 0 {
+        anyhow::bail!(
+            "{} package(s) failed checks.\n\n\
+            Reinstall to restore the locked state:\n\
+              unrealpm install",
+            errors
+        );
+    }
+
+    if warnings > 0 {
+        println!("✓ All packages match the lockfile ({} warnings)", warnings);
+    } else {
+        println!("✓ All packages match the lockfile");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+//! CI command - clean, reproducible install from unrealpm.lock
+//!
+//! Removes every plugin unrealpm installed, then installs exactly what the
+//! lockfile records (checksums verified) without resolving the manifest.
+//! Fails if unrealpm.lock is missing or out of sync with unrealpm.json.
+//! Hand-placed plugins in Plugins/ are left alone.
+
+use anyhow::Result;
+use std::env;
+use unrealpm::{outln, Manifest, LOCKFILE_NAME};
+
+use crate::commands::install::{LockfileMode, UnmanagedPolicy};
+
+pub fn run(
+    production: bool,
+    source_only_files: bool,
+    jobs: Option<usize>,
+    show_timings: bool,
+) -> Result<()> {
+    let current_dir = env::current_dir()?;
+    if !Manifest::exists(&current_dir) {
+        anyhow::bail!("No unrealpm.json found in current directory");
+    }
+    if !current_dir.join(LOCKFILE_NAME).exists() {
+        anyhow::bail!(
+            "No unrealpm.lock found; `unrealpm ci` installs only what the lockfile records\n\n\
+            Run `unrealpm install` and commit the lockfile"
+        );
+    }
+
+    outln!("Clean install from {}", LOCKFILE_NAME);
+    outln!();
+
+    crate::commands::install::run(
+        Vec::new(),
+        Vec::new(),
+        None,
+        None,
+        false,
+        None,
+        false,
+        false,
+        false,
+        source_only_files,
+        false,
+        false,
+        UnmanagedPolicy::Refuse,
+        true,
+        false,
+        None,
+        None,
+        true,
+        production,
+        LockfileMode::Clean,
+        false,
+        jobs,
+        show_timings,
+    )
+}
@@ -1,13 +1,18 @@
 use anyhow::Result;
 use std::path::PathBuf;
-use unrealpm::Config;
+use unrealpm::outln;
+use unrealpm::{Config, DryRun, InstallLayout, InstallMode, SaveStyle};
 
 pub fn run(action: &crate::ConfigAction) -> Result<()> {
     use crate::ConfigAction;
 
     match action {
         ConfigAction::Show => show_config(),
-        ConfigAction::Set { key, value } => set_config(key, value),
+        ConfigAction::Set {
+            key,
+            value,
+            dry_run,
+        } => set_config(key, value, DryRun::new(*dry_run)),
         ConfigAction::AddEngine { version, path } => add_engine(version, path),
         ConfigAction::RemoveEngine { version } => remove_engine(version),
         ConfigAction::ListEngines => list_engines(),
@@ -18,65 +23,72 @@ fn show_config() -> Result<()> {
     let config = Config::load()?;
     let config_path = Config::default_path()?;
 
-    println!();
-    println!("╔══════════════════════════════════════════════════════════════════════════════╗");
-    println!("║                         UnrealPM Configuration                               ║");
-    println!("╚══════════════════════════════════════════════════════════════════════════════╝");
-    println!();
-    println!("  📁 Config file: {}", config_path.display());
-    println!();
+    outln!();
+    outln!("╔══════════════════════════════════════════════════════════════════════════════╗");
+    outln!("║                         UnrealPM Configuration                               ║");
+    outln!("╚══════════════════════════════════════════════════════════════════════════════╝");
+    outln!();
+    outln!("  📁 Config file: {}", config_path.display());
+    outln!();
 
     // Build settings
-    println!("┌─ Build Settings ─────────────────────────────────────────────────────────────┐");
-    println!("│                                                                              │");
-    println!(
+    outln!("┌─ Build Settings ─────────────────────────────────────────────────────────────┐");
+    outln!("│                                                                              │");
+    outln!(
         "│  Auto-build on publish:  {}                                             │",
         format_bool(config.build.auto_build_on_publish)
     );
-    println!(
+    outln!(
         "│  Auto-build on install:  {}                                             │",
         format_bool(config.build.auto_build_on_install)
     );
-    println!(
+    outln!(
         "│  Target platforms:       {}                                    │",
         config.build.platforms.join(", ")
     );
-    println!(
+    outln!(
         "│  Build configuration:    {}                                       │",
         config.build.configuration
     );
-    println!("│                                                                              │");
-    println!("└──────────────────────────────────────────────────────────────────────────────┘");
-    println!();
+    outln!("│                                                                              │");
+    outln!("└──────────────────────────────────────────────────────────────────────────────┘");
+    outln!();
 
     // Registry settings
-    println!("┌─ Registry Settings ──────────────────────────────────────────────────────────┐");
-    println!("│                                                                              │");
-    println!(
+    outln!("┌─ Registry Settings ──────────────────────────────────────────────────────────┐");
+    outln!("│                                                                              │");
+    outln!(
         "│  Registry URL:  {}                              │",
         config.registry.url
     );
-    println!("│                                                                              │");
-    println!("└──────────────────────────────────────────────────────────────────────────────┘");
-    println!();
+    if !config.registry.download_hosts.is_empty() {
+        outln!(
+            "│  Download hosts: {}                              │",
+            config.registry.download_hosts.join(", ")
+        );
+    }
+    let mirrors = config.mirrors_for(&config.registry.url);
+    if !mirrors.is_empty() {
+        outln!(
+            "│  Mirrors:       {}                              │",
+            mirrors.join(", ")
+        );
+    }
+    outln!("│                                                                              │");
+    outln!("└──────────────────────────────────────────────────────────────────────────────┘");
+    outln!();
 
     // Engine installations
     let all_engines = config.get_all_engines();
 
-    println!("┌─ Unreal Engine Installations ────────────────────────────────────────────────┐");
-    println!("│                                                                              │");
+    outln!("┌─ Unreal Engine Installations ────────────────────────────────────────────────┐");
+    outln!("│                                                                              │");
 
     if all_engines.is_empty() {
-        println!(
-            "│  No engines found                                                            │"
-        );
-        println!(
-            "│                                                                              │"
-        );
-        println!(
-            "│  💡 Engines are auto-detected from standard locations                        │"
-        );
-        println!("│  Or add manually: unrealpm config add-engine <version> <path>               │");
+        outln!("│  No engines found                                                            │");
+        outln!("│                                                                              │");
+        outln!("│  💡 Engines are auto-detected from standard locations                        │");
+        outln!("│  Or add manually: unrealpm config add-engine <version> <path>               │");
     } else {
         // Separate configured vs auto-detected
         let configured: Vec<_> = all_engines
@@ -90,65 +102,92 @@ fn show_config() -> Result<()> {
             .collect();
 
         if !configured.is_empty() {
-            println!(
+            outln!(
                 "│  📌 Configured:                                                              │"
             );
             for engine in configured {
                 let path_str = truncate_path(&engine.path, 58);
-                println!(
+                outln!(
                     "│     {:6} → {}{}│",
                     engine.version,
                     path_str,
                     " ".repeat(58_usize.saturating_sub(path_str.len()))
                 );
             }
-            println!(
+            outln!(
                 "│                                                                              │"
             );
         }
 
         if !auto_detected.is_empty() {
-            println!(
+            outln!(
                 "│  🔍 Auto-detected:                                                           │"
             );
             for engine in auto_detected {
                 let path_str = truncate_path(&engine.path, 58);
-                println!(
+                outln!(
                     "│     {:6} → {}{}│",
                     engine.version,
                     path_str,
                     " ".repeat(58_usize.saturating_sub(path_str.len()))
                 );
             }
-            println!(
+            outln!(
                 "│                                                                              │"
             );
         }
 
-        println!(
+        outln!(
             "│  Total: {} engine{}                                                         │",
             all_engines.len(),
             if all_engines.len() == 1 { " " } else { "s" }
         );
     }
 
-    println!("└──────────────────────────────────────────────────────────────────────────────┘");
-    println!();
-
-    println!("💡 Modify settings:");
-    println!("   unrealpm config set <key> <value>");
-    println!();
-    println!("   Available keys:");
-    println!("     • build.auto_build_on_publish");
-    println!("     • build.auto_build_on_install");
-    println!("     • build.configuration");
-    println!("     • registry.url");
-    println!();
+    outln!("└──────────────────────────────────────────────────────────────────────────────┘");
+    outln!();
+
+    outln!("💡 Modify settings:");
+    outln!("   unrealpm config set <key> <value>");
+    outln!();
+    outln!("   Available keys:");
+    outln!("     • build.auto_build_on_publish");
+    outln!("     • build.auto_build_on_install");
+    outln!("     • build.configuration");
+    outln!("     • registry.url");
+    outln!("     • registry.download_hosts (comma-separated CDN hosts)");
+    outln!("     • registry.mirrors (comma-separated mirror registry URLs)");
+    outln!("     • install.mode (prefer-source, prefer-binary, source-only, binary-only)");
+    outln!("     • install.save_style (caret, tilde, exact)");
+    outln!("     • install.layout (copy, store)");
+    outln!("     • install.confirm_threshold (lockfile changes before install asks, 0 = never)");
+    outln!("     • limits.max_package_mb, limits.max_file_mb, limits.max_files (0 = no limit)");
+    outln!("     • network.max_parallel_downloads (packages downloaded at once, default 4)");
+    outln!("     • ui.strict (treat warnings as errors, like --strict)");
+    outln!(
+        "     • ui.locale (message language: {}; empty = from the environment)",
+        unrealpm::i18n::builtin_locales().join(", ")
+    );
+    outln!("     • self_update.channel (stable, beta), self_update.url, self_update.public_key");
+    outln!("     • vcs.type (perforce, plastic, none: check files out before writing)");
+    outln!("     • vcs.ignore (gitignore, p4ignore, list, none: list installed plugins)");
+    outln!("     • policy.allowed_licenses (comma-separated SPDX ids; empty = any license)");
+    outln!("     • alias.<name> (command shortcut, e.g. alias.bi \"install --prefer-binary\")");
+    outln!("     • signing.key (named key to sign with; see `unrealpm keys list`)");
+    outln!();
+    outln!(
+        "   Per-project overrides go in {} (e.g., [install] mode = \"prefer-binary\")",
+        unrealpm::PROJECT_CONFIG_PATH
+    );
+    outln!();
 
     Ok(())
 }
 
 fn format_bool(value: bool) -> String {
+    if unrealpm::output::is_plain() {
+        return if value { "enabled" } else { "disabled" }.to_string();
+    }
     if value {
         "✅ enabled ".to_string()
     } else {
@@ -165,19 +204,19 @@ fn truncate_path(path: &std::path::Path, max_len: usize) -> String {
     }
 }
 
-fn set_config(key: &str, value: &str) -> Result<()> {
+fn set_config(key: &str, value: &str, dry_run: DryRun) -> Result<()> {
     let mut config = Config::load()?;
 
-    println!();
-    println!("⚙️  Updating configuration...");
-    println!();
+    outln!();
+    outln!("{}⚙️  Updating configuration...", dry_run.prefix());
+    outln!();
 
     match key {
         "build.auto_build_on_publish" => {
             config.build.auto_build_on_publish = value
                 .parse::<bool>()
                 .map_err(|_| anyhow::anyhow!("Invalid boolean value. Use 'true' or 'false'"))?;
-            println!(
+            outln!(
                 "  ✓ build.auto_build_on_publish = {}",
                 format_bool(config.build.auto_build_on_publish)
             );
@@ -186,51 +225,301 @@ fn set_config(key: &str, value: &str) -> Result<()> {
             config.build.auto_build_on_install = value
                 .parse::<bool>()
                 .map_err(|_| anyhow::anyhow!("Invalid boolean value. Use 'true' or 'false'"))?;
-            println!(
+            outln!(
                 "  ✓ build.auto_build_on_install = {}",
                 format_bool(config.build.auto_build_on_install)
             );
         }
         "build.configuration" => {
             config.build.configuration = value.to_string();
-            println!("  ✓ build.configuration = \"{}\"", value);
+            outln!("  ✓ build.configuration = \"{}\"", value);
         }
         "registry.url" => {
             config.registry.url = value.to_string();
-            println!("  ✓ registry.url = \"{}\"", value);
+            outln!("  ✓ registry.url = \"{}\"", value);
         }
         "registry.registry_type" => {
             config.registry.registry_type = value.to_string();
-            println!("  ✓ registry.registry_type = \"{}\"", value);
+            outln!("  ✓ registry.registry_type = \"{}\"", value);
+        }
+        "registry.download_hosts" => {
+            config.registry.download_hosts = value
+                .split(',')
+                .map(|h| h.trim().to_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect();
+            outln!(
+                "  ✓ registry.download_hosts = [{}]",
+                config.registry.download_hosts.join(", ")
+            );
+        }
+        "registry.mirrors" => {
+            config.registry.mirrors = value
+                .split(',')
+                .map(|m| m.trim().trim_end_matches('/').to_string())
+                .filter(|m| !m.is_empty())
+                .collect();
+            outln!(
+                "  ✓ registry.mirrors = [{}]",
+                config.registry.mirrors.join(", ")
+            );
+        }
+        "install.mode" => {
+            config.install.mode = if value.is_empty() {
+                None
+            } else {
+                Some(value.parse::<InstallMode>()?)
+            };
+            outln!(
+                "  ✓ install.mode = \"{}\"",
+                config.install.mode.unwrap_or_default()
+            );
+        }
+        "install.save_style" => {
+            config.install.save_style = if value.is_empty() {
+                None
+            } else {
+                Some(value.parse::<SaveStyle>()?)
+            };
+            outln!(
+                "  ✓ install.save_style = \"{}\"",
+                config.install.save_style.unwrap_or_default()
+            );
+        }
+        "install.layout" => {
+            config.install.layout = if value.is_empty() {
+                None
+            } else {
+                Some(value.parse::<InstallLayout>()?)
+            };
+            outln!(
+                "  ✓ install.layout = \"{}\"",
+                config.install.layout.unwrap_or_default()
+            );
+        }
+        "install.confirm_threshold" => {
+            config.install.confirm_threshold = if value.is_empty() {
+                None
+            } else {
+                Some(value.parse::<usize>().map_err(|_| {
+                    anyhow::anyhow!("Invalid number. Use a count of changes (0 = never ask)")
+                })?)
+            };
+            outln!(
+                "  ✓ install.confirm_threshold = {}",
+                config
+                    .install
+                    .confirm_threshold
+                    .unwrap_or(unrealpm::config::DEFAULT_CONFIRM_THRESHOLD)
+            );
+        }
+        "limits.max_package_mb" => {
+            config.limits.max_package_mb = value
+                .parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("Invalid number. Use a size in MB (0 = no limit)"))?;
+            outln!(
+                "  ✓ limits.max_package_mb = {}",
+                config.limits.max_package_mb
+            );
+        }
+        "limits.max_file_mb" => {
+            config.limits.max_file_mb = value
+                .parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("Invalid number. Use a size in MB (0 = no limit)"))?;
+            outln!("  ✓ limits.max_file_mb = {}", config.limits.max_file_mb);
+        }
+        "limits.max_files" => {
+            config.limits.max_files = value
+                .parse::<usize>()
+                .map_err(|_| anyhow::anyhow!("Invalid number. Use a file count (0 = no limit)"))?;
+            outln!("  ✓ limits.max_files = {}", config.limits.max_files);
+        }
+        "network.max_parallel_downloads" => {
+            config.network.max_parallel_downloads = value
+                .parse::<usize>()
+                .ok()
+                .filter(|&jobs| jobs > 0)
+                .ok_or_else(|| anyhow::anyhow!("Invalid number. Use a positive download count"))?;
+            outln!(
+                "  ✓ network.max_parallel_downloads = {}",
+                config.network.max_parallel_downloads
+            );
+        }
+        "ui.strict" => {
+            config.ui.strict = value
+                .parse::<bool>()
+                .map_err(|_| anyhow::anyhow!("Invalid boolean value. Use 'true' or 'false'"))?;
+            outln!("  ✓ ui.strict = {}", format_bool(config.ui.strict));
+        }
+        "ui.locale" => {
+            config.ui.locale = if value.is_empty() {
+                None
+            } else {
+                let language = unrealpm::i18n::language(value).ok_or_else(|| {
+                    anyhow::anyhow!("Invalid locale. Use a language code such as 'en' or 'ja'")
+                })?;
+                if !unrealpm::i18n::builtin_locales().contains(&language.as_str()) {
+                    outln!(
+                        "  ⚠ No built-in catalog for '{}'; messages stay in English unless ~/.unrealpm/locales/{}.toml exists",
+                        language, language
+                    );
+                }
+                Some(language)
+            };
+            outln!(
+                "  ✓ ui.locale = \"{}\"",
+                unrealpm::i18n::select_locale(config.ui.locale.as_deref())
+            );
+        }
+        _ if key.starts_with("alias.") => {
+            let name = &key["alias.".len()..];
+            if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+                anyhow::bail!("Invalid alias name '{}'", name);
+            }
+            if value.trim().is_empty() {
+                config.alias.remove(name);
+                outln!("  ✓ alias.{} removed", name);
+            } else {
+                config.alias.insert(
+                    name.to_string(),
+                    unrealpm::CommandAlias::Line(value.to_string()),
+                );
+                outln!("  ✓ alias.{} = \"{}\"", name, value);
+            }
+        }
+        "self_update.channel" => {
+            config.self_update.channel = if value.is_empty() {
+                None
+            } else {
+                Some(value.parse::<unrealpm::self_update::ReleaseChannel>()?)
+            };
+            outln!(
+                "  ✓ self_update.channel = \"{}\"",
+                config.self_update.channel.unwrap_or_default()
+            );
+        }
+        "self_update.url" => {
+            config.self_update.url = (!value.is_empty()).then(|| value.to_string());
+            outln!(
+                "  ✓ self_update.url = \"{}\"",
+                config.self_update.releases_url(&config.registry)
+            );
+        }
+        "self_update.public_key" => {
+            let valid_key = value.len() == 64 && hex::decode(value).is_ok();
+            if !value.is_empty() && !valid_key {
+                anyhow::bail!("Invalid public key. Use the 64-character hex Ed25519 key");
+            }
+            config.self_update.public_key = (!value.is_empty()).then(|| value.to_string());
+            outln!(
+                "  ✓ self_update.public_key = {}",
+                if value.is_empty() {
+                    "<cleared>"
+                } else {
+                    "<set>"
+                }
+            );
+        }
+        "vcs.type" => {
+            config.vcs.kind = match value {
+                "" | "none" => None,
+                value => Some(value.parse::<unrealpm::vcs::VcsKind>()?),
+            };
+            match config.vcs.kind {
+                Some(kind) => outln!("  ✓ vcs.type = \"{}\"", kind),
+                None => outln!("  ✓ vcs.type = <none>"),
+            }
+        }
+        "vcs.ignore" => {
+            config.vcs.ignore = match value {
+                "" | "none" => None,
+                value => Some(value.parse::<unrealpm::IgnoreFile>()?),
+            };
+            match config.vcs.ignore {
+                Some(file) => outln!(
+                    "  ✓ vcs.ignore = \"{}\" (run 'unrealpm vcs-ignore sync' to update {} now)",
+                    file,
+                    file.file_name()
+                ),
+                None => outln!("  ✓ vcs.ignore = <none>"),
+            }
+        }
+        "policy.allowed_licenses" => {
+            config.policy.allowed_licenses = value
+                .split(',')
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect();
+            outln!(
+                "  ✓ policy.allowed_licenses = [{}]",
+                config.policy.allowed_licenses.join(", ")
+            );
+        }
+        "signing.key" => {
+            if value.is_empty() || value == unrealpm::signing::DEFAULT_KEY_NAME {
+                config.signing.key = None;
+                outln!("  ✓ signing.key = {}", unrealpm::signing::DEFAULT_KEY_NAME);
+            } else {
+                unrealpm::signing::validate_key_name(value)?;
+                config.signing.key = Some(value.to_string());
+                outln!("  ✓ signing.key = \"{}\"", value);
+            }
         }
         "auth.token" => {
             if value.is_empty() {
                 config.auth.token = None;
-                println!("  ✓ auth.token = <cleared>");
+                outln!("  ✓ auth.token = <cleared>");
             } else {
                 config.auth.token = Some(value.to_string());
-                println!("  ✓ auth.token = <set>");
+                outln!("  ✓ auth.token = <set>");
             }
         }
         _ => {
-            println!("  ❌ Unknown key: {}", key);
-            println!();
-            println!("  Available keys:");
-            println!("    • build.auto_build_on_publish");
-            println!("    • build.auto_build_on_install");
-            println!("    • build.configuration");
-            println!("    • registry.url");
-            println!("    • registry.registry_type");
-            println!("    • auth.token");
-            println!();
+            outln!("  ❌ Unknown key: {}", key);
+            outln!();
+            outln!("  Available keys:");
+            outln!("    • build.auto_build_on_publish");
+            outln!("    • build.auto_build_on_install");
+            outln!("    • build.configuration");
+            outln!("    • registry.url");
+            outln!("    • registry.registry_type");
+            outln!("    • registry.download_hosts");
+            outln!("    • registry.mirrors");
+            outln!("    • install.mode");
+            outln!("    • install.save_style");
+            outln!("    • install.layout");
+            outln!("    • install.confirm_threshold");
+            outln!("    • limits.max_package_mb");
+            outln!("    • limits.max_file_mb");
+            outln!("    • limits.max_files");
+            outln!("    • network.max_parallel_downloads");
+            outln!("    • ui.strict");
+            outln!("    • ui.locale");
+            outln!("    • self_update.channel");
+            outln!("    • self_update.url");
+            outln!("    • self_update.public_key");
+            outln!("    • vcs.type");
+            outln!("    • vcs.ignore");
+            outln!("    • policy.allowed_licenses");
+            outln!("    • alias.<name>");
+            outln!("    • signing.key");
+            outln!("    • auth.token");
+            outln!();
             anyhow::bail!("Invalid configuration key");
         }
     }
 
-    config.save()?;
-    println!();
-    println!("✅ Configuration saved");
-    println!();
+    dry_run.apply(|| config.save())?;
+    outln!();
+    if dry_run.is_enabled() {
+        outln!(
+            "[DRY RUN] Configuration not saved ({})",
+            Config::default_path()?.display()
+        );
+    } else {
+        outln!("✅ Configuration saved");
+    }
+    outln!();
 
     Ok(())
 }
@@ -239,14 +528,14 @@ fn add_engine(version: &str, path: &str) -> Result<()> {
     let mut config = Config::load()?;
     let engine_path = PathBuf::from(path);
 
-    println!();
-    println!("🔧 Adding Unreal Engine {}...", version);
-    println!();
+    outln!();
+    outln!("🔧 Adding Unreal Engine {}...", version);
+    outln!();
 
     // Validate path exists
     if !engine_path.exists() {
-        println!("  ❌ Path does not exist: {}", path);
-        println!();
+        outln!("  ❌ Path does not exist: {}", path);
+        outln!();
         anyhow::bail!("Invalid engine path");
     }
 
@@ -268,20 +557,20 @@ fn add_engine(version: &str, path: &str) -> Result<()> {
     };
 
     if !ubt_check {
-        println!("  ⚠️  Warning: Could not verify UnrealBuildTool at this path");
-        println!("     Make sure this is a valid Unreal Engine installation");
-        println!();
+        outln!("  ⚠️  Warning: Could not verify UnrealBuildTool at this path");
+        outln!("     Make sure this is a valid Unreal Engine installation");
+        outln!();
     } else {
-        println!("  ✓ Validated Unreal Engine installation");
-        println!();
+        outln!("  ✓ Validated Unreal Engine installation");
+        outln!();
     }
 
     config.add_engine(version.to_string(), engine_path.clone());
     config.save()?;
 
-    println!("✅ Added Unreal Engine {}", version);
-    println!("   Path: {}", engine_path.display());
-    println!();
+    outln!("✅ Added Unreal Engine {}", version);
+    outln!("   Path: {}", engine_path.display());
+    outln!();
 
     Ok(())
 }
@@ -289,26 +578,26 @@ fn add_engine(version: &str, path: &str) -> Result<()> {
 fn remove_engine(version: &str) -> Result<()> {
     let mut config = Config::load()?;
 
-    println!();
-    println!("🗑️  Removing Unreal Engine {}...", version);
-    println!();
+    outln!();
+    outln!("🗑️  Removing Unreal Engine {}...", version);
+    outln!();
 
     if !config.engines.iter().any(|e| e.version == version) {
-        println!(
+        outln!(
             "  ❌ Engine version '{}' not found in configured engines",
             version
         );
-        println!();
-        println!("  💡 View configured engines: unrealpm config list-engines");
-        println!();
+        outln!();
+        outln!("  💡 View configured engines: unrealpm config list-engines");
+        outln!();
         anyhow::bail!("Engine not found");
     }
 
     config.remove_engine(version);
     config.save()?;
 
-    println!("✅ Removed Unreal Engine {}", version);
-    println!();
+    outln!("✅ Removed Unreal Engine {}", version);
+    outln!();
 
     Ok(())
 }
@@ -316,27 +605,27 @@ fn remove_engine(version: &str) -> Result<()> {
 fn list_engines() -> Result<()> {
     let config = Config::load()?;
 
-    println!();
-    println!("╔══════════════════════════════════════════════════════════════════════════════╗");
-    println!("║                   Unreal Engine Installations                                ║");
-    println!("╚══════════════════════════════════════════════════════════════════════════════╝");
-    println!();
+    outln!();
+    outln!("╔══════════════════════════════════════════════════════════════════════════════╗");
+    outln!("║                   Unreal Engine Installations                                ║");
+    outln!("╚══════════════════════════════════════════════════════════════════════════════╝");
+    outln!();
 
     let all_engines = config.get_all_engines();
 
     if all_engines.is_empty() {
-        println!("  ❌ No Unreal Engine installations found");
-        println!();
-        println!("  💡 Auto-detection scans standard locations:");
-        println!("     • Windows: C:\\Program Files\\Epic Games\\UE_*");
-        println!("     • Linux:   ~/UnrealEngine/UE_* and /opt/UnrealEngine/*");
-        println!("     • macOS:   /Users/Shared/Epic Games/UE_*");
-        println!();
-        println!("  Or add manually:");
-        println!("     unrealpm config add-engine <version> <path>");
-        println!();
-        println!("  Example:");
-        println!("     unrealpm config add-engine 5.3 /path/to/UE_5.3");
+        outln!("  ❌ No Unreal Engine installations found");
+        outln!();
+        outln!("  💡 Auto-detection scans standard locations:");
+        outln!("     • Windows: C:\\Program Files\\Epic Games\\UE_*");
+        outln!("     • Linux:   ~/UnrealEngine/UE_* and /opt/UnrealEngine/*");
+        outln!("     • macOS:   /Users/Shared/Epic Games/UE_*");
+        outln!();
+        outln!("  Or add manually:");
+        outln!("     unrealpm config add-engine <version> <path>");
+        outln!();
+        outln!("  Example:");
+        outln!("     unrealpm config add-engine 5.3 /path/to/UE_5.3");
     } else {
         // Separate configured vs auto-detected
         let configured: Vec<_> = all_engines
@@ -350,52 +639,52 @@ fn list_engines() -> Result<()> {
             .collect();
 
         if !configured.is_empty() {
-            println!("  📌 Configured Engines:");
-            println!(
+            outln!("  📌 Configured Engines:");
+            outln!(
                 "  ┌──────────────────────────────────────────────────────────────────────────┐"
             );
             for engine in configured {
                 let path_str = truncate_path(&engine.path, 60);
-                println!(
+                outln!(
                     "  │  {:6} → {}{}│",
                     engine.version,
                     path_str,
                     " ".repeat(60_usize.saturating_sub(path_str.len()))
                 );
             }
-            println!(
+            outln!(
                 "  └──────────────────────────────────────────────────────────────────────────┘"
             );
-            println!();
+            outln!();
         }
 
         if !auto_detected.is_empty() {
-            println!("  🔍 Auto-Detected Engines:");
-            println!(
+            outln!("  🔍 Auto-Detected Engines:");
+            outln!(
                 "  ┌──────────────────────────────────────────────────────────────────────────┐"
             );
             for engine in auto_detected {
                 let path_str = truncate_path(&engine.path, 60);
-                println!(
+                outln!(
                     "  │  {:6} → {}{}│",
                     engine.version,
                     path_str,
                     " ".repeat(60_usize.saturating_sub(path_str.len()))
                 );
             }
-            println!(
+            outln!(
                 "  └──────────────────────────────────────────────────────────────────────────┘"
             );
-            println!();
+            outln!();
         }
 
-        println!(
+        outln!(
             "  📊 Total: {} engine{}",
             all_engines.len(),
             if all_engines.len() == 1 { "" } else { "s" }
         );
     }
-    println!();
+    outln!();
 
     Ok(())
 }
@@ -3,6 +3,7 @@
 //! Checks:
 //! - Unreal Engine installations
 //! - Registry connectivity
+//! - Mirror health (latency and reachability of each configured mirror)
 //! - Configuration validity
 //! - Cache health
 //! - Authentication status
@@ -12,6 +13,7 @@ use std::env;
 use std::fs;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
+use unrealpm::outln;
 use unrealpm::{get_store_dir, get_store_stats, Config, Lockfile, Manifest, RegistryClient};
 
 /// Status of a check
@@ -64,7 +66,7 @@ impl CheckResult {
 
     fn print(&self, verbose: bool) {
         let reset = "\x1b[0m";
-        println!(
+        outln!(
             "  {}{}{} {} - {}",
             self.status.color_code(),
             self.status.symbol(),
@@ -75,7 +77,7 @@ impl CheckResult {
         if verbose {
             if let Some(ref details) = self.details {
                 for line in details.lines() {
-                    println!("      {}", line);
+                    outln!("      {}", line);
                 }
             }
         }
@@ -83,42 +85,17 @@ impl CheckResult {
 }
 
 pub fn run(verbose: bool, fix: bool) -> Result<()> {
-    println!("UnrealPM Doctor");
-    println!("===============");
-    println!();
-    println!("Checking your setup...");
-    println!();
+    outln!("UnrealPM Doctor");
+    outln!("===============");
+    outln!();
+    outln!("Checking your setup...");
+    outln!();
 
-    let mut results = Vec::new();
-    let mut fixable_issues = Vec::new();
-
-    // Check 1: Configuration
-    results.push(check_config());
-
-    // Check 2: Registry connectivity
-    results.push(check_registry());
-
-    // Check 3: Unreal Engine installations
-    results.push(check_engines());
-
-    // Check 4: Cache health
-    let (cache_result, cache_fix) = check_cache();
-    results.push(cache_result);
-    if let Some(fix_fn) = cache_fix {
-        fixable_issues.push(("Clean stale cache entries", fix_fn));
-    }
-
-    // Check 5: Project (if in a project directory)
-    if let Some(result) = check_project() {
-        results.push(result);
-    }
-
-    // Check 6: Authentication
-    results.push(check_auth());
+    let (results, fixable_issues) = run_checks();
 
     // Print results
-    println!("Results:");
-    println!();
+    outln!("Results:");
+    outln!();
     for result in &results {
         result.print(verbose);
     }
@@ -137,49 +114,104 @@ pub fn run(verbose: bool, fix: bool) -> Result<()> {
         .filter(|r| matches!(r.status, CheckStatus::Error))
         .count();
 
-    println!();
-    println!(
+    outln!();
+    outln!(
         "Summary: {} passed, {} warnings, {} errors",
-        ok_count, warn_count, error_count
+        ok_count,
+        warn_count,
+        error_count
     );
 
     // Offer fixes
     if !fixable_issues.is_empty() {
-        println!();
+        outln!();
         if fix {
-            println!("Applying fixes...");
-            println!();
+            outln!("Applying fixes...");
+            outln!();
             for (name, fix_fn) in fixable_issues {
                 print!("  Fixing: {}... ", name);
                 match fix_fn() {
-                    Ok(msg) => println!("{}", msg),
-                    Err(e) => println!("Failed: {}", e),
+                    Ok(msg) => outln!("{}", msg),
+                    Err(e) => outln!("Failed: {}", e),
                 }
             }
         } else {
-            println!("Some issues can be fixed automatically. Run with --fix to apply:");
+            outln!("Some issues can be fixed automatically. Run with --fix to apply:");
             for (name, _) in &fixable_issues {
-                println!("  - {}", name);
+                outln!("  - {}", name);
             }
         }
     }
 
-    println!();
+    outln!();
 
     if error_count > 0 {
-        println!("Some checks failed. See above for details.");
+        outln!("Some checks failed. See above for details.");
         if !verbose {
-            println!("Run with --verbose for more information.");
+            outln!("Run with --verbose for more information.");
         }
     } else if warn_count > 0 {
-        println!("All critical checks passed, but there are some warnings.");
+        outln!("All critical checks passed, but there are some warnings.");
     } else {
-        println!("All checks passed! Your setup looks good.");
+        outln!("All checks passed! Your setup looks good.");
     }
 
     Ok(())
 }
 
+/// A fix `--fix` can apply, with its description
+type Fix = (&'static str, Box<dyn FnOnce() -> Result<String>>);
+
+/// Run every check, returning the results and the fixes on offer
+fn run_checks() -> (Vec<CheckResult>, Vec<Fix>) {
+    let mut results = Vec::new();
+    let mut fixable_issues: Vec<Fix> = Vec::new();
+
+    // Check 1: Configuration
+    results.push(check_config());
+
+    // Check 2: Registry connectivity
+    results.push(check_registry());
+    results.extend(check_mirrors());
+
+    // Check 3: Unreal Engine installations
+    results.push(check_engines());
+
+    // Check 4: Cache health
+    let (cache_result, cache_fix) = check_cache();
+    results.push(cache_result);
+    if let Some(fix_fn) = cache_fix {
+        fixable_issues.push(("Clean stale cache entries", fix_fn));
+    }
+
+    // Check 5: Project (if in a project directory)
+    if let Some(result) = check_project() {
+        results.push(result);
+    }
+
+    // Check 6: Authentication
+    results.push(check_auth());
+
+    (results, fixable_issues)
+}
+
+/// Results of every check with their details, as plain text (for bug reports)
+pub fn diagnose() -> String {
+    let mut text = String::new();
+    for result in run_checks().0 {
+        text.push_str(&format!(
+            "{} {} - {}\n",
+            result.status.symbol(),
+            result.name,
+            result.message
+        ));
+        for line in result.details.iter().flat_map(|details| details.lines()) {
+            text.push_str(&format!("    {}\n", line));
+        }
+    }
+    text
+}
+
 fn check_config() -> CheckResult {
     match Config::load() {
         Ok(config) => {
@@ -268,6 +300,55 @@ fn check_registry() -> CheckResult {
     }
 }
 
+/// One result per configured mirror, in latency order
+fn check_mirrors() -> Vec<CheckResult> {
+    let Ok(config) = Config::load() else {
+        return Vec::new();
+    };
+    if config.mirrors_for(&config.registry.url).is_empty() {
+        return Vec::new();
+    }
+    let Ok(RegistryClient::Http(registry)) = RegistryClient::from_config(&config) else {
+        return vec![CheckResult::new(
+            "Mirrors",
+            CheckStatus::Warning,
+            "Mirrors are configured but only used with an HTTP registry",
+        )];
+    };
+
+    let health = registry.mirror_health();
+    let fastest = health.iter().find(|h| h.is_up()).map(|h| h.url.clone());
+    health
+        .iter()
+        .filter(|h| h.url != registry.base_url().trim_end_matches('/'))
+        .map(|h| {
+            let name = format!("Mirror {}", h.url);
+            match (h.latency, &h.error) {
+                (Some(latency), None) => {
+                    let fastest = fastest.as_deref() == Some(h.url.as_str());
+                    CheckResult::new(
+                        &name,
+                        CheckStatus::Ok,
+                        &format!(
+                            "Reachable ({} ms{})",
+                            latency.as_millis(),
+                            if fastest { ", fastest" } else { "" }
+                        ),
+                    )
+                }
+                (_, error) => CheckResult::new(
+                    &name,
+                    CheckStatus::Warning,
+                    &format!(
+                        "Unavailable: {} (skipped for failover)",
+                        error.as_deref().unwrap_or("unknown error")
+                    ),
+                ),
+            }
+        })
+        .collect()
+}
+
 fn check_engines() -> CheckResult {
     let config = match Config::load() {
         Ok(c) => c,
@@ -294,7 +375,7 @@ fn check_engines() -> CheckResult {
                 "Unreal Engines",
                 CheckStatus::Warning,
                 &format!(
-                    "No configured engines, but {} detected. Run `unrealpm config add-engine` to add them.",
+                    "No configured engines, but {} detected. Run `unrealpm engine add` to add them.",
                     detected.len()
                 ),
             )
@@ -437,9 +518,8 @@ fn check_cache() -> (CheckResult, Option<Box<dyn FnOnce() -> Result<String>>>) {
 fn check_project() -> Option<CheckResult> {
     let current_dir = env::current_dir().ok()?;
 
-    // Check for unrealpm.json
-    let manifest_path = current_dir.join("unrealpm.json");
-    if !manifest_path.exists() {
+    // Check for unrealpm.json / unrealpm.toml
+    if !Manifest::exists(&current_dir) {
         return None; // Not in a project, skip this check
     }
 
@@ -522,7 +602,8 @@ fn check_auth() -> CheckResult {
         }
     };
 
-    if let Some(token) = &config.auth.token {
+    let project_dir = std::env::current_dir().unwrap_or_default();
+    if let Some(token) = config.auth_token(project_dir) {
         if token.starts_with("urpm_") {
             CheckResult::new("Authentication", CheckStatus::Ok, "API token configured")
                 .with_details(&format!("Token: {}...", &token[..15.min(token.len())]))
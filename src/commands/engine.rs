@@ -0,0 +1,441 @@
+//! Engine command - register, verify, and bootstrap Unreal Engine installations
+//!
+//! Subcommands:
+//! - list: Show configured and auto-detected engines with their Build.version
+//! - add: Register an engine, reading its version from Build.version
+//! - platforms: Set or reset the target platforms of a registered engine
+//! - verify: Validate registered engines (path, UnrealBuildTool, Build.version)
+//! - setup: Run Setup and GenerateProjectFiles for source builds
+//! - alias/unalias: Map custom EngineAssociation strings to engine versions
+
+use anyhow::Result;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use unrealpm::{
+    is_source_engine_build, is_valid_engine_install, read_engine_build_version, Config,
+};
+
+pub fn run(action: &crate::EngineAction) -> Result<()> {
+    use crate::EngineAction;
+
+    match action {
+        EngineAction::List => run_list(),
+        EngineAction::Add {
+            path,
+            version,
+            platforms,
+        } => run_add(path, version.as_deref(), platforms),
+        EngineAction::Platforms { version, platforms } => run_platforms(version, platforms),
+        EngineAction::Verify { version } => run_verify(version.as_deref()),
+        EngineAction::Setup {
+            version,
+            skip_project_files,
+            dry_run,
+        } => run_setup(version, *skip_project_files, *dry_run),
+        EngineAction::Alias {
+            association,
+            version,
+            path,
+        } => run_alias(association, version, path.as_deref()),
+        EngineAction::Unalias { association } => run_unalias(association),
+    }
+}
+
+fn run_list() -> Result<()> {
+    let config = Config::load()?;
+    let engines = config.get_all_engines();
+
+    println!("Unreal Engine installations:");
+    println!();
+
+    if engines.is_empty() {
+        println!("  No engines configured or detected");
+        println!();
+        println!("Register one with:");
+        println!("  unrealpm engine add /path/to/UE_5.3");
+        return Ok(());
+    }
+
+    for engine in &engines {
+        let source = if config.engines.iter().any(|e| e.version == engine.version) {
+            "configured"
+        } else {
+            "detected"
+        };
+        let kind = if is_source_engine_build(&engine.path) {
+            "source build"
+        } else {
+            "installed build"
+        };
+
+        println!("  UE {} ({}, {})", engine.version, source, kind);
+        println!("    Path: {}", engine.path.display());
+        if let Some(build_version) = read_engine_build_version(&engine.path) {
+            println!("    Build.version: {}", build_version.full());
+        }
+        let platforms = engine.target_platforms();
+        if !platforms.is_empty() {
+            println!(
+                "    Platforms: {}{}",
+                platforms.join(", "),
+                if engine.platforms.is_some() {
+                    ""
+                } else {
+                    " (detected)"
+                }
+            );
+        }
+    }
+
+    if !config.engine_aliases.is_empty() {
+        println!();
+        println!("Engine associations:");
+        for (association, alias) in &config.engine_aliases {
+            match &alias.path {
+                Some(path) => println!(
+                    "  {} -> UE {} ({})",
+                    association,
+                    alias.version,
+                    path.display()
+                ),
+                None => println!("  {} -> UE {}", association, alias.version),
+            }
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+fn run_alias(association: &str, version: &str, path: Option<&str>) -> Result<()> {
+    let mut config = Config::load()?;
+
+    if unrealpm::EngineVersion::parse(version).is_none() {
+        anyhow::bail!(
+            "Invalid engine version '{}' (expected e.g. 5.3)\n\n\
+            Usage:\n\
+              unrealpm engine alias \"{}\" 5.3 [--path /path/to/engine]",
+            version,
+            association
+        );
+    }
+
+    let engine_path = path.map(|p| PathBuf::from(shellexpand::tilde(p).to_string()));
+    if let Some(engine_path) = &engine_path {
+        if !engine_path.exists() {
+            anyhow::bail!("Engine path does not exist: {}", engine_path.display());
+        }
+        if !is_valid_engine_install(engine_path) && !is_source_engine_build(engine_path) {
+            println!("  ⚠ Could not verify UnrealBuildTool at this path");
+        }
+    }
+
+    config.set_engine_alias(
+        association.to_string(),
+        version.to_string(),
+        engine_path.clone(),
+    );
+    config.save()?;
+
+    println!(
+        "✓ Mapped engine association '{}' to UE {}",
+        association, version
+    );
+    match &engine_path {
+        Some(engine_path) => println!("  Path: {}", engine_path.display()),
+        None => match config.find_engine(association) {
+            Some(engine) => println!("  Path: {} (UE {})", engine.path.display(), engine.version),
+            None => println!(
+                "  ⚠ No UE {} installation found; pass --path to set one",
+                version
+            ),
+        },
+    }
+
+    Ok(())
+}
+
+fn run_unalias(association: &str) -> Result<()> {
+    let mut config = Config::load()?;
+
+    if !config.remove_engine_alias(association) {
+        anyhow::bail!(
+            "No mapping for engine association '{}'.\n\n\
+            See configured mappings with:\n\
+              unrealpm engine list",
+            association
+        );
+    }
+    config.save()?;
+
+    println!("✓ Removed engine association '{}'", association);
+    Ok(())
+}
+
+fn run_add(path: &str, version: Option<&str>, platforms: &[String]) -> Result<()> {
+    let mut config = Config::load()?;
+    let engine_path = PathBuf::from(shellexpand::tilde(path).to_string());
+
+    if !engine_path.exists() {
+        anyhow::bail!("Engine path does not exist: {}", engine_path.display());
+    }
+
+    let build_version = read_engine_build_version(&engine_path);
+
+    let version = match (version, &build_version) {
+        (Some(v), Some(bv)) => {
+            if unrealpm::normalize_engine_version(v) != bv.short() {
+                println!(
+                    "  ⚠ Requested version {} does not match Build.version ({})",
+                    v,
+                    bv.full()
+                );
+            }
+            v.to_string()
+        }
+        (Some(v), None) => v.to_string(),
+        (None, Some(bv)) => bv.short(),
+        (None, None) => anyhow::bail!(
+            "Could not determine engine version (no Engine/Build/Build.version found).\n\n\
+            Specify it explicitly:\n\
+              unrealpm engine add {} --version 5.3",
+            path
+        ),
+    };
+
+    println!("Registering Unreal Engine {}...", version);
+    println!();
+
+    if is_valid_engine_install(&engine_path) {
+        println!("  ✓ Found UnrealBuildTool");
+    } else if is_source_engine_build(&engine_path) {
+        println!("  ⚠ UnrealBuildTool not built yet (source build)");
+        println!("    Run: unrealpm engine setup {}", version);
+    } else {
+        println!("  ⚠ Could not verify UnrealBuildTool at this path");
+    }
+
+    config.add_engine(version.clone(), engine_path.clone());
+    if !platforms.is_empty() {
+        config.set_engine_platforms(&version, Some(platforms.to_vec()));
+    }
+    config.save()?;
+
+    let target_platforms = config
+        .find_engine(&version)
+        .map(|e| e.target_platforms())
+        .unwrap_or_default();
+
+    println!();
+    println!("✓ Registered Unreal Engine {}", version);
+    println!("  Path: {}", engine_path.display());
+    if !target_platforms.is_empty() {
+        println!("  Platforms: {}", target_platforms.join(", "));
+    }
+
+    Ok(())
+}
+
+fn run_platforms(version: &str, platforms: &[String]) -> Result<()> {
+    let mut config = Config::load()?;
+
+    let new_platforms = if platforms.is_empty() {
+        None
+    } else {
+        Some(platforms.to_vec())
+    };
+
+    if !config.set_engine_platforms(version, new_platforms) {
+        anyhow::bail!(
+            "Engine version '{}' is not configured.\n\n\
+            Register it first:\n\
+              unrealpm engine add /path/to/UE_{} --version {}",
+            version,
+            version,
+            version
+        );
+    }
+    config.save()?;
+
+    println!(
+        "✓ UE {} platforms: {}{}",
+        version,
+        config.platforms_for_engine(version).join(", "),
+        if platforms.is_empty() {
+            " (detected)"
+        } else {
+            ""
+        }
+    );
+
+    Ok(())
+}
+
+fn run_verify(version: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+
+    let engines = match version {
+        Some(v) => vec![config
+            .find_engine(v)
+            .ok_or_else(|| anyhow::anyhow!("Unreal Engine {} not found", v))?],
+        None => config.get_all_engines(),
+    };
+
+    if engines.is_empty() {
+        println!("No engines to verify");
+        return Ok(());
+    }
+
+    println!("Verifying engine installations...");
+    println!();
+
+    let mut failed = 0;
+    for engine in &engines {
+        let problems = verify_engine(&engine.version, &engine.path);
+        if problems.is_empty() {
+            println!("  ✓ UE {} - {}", engine.version, engine.path.display());
+        } else {
+            failed += 1;
+            println!("  ✗ UE {} - {}", engine.version, engine.path.display());
+            for problem in &problems {
+                println!("      {}", problem);
+            }
+        }
+    }
+    println!();
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} engine(s) failed verification",
+            failed,
+            engines.len()
+        );
+    }
+
+    println!("✓ All {} engine(s) verified", engines.len());
+    Ok(())
+}
+
+/// Check a single engine installation, returning a list of problems found
+fn verify_engine(version: &str, path: &Path) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if !path.exists() {
+        problems.push("Path does not exist".to_string());
+        return problems;
+    }
+
+    match read_engine_build_version(path) {
+        Some(build_version) => {
+            if unrealpm::normalize_engine_version(version) != build_version.short() {
+                problems.push(format!(
+                    "Build.version reports {}, registered as {}",
+                    build_version.full(),
+                    version
+                ));
+            }
+        }
+        None => problems.push("Missing or invalid Engine/Build/Build.version".to_string()),
+    }
+
+    if !is_valid_engine_install(path) {
+        if is_source_engine_build(path) {
+            problems.push(format!(
+                "UnrealBuildTool not found - run `unrealpm engine setup {}`",
+                version
+            ));
+        } else {
+            problems.push("UnrealBuildTool not found".to_string());
+        }
+    }
+
+    problems
+}
+
+fn run_setup(version: &str, skip_project_files: bool, dry_run: bool) -> Result<()> {
+    let config = Config::load()?;
+    let engine = config.find_engine(version).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unreal Engine {} not found.\n\n\
+            Register it first:\n\
+              unrealpm engine add /path/to/UnrealEngine --version {}",
+            version,
+            version
+        )
+    })?;
+
+    if !is_source_engine_build(&engine.path) {
+        anyhow::bail!(
+            "UE {} at {} is not a source build.\n\
+            Setup is only needed for engines cloned from GitHub.",
+            version,
+            engine.path.display()
+        );
+    }
+
+    println!("Setting up Unreal Engine {} source build...", version);
+    println!("  Path: {}", engine.path.display());
+    println!();
+
+    let mut steps = vec!["Setup"];
+    if !skip_project_files {
+        steps.push("GenerateProjectFiles");
+    }
+
+    for step in steps {
+        let script = script_path(&engine.path, step);
+        if !script.exists() {
+            anyhow::bail!("{} not found at: {}", step, script.display());
+        }
+
+        if dry_run {
+            println!("[DRY RUN] Would run: {}", script.display());
+            continue;
+        }
+
+        println!("  Running {}...", step);
+        let status = script_command(&script).current_dir(&engine.path).status()?;
+
+        if !status.success() {
+            anyhow::bail!("{} failed with exit code: {:?}", step, status.code());
+        }
+        println!("  ✓ {} complete", step);
+    }
+
+    println!();
+    if !dry_run {
+        println!("✓ Engine {} is ready", version);
+        println!();
+        println!("Next steps:");
+        println!("  • Build the editor from the generated solution/Makefile");
+        println!("  • Verify with: unrealpm engine verify {}", version);
+    }
+
+    Ok(())
+}
+
+/// Path to a root-level engine script for the current host (`.bat` or `.sh`)
+fn script_path(engine_path: &Path, name: &str) -> PathBuf {
+    let ext = if cfg!(windows) { "bat" } else { "sh" };
+    engine_path.join(format!("{}.{}", name, ext))
+}
+
+fn script_command(script: &Path) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd.exe");
+        cmd.arg("/C").arg(script);
+        cmd
+    } else if env::var("WSL_DISTRO_NAME").is_ok() && script.starts_with("/mnt/") {
+        // Windows engine accessed from WSL - scripts must go through cmd.exe
+        let mut cmd = Command::new("cmd.exe");
+        cmd.arg("/C").arg(
+            unrealpm::wsl_to_windows_path(script).unwrap_or_else(|| script.display().to_string()),
+        );
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg(script);
+        cmd
+    }
+}
@@ -0,0 +1,130 @@
+//! Env command - export the resolved plugin environment for custom build pipelines
+//!
+//! Output formats:
+//! - default: `UNREALPM_*=value` lines, suitable for CI env files or `eval`
+//! - `--ubt`: UnrealBuildTool/UAT arguments (`-Project=`, `-EnablePlugin=`)
+//! - `--json`: structured output for Build.cs/Target.cs scripts
+
+use anyhow::Result;
+use std::env;
+use std::path::PathBuf;
+use unrealpm::{Config, Lockfile, Manifest, UPlugin};
+
+/// A managed plugin as seen by the engine
+struct EnvPlugin {
+    /// Package name in unrealpm.json / unrealpm.lock
+    package: String,
+    /// Plugin name as UBT knows it (the .uplugin file stem)
+    plugin: String,
+    version: String,
+    uplugin_path: PathBuf,
+}
+
+pub fn run(ubt: bool, json: bool) -> Result<()> {
+    let project_dir = env::current_dir()?;
+
+    let manifest = Manifest::load(&project_dir)?;
+    let lockfile = Lockfile::load()?.ok_or_else(|| {
+        anyhow::anyhow!("No lockfile found (unrealpm.lock). Run `unrealpm install` first.")
+    })?;
+
+    let uproject = match manifest.uproject_path(&project_dir) {
+        Ok(path) => Some(path),
+        Err(unrealpm::Error::NoUProjectFile) => None,
+        Err(e) => return Err(e.into()),
+    };
+    let engine_dir = manifest.engine_version.as_deref().and_then(|version| {
+        Config::load()
+            .ok()
+            .and_then(|config| config.find_engine(version))
+            .map(|engine| engine.path)
+    });
+
+    let plugins_dir = unrealpm::plugins_dir(&project_dir);
+    let plugin_dirs = vec![plugins_dir.clone()];
+
+    let mut names: Vec<_> = lockfile.packages.keys().collect();
+    names.sort();
+
+    let mut plugins = Vec::new();
+    for name in names {
+        let locked = &lockfile.packages[name];
+        let uplugin_path = match UPlugin::find(plugins_dir.join(unrealpm::scope::unscoped(name))) {
+            Ok(path) => path,
+            // Engine-installed Fab plugins are already visible to UBT
+            Err(_) if locked.is_externally_managed() => continue,
+            Err(_) => {
+                eprintln!(
+                    "⚠ {}@{} is not installed - run `unrealpm install`",
+                    name, locked.version
+                );
+                continue;
+            }
+        };
+        plugins.push(EnvPlugin {
+            package: name.clone(),
+            plugin: UPlugin::name(&uplugin_path).unwrap_or_else(|| name.clone()),
+            version: locked.version.clone(),
+            uplugin_path,
+        });
+    }
+
+    if json {
+        let output = serde_json::json!({
+            "project_dir": project_dir,
+            "uproject": uproject,
+            "engine_version": manifest.engine_version,
+            "engine_dir": engine_dir,
+            "plugin_dirs": plugin_dirs,
+            "plugins": plugins
+                .iter()
+                .map(|p| {
+                    serde_json::json!({
+                        "package": p.package,
+                        "name": p.plugin,
+                        "version": p.version,
+                        "uplugin": p.uplugin_path,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if ubt {
+        let mut args = Vec::new();
+        if let Some(uproject) = &uproject {
+            args.push(format!("-Project=\"{}\"", uproject.display()));
+        }
+        args.extend(
+            plugins
+                .iter()
+                .map(|p| format!("-EnablePlugin={}", p.plugin)),
+        );
+        println!("{}", args.join(" "));
+    } else {
+        println!("UNREALPM_PROJECT_DIR={}", project_dir.display());
+        if let Some(uproject) = &uproject {
+            println!("UNREALPM_UPROJECT={}", uproject.display());
+        }
+        if let Some(version) = &manifest.engine_version {
+            println!("UNREALPM_ENGINE_VERSION={}", version);
+        }
+        if let Some(engine_dir) = &engine_dir {
+            println!("UNREALPM_ENGINE_DIR={}", engine_dir.display());
+        }
+        println!(
+            "UNREALPM_PLUGIN_DIRS={}",
+            env::join_paths(&plugin_dirs)?.to_string_lossy()
+        );
+        // '+' matches the list separator UBT uses for plugin arguments
+        println!(
+            "UNREALPM_PLUGINS={}",
+            plugins
+                .iter()
+                .map(|p| p.plugin.as_str())
+                .collect::<Vec<_>>()
+                .join("+")
+        );
+    }
+
+    Ok(())
+}
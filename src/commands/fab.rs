@@ -0,0 +1,164 @@
+//! Fab command - list and pin Fab/Marketplace plugins
+//!
+//! Fab plugins are installed by the Epic Games Launcher, not by UnrealPM.
+//! Pinning records their name, version, and tree hash in the lockfile with a
+//! `fab+<marketplace id>` source so they show up alongside managed packages.
+
+use anyhow::Result;
+use std::env;
+use std::path::Path;
+use unrealpm::marketplace::{scan_engine, scan_project, MarketplacePlugin};
+use unrealpm::patch::hash_directory;
+use unrealpm::{Config, Lockfile, Manifest};
+
+pub fn run(action: &crate::FabAction) -> Result<()> {
+    let project_dir = env::current_dir()?;
+
+    match action {
+        crate::FabAction::List { engine } => list(&project_dir, engine.as_deref()),
+        crate::FabAction::Pin {
+            plugins,
+            all,
+            engine,
+        } => pin(&project_dir, plugins, *all, engine.as_deref()),
+        crate::FabAction::Unpin { plugin } => unpin(plugin),
+    }
+}
+
+/// Discover Fab plugins in the project's engine and Plugins/ directory
+fn discover(project_dir: &Path, engine_override: Option<&str>) -> Result<Vec<MarketplacePlugin>> {
+    let engine_version = match engine_override {
+        Some(version) => Some(version.to_string()),
+        None => Manifest::load(project_dir)
+            .ok()
+            .and_then(|m| m.engine_version),
+    };
+
+    let mut plugins = scan_project(project_dir)?;
+
+    match engine_version {
+        Some(version) => match Config::load()?.find_engine(&version) {
+            Some(engine) => plugins.extend(scan_engine(&engine.path)?),
+            None => println!(
+                "⚠ Engine {} not found - only scanning Plugins/ (run `unrealpm engine add`)",
+                version
+            ),
+        },
+        None => println!("⚠ No engine version set - only scanning Plugins/"),
+    }
+
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    plugins.dedup_by(|a, b| a.name == b.name);
+    Ok(plugins)
+}
+
+fn list(project_dir: &Path, engine: Option<&str>) -> Result<()> {
+    let plugins = discover(project_dir, engine)?;
+    let lockfile = Lockfile::load()?.unwrap_or_default();
+
+    if plugins.is_empty() {
+        println!("No Fab/Marketplace plugins found.");
+        return Ok(());
+    }
+
+    println!("Fab/Marketplace plugins (externally managed):");
+    println!();
+    println!(
+        "{:<30} {:<12} {:<40} Pinned",
+        "Plugin", "Version", "Marketplace ID"
+    );
+    println!("{}", "-".repeat(90));
+
+    for plugin in &plugins {
+        let pinned = match lockfile.get_package(&plugin.name) {
+            Some(locked) if locked.source.as_deref() == Some(plugin.source().as_str()) => {
+                if locked.version == plugin.version {
+                    "✓".to_string()
+                } else {
+                    format!("⚠ {}", locked.version)
+                }
+            }
+            _ => "-".to_string(),
+        };
+        println!(
+            "{:<30} {:<12} {:<40} {}",
+            plugin.name, plugin.version, plugin.marketplace_id, pinned
+        );
+    }
+
+    println!();
+    println!("Pin into unrealpm.lock with: unrealpm fab pin <plugin> (or --all)");
+    Ok(())
+}
+
+fn pin(project_dir: &Path, names: &[String], all: bool, engine: Option<&str>) -> Result<()> {
+    if names.is_empty() && !all {
+        anyhow::bail!(
+            "Specify plugins to pin, or --all.\n\n\
+            See available plugins with: unrealpm fab list"
+        );
+    }
+
+    let plugins = discover(project_dir, engine)?;
+
+    for name in names {
+        if !plugins.iter().any(|p| &p.name == name) {
+            anyhow::bail!(
+                "Fab plugin '{}' not found in the engine or Plugins/.\n\n\
+                See available plugins with: unrealpm fab list",
+                name
+            );
+        }
+    }
+
+    let mut lockfile = Lockfile::load()?.unwrap_or_default();
+    let mut pinned = 0;
+
+    for plugin in plugins.iter().filter(|p| all || names.contains(&p.name)) {
+        if let Some(locked) = lockfile.get_package(&plugin.name) {
+            if locked.source.is_none() {
+                println!(
+                    "  ⚠ Skipping {} - already installed from the registry",
+                    plugin.name
+                );
+                continue;
+            }
+        }
+
+        let tree_hash = hash_directory(&plugin.path)?;
+        lockfile.update_package(plugin.name.clone(), plugin.version.clone(), tree_hash, None);
+        lockfile.set_source(&plugin.name, Some(plugin.source()));
+        println!(
+            "  ✓ Pinned {}@{} ({})",
+            plugin.name,
+            plugin.version,
+            plugin.source()
+        );
+        pinned += 1;
+    }
+
+    lockfile.save()?;
+    println!();
+    println!("✓ Pinned {} Fab plugin(s) in unrealpm.lock", pinned);
+    Ok(())
+}
+
+fn unpin(name: &str) -> Result<()> {
+    let mut lockfile = Lockfile::load()?
+        .ok_or_else(|| anyhow::anyhow!("No lockfile found (unrealpm.lock). Nothing to unpin."))?;
+
+    match lockfile.get_package(name) {
+        Some(locked) if locked.is_externally_managed() => {}
+        Some(_) => anyhow::bail!(
+            "'{}' is not a Fab plugin. Use `unrealpm uninstall {}` instead.",
+            name,
+            name
+        ),
+        None => anyhow::bail!("'{}' is not pinned in unrealpm.lock", name),
+    }
+
+    lockfile.remove_package(name);
+    lockfile.save()?;
+    println!("✓ Unpinned {}", name);
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Info command - show a package's details, or render its README
+//!
+//! `unrealpm info <package>[@version]` summarizes one version from the
+//! registry. With `--readme` it renders the README stored at publish time as
+//! Markdown, through `$PAGER` (or `less`) when stdout is a terminal. With
+//! `--files` it lists the files in the version's tarball, read from the
+//! cache (or downloaded) without extracting it.
+
+use anyhow::Result;
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+use unrealpm::{outln, Config, PackageVersion, RegistryClient};
+
+use crate::commands::versions::{compare_versions, engines, summary_from_metadata};
+
+/// Widest the README is wrapped to, even on wide terminals
+const MAX_README_WIDTH: usize = 100;
+
+pub fn run(package: &str, readme: bool, no_pager: bool, files: bool) -> Result<()> {
+    let (name, version) = unrealpm::scope::split_spec(package);
+
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+    let metadata = registry.get_package(name)?;
+
+    let selected: Option<&PackageVersion> = match version {
+        Some(version) => metadata.versions.iter().find(|v| v.version == version),
+        None => metadata
+            .versions
+            .iter()
+            .max_by(|a, b| compare_versions(&a.version, &b.version)),
+    };
+    let Some(selected) = selected else {
+        anyhow::bail!(
+            "{} has no version {}\n\n\
+            • List published versions: unrealpm versions {}",
+            name,
+            version.unwrap_or("(none published)"),
+            name
+        );
+    };
+
+    if readme {
+        let Some(text) = registry.get_readme(name, &selected.version)? else {
+            anyhow::bail!(
+                "{}@{} was published without a README",
+                name,
+                selected.version
+            );
+        };
+        let rendered = unrealpm::markdown::render(&text, readme_width());
+        return page(&rendered, no_pager);
+    }
+
+    if files {
+        let tarball = registry.fetch_tarball(name, &selected.version, &selected.checksum)?;
+        let entries = unrealpm::list_tarball_entries(&tarball)?;
+        let total: u64 = entries.iter().map(|entry| entry.size).sum();
+
+        outln!(
+            "{}@{}: {} file{}, {}",
+            name,
+            selected.version,
+            entries.len(),
+            if entries.len() == 1 { "" } else { "s" },
+            unrealpm::disk::format_bytes(total)
+        );
+        for entry in &entries {
+            outln!(
+                "  {:>12}  {}",
+                unrealpm::disk::format_bytes(entry.size),
+                entry.path.display()
+            );
+        }
+        return Ok(());
+    }
+
+    outln!("{}@{}", name, selected.version);
+    if let Some(description) = &metadata.description {
+        outln!("  {}", description);
+    }
+    outln!();
+
+    let summary = summary_from_metadata(selected);
+    outln!("  Versions:     {} published", metadata.versions.len());
+    outln!("  Engines:      {}", engines(&summary));
+    outln!("  Type:         {}", summary.package_type);
+    outln!(
+        "  Signed:       {}",
+        if summary.signed { "yes" } else { "no" }
+    );
+    if !metadata.categories.is_empty() {
+        outln!("  Categories:   {}", metadata.categories.join(", "));
+    }
+    if !metadata.keywords.is_empty() {
+        outln!("  Keywords:     {}", metadata.keywords.join(", "));
+    }
+    if let Some(modules) = selected.modules.as_ref().filter(|m| !m.is_empty()) {
+        outln!("  Modules:      {}", modules.join(", "));
+    }
+    match selected.dependencies.as_ref().filter(|d| !d.is_empty()) {
+        Some(dependencies) => {
+            outln!("  Dependencies:");
+            for dependency in dependencies {
+                outln!("    {} {}", dependency.name, dependency.version);
+            }
+        }
+        None => outln!("  Dependencies: none"),
+    }
+    outln!();
+    outln!(
+        "  • Read the README: unrealpm info {}@{} --readme",
+        name,
+        selected.version
+    );
+
+    Ok(())
+}
+
+/// Column to wrap the README at: `$COLUMNS`, capped for readability
+fn readme_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+        .unwrap_or(80)
+        .min(MAX_README_WIDTH)
+}
+
+/// Show `text` through a pager when stdout is an interactive terminal
+///
+/// Uses `$PAGER`, or `less -FRX` so a short README prints without waiting
+/// for a keypress. Falls back to printing if the pager can't be started.
+fn page(text: &str, no_pager: bool) -> Result<()> {
+    let interactive = std::io::stdout().is_terminal() && !unrealpm::output::is_plain();
+    if !no_pager && interactive {
+        let pager = std::env::var("PAGER")
+            .ok()
+            .filter(|pager| !pager.trim().is_empty())
+            .unwrap_or_else(|| "less -FRX".to_string());
+        let mut words = pager.split_whitespace();
+        if let Some(program) = words.next() {
+            let child = Command::new(program)
+                .args(words)
+                .stdin(Stdio::piped())
+                .spawn();
+            if let Ok(mut child) = child {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // The user quitting the pager early closes the pipe
+                    let _ = writeln!(stdin, "{}", text);
+                }
+                child.wait()?;
+                return Ok(());
+            }
+        }
+    }
+
+    for line in text.lines() {
+        outln!("{}", line);
+    }
+    Ok(())
+}
@@ -1,28 +1,45 @@
 use anyhow::Result;
 use std::env;
-use unrealpm::{Manifest, UProject};
+use unrealpm::{Manifest, ManifestFormat, ProjectSettings, UPlugin, UProject};
 
-pub fn run() -> Result<()> {
+pub fn run(uproject: Option<String>, toml: bool) -> Result<()> {
     let current_dir = env::current_dir()?;
 
-    // Check if unrealpm.json already exists
+    // Check if a manifest already exists
     if Manifest::exists(&current_dir) {
-        println!("✓ unrealpm.json already exists in this directory");
+        let file_name = Manifest::file_name(&current_dir);
+        println!("✓ {} already exists in this directory", file_name);
         println!();
-        println!("To reinitialize, delete unrealpm.json and run 'unrealpm init' again.");
+        println!(
+            "To reinitialize, delete {} and run 'unrealpm init' again.",
+            file_name
+        );
         return Ok(());
     }
+    let format = if toml {
+        ManifestFormat::Toml
+    } else {
+        ManifestFormat::Json
+    };
 
     println!("Initializing UnrealPM project...");
     println!();
 
     // Try to find .uproject file
-    let uproject_path = match UProject::find(&current_dir) {
+    let uproject_path = match UProject::select(&current_dir, uproject.as_deref()) {
         Ok(path) => {
             let project_name = UProject::name(&path).unwrap_or_else(|| "UnrealProject".to_string());
             println!("✓ Found Unreal project: {}", project_name);
             Some(path)
         }
+        Err(e @ unrealpm::Error::MultipleUProjectFiles(_)) => return Err(e.into()),
+        Err(unrealpm::Error::InvalidManifest(_)) => {
+            anyhow::bail!(
+                "'{}' not found in the current directory",
+                uproject.unwrap_or_default()
+            );
+        }
+        Err(_) if unrealpm::is_standalone_plugin(&current_dir) => None,
         Err(_) => {
             println!("⚠ No .uproject file found in current directory");
             println!(
@@ -34,6 +51,27 @@ pub fn run() -> Result<()> {
 
     // Create manifest
     let mut manifest = Manifest::new();
+    if uproject.is_some() {
+        manifest.project = Some(ProjectSettings { uproject });
+    }
+
+    // A plugin developed without a project describes itself in its .uplugin
+    if uproject_path.is_none() {
+        if let Ok(path) = UPlugin::find(&current_dir) {
+            let plugin_name = UPlugin::name(&path).unwrap_or_else(|| "Plugin".to_string());
+            println!("✓ Found standalone plugin: {}", plugin_name);
+            println!(
+                "  Dependencies will be installed into {}/Plugins/",
+                unrealpm::STANDALONE_DEPS_DIR
+            );
+            if let Ok(uplugin) = UPlugin::load(&path) {
+                manifest.name = Some(plugin_name);
+                manifest.version = Some(uplugin.version_name);
+                manifest.description = uplugin.description;
+                manifest.engine_version = uplugin.engine_version;
+            }
+        }
+    }
 
     // If we found a .uproject, extract some info from it
     if let Some(path) = uproject_path {
@@ -50,10 +88,10 @@ pub fn run() -> Result<()> {
     }
 
     // Save the manifest
-    manifest.save(&current_dir)?;
+    manifest.save_as(&current_dir, format)?;
 
     println!();
-    println!("✓ Created unrealpm.json");
+    println!("✓ Created {}", format.file_name());
     println!();
     println!("Next steps:");
     println!("  • Add dependencies: unrealpm install <package>");
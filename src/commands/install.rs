@@ -1,743 +1,2697 @@
 use anyhow::Result;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::collections::{HashMap, HashSet};
 use std::env;
-use std::sync::Arc;
+use std::io::IsTerminal;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use unrealpm::compat::{check_engine_plugin_conflicts, check_module_collisions};
+use unrealpm::config::VerificationConfig;
+use unrealpm::integrity::{enforce_pins, pin_mismatch, report_incident, Incident, MismatchLayer};
+use unrealpm::link::{self, LinkedPlugin};
+use unrealpm::metrics::{format_duration, InstallHistory};
+use unrealpm::pipeline::{run_concurrent, TaskState};
+use unrealpm::trust::KeyCheck;
 use unrealpm::{
-    find_matching_version, install_package_cas, is_package_in_store, resolve_dependencies,
-    verify_checksum, verify_signature, Config, Lockfile, Manifest, PrebuiltBinary,
-    ProgressCallback, RegistryClient, ResolverConfig,
+    calculate_checksum, find_matching_version, find_plugin_dir, install_order, install_package_cas,
+    is_package_in_store, read_tarball_metadata, read_tarball_uplugin, resolve_dependencies,
+    select_artifacts, validate_tarball_entries, verify_signature, ArtifactSelection, ArtifactSize,
+    Config, Event, EventSink, InstallLayout, InstallMode, LockChangeKind, Lockfile, Manifest,
+    PackageVersion, Phase, PrebuiltBinary, RegistryClient, ResolutionCache, ResolvedPackage,
+    ResolverConfig, StatusFile, Timings, UPlugin,
 };
+use unrealpm::{eoutln, outln};
 
-/// Create an indicatif-based progress callback for CLI display
-fn create_spinner_callback() -> ProgressCallback {
-    let spinner = Arc::new(std::sync::Mutex::new(ProgressBar::new_spinner()));
-    {
-        let s = spinner.lock().unwrap();
-        s.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg}")
-                .unwrap()
-                .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
-        );
-        s.enable_steady_tick(std::time::Duration::from_millis(80));
-    }
+/// Create an indicatif-based progress sink for CLI display
+fn create_spinner_sink() -> EventSink {
+    let spinner = Arc::new(std::sync::Mutex::new(unrealpm::output::spinner("")));
 
     let spinner_clone = spinner.clone();
-    Arc::new(move |msg: &str, current: u64, total: u64| {
+    Arc::new(move |event: &Event| {
+        // The command prints the outcome itself
+        if matches!(event, Event::Completed { .. } | Event::Failed { .. }) {
+            return;
+        }
         let s = spinner_clone.lock().unwrap();
+        let (current, total) = event.progress();
         if current >= total && total > 0 {
-            s.finish_with_message(format!("✓ {}", msg));
+            unrealpm::output::finish_spinner(&s, format!("✓ {}", event.message()));
         } else {
-            s.set_message(msg.to_string());
+            s.set_message(event.message());
         }
     })
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn run(
-    package: Option<String>,
+    packages: Vec<String>,
+    features: Vec<String>,
+    local_path: Option<String>,
+    tarball: Option<String>,
     force: bool,
     engine_version_override: Option<String>,
     prefer_binary: bool,
     source_only: bool,
     binary_only: bool,
+    source_only_files: bool,
     dry_run: bool,
     offline: bool,
+    unmanaged: UnmanagedPolicy,
+    assume_yes: bool,
     verbose_resolve: bool,
     max_depth: Option<usize>,
     resolve_timeout: Option<u64>,
+    no_resolve_cache: bool,
+    production: bool,
+    lockfile_mode: LockfileMode,
+    workspace: bool,
+    jobs: Option<usize>,
+    show_timings: bool,
 ) -> Result<()> {
     let current_dir = env::current_dir()?;
 
-    // Offline mode: install from lockfile and cache only
-    if offline {
-        return install_offline(&current_dir, dry_run);
+    // Offline mode: resolve against the vendored mirror when the project has
+    // one, otherwise install from lockfile and cache only
+    if offline && !crate::commands::vendor::enable_offline(&current_dir, false)? {
+        install_offline(&current_dir, dry_run, production)?;
+        if !dry_run {
+            write_status_file(&current_dir, "install");
+            crate::commands::vcs_ignore::sync_configured(&current_dir);
+        }
+        return Ok(());
     }
 
-    // Determine installation mode
-    let install_mode = if binary_only {
-        InstallMode::BinaryOnly
-    } else if source_only {
-        InstallMode::SourceOnly
-    } else if prefer_binary {
-        InstallMode::PreferBinary
-    } else {
-        InstallMode::PreferSource
-    };
-
     // Build resolver config from CLI args and loaded config
     let loaded_config = Config::load()?;
+    let jobs = jobs.unwrap_or(loaded_config.network.max_parallel_downloads);
+
+    // Determine installation mode: flags override the project/user config
+    let install_mode = InstallMode::from_flags(prefer_binary, source_only, binary_only)
+        .unwrap_or_else(|| loaded_config.install_mode(&current_dir));
+    // Workspace members share one install pass, so they always get copies;
+    // partial installs are extracted into Plugins/ and never use the store
+    let layout = if workspace || source_only_files {
+        InstallLayout::Copy
+    } else {
+        install_layout(
+            &current_dir,
+            &loaded_config,
+            engine_version_override.as_deref(),
+        )
+    };
+    unrealpm::installer::set_store_layout(layout == InstallLayout::Store);
+    unrealpm::installer::set_source_files_only(source_only_files);
+    unrealpm::saved_plugins::set_keep(loaded_config.saved_versions(&current_dir));
+    if source_only_files {
+        outln!("Installing without Content/ folders (--source-only-files)");
+        outln!("  unrealpm.lock marks these installs as partial; run `unrealpm install` for complete plugins");
+        outln!();
+    }
     let resolver_config = ResolverConfig {
         max_depth: max_depth.unwrap_or(loaded_config.resolver.max_depth),
         verbose_conflicts: verbose_resolve || loaded_config.resolver.verbose_conflicts,
         resolution_timeout_seconds: resolve_timeout
             .unwrap_or(loaded_config.resolver.resolution_timeout_seconds),
+        features: HashMap::new(),
+        remote: loaded_config.resolver.remote,
+    };
+
+    let mut timings = Timings::new();
+
+    let local_source = match (local_path, tarball) {
+        (Some(path), _) => Some(LocalSource::Directory(path.into())),
+        (None, Some(file)) => Some(LocalSource::Tarball(file.into())),
+        (None, None) => None,
     };
 
-    match package {
-        Some(pkg) => install_single_package(
-            &pkg,
+    let result = match (local_source, packages) {
+        _ if workspace => crate::commands::workspace::install(
+            "install",
+            force,
+            engine_version_override,
+            install_mode,
+            dry_run,
+            unmanaged,
+            &resolver_config,
+            jobs,
+            &mut timings,
+        ),
+        (Some(source), _) => install_local(&current_dir, source, dry_run, &mut timings),
+        (None, packages) if !packages.is_empty() => install_packages(
+            &packages,
+            &features,
             &current_dir,
             force,
             engine_version_override,
             install_mode,
             dry_run,
+            unmanaged,
+            assume_yes,
             &resolver_config,
+            &mut timings,
         ),
-        None => install_all_dependencies(
+        (None, _) => install_all_dependencies(
             &current_dir,
             force,
             engine_version_override,
             install_mode,
             dry_run,
+            unmanaged,
+            assume_yes,
             &resolver_config,
+            !no_resolve_cache,
+            production,
+            lockfile_mode,
+            jobs,
+            &mut timings,
         ),
+    };
+
+    // Workspace installs refresh each member themselves
+    if result.is_ok() && !dry_run && !workspace {
+        sync_store_layout(&current_dir, layout);
+        write_status_file(&current_dir, "install");
+        crate::commands::vcs_ignore::sync_configured(&current_dir);
+        print_standalone_hint(&current_dir);
+    }
+    if !dry_run {
+        save_install_history(&timings);
+    }
+
+    if show_timings {
+        outln!("{}", timings.report());
+        outln!();
     }
-}
 
-#[derive(Debug, Clone, Copy)]
-enum InstallMode {
-    PreferSource, // Default: use source, ignore binaries
-    PreferBinary, // Try binary first, fall back to source
-    SourceOnly,   // Never use binaries
-    BinaryOnly,   // Require binary, fail if not available
+    result
 }
 
-fn install_single_package(
-    package_spec: &str,
+#[allow(clippy::too_many_arguments)]
+fn install_packages(
+    package_specs: &[String],
+    requested_features: &[String],
     project_dir: &std::path::Path,
     force: bool,
     engine_version_override: Option<String>,
     install_mode: InstallMode,
     dry_run: bool,
+    unmanaged: UnmanagedPolicy,
+    assume_yes: bool,
     resolver_config: &ResolverConfig,
+    timings: &mut Timings,
 ) -> Result<()> {
-    // Parse package spec (e.g., "awesome-plugin" or "awesome-plugin@^1.2.0")
-    let (package_name, version_constraint) = if let Some(pos) = package_spec.find('@') {
-        let (name, version) = package_spec.split_at(pos);
-        (name.to_string(), version[1..].to_string()) // Skip the '@'
-    } else {
-        (package_spec.to_string(), "*".to_string()) // Default to any version
-    };
+    // Parse package specs (e.g., "awesome-plugin" or "awesome-plugin@^1.2.0")
+    let requested: Vec<(String, String)> = package_specs
+        .iter()
+        .map(|spec| match unrealpm::scope::split_spec(spec) {
+            (name, Some(version)) => (name.to_string(), version.to_string()),
+            (name, None) => (name.to_string(), "*".to_string()), // Default to any version
+        })
+        .collect();
+    let specs = requested
+        .iter()
+        .map(|(name, constraint)| format!("{}@{}", name, constraint))
+        .collect::<Vec<_>>()
+        .join(", ");
 
     if dry_run {
-        println!(
-            "[DRY RUN] Would install {}@{}...",
-            package_name, version_constraint
-        );
+        outln!("[DRY RUN] Would install {}...", specs);
     } else {
-        println!("Installing {}@{}...", package_name, version_constraint);
+        outln!("Installing {}...", specs);
     }
-    println!();
+    outln!();
+
+    let config_for_registry = Config::load()?;
 
     // Load manifest to get engine version (or use override)
     let manifest = Manifest::load(project_dir).unwrap_or_default();
+    let mapped_engine = manifest
+        .engine_version
+        .as_deref()
+        .map(|engine| config_for_registry.logical_engine_version(engine));
     let engine_version = if let Some(ref override_version) = engine_version_override {
-        println!("  Engine version: {} (overridden)", override_version);
+        outln!("  Engine version: {} (overridden)", override_version);
         Some(override_version.as_str())
     } else {
-        let detected = manifest.engine_version.as_deref();
-        if let Some(engine) = detected {
-            println!("  Engine version: {}", engine);
+        print_engine_version(
+            "  ",
+            manifest.engine_version.as_deref(),
+            mapped_engine.as_deref(),
+        );
+        mapped_engine.as_deref()
+    };
+
+    // Features already enabled in the manifest stay enabled
+    let mut features = manifest.dependency_features.clone();
+    for (name, feature) in assign_features(&requested, requested_features)? {
+        let enabled = features.entry(name).or_default();
+        if !enabled.contains(&feature) {
+            enabled.push(feature);
         }
-        detected
+    }
+    let resolver_config = &ResolverConfig {
+        features: features.clone(),
+        ..resolver_config.clone()
     };
 
     // Get registry client (uses HTTP if configured)
-    let config_for_registry = Config::load()?;
-    let registry = RegistryClient::from_config(&config_for_registry)?;
+    let registry = RegistryClient::from_config(&config_for_registry)?
+        .with_locked_registries(&Lockfile::load()?.unwrap_or_default(), &config_for_registry)?;
 
     // Get package metadata with spinner
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.blue} {msg}")
-            .unwrap()
-            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
-    );
-    spinner.set_message("Fetching package metadata...");
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    let spinner = unrealpm::output::spinner("Fetching package metadata...");
 
-    let metadata = registry.get_package(&package_name)?;
+    let resolve_start = Instant::now();
+    let metadata = requested
+        .iter()
+        .map(|(name, _)| registry.get_package(name))
+        .collect::<unrealpm::Result<Vec<_>>>()?;
     spinner.finish_and_clear();
 
-    // Find matching version
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.blue} {msg}")
-            .unwrap()
-            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
-    );
-    spinner.set_message("Resolving version...");
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    // Find matching versions
+    let spinner = unrealpm::output::spinner("Resolving version...");
 
-    let resolved_version =
-        find_matching_version(&metadata, &version_constraint, engine_version, force)?;
+    let mut resolved_versions = HashMap::new();
+    for ((name, constraint), metadata) in requested.iter().zip(&metadata) {
+        let version = find_matching_version(metadata, constraint, engine_version, force)?;
+        resolved_versions.insert(name.clone(), version);
+    }
 
     if force && engine_version.is_some() {
-        println!("  ⚠ WARNING: Force installing - engine compatibility not checked");
+        unrealpm::strict::warn("Force installing - engine compatibility not checked")?;
     }
-    spinner.finish_with_message(format!(
-        "✓ Resolved to version {}",
-        resolved_version.version
-    ));
+    let message = match requested.as_slice() {
+        [(name, _)] => format!("✓ Resolved to version {}", resolved_versions[name].version),
+        _ => format!(
+            "✓ Resolved {}",
+            installed_specs(&requested, &resolved_versions)
+        ),
+    };
+    unrealpm::output::finish_spinner(&spinner, message);
 
-    // Resolve transitive dependencies
-    let mut direct_deps = std::collections::HashMap::new();
-    direct_deps.insert(package_name.clone(), version_constraint.clone());
+    // Resolve the requested packages together so they constrain each other
+    let direct_deps: HashMap<String, String> = requested.iter().cloned().collect();
 
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.blue} {msg}")
-            .unwrap()
-            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
-    );
-    spinner.set_message("Resolving dependencies...");
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    let spinner = unrealpm::output::spinner("Resolving dependencies...");
 
-    let all_resolved = resolve_dependencies(
+    let mut all_resolved = resolve_dependencies(
         &direct_deps,
         &registry,
         engine_version,
         force,
         Some(resolver_config),
     )?;
+    timings.record(Phase::Resolve, resolve_start.elapsed());
+
+    // Features and shared dependencies can rule out the newest matching version
+    for ((name, _), metadata) in requested.iter().zip(&metadata) {
+        if let Some(pkg) = all_resolved.get(name) {
+            if pkg.version != resolved_versions[name].version {
+                let version = find_matching_version(
+                    metadata,
+                    &format!("={}", pkg.version),
+                    engine_version,
+                    force,
+                )?;
+                resolved_versions.insert(name.clone(), version);
+            }
+        }
+    }
 
-    let dep_count = all_resolved.len();
-    if dep_count > 1 {
-        spinner.finish_with_message(format!(
-            "✓ Resolved {} packages (including {} dependencies)",
-            dep_count,
-            dep_count - 1
-        ));
+    let dep_count = all_resolved.len().saturating_sub(requested.len());
+    if dep_count > 0 {
+        unrealpm::output::finish_spinner(
+            &spinner,
+            format!(
+                "✓ Resolved {} packages (including {} dependencies)",
+                all_resolved.len(),
+                dep_count
+            ),
+        );
     } else {
-        spinner.finish_with_message("✓ No additional dependencies");
+        unrealpm::output::finish_spinner(&spinner, "✓ No additional dependencies");
+    }
+    warn_module_collisions(&registry, &all_resolved)?;
+    warn_engine_plugin_conflicts(
+        &config_for_registry,
+        &registry,
+        &all_resolved,
+        engine_version_override
+            .as_deref()
+            .or(manifest.engine_version.as_deref()),
+        &manifest.engine_overrides,
+    )?;
+
+    // Install dependencies first (before the requested packages)
+    let existing_lockfile = Lockfile::load()?;
+    let had_lockfile = existing_lockfile.is_some();
+    let mut lockfile = existing_lockfile.unwrap_or_default();
+    let selection = artifact_selection(
+        install_mode,
+        engine_version,
+        &config_for_registry.build_configuration(project_dir),
+    );
+    select_artifacts(&mut all_resolved, &registry, &selection, Some(&lockfile))?;
+    check_integrity_pins(&manifest, &mut all_resolved, &registry.location())?;
+
+    let plan = InstallPlan::new(&lockfile, &all_resolved, &[]);
+    let threshold = had_lockfile
+        .then(|| config_for_registry.confirm_threshold(project_dir))
+        .flatten();
+    if !plan.confirm(&registry, &all_resolved, threshold, assume_yes || dry_run)? {
+        return Ok(());
     }
 
-    // Install dependencies first (before the main package)
-    let mut lockfile = Lockfile::load()?.unwrap_or_default();
+    let mut targets: Vec<(&String, &ResolvedPackage)> = all_resolved.iter().collect();
+    targets.sort_by(|a, b| a.0.cmp(b.0));
+    let adopted = claim_install_targets(project_dir, &targets, &mut lockfile, unmanaged, dry_run)?;
 
-    for (dep_name, resolved_pkg) in &all_resolved {
-        if dep_name == &package_name {
-            continue; // Skip the main package, we'll install it with full verification below
+    let order = install_order(&all_resolved);
+    let mut signatures = SignatureSummary::default();
+    for dep_name in &order {
+        if direct_deps.contains_key(dep_name) {
+            continue; // Requested packages are installed with full verification below
+        }
+        if adopted.contains(dep_name) {
+            continue;
         }
+        let resolved_pkg = &all_resolved[dep_name];
 
-        // Check if already installed
+        // Check if already installed; a partial copy is only enough for
+        // another partial install
         if let Some(locked) = lockfile.get_package(dep_name) {
-            if locked.version == resolved_pkg.version {
-                println!(
+            let complete_enough = !locked.partial || unrealpm::installer::source_files_only();
+            if locked.version == resolved_pkg.version && complete_enough {
+                outln!(
                     "  ✓ {} {} (already installed)",
-                    dep_name, resolved_pkg.version
+                    dep_name,
+                    resolved_pkg.version
                 );
                 continue;
             }
         }
 
-        println!(
+        outln!(
             "  Installing dependency {}@{}...",
-            dep_name, resolved_pkg.version
+            dep_name,
+            resolved_pkg.version
         );
 
         // Download if HTTP registry
         let dep_tarball = match &registry {
-            unrealpm::RegistryClient::Http(http_client) => http_client.download_if_needed(
-                dep_name,
-                &resolved_pkg.version,
-                &resolved_pkg.checksum,
-            )?,
+            unrealpm::RegistryClient::Http(http_client) => {
+                let cached = http_client
+                    .get_tarball_path(dep_name, resolved_pkg.artifact_id())
+                    .exists();
+                time_download(timings, dep_name, cached, || {
+                    http_client.download_if_needed(
+                        dep_name,
+                        resolved_pkg.artifact_id(),
+                        resolved_pkg.artifact_checksum(),
+                    )
+                })?
+            }
             unrealpm::RegistryClient::File(_) => {
-                registry.get_tarball_path(dep_name, &resolved_pkg.version)
+                registry.get_tarball_path(dep_name, resolved_pkg.artifact_id())
             }
         };
 
         // Verify checksum
-        verify_checksum(&dep_tarball, &resolved_pkg.checksum, None)?;
-
-        // Install using CAS (Content-Addressable Storage)
-        install_package_cas(
-            &dep_tarball,
-            &project_dir.to_path_buf(),
+        timings.time_package(dep_name, Phase::Verify, || {
+            registry.verify_tarball(
+                dep_name,
+                resolved_pkg.artifact_id(),
+                &dep_tarball,
+                resolved_pkg.artifact_checksum(),
+                None,
+            )
+        })?;
+        let signature = check_package_signature(
+            &registry,
+            &config_for_registry.verification,
             dep_name,
-            &resolved_pkg.checksum,
-            None,
+            &resolved_pkg.version,
+            registry_version(&registry, dep_name, &resolved_pkg.version)?.as_ref(),
+            &dep_tarball,
         )?;
+        if let Some(warning) = signature.warning(dep_name, &resolved_pkg.version) {
+            unrealpm::strict::warn(warning)?;
+        }
+        signatures.record(dep_name, &resolved_pkg.version, signature);
+        let license = check_license(project_dir, dep_name, &resolved_pkg.version, &dep_tarball)?;
+
+        // Install using CAS (Content-Addressable Storage)
+        timings
+            .store_cache
+            .record(is_package_in_store(resolved_pkg.artifact_checksum()).unwrap_or(false));
+        let dep_path = timings.time_package(dep_name, Phase::Extract, || {
+            install_package_cas(
+                &dep_tarball,
+                &project_dir.to_path_buf(),
+                dep_name,
+                resolved_pkg.artifact_checksum(),
+                None,
+            )
+        })?;
+        let patched_hash = apply_manifest_patches(project_dir, dep_name, &dep_path)?;
 
         // Update lockfile
         lockfile.update_package(
             dep_name.clone(),
             resolved_pkg.version.clone(),
             resolved_pkg.checksum.clone(),
-            resolved_pkg.dependencies.clone(),
+            lockfile_dependencies(resolved_pkg, &dep_tarball),
+        );
+        lockfile.set_patched_hash(dep_name, patched_hash);
+        lockfile.set_binary(dep_name, resolved_pkg.binary.clone());
+        lockfile.set_partial(dep_name, unrealpm::installer::source_files_only());
+        lockfile.set_registry(dep_name, registry.lock_source(dep_name));
+        lockfile.set_license(dep_name, license);
+
+        outln!("  ✓ Installed {}", dep_name);
+    }
+
+    // Requested packages in dependency order, so one another needs goes first
+    for name in order.iter().filter(|name| direct_deps.contains_key(*name)) {
+        let resolved_version = &resolved_versions[name];
+
+        // An adopted copy stays as it is; only the manifest and lockfile change
+        if adopted.contains(name) {
+            outln!(
+                "  ✓ {}@{} is now managed by UnrealPM",
+                name,
+                resolved_version.version
+            );
+            continue;
+        }
+        if requested.len() > 1 {
+            outln!();
+            outln!("  Installing {}@{}...", name, resolved_version.version);
+        }
+
+        let Some(installed) = install_requested_package(
+            name,
+            resolved_version,
+            &registry,
+            &selection,
+            project_dir,
+            engine_version,
+            dry_run,
+            timings,
+        )?
+        else {
+            continue;
+        };
+
+        // Dependencies were added to the lockfile above
+        let main_pkg = &all_resolved[name];
+        lockfile.update_package(
+            name.clone(),
+            main_pkg.version.clone(),
+            main_pkg.checksum.clone(),
+            main_pkg.dependencies.clone(),
         );
+        lockfile.set_patched_hash(name, installed.patched_hash);
+        lockfile.set_binary(name, installed.binary);
+        lockfile.set_partial(name, unrealpm::installer::source_files_only());
+        lockfile.set_registry(name, registry.lock_source(name));
+        lockfile.set_license(name, installed.license);
+        signatures.record(name, &resolved_version.version, installed.signature);
+    }
 
-        println!("  ✓ Installed {}", dep_name);
+    let installed = installed_specs(&requested, &resolved_versions);
+    if dry_run {
+        outln!("  [DRY RUN] Would update manifest (unrealpm.json)");
+        outln!("  [DRY RUN] Would update lockfile (unrealpm.lock)");
+        outln!();
+        outln!("[DRY RUN] Would successfully install {}", installed);
+        outln!();
+        return Ok(());
     }
 
+    // Update manifest once for every requested package (preserve engine
+    // version from earlier load)
+    outln!("  Updating manifest...");
+    let mut manifest = Manifest::load(project_dir).unwrap_or_default();
+    manifest.dependencies.extend(direct_deps);
+    manifest.dependency_features = features.clone();
+    manifest.save(project_dir)?;
+
+    outln!("  Updating lockfile...");
+    lockfile.set_features(&features);
+    lockfile.set_engine(engine_version);
+    lockfile.save()?;
+    outln!("  ✓ Lockfile updated");
+
+    outln!();
+    signatures.print();
+    outln!("✓ Successfully installed {}", installed);
+    outln!();
+
+    Ok(())
+}
+
+/// `name@version` of each requested package, in the order given
+fn installed_specs(
+    requested: &[(String, String)],
+    resolved_versions: &HashMap<String, unrealpm::PackageVersion>,
+) -> String {
+    requested
+        .iter()
+        .map(|(name, _)| format!("{}@{}", name, resolved_versions[name].version))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Pair each `--features` value with the requested package it enables
+///
+/// With one package, plain feature names apply to it; with several, each
+/// must be written as `<package>/<feature>`.
+fn assign_features(
+    requested: &[(String, String)],
+    features: &[String],
+) -> Result<Vec<(String, String)>> {
+    features
+        .iter()
+        .map(|feature| match (feature.split_once('/'), requested) {
+            (Some((name, feature)), _) => {
+                if !requested.iter().any(|(requested, _)| requested == name) {
+                    anyhow::bail!("--features {}/{}: {} is not being installed", name, feature, name);
+                }
+                Ok((name.to_string(), feature.to_string()))
+            }
+            (None, [(name, _)]) => Ok((name.clone(), feature.clone())),
+            (None, _) => anyhow::bail!(
+                "--features {} is ambiguous when installing several packages; write it as <package>/{}",
+                feature,
+                feature
+            ),
+        })
+        .collect()
+}
+
+/// Download, verify, and install a package named on the command line, then
+/// auto-build it if configured
+///
+/// Returns what to record in the lockfile, or `None` for a dry run.
+#[allow(clippy::too_many_arguments)]
+fn install_requested_package(
+    package_name: &str,
+    resolved_version: &unrealpm::PackageVersion,
+    registry: &RegistryClient,
+    selection: &ArtifactSelection,
+    project_dir: &std::path::Path,
+    engine_version: Option<&str>,
+    dry_run: bool,
+    timings: &mut Timings,
+) -> Result<Option<InstalledPackage>> {
     // Determine which tarball to use (binary or source)
-    let (tarball_path, checksum, install_type) = select_installation_source(
-        &resolved_version,
-        &registry,
-        &package_name,
-        engine_version,
-        install_mode,
-    )?;
+    let (tarball_path, checksum, install_type, binary) =
+        select_installation_source(resolved_version, registry, package_name, selection)?;
 
     if let Some(ref itype) = install_type {
-        println!("  Using: {}", itype);
+        outln!("  Using: {}", itype);
     }
 
     if dry_run {
         // Dry run: show what would happen without actually doing it
         if resolved_version.public_key.is_some() {
-            println!("  [DRY RUN] Would verify signature");
+            outln!("  [DRY RUN] Would verify signature");
         }
-        println!("  [DRY RUN] Would verify checksum: {}", checksum);
-        println!(
+        outln!("  [DRY RUN] Would verify checksum: {}", checksum);
+        outln!(
             "  [DRY RUN] Would install to: {}/Plugins/{}",
             project_dir.display(),
             package_name
         );
+        if let Some(patch_dir) = Manifest::load(project_dir)
+            .ok()
+            .and_then(|m| m.patches.get(package_name).cloned())
+        {
+            outln!("  [DRY RUN] Would apply patches from {}", patch_dir);
+        }
 
         // Check if auto-build would be triggered
         let config = Config::load()?;
         let was_source_install = install_type.as_ref().is_none_or(|t| t.contains("source"));
 
         if config.build.auto_build_on_install && was_source_install && engine_version.is_some() {
-            println!(
+            outln!(
                 "  [DRY RUN] Would auto-build binaries for {}",
                 unrealpm::detect_platform()
             );
         }
 
-        println!("  [DRY RUN] Would update manifest (unrealpm.json)");
-        println!("  [DRY RUN] Would update lockfile (unrealpm.lock)");
-        println!();
-        println!(
-            "[DRY RUN] Would successfully install {}@{}",
-            package_name, resolved_version.version
-        );
-        println!();
-        return Ok(());
+        return Ok(None);
     }
 
     // Download if using HTTP registry (cache-first) - BEFORE signature verification
-    let tarball_path = match &registry {
+    let tarball_path = match registry {
         unrealpm::RegistryClient::Http(http_client) => {
-            http_client.download_if_needed(&package_name, &resolved_version.version, &checksum)?
+            let cached = http_client
+                .get_tarball_path(package_name, &resolved_version.version)
+                .exists();
+            time_download(timings, package_name, cached, || match &binary {
+                Some(binary) => {
+                    http_client.download_if_needed(package_name, &binary.tarball, &checksum)
+                }
+                None => http_client.download_from(
+                    package_name,
+                    &resolved_version.version,
+                    &checksum,
+                    &resolved_version.tarball,
+                ),
+            })?
         }
         unrealpm::RegistryClient::File(_) => tarball_path,
     };
 
     // Load config for verification settings
     let config = Config::load()?;
+    let verify_start = Instant::now();
 
     // Verify signature (if package is signed)
-    if let Some(public_key) = &resolved_version.public_key {
-        println!("  Verifying signature...");
-
-        // Download signature from registry (or get local path for file registry)
-        match registry.download_signature(&package_name, &resolved_version.version) {
-            Ok(sig_path) => {
-                // Read tarball and signature
-                let tarball_bytes = std::fs::read(&tarball_path)?;
-                let signature_bytes = std::fs::read(&sig_path)?;
-
-                // Verify
-                let is_valid = verify_signature(&tarball_bytes, &signature_bytes, public_key)?;
-
-                if !is_valid {
-                    if config.verification.strict_verification {
-                        anyhow::bail!(
-                            "Signature verification FAILED for {}@{}\n\n\
-                            The package signature is invalid. This could mean:\n\
-                            • The package has been tampered with\n\
-                            • The signature file is corrupted\n\
-                            • The public key doesn't match\n\n\
-                            For your security, installation has been aborted.\n\
-                            If you trust this package, you can:\n\
-                            • Contact the package author\n\
-                            • Disable strict verification: unrealpm config set verification.strict_verification false",
-                            package_name,
-                            resolved_version.version
-                        );
-                    } else {
-                        println!(
-                            "  ⚠ WARNING: Signature verification failed for {}@{} - continuing anyway (strict_verification=false)",
-                            package_name, resolved_version.version
-                        );
-                    }
-                } else {
-                    println!(
-                        "  ✓ Signature verified (publisher: {}...)",
-                        &public_key[..16]
-                    );
-                }
-            }
-            Err(_) => {
-                // Signature download failed or file missing
-                if config.verification.require_signatures {
-                    anyhow::bail!(
-                        "Signature verification required but signature could not be retrieved for {}@{}\n\n\
-                        This package is marked as signed but the signature file is not available.\n\n\
-                        Solutions:\n\
-                        • Disable signature requirement: unrealpm config set verification.require_signatures false\n\
-                        • Contact the package author to republish with a valid signature",
-                        package_name,
-                        resolved_version.version
-                    );
-                } else {
-                    println!("  ⚠ Signature not available (package marked as signed)");
-                }
+    if resolved_version.public_key.is_some() {
+        outln!("  Verifying signature...");
+    }
+    let signature = check_package_signature(
+        registry,
+        &config.verification,
+        package_name,
+        &resolved_version.version,
+        Some(resolved_version),
+        &tarball_path,
+    )?;
+    match signature {
+        SignatureCheck::Verified { pinned, rotated } => {
+            let public_key = resolved_version.public_key.as_deref().unwrap_or_default();
+            outln!(
+                "  ✓ Signature verified (publisher: {}...)",
+                public_key.get(..16).unwrap_or(public_key)
+            );
+            if rotated {
+                outln!("  ✓ Accepted rotated publisher key (endorsed by the pinned key)");
+            } else if pinned {
+                outln!("  ✓ Pinned publisher key (first use)");
             }
         }
-    } else {
-        // Package is not signed
-        if config.verification.require_signatures {
-            anyhow::bail!(
-                "Signature verification required but package '{}@{}' is not signed\n\n\
-                Solutions:\n\
-                • Disable signature requirement: unrealpm config set verification.require_signatures false\n\
-                • Request the package author to publish signed packages\n\
-                • Use a different package version that is signed",
-                package_name,
-                resolved_version.version
-            );
+        check => {
+            if let Some(warning) = check.warning(package_name, &resolved_version.version) {
+                unrealpm::strict::warn(warning)?;
+            }
         }
     }
 
     // Verify checksum with progress spinner
-    let progress = Some(create_spinner_callback());
-    verify_checksum(&tarball_path, &checksum, progress)?;
-
-    // Install package using CAS with progress spinner
-    let progress = Some(create_spinner_callback());
-    let installed_path = install_package_cas(
+    let progress = Some(create_spinner_sink());
+    registry.verify_tarball(
+        package_name,
+        &resolved_version.version,
         &tarball_path,
-        &project_dir.to_path_buf(),
-        &package_name,
         &checksum,
         progress,
     )?;
-    println!("  ✓ Installed to {}", installed_path.display());
+    timings.record_package(package_name, Phase::Verify, verify_start.elapsed());
+    let license = check_license(
+        project_dir,
+        package_name,
+        &resolved_version.version,
+        &tarball_path,
+    )?;
+
+    // Install package using CAS with progress spinner
+    let progress = Some(create_spinner_sink());
+    timings
+        .store_cache
+        .record(is_package_in_store(&checksum).unwrap_or(false));
+    let installed_path = timings.time_package(package_name, Phase::Extract, || {
+        install_package_cas(
+            &tarball_path,
+            &project_dir.to_path_buf(),
+            package_name,
+            &checksum,
+            progress,
+        )
+    })?;
+    outln!("  ✓ Installed to {}", installed_path.display());
+    let patched_hash = apply_manifest_patches(project_dir, package_name, &installed_path)?;
 
     // Check if we should auto-build binaries (config already loaded above)
     let was_source_install = install_type.as_ref().is_none_or(|t| t.contains("source"));
 
     if let Some(engine_ver) = engine_version {
         if config.build.auto_build_on_install && was_source_install {
-            println!();
-            println!("⚙ Auto-build enabled, building binaries...");
-            println!();
+            outln!();
+            outln!("⚙ Auto-build enabled, building binaries...");
+            if let Some(estimate) = InstallHistory::open_default()
+                .ok()
+                .and_then(|history| history.estimate(package_name, &[Phase::Build]))
+            {
+                outln!("  Previous builds took ~{}", format_duration(estimate));
+            }
+            outln!();
 
             let current_platform = unrealpm::detect_platform();
-            match crate::commands::build::build_for_platform(
-                &installed_path,
-                &package_name,
-                engine_ver,
-                &current_platform,
-                &config,
-            ) {
-                Ok(_) => println!("  ✓ Built for {}", current_platform),
+            let build_result = timings.time_package(package_name, Phase::Build, || {
+                crate::commands::build::build_for_platform(
+                    &installed_path,
+                    package_name,
+                    engine_ver,
+                    &current_platform,
+                    &config.build_configuration(project_dir),
+                    &config,
+                )
+            });
+            match build_result {
+                Ok(_) => outln!("  ✓ Built for {}", current_platform),
                 Err(e) => {
-                    eprintln!("  ✗ Build failed: {}", e);
-                    eprintln!("  Plugin installed as source-only");
+                    eoutln!("  ✗ Build failed: {}", e);
+                    eoutln!("  Plugin installed as source-only");
                 }
             }
-            println!();
+            outln!();
         }
     }
 
-    // Update manifest (preserve engine version from earlier load)
-    println!("  Updating manifest...");
-    let mut manifest = Manifest::load(project_dir).unwrap_or_default();
-    manifest
-        .dependencies
-        .insert(package_name.clone(), version_constraint.clone());
-    manifest.save(project_dir)?;
-
-    // Update lockfile with main package (dependencies already added earlier)
-    println!("  Updating lockfile...");
-    // Get the resolved info for the main package from all_resolved
-    if let Some(main_pkg) = all_resolved.get(&package_name) {
-        lockfile.update_package(
-            package_name.clone(),
-            main_pkg.version.clone(),
-            main_pkg.checksum.clone(),
-            main_pkg.dependencies.clone(),
-        );
-    }
-    lockfile.save()?;
-    println!("  ✓ Lockfile updated");
+    Ok(Some(InstalledPackage {
+        patched_hash,
+        binary,
+        license,
+        signature,
+    }))
+}
 
-    println!();
-    println!(
-        "✓ Successfully installed {}@{}",
-        package_name, resolved_version.version
-    );
-    println!();
+/// Lockfile details of a package [`install_requested_package`] installed
+struct InstalledPackage {
+    patched_hash: Option<String>,
+    binary: Option<PrebuiltBinary>,
+    license: Option<String>,
+    signature: SignatureCheck,
+}
 
-    Ok(())
+/// License of a verified package tarball, refused unless the project's
+/// `[policy] allowed_licenses` permits it
+pub fn check_license(
+    project_dir: &std::path::Path,
+    name: &str,
+    version: &str,
+    tarball_path: &std::path::Path,
+) -> Result<Option<String>> {
+    let license = unrealpm::license::from_tarball(tarball_path)?;
+    let allowed = Config::load()?.allowed_licenses(project_dir);
+    if allowed.is_empty() {
+        return Ok(license);
+    }
+    match &license {
+        Some(found) if unrealpm::license::is_allowed(found, &allowed) => Ok(license),
+        Some(found) => anyhow::bail!(
+            "{}@{} is licensed under {}, which the license policy does not allow\n\n\
+            Allowed licenses ([policy] allowed_licenses): {}",
+            name,
+            version,
+            found,
+            allowed.join(", ")
+        ),
+        None => anyhow::bail!(
+            "{}@{} does not declare a license, and a license policy is set\n\n\
+            Packages declare one with `license` in unrealpm.json, `License` in the\n\
+            .uplugin, or a LICENSE file. Allowed licenses: {}",
+            name,
+            version,
+            allowed.join(", ")
+        ),
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn install_all_dependencies(
     project_dir: &std::path::Path,
     force: bool,
     engine_version_override: Option<String>,
-    _install_mode: InstallMode,
+    install_mode: InstallMode,
     dry_run: bool,
+    unmanaged: UnmanagedPolicy,
+    assume_yes: bool,
     resolver_config: &ResolverConfig,
+    use_resolve_cache: bool,
+    production: bool,
+    lockfile_mode: LockfileMode,
+    jobs: usize,
+    timings: &mut Timings,
 ) -> Result<()> {
     if dry_run {
-        println!("[DRY RUN] Would install all dependencies from manifest...");
+        outln!("[DRY RUN] Would install all dependencies from manifest...");
     } else {
-        println!("Installing all dependencies from manifest...");
+        outln!("Installing all dependencies from manifest...");
     }
-    println!();
+    outln!();
 
     // Load manifest
     let manifest = Manifest::load(project_dir)?;
 
-    if manifest.dependencies.is_empty() {
-        println!("No dependencies to install.");
-        println!();
-        println!("Add dependencies with: unrealpm install <package>");
+    if manifest.dependencies.is_empty()
+        && manifest.path_dependencies.is_empty()
+        && manifest.dev_dependencies.is_empty()
+    {
+        outln!("No dependencies to install.");
+        outln!();
+        outln!("Add dependencies with: unrealpm install <package>");
         return Ok(());
     }
 
-    println!("Found {} direct dependencies", manifest.dependencies.len());
-    println!();
+    let direct = manifest.dependencies.len() + manifest.path_dependencies.len();
+    match manifest.dev_dependencies.len() {
+        0 => outln!("Found {} direct dependencies", direct),
+        dev if production => outln!(
+            "Found {} direct dependencies ({} dev dependencies skipped with --production)",
+            direct,
+            dev
+        ),
+        dev => outln!(
+            "Found {} direct dependencies and {} dev dependencies",
+            direct,
+            dev
+        ),
+    }
+    outln!();
+
+    // Path dependencies are linked, not resolved; their own dependencies
+    // come from the registry with the rest
+    let linked = inspect_path_dependencies(project_dir, &manifest)?;
 
     // Get registry client (uses HTTP if configured)
     let config_for_registry = Config::load()?;
-    let registry = RegistryClient::from_config(&config_for_registry)?;
+    let registry = RegistryClient::from_config(&config_for_registry)?
+        .with_locked_registries(&Lockfile::load()?.unwrap_or_default(), &config_for_registry)?;
 
     // Get engine version for filtering (or use override)
+    let mapped_engine = manifest
+        .engine_version
+        .as_deref()
+        .map(|engine| config_for_registry.logical_engine_version(engine));
     let engine_version = if let Some(ref override_version) = engine_version_override {
-        println!("Engine version: {} (overridden)", override_version);
+        outln!("Engine version: {} (overridden)", override_version);
         Some(override_version.as_str())
     } else {
-        let detected = manifest.engine_version.as_deref();
-        if let Some(engine) = detected {
-            println!("Engine version: {}", engine);
+        print_engine_version(
+            "",
+            manifest.engine_version.as_deref(),
+            mapped_engine.as_deref(),
+        );
+        mapped_engine.as_deref()
+    };
+
+    // Skip dependencies whose platform/engine conditions don't hold on this host
+    let platform = unrealpm::detect_platform();
+    let inactive = manifest.inactive_dependencies(&platform, engine_version);
+    for (name, conditions) in &inactive {
+        outln!("Skipping {} ({})", name, conditions.describe());
+    }
+    let mut dependencies = manifest.active_dependencies(&platform, engine_version);
+    for plugin in &linked {
+        for (name, constraint) in &plugin.dependencies {
+            dependencies
+                .entry(name.clone())
+                .or_insert_with(|| constraint.clone());
         }
-        detected
+    }
+    // Dev dependencies are always resolved so the lockfile is the same with
+    // or without --production; a runtime constraint wins over a dev one
+    let runtime_roots: Vec<String> = dependencies.keys().cloned().collect();
+    for (name, constraint) in &manifest.dev_dependencies {
+        dependencies
+            .entry(name.clone())
+            .or_insert_with(|| constraint.clone());
+    }
+
+    let resolver_config = &ResolverConfig {
+        features: manifest.dependency_features.clone(),
+        ..resolver_config.clone()
     };
 
-    // Resolve all transitive dependencies with spinner
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.blue} {msg}")
-            .unwrap()
-            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
-    );
-    spinner.set_message("Resolving dependency tree...");
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    // Resolve all transitive dependencies with spinner; --frozen takes
+    // unrealpm.lock as the resolution instead
+    let frozen = matches!(lockfile_mode, LockfileMode::Frozen | LockfileMode::Clean);
+    let spinner = unrealpm::output::spinner(if frozen {
+        "Reading unrealpm.lock..."
+    } else {
+        "Resolving dependency tree..."
+    });
+
+    // Reuse the previous resolution if no involved package changed in the registry
+    let cache = (use_resolve_cache && !frozen)
+        .then(ResolutionCache::open_default)
+        .and_then(|cache| cache.ok());
+    let (mut resolved, from_cache) = timings.time(Phase::Resolve, || match &cache {
+        _ if frozen => frozen_resolution(&manifest, &dependencies, &inactive).map(|r| (r, false)),
+        Some(cache) => cache.resolve(
+            &dependencies,
+            &registry,
+            &registry.location(),
+            engine_version,
+            force,
+            Some(resolver_config),
+        ),
+        None => resolve_dependencies(
+            &dependencies,
+            &registry,
+            engine_version,
+            force,
+            Some(resolver_config),
+        )
+        .map(|resolved| (resolved, false)),
+    })?;
+    if cache.is_some() {
+        timings.resolve_cache.record(from_cache);
+    }
 
-    let resolved = resolve_dependencies(
-        &manifest.dependencies,
+    if force && engine_version.is_some() {
+        unrealpm::strict::warn("Force installing - engine compatibility not checked")?;
+        outln!();
+    }
+    let message = if frozen {
+        format!("✓ Using {} packages from unrealpm.lock", resolved.len())
+    } else {
+        format!(
+            "✓ Resolved {} total packages (including transitive dependencies){}",
+            resolved.len(),
+            if from_cache {
+                ", reused cached resolution"
+            } else {
+                ""
+            }
+        )
+    };
+    unrealpm::output::finish_spinner(&spinner, message);
+    outln!();
+    // A linked working copy stands in for the registry package of that name
+    resolved.retain(|name, _| !manifest.path_dependencies.contains_key(name));
+    let dev_only = unrealpm::dev_only_packages(&resolved, runtime_roots.iter().map(String::as_str));
+    warn_module_collisions(&registry, &resolved)?;
+    warn_engine_plugin_conflicts(
+        &config_for_registry,
         &registry,
+        &resolved,
+        engine_version_override
+            .as_deref()
+            .or(manifest.engine_version.as_deref()),
+        &manifest.engine_overrides,
+    )?;
+
+    // Strict mode expects a complete, signed lockfile (e.g. in CI)
+    let existing_lockfile = Lockfile::load()?;
+    if unrealpm::strict::is_enabled() {
+        check_lockfile_entries(existing_lockfile.as_ref(), &resolved)?;
+        check_signed(&registry, &resolved)?;
+    }
+
+    // Pick source or binary per package, keeping what the lockfile recorded
+    let selection = artifact_selection(
+        install_mode,
         engine_version,
-        force,
-        Some(resolver_config),
+        &config_for_registry.build_configuration(project_dir),
+    );
+    select_artifacts(
+        &mut resolved,
+        &registry,
+        &selection,
+        existing_lockfile.as_ref(),
     )?;
+    check_integrity_pins(&manifest, &mut resolved, &registry.location())?;
+
+    // Locked packages nothing depends on any more are pruned. Dependencies
+    // skipped on this host stay locked for the hosts that install them.
+    let had_lockfile = existing_lockfile.is_some();
+    let mut lockfile = existing_lockfile.unwrap_or_default();
+    let stale = lockfile.unreachable_packages(
+        resolved
+            .keys()
+            .map(String::as_str)
+            .chain(inactive.iter().map(|(name, _)| *name))
+            .chain(linked.iter().map(|plugin| plugin.name.as_str())),
+    );
 
-    if force && engine_version.is_some() {
-        println!("⚠ WARNING: Force installing - engine compatibility not checked");
-        println!();
+    // Dev-only packages stay locked under --production, just not installed
+    if production && !dev_only.is_empty() {
+        resolved.retain(|name, _| !dev_only.contains(name));
+        outln!(
+            "Skipping {} package(s) only dev dependencies need: {}",
+            dev_only.len(),
+            dev_only.join(", ")
+        );
+        outln!();
+    }
+
+    let plan = InstallPlan::new(&lockfile, &resolved, &stale);
+    if lockfile_mode == LockfileMode::Locked && !plan.changes.is_empty() {
+        let changes: Vec<String> = plan
+            .changes
+            .iter()
+            .map(|change| format!("  {}", InstallPlan::describe(change)))
+            .collect();
+        anyhow::bail!(
+            "unrealpm.lock would change (--locked):\n{}\n\n\
+            Run `unrealpm install` without --locked to update it.",
+            changes.join("\n")
+        );
+    }
+    let threshold = had_lockfile
+        .then(|| config_for_registry.confirm_threshold(project_dir))
+        .flatten();
+    if !plan.confirm(&registry, &resolved, threshold, assume_yes || dry_run)? {
+        return Ok(());
+    }
+
+    if lockfile_mode == LockfileMode::Clean && !dry_run {
+        remove_managed_plugins(project_dir, &lockfile)?;
     }
-    spinner.finish_with_message(format!(
-        "✓ Resolved {} total packages (including transitive dependencies)",
-        resolved.len()
-    ));
-    println!();
+
+    let mut packages: Vec<(&String, &ResolvedPackage)> = resolved.iter().collect();
+    packages.sort_by(|a, b| a.0.cmp(b.0));
+    let adopted = claim_install_targets(project_dir, &packages, &mut lockfile, unmanaged, dry_run)?;
+    packages.retain(|(name, _)| !adopted.contains(*name));
 
     if dry_run {
         // Dry run: show what would be installed
-        println!("[DRY RUN] Would install the following packages:");
-        println!();
+        outln!("[DRY RUN] Would install the following packages:");
+        outln!();
         for (name, resolved_pkg) in &resolved {
-            println!("  - {}@{}", name, resolved_pkg.version);
+            match &resolved_pkg.binary {
+                Some(binary) => outln!(
+                    "  - {}@{} (pre-built binary {})",
+                    name,
+                    resolved_pkg.version,
+                    binary.target()
+                ),
+                None => outln!("  - {}@{}", name, resolved_pkg.version),
+            }
             if let Some(deps) = &resolved_pkg.dependencies {
                 if !deps.is_empty() {
-                    println!("    Dependencies:");
+                    outln!("    Dependencies:");
                     for (dep_name, dep_version) in deps {
-                        println!("      - {}@{}", dep_name, dep_version);
+                        outln!("      - {}@{}", dep_name, dep_version);
                     }
                 }
             }
         }
-        println!();
-        println!("[DRY RUN] Would update lockfile (unrealpm.lock)");
-        println!();
-        println!(
+        for plugin in &linked {
+            outln!(
+                "  - {}@{} (linked from {})",
+                plugin.name,
+                plugin.version,
+                plugin.path
+            );
+        }
+        for name in &stale {
+            outln!("  - {} (no longer required, would be removed)", name);
+        }
+        outln!();
+        outln!("[DRY RUN] Would update lockfile (unrealpm.lock)");
+        outln!();
+        outln!(
             "[DRY RUN] Would successfully install {} packages",
             resolved.len()
         );
-        println!();
+        outln!();
         return Ok(());
     }
 
-    // Load or create lockfile
-    let mut lockfile = Lockfile::load()?.unwrap_or_default();
+    let conflicts = skip_checksum_conflicts(&lockfile, &mut packages, &registry.location());
+
+    let partial = lockfile.partial_packages();
+    if !partial.is_empty() && !unrealpm::installer::source_files_only() {
+        outln!(
+            "Replacing {} partial install(s) (no Content/) with complete packages: {}",
+            partial.len(),
+            partial
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        outln!();
+    }
 
-    // Create a progress bar for package installation
-    let pb = ProgressBar::new(resolved.len() as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("[{bar:40.cyan/blue}] {pos}/{len} packages")
-            .unwrap()
-            .progress_chars("#>-"),
+    // Start the slowest packages first so their downloads overlap the rest
+    let history = InstallHistory::open_default().unwrap_or_default();
+    history.sort_slowest_first(&mut packages, |(name, _)| name.as_str());
+    print_eta(&history, &packages, jobs);
+
+    // Download and verify in parallel, extract one package at a time
+    let mut registry = registry;
+    registry.set_quiet(true);
+    let table = StatusTable::new(
+        packages
+            .iter()
+            .map(|(name, pkg)| format!("{}@{}", name, pkg.version))
+            .collect(),
+    )
+    .with_estimates(
+        packages
+            .iter()
+            .map(|(name, _)| history.estimate_total(name))
+            .collect(),
     );
-
-    // Install each resolved package
-    for (name, resolved_pkg) in &resolved {
-        pb.set_message(format!("Installing {}@{}", name, resolved_pkg.version));
-
-        // Get tarball path
-        let tarball_path = registry.get_tarball_path(name, &resolved_pkg.version);
-
-        // Verify checksum (no spinner for batch installs - we have a progress bar)
-        match verify_checksum(&tarball_path, &resolved_pkg.checksum, None) {
-            Ok(_) => {}
-            Err(e) => {
-                eprintln!("  ✗ Checksum verification failed for {}: {}", name, e);
-                eprintln!("  Skipping package...");
-                eprintln!();
-                continue;
+    let shared_timings = Mutex::new(&mut *timings);
+    let signatures = Mutex::new(SignatureSummary::default());
+
+    let outcomes = run_concurrent(
+        &packages,
+        jobs,
+        |(name, resolved_pkg), report| {
+            prepare_package(
+                &registry,
+                name,
+                resolved_pkg,
+                &shared_timings,
+                &signatures,
+                report,
+            )
+        },
+        |(name, resolved_pkg), tarball_path| {
+            let license = check_license(project_dir, name, &resolved_pkg.version, &tarball_path)?;
+            let mut timings = shared_timings.lock().unwrap();
+            timings
+                .store_cache
+                .record(is_package_in_store(resolved_pkg.artifact_checksum()).unwrap_or(false));
+            let installed_path = timings.time_package(name, Phase::Extract, || {
+                install_package_cas(
+                    &tarball_path,
+                    &project_dir.to_path_buf(),
+                    name,
+                    resolved_pkg.artifact_checksum(),
+                    None,
+                )
+            })?;
+
+            let dependencies = lockfile_dependencies(resolved_pkg, &tarball_path);
+            if resolved_pkg.dependencies.is_none() {
+                for dep_name in dependencies.iter().flat_map(|deps| deps.keys()) {
+                    if !resolved.contains_key(dep_name) {
+                        let message = format!(
+                            "{} depends on {} (from its unrealpm.json), which the registry does not list",
+                            name, dep_name
+                        );
+                        unrealpm::strict::check(message.clone())?;
+                        table.println(format!("  ⚠ {}", message));
+                    }
+                }
             }
-        }
+            // An unpatched install fails the package rather than getting locked
+            let patched_hash = apply_manifest_patches(project_dir, name, &installed_path)
+                .map_err(|e| anyhow::anyhow!("Failed to patch {}: {}", name, e))?;
+            lockfile.update_package(
+                (*name).clone(),
+                resolved_pkg.version.clone(),
+                resolved_pkg.checksum.clone(),
+                dependencies,
+            );
+            lockfile.set_binary(name, resolved_pkg.binary.clone());
+            lockfile.set_partial(name, unrealpm::installer::source_files_only());
+            lockfile.set_registry(name, registry.lock_source(name));
+            lockfile.set_license(name, license);
+            lockfile.set_patched_hash(name, patched_hash);
+            Ok(())
+        },
+        |index, state| table.set(index, state),
+    );
+    drop(table);
+    outln!();
 
-        // Install package using CAS (no spinner for batch installs)
-        match install_package_cas(
-            &tarball_path,
-            &project_dir.to_path_buf(),
-            name,
-            &resolved_pkg.checksum,
-            None,
-        ) {
-            Ok(_installed_path) => {
-                // Update lockfile
-                lockfile.update_package(
+    let failed = report_failures(&packages, &outcomes);
+    if failed == 0 {
+        outln!("✓ All packages processed");
+    } else {
+        outln!(
+            "⚠ {} of {} packages failed; continuing with the rest",
+            failed,
+            packages.len()
+        );
+    }
+    signatures.into_inner().unwrap().print();
+    outln!();
+
+    link_path_dependencies(project_dir, &manifest, &linked, &mut lockfile)?;
+    for name in &stale {
+        remove_stale_package(project_dir, &mut lockfile, name);
+    }
+    lockfile.set_dev_packages(&dev_only);
+    lockfile.set_features(&manifest.dependency_features);
+    lockfile.set_engine(engine_version);
+
+    // Save lockfile
+    lockfile.save()?;
+    outln!("  ✓ Lockfile updated");
+    outln!();
+
+    // The packages that made it are locked, but the install still failed
+    batch_result(conflicts.into_iter().chain(outcomes).collect())?;
+    outln!("✓ Finished installing dependencies");
+    outln!();
+
+    Ok(())
+}
+
+/// Install layout for the project in `project_dir`
+///
+/// The store layout needs a .uproject to register store folders in and an
+/// engine that reads `AdditionalPluginDirectories`; otherwise packages are
+/// copied into Plugins/ as usual.
+pub fn install_layout(
+    project_dir: &std::path::Path,
+    config: &Config,
+    engine_version_override: Option<&str>,
+) -> InstallLayout {
+    if config.install_layout(project_dir) == InstallLayout::Copy {
+        return InstallLayout::Copy;
+    }
+
+    let engine_version = engine_version_override
+        .map(str::to_string)
+        .or_else(|| Manifest::load(project_dir).ok()?.engine_version);
+    let fallback = if unrealpm::is_standalone_plugin(project_dir) {
+        Some("standalone plugins keep dependencies in Deps/Plugins/".to_string())
+    } else if unrealpm::UProject::find_all(project_dir).map_or(true, |found| found.is_empty()) {
+        Some("no .uproject to register store folders in".to_string())
+    } else if !unrealpm::installer::supports_store_layout(engine_version.as_deref()) {
+        let (major, minor) = unrealpm::installer::MIN_STORE_LAYOUT_ENGINE;
+        Some(format!(
+            "UE {} is older than {}.{}, which added AdditionalPluginDirectories",
+            engine_version.as_deref().unwrap_or_default(),
+            major,
+            minor
+        ))
+    } else {
+        None
+    };
+
+    match fallback {
+        Some(reason) => {
+            outln!(
+                "  • Store layout unavailable ({}); copying into Plugins/",
+                reason
+            );
+            InstallLayout::Copy
+        }
+        None => InstallLayout::Store,
+    }
+}
+
+/// List the store folders of the locked packages in the project's
+/// .uproject, or remove them when packages are copied
+///
+/// Packages that ended up in Plugins/ anyway (patched, linked) are left out.
+pub fn sync_store_layout(project_dir: &std::path::Path, layout: InstallLayout) {
+    let uproject = Manifest::load(project_dir)
+        .and_then(|manifest| manifest.uproject_path(project_dir))
+        .or_else(|_| unrealpm::UProject::find(project_dir));
+    let Ok(uproject) = uproject else {
+        return;
+    };
+    let Ok(store_dir) = unrealpm::get_store_dir() else {
+        return;
+    };
+
+    let mut dirs = Vec::new();
+    if layout == InstallLayout::Store {
+        let lockfile = Lockfile::load_from(project_dir.join(unrealpm::LOCKFILE_NAME))
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let plugins_dir = unrealpm::plugins_dir(project_dir);
+        let mut packages: Vec<_> = lockfile
+            .packages
+            .iter()
+            .filter(|(_, pkg)| !pkg.is_externally_managed() && pkg.linked_path().is_none())
+            .filter(|(name, _)| find_plugin_dir(&plugins_dir, name).is_none())
+            .collect();
+        packages.sort_by(|a, b| a.0.cmp(b.0));
+        dirs.extend(
+            packages
+                .into_iter()
+                .filter_map(|(_, pkg)| {
+                    unrealpm::get_package_store_path(pkg.artifact_checksum()).ok()
+                })
+                .filter(|dir| dir.is_dir()),
+        );
+    }
+
+    match unrealpm::UProject::set_store_plugin_directories(&uproject, &store_dir, &dirs) {
+        Ok(true) if dirs.is_empty() => outln!(
+            "  • Removed store folders from {}",
+            uproject.file_name().unwrap_or_default().to_string_lossy()
+        ),
+        Ok(true) => outln!(
+            "  • Registered {} store folder(s) in {}",
+            dirs.len(),
+            uproject.file_name().unwrap_or_default().to_string_lossy()
+        ),
+        Ok(false) => {}
+        Err(e) => outln!("  ⚠ Failed to update {}: {}", uproject.display(), e),
+    }
+}
+
+/// Tell the author of a standalone plugin how a host project finds its
+/// dependencies, which are not in any project's Plugins/ folder
+fn print_standalone_hint(project_dir: &std::path::Path) {
+    if !unrealpm::is_standalone_plugin(project_dir) {
+        return;
+    }
+    let deps_dir = unrealpm::plugins_dir(project_dir);
+    if !deps_dir.is_dir() {
+        return;
+    }
+    outln!(
+        "Standalone plugin: dependencies are in {}",
+        deps_dir.display()
+    );
+    outln!("  • Load them in a host project by adding to its .uproject:");
+    outln!(
+        "      \"AdditionalPluginDirectories\": [\"{}\"]",
+        deps_dir.display().to_string().replace('\\', "/")
+    );
+    outln!();
+}
+
+/// Check every path dependency in the manifest, sorted by name
+pub fn inspect_path_dependencies(
+    project_dir: &std::path::Path,
+    manifest: &Manifest,
+) -> Result<Vec<LinkedPlugin>> {
+    let mut linked = manifest
+        .path_dependencies
+        .iter()
+        .map(|(name, path)| LinkedPlugin::inspect(project_dir, name, path))
+        .collect::<unrealpm::Result<Vec<_>>>()?;
+    linked.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(linked)
+}
+
+/// Link path dependencies into Plugins/ and lock them without checksums
+///
+/// Links for path dependencies removed from the manifest are removed too;
+/// the directories they point to are never touched.
+fn link_path_dependencies(
+    project_dir: &std::path::Path,
+    manifest: &Manifest,
+    linked: &[LinkedPlugin],
+    lockfile: &mut Lockfile,
+) -> Result<()> {
+    let plugins_dir = unrealpm::plugins_dir(project_dir);
+    for plugin in linked {
+        let link_path = plugins_dir.join(&plugin.name);
+        if !link::points_to(&link_path, &plugin.dir) {
+            link::link_dir(&plugin.dir, &link_path)?;
+        }
+        lockfile.update_package(
+            plugin.name.clone(),
+            plugin.version.clone(),
+            String::new(),
+            Some(plugin.dependencies.clone()),
+        );
+        lockfile.set_source(&plugin.name, Some(plugin.source()));
+        outln!(
+            "  ✓ Linked {} -> {}",
+            link_path.display(),
+            plugin.dir.display()
+        );
+    }
+
+    let mut removed: Vec<String> = lockfile
+        .packages
+        .iter()
+        .filter(|(name, locked)| {
+            locked.linked_path().is_some() && !manifest.path_dependencies.contains_key(*name)
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    removed.sort();
+    for name in removed {
+        let link_path = plugins_dir.join(&name);
+        if link::is_link(&link_path) {
+            link::remove_link(&link_path)?;
+        }
+        lockfile.remove_package(&name);
+        outln!("  ✓ Unlinked {} (no longer a path dependency)", name);
+    }
+    Ok(())
+}
+
+/// Skip packages whose locked version now has a different checksum
+///
+/// A locked version must keep its contents; each conflict is reported as a
+/// lockfile-layer incident against `source`. Returns one failure per skipped
+/// package, for [`batch_result`].
+pub fn skip_checksum_conflicts(
+    lockfile: &Lockfile,
+    packages: &mut Vec<(&String, &ResolvedPackage)>,
+    source: &str,
+) -> Vec<Result<()>> {
+    let mut skipped = Vec::new();
+    packages.retain(|(name, resolved_pkg)| {
+        let Some(locked) =
+            lockfile.checksum_conflict(name, &resolved_pkg.version, &resolved_pkg.checksum)
+        else {
+            return true;
+        };
+        outln!(
+            "  ✗ {}@{}: registry checksum differs from unrealpm.lock (lockfile layer)",
+            name, resolved_pkg.version
+        );
+        outln!(
+            "    Locked: {}\n    Registry: {}",
+            locked, resolved_pkg.checksum
+        );
+        outln!("    The registry now serves different contents for a locked version; skipping.");
+        outln!("    If the change is expected, reinstall with: unrealpm uninstall <pkg> && unrealpm install <pkg>");
+        report_incident(
+            &Incident::new(name, &resolved_pkg.version, MismatchLayer::Lockfile, locked)
+                .with_actual(&resolved_pkg.checksum)
+                .with_source(source),
+        );
+        skipped.push(Err(anyhow::Error::new(unrealpm::Error::ChecksumMismatch {
+            expected: locked.to_string(),
+            actual: resolved_pkg.checksum.clone(),
+        })
+        .context(format!(
+            "{}@{}: registry checksum differs from unrealpm.lock",
+            name, resolved_pkg.version
+        ))));
+        false
+    });
+    skipped
+}
+
+/// Lockfile changes an install is about to make
+struct InstallPlan {
+    changes: Vec<unrealpm::LockChange>,
+}
+
+impl InstallPlan {
+    /// One line for a change, e.g. `↑ alpha 1.0.0 -> 1.5.0`
+    fn describe(change: &unrealpm::LockChange) -> String {
+        let version = |p: &Option<unrealpm::LockedPackage>| {
+            p.as_ref().map(|p| p.version.clone()).unwrap_or_default()
+        };
+        match change.kind {
+            LockChangeKind::Added => format!("+ {} {}", change.name, version(&change.new)),
+            LockChangeKind::Removed => format!("- {} {}", change.name, version(&change.old)),
+            LockChangeKind::Upgraded => format!(
+                "↑ {} {} -> {}",
+                change.name,
+                version(&change.old),
+                version(&change.new)
+            ),
+            LockChangeKind::Downgraded => format!(
+                "↓ {} {} -> {}",
+                change.name,
+                version(&change.old),
+                version(&change.new)
+            ),
+            LockChangeKind::Changed => format!(
+                "~ {} {} (checksum changed)",
+                change.name,
+                version(&change.new)
+            ),
+        }
+    }
+
+    /// Compare `lockfile` with the resolution, pruning `stale`
+    fn new(
+        lockfile: &Lockfile,
+        resolved: &HashMap<String, ResolvedPackage>,
+        stale: &[String],
+    ) -> Self {
+        let mut planned = lockfile.clone();
+        for (name, resolved_pkg) in resolved {
+            let unchanged = lockfile.get_package(name).is_some_and(|locked| {
+                locked.version == resolved_pkg.version && locked.checksum == resolved_pkg.checksum
+            });
+            if !unchanged {
+                planned.update_package(
                     name.clone(),
                     resolved_pkg.version.clone(),
                     resolved_pkg.checksum.clone(),
                     resolved_pkg.dependencies.clone(),
                 );
-                pb.inc(1);
             }
-            Err(e) => {
-                pb.println(format!("  ✗ Failed to install {}: {}", name, e));
-                pb.println("  Continuing with remaining packages...");
-                pb.inc(1);
+        }
+        for name in stale {
+            planned.remove_package(name);
+        }
+        Self {
+            changes: lockfile.diff(&planned),
+        }
+    }
+
+    /// Print the plan and ask before applying more than `threshold` changes
+    ///
+    /// Returns false if the user declined. Without a terminal to ask on, a
+    /// large plan is an error unless `assume_yes` is set.
+    fn confirm(
+        &self,
+        registry: &RegistryClient,
+        resolved: &HashMap<String, ResolvedPackage>,
+        threshold: Option<usize>,
+        assume_yes: bool,
+    ) -> Result<bool> {
+        if self.changes.is_empty() {
+            outln!("✓ unrealpm.lock is up to date");
+            outln!();
+            return Ok(true);
+        }
+
+        // Ask for all sizes at once; each may be a HEAD request
+        let sizes: Vec<Option<ArtifactSize>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .changes
+                .iter()
+                .map(|change| {
+                    let resolved_pkg = resolved.get(&change.name);
+                    scope.spawn(move || {
+                        resolved_pkg.map(|pkg| registry.artifact_size(&pkg.name, pkg.artifact_id()))
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().ok().flatten())
+                .collect()
+        });
+
+        outln!("Install plan (unrealpm.lock):");
+        for (change, size) in self.changes.iter().zip(&sizes) {
+            let line = Self::describe(change);
+            match size {
+                Some(ArtifactSize::Download(bytes)) => outln!(
+                    "  {} ({} download)",
+                    line,
+                    unrealpm::disk::format_bytes(*bytes)
+                ),
+                Some(ArtifactSize::Local(bytes)) => outln!(
+                    "  {} ({}, cached)",
+                    line,
+                    unrealpm::disk::format_bytes(*bytes)
+                ),
+                Some(ArtifactSize::Unknown) | None => outln!("  {}", line),
             }
         }
+
+        let count = |kind: LockChangeKind| self.changes.iter().filter(|c| c.kind == kind).count();
+        let summary: Vec<String> = [
+            (LockChangeKind::Added, "to add"),
+            (LockChangeKind::Upgraded, "to upgrade"),
+            (LockChangeKind::Downgraded, "to downgrade"),
+            (LockChangeKind::Removed, "to remove"),
+            (LockChangeKind::Changed, "to replace"),
+        ]
+        .into_iter()
+        .filter(|(kind, _)| count(*kind) > 0)
+        .map(|(kind, label)| format!("{} {}", count(kind), label))
+        .collect();
+        let download: u64 = sizes
+            .iter()
+            .map(|size| match size {
+                Some(ArtifactSize::Download(bytes)) => *bytes,
+                _ => 0,
+            })
+            .sum();
+        let unknown = sizes
+            .iter()
+            .zip(&self.changes)
+            .any(|(size, change)| change.new.is_some() && size == &Some(ArtifactSize::Unknown));
+        let download = match (download, unknown) {
+            (0, true) => "download size unknown".to_string(),
+            (0, false) => "nothing to download".to_string(),
+            (bytes, unknown) => format!(
+                "{}{} to download",
+                if unknown { "at least " } else { "" },
+                unrealpm::disk::format_bytes(bytes)
+            ),
+        };
+        outln!("  {}; {}", summary.join(", "), download);
+        outln!();
+
+        let Some(threshold) = threshold else {
+            return Ok(true);
+        };
+        if assume_yes || self.changes.len() <= threshold {
+            return Ok(true);
+        }
+
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!(
+                "Install would change {} packages in unrealpm.lock (confirmation threshold is {})\n\
+                 • Review the plan above, then rerun with: unrealpm install --yes\n\
+                 • Or raise the threshold: unrealpm config set install.confirm_threshold <n>",
+                self.changes.len(),
+                threshold
+            );
+        }
+
+        print!("Apply {} changes? [y/N]: ", self.changes.len());
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            outln!("Install cancelled; nothing was changed.");
+            return Ok(false);
+        }
+        outln!();
+        Ok(true)
+    }
+}
+
+/// Drop a package nothing depends on from the lockfile and Plugins/
+pub fn remove_stale_package(project_dir: &std::path::Path, lockfile: &mut Lockfile, name: &str) {
+    let plugin_dir = find_plugin_dir(&unrealpm::plugins_dir(project_dir), name);
+    // A linked working copy is the user's to remove with `unrealpm unlink`
+    if let Some(plugin_dir) = plugin_dir.filter(|dir| !link::is_link(dir)) {
+        let removed =
+            unrealpm::vcs::track(&[&plugin_dir], || Ok(std::fs::remove_dir_all(&plugin_dir)?));
+        if let Err(e) = removed {
+            outln!("  ⚠ Failed to remove {}: {}", plugin_dir.display(), e);
+            return;
+        }
     }
+    lockfile.remove_package(name);
+    outln!("  ✓ Removed {} (no longer required)", name);
+}
 
-    pb.finish_with_message("✓ All packages processed");
+/// Remove the plugin folders `lockfile` records, so the install starts from
+/// a clean Plugins/
+///
+/// Hand-placed plugins are kept and listed. So are packages linked with
+/// `unrealpm link`, whose folders point at a working copy.
+fn remove_managed_plugins(project_dir: &std::path::Path, lockfile: &Lockfile) -> Result<()> {
+    let installed = unrealpm::installed_plugins(project_dir, lockfile);
+    let mut removed = 0;
+    for (name, dir) in &installed.managed {
+        if link::is_link(dir) {
+            continue;
+        }
+        unrealpm::remove_installed_package(project_dir, name, unrealpm::DryRun::OFF)?;
+        removed += 1;
+    }
+    outln!("✓ Removed {} installed plugin(s)", removed);
+    if !installed.unmanaged.is_empty() {
+        outln!("Keeping plugins unrealpm didn't install:");
+        for dir in &installed.unmanaged {
+            outln!(
+                "  • {}",
+                dir.strip_prefix(project_dir).unwrap_or(dir).display()
+            );
+        }
+    }
+    outln!();
+    Ok(())
+}
 
-    // Save lockfile
-    lockfile.save()?;
-    println!("  ✓ Lockfile updated");
-    println!();
+/// How much `install` may change unrealpm.lock
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LockfileMode {
+    /// Resolve and update the lockfile as needed
+    #[default]
+    Update,
+    /// Resolve, but fail if the lockfile would change
+    Locked,
+    /// Skip resolution and install exactly what the lockfile records; fail
+    /// if it is out of sync with the manifest
+    Frozen,
+    /// As `Frozen`, after removing every plugin unrealpm installed
+    /// (`unrealpm ci`)
+    Clean,
+}
 
-    println!("✓ Finished installing dependencies");
-    println!();
+impl LockfileMode {
+    /// Mode selected by the `--locked`/`--frozen` flags
+    pub fn from_flags(locked: bool, frozen: bool) -> Self {
+        if frozen {
+            Self::Frozen
+        } else if locked {
+            Self::Locked
+        } else {
+            Self::Update
+        }
+    }
+}
 
+/// What to do when a plugin folder exists but unrealpm.lock doesn't list it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnmanagedPolicy {
+    /// Stop before touching anything
+    #[default]
+    Refuse,
+    /// Keep the existing files and record them in the lockfile
+    Adopt,
+    /// Replace the existing files with the package
+    Overwrite,
+}
+
+impl UnmanagedPolicy {
+    /// Policy selected by the `--adopt`/`--overwrite` flags
+    pub fn from_flags(adopt: bool, overwrite: bool) -> Self {
+        if adopt {
+            Self::Adopt
+        } else if overwrite {
+            Self::Overwrite
+        } else {
+            Self::Refuse
+        }
+    }
+}
+
+/// Check install targets for plugin folders UnrealPM doesn't manage
+///
+/// A copy that was dropped into Plugins/ by hand would otherwise be silently
+/// replaced. Adopted packages are recorded in `lockfile` and returned; they
+/// must not be installed. Packages linked with `unrealpm link` are locked as
+/// resolved and returned the same way, so the link stays in place.
+pub fn claim_install_targets(
+    project_dir: &std::path::Path,
+    packages: &[(&String, &ResolvedPackage)],
+    lockfile: &mut Lockfile,
+    policy: UnmanagedPolicy,
+    dry_run: bool,
+) -> Result<HashSet<String>> {
+    let plugins_dir = unrealpm::plugins_dir(project_dir);
+    let mut adopted = HashSet::new();
+    for (name, pkg) in packages {
+        let Some(target) = link::link_target(&plugins_dir.join(unrealpm::scope::unscoped(name)))
+        else {
+            continue;
+        };
+        if !lockfile.is_locked_at(name, &pkg.version, pkg.binary.as_ref()) {
+            lockfile.update_package(
+                (*name).clone(),
+                pkg.version.clone(),
+                pkg.checksum.clone(),
+                pkg.dependencies.clone(),
+            );
+            lockfile.set_binary(name, pkg.binary.clone());
+        }
+        outln!(
+            "  • Keeping linked {} ({}); locked at {}",
+            name,
+            target.display(),
+            pkg.version
+        );
+        adopted.insert((*name).clone());
+    }
+
+    let unmanaged: Vec<(&String, &ResolvedPackage, std::path::PathBuf)> = packages
+        .iter()
+        .filter(|(name, _)| !lockfile.has_package(name) && !adopted.contains(*name))
+        .filter_map(|(name, pkg)| find_plugin_dir(&plugins_dir, name).map(|dir| (*name, *pkg, dir)))
+        .collect();
+
+    if unmanaged.is_empty() {
+        return Ok(adopted);
+    }
+
+    let relative = |dir: &std::path::Path| {
+        dir.strip_prefix(project_dir)
+            .unwrap_or(dir)
+            .display()
+            .to_string()
+    };
+
+    match policy {
+        UnmanagedPolicy::Refuse => {
+            let list = unmanaged
+                .iter()
+                .map(|(name, _, dir)| format!("  {} ({})", name, relative(dir)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            anyhow::bail!(
+                "Plugins/ already contains plugins that unrealpm.lock doesn't list:\n{}\n\n\
+                Installing would replace them. Options:\n\
+                  • Keep the existing files and manage them: unrealpm install --adopt\n\
+                  • Replace them with the registry packages: unrealpm install --overwrite",
+                list
+            );
+        }
+        UnmanagedPolicy::Overwrite => {
+            for (name, _, dir) in &unmanaged {
+                outln!(
+                    "  ⚠ {} unmanaged {} ({})",
+                    if dry_run {
+                        "Would replace"
+                    } else {
+                        "Replacing"
+                    },
+                    name,
+                    relative(dir)
+                );
+            }
+        }
+        UnmanagedPolicy::Adopt => {
+            for (name, pkg, dir) in unmanaged {
+                let existing = UPlugin::find(&dir)
+                    .and_then(UPlugin::load)
+                    .map(|uplugin| uplugin.version_name)
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Cannot adopt {}: no readable .uplugin in {} ({})\n\n\
+                            Replace it instead: unrealpm install --overwrite",
+                            name,
+                            relative(&dir),
+                            e
+                        )
+                    })?;
+                if existing != pkg.version {
+                    anyhow::bail!(
+                        "Cannot adopt {}: {} is version {}, but {} was resolved\n\n\
+                        Replace it instead: unrealpm install --overwrite",
+                        name,
+                        relative(&dir),
+                        existing,
+                        pkg.version
+                    );
+                }
+
+                lockfile.update_package(
+                    name.clone(),
+                    pkg.version.clone(),
+                    pkg.checksum.clone(),
+                    pkg.dependencies.clone(),
+                );
+                outln!(
+                    "  ✓ {} {}@{} ({})",
+                    if dry_run { "Would adopt" } else { "Adopted" },
+                    name,
+                    pkg.version,
+                    relative(&dir)
+                );
+                adopted.insert(name.clone());
+            }
+        }
+    }
+
+    Ok(adopted)
+}
+
+/// Warn about packages that declare the same module name
+///
+/// UBT fails to build a project with duplicate module names, so this is
+/// reported before installing rather than left for the build to find.
+///
+/// Fails instead in strict mode.
+pub fn warn_module_collisions(
+    registry: &RegistryClient,
+    resolved: &HashMap<String, ResolvedPackage>,
+) -> Result<()> {
+    let collisions = check_module_collisions(registry, resolved);
+    if collisions.is_empty() {
+        return Ok(());
+    }
+
+    outln!("⚠ Module name collisions (Unreal Build Tool requires unique module names):");
+    for collision in &collisions {
+        outln!("  {}: {}", collision.module, collision.packages.join(", "));
+    }
+    outln!("  The project will fail to build until only one of each is installed.");
+    outln!();
+    unrealpm::strict::check(format!(
+        "{} module name collision(s) between packages",
+        collisions.len()
+    ))?;
     Ok(())
 }
 
-/// Select the best installation source (binary or source) based on availability and preferences
-/// Returns: (tarball_path, checksum, install_type_description)
-fn select_installation_source(
-    resolved_version: &unrealpm::PackageVersion,
+/// Warn about packages whose plugin the project's engine already ships
+///
+/// Unreal would find two plugins with the same name and load either one.
+/// Dependencies marked `engine_override` in unrealpm.json replace the
+/// engine's copy on purpose and are skipped. Nothing is checked when the
+/// engine installation can't be found.
+///
+/// Fails instead in strict mode.
+pub fn warn_engine_plugin_conflicts(
+    config: &Config,
     registry: &RegistryClient,
-    package_name: &str,
-    engine_version: Option<&str>,
-    install_mode: InstallMode,
-) -> Result<(std::path::PathBuf, String, Option<String>)> {
-    // Detect current platform
-    let platform = unrealpm::platform::detect_platform();
+    resolved: &HashMap<String, ResolvedPackage>,
+    engine_association: Option<&str>,
+    overrides: &HashSet<String>,
+) -> Result<()> {
+    let Some(engine) = engine_association.and_then(|version| config.find_engine(version)) else {
+        return Ok(());
+    };
+    let conflicts: Vec<_> = check_engine_plugin_conflicts(registry, resolved, &engine.path)
+        .into_iter()
+        .filter(|conflict| !overrides.contains(&conflict.package))
+        .collect();
+    if conflicts.is_empty() {
+        return Ok(());
+    }
 
-    // Check for pre-built binary if requested
-    if matches!(
-        install_mode,
-        InstallMode::PreferBinary | InstallMode::BinaryOnly
-    ) {
-        if let Some(binaries) = &resolved_version.binaries {
-            // Try to find matching binary
-            if let Some(engine) = engine_version {
-                let normalized_engine = unrealpm::platform::normalize_engine_version(engine);
-
-                for binary in binaries {
-                    if binary.platform == platform
-                        && unrealpm::platform::normalize_engine_version(&binary.engine)
-                            == normalized_engine
-                    {
-                        // Found matching binary!
-                        let binary_tarball_path =
-                            registry.get_tarball_path(package_name, &binary.tarball);
-                        return Ok((
-                            binary_tarball_path,
-                            binary.checksum.clone(),
-                            Some(format!("pre-built binary ({}/{})", platform, engine)),
-                        ));
-                    }
-                }
+    outln!(
+        "⚠ Plugins that Unreal Engine {} already ships (the engine would load either copy):",
+        engine.version
+    );
+    for conflict in &conflicts {
+        outln!(
+            "  {}: {} ({}{})",
+            conflict.package,
+            conflict.plugin,
+            conflict.engine_path.display(),
+            conflict
+                .engine_version
+                .as_deref()
+                .map(|version| format!(", engine version {}", version))
+                .unwrap_or_default()
+        );
+    }
+    outln!("  • Use the engine's copy: remove the dependency and enable the built-in plugin");
+    outln!("  • Rename the plugin in the package so both can coexist");
+    outln!("  • Replace the engine's copy on purpose: set \"engine_override\": true on the dependency in unrealpm.json");
+    outln!();
+    unrealpm::strict::check(format!(
+        "{} package(s) conflict with plugins shipped by the engine",
+        conflicts.len()
+    ))?;
+    Ok(())
+}
+
+/// Fail if a package pinned with `integrity` in unrealpm.json resolved to
+/// different contents
+///
+/// Runs on every resolution, so a missing or regenerated lockfile can't let
+/// changed bytes through. Pinned packages are installed from source, since
+/// the pin covers the source tarball.
+pub fn check_integrity_pins(
+    manifest: &Manifest,
+    resolved: &mut HashMap<String, ResolvedPackage>,
+    source: &str,
+) -> Result<()> {
+    if manifest.dependency_integrity.is_empty() {
+        return Ok(());
+    }
+    report_pin_mismatches(
+        enforce_pins(&manifest.dependency_integrity, resolved),
+        source,
+    )
+}
+
+/// Report each pin mismatch and fail if there are any
+fn report_pin_mismatches(mismatches: Vec<Incident>, source: &str) -> Result<()> {
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    for incident in &mismatches {
+        eoutln!(
+            "  ✗ {}@{}: checksum differs from the integrity pin in unrealpm.json (manifest layer)",
+            incident.package,
+            incident.version
+        );
+        eoutln!(
+            "    Pinned:   sha256-{}\n    Resolved: sha256-{}",
+            incident.expected,
+            incident.actual.as_deref().unwrap_or("unknown")
+        );
+        report_incident(&incident.clone().with_source(source));
+    }
+    eoutln!();
+    let first = &mismatches[0];
+    let mismatch = unrealpm::Error::ChecksumMismatch {
+        expected: first.expected.clone(),
+        actual: first
+            .actual
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+    };
+    Err(anyhow::Error::new(mismatch).context(format!(
+        "{} pinned package(s) would install different contents than unrealpm.json allows\n\n\
+        • The registry now serves different bytes for a pinned version; nothing was installed\n\
+        • If the change is expected, review it and update the package's integrity in unrealpm.json",
+        mismatches.len()
+    )))
+}
+
+/// The locked packages `install --frozen` installs, in place of a resolution
+///
+/// `dependencies` are the direct dependencies active on this host; packages
+/// only `inactive` ones need stay locked but are not returned. Fails if there
+/// is no lockfile or it is out of sync with the manifest.
+fn frozen_resolution(
+    manifest: &Manifest,
+    dependencies: &HashMap<String, String>,
+    inactive: &[(&str, &unrealpm::DependencyConditions)],
+) -> unrealpm::Result<HashMap<String, ResolvedPackage>> {
+    let lockfile = Lockfile::load()?.ok_or_else(|| {
+        unrealpm::Error::Other(
+            "No unrealpm.lock found; --frozen installs only what the lockfile records\n\n\
+            Run `unrealpm install` and commit the lockfile"
+                .to_string(),
+        )
+    })?;
+
+    let mut expected = dependencies.clone();
+    for (name, _) in inactive {
+        if let Some(constraint) = manifest.dependencies.get(*name) {
+            expected.insert(name.to_string(), constraint.clone());
+        }
+    }
+    let mismatches = lockfile.manifest_mismatches(&expected, &manifest.dependency_features);
+    if !mismatches.is_empty() {
+        return Err(unrealpm::Error::Other(format!(
+            "unrealpm.json and unrealpm.lock are out of sync (--frozen):\n  {}\n\n\
+            Run `unrealpm install` without --frozen to update the lockfile",
+            mismatches.join("\n  ")
+        )));
+    }
+
+    let skipped = lockfile.unreachable_packages(dependencies.keys().map(String::as_str));
+    Ok(lockfile
+        .packages
+        .into_iter()
+        .filter(|(name, locked)| locked.source.is_none() && !skipped.contains(name))
+        .map(|(name, locked)| {
+            let resolved_pkg = ResolvedPackage {
+                name: name.clone(),
+                version: locked.version,
+                checksum: locked.checksum,
+                dependencies: locked.dependencies,
+                binary: locked.binary,
+            };
+            (name, resolved_pkg)
+        })
+        .collect())
+}
+
+/// Fail if unrealpm.lock is missing or lacks any resolved package (strict mode)
+fn check_lockfile_entries(
+    lockfile: Option<&Lockfile>,
+    resolved: &HashMap<String, ResolvedPackage>,
+) -> Result<()> {
+    let Some(lockfile) = lockfile else {
+        unrealpm::strict::check(
+            "No unrealpm.lock found\n\n\
+            Run `unrealpm install` without --strict and commit the lockfile",
+        )?;
+        return Ok(());
+    };
+
+    let mut missing: Vec<&String> = resolved
+        .keys()
+        .filter(|name| !lockfile.has_package(name))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    missing.sort();
+    let missing: Vec<&str> = missing.iter().map(|name| name.as_str()).collect();
+    unrealpm::strict::check(format!(
+        "unrealpm.lock has no entry for: {}\n\n\
+        Run `unrealpm install` without --strict and commit the updated lockfile",
+        missing.join(", ")
+    ))?;
+    Ok(())
+}
+
+/// Fail if any resolved package version is unsigned (strict mode)
+fn check_signed(
+    registry: &RegistryClient,
+    resolved: &HashMap<String, ResolvedPackage>,
+) -> Result<()> {
+    let mut names: Vec<&String> = resolved.keys().collect();
+    names.sort();
+    for name in names {
+        let version = &resolved[name].version;
+        let signed = registry
+            .get_package(name)?
+            .versions
+            .iter()
+            .any(|v| &v.version == version && v.public_key.is_some());
+        if !signed {
+            unrealpm::strict::check(format!("{}@{} is not signed", name, version))?;
+        }
+    }
+    Ok(())
+}
+
+/// Download (HTTP registries) and verify one package for a concurrent install
+///
+/// The tarball and its signature are both fetched and checked here, so the
+/// worker pool downloads signatures in parallel too. Signature warnings are
+/// not printed while the status table is drawn; the outcome is recorded in
+/// `signatures` for the summary after the install.
+pub fn prepare_package(
+    registry: &RegistryClient,
+    name: &str,
+    resolved_pkg: &ResolvedPackage,
+    timings: &Mutex<&mut Timings>,
+    signatures: &Mutex<SignatureSummary>,
+    report: &dyn Fn(TaskState),
+) -> Result<std::path::PathBuf> {
+    report(TaskState::Downloading);
+    let cached = registry.is_tarball_cached(name, resolved_pkg.artifact_id());
+    let start = Instant::now();
+    let tarball_path = registry.fetch_tarball(
+        name,
+        resolved_pkg.artifact_id(),
+        resolved_pkg.artifact_checksum(),
+    )?;
+    if let RegistryClient::Http(_) = registry {
+        let mut timings = timings.lock().unwrap();
+        timings.download_cache.record(cached);
+        // A cache hit says nothing about how long the download takes
+        if cached {
+            timings.record(Phase::Download, start.elapsed());
+        } else {
+            timings.record_package(name, Phase::Download, start.elapsed());
+        }
+    }
+
+    report(TaskState::Verifying);
+    let start = Instant::now();
+    let verified = registry.verify_tarball(
+        name,
+        resolved_pkg.artifact_id(),
+        &tarball_path,
+        resolved_pkg.artifact_checksum(),
+        None,
+    );
+    if verified.is_ok() {
+        timings
+            .lock()
+            .unwrap()
+            .record_package(name, Phase::Verify, start.elapsed());
+    } else {
+        timings
+            .lock()
+            .unwrap()
+            .record(Phase::Verify, start.elapsed());
+    }
+    verified?;
+
+    let start = Instant::now();
+    let signature = check_package_signature(
+        registry,
+        &Config::load()?.verification,
+        name,
+        &resolved_pkg.version,
+        registry_version(registry, name, &resolved_pkg.version)?.as_ref(),
+        &tarball_path,
+    )?;
+    timings
+        .lock()
+        .unwrap()
+        .record_package(name, Phase::Verify, start.elapsed());
+    if let Some(warning) = signature.warning(name, &resolved_pkg.version) {
+        unrealpm::strict::check(warning)?;
+    }
+    signatures
+        .lock()
+        .unwrap()
+        .record(name, &resolved_pkg.version, signature);
+
+    Ok(tarball_path)
+}
+
+/// Outcome of checking the publisher signature of one package
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureCheck {
+    /// The signature matches the tarball; `pinned` if its key was pinned
+    /// now, `rotated` if it replaced a pinned key that endorsed it
+    Verified { pinned: bool, rotated: bool },
+    /// The registry lists no signature for the version
+    Unsigned,
+    /// The version is signed but its signature could not be retrieved
+    Unavailable,
+    /// The signature doesn't match (tolerated with strict_verification off)
+    Invalid,
+}
+
+impl SignatureCheck {
+    /// Warning to report for a package whose signature did not verify
+    pub fn warning(&self, name: &str, version: &str) -> Option<String> {
+        match self {
+            SignatureCheck::Verified { .. } => None,
+            SignatureCheck::Unsigned => Some(format!("{}@{} is not signed", name, version)),
+            SignatureCheck::Unavailable => Some(format!(
+                "Signature not available for {}@{} (package marked as signed)",
+                name, version
+            )),
+            SignatureCheck::Invalid => Some(format!(
+                "WARNING: Signature verification failed for {}@{} - continuing anyway (strict_verification=false)",
+                name, version
+            )),
+        }
+    }
+}
+
+/// Check the publisher signature of a downloaded package tarball
+///
+/// Every install path calls this, for requested packages and dependencies
+/// alike, so `verification.require_signatures` and `strict_verification`
+/// apply to every package. `listing` is the registry's entry for the
+/// version. Fails when the settings forbid the outcome or the publisher key
+/// differs from the pinned one; otherwise the caller reports
+/// [`SignatureCheck::warning`].
+pub fn check_package_signature(
+    registry: &RegistryClient,
+    verification: &VerificationConfig,
+    name: &str,
+    version: &str,
+    listing: Option<&PackageVersion>,
+    tarball_path: &std::path::Path,
+) -> Result<SignatureCheck> {
+    let endorsement = listing.and_then(|listed| listed.key_endorsement.as_ref());
+    let Some(public_key) = listing.and_then(|listed| listed.public_key.as_deref()) else {
+        if verification.require_signatures {
+            return Err(unrealpm::Error::SignatureVerificationFailed(format!(
+                "Signature verification required but package '{}@{}' is not signed\n\n\
+                Solutions:\n\
+                • Disable signature requirement: unrealpm config set verification.require_signatures false\n\
+                • Contact the package author to sign their package\n\
+                • Use a different package version that is signed",
+                name, version
+            ))
+            .into());
+        }
+        unrealpm::trust::check_unsigned_package(name, version)?;
+        return Ok(SignatureCheck::Unsigned);
+    };
+    unrealpm::trust::check_publisher_key(name, version, public_key, endorsement)?;
+
+    // Download signature from registry (or get local path for file registry)
+    let Ok(sig_path) = registry.download_signature(name, version) else {
+        if verification.require_signatures {
+            return Err(unrealpm::Error::SignatureVerificationFailed(format!(
+                "Signature verification required but signature could not be retrieved for {}@{}\n\n\
+                This package is marked as signed but the signature file is not available.\n\n\
+                Solutions:\n\
+                • Disable signature requirement: unrealpm config set verification.require_signatures false\n\
+                • Contact the package author to republish with a valid signature",
+                name, version
+            ))
+            .into());
+        }
+        return Ok(SignatureCheck::Unavailable);
+    };
+
+    let tarball_bytes = std::fs::read(tarball_path)?;
+    let signature_bytes = std::fs::read(&sig_path)?;
+    if verify_signature(&tarball_bytes, &signature_bytes, public_key)? {
+        let key = unrealpm::trust::pin_on_first_use(name, version, public_key, endorsement)?;
+        return Ok(SignatureCheck::Verified {
+            pinned: matches!(key, KeyCheck::FirstUse | KeyCheck::Rotated(_)),
+            rotated: matches!(key, KeyCheck::Rotated(_)),
+        });
+    }
+    if verification.strict_verification {
+        return Err(unrealpm::Error::SignatureVerificationFailed(format!(
+            "Signature verification FAILED for {}@{}\n\n\
+            The package signature is invalid. This could mean:\n\
+            • The package has been tampered with\n\
+            • The signature file is corrupted\n\
+            • The public key doesn't match\n\n\
+            For your security, installation has been aborted.\n\
+            If you trust this package, you can:\n\
+            • Contact the package author\n\
+            • Disable strict verification: unrealpm config set verification.strict_verification false",
+            name, version
+        ))
+        .into());
+    }
+    Ok(SignatureCheck::Invalid)
+}
+
+/// The registry's entry for `name@version`
+pub fn registry_version(
+    registry: &RegistryClient,
+    name: &str,
+    version: &str,
+) -> Result<Option<PackageVersion>> {
+    Ok(registry
+        .get_package(name)?
+        .versions
+        .into_iter()
+        .find(|v| v.version == version))
+}
+
+/// Signature outcomes of the packages one command installed
+#[derive(Debug, Default)]
+pub struct SignatureSummary {
+    verified: Vec<String>,
+    unsigned: Vec<String>,
+    unavailable: Vec<String>,
+    invalid: Vec<String>,
+}
+
+impl SignatureSummary {
+    pub fn record(&mut self, name: &str, version: &str, check: SignatureCheck) {
+        let list = match check {
+            SignatureCheck::Verified { .. } => &mut self.verified,
+            SignatureCheck::Unsigned => &mut self.unsigned,
+            SignatureCheck::Unavailable => &mut self.unavailable,
+            SignatureCheck::Invalid => &mut self.invalid,
+        };
+        list.push(format!("{}@{}", name, version));
+    }
+
+    /// Print how many packages were signed, listing the ones that weren't
+    pub fn print(&mut self) {
+        let total =
+            self.verified.len() + self.unsigned.len() + self.unavailable.len() + self.invalid.len();
+        if total == 0 {
+            return;
+        }
+        if self.verified.len() == total {
+            outln!("✓ Signatures verified for all {} packages", total);
+            return;
+        }
+        outln!(
+            "Signatures: {} of {} packages verified",
+            self.verified.len(),
+            total
+        );
+        for (label, list) in [
+            ("Unsigned", &mut self.unsigned),
+            ("Signature unavailable", &mut self.unavailable),
+            ("Signature INVALID", &mut self.invalid),
+        ] {
+            if !list.is_empty() {
+                list.sort();
+                outln!("  {}: {}", label, list.join(", "));
             }
         }
+    }
+}
 
-        // No binary found
-        if matches!(install_mode, InstallMode::BinaryOnly) {
-            anyhow::bail!(
-                "No pre-built binary available for {} on platform {} with engine {}.\n\n\
-                Available binaries:\n{}\n\n\
-                Suggestions:\n\
-                  • Use --prefer-binary to fall back to source\n\
-                  • Use --source-only to install from source\n\
-                  • Check if binaries exist for your platform/engine combination",
-                package_name,
-                platform,
-                engine_version.unwrap_or("unknown"),
-                format_available_binaries(&resolved_version.binaries)
-            );
+/// Dependencies to record in the lockfile for a downloaded package
+///
+/// Uses the registry's list when it has one. Older registry entries carry
+/// no dependency list, so fall back to the `unrealpm.json` inside the
+/// tarball, read without extracting the rest of the archive.
+pub fn lockfile_dependencies(
+    resolved_pkg: &ResolvedPackage,
+    tarball_path: &std::path::Path,
+) -> Option<HashMap<String, String>> {
+    if resolved_pkg.dependencies.is_some() {
+        return resolved_pkg.dependencies.clone();
+    }
+    read_tarball_metadata(tarball_path)
+        .ok()
+        .and_then(|metadata| metadata.manifest)
+        .map(|manifest| manifest.dependencies)
+        .filter(|deps| !deps.is_empty())
+}
+
+/// Time a download, keeping cache hits out of the package's history
+fn time_download<T>(
+    timings: &mut Timings,
+    name: &str,
+    cached: bool,
+    download: impl FnOnce() -> T,
+) -> T {
+    timings.download_cache.record(cached);
+    if cached {
+        timings.time(Phase::Download, download)
+    } else {
+        timings.time_package(name, Phase::Download, download)
+    }
+}
+
+/// Print how long installing `packages` took last time, if known
+pub fn print_eta(history: &InstallHistory, packages: &[(&String, &ResolvedPackage)], jobs: usize) {
+    let names: Vec<&str> = packages.iter().map(|(name, _)| name.as_str()).collect();
+    let Some(eta) = history.eta(&names, jobs) else {
+        return;
+    };
+    if eta.known == eta.total {
+        outln!("Estimated time: ~{}", format_duration(eta.duration));
+    } else {
+        outln!(
+            "Estimated time: at least ~{} ({} of {} packages installed before)",
+            format_duration(eta.duration),
+            eta.known,
+            eta.total
+        );
+    }
+    outln!();
+}
+
+/// Add this run's per-package timings to the install history
+pub fn save_install_history(timings: &Timings) {
+    if timings.packages().is_empty() {
+        return;
+    }
+    if let Ok(mut history) = InstallHistory::open_default() {
+        history.record(timings);
+        let _ = history.save();
+    }
+}
+
+/// Print the error for each failed package, returning how many failed
+pub fn report_failures(packages: &[(&String, &ResolvedPackage)], outcomes: &[Result<()>]) -> usize {
+    let mut failed = 0;
+    for ((name, resolved_pkg), outcome) in packages.iter().zip(outcomes) {
+        if let Err(e) = outcome {
+            eoutln!("  ✗ {}@{}: {}", name, resolved_pkg.version, e);
+            failed += 1;
         }
     }
+    if failed > 0 {
+        eoutln!();
+    }
+    failed
+}
 
-    // Fall back to source (or use source if preferred)
-    if matches!(
-        install_mode,
-        InstallMode::SourceOnly | InstallMode::PreferSource | InstallMode::PreferBinary
-    ) {
-        let source_tarball_path =
-            registry.get_tarball_path(package_name, &resolved_version.version);
-        return Ok((
-            source_tarball_path,
-            resolved_version.checksum.clone(),
-            if resolved_version.binaries.is_some() {
-                Some("source code".to_string())
-            } else {
-                None // Don't show "using source" if there's no binary option
+/// Combine the outcomes of a concurrent batch into one result
+///
+/// The first failure carrying an [`unrealpm::Error`] becomes the source of
+/// the error, so the exit code still says what went wrong.
+pub fn batch_result(outcomes: Vec<Result<()>>) -> Result<()> {
+    let total = outcomes.len();
+    let mut errors: Vec<anyhow::Error> = outcomes.into_iter().filter_map(Result::err).collect();
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let failed = errors.len();
+    let first = errors
+        .iter()
+        .position(|e| e.chain().any(|cause| cause.is::<unrealpm::Error>()))
+        .unwrap_or(0);
+    Err(errors
+        .swap_remove(first)
+        .context(format!("{} of {} packages failed", failed, total)))
+}
+
+/// Live per-package status table for concurrent installs and updates
+///
+/// One row per package shows its current state, with a combined progress
+/// bar underneath counting finished packages and the ones in flight. When
+/// stderr is not a terminal the rows are hidden and each package prints a
+/// line as it finishes instead.
+pub struct StatusTable {
+    multi: MultiProgress,
+    rows: Vec<ProgressBar>,
+    overall: ProgressBar,
+    states: Mutex<Vec<TaskState>>,
+    labels: Vec<String>,
+    estimates: Vec<Option<Duration>>,
+    live: bool,
+    done: &'static str,
+}
+
+impl StatusTable {
+    pub fn new(labels: Vec<String>) -> Self {
+        let live = std::io::stderr().is_terminal() && !unrealpm::output::is_plain();
+        let width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+        let style = ProgressStyle::default_spinner()
+            .template(&format!("  {{prefix:<{}}}  {{msg}}", width))
+            .unwrap();
+
+        let multi = MultiProgress::new();
+        let rows = labels
+            .iter()
+            .map(|label| {
+                let row = multi.add(ProgressBar::new_spinner());
+                row.set_style(style.clone());
+                row.set_prefix(label.clone());
+                row
+            })
+            .collect();
+        let overall = multi.add(ProgressBar::new(labels.len() as u64));
+        overall.set_style(
+            ProgressStyle::default_bar()
+                .template("  [{bar:40.cyan/blue}] {pos}/{len} packages  {msg} [{elapsed_precise}]")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+
+        Self {
+            multi,
+            rows,
+            overall,
+            states: Mutex::new(vec![TaskState::Queued; labels.len()]),
+            labels,
+            estimates: Vec::new(),
+            live,
+            done: "installed",
+        }
+    }
+
+    /// Typical time per package, shown next to packages in progress
+    pub fn with_estimates(mut self, estimates: Vec<Option<Duration>>) -> Self {
+        self.estimates = estimates;
+        self
+    }
+
+    /// Word shown for packages that finish successfully (default "installed")
+    pub fn finished_as(mut self, done: &'static str) -> Self {
+        self.done = done;
+        self
+    }
+
+    /// Update a package's row
+    pub fn set(&self, index: usize, state: TaskState) {
+        let row = &self.rows[index];
+        match state {
+            TaskState::Installed => row.finish_with_message(format!("✓ {}", self.done)),
+            TaskState::Failed => row.finish_with_message("✗ failed"),
+            TaskState::Queued => row.set_message(state.to_string()),
+            _ => match self.estimates.get(index).copied().flatten() {
+                Some(estimate) => row.set_message(format!(
+                    "{} (usually ~{})",
+                    state,
+                    format_duration(estimate)
+                )),
+                None => row.set_message(state.to_string()),
             },
+        }
+        self.update_overall(index, state);
+
+        if !self.live && state.is_finished() {
+            let (marker, word) = if state == TaskState::Failed {
+                ("✗", "failed")
+            } else {
+                ("✓", self.done)
+            };
+            outln!("  {} {} {}", marker, self.labels[index], word);
+        }
+    }
+
+    /// Advance the combined bar and summarize what is in flight
+    fn update_overall(&self, index: usize, state: TaskState) {
+        let mut states = self.states.lock().unwrap();
+        states[index] = state;
+        let finished = states.iter().filter(|s| s.is_finished()).count();
+        self.overall.set_position(finished as u64);
+        if finished == states.len() {
+            self.overall.finish_and_clear();
+            return;
+        }
+
+        let count = |wanted: TaskState| states.iter().filter(|&&s| s == wanted).count();
+        let active: Vec<String> = [
+            TaskState::Downloading,
+            TaskState::Verifying,
+            TaskState::Installing,
+        ]
+        .into_iter()
+        .map(|s| (count(s), s))
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, s)| format!("{} {}", n, s))
+        .collect();
+        self.overall.set_message(active.join(", "));
+    }
+
+    /// Print a line above the table without garbling it
+    pub fn println(&self, message: String) {
+        if self.live {
+            let _ = self.multi.println(message);
+        } else {
+            outln!("{}", message);
+        }
+    }
+}
+
+/// Binary/source selection for this host, install mode, and engine
+pub fn artifact_selection(
+    install_mode: InstallMode,
+    engine_version: Option<&str>,
+    configuration: &str,
+) -> ArtifactSelection {
+    let platform = unrealpm::platform::detect_platform();
+    ArtifactSelection {
+        mode: install_mode,
+        platforms: match engine_version {
+            Some(engine) => binary_platform_candidates(&platform, engine),
+            None => vec![platform],
+        },
+        engine: engine_version.map(str::to_string),
+        configuration: configuration.to_string(),
+    }
+}
+
+/// Select the best installation source (binary or source) based on availability and preferences
+/// Returns: (tarball_path, checksum, install_type_description, selected_binary)
+pub fn select_installation_source(
+    resolved_version: &unrealpm::PackageVersion,
+    registry: &RegistryClient,
+    package_name: &str,
+    selection: &ArtifactSelection,
+) -> Result<(
+    std::path::PathBuf,
+    String,
+    Option<String>,
+    Option<PrebuiltBinary>,
+)> {
+    if let Some(binary) = selection.select(package_name, resolved_version)? {
+        return Ok((
+            registry.get_tarball_path(package_name, &binary.tarball),
+            binary.checksum.clone(),
+            Some(format!("pre-built binary ({})", binary.target())),
+            Some(binary),
         ));
     }
 
-    unreachable!("Invalid install mode state")
+    Ok((
+        registry.get_tarball_path(package_name, &resolved_version.version),
+        resolved_version.checksum.clone(),
+        if resolved_version.binaries.is_some() {
+            Some("source code".to_string())
+        } else {
+            None // Don't show "using source" if there's no binary option
+        },
+        None,
+    ))
 }
 
-fn format_available_binaries(binaries: &Option<Vec<PrebuiltBinary>>) -> String {
-    if let Some(bins) = binaries {
-        if bins.is_empty() {
-            return "  None".to_string();
-        }
-        bins.iter()
-            .map(|b| format!("  - {}/{}", b.platform, b.engine))
-            .collect::<Vec<_>>()
-            .join("\n")
+/// Print the project's engine version, noting when it came from an engine alias
+fn print_engine_version(indent: &str, association: Option<&str>, mapped: Option<&str>) {
+    match (association, mapped) {
+        (Some(association), Some(mapped)) if association != mapped => outln!(
+            "{}Engine version: {} (mapped from {})",
+            indent,
+            mapped,
+            association
+        ),
+        (Some(association), _) => outln!("{}Engine version: {}", indent, association),
+        _ => {}
+    }
+}
+
+/// Platforms to accept binaries for, in order of preference
+///
+/// The host platform is used when the engine supports it (or the engine's
+/// platform set is unknown). Otherwise fall back to the engine's own target
+/// platforms from config or Engine/Platforms, e.g. a Win64-only engine used
+/// from a Linux shell.
+fn binary_platform_candidates(host_platform: &str, engine_version: &str) -> Vec<String> {
+    let engine_platforms = Config::load()
+        .ok()
+        .and_then(|config| config.find_engine(engine_version))
+        .map(|engine| engine.target_platforms())
+        .unwrap_or_default();
+
+    if engine_platforms.is_empty() || engine_platforms.iter().any(|p| p == host_platform) {
+        vec![host_platform.to_string()]
     } else {
-        "  None".to_string()
+        engine_platforms
     }
 }
 
@@ -748,9 +2702,9 @@ fn format_available_binaries(binaries: &Option<Vec<PrebuiltBinary>>) -> String {
 /// - Uses the lockfile to determine exact versions
 /// - Installs from the global CAS store
 /// - Fails if a package is not in the cache
-fn install_offline(project_dir: &std::path::Path, dry_run: bool) -> Result<()> {
-    println!("Installing in offline mode...");
-    println!();
+fn install_offline(project_dir: &std::path::Path, dry_run: bool, production: bool) -> Result<()> {
+    outln!("Installing in offline mode...");
+    outln!();
 
     // Load lockfile
     let lockfile = match Lockfile::load()? {
@@ -765,18 +2719,33 @@ fn install_offline(project_dir: &std::path::Path, dry_run: bool) -> Result<()> {
     };
 
     if lockfile.packages.is_empty() {
-        println!("Lockfile is empty - nothing to install.");
+        outln!("Lockfile is empty - nothing to install.");
         return Ok(());
     }
 
-    println!("Found {} packages in lockfile", lockfile.packages.len());
-    println!();
+    let pins = Manifest::load(project_dir)
+        .map(|manifest| manifest.dependency_integrity)
+        .unwrap_or_default();
+    let mut mismatches: Vec<Incident> = lockfile
+        .packages
+        .iter()
+        .filter_map(|(name, pkg)| pin_mismatch(&pins, name, &pkg.version, &pkg.checksum))
+        .collect();
+    mismatches.sort_by(|a, b| a.package.cmp(&b.package));
+    report_pin_mismatches(mismatches, unrealpm::LOCKFILE_NAME)?;
+
+    outln!("Found {} packages in lockfile", lockfile.packages.len());
+    outln!();
 
     // Check which packages are in the cache
     let mut cached = Vec::new();
     let mut missing = Vec::new();
 
     for (name, pkg) in &lockfile.packages {
+        // Fab/Marketplace plugins are installed by the Epic Games Launcher
+        if pkg.is_externally_managed() || (production && pkg.dev) {
+            continue;
+        }
         match is_package_in_store(&pkg.checksum) {
             Ok(true) => cached.push((name.clone(), pkg.clone())),
             _ => missing.push((name.clone(), pkg.checksum.clone())),
@@ -784,16 +2753,16 @@ fn install_offline(project_dir: &std::path::Path, dry_run: bool) -> Result<()> {
     }
 
     if !missing.is_empty() {
-        println!("Missing packages (not in cache):");
+        outln!("Missing packages (not in cache):");
         for (name, checksum) in &missing {
             let short_checksum = if checksum.len() > 12 {
                 &checksum[..12]
             } else {
                 checksum
             };
-            println!("  - {} ({}...)", name, short_checksum);
+            outln!("  - {} ({}...)", name, short_checksum);
         }
-        println!();
+        outln!();
         anyhow::bail!(
             "Cannot install offline: {} package(s) not in cache.\n\n\
             Run `unrealpm install` (online) to download the missing packages,\n\
@@ -803,20 +2772,23 @@ fn install_offline(project_dir: &std::path::Path, dry_run: bool) -> Result<()> {
     }
 
     if dry_run {
-        println!(
+        outln!(
             "[DRY RUN] Would install {} packages from cache:",
             cached.len()
         );
         for (name, pkg) in &cached {
-            println!("  - {}@{}", name, pkg.version);
+            outln!("  - {}@{}", name, pkg.version);
         }
-        println!();
-        println!("[DRY RUN] All packages are cached and ready for offline install.");
+        outln!();
+        outln!("[DRY RUN] All packages are cached and ready for offline install.");
         return Ok(());
     }
 
     // Install from cache
     let pb = ProgressBar::new(cached.len() as u64);
+    if unrealpm::output::is_plain() {
+        pb.set_draw_target(indicatif::ProgressDrawTarget::hidden());
+    }
     pb.set_style(
         ProgressStyle::default_bar()
             .template("[{bar:40.cyan/blue}] {pos}/{len} packages")
@@ -824,6 +2796,7 @@ fn install_offline(project_dir: &std::path::Path, dry_run: bool) -> Result<()> {
             .progress_chars("#>-"),
     );
 
+    unrealpm::store::hold_for_install(project_dir)?;
     for (name, pkg) in &cached {
         pb.set_message(format!("Installing {}@{}", name, pkg.version));
 
@@ -831,10 +2804,10 @@ fn install_offline(project_dir: &std::path::Path, dry_run: bool) -> Result<()> {
         let store_path = unrealpm::get_package_store_path(&pkg.checksum)?;
 
         // Link or copy from store to project
-        let plugins_dir = project_dir.join("Plugins");
+        let plugins_dir = unrealpm::plugins_dir(project_dir);
         std::fs::create_dir_all(&plugins_dir)?;
 
-        let target_path = plugins_dir.join(name);
+        let target_path = plugins_dir.join(unrealpm::scope::unscoped(name));
 
         // Find the plugin directory in the store
         let plugin_store_path = find_plugin_in_store(&store_path, name)?;
@@ -842,17 +2815,299 @@ fn install_offline(project_dir: &std::path::Path, dry_run: bool) -> Result<()> {
         // Link or copy
         unrealpm::link_or_copy_from_store(&plugin_store_path, &target_path, None)?;
 
+        // Re-apply local patches and make sure we end up with the locked tree
+        if let Some(patched_hash) = apply_manifest_patches(project_dir, name, &target_path)? {
+            if pkg.patched_hash.as_deref() != Some(patched_hash.as_str()) {
+                pb.println(format!(
+                    "  ⚠ {} patched tree differs from lockfile - run `unrealpm install` online",
+                    name
+                ));
+            }
+        }
+
         pb.inc(1);
     }
 
     pb.finish_with_message("✓ All packages installed from cache");
-    println!();
-    println!("✓ Installed {} packages in offline mode", cached.len());
-    println!();
+    outln!();
+    outln!("✓ Installed {} packages in offline mode", cached.len());
+    outln!();
+
+    Ok(())
+}
+
+/// A package source outside the registry
+enum LocalSource {
+    /// Plugin directory, packed to a temporary tarball before installing
+    Directory(std::path::PathBuf),
+    /// Package tarball created by `unrealpm pack`
+    Tarball(std::path::PathBuf),
+}
+
+/// Install a package from a local directory or tarball, bypassing the registry
+///
+/// The package goes through the same zip-slip validation, checksum, and CAS
+/// install as registry packages. The lockfile records the local source so the
+/// package is not mistaken for a registry release. The manifest is not changed.
+fn install_local(
+    project_dir: &std::path::Path,
+    source: LocalSource,
+    dry_run: bool,
+    timings: &mut Timings,
+) -> Result<()> {
+    match source {
+        LocalSource::Directory(dir) => {
+            outln!("Installing from local directory: {}", dir.display());
+            outln!();
+
+            if !dir.is_dir() {
+                anyhow::bail!("Plugin directory does not exist: {}", dir.display());
+            }
+            let dir = dir.canonicalize()?;
+            unrealpm::UPlugin::find(&dir)?;
+
+            // Pack exactly what `unrealpm publish` would upload
+            outln!("  Packing {}...", dir.display());
+            let tarball_path =
+                env::temp_dir().join(format!("unrealpm-local-{}.tar.gz", std::process::id()));
+            crate::commands::pack::create_tarball(&dir, &tarball_path, false)?;
+
+            let result = install_local_tarball(
+                project_dir,
+                &tarball_path,
+                format!("path+{}", dir.display()),
+                false,
+                dry_run,
+                timings,
+            );
+            let _ = std::fs::remove_file(&tarball_path);
+            result
+        }
+        LocalSource::Tarball(file) => {
+            outln!("Installing from local tarball: {}", file.display());
+            outln!();
+
+            if !file.is_file() {
+                anyhow::bail!("Package tarball does not exist: {}", file.display());
+            }
+            let file = file.canonicalize()?;
+
+            install_local_tarball(
+                project_dir,
+                &file,
+                format!("tarball+{}", file.display()),
+                true,
+                dry_run,
+                timings,
+            )
+        }
+    }
+}
+
+fn install_local_tarball(
+    project_dir: &std::path::Path,
+    tarball_path: &std::path::Path,
+    source: String,
+    check_signature: bool,
+    dry_run: bool,
+    timings: &mut Timings,
+) -> Result<()> {
+    // Validate archive paths and read metadata without extracting
+    outln!("  Validating package...");
+    timings.time(Phase::Verify, || validate_tarball_entries(tarball_path))?;
+    let (package_name, uplugin) = read_tarball_uplugin(tarball_path)?;
+    let version = uplugin.version_name.clone();
+    outln!("  ✓ {}@{}", package_name, version);
+
+    let checksum = timings.time(Phase::Verify, || calculate_checksum(tarball_path))?;
+    outln!("  Checksum: {}", checksum);
+
+    if check_signature {
+        let config = Config::load()?;
+        timings.time(Phase::Verify, || {
+            verify_local_signature(tarball_path, &package_name, &version, &config)
+        })?;
+    }
+
+    if dry_run {
+        outln!(
+            "  [DRY RUN] Would install to: {}/Plugins/{}",
+            project_dir.display(),
+            package_name
+        );
+        if let Ok(manifest) = Manifest::load(project_dir) {
+            if let Some(patch_dir) = manifest.patches.get(&package_name) {
+                outln!("  [DRY RUN] Would apply patches from {}", patch_dir);
+            }
+        }
+        outln!(
+            "  [DRY RUN] Would update lockfile (unrealpm.lock) with source {}",
+            source
+        );
+        outln!();
+        outln!(
+            "[DRY RUN] Would successfully install {}@{}",
+            package_name,
+            version
+        );
+        outln!();
+        return Ok(());
+    }
+
+    // Install package using CAS with progress spinner
+    let progress = Some(create_spinner_sink());
+    timings
+        .store_cache
+        .record(is_package_in_store(&checksum).unwrap_or(false));
+    let installed_path = timings.time(Phase::Extract, || {
+        install_package_cas(
+            tarball_path,
+            project_dir,
+            &package_name,
+            &checksum,
+            progress,
+        )
+    })?;
+    outln!("  ✓ Installed to {}", installed_path.display());
+    let patched_hash = apply_manifest_patches(project_dir, &package_name, &installed_path)?;
+
+    outln!("  Updating lockfile...");
+    let mut lockfile = Lockfile::load()?.unwrap_or_default();
+    lockfile.update_package(package_name.clone(), version.clone(), checksum, None);
+    lockfile.set_patched_hash(&package_name, patched_hash);
+    lockfile.set_source(&package_name, Some(source));
+    lockfile.save()?;
+    outln!("  ✓ Lockfile updated");
+
+    outln!();
+    outln!(
+        "✓ Successfully installed {}@{} (local)",
+        package_name,
+        version
+    );
+    outln!("  unrealpm.json was not changed; publish the package to add it as a dependency.");
+    outln!();
+
+    Ok(())
+}
+
+/// Verify the detached signature next to a local tarball, if there is one
+///
+/// Looks for `<name>.tar.sig` (as written by `unrealpm publish`) or
+/// `<name>.tar.gz.sig`, and checks it against the local public key.
+fn verify_local_signature(
+    tarball_path: &std::path::Path,
+    package_name: &str,
+    version: &str,
+    config: &Config,
+) -> Result<()> {
+    let mut appended = tarball_path.as_os_str().to_owned();
+    appended.push(".sig");
+    let sig_path = [tarball_path.with_extension("sig"), appended.into()]
+        .into_iter()
+        .find(|p: &std::path::PathBuf| p.is_file());
+
+    let Some(sig_path) = sig_path else {
+        if config.verification.require_signatures {
+            return Err(unrealpm::Error::SignatureVerificationFailed(format!(
+                "Signature verification required but no signature found for {}@{}\n\n\
+                Solutions:\n\
+                • Place the .sig file next to the tarball\n\
+                • Install from the plugin directory instead: unrealpm install --path <dir>\n\
+                • Disable signature requirement: unrealpm config set verification.require_signatures false",
+                package_name,
+                version
+            ))
+            .into());
+        }
+        unrealpm::strict::warn("No signature found (unsigned local package)")?;
+        return Ok(());
+    };
+
+    outln!("  Verifying signature...");
+    let (_, public_key_path) = config.signing.key_paths();
+    let public_key = unrealpm::load_public_key_hex(&public_key_path).map_err(|e| {
+        anyhow::anyhow!(
+            "Cannot verify {}: {}\n\n\
+            Local tarballs are verified against your public key ({}).\n\
+            Generate keys with: unrealpm keys generate",
+            sig_path.display(),
+            e,
+            public_key_path.display()
+        )
+    })?;
+
+    let tarball_bytes = std::fs::read(tarball_path)?;
+    let signature_bytes = std::fs::read(&sig_path)?;
+    if verify_signature(&tarball_bytes, &signature_bytes, &public_key)? {
+        outln!(
+            "  ✓ Signature verified (publisher: {}...)",
+            &public_key[..16]
+        );
+    } else if config.verification.strict_verification {
+        return Err(unrealpm::Error::SignatureVerificationFailed(format!(
+            "Signature verification FAILED for {}@{}\n\n\
+            The signature in {} does not match the tarball and your public key.\n\
+            For your security, installation has been aborted.",
+            package_name,
+            version,
+            sig_path.display()
+        ))
+        .into());
+    } else {
+        unrealpm::strict::warn(format!(
+            "WARNING: Signature verification failed for {}@{} - continuing anyway (strict_verification=false)",
+            package_name, version
+        ))?;
+    }
 
     Ok(())
 }
 
+/// Refresh `Plugins/.unrealpm-status.json` for the editor
+///
+/// Best effort: a failure is reported but never fails the command.
+pub fn write_status_file(project_dir: &std::path::Path, operation: &str) {
+    if !Manifest::exists(project_dir) {
+        return;
+    }
+    if let Err(e) =
+        StatusFile::compute(project_dir, operation).and_then(|status| status.save(project_dir))
+    {
+        eoutln!("⚠ Failed to write {}: {}", unrealpm::STATUS_FILE_NAME, e);
+    }
+}
+
+/// Apply local patches from the manifest's `patches` section to an installed package
+///
+/// Returns the patched tree hash to record in the lockfile, or `None` if the
+/// package has no patches configured.
+pub fn apply_manifest_patches(
+    project_dir: &std::path::Path,
+    package_name: &str,
+    installed_path: &std::path::Path,
+) -> Result<Option<String>> {
+    let Ok(manifest) = Manifest::load(project_dir) else {
+        return Ok(None);
+    };
+    let Some(patch_dir) = manifest.patches.get(package_name) else {
+        return Ok(None);
+    };
+
+    let patch_dir = project_dir.join(patch_dir);
+    let count = unrealpm::patch::list_patches(&patch_dir)?.len();
+    let patched_hash = unrealpm::patch::apply_patches(&patch_dir, installed_path)?;
+
+    outln!(
+        "  ✓ Applied {} patch{} to {}",
+        count,
+        if count == 1 { "" } else { "es" },
+        package_name
+    );
+
+    Ok(Some(patched_hash))
+}
+
 /// Find the plugin directory within a store path
 fn find_plugin_in_store(
     store_path: &std::path::Path,
@@ -1,26 +1,48 @@
-use anyhow::Result;
-use std::path::PathBuf;
-use unrealpm::{Config, PackageSigningKey};
+//! Keys commands - manage the keys packages are signed with
+//!
+//! A machine can hold several named signing keys; `signing.key` in the user
+//! config selects the one `publish` signs with. `keys rotate` replaces the
+//! active key, `keys export`/`keys import` move a key between machines
+//! encrypted with a passphrase.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use unrealpm::signing::{validate_key_name, DEFAULT_KEY_NAME};
+use unrealpm::{verify_signature, Config, KeyEndorsement, PackageSigningKey, RegistryClient};
+
+/// Environment variable holding the passphrase for `keys export`/`import`
+const PASSPHRASE_ENV: &str = "UNREALPM_KEY_PASSPHRASE";
 
 pub fn run(action: &crate::KeysAction) -> Result<()> {
     match action {
-        crate::KeysAction::Generate => generate(),
+        crate::KeysAction::Generate { name } => generate(name.as_deref()),
         crate::KeysAction::Show => show(),
+        crate::KeysAction::List => list(),
+        crate::KeysAction::Use { name } => use_key(name),
+        crate::KeysAction::Rotate { name, packages } => rotate(name.as_deref(), packages),
+        crate::KeysAction::Export { name, output } => export(name.as_deref(), output.as_deref()),
+        crate::KeysAction::Import {
+            file,
+            name,
+            activate,
+        } => import(file, name.as_deref(), *activate),
     }
 }
 
-fn generate() -> Result<()> {
+fn generate(name: Option<&str>) -> Result<()> {
     println!("Generating new signing keys...");
     println!();
 
     // Load config to get key paths
     let config = Config::load()?;
 
-    // Expand tilde in paths
-    let private_key_path =
-        PathBuf::from(shellexpand::tilde(&config.signing.private_key_path).to_string());
-    let public_key_path =
-        PathBuf::from(shellexpand::tilde(&config.signing.public_key_path).to_string());
+    if let Some(name) = name {
+        validate_key_name(name)?;
+    }
+    let (private_key_path, public_key_path) = match name {
+        Some(name) => config.signing.named_key_paths(name),
+        None => config.signing.key_paths(),
+    };
 
     // Check if keys already exist
     if private_key_path.exists() || public_key_path.exists() {
@@ -34,12 +56,11 @@ fn generate() -> Result<()> {
             println!("  • Public key: {}", public_key_path.display());
         }
         println!();
-        println!("To regenerate keys:");
-        println!("  1. Back up your existing keys");
-        println!("  2. Delete the old keys");
-        println!("  3. Run 'unrealpm keys generate' again");
+        println!("To replace them, rotate to a new key instead:");
+        println!("  unrealpm keys rotate <package>...");
         println!();
-        println!("⚠  WARNING: Regenerating keys will invalidate all previously signed packages!");
+        println!("Or generate an additional key:");
+        println!("  unrealpm keys generate --name <name>");
         println!();
         return Ok(());
     }
@@ -85,11 +106,10 @@ fn show() -> Result<()> {
     // Load config to get key paths
     let config = Config::load()?;
 
-    // Expand tilde in paths
-    let private_key_path =
-        PathBuf::from(shellexpand::tilde(&config.signing.private_key_path).to_string());
-    let public_key_path =
-        PathBuf::from(shellexpand::tilde(&config.signing.public_key_path).to_string());
+    let (private_key_path, public_key_path) = config.signing.key_paths();
+
+    println!("Active key: {}", config.signing.key_name());
+    println!();
 
     // Check if keys exist
     if !private_key_path.exists() && !public_key_path.exists() {
@@ -167,3 +187,293 @@ fn show() -> Result<()> {
 
     Ok(())
 }
+
+fn list() -> Result<()> {
+    let config = Config::load()?;
+    let names = config.signing.key_names();
+    if names.is_empty() {
+        println!("No signing keys found");
+        println!();
+        println!("Generate keys with:");
+        println!("  unrealpm keys generate");
+        return Ok(());
+    }
+
+    println!("Signing keys:");
+    println!();
+    for name in names {
+        let (private_key_path, public_key_path) = config.signing.named_key_paths(&name);
+        let public_key = PackageSigningKey::load_from_files(&private_key_path, &public_key_path)
+            .map(|keys| keys.public_key_hex())
+            .unwrap_or_else(|e| format!("(unreadable: {})", e));
+        let marker = if name == config.signing.key_name() {
+            "*"
+        } else {
+            " "
+        };
+        println!("{} {:<20} {}", marker, name, public_key);
+    }
+    println!();
+    println!("* = used for signing. Switch with: unrealpm keys use <name>");
+    Ok(())
+}
+
+fn use_key(name: &str) -> Result<()> {
+    validate_key_name(name)?;
+    let mut config = Config::load()?;
+    let (private_key_path, public_key_path) = config.signing.named_key_paths(name);
+    let keys = PackageSigningKey::load_from_files(&private_key_path, &public_key_path)
+        .with_context(|| format!("No usable signing key named '{}'", name))?;
+
+    config.signing.key = (name != DEFAULT_KEY_NAME).then(|| name.to_string());
+    config.save()?;
+    println!("✓ Signing with key '{}'", name);
+    println!("  Public key: {}", keys.public_key_hex());
+    Ok(())
+}
+
+/// Replace the active key with a new one
+///
+/// The old key endorses the new one, the new public key is registered with
+/// the registry, and the latest version of each given package is re-signed
+/// with the new key. Re-running with the same `--name` resumes an
+/// interrupted rotation.
+fn rotate(name: Option<&str>, packages: &[String]) -> Result<()> {
+    let mut config = Config::load()?;
+    let old_name = config.signing.key_name().to_string();
+    let (old_private, old_public) = config.signing.key_paths();
+    let old_keys =
+        PackageSigningKey::load_from_files(&old_private, &old_public).with_context(|| {
+            format!(
+                "No signing key '{}' to rotate from\n\nGenerate one with: unrealpm keys generate",
+                old_name
+            )
+        })?;
+
+    let new_name = match name {
+        Some(name) => name.to_string(),
+        None => format!("key-{}", chrono::Utc::now().format("%Y%m%d")),
+    };
+    validate_key_name(&new_name)?;
+    if new_name == old_name {
+        anyhow::bail!(
+            "'{}' is already the active key; pick another name with --name",
+            new_name
+        );
+    }
+
+    println!("Rotating signing key '{}' -> '{}'...", old_name, new_name);
+    println!();
+    let (new_private, new_public) = config.signing.named_key_paths(&new_name);
+    let new_keys = if new_private.exists() {
+        println!("  Resuming with the existing key '{}'", new_name);
+        PackageSigningKey::load_from_files(&new_private, &new_public)?
+    } else {
+        let keys = PackageSigningKey::generate()?;
+        keys.save_to_files(&new_private, &new_public)?;
+        println!("  ✓ Generated {}", new_private.display());
+        keys
+    };
+    let endorsement = old_keys.endorse_key(&new_keys.public_key_hex()).to_bytes();
+
+    let registry = RegistryClient::from_config(&config)?;
+    match &registry {
+        RegistryClient::Http(client) => {
+            client.register_signing_key(
+                &new_keys.public_key_hex(),
+                Some((&old_keys.public_key_hex(), &endorsement)),
+            )?;
+            println!(
+                "  ✓ Registered the new public key with {}",
+                registry.location()
+            );
+        }
+        RegistryClient::File(_) => {
+            println!("  File registries keep no account keys; skipped key upload");
+        }
+    }
+
+    for package in packages {
+        let (version, resigned) = resign_latest(&registry, &old_keys, &new_keys, package)?;
+        if resigned {
+            println!("  ✓ Re-signed {}@{}", package, version);
+        } else {
+            println!(
+                "  ✓ {}@{} is already signed with the new key",
+                package, version
+            );
+        }
+    }
+
+    config.signing.key = (new_name != DEFAULT_KEY_NAME).then(|| new_name.clone());
+    config.save()?;
+
+    println!();
+    println!("✓ Now signing with '{}'", new_name);
+    println!("  New public key: {}", new_keys.public_key_hex());
+    println!("  Endorsed by:    {}", old_keys.public_key_hex());
+    println!("  Endorsement:    {}", hex::encode(endorsement));
+    println!();
+    println!(
+        "The old key stays in place as '{}'; remove it once you no longer",
+        old_name
+    );
+    println!("need it. Users who pinned the old key accept the new one on re-signed");
+    println!("versions; elsewhere they can check it against the endorsement above.");
+    Ok(())
+}
+
+/// Re-sign the newest stable, unyanked version of `package` with `new_keys`
+///
+/// Returns the version, and false if an earlier run already re-signed it.
+/// Only a version whose current signature verifies with `old_keys` is
+/// re-signed, so rotating never vouches for a tarball the publisher didn't
+/// sign.
+fn resign_latest(
+    registry: &RegistryClient,
+    old_keys: &PackageSigningKey,
+    new_keys: &PackageSigningKey,
+    package: &str,
+) -> Result<(String, bool)> {
+    let metadata = registry.get_package(package)?;
+    let yanked: Vec<String> = match registry {
+        RegistryClient::Http(client) => client
+            .list_versions(package)?
+            .into_iter()
+            .filter(|v| v.yanked)
+            .map(|v| v.version)
+            .collect(),
+        RegistryClient::File(_) => Vec::new(),
+    };
+    let latest = metadata
+        .versions
+        .iter()
+        .filter(|v| !yanked.contains(&v.version))
+        .filter_map(|v| Some((semver::Version::parse(&v.version).ok()?, v)))
+        .filter(|(version, _)| version.pre.is_empty())
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, v)| v)
+        .with_context(|| format!("{} has no stable, unyanked versions to re-sign", package))?;
+
+    let tarball = registry.fetch_tarball(package, &latest.version, &latest.checksum)?;
+    let tarball_bytes = std::fs::read(&tarball)?;
+    let existing = registry
+        .download_signature(package, &latest.version)
+        .and_then(|path| Ok(std::fs::read(path)?))
+        .with_context(|| {
+            format!(
+                "{}@{} has no signature to carry over to the new key",
+                package, latest.version
+            )
+        })?;
+    if verify_signature(&tarball_bytes, &existing, &new_keys.public_key_hex())? {
+        return Ok((latest.version.clone(), false));
+    }
+    if !verify_signature(&tarball_bytes, &existing, &old_keys.public_key_hex())? {
+        anyhow::bail!(
+            "{}@{} is not signed by the key being rotated out; refusing to re-sign it",
+            package,
+            latest.version
+        );
+    }
+
+    let signature = new_keys.sign(&tarball_bytes);
+    let endorsement = KeyEndorsement {
+        previous_key: old_keys.public_key_hex(),
+        signature: hex::encode(old_keys.endorse_key(&new_keys.public_key_hex()).to_bytes()),
+    };
+    registry.replace_signature(
+        package,
+        &latest.version,
+        &signature.to_bytes(),
+        &new_keys.public_key_hex(),
+        Some(&endorsement),
+    )?;
+    Ok((latest.version.clone(), true))
+}
+
+fn export(name: Option<&str>, output: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    let name = name.unwrap_or(config.signing.key_name());
+    validate_key_name(name)?;
+    let (private_key_path, public_key_path) = config.signing.named_key_paths(name);
+    let keys = PackageSigningKey::load_from_files(&private_key_path, &public_key_path)
+        .with_context(|| format!("No usable signing key named '{}'", name))?;
+
+    let passphrase = read_passphrase(true)?;
+    let exported = keys.export_encrypted(name, &passphrase)?;
+    match output {
+        None => print!("{}", exported),
+        Some(path) => {
+            write_private(Path::new(path), &exported)?;
+            eprintln!("✓ Exported key '{}' to {}", name, path);
+            eprintln!(
+                "  Import it on the other machine with: unrealpm keys import {}",
+                path
+            );
+        }
+    }
+    Ok(())
+}
+
+fn import(file: &str, name: Option<&str>, activate: bool) -> Result<()> {
+    let exported =
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+    let passphrase = read_passphrase(false)?;
+    let (exported_name, keys) = PackageSigningKey::import_encrypted(&exported, &passphrase)?;
+    let name = name.unwrap_or(&exported_name);
+    validate_key_name(name)?;
+
+    let mut config = Config::load()?;
+    let (private_key_path, public_key_path) = config.signing.named_key_paths(name);
+    if private_key_path.exists() {
+        anyhow::bail!(
+            "A signing key named '{}' already exists ({})\n\nImport under another name with --name",
+            name,
+            private_key_path.display()
+        );
+    }
+    keys.save_to_files(&private_key_path, &public_key_path)?;
+    println!("✓ Imported key '{}'", name);
+    println!("  Public key: {}", keys.public_key_hex());
+
+    if activate {
+        config.signing.key = (name != DEFAULT_KEY_NAME).then(|| name.to_string());
+        config.save()?;
+        println!("  ✓ Now signing with '{}'", name);
+    } else if name != config.signing.key_name() {
+        println!("  Sign with it: unrealpm keys use {}", name);
+    }
+    Ok(())
+}
+
+/// Passphrase from UNREALPM_KEY_PASSPHRASE, or asked for (twice when `confirm`)
+fn read_passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    let passphrase =
+        rpassword::prompt_password("Passphrase: ").context("Failed to read passphrase")?;
+    if confirm {
+        if passphrase.len() < 8 {
+            anyhow::bail!("Use a passphrase of at least 8 characters");
+        }
+        let again = rpassword::prompt_password("Confirm passphrase: ")
+            .context("Failed to read passphrase")?;
+        if again != passphrase {
+            anyhow::bail!("Passphrases do not match");
+        }
+    }
+    Ok(passphrase)
+}
+
+/// Write `content` readable by the owner only
+fn write_private(path: &Path, content: &str) -> Result<()> {
+    std::fs::write(path, content)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Licenses command - report the license of every locked package
+//!
+//! Uses the license install recorded in unrealpm.lock, or reads it from the
+//! installed plugin for entries locked before licenses were recorded. With
+//! a `[policy] allowed_licenses` list, packages it doesn't allow are flagged.
+
+use anyhow::Result;
+use unrealpm::license::{self, PackageLicense};
+use unrealpm::{Config, Lockfile, LOCKFILE_NAME};
+
+use crate::LicenseFormat;
+
+pub fn run(format: LicenseFormat) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let Some(lockfile) = Lockfile::load_from(current_dir.join(LOCKFILE_NAME))? else {
+        anyhow::bail!(
+            "No unrealpm.lock found in {}\n\n\
+            • Run 'unrealpm install' first",
+            current_dir.display()
+        );
+    };
+
+    // Fab/Marketplace plugins are covered by the Fab EULA
+    let mut licenses: Vec<PackageLicense> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, locked)| !locked.is_externally_managed())
+        .map(|(name, locked)| PackageLicense {
+            name: name.clone(),
+            version: locked.version.clone(),
+            license: locked.license.clone().or_else(|| {
+                unrealpm::installed_plugin_dir(&current_dir, name, locked.artifact_checksum())
+                    .and_then(license::from_dir)
+            }),
+        })
+        .collect();
+    licenses.sort_by(|a, b| a.name.cmp(&b.name));
+
+    match format {
+        LicenseFormat::Json => println!("{}", license::render_json(&licenses)?),
+        LicenseFormat::Csv => print!("{}", license::render_csv(&licenses)),
+        LicenseFormat::Text => {
+            print!("{}", license::render_text(&licenses));
+
+            let allowed = Config::load()?.allowed_licenses(&current_dir);
+            if allowed.is_empty() {
+                return Ok(());
+            }
+            let denied: Vec<String> = licenses
+                .iter()
+                .filter(|entry| {
+                    !entry
+                        .license
+                        .as_deref()
+                        .is_some_and(|found| license::is_allowed(found, &allowed))
+                })
+                .map(|entry| {
+                    format!(
+                        "{} ({})",
+                        entry.name,
+                        entry.license.as_deref().unwrap_or("unknown")
+                    )
+                })
+                .collect();
+            println!();
+            if denied.is_empty() {
+                println!("✓ All licenses are allowed by the license policy");
+            } else {
+                unrealpm::strict::warn(format!(
+                    "Not allowed by the license policy: {}",
+                    denied.join(", ")
+                ))?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Link command - use a plugin's working copy in a project, like `npm link`
+//!
+//! In a plugin folder, `unrealpm link` registers the plugin in
+//! `~/.unrealpm/links.json`. In a game project, `unrealpm link <plugin>`
+//! links the registered working copy into Plugins/ in place of the registry
+//! package. unrealpm.json and unrealpm.lock are not changed, and `install`
+//! and `update` leave the link alone until `unrealpm unlink`.
+
+use anyhow::Result;
+use std::env;
+use std::path::Path;
+use unrealpm::link::{self, GlobalLinks};
+use unrealpm::{
+    find_plugin_dir, outln, remove_installed_package, DryRun, Lockfile, Manifest, UPlugin,
+    LOCKFILE_NAME,
+};
+
+pub fn run(plugin: Option<String>) -> Result<()> {
+    let current_dir = env::current_dir()?;
+    let mut links = GlobalLinks::open_default()?;
+
+    match plugin {
+        None => register(&current_dir, &mut links),
+        Some(name) => link_into_project(&current_dir, &links, &name),
+    }
+}
+
+/// Register the plugin in `dir` so projects can link it by name
+fn register(dir: &Path, links: &mut GlobalLinks) -> Result<()> {
+    let Ok(uplugin_path) = UPlugin::find(dir) else {
+        anyhow::bail!(
+            "No .uplugin found in {}\n\n\
+            • Register a plugin: run 'unrealpm link' in its folder\n\
+            • Use a registered plugin: run 'unrealpm link <plugin>' in a project",
+            dir.display()
+        );
+    };
+    let name = UPlugin::name(&uplugin_path)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine plugin name from file"))?;
+    let dir = dir.canonicalize()?;
+
+    let previous = links.register(&name, dir.clone());
+    links.save()?;
+    outln!("✓ Registered {} -> {}", name, dir.display());
+    if let Some(previous) = previous.filter(|previous| *previous != dir) {
+        outln!("  (previously {})", previous.display());
+    }
+    outln!("  • Use it in a project: unrealpm link {}", name);
+    Ok(())
+}
+
+/// Link a registered plugin into the project's Plugins/ folder
+fn link_into_project(project_dir: &Path, links: &GlobalLinks, name: &str) -> Result<()> {
+    let Some((name, dir)) = links.get(name) else {
+        let registered: Vec<&str> = links.iter().map(|(name, _)| name).collect();
+        anyhow::bail!(
+            "{} is not registered for linking{}\n\n\
+            • Register it: run 'unrealpm link' in the plugin's folder",
+            name,
+            if registered.is_empty() {
+                String::new()
+            } else {
+                format!(" (registered: {})", registered.join(", "))
+            }
+        );
+    };
+    if !dir.is_dir() {
+        anyhow::bail!(
+            "{} was registered from {}, which no longer exists\n\n\
+            • Register it from its new location: run 'unrealpm link' in the plugin's folder",
+            name,
+            dir.display()
+        );
+    }
+    if let Ok(manifest) = Manifest::load(project_dir) {
+        if let Some(path) = manifest.path_dependencies.get(name) {
+            anyhow::bail!(
+                "{} is already a path dependency in {} ({})",
+                name,
+                Manifest::file_name(project_dir),
+                path
+            );
+        }
+    }
+
+    // An installed registry copy makes way; `unlink` and `install` restore it
+    let plugins_dir = unrealpm::plugins_dir(project_dir);
+    let link_path = plugins_dir.join(name);
+    if let Some(installed) = find_plugin_dir(&plugins_dir, name).filter(|d| !link::is_link(d)) {
+        let lockfile = Lockfile::load_from(project_dir.join(LOCKFILE_NAME))?;
+        let Some(locked) = lockfile.as_ref().and_then(|l| l.get_package(name)) else {
+            anyhow::bail!(
+                "{} is not managed by unrealpm\n\n\
+                • Move it out of Plugins/ to link {} in its place",
+                installed.display(),
+                dir.display()
+            );
+        };
+        remove_installed_package(project_dir, name, DryRun::OFF)?;
+        outln!(
+            "  Removed installed {}@{} ({})",
+            name,
+            locked.version,
+            installed.display()
+        );
+    }
+
+    link::link_dir(dir, &link_path)?;
+    outln!("✓ Linked {} -> {}", link_path.display(), dir.display());
+    outln!("  unrealpm.json and unrealpm.lock were not changed");
+    outln!(
+        "  • Go back to the registry package: unrealpm unlink {}",
+        name
+    );
+    Ok(())
+}
@@ -1,10 +1,49 @@
 use anyhow::Result;
 use std::env;
-use unrealpm::Manifest;
+use std::fs;
+use std::path::{Path, PathBuf};
+use unrealpm::disk::format_bytes;
+use unrealpm::link;
+use unrealpm::{Lockfile, Manifest};
 
-pub fn run() -> Result<()> {
+use crate::commands::cache::dir_size;
+
+/// Plugin subfolders broken out in `list --tree-size`; everything else is "other"
+const SIZE_FOLDERS: [&str; 4] = ["Binaries", "Content", "Source", "Intermediate"];
+
+/// Only suggest reclaiming space above this many bytes
+const SUGGESTION_THRESHOLD: u64 = 100 * 1024 * 1024;
+
+/// Disk usage of one installed plugin
+struct PluginSize {
+    name: String,
+    version: String,
+    /// Sizes of [`SIZE_FOLDERS`], in the same order
+    folders: [u64; 4],
+    other: u64,
+}
+
+impl PluginSize {
+    fn total(&self) -> u64 {
+        self.folders.iter().sum::<u64>() + self.other
+    }
+
+    fn folder(&self, name: &str) -> u64 {
+        SIZE_FOLDERS
+            .iter()
+            .position(|f| *f == name)
+            .map(|i| self.folders[i])
+            .unwrap_or(0)
+    }
+}
+
+pub fn run(tree_size: bool) -> Result<()> {
     let current_dir = env::current_dir()?;
 
+    if tree_size {
+        return run_tree_size(&current_dir);
+    }
+
     // Try to load the manifest
     let manifest = match Manifest::load(&current_dir) {
         Ok(m) => m,
@@ -16,8 +55,24 @@ pub fn run() -> Result<()> {
         }
     };
 
+    // Fab/Marketplace plugins pinned in the lockfile
+    let mut external: Vec<_> = Lockfile::load()
+        .ok()
+        .flatten()
+        .map(|lf| {
+            lf.packages
+                .into_iter()
+                .filter(|(_, pkg)| pkg.is_externally_managed())
+                .collect()
+        })
+        .unwrap_or_default();
+    external.sort_by(|a, b| a.0.cmp(&b.0));
+
     // Check if there are any dependencies
-    let total_deps = manifest.dependencies.len() + manifest.dev_dependencies.len();
+    let total_deps = manifest.dependencies.len()
+        + manifest.path_dependencies.len()
+        + manifest.dev_dependencies.len()
+        + external.len();
 
     if total_deps == 0 {
         println!("No packages installed.");
@@ -35,6 +90,27 @@ pub fn run() -> Result<()> {
         println!();
     }
 
+    // Display path dependencies, linked from a local directory
+    if !manifest.path_dependencies.is_empty() {
+        let mut linked: Vec<_> = manifest.path_dependencies.iter().collect();
+        linked.sort();
+        println!("Path Dependencies (linked):");
+        for (name, path) in linked {
+            println!("  {} -> {} (path)", name, path);
+        }
+        println!();
+    }
+
+    // Display working copies linked with `unrealpm link`
+    let linked = linked_plugins(&current_dir, &manifest);
+    if !linked.is_empty() {
+        println!("Linked (unrealpm link):");
+        for (name, target) in &linked {
+            println!("  {} -> {} (link)", name, target.display());
+        }
+        println!();
+    }
+
     // Display dev dependencies
     if !manifest.dev_dependencies.is_empty() {
         println!("Dev Dependencies:");
@@ -44,6 +120,20 @@ pub fn run() -> Result<()> {
         println!();
     }
 
+    // Display externally managed plugins
+    if !external.is_empty() {
+        println!("Externally Managed (Fab/Marketplace):");
+        for (name, pkg) in &external {
+            println!(
+                "  {} @ {} ({})",
+                name,
+                pkg.version,
+                pkg.source.as_deref().unwrap_or_default()
+            );
+        }
+        println!();
+    }
+
     // Summary
     println!(
         "Total: {} package{}",
@@ -53,3 +143,210 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
+
+/// Links in Plugins/ made by `unrealpm link` (not path dependencies), sorted
+fn linked_plugins(project_dir: &Path, manifest: &Manifest) -> Vec<(String, PathBuf)> {
+    let mut linked: Vec<(String, PathBuf)> = fs::read_dir(unrealpm::plugins_dir(project_dir))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let target = link::link_target(&entry.path())?;
+            (!manifest.path_dependencies.contains_key(&name)).then_some((name, target))
+        })
+        .collect();
+    linked.sort();
+    linked
+}
+
+/// Show how much disk space each installed plugin uses, largest first
+fn run_tree_size(project_dir: &Path) -> Result<()> {
+    let lockfile = Lockfile::load()?.ok_or_else(|| {
+        anyhow::anyhow!("No lockfile found (unrealpm.lock). Run `unrealpm install` first.")
+    })?;
+
+    let mut sizes: Vec<PluginSize> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, locked)| !locked.is_externally_managed())
+        .filter_map(|(name, locked)| {
+            let plugin_dir =
+                unrealpm::installed_plugin_dir(project_dir, name, locked.artifact_checksum())?;
+            Some(measure(name, &locked.version, &plugin_dir))
+        })
+        .collect();
+
+    if sizes.is_empty() {
+        println!("No managed plugins installed.");
+        println!();
+        println!("Install packages with: unrealpm install");
+        return Ok(());
+    }
+    sizes.sort_by(|a, b| b.total().cmp(&a.total()).then(a.name.cmp(&b.name)));
+
+    let headers = [
+        "PLUGIN",
+        "TOTAL",
+        "BINARIES",
+        "CONTENT",
+        "SOURCE",
+        "INTERMEDIATE",
+        "OTHER",
+    ];
+
+    let rows: Vec<Vec<String>> = sizes
+        .iter()
+        .map(|size| {
+            let mut row = vec![
+                format!("{}@{}", size.name, size.version),
+                format_bytes(size.total()),
+            ];
+            row.extend(size.folders.iter().map(|&bytes| size_cell(bytes)));
+            row.push(size_cell(size.other));
+            row
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let print_row = |cells: &[&str]| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                // Names left-aligned, sizes right-aligned
+                if i == 0 {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("  {}", line.trim_end());
+    };
+
+    println!("Disk usage of installed plugins:");
+    println!();
+    print_row(&headers);
+    for row in &rows {
+        print_row(&row.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+
+    let total: u64 = sizes.iter().map(PluginSize::total).sum();
+    println!();
+    println!(
+        "Total: {} across {} plugin{}",
+        format_bytes(total),
+        sizes.len(),
+        if sizes.len() == 1 { "" } else { "s" }
+    );
+
+    print_suggestions(&sizes);
+    Ok(())
+}
+
+/// Measure a plugin directory, broken down by top-level subfolder
+fn measure(name: &str, version: &str, plugin_dir: &Path) -> PluginSize {
+    let mut size = PluginSize {
+        name: name.to_string(),
+        version: version.to_string(),
+        folders: [0; 4],
+        other: 0,
+    };
+
+    for entry in fs::read_dir(plugin_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let bytes = if path.is_dir() {
+            dir_size(&path)
+        } else {
+            fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+        };
+
+        let folder = entry.file_name().to_string_lossy().to_string();
+        match SIZE_FOLDERS
+            .iter()
+            .position(|f| f.eq_ignore_ascii_case(&folder))
+        {
+            Some(i) => size.folders[i] += bytes,
+            None => size.other += bytes,
+        }
+    }
+
+    size
+}
+
+fn size_cell(bytes: u64) -> String {
+    if bytes == 0 {
+        "-".to_string()
+    } else {
+        format_bytes(bytes)
+    }
+}
+
+/// Point out the biggest ways to reclaim space
+fn print_suggestions(sizes: &[PluginSize]) {
+    let mut suggestions = Vec::new();
+
+    let intermediate: Vec<&PluginSize> = sizes
+        .iter()
+        .filter(|s| s.folder("Intermediate") > 0)
+        .collect();
+    let intermediate_total: u64 = intermediate.iter().map(|s| s.folder("Intermediate")).sum();
+    if intermediate_total >= SUGGESTION_THRESHOLD {
+        suggestions.push(format!(
+            "Delete Intermediate/ build output in {} plugin(s) to free {}; it is regenerated on the next build",
+            intermediate.len(),
+            format_bytes(intermediate_total)
+        ));
+    }
+
+    // Prebuilt binaries next to the source can be rebuilt locally
+    let rebuildable: Vec<&PluginSize> = sizes
+        .iter()
+        .filter(|s| s.folder("Binaries") > 0 && s.folder("Source") > 0)
+        .collect();
+    let binaries_total: u64 = rebuildable.iter().map(|s| s.folder("Binaries")).sum();
+    if binaries_total >= SUGGESTION_THRESHOLD {
+        suggestions.push(format!(
+            "Purge binaries: {} in Binaries/ of {} plugin(s) that also ship Source/. \
+            Install from source with `unrealpm config set install.mode source-only` \
+            and reinstall",
+            format_bytes(binaries_total),
+            rebuildable.len()
+        ));
+    }
+
+    // One plugin dominating the project is worth a closer look
+    let total: u64 = sizes.iter().map(PluginSize::total).sum();
+    if let Some(largest) = sizes.first() {
+        if sizes.len() > 1 && largest.total() >= SUGGESTION_THRESHOLD && largest.total() * 2 > total
+        {
+            suggestions.push(format!(
+                "{} alone uses {} ({}%); see what pulls it in with `unrealpm why {}`",
+                largest.name,
+                format_bytes(largest.total()),
+                largest.total() * 100 / total,
+                largest.name
+            ));
+        }
+    }
+
+    if suggestions.is_empty() {
+        return;
+    }
+    println!();
+    println!("Suggestions:");
+    for suggestion in suggestions {
+        println!("  • {}", suggestion);
+    }
+}
@@ -0,0 +1,303 @@
+//! Lock command - inspect unrealpm.lock
+//!
+//! Subcommands:
+//! - diff: Compare two lockfiles (by default the committed lockfile at git
+//!   HEAD against the working copy) and list added, removed, upgraded, and
+//!   downgraded packages with checksum changes highlighted
+
+use anyhow::{Context, Result};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
+use unrealpm::{LockChange, LockChangeKind, Lockfile, LOCKFILE_NAME};
+
+use crate::{DiffFormat, LockAction};
+
+pub fn run(action: &LockAction) -> Result<()> {
+    match action {
+        LockAction::Diff {
+            old,
+            new,
+            rev,
+            format,
+        } => run_diff(old.as_deref(), new.as_deref(), rev, *format),
+    }
+}
+
+fn run_diff(old: Option<&str>, new: Option<&str>, rev: &str, format: DiffFormat) -> Result<()> {
+    let (old_lockfile, old_label) = match old {
+        Some(path) => (load_file(path)?, path.to_string()),
+        None => (load_git(rev)?, format!("{}:{}", rev, LOCKFILE_NAME)),
+    };
+    let new_path = new.unwrap_or(LOCKFILE_NAME);
+    let new_lockfile = load_file(new_path)?;
+
+    let changes = old_lockfile.diff(&new_lockfile);
+
+    match format {
+        DiffFormat::Text => print_text(&changes, &old_label, new_path),
+        DiffFormat::Markdown => print_markdown(&changes),
+    }
+
+    Ok(())
+}
+
+fn load_file(path: &str) -> Result<Lockfile> {
+    Lockfile::load_from(path)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Lockfile not found: {}\n\n\
+            Run 'unrealpm install' to generate one, or pass the path explicitly:\n\
+              unrealpm lock diff old.lock new.lock",
+            path
+        )
+    })
+}
+
+/// Read unrealpm.lock as committed at a git revision
+///
+/// A revision that does not contain a lockfile yet compares as empty, so every
+/// package shows up as added.
+fn load_git(rev: &str) -> Result<Lockfile> {
+    let output = Command::new("git")
+        .args(["show", &format!("{}:./{}", rev, LOCKFILE_NAME)])
+        .output()
+        .context("Failed to run git (is it installed and on PATH?)")?;
+
+    if output.status.success() {
+        let contents = String::from_utf8_lossy(&output.stdout);
+        return Lockfile::parse(&contents)
+            .with_context(|| format!("Failed to read {} at {}", LOCKFILE_NAME, rev));
+    }
+
+    let rev_exists = Command::new("git")
+        .args([
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", rev),
+        ])
+        .output()
+        .is_ok_and(|o| o.status.success());
+    if rev_exists && Path::new(LOCKFILE_NAME).exists() {
+        return Ok(Lockfile::new());
+    }
+
+    anyhow::bail!(
+        "Could not read {} at git revision '{}':\n  {}\n\n\
+        Compare two files instead:\n\
+          unrealpm lock diff old.lock new.lock",
+        LOCKFILE_NAME,
+        rev,
+        String::from_utf8_lossy(&output.stderr).trim()
+    )
+}
+
+fn print_text(changes: &[LockChange], old_label: &str, new_label: &str) {
+    println!("Lockfile changes ({} -> {}):", old_label, new_label);
+    println!();
+
+    if changes.is_empty() {
+        println!("  No changes");
+        println!();
+        return;
+    }
+
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let paint = |code: &str, text: String| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text
+        }
+    };
+
+    for change in changes {
+        let line = match change.kind {
+            LockChangeKind::Added => {
+                paint("32", format!("+ {} {}", change.name, new_version(change)))
+            }
+            LockChangeKind::Removed => {
+                paint("31", format!("- {} {}", change.name, old_version(change)))
+            }
+            LockChangeKind::Upgraded => format!(
+                "↑ {} {} -> {}",
+                change.name,
+                old_version(change),
+                new_version(change)
+            ),
+            LockChangeKind::Downgraded => format!(
+                "↓ {} {} -> {}",
+                change.name,
+                old_version(change),
+                new_version(change)
+            ),
+            LockChangeKind::Changed if old_version(change) == new_version(change) => {
+                format!("~ {} {}", change.name, new_version(change))
+            }
+            LockChangeKind::Changed => format!(
+                "~ {} {} -> {}",
+                change.name,
+                old_version(change),
+                new_version(change)
+            ),
+        };
+        println!("  {}", line);
+
+        if change.checksum_changed() {
+            let checksums = format!(
+                "checksum {} -> {}",
+                short_checksum(&change.old.as_ref().unwrap().checksum),
+                short_checksum(&change.new.as_ref().unwrap().checksum)
+            );
+            if change.is_suspicious() {
+                println!(
+                    "      {}",
+                    paint("1;33", format!("⚠ {} (same version)", checksums))
+                );
+            } else {
+                println!("      {}", checksums);
+            }
+        }
+        if let (Some(old), Some(new)) = (&change.old, &change.new) {
+            if old.source != new.source {
+                println!(
+                    "      source {} -> {}",
+                    old.source.as_deref().unwrap_or("registry"),
+                    new.source.as_deref().unwrap_or("registry")
+                );
+            }
+        }
+    }
+
+    println!();
+    println!("{}", summary(changes));
+    warn_suspicious(changes);
+}
+
+fn print_markdown(changes: &[LockChange]) {
+    println!("### Lockfile changes");
+    println!();
+
+    if changes.is_empty() {
+        println!("No package changes.");
+        return;
+    }
+
+    println!("| Package | Change | Old | New | Checksum |");
+    println!("| --- | --- | --- | --- | --- |");
+    for change in changes {
+        let checksum = match (&change.old, &change.new) {
+            (Some(old), Some(new)) if change.checksum_changed() => {
+                let diff = format!(
+                    "`{}` → `{}`",
+                    short_checksum(&old.checksum),
+                    short_checksum(&new.checksum)
+                );
+                if change.is_suspicious() {
+                    format!("⚠️ **{}**", diff)
+                } else {
+                    diff
+                }
+            }
+            (_, Some(package)) | (Some(package), None) => {
+                format!("`{}`", short_checksum(&package.checksum))
+            }
+            (None, None) => String::new(),
+        };
+        println!(
+            "| `{}` | {} | {} | {} | {} |",
+            change.name,
+            change.kind,
+            or_dash(old_version(change)),
+            or_dash(new_version(change)),
+            checksum
+        );
+    }
+
+    println!();
+    println!("{}", summary(changes));
+
+    let suspicious: Vec<&str> = changes
+        .iter()
+        .filter(|c| c.is_suspicious())
+        .map(|c| c.name.as_str())
+        .collect();
+    if !suspicious.is_empty() {
+        println!();
+        println!(
+            "> **Warning:** checksum changed without a version change: {}",
+            suspicious
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+fn warn_suspicious(changes: &[LockChange]) {
+    let suspicious: Vec<&str> = changes
+        .iter()
+        .filter(|c| c.is_suspicious())
+        .map(|c| c.name.as_str())
+        .collect();
+    if !suspicious.is_empty() {
+        println!();
+        println!(
+            "⚠ Checksum changed without a version change: {}",
+            suspicious.join(", ")
+        );
+        println!("  The same release now has different contents; verify before merging.");
+    }
+}
+
+/// "2 added, 1 upgraded" style summary
+fn summary(changes: &[LockChange]) -> String {
+    let kinds = [
+        LockChangeKind::Added,
+        LockChangeKind::Removed,
+        LockChangeKind::Upgraded,
+        LockChangeKind::Downgraded,
+        LockChangeKind::Changed,
+    ];
+    kinds
+        .iter()
+        .filter_map(|kind| {
+            let count = changes.iter().filter(|c| c.kind == *kind).count();
+            (count > 0).then(|| format!("{} {}", count, kind))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn old_version(change: &LockChange) -> &str {
+    change
+        .old
+        .as_ref()
+        .map(|p| p.version.as_str())
+        .unwrap_or("")
+}
+
+fn new_version(change: &LockChange) -> &str {
+    change
+        .new
+        .as_ref()
+        .map(|p| p.version.as_str())
+        .unwrap_or("")
+}
+
+fn or_dash(version: &str) -> &str {
+    if version.is_empty() {
+        "-"
+    } else {
+        version
+    }
+}
+
+/// First 12 hex digits of a checksum, keeping the algorithm prefix
+fn short_checksum(checksum: &str) -> String {
+    match checksum.split_once(':') {
+        Some((algo, hex)) => format!("{}:{}", algo, hex.chars().take(12).collect::<String>()),
+        None => checksum.chars().take(12).collect(),
+    }
+}
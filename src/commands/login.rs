@@ -31,7 +31,23 @@ struct ErrorResponse {
     error: String,
 }
 
+/// Tokens are saved for the configured registry, so they can't come from
+/// logging in to a `--registry` one
+pub fn ensure_configured_registry() -> Result<()> {
+    if let Some(url) = unrealpm::config::registry_override() {
+        anyhow::bail!(
+            "Can't log in to {} with --registry: the saved token is for the configured registry\n\n\
+            Set {} to a token for it instead",
+            url,
+            unrealpm::config::token_env_var(&url).unwrap_or_else(|| unrealpm::config::TOKEN_ENV.to_string())
+        );
+    }
+    Ok(())
+}
+
 pub fn run(use_github: bool, use_email: bool) -> Result<()> {
+    ensure_configured_registry()?;
+
     // If explicit flag provided, use that method
     if use_github {
         return run_github_oauth();
@@ -282,6 +298,7 @@ fn run_email_login() -> Result<()> {
 
         if status.as_u16() == 401 {
             println!("Please check your email and password.");
+            println!("Forgot your password? Run: unrealpm auth reset-password");
             println!();
             println!(
                 "Don't have an account? Register at: {}/register",
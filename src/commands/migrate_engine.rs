@@ -0,0 +1,273 @@
+//! Migrate-engine command - upgrade advice for moving a project to a new engine
+//!
+//! For each direct dependency, reports whether the locked version already
+//! supports the target engine, which newer version does, or that none does.
+//! With `--apply`, bumps the constraints in unrealpm.json that stay within
+//! the same major version; major bumps are left for review.
+
+use anyhow::Result;
+use semver::{Version, VersionReq};
+use std::env;
+use unrealpm::{
+    describe_engine_version, find_matching_version, normalize_engine_version, Config,
+    EngineVersion, Lockfile, Manifest, PackageMetadata, RegistryClient,
+};
+
+/// What a dependency needs to work on the target engine
+enum Advice {
+    /// The locked version already supports the engine
+    Compatible,
+    /// A newer version within the current constraint supports it
+    Update { version: String },
+    /// Only a version outside the constraint supports it; `safe` when the
+    /// bump stays within the current major version
+    Bump { version: String, safe: bool },
+    /// No published version supports it
+    Incompatible,
+    /// Registry metadata could not be fetched
+    Unknown(String),
+}
+
+struct Row {
+    name: String,
+    constraint: String,
+    current: Option<String>,
+    advice: Advice,
+}
+
+pub fn run(engine: &str, apply: bool) -> Result<()> {
+    if EngineVersion::parse(engine).is_none() {
+        anyhow::bail!(
+            "Invalid engine version '{}'\n\nUse a version like 5.4",
+            engine
+        );
+    }
+    let target = normalize_engine_version(engine);
+
+    let current_dir = env::current_dir()?;
+    if !Manifest::exists(&current_dir) {
+        println!("✗ No unrealpm.json found in current directory");
+        println!();
+        println!("Run 'unrealpm init' first to initialize the project.");
+        return Ok(());
+    }
+
+    let mut manifest = Manifest::load(&current_dir)?;
+    let lockfile = Lockfile::load()?.unwrap_or_default();
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+
+    match manifest.engine_version.as_deref() {
+        Some(current) => println!(
+            "Engine migration: {} → {}",
+            describe_engine_version(&config.logical_engine_version(current)),
+            target
+        ),
+        None => println!("Engine migration: → {}", target),
+    }
+    println!();
+
+    // Dependencies as they will apply on the target engine
+    let dependencies = manifest.active_dependencies(&unrealpm::detect_platform(), Some(&target));
+    if dependencies.is_empty() {
+        println!("No dependencies to check.");
+        return Ok(());
+    }
+
+    let mut rows: Vec<Row> = dependencies
+        .iter()
+        .map(|(name, constraint)| {
+            let current = lockfile.get_package(name).map(|p| p.version.clone());
+            let advice = match registry.get_package(name) {
+                Ok(metadata) => advise(&metadata, constraint, current.as_deref(), &target),
+                Err(e) => Advice::Unknown(e.to_string()),
+            };
+            Row {
+                name: name.clone(),
+                constraint: constraint.clone(),
+                current,
+                advice,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
+
+    print_table(&rows);
+    println!();
+
+    let count = |f: fn(&Advice) -> bool| rows.iter().filter(|r| f(&r.advice)).count();
+    let compatible = count(|a| matches!(a, Advice::Compatible));
+    let updates = count(|a| matches!(a, Advice::Update { .. }));
+    let safe_bumps = count(|a| matches!(a, Advice::Bump { safe: true, .. }));
+    let major_bumps = count(|a| matches!(a, Advice::Bump { safe: false, .. }));
+    let blocked = count(|a| matches!(a, Advice::Incompatible | Advice::Unknown(_)));
+
+    println!(
+        "{} compatible, {} update(s), {} safe bump(s), {} major bump(s), {} blocked",
+        compatible, updates, safe_bumps, major_bumps, blocked
+    );
+    println!();
+
+    if apply && safe_bumps > 0 {
+        // Same style as constraints `install` writes
+        let style = config.save_style(&current_dir);
+        for row in &rows {
+            if let Advice::Bump {
+                version,
+                safe: true,
+            } = &row.advice
+            {
+                let constraint = style.constraint(version);
+                println!("  ✓ {}: {} → {}", row.name, row.constraint, constraint);
+                manifest.dependencies.insert(row.name.clone(), constraint);
+            }
+        }
+        manifest.save(&current_dir)?;
+        println!();
+        println!("✓ Updated {}", Manifest::file_name(&current_dir));
+        println!();
+    } else if safe_bumps > 0 {
+        println!("Apply the safe constraint bumps with:");
+        println!("  unrealpm migrate-engine {} --apply", engine);
+        println!();
+    }
+
+    if major_bumps > 0 {
+        println!("Major version bumps may change the plugin's API; review their changelogs");
+        println!("and update the constraints in unrealpm.json by hand.");
+        println!();
+    }
+    if blocked > 0 {
+        println!(
+            "⚠ {} dependenc{} cannot move to {} yet; ask the author or replace {}.",
+            blocked,
+            if blocked == 1 { "y" } else { "ies" },
+            target,
+            if blocked == 1 { "it" } else { "them" }
+        );
+        println!();
+    }
+
+    println!("Next steps:");
+    println!(
+        "  1. Set \"engine_version\": \"{}\" in unrealpm.json and switch the .uproject",
+        target
+    );
+    println!("  2. Run: unrealpm update");
+
+    Ok(())
+}
+
+/// Work out what one dependency needs to support `target`
+fn advise(
+    metadata: &PackageMetadata,
+    constraint: &str,
+    current: Option<&str>,
+    target: &str,
+) -> Advice {
+    // Engine-specific builds share a version string; any one of them will do
+    if let Some(current) = current {
+        let supported = metadata
+            .versions
+            .iter()
+            .any(|v| v.version == current && v.supports_engine(target));
+        if supported {
+            return Advice::Compatible;
+        }
+    }
+
+    if let Ok(version) = find_matching_version(metadata, constraint, Some(target), false) {
+        return match current {
+            Some(current) if current == version.version => Advice::Compatible,
+            _ => Advice::Update {
+                version: version.version,
+            },
+        };
+    }
+
+    match find_matching_version(metadata, "*", Some(target), false) {
+        Ok(version) => {
+            // Safe when the bump stays semver-compatible with what is used today
+            let baseline = current.map(str::to_string).or_else(|| {
+                find_matching_version(metadata, constraint, None, true)
+                    .ok()
+                    .map(|v| v.version)
+            });
+            let safe = baseline
+                .and_then(|b| VersionReq::parse(&format!("^{}", b)).ok())
+                .zip(Version::parse(&version.version).ok())
+                .is_some_and(|(req, v)| req.matches(&v));
+            Advice::Bump {
+                version: version.version,
+                safe,
+            }
+        }
+        Err(_) => Advice::Incompatible,
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let (marker, target, action) = match &row.advice {
+                Advice::Compatible => ("✓", String::new(), "compatible as-is".to_string()),
+                Advice::Update { version } => (
+                    "↑",
+                    version.clone(),
+                    format!("update within {}", row.constraint),
+                ),
+                Advice::Bump {
+                    version,
+                    safe: true,
+                } => (
+                    "↑",
+                    version.clone(),
+                    format!("bump {} → ^{}", row.constraint, version),
+                ),
+                Advice::Bump {
+                    version,
+                    safe: false,
+                } => (
+                    "⚠",
+                    version.clone(),
+                    format!("major bump {} → ^{} (review)", row.constraint, version),
+                ),
+                Advice::Incompatible => ("✗", String::new(), "no compatible version".to_string()),
+                Advice::Unknown(e) => ("✗", String::new(), format!("metadata unavailable: {}", e)),
+            };
+            [
+                format!("{} {}", marker, row.name),
+                row.current.clone().unwrap_or_else(|| "-".to_string()),
+                target,
+                action,
+            ]
+        })
+        .collect();
+
+    let headers = ["PACKAGE", "CURRENT", "TARGET", "ACTION"];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].len() + if i == 0 { 2 } else { 0 }])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let print_row = |row: [&str; 4]| {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("  {}", line.trim_end());
+    };
+    print_row(["  PACKAGE", "CURRENT", "TARGET", "ACTION"]);
+    for row in &cells {
+        print_row([&row[0], &row[1], &row[2], &row[3]]);
+    }
+}
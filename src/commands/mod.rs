@@ -1,23 +1,52 @@
+pub mod attest;
+pub mod audit;
+pub mod auth;
 pub mod build;
 pub mod cache;
+pub mod categories;
+pub mod check;
+pub mod ci;
 pub mod config;
 pub mod doctor;
+pub mod engine;
+pub mod env;
+pub mod fab;
+pub mod info;
 pub mod init;
 pub mod install;
 pub mod keys;
+pub mod licenses;
+pub mod link;
 pub mod list;
+pub mod lock;
 pub mod login;
+pub mod migrate_engine;
+pub mod notices;
 pub mod outdated;
 pub mod pack;
 pub mod publish;
+pub mod rebuild;
 pub mod register;
+pub mod registry;
+pub mod report;
+pub mod report_bug;
+pub mod sbom;
 pub mod search;
+pub mod self_update;
+pub mod store;
 pub mod tokens;
 pub mod tree;
+pub mod trust;
 pub mod uninstall;
+pub mod unlink;
 pub mod unpublish;
 pub mod update;
+pub mod validate;
+pub mod vcs_ignore;
+pub mod vendor;
 pub mod verify;
+pub mod versions;
 pub mod whoami;
 pub mod why;
+pub mod workspace;
 pub mod yank;
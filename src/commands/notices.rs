@@ -0,0 +1,65 @@
+//! Notices command - aggregate third-party license files
+//!
+//! Writes the LICENSE/NOTICE files of every installed package into one
+//! `ThirdPartyNotices.txt`, with a name/version header per package, for
+//! platform submissions that require crediting bundled components.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use unrealpm::notices::{self, NOTICES_FILE_NAME};
+use unrealpm::{outln, Lockfile, LOCKFILE_NAME};
+
+pub fn run(output: Option<String>, stdout: bool) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let Some(lockfile) = Lockfile::load_from(current_dir.join(LOCKFILE_NAME))? else {
+        anyhow::bail!(
+            "No unrealpm.lock found in {}\n\n\
+            • Run 'unrealpm install' first",
+            current_dir.display()
+        );
+    };
+
+    let packages = notices::collect(&current_dir, &lockfile)?;
+    let text = notices::render(&packages);
+    if stdout {
+        print!("{}", text);
+        return Ok(());
+    }
+
+    let path = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| current_dir.join(NOTICES_FILE_NAME));
+    unrealpm::vcs::track(&[&path], || Ok(std::fs::write(&path, &text)?))?;
+
+    let files: usize = packages.iter().map(|p| p.files.len()).sum();
+    outln!(
+        "✓ Wrote {} ({} file{} from {} package{})",
+        path.display(),
+        files,
+        if files == 1 { "" } else { "s" },
+        packages.len(),
+        if packages.len() == 1 { "" } else { "s" }
+    );
+
+    let not_installed = lockfile
+        .packages
+        .iter()
+        .filter(|(_, locked)| !locked.is_externally_managed())
+        .count()
+        - packages.len();
+    if not_installed > 0 {
+        outln!(
+            "  ⚠ {} locked package{} not installed; run 'unrealpm install' to include {}",
+            not_installed,
+            if not_installed == 1 { " is" } else { "s are" },
+            if not_installed == 1 { "it" } else { "them" }
+        );
+    }
+    for package in packages.iter().filter(|p| p.files.is_empty()) {
+        unrealpm::strict::warn(format!(
+            "{} {} has no LICENSE or NOTICE file; check its terms with the author",
+            package.name, package.version
+        ))?;
+    }
+    Ok(())
+}
@@ -1,52 +1,202 @@
 use anyhow::Result;
 use std::env;
-use unrealpm::{find_matching_version, Config, Lockfile, Manifest, RegistryClient};
+use std::path::{Path, PathBuf};
+use unrealpm::{
+    find_matching_version, Config, Lockfile, Manifest, RegistryClient, StatusFile, Workspace,
+    LOCKFILE_NAME,
+};
+
+/// An outdated dependency, possibly shared by several workspace members
+struct OutdatedPackage {
+    name: String,
+    current: String,
+    latest: String,
+    constraint: String,
+    members: Vec<String>,
+}
 
-pub fn run() -> Result<()> {
+pub fn run(workspace: bool, exit_code: bool) -> Result<()> {
     let current_dir = env::current_dir()?;
 
     println!("Checking for outdated packages...");
     println!();
 
-    // Check if manifest exists
-    if !Manifest::exists(&current_dir) {
-        println!("✗ No unrealpm.json found in current directory");
+    // Collect the projects to check: workspace members or just the current project
+    let projects: Vec<(String, PathBuf)> = if workspace {
+        let (root, ws) = Workspace::find(&current_dir)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "No {} found in this directory or any parent.\n\n\
+                Create one listing the member projects:\n\
+                  {{ \"members\": [\"Game\", \"DedicatedServer\"] }}",
+                unrealpm::WORKSPACE_FILE_NAME
+            )
+        })?;
+        println!(
+            "Workspace: {} ({} members)",
+            root.display(),
+            ws.members.len()
+        );
+        println!();
+        ws.members
+            .iter()
+            .cloned()
+            .zip(ws.member_dirs(&root))
+            .collect()
+    } else {
+        // Check if manifest exists
+        if !Manifest::exists(&current_dir) {
+            println!("✗ No unrealpm.json found in current directory");
+            println!();
+            println!("Run 'unrealpm init' first to initialize the project.");
+            return Ok(());
+        }
+        vec![(".".to_string(), current_dir.clone())]
+    };
+
+    // Get registry client (uses HTTP if configured)
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+
+    let mut outdated_packages: Vec<OutdatedPackage> = Vec::new();
+
+    for (member, project_dir) in &projects {
+        if workspace {
+            println!("  Checking {}...", member);
+        }
+
+        let project_outdated = check_project(project_dir, &config, &registry)?;
+
+        // Let the editor plugin know which dependencies are behind
+        if let Ok(mut status) = StatusFile::compute(project_dir, "outdated") {
+            for (name, _, latest, _) in &project_outdated {
+                status.set_latest(name, latest);
+            }
+            if let Err(e) = status.save(project_dir) {
+                eprintln!("  ⚠ Failed to write {}: {}", unrealpm::STATUS_FILE_NAME, e);
+            }
+        }
+
+        for (name, current, latest, constraint) in project_outdated {
+            // Deduplicate shared dependencies across members
+            match outdated_packages
+                .iter_mut()
+                .find(|p| p.name == name && p.current == current && p.latest == latest)
+            {
+                Some(existing) => existing.members.push(member.clone()),
+                None => outdated_packages.push(OutdatedPackage {
+                    name,
+                    current,
+                    latest,
+                    constraint,
+                    members: vec![member.clone()],
+                }),
+            }
+        }
+    }
+
+    if workspace {
+        println!();
+    }
+
+    // Display results
+    if outdated_packages.is_empty() {
+        println!("✓ All packages are up to date!");
+        println!();
+    } else {
+        outdated_packages.sort_by(|a, b| a.name.cmp(&b.name).then(a.current.cmp(&b.current)));
+
+        println!("Found {} outdated packages:", outdated_packages.len());
+        println!();
+
+        // Print table header
+        if workspace {
+            println!(
+                "{:<30} {:<15} {:<15} {:<20} Members",
+                "Package", "Current", "Latest", "Constraint"
+            );
+            println!("{}", "-".repeat(100));
+        } else {
+            println!(
+                "{:<30} {:<15} {:<15} {:<20}",
+                "Package", "Current", "Latest", "Constraint"
+            );
+            println!("{}", "-".repeat(80));
+        }
+
+        // Print outdated packages
+        for pkg in &outdated_packages {
+            if workspace {
+                println!(
+                    "{:<30} {:<15} {:<15} {:<20} {}",
+                    pkg.name,
+                    pkg.current,
+                    pkg.latest,
+                    pkg.constraint,
+                    pkg.members.join(", ")
+                );
+            } else {
+                println!(
+                    "{:<30} {:<15} {:<15} {:<20}",
+                    pkg.name, pkg.current, pkg.latest, pkg.constraint
+                );
+            }
+        }
+
+        println!();
+        println!("Run 'unrealpm update' to update all packages");
+        println!("Run 'unrealpm update <package>' to update a specific package");
         println!();
-        println!("Run 'unrealpm init' first to initialize the project.");
-        return Ok(());
+
+        if exit_code {
+            std::process::exit(1);
+        }
     }
 
+    Ok(())
+}
+
+/// Check one project for outdated dependencies
+///
+/// Returns (name, current, latest, constraint) for each outdated package.
+fn check_project(
+    project_dir: &Path,
+    config: &Config,
+    registry: &RegistryClient,
+) -> Result<Vec<(String, String, String, String)>> {
     // Load manifest and lockfile
-    let manifest = Manifest::load(&current_dir)?;
-    let lockfile = Lockfile::load()?;
+    let manifest = Manifest::load(project_dir)?;
 
     if manifest.dependencies.is_empty() {
         println!("No dependencies to check.");
         println!();
-        return Ok(());
+        return Ok(Vec::new());
     }
 
-    let lockfile = match lockfile {
+    let lockfile = match Lockfile::load_from(project_dir.join(LOCKFILE_NAME))? {
         Some(lf) => lf,
         None => {
-            println!("✗ No lockfile found (unrealpm.lock)");
+            println!(
+                "✗ No lockfile found (unrealpm.lock) in {}",
+                project_dir.display()
+            );
             println!();
             println!("Run 'unrealpm install' first to install dependencies.");
-            return Ok(());
+            return Ok(Vec::new());
         }
     };
 
-    // Get engine version
-    let engine_version = manifest.engine_version.as_deref();
-
-    // Get registry client (uses HTTP if configured)
-    let config = Config::load()?;
-    let registry = RegistryClient::from_config(&config)?;
+    // Get engine version (custom associations map through engine aliases)
+    let mapped_engine = manifest
+        .engine_version
+        .as_deref()
+        .map(|engine| config.logical_engine_version(engine));
+    let engine_version = mapped_engine.as_deref();
 
     let mut outdated_packages = Vec::new();
 
-    // Check each dependency
-    for (name, constraint) in &manifest.dependencies {
+    // Check each dependency that applies to this platform/engine
+    let dependencies = manifest.active_dependencies(&unrealpm::detect_platform(), engine_version);
+    for (name, constraint) in &dependencies {
         // Get current installed version from lockfile
         let current_version = match lockfile.get_package(name) {
             Some(pkg) => &pkg.version,
@@ -86,34 +236,5 @@ pub fn run() -> Result<()> {
         }
     }
 
-    // Display results
-    if outdated_packages.is_empty() {
-        println!("✓ All packages are up to date!");
-        println!();
-    } else {
-        println!("Found {} outdated packages:", outdated_packages.len());
-        println!();
-
-        // Print table header
-        println!(
-            "{:<30} {:<15} {:<15} {:<20}",
-            "Package", "Current", "Latest", "Constraint"
-        );
-        println!("{}", "-".repeat(80));
-
-        // Print outdated packages
-        for (name, current, latest, constraint) in outdated_packages {
-            println!(
-                "{:<30} {:<15} {:<15} {:<20}",
-                name, current, latest, constraint
-            );
-        }
-
-        println!();
-        println!("Run 'unrealpm update' to update all packages");
-        println!("Run 'unrealpm update <package>' to update a specific package");
-        println!();
-    }
-
-    Ok(())
+    Ok(outdated_packages)
 }
@@ -12,7 +12,8 @@ use sha2::{Digest, Sha256};
 use std::env;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
-use unrealpm::UPlugin;
+use unrealpm::limits::PackageContents;
+use unrealpm::{Config, UPlugin};
 
 pub fn run(
     path: Option<String>,
@@ -71,7 +72,15 @@ pub fn run(
     let file_count = count_files(&plugin_dir, include_binaries)?;
     println!("  Files to pack: {}", file_count);
 
+    let config = Config::load()?;
     if dry_run {
+        let max_files = config.limits.max_files;
+        if max_files > 0 && file_count > max_files {
+            println!(
+                "  ⚠ More files than limits.max_files ({}); packing would fail",
+                max_files
+            );
+        }
         println!();
         println!("[DRY RUN] Would create: {}", output_path.display());
         println!();
@@ -83,6 +92,7 @@ pub fn run(
     // Create tarball
     println!("  Creating tarball...");
     create_tarball(&plugin_dir, &output_path, include_binaries)?;
+    enforce_limits(&output_path, &config)?;
 
     // Calculate checksum
     let checksum = calculate_checksum(&output_path)?;
@@ -108,7 +118,8 @@ pub fn run(
     Ok(())
 }
 
-fn create_tarball(source_dir: &Path, output_path: &Path, include_binaries: bool) -> Result<()> {
+/// Pack a plugin directory into a `.tar.gz` rooted at the directory name
+pub fn create_tarball(source_dir: &Path, output_path: &Path, include_binaries: bool) -> Result<()> {
     // Ensure parent directory exists
     if let Some(parent) = output_path.parent() {
         fs::create_dir_all(parent)?;
@@ -144,6 +155,55 @@ fn create_tarball(source_dir: &Path, output_path: &Path, include_binaries: bool)
     Ok(())
 }
 
+/// Check a packed tarball against the `[limits]` config
+///
+/// On failure the tarball is deleted and the error lists every exceeded
+/// limit with the largest folders and files, so the culprit is obvious.
+pub fn enforce_limits(tarball: &Path, config: &Config) -> Result<()> {
+    let contents = PackageContents::scan(tarball)?;
+    let violations = contents.check(&config.limits);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let _ = fs::remove_file(tarball);
+
+    println!();
+    println!("  ✗ Package exceeds the configured limits:");
+    for violation in &violations {
+        println!("    • {}", violation);
+    }
+    println!();
+    println!(
+        "  Contents: {} files, {} uncompressed",
+        contents.entries.len(),
+        format_size(contents.total_size())
+    );
+    println!("  Largest folders:");
+    let folders = contents.largest_folders(5);
+    let width = folders.iter().map(|(f, _, _)| f.len()).max().unwrap_or(0);
+    for (folder, size, count) in &folders {
+        println!(
+            "    {:<width$}  {:>10}  ({} files)",
+            folder,
+            format_size(*size),
+            count,
+            width = width
+        );
+    }
+    println!("  Largest files:");
+    for entry in contents.largest_files(5) {
+        println!("    {:>10}  {}", format_size(entry.size), entry.path);
+    }
+    println!();
+
+    anyhow::bail!(
+        "Package exceeds the configured limits\n\n\
+        Remove or exclude the files above (Saved/, DerivedDataCache/, debug symbols), \
+        or raise the limit, e.g.:\n  \
+        unrealpm config set limits.max_file_mb 500"
+    )
+}
+
 fn should_include_entry(entry: &walkdir::DirEntry, include_binaries: bool) -> bool {
     let path = entry.path();
     let path_str = path.to_string_lossy();
@@ -208,6 +268,11 @@ fn should_include_entry(entry: &walkdir::DirEntry, include_binaries: bool) -> bo
         "*~",
     ];
 
+    // Dependencies a standalone plugin installed for itself
+    if entry.depth() == 1 && entry.file_name() == unrealpm::STANDALONE_DEPS_DIR {
+        return false;
+    }
+
     // Check binaries
     if !include_binaries && path_str.contains("Binaries") {
         return false;
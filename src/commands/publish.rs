@@ -3,52 +3,52 @@ use chrono::Utc;
 use flate2::write::GzEncoder;
 use flate2::Compression;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::env;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use unrealpm::signing::load_or_generate_keys;
-use unrealpm::{Config, PackageMetadata, PackageType, PackageVersion, RegistryClient, UPlugin};
+use unrealpm::{
+    read_tarball_metadata, BinaryUpsert, Config, DiscoveryTags, Manifest, PackageMetadata,
+    PackageType, PackageVersion, PrebuiltBinary, RegistryClient, UPlugin,
+};
 
 pub fn run(
     path: Option<String>,
     dry_run: bool,
     include_binaries: bool,
-    target_engine: Option<String>,
+    target_engines: Vec<String>,
+    multi_engine: bool,
     git_repo: Option<String>,
     git_ref: Option<String>,
 ) -> Result<()> {
     println!("Publishing package...");
     println!();
 
-    // Parse target engine version if provided
-    let (engine_major, engine_minor, engine_patch, is_multi_engine) =
-        if let Some(ref eng) = target_engine {
-            // Parse engine version (e.g., "5.3", "4.27", "5.4.2")
-            let parts: Vec<&str> = eng.split('.').collect();
-            let major = parts
-                .first()
-                .and_then(|s| s.parse::<i32>().ok())
-                .ok_or_else(|| {
-                    anyhow::anyhow!("Invalid engine version format. Use: 4.27, 5.3, etc.")
-                })?;
-            let minor = parts
-                .get(1)
-                .and_then(|s| s.parse::<i32>().ok())
-                .unwrap_or(0);
-            let patch = parts
-                .get(2)
-                .and_then(|s| s.parse::<i32>().ok())
-                .unwrap_or(0);
-
-            println!("  Target engine: UE {}.{}.{}", major, minor, patch);
-            println!("  Publishing engine-specific version");
+    // One tarball, one registry row per target
+    let targets = parse_engine_targets(&target_engines, multi_engine)?;
+    match targets.as_slice() {
+        [target] if target.is_multi_engine && target.engine_versions.is_none() => {}
+        [target] if target.is_multi_engine => {
+            println!("  Target engines: {}", target.label());
+            println!("  Publishing one multi-engine version");
             println!();
-
-            (Some(major), Some(minor), Some(patch), false)
-        } else {
-            // Multi-engine version (current behavior)
-            (None, None, None, true)
-        };
+        }
+        _ => {
+            for target in &targets {
+                println!("  Target engine: {}", target.label());
+            }
+            if targets.len() == 1 {
+                println!("  Publishing engine-specific version");
+            } else {
+                println!(
+                    "  Publishing {} engine-specific versions from one tarball",
+                    targets.len()
+                );
+            }
+            println!();
+        }
+    }
 
     // Determine plugin directory
     let plugin_dir = if let Some(p) = path {
@@ -67,6 +67,7 @@ pub fn run(
     let uplugin = UPlugin::load(&uplugin_path)?;
     let plugin_name = UPlugin::name(&uplugin_path)
         .ok_or_else(|| anyhow::anyhow!("Could not determine plugin name from file"))?;
+    let package_name = package_name(&plugin_dir, &plugin_name)?;
 
     println!("  ✓ Found plugin: {}", plugin_name);
     println!("    Version: {}", uplugin.version_name);
@@ -80,8 +81,28 @@ pub fn run(
         println!("    Engine version: {}", engine);
     }
 
-    // Get enabled plugin dependencies from .uplugin
-    let plugin_dependencies: Vec<_> = uplugin.plugins.iter().filter(|p| p.enabled).collect();
+    // Categories come from unrealpm.json when set, otherwise the .uplugin
+    let manifest = Manifest::load(&plugin_dir).ok();
+    let tags = DiscoveryTags::from_plugin(&uplugin, manifest.as_ref());
+    if !tags.categories.is_empty() {
+        println!("    Categories: {}", tags.categories.join(", "));
+    }
+    if !tags.keywords.is_empty() {
+        println!("    Keywords: {}", tags.keywords.join(", "));
+    }
+
+    // Get enabled plugin dependencies from .uplugin; dev dependencies (test
+    // frameworks, editor tools) are the author's, not the installer's
+    let is_dev = |name: &str| {
+        manifest
+            .as_ref()
+            .is_some_and(|m| m.dev_dependencies.contains_key(name))
+    };
+    let plugin_dependencies: Vec<_> = uplugin
+        .plugins
+        .iter()
+        .filter(|p| p.enabled && !is_dev(&p.name))
+        .collect();
 
     if !plugin_dependencies.is_empty() {
         println!();
@@ -91,33 +112,58 @@ pub fn run(
         }
     }
 
+    // Optional dependencies grouped by feature, from unrealpm.json
+    let features = manifest
+        .as_ref()
+        .map(|m| m.feature_dependencies())
+        .unwrap_or_default();
+    if !features.is_empty() {
+        let mut names: Vec<&String> = features.keys().collect();
+        names.sort();
+        println!();
+        println!("  Features:");
+        for name in names {
+            let deps: Vec<&str> = features[name].iter().map(|d| d.name.as_str()).collect();
+            println!("    • {} ({})", name, deps.join(", "));
+        }
+    }
+
     // Look for README file
     let readme_content = find_readme(&plugin_dir);
     if let Some((readme_name, _)) = &readme_content {
         println!("  ✓ Found {}", readme_name);
     }
+    let changelog_content = find_changelog(&plugin_dir);
+    if let Some((changelog_name, _)) = &changelog_content {
+        println!("  ✓ Found {}", changelog_name);
+    }
     println!();
 
     // Check if auto-build is enabled
     let config = Config::load()?;
+    let mut auto_built: Vec<String> = Vec::new();
     if config.build.auto_build_on_publish && !include_binaries {
         println!("⚙ Auto-build enabled, building binaries...");
         println!();
 
         // Run build command for configured platforms
         if let Some(engine_version) = &uplugin.engine_version {
-            for platform in &config.build.platforms {
+            for platform in &config.platforms_for_engine(engine_version) {
                 match crate::commands::build::build_for_platform(
                     &plugin_dir,
                     &plugin_name,
                     engine_version,
                     platform,
+                    &config.build.configuration,
                     &config,
                 ) {
-                    Ok(_) => println!("  ✓ Built for {}", platform),
+                    Ok(_) => {
+                        println!("  ✓ Built for {}", platform);
+                        auto_built.push(platform.clone());
+                    }
                     Err(e) => {
                         eprintln!("  ✗ Failed to build for {}: {}", platform, e);
-                        eprintln!("  Continuing with source-only publish...");
+                        eprintln!("  Continuing without {} binaries...", platform);
                     }
                 }
             }
@@ -135,7 +181,21 @@ pub fn run(
     fs::create_dir_all(&temp_dir)?;
 
     let tarball_path = temp_dir.join(&tarball_name);
-    create_tarball(&plugin_dir, &tarball_path, include_binaries)?;
+    // Auto-built binaries are published only for the platforms just built
+    let mut bundled = if include_binaries || !auto_built.is_empty() {
+        bundled_builds(&plugin_dir, &config.build.configuration)
+    } else {
+        Vec::new()
+    };
+    if !include_binaries {
+        bundled.retain(|build| auto_built.contains(&build.platform));
+    }
+    for build in &bundled {
+        println!("  Bundling {}", build.binaries_dir.display());
+    }
+    create_tarball(&plugin_dir, &tarball_path, &bundled)?;
+    validate_packed_metadata(&tarball_path, &plugin_dir, &plugin_name, &uplugin)?;
+    crate::commands::pack::enforce_limits(&tarball_path, &config)?;
 
     // Calculate checksum
     println!("  Calculating checksum...");
@@ -155,7 +215,10 @@ pub fn run(
         println!("--dry-run specified, skipping publish");
         println!();
         println!("Summary:");
-        println!("  Package: {}@{}", plugin_name, uplugin.version_name);
+        println!("  Package: {}@{}", package_name, uplugin.version_name);
+        for target in &targets {
+            println!("  Version row: {}", target.label());
+        }
         println!("  Tarball: {}", tarball_path.display());
         println!("  Ready to publish!");
 
@@ -202,39 +265,40 @@ pub fn run(
     }
 
     // Check if package already exists
-    let is_new_package = registry.get_package(&plugin_name).is_err();
+    let is_new_package = registry.get_package(&package_name).is_err();
 
     if !is_new_package {
-        // Package exists, check if this version already exists
-        let existing = registry.get_package(&plugin_name)?;
-        let version_exists = existing.versions.iter().any(|v| {
-            v.version == uplugin.version_name && {
-                if is_multi_engine {
-                    // Multi-engine: Check if another multi-engine version exists
-                    v.is_multi_engine
+        // Package exists; nothing is published if any target's version already exists
+        let existing = registry.get_package(&package_name)?;
+        for target in &targets {
+            let version_exists = existing.versions.iter().any(|v| {
+                v.version == uplugin.version_name && {
+                    if target.is_multi_engine {
+                        // Multi-engine: Check if another multi-engine version exists
+                        v.is_multi_engine
+                    } else {
+                        // Engine-specific: Check if same engine version exists
+                        v.engine_major == target.major && v.engine_minor == target.minor
+                    }
+                }
+            });
+
+            if version_exists {
+                if target.is_multi_engine {
+                    anyhow::bail!(
+                        "Version {} of package '{}' already exists in registry",
+                        uplugin.version_name,
+                        package_name
+                    );
                 } else {
-                    // Engine-specific: Check if same engine version exists
-                    v.engine_major == engine_major && v.engine_minor == engine_minor
+                    anyhow::bail!(
+                        "Version {} for engine {} of package '{}' already exists in registry",
+                        uplugin.version_name,
+                        target.label(),
+                        package_name
+                    );
                 }
             }
-        });
-
-        if version_exists {
-            if is_multi_engine {
-                anyhow::bail!(
-                    "Version {} of package '{}' already exists in registry",
-                    uplugin.version_name,
-                    plugin_name
-                );
-            } else {
-                anyhow::bail!(
-                    "Version {} for engine {}.{} of package '{}' already exists in registry",
-                    uplugin.version_name,
-                    engine_major.unwrap(),
-                    engine_minor.unwrap(),
-                    plugin_name
-                );
-            }
         }
     } else {
         // New package - show publishing rights notice
@@ -256,24 +320,44 @@ pub fn run(
     // Check registry type to determine publish method
     match &registry {
         RegistryClient::Http(http_client) => {
-            // Publish to HTTP registry
+            // Publish to HTTP registry, uploading the same tarball for each target
             println!("  Publishing to HTTP registry...");
-            publish_to_http(
-                http_client,
-                &tarball_path,
-                &plugin_name,
-                &uplugin,
-                &checksum,
-                &config,
-                engine_major,
-                engine_minor,
-                engine_patch,
-                is_multi_engine,
-                git_repo.clone(),
-                git_ref.clone(),
-                &plugin_dependencies,
-                readme_content,
-            )?;
+            for (index, target) in targets.iter().enumerate() {
+                if targets.len() > 1 {
+                    println!("  Publishing for {}...", target.label());
+                }
+                let result = publish_to_http(
+                    http_client,
+                    &tarball_path,
+                    &package_name,
+                    &uplugin,
+                    &checksum,
+                    &config,
+                    target,
+                    git_repo.clone(),
+                    git_ref.clone(),
+                    &plugin_dependencies,
+                    &features,
+                    readme_content.clone(),
+                    changelog_content
+                        .as_ref()
+                        .map(|(_, content)| content.clone()),
+                    &tags,
+                );
+                if let Err(e) = result {
+                    if index > 0 {
+                        let published: Vec<String> =
+                            targets[..index].iter().map(|t| t.label()).collect();
+                        anyhow::bail!(
+                            "{}\n\nAlready published for: {}\n\
+                            Re-run with the remaining --engine values to finish.",
+                            e,
+                            published.join(", ")
+                        );
+                    }
+                    return Err(e);
+                }
+            }
 
             // Clean up temp directory
             fs::remove_dir_all(&temp_dir)?;
@@ -282,11 +366,11 @@ pub fn run(
             println!();
             println!(
                 "✓ Successfully published {}@{}",
-                plugin_name, uplugin.version_name
+                package_name, uplugin.version_name
             );
             println!();
             println!("Install with:");
-            println!("  unrealpm install {}", plugin_name);
+            println!("  unrealpm install {}", package_name);
             println!();
 
             return Ok(());
@@ -298,21 +382,17 @@ pub fn run(
 
     // Move tarball to registry (file-based only)
     println!("  Publishing to file registry...");
-    let tarballs_dir = registry.get_tarballs_dir();
-    fs::create_dir_all(&tarballs_dir)?;
-
-    let final_tarball_path = tarballs_dir.join(&tarball_name);
+    let final_tarball_path = registry.get_tarball_path(&package_name, &uplugin.version_name);
+    if let Some(parent) = final_tarball_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
     fs::rename(&tarball_path, &final_tarball_path)?;
 
     // Sign the package (if signing is enabled)
     let (public_key_hex, signed_at) = if config.signing.enabled {
         println!("  Signing package...");
 
-        // Expand tilde in paths
-        let private_key_path =
-            PathBuf::from(shellexpand::tilde(&config.signing.private_key_path).to_string());
-        let public_key_path =
-            PathBuf::from(shellexpand::tilde(&config.signing.public_key_path).to_string());
+        let (private_key_path, public_key_path) = config.signing.key_paths();
 
         // Load or generate keys
         let keys = load_or_generate_keys(&private_key_path, &public_key_path)?;
@@ -324,10 +404,10 @@ pub fn run(
         let signature = keys.sign(&tarball_bytes);
 
         // Save signature
-        let signatures_dir = registry.get_signatures_dir();
-        fs::create_dir_all(&signatures_dir)?;
-
-        let signature_path = registry.get_signature_path(&plugin_name, &uplugin.version_name);
+        let signature_path = registry.get_signature_path(&package_name, &uplugin.version_name);
+        if let Some(parent) = signature_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
         fs::write(&signature_path, signature.to_bytes())?;
 
         let public_key_hex = keys.public_key_hex();
@@ -345,7 +425,8 @@ pub fn run(
 
     // Create/update package metadata
     let packages_dir = registry.get_packages_dir();
-    let metadata_path = packages_dir.join(format!("{}.json", plugin_name));
+    let metadata_path =
+        packages_dir.join(format!("{}.json", unrealpm::scope::encode(&package_name)));
 
     let mut package_metadata = if metadata_path.exists() {
         // Load existing metadata
@@ -354,51 +435,64 @@ pub fn run(
     } else {
         // Create new metadata
         PackageMetadata {
-            name: plugin_name.clone(),
+            name: package_name.clone(),
             description: uplugin.description.clone(),
             versions: vec![],
+            categories: Vec::new(),
+            keywords: Vec::new(),
         }
     };
 
+    // The latest publish decides how the package is discovered
+    if !tags.is_empty() {
+        package_metadata.categories = tags.categories.clone();
+        package_metadata.keywords = tags.keywords.clone();
+    }
+
     // Add new version
-    let package_type = if include_binaries {
+    let package_type = if !bundled.is_empty() {
         PackageType::Binary
     } else {
         PackageType::Source
     };
 
-    let new_version = PackageVersion {
-        version: uplugin.version_name.clone(),
-        tarball: tarball_name.clone(),
-        checksum,
-        engine_versions: if is_multi_engine {
-            uplugin.engine_version.as_ref().map(|v| vec![v.clone()])
-        } else {
-            None
-        },
-        engine_major,
-        engine_minor,
-        is_multi_engine,
-        package_type,
-        binaries: None, // Will be added manually or via future `publish-binary` command
-        dependencies: if plugin_dependencies.is_empty() {
-            None
-        } else {
-            Some(
-                plugin_dependencies
-                    .iter()
-                    .map(|p| unrealpm::Dependency {
-                        name: p.name.clone(),
-                        version: "*".to_string(), // Default to any version
-                    })
-                    .collect(),
-            )
-        },
-        public_key: public_key_hex,
-        signed_at,
-    };
+    // Every target shares the same tarball and signature
+    for target in &targets {
+        let new_version = PackageVersion {
+            version: uplugin.version_name.clone(),
+            tarball: tarball_name.clone(),
+            checksum: checksum.clone(),
+            engine_versions: target.engine_versions(&uplugin),
+            engine_major: target.major,
+            engine_minor: target.minor,
+            is_multi_engine: target.is_multi_engine,
+            package_type: package_type.clone(),
+            // Binaries bundled into the main tarball; others are attached
+            // per platform with `publish-binary`
+            binaries: Some(bundled_binaries(&bundled, target, &uplugin, &checksum))
+                .filter(|binaries| !binaries.is_empty()),
+            dependencies: if plugin_dependencies.is_empty() {
+                None
+            } else {
+                Some(
+                    plugin_dependencies
+                        .iter()
+                        .map(|p| unrealpm::Dependency {
+                            name: p.name.clone(),
+                            version: "*".to_string(), // Default to any version
+                        })
+                        .collect(),
+                )
+            },
+            public_key: public_key_hex.clone(),
+            signed_at: signed_at.clone(),
+            key_endorsement: None,
+            modules: Some(uplugin.module_names()),
+            features: (!features.is_empty()).then(|| features.clone()),
+        };
 
-    package_metadata.versions.push(new_version);
+        package_metadata.versions.push(new_version);
+    }
 
     // Save metadata
     let metadata_json = serde_json::to_string_pretty(&package_metadata)?;
@@ -412,17 +506,342 @@ pub fn run(
 
     println!(
         "✓ Successfully published {}@{}",
-        plugin_name, uplugin.version_name
+        package_name, uplugin.version_name
     );
     println!();
     println!("Install with:");
-    println!("  unrealpm install {}", plugin_name);
+    println!("  unrealpm install {}", package_name);
     println!();
 
     Ok(())
 }
 
-fn create_tarball(source_dir: &Path, output_path: &Path, include_binaries: bool) -> Result<()> {
+/// Attach a pre-built binary for one platform/engine to an already published version
+///
+/// Meant for CI where each platform builds on its own machine: every runner
+/// publishes its own `Binaries/<Platform>` artifact. Re-running is safe; an
+/// identical binary is accepted as-is, and a different one is rejected unless
+/// `replace` is set.
+pub fn run_binary(
+    path: Option<String>,
+    platform: String,
+    engine: String,
+    configuration: Option<String>,
+    replace: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let plugin_dir = match path {
+        Some(p) => PathBuf::from(p),
+        None => env::current_dir()?,
+    };
+
+    let uplugin_path = UPlugin::find(&plugin_dir)?;
+    let uplugin = UPlugin::load(&uplugin_path)?;
+    let plugin_name = UPlugin::name(&uplugin_path)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine plugin name from file"))?;
+    let package_name = package_name(&plugin_dir, &plugin_name)?;
+    let version = uplugin.version_name.clone();
+    let engine = unrealpm::platform::normalize_engine_version(&engine);
+
+    let binary = PrebuiltBinary {
+        platform: platform.clone(),
+        engine: engine.clone(),
+        tarball: String::new(),
+        checksum: String::new(),
+        configuration,
+    };
+    let target_label = binary.target();
+
+    println!(
+        "Publishing {} binary for {}@{}...",
+        target_label, package_name, version
+    );
+    println!();
+
+    // `build` packages each configuration separately; without one, the
+    // plugin's own Binaries/ are published
+    let source_dir = binary
+        .configuration
+        .as_deref()
+        .map(|configuration| {
+            crate::commands::build::package_dir(&plugin_dir, &platform, configuration)
+        })
+        .filter(|dir| dir.join("Binaries").join(&platform).is_dir())
+        .unwrap_or_else(|| plugin_dir.clone());
+    let binaries_dir = source_dir.join("Binaries").join(&platform);
+    if !binaries_dir.is_dir() {
+        anyhow::bail!(
+            "No binaries found at {}\n\n\
+            Build them first:\n\
+              unrealpm build --engine {} --platform {}{}",
+            binaries_dir.display(),
+            engine,
+            platform,
+            binary
+                .configuration
+                .as_deref()
+                .map(|configuration| format!(" --config {}", configuration))
+                .unwrap_or_default()
+        );
+    }
+
+    // The registry key for this artifact; file registries store it as
+    // <name>-<version>-<platform>-<engine>[-<configuration>].tar.gz
+    let binary_id = binary.artifact_id(&version);
+    let temp_dir = env::temp_dir().join(format!("unrealpm-publish-{}", plugin_name));
+    fs::create_dir_all(&temp_dir)?;
+    let tarball_path = temp_dir.join(format!("{}-{}.tar.gz", plugin_name, binary_id));
+    let folder_name = plugin_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Could not determine plugin name"))?;
+    create_binary_tarball(&source_dir, folder_name, &tarball_path, &platform)?;
+    let config = Config::load()?;
+    crate::commands::pack::enforce_limits(&tarball_path, &config)?;
+    let checksum = calculate_checksum(&tarball_path)?;
+
+    println!("  ✓ Packed {}", binaries_dir.display());
+    println!("    Checksum: {}", checksum);
+    println!();
+
+    let registry = RegistryClient::from_config(&config)?;
+
+    // Client-side conflict check against what the registry already has
+    let existing = registry.get_package(&package_name).map_err(|_| {
+        anyhow::anyhow!(
+            "Package '{}' not found in registry.\n\n\
+            Publish the version before attaching binaries: unrealpm publish",
+            package_name
+        )
+    })?;
+    let target = find_binary_target(&existing, &version, &engine).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{}@{} (UE {}) not found in registry.\n\n\
+            Publish the version before attaching binaries: unrealpm publish",
+            package_name,
+            version,
+            engine
+        )
+    })?;
+
+    let binary = PrebuiltBinary {
+        tarball: binary_id.clone(),
+        checksum: checksum.clone(),
+        ..binary
+    };
+    let mut updated = existing.versions[target].clone();
+    let outcome = updated.upsert_binary(binary.clone(), replace)?;
+
+    if outcome == BinaryUpsert::Unchanged {
+        fs::remove_dir_all(&temp_dir)?;
+        println!(
+            "✓ {} binary already attached to {}@{} - nothing to do",
+            target_label, package_name, version
+        );
+        return Ok(());
+    }
+
+    if dry_run {
+        fs::remove_dir_all(&temp_dir)?;
+        println!(
+            "[DRY RUN] Would {} {} binary on {}@{}",
+            if outcome == BinaryUpsert::Replaced {
+                "replace"
+            } else {
+                "attach"
+            },
+            target_label,
+            package_name,
+            version
+        );
+        return Ok(());
+    }
+
+    let signing = if config.signing.enabled {
+        let (private_key_path, public_key_path) = config.signing.key_paths();
+        let keys = load_or_generate_keys(&private_key_path, &public_key_path)?;
+        let signature = keys.sign(&fs::read(&tarball_path)?);
+        println!("  ✓ Binary signed");
+        Some((
+            signature.to_bytes(),
+            keys.public_key_hex(),
+            Utc::now().to_rfc3339(),
+        ))
+    } else {
+        None
+    };
+
+    match &registry {
+        RegistryClient::Http(http_client) => {
+            let sig_path = match &signing {
+                Some((signature, _, _)) => {
+                    let sig_path = tarball_path.with_extension("sig");
+                    fs::write(&sig_path, signature)?;
+                    Some(sig_path)
+                }
+                None => None,
+            };
+            let metadata = unrealpm::registry_http::BinaryPublishMetadata {
+                platform: platform.clone(),
+                engine: engine.clone(),
+                configuration: binary.configuration.clone(),
+                checksum,
+                replace,
+                public_key: signing.as_ref().map(|(_, key, _)| key.clone()),
+                signed_at: signing.as_ref().map(|(_, _, at)| at.clone()),
+            };
+            http_client.publish_binary(
+                &package_name,
+                &version,
+                &tarball_path,
+                sig_path.as_deref(),
+                &metadata,
+            )?;
+        }
+        RegistryClient::File(_) => {
+            let dest = registry.get_tarball_path(&package_name, &binary_id);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(&tarball_path, dest)?;
+            if let Some((signature, _, _)) = &signing {
+                let signature_path = registry.get_signature_path(&package_name, &binary_id);
+                if let Some(parent) = signature_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(signature_path, signature)?;
+            }
+
+            // Re-read and re-apply so concurrent publishes of other platforms are kept
+            let metadata_path = registry
+                .get_packages_dir()
+                .join(format!("{}.json", unrealpm::scope::encode(&package_name)));
+            let mut package_metadata: PackageMetadata =
+                serde_json::from_str(&fs::read_to_string(&metadata_path)?)?;
+            let target = find_binary_target(&package_metadata, &version, &engine)
+                .ok_or_else(|| anyhow::anyhow!("{}@{} disappeared", package_name, version))?;
+            package_metadata.versions[target].upsert_binary(binary, replace)?;
+            fs::write(
+                &metadata_path,
+                serde_json::to_string_pretty(&package_metadata)?,
+            )?;
+        }
+    }
+
+    fs::remove_dir_all(&temp_dir)?;
+
+    println!(
+        "✓ {} {} binary on {}@{}",
+        if outcome == BinaryUpsert::Replaced {
+            "Replaced"
+        } else {
+            "Attached"
+        },
+        target_label,
+        package_name,
+        version
+    );
+    Ok(())
+}
+
+/// Index of the version a binary attaches to: the engine-specific build for
+/// `engine` if one exists, otherwise the multi-engine version
+fn find_binary_target(metadata: &PackageMetadata, version: &str, engine: &str) -> Option<usize> {
+    let engine_specific = metadata.versions.iter().position(|v| {
+        v.version == version
+            && !v.is_multi_engine
+            && v.engine_major
+                .zip(v.engine_minor)
+                .map(|(major, minor)| format!("{}.{}", major, minor))
+                == Some(engine.to_string())
+    });
+    engine_specific.or_else(|| {
+        metadata
+            .versions
+            .iter()
+            .position(|v| v.version == version && v.is_multi_engine)
+    })
+}
+
+/// Pack the plugin with only `Binaries/<platform>` as its binaries
+///
+/// `source_dir` is either the plugin itself or a configuration's packaged
+/// build; either way the archive is rooted at the plugin's `folder_name`.
+fn create_binary_tarball(
+    source_dir: &Path,
+    folder_name: &str,
+    output_path: &Path,
+    platform: &str,
+) -> Result<()> {
+    let tar_gz = File::create(output_path)?;
+    let enc = GzEncoder::new(tar_gz, Compression::default());
+    let mut tar = tar::Builder::new(enc);
+
+    let binaries_dir = source_dir.join("Binaries");
+
+    for entry in walkdir::WalkDir::new(source_dir)
+        .into_iter()
+        .filter_entry(|e| {
+            should_include_entry(e, true)
+                && (e.path().parent() != Some(binaries_dir.as_path())
+                    || e.file_name().to_string_lossy() == platform)
+        })
+    {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_file() {
+            let relative_path = path.strip_prefix(source_dir)?;
+            tar.append_path_with_name(path, PathBuf::from(folder_name).join(relative_path))?;
+        }
+    }
+
+    tar.finish()?;
+    Ok(())
+}
+
+/// Check that the packed tarball carries the metadata installers will read
+///
+/// Installers read the `.uplugin` and `unrealpm.json` straight from the
+/// tarball, so a descriptor lost to an exclude pattern (or shadowed by a
+/// stray copy) would only surface on someone else's machine.
+fn validate_packed_metadata(
+    tarball_path: &Path,
+    plugin_dir: &Path,
+    plugin_name: &str,
+    uplugin: &UPlugin,
+) -> Result<()> {
+    let packed = read_tarball_metadata(tarball_path).map_err(|e| {
+        anyhow::anyhow!(
+            "Packed tarball is not installable: {}\n\n\
+            Make sure the .uplugin file is not excluded from the package",
+            e
+        )
+    })?;
+
+    if packed.plugin_name != plugin_name || packed.uplugin.version_name != uplugin.version_name {
+        anyhow::bail!(
+            "Packed tarball describes {}@{}, expected {}@{}\n\n\
+            Remove extra .uplugin files from the plugin root",
+            packed.plugin_name,
+            packed.uplugin.version_name,
+            plugin_name,
+            uplugin.version_name
+        );
+    }
+
+    if Manifest::exists(plugin_dir) && packed.manifest.is_none() {
+        println!(
+            "  ⚠ {} was not packed; its dependencies won't be visible to installers",
+            Manifest::file_name(plugin_dir)
+        );
+    }
+
+    Ok(())
+}
+
+/// Pack the plugin source, with the `bundled` builds as its `Binaries/`
+fn create_tarball(source_dir: &Path, output_path: &Path, bundled: &[BundledBuild]) -> Result<()> {
     let tar_gz = File::create(output_path)?;
     let enc = GzEncoder::new(tar_gz, Compression::default());
     let mut tar = tar::Builder::new(enc);
@@ -436,7 +855,7 @@ fn create_tarball(source_dir: &Path, output_path: &Path, include_binaries: bool)
     // Walk the directory and add files
     for entry in walkdir::WalkDir::new(source_dir)
         .into_iter()
-        .filter_entry(|e| should_include_entry(e, include_binaries))
+        .filter_entry(|e| should_include_entry(e, false))
     {
         let entry = entry?;
         let path = entry.path();
@@ -449,6 +868,20 @@ fn create_tarball(source_dir: &Path, output_path: &Path, include_binaries: bool)
         }
     }
 
+    for build in bundled {
+        let archive_dir = PathBuf::from(plugin_name)
+            .join("Binaries")
+            .join(&build.platform);
+        for entry in walkdir::WalkDir::new(&build.binaries_dir) {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_file() {
+                let relative_path = path.strip_prefix(&build.binaries_dir)?;
+                tar.append_path_with_name(path, archive_dir.join(relative_path))?;
+            }
+        }
+    }
+
     tar.finish()?;
     Ok(())
 }
@@ -517,6 +950,11 @@ fn should_include_entry(entry: &walkdir::DirEntry, include_binaries: bool) -> bo
         "*~",
     ];
 
+    // Dependencies a standalone plugin installed for itself
+    if entry.depth() == 1 && entry.file_name() == unrealpm::STANDALONE_DEPS_DIR {
+        return false;
+    }
+
     // Check if we should exclude binaries
     if !include_binaries && path_str.contains("Binaries") {
         return false;
@@ -540,6 +978,29 @@ fn calculate_checksum(file_path: &Path) -> Result<String> {
     Ok(format!("{:x}", hash))
 }
 
+/// Registry name to publish a plugin under: `name` from its unrealpm.json
+/// when that is scoped (`@studio/<PluginName>`), otherwise the plugin name
+fn package_name(plugin_dir: &Path, plugin_name: &str) -> Result<String> {
+    let Some(name) = Manifest::load(plugin_dir).ok().and_then(|m| m.name) else {
+        return Ok(plugin_name.to_string());
+    };
+    let Some(scope) = unrealpm::scope::scope(&name) else {
+        return Ok(plugin_name.to_string());
+    };
+    unrealpm::scope::validate(&name)?;
+    if unrealpm::scope::unscoped(&name) != plugin_name {
+        anyhow::bail!(
+            "unrealpm.json names the package {}, but the plugin is {}\n\n\
+            A scoped name ends in the plugin's name: {}/{}",
+            name,
+            plugin_name,
+            scope,
+            plugin_name
+        );
+    }
+    Ok(name)
+}
+
 /// Publish to HTTP registry
 #[allow(clippy::too_many_arguments)]
 fn publish_to_http(
@@ -549,23 +1010,20 @@ fn publish_to_http(
     uplugin: &UPlugin,
     checksum: &str,
     config: &Config,
-    engine_major: Option<i32>,
-    engine_minor: Option<i32>,
-    engine_patch: Option<i32>,
-    is_multi_engine: bool,
+    target: &EngineTarget,
     git_repo: Option<String>,
     git_ref: Option<String>,
     plugin_dependencies: &[&unrealpm::UPluginDependency],
+    features: &HashMap<String, Vec<unrealpm::Dependency>>,
     readme_content: Option<(String, String)>,
+    changelog: Option<String>,
+    tags: &DiscoveryTags,
 ) -> Result<()> {
     // Sign the package if enabled
     let (public_key, signed_at, signature_path) = if config.signing.enabled {
         println!("  Signing package...");
 
-        let private_key_path =
-            PathBuf::from(shellexpand::tilde(&config.signing.private_key_path).to_string());
-        let public_key_path =
-            PathBuf::from(shellexpand::tilde(&config.signing.public_key_path).to_string());
+        let (private_key_path, public_key_path) = config.signing.key_paths();
 
         let keys = unrealpm::load_or_generate_keys(&private_key_path, &public_key_path)?;
         let tarball_bytes = fs::read(tarball_path)?;
@@ -605,11 +1063,7 @@ fn publish_to_http(
         description: uplugin.description.clone(),
         checksum: checksum.to_string(),
         package_type: "source".to_string(), // TODO: Handle binary packages
-        engine_versions: if is_multi_engine {
-            uplugin.engine_version.as_ref().map(|v| vec![v.clone()])
-        } else {
-            None // Engine-specific versions don't use array
-        },
+        engine_versions: target.engine_versions(uplugin),
         dependencies: if plugin_dependencies.is_empty() {
             None
         } else {
@@ -625,14 +1079,31 @@ fn publish_to_http(
         },
         public_key,
         signed_at,
-        engine_major,
-        engine_minor,
-        engine_patch,
-        is_multi_engine: Some(is_multi_engine),
+        engine_major: target.major,
+        engine_minor: target.minor,
+        engine_patch: target.patch,
+        is_multi_engine: Some(target.is_multi_engine),
         git_repository: git_repo,
         git_tag: git_ref,
         readme,
         readme_type,
+        changelog,
+        categories: tags.categories.clone(),
+        keywords: tags.keywords.clone(),
+        modules: uplugin.module_names(),
+        features: features
+            .iter()
+            .map(|(feature, deps)| {
+                let specs = deps
+                    .iter()
+                    .map(|d| unrealpm::registry_http::DependencySpec {
+                        name: d.name.clone(),
+                        version: d.version.clone(),
+                    })
+                    .collect();
+                (feature.clone(), specs)
+            })
+            .collect(),
     };
 
     // Publish via HTTP
@@ -641,6 +1112,209 @@ fn publish_to_http(
     Ok(())
 }
 
+/// One platform's binaries packed into the main tarball
+struct BundledBuild {
+    platform: String,
+    /// Configuration that produced the binaries; `None` when they come from
+    /// the plugin's own `Binaries/` and it is unknown
+    configuration: Option<String>,
+    binaries_dir: PathBuf,
+}
+
+/// Binaries to pack with the plugin, one set per platform
+///
+/// A platform's `build` output for `configuration` (under `Saved/Packages/`)
+/// is preferred; otherwise the plugin's own `Binaries/<platform>` is used.
+fn bundled_builds(plugin_dir: &Path, configuration: &str) -> Vec<BundledBuild> {
+    let dir_names = |dir: PathBuf| -> Vec<String> {
+        fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .collect()
+    };
+    let suffix = format!("-{}", configuration);
+    let mut platforms: Vec<String> = dir_names(plugin_dir.join("Binaries"))
+        .into_iter()
+        .chain(
+            dir_names(plugin_dir.join("Saved").join("Packages"))
+                .iter()
+                .filter_map(|name| name.strip_suffix(&suffix).map(str::to_string)),
+        )
+        .collect();
+    platforms.sort();
+    platforms.dedup();
+
+    platforms
+        .into_iter()
+        .filter_map(|platform| {
+            let packaged =
+                crate::commands::build::package_dir(plugin_dir, &platform, configuration)
+                    .join("Binaries")
+                    .join(&platform);
+            let (binaries_dir, configuration) = if packaged.is_dir() {
+                (packaged, Some(configuration.to_string()))
+            } else {
+                (plugin_dir.join("Binaries").join(&platform), None)
+            };
+            binaries_dir.is_dir().then_some(BundledBuild {
+                platform,
+                configuration,
+                binaries_dir,
+            })
+        })
+        .collect()
+}
+
+/// Describe the platform binaries packed into the main tarball
+///
+/// Each entry points at the version's own tarball so installers can match
+/// it by platform, engine and configuration.
+fn bundled_binaries(
+    bundled: &[BundledBuild],
+    target: &EngineTarget,
+    uplugin: &UPlugin,
+    checksum: &str,
+) -> Vec<PrebuiltBinary> {
+    let engine = match (target.major, target.minor) {
+        (Some(major), Some(minor)) => format!("{}.{}", major, minor),
+        _ => match &uplugin.engine_version {
+            Some(engine) => unrealpm::platform::normalize_engine_version(engine),
+            None => return Vec::new(),
+        },
+    };
+    bundled
+        .iter()
+        .map(|build| PrebuiltBinary {
+            platform: build.platform.clone(),
+            engine: engine.clone(),
+            tarball: uplugin.version_name.clone(),
+            checksum: checksum.to_string(),
+            configuration: build.configuration.clone(),
+        })
+        .collect()
+}
+
+/// Engine compatibility for one registry row created by `publish`
+struct EngineTarget {
+    major: Option<i32>,
+    minor: Option<i32>,
+    patch: Option<i32>,
+    is_multi_engine: bool,
+    /// Exact engine list for a multi-engine row (`None` uses the .uplugin's EngineVersion)
+    engine_versions: Option<Vec<String>>,
+}
+
+impl EngineTarget {
+    fn label(&self) -> String {
+        match (&self.engine_versions, self.major, self.minor) {
+            (Some(list), _, _) => format!("UE {}", list.join(", ")),
+            (None, Some(major), Some(minor)) => {
+                format!("UE {}.{}.{}", major, minor, self.patch.unwrap_or(0))
+            }
+            _ => "all engines".to_string(),
+        }
+    }
+
+    /// The `engine_versions` array to publish
+    fn engine_versions(&self, uplugin: &UPlugin) -> Option<Vec<String>> {
+        if !self.is_multi_engine {
+            // Engine-specific versions don't use array
+            return None;
+        }
+        self.engine_versions
+            .clone()
+            .or_else(|| uplugin.engine_version.as_ref().map(|v| vec![v.clone()]))
+    }
+}
+
+/// Turn repeated `--engine` values into registry rows
+///
+/// No engines publishes one multi-engine version (from the .uplugin). Each
+/// engine otherwise gets its own engine-specific row, or with `multi_engine`
+/// they are combined into one multi-engine row listing exactly those engines.
+fn parse_engine_targets(engines: &[String], multi_engine: bool) -> Result<Vec<EngineTarget>> {
+    if engines.is_empty() {
+        return Ok(vec![EngineTarget {
+            major: None,
+            minor: None,
+            patch: None,
+            is_multi_engine: true,
+            engine_versions: None,
+        }]);
+    }
+
+    let mut parsed: Vec<unrealpm::EngineVersion> = Vec::new();
+    for eng in engines {
+        // Parse engine version (e.g., "5.3", "4.27", "5.4.2", "5.4.0-preview")
+        let version = unrealpm::EngineVersion::parse(eng).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid engine version '{}'. Use: 4.27, 5.3, 5.4.0-preview, etc.",
+                eng
+            )
+        })?;
+        if !parsed
+            .iter()
+            .any(|p| p.major == version.major && p.minor == version.minor)
+        {
+            parsed.push(version);
+        }
+    }
+
+    if multi_engine {
+        return Ok(vec![EngineTarget {
+            major: None,
+            minor: None,
+            patch: None,
+            is_multi_engine: true,
+            engine_versions: Some(
+                parsed
+                    .iter()
+                    .map(|v| format!("{}.{}", v.major, v.minor))
+                    .collect(),
+            ),
+        }]);
+    }
+
+    Ok(parsed
+        .iter()
+        .map(|v| EngineTarget {
+            major: Some(v.major as i32),
+            minor: Some(v.minor as i32),
+            patch: Some(v.patch.unwrap_or(0) as i32),
+            is_multi_engine: false,
+            engine_versions: None,
+        })
+        .collect())
+}
+
+/// Find a changelog file (CHANGELOG.md, CHANGES, HISTORY.md, ...) in the plugin directory
+/// Returns (filename, content) if found
+fn find_changelog(plugin_dir: &Path) -> Option<(String, String)> {
+    let mut entries: Vec<_> = fs::read_dir(plugin_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    entries.sort();
+
+    // Stems in order of preference
+    unrealpm::changelog::CHANGELOG_STEMS
+        .iter()
+        .find_map(|stem| {
+            entries.iter().find_map(|path| {
+                let matches = path
+                    .file_stem()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(stem));
+                let content = fs::read_to_string(path).ok().filter(|_| matches)?;
+                Some((path.file_name()?.to_string_lossy().to_string(), content))
+            })
+        })
+}
+
 /// Find README file in plugin directory
 /// Returns (filename, content) if found
 fn find_readme(plugin_dir: &Path) -> Option<(String, String)> {
@@ -0,0 +1,205 @@
+//! Rebuild command - replace pre-built binaries made for another engine
+//!
+//! Binaries are chosen for the engine in unrealpm.json, which `unrealpm
+//! init` copies from the .uproject's EngineAssociation. After the project
+//! is switched to another engine, the installed binaries no longer load.
+//! unrealpm.lock records the engine they were chosen for, every command
+//! warns when the .uproject now names a different one, and
+//! `unrealpm rebuild --stale` reinstalls those packages for the new engine:
+//! its pre-built binary where the registry has one, else the source.
+
+use anyhow::Result;
+use std::env;
+use std::path::Path;
+use unrealpm::{
+    eoutln, outln, remove_installed_package, Config, DryRun, Lockfile, Manifest, UProject,
+    LOCKFILE_NAME,
+};
+
+use crate::commands::install::{LockfileMode, UnmanagedPolicy};
+
+/// The .uproject's EngineAssociation and the engine version it maps to
+fn project_engine(
+    project_dir: &Path,
+    manifest: &Manifest,
+    config: &Config,
+) -> Option<(String, String)> {
+    let uproject = manifest
+        .uproject_path(project_dir)
+        .and_then(UProject::load)
+        .ok()?;
+    let engine = config.logical_engine_version(&uproject.engine_association);
+    Some((uproject.engine_association, engine))
+}
+
+/// Warn when installed binaries were built for an engine other than the
+/// one the project now uses
+///
+/// Silent outside projects and when anything can't be read.
+pub fn warn_if_stale(config: Option<&Config>) {
+    let Ok(project_dir) = env::current_dir() else {
+        return;
+    };
+    let Ok(manifest) = Manifest::load(&project_dir) else {
+        return;
+    };
+    let Ok(Some(lockfile)) = Lockfile::load_from(project_dir.join(LOCKFILE_NAME)) else {
+        return;
+    };
+    let default_config = Config::default();
+    let config = config.unwrap_or(&default_config);
+    let Some((_, engine)) = project_engine(&project_dir, &manifest, config) else {
+        return;
+    };
+    let stale = lockfile.stale_binaries(&engine);
+    if stale.is_empty() {
+        return;
+    }
+
+    let previous = lockfile
+        .metadata
+        .engine
+        .clone()
+        .unwrap_or_else(|| stale[0].1.engine.clone());
+    eoutln!(
+        "⚠ The project now uses Unreal Engine {}, but {} installed plugin(s) are pre-built for {}:",
+        engine,
+        stale.len(),
+        previous
+    );
+    for (name, binary) in &stale {
+        eoutln!("  - {} ({})", name, binary.target());
+    }
+    eoutln!(
+        "  Run `unrealpm rebuild --stale` to install them for {}",
+        engine
+    );
+    eoutln!();
+}
+
+pub fn run(stale_only: bool, dry_run: bool) -> Result<()> {
+    let current_dir = env::current_dir()?;
+    if !Manifest::exists(&current_dir) {
+        anyhow::bail!("No unrealpm.json found in current directory");
+    }
+    let mut manifest = Manifest::load(&current_dir)?;
+    let lockfile_path = current_dir.join(LOCKFILE_NAME);
+    let Some(mut lockfile) = Lockfile::load_from(&lockfile_path)? else {
+        anyhow::bail!("No unrealpm.lock found; run `unrealpm install` first");
+    };
+    let config = Config::load()?;
+    let Some((association, engine)) = project_engine(&current_dir, &manifest, &config) else {
+        anyhow::bail!("Could not read the engine version from the .uproject");
+    };
+
+    let packages: Vec<(String, String)> = if stale_only {
+        lockfile
+            .stale_binaries(&engine)
+            .into_iter()
+            .map(|(name, binary)| (name.clone(), binary.target()))
+            .collect()
+    } else {
+        let mut all: Vec<(String, String)> = lockfile
+            .packages
+            .iter()
+            .filter_map(|(name, package)| Some((name.clone(), package.binary.as_ref()?.target())))
+            .collect();
+        all.sort();
+        all
+    };
+    if packages.is_empty() {
+        outln!(
+            "✓ No pre-built binaries to replace; installed binaries match Unreal Engine {}",
+            engine
+        );
+        return Ok(());
+    }
+
+    outln!(
+        "{}Reinstalling {} package(s) for Unreal Engine {}:",
+        if dry_run { "[DRY RUN] " } else { "" },
+        packages.len(),
+        engine
+    );
+    for (name, target) in &packages {
+        outln!("  - {} (was {})", name, target);
+    }
+    outln!();
+
+    // Resolution and artifact selection read the engine from unrealpm.json
+    let engine_changed = manifest.engine_version.as_deref() != Some(association.as_str());
+    if engine_changed {
+        outln!(
+            "  Engine in unrealpm.json: {} → {}",
+            manifest.engine_version.as_deref().unwrap_or("(none)"),
+            association
+        );
+        outln!();
+    }
+    if dry_run {
+        outln!("[DRY RUN] No files were changed");
+        return Ok(());
+    }
+    if engine_changed {
+        manifest.engine_version = Some(association);
+        manifest.save(&current_dir)?;
+    }
+
+    // Dropping the lockfile entries makes the install choose artifacts afresh
+    for (name, _) in &packages {
+        remove_installed_package(&current_dir, name, DryRun::new(false))?;
+        lockfile.remove_package(name);
+    }
+    lockfile.save_to(&lockfile_path)?;
+
+    crate::commands::install::run(
+        Vec::new(),
+        Vec::new(),
+        None,
+        None,
+        false,
+        None,
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+        UnmanagedPolicy::Refuse,
+        true,
+        false,
+        None,
+        None,
+        false,
+        false,
+        LockfileMode::Update,
+        false,
+        None,
+        false,
+    )?;
+
+    let from_source: Vec<&str> = Lockfile::load_from(&lockfile_path)?
+        .map(|lockfile| {
+            packages
+                .iter()
+                .filter(|(name, _)| {
+                    lockfile
+                        .get_package(name)
+                        .is_some_and(|package| package.binary.is_none())
+                })
+                .map(|(name, _)| name.as_str())
+                .collect()
+        })
+        .unwrap_or_default();
+    if !from_source.is_empty() && !config.build.auto_build_on_install {
+        outln!(
+            "No Unreal Engine {} binaries were published for: {}",
+            engine,
+            from_source.join(", ")
+        );
+        outln!("They were installed from source and build with the project.");
+        outln!();
+    }
+
+    Ok(())
+}
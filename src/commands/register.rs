@@ -1,8 +1,18 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::io::{self, Write};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 use unrealpm::Config;
 
+use crate::commands::auth::{email_verified, error_message, resend_verification};
+
+/// How often to check whether the email address has been verified
+const VERIFICATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait for email verification before giving up
+const VERIFICATION_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
 #[derive(Debug, Serialize)]
 struct RegisterRequest {
     username: String,
@@ -17,30 +27,83 @@ struct RegisterResponse {
     success: bool,
     user_id: String,
     message: String,
+    #[serde(default)]
+    requires_verification: bool,
 }
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct LoginResponse {
     success: bool,
-    token: String,
-    expires_in: u64,
+    token: Option<String>,
+    expires_in: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
-struct ErrorResponse {
-    error: String,
+/// Result of a login attempt right after registering
+enum LoginAttempt {
+    LoggedIn {
+        token: String,
+        expires_in: Option<u64>,
+    },
+    /// The registry refuses logins until the email address is verified
+    Unverified,
+    Failed(String),
 }
 
-pub fn run() -> Result<()> {
+pub fn run(use_github: bool, use_email: bool, no_wait: bool) -> Result<()> {
+    crate::commands::login::ensure_configured_registry()?;
+
+    // GitHub accounts are created on first sign-in
+    if use_github {
+        return crate::commands::login::run(true, false);
+    }
+    if use_email {
+        return run_email_registration(no_wait);
+    }
+
     println!("Register for UnrealPM Registry");
     println!();
+    println!("Choose sign-up method:");
+    println!("  [1] GitHub (recommended)");
+    println!("  [2] Email/Password");
+    println!();
+    print!("Enter choice (1 or 2): ");
+    io::stdout().flush()?;
+
+    let mut choice = String::new();
+    io::stdin().read_line(&mut choice)?;
+
+    match choice.trim() {
+        "1" | "github" | "g" => {
+            println!();
+            crate::commands::login::run(true, false)
+        }
+        "2" | "email" | "e" => {
+            println!();
+            run_email_registration(no_wait)
+        }
+        _ => {
+            println!();
+            println!("Invalid choice. Please run 'unrealpm register' again.");
+            println!();
+            println!("Or use flags directly:");
+            println!("  unrealpm register --github");
+            println!("  unrealpm register --email");
+            Ok(())
+        }
+    }
+}
+
+/// Create an account with email and password, verify it, and log in
+fn run_email_registration(no_wait: bool) -> Result<()> {
+    println!("Register with Email/Password");
+    println!();
 
     // Load config to get registry URL
     let mut config = Config::load().context("Failed to load config")?;
 
     let registry_url = if config.registry.registry_type == "http" {
-        config.registry.url.clone()
+        config.registry.url.trim_end_matches('/').to_string()
     } else {
         println!("ERROR: You are using a file-based registry.");
         println!("Registration is only supported for HTTP registries.");
@@ -51,6 +114,19 @@ pub fn run() -> Result<()> {
         anyhow::bail!("File-based registry does not support authentication");
     };
 
+    // Security: Require HTTPS for passwords (except localhost for development)
+    let is_localhost = registry_url.contains("localhost") || registry_url.contains("127.0.0.1");
+    if !registry_url.starts_with("https://") && !is_localhost {
+        println!("ERROR: Email/password registration requires HTTPS for security.");
+        println!();
+        println!("Your current registry URL: {}", registry_url);
+        println!();
+        println!("Either:");
+        println!("  1. Use HTTPS: unrealpm config set registry.url https://registry.unreal.dev");
+        println!("  2. Sign up with GitHub instead: unrealpm register --github");
+        anyhow::bail!("Refusing to send credentials over unencrypted connection");
+    }
+
     // Prompt for username
     print!("Username: ");
     io::stdout().flush()?;
@@ -142,90 +218,196 @@ pub fn run() -> Result<()> {
 
     let status = response.status();
 
-    if status.is_success() {
-        let register_response: RegisterResponse = response
-            .json()
-            .context("Failed to parse registration response")?;
+    if !status.is_success() {
+        let error_msg = error_message(response, status);
 
-        println!("✓ Registration successful!");
-        println!();
-        println!("  User ID: {}", register_response.user_id);
+        println!("✗ Registration failed: {}", error_msg);
         println!();
 
-        // Auto-login after successful registration
-        println!("Logging you in...");
+        if status.as_u16() == 409 {
+            println!("This username or email is already taken.");
+            println!("Please try a different username or email, or log in:");
+            println!("  unrealpm login");
+        } else if status.as_u16() == 400 {
+            println!("Invalid input. Please check your details and try again.");
+        } else if status.as_u16() == 404 {
+            println!("Registry endpoint not found. Is the registry server running?");
+            println!("Registry URL: {}", registry_url);
+        }
 
-        let login_url = format!("{}/api/v1/auth/login", registry_url);
-        let login_body = serde_json::json!({
-            "email": email,
-            "password": password,
-        });
+        anyhow::bail!("Registration failed");
+    }
 
-        let login_response = client
-            .post(&login_url)
-            .json(&login_body)
-            .send()
-            .context("Failed to login after registration")?;
+    let register_response: RegisterResponse = response
+        .json()
+        .context("Failed to parse registration response")?;
 
-        if login_response.status().is_success() {
-            let login_data: LoginResponse = login_response
-                .json()
-                .context("Failed to parse login response")?;
+    println!("✓ Account created");
+    println!("  User ID: {}", register_response.user_id);
+    println!();
 
-            // Save token to config
-            config.auth.token = Some(login_data.token);
-            config
-                .save()
-                .context("Failed to save authentication token to config")?;
+    // Log in straight away; some registries only allow it once the email is verified
+    let login = || login(&client, &registry_url, &email, &password);
+    let mut attempt = login()?;
+    let mut verified = false;
 
-            println!("✓ Logged in successfully!");
+    if matches!(attempt, LoginAttempt::Unverified) {
+        println!("We sent a verification link to {}.", email);
+        if no_wait {
             println!();
-            println!("Your authentication token has been saved to ~/.unrealpm/config.toml");
-            println!(
-                "Token expires in {} seconds (~{} hours)",
-                login_data.expires_in,
-                login_data.expires_in / 3600
-            );
-        } else {
-            println!("⚠ Registration successful, but auto-login failed.");
-            println!("  Please run: unrealpm login");
+            println!("After verifying, log in with: unrealpm login --email");
+            return Ok(());
         }
-
-        println!();
-        println!("You can now publish packages with: unrealpm publish");
         println!();
 
-        if register_response.message.contains("verify") {
-            println!("Note: {}", register_response.message);
-            println!("You may need to verify your email before publishing.");
+        let resend = || resend_verification(&client, &registry_url, &email, None);
+        wait_for_verification(
+            || {
+                attempt = login()?;
+                Ok(!matches!(attempt, LoginAttempt::Unverified))
+            },
+            resend,
+        )?;
+        verified = true;
+    }
+
+    let (token, expires_in) = match attempt {
+        LoginAttempt::LoggedIn { token, expires_in } => (token, expires_in),
+        LoginAttempt::Unverified => unreachable!("waited for verification above"),
+        LoginAttempt::Failed(error) => {
+            println!("⚠ Account created, but logging in failed: {}", error);
+            println!("  Please run: unrealpm login --email");
+            anyhow::bail!("Login after registration failed");
         }
-    } else {
-        // Try to parse error response
-        let error_msg = if let Ok(error_response) = response.json::<ErrorResponse>() {
-            error_response.error
-        } else {
-            format!(
-                "HTTP {}: {}",
-                status.as_u16(),
-                status.canonical_reason().unwrap_or("Unknown error")
-            )
-        };
+    };
 
-        println!("✗ Registration failed: {}", error_msg);
-        println!();
+    // Save token to config
+    config.auth.token = Some(token.clone());
+    config
+        .save()
+        .context("Failed to save authentication token to config")?;
+
+    println!("✓ Logged in");
+    println!("  Your authentication token has been saved to ~/.unrealpm/config.toml");
+    if let Some(expires_in) = expires_in {
+        println!(
+            "  Token expires in {} seconds (~{} hours)",
+            expires_in,
+            expires_in / 3600
+        );
+    }
+    println!();
 
-        if status.as_u16() == 409 {
-            println!("This username or email is already taken.");
-            println!("Please try a different username or email.");
-        } else if status.as_u16() == 400 {
-            println!("Invalid input. Please check your details and try again.");
-        } else if status.as_u16() == 404 {
-            println!("Registry endpoint not found. Is the registry server running?");
-            println!("Registry URL: {}", registry_url);
+    // Logged in but unverified: publishing still needs a verified address
+    let needs_verification = !verified
+        && (register_response.requires_verification
+            || !email_verified(&client, &registry_url, &token).unwrap_or(true));
+    if needs_verification {
+        println!("Verify your email address to publish packages.");
+        println!("We sent a verification link to {}.", email);
+        if no_wait {
+            println!();
+            println!("Check your status later with: unrealpm whoami");
+            return Ok(());
         }
+        println!();
 
-        anyhow::bail!("Registration failed");
+        wait_for_verification(
+            || email_verified(&client, &registry_url, &token),
+            || resend_verification(&client, &registry_url, &email, Some(&token)),
+        )?;
     }
 
+    println!("You can now publish packages with: unrealpm publish");
+    println!();
+
     Ok(())
 }
+
+/// Try to log in with the new account's credentials
+fn login(
+    client: &reqwest::blocking::Client,
+    registry_url: &str,
+    email: &str,
+    password: &str,
+) -> Result<LoginAttempt> {
+    let response = client
+        .post(format!("{}/api/v1/auth/login", registry_url))
+        .json(&serde_json::json!({
+            "email": email,
+            "password": password,
+        }))
+        .send()
+        .context("Failed to send login request")?;
+
+    let status = response.status();
+    if status.is_success() {
+        let login: LoginResponse = response.json().context("Failed to parse login response")?;
+        return Ok(match login.token {
+            Some(token) => LoginAttempt::LoggedIn {
+                token,
+                expires_in: login.expires_in,
+            },
+            None => LoginAttempt::Failed("no token was returned".to_string()),
+        });
+    }
+
+    let error = error_message(response, status);
+    if status.as_u16() == 403 && error.to_lowercase().contains("verif") {
+        return Ok(LoginAttempt::Unverified);
+    }
+    Ok(LoginAttempt::Failed(error))
+}
+
+/// Poll `check` until it reports the email as verified
+///
+/// Pressing Enter resends the verification email; Ctrl+C cancels.
+fn wait_for_verification(
+    mut check: impl FnMut() -> Result<bool>,
+    mut resend: impl FnMut() -> Result<()>,
+) -> Result<()> {
+    println!("Waiting for you to click the link...");
+    println!("(Press Enter to resend the email, Ctrl+C to cancel)");
+    println!();
+
+    // Read Enter presses without blocking the polling loop
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        while io::stdin().read_line(&mut line).is_ok_and(|n| n > 0) {
+            if tx.send(()).is_err() {
+                break;
+            }
+            line.clear();
+        }
+    });
+
+    let started = Instant::now();
+    loop {
+        if check()? {
+            println!("✓ Email verified");
+            println!();
+            return Ok(());
+        }
+
+        if started.elapsed() >= VERIFICATION_TIMEOUT {
+            anyhow::bail!(
+                "Timed out waiting for email verification\n\n\
+                Your account was created. After verifying, log in with:\n\
+                  unrealpm login --email"
+            );
+        }
+
+        match rx.recv_timeout(VERIFICATION_POLL_INTERVAL) {
+            Ok(()) => match resend() {
+                Ok(()) => println!("  ✓ Verification email sent again"),
+                Err(e) => println!("  ⚠ Could not resend verification email: {}", e),
+            },
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            // stdin closed; keep polling without resend support
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                std::thread::sleep(VERIFICATION_POLL_INTERVAL)
+            }
+        }
+    }
+}
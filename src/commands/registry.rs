@@ -0,0 +1,87 @@
+//! Registry command - maintain the local file registry
+//!
+//! `registry layout` shows or changes how tarballs and signatures are laid
+//! out on disk. The flat layout keeps every file in one directory, which gets
+//! slow to list past a few hundred packages; the sharded layout spreads them
+//! over `ab/cd/` subdirectories.
+
+use anyhow::Result;
+use unrealpm::{Config, DryRun, RegistryClient, TarballLayout};
+
+pub fn run(action: &crate::RegistryAction) -> Result<()> {
+    match action {
+        crate::RegistryAction::Layout { layout, dry_run } => {
+            run_layout(layout.as_deref(), DryRun::new(*dry_run))
+        }
+    }
+}
+
+fn run_layout(layout: Option<&str>, dry_run: DryRun) -> Result<()> {
+    let config = Config::load()?;
+    let RegistryClient::File(mut registry) = RegistryClient::from_config(&config)? else {
+        anyhow::bail!(
+            "The configured registry is an HTTP registry ({})\n\n\
+            Tarball layout only applies to file registries; the server manages its own storage.",
+            config.registry.url
+        );
+    };
+    let location = RegistryClient::default_registry_path()?;
+
+    let Some(layout) = layout else {
+        println!("Registry: {}", location.display());
+        println!("Layout:   {}", registry.layout());
+        return Ok(());
+    };
+    let Some(target) = TarballLayout::parse(layout) else {
+        anyhow::bail!("Unknown layout '{}'\n\nUse one of: flat, sharded", layout);
+    };
+
+    if target == registry.layout() {
+        println!("✓ Registry already uses the {} layout", target);
+        return Ok(());
+    }
+
+    println!(
+        "{}Migrating {} from {} to {} layout...",
+        dry_run.prefix(),
+        location.display(),
+        registry.layout(),
+        target
+    );
+    println!();
+
+    let migration = registry.migrate_layout(target, dry_run)?;
+    let relative = |path: &std::path::Path| {
+        path.strip_prefix(&location)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    for (from, to) in &migration.moved {
+        println!("  {} → {}", relative(from), relative(to));
+    }
+    for path in &migration.missing {
+        println!("  ⚠ Missing {}", relative(path));
+    }
+    if !migration.moved.is_empty() || !migration.missing.is_empty() {
+        println!();
+    }
+
+    if dry_run.is_enabled() {
+        println!("Would move {} file(s)", migration.moved.len());
+    } else {
+        println!(
+            "✓ Moved {} file(s); registry now uses the {} layout",
+            migration.moved.len(),
+            target
+        );
+    }
+    if !migration.missing.is_empty() {
+        println!(
+            "⚠ {} tarball(s) listed in package metadata were not found; installs of those versions will fail",
+            migration.missing.len()
+        );
+    }
+
+    Ok(())
+}
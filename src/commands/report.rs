@@ -0,0 +1,225 @@
+//! Report command - cross-project dependency report
+//!
+//! `unrealpm report --scan <dir>` walks a directory tree of Unreal projects,
+//! reads every unrealpm.lock it finds, and reports which plugin versions are
+//! used where. Packages locked at different versions across projects are
+//! flagged as divergent, and duplicate installed copies are totalled to show
+//! how much disk a shared store would save.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use unrealpm::{Lockfile, LOCKFILE_NAME};
+
+use crate::commands::cache::{dir_size, format_size};
+
+/// Directories that never contain project lockfiles worth reporting
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    "Binaries",
+    "DerivedDataCache",
+    "Intermediate",
+    "Plugins",
+    "Saved",
+    "node_modules",
+];
+
+/// One project using a specific package version
+struct Usage {
+    project: String,
+    checksum: String,
+    /// Size of the installed copy in Plugins/, if present
+    size: Option<u64>,
+}
+
+pub fn run(scan: String, json: bool) -> Result<()> {
+    let scan = PathBuf::from(shellexpand::tilde(&scan).to_string());
+    if !scan.is_dir() {
+        anyhow::bail!("Scan directory not found: {}", scan.display());
+    }
+
+    let projects = find_projects(&scan);
+
+    // package -> version -> usages
+    let mut packages: BTreeMap<String, BTreeMap<String, Vec<Usage>>> = BTreeMap::new();
+
+    for project_dir in &projects {
+        let lockfile = match Lockfile::load_from(project_dir.join(LOCKFILE_NAME)) {
+            Ok(Some(lockfile)) => lockfile,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("⚠ Skipping {}: {}", project_dir.display(), e);
+                continue;
+            }
+        };
+
+        let project = project_dir
+            .strip_prefix(&scan)
+            .ok()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .display()
+            .to_string();
+
+        for (name, locked) in lockfile.packages {
+            // Fab plugins live in the engine, not in a per-project copy
+            let size = if locked.is_externally_managed() {
+                None
+            } else {
+                unrealpm::installed_plugin_dir(project_dir, &name, locked.artifact_checksum())
+                    .map(|p| dir_size(&p))
+            };
+
+            packages
+                .entry(name)
+                .or_default()
+                .entry(locked.version)
+                .or_default()
+                .push(Usage {
+                    project: project.clone(),
+                    checksum: locked.checksum,
+                    size,
+                });
+        }
+    }
+
+    let divergent: Vec<&String> = packages
+        .iter()
+        .filter(|(_, versions)| versions.len() > 1)
+        .map(|(name, _)| name)
+        .collect();
+
+    let installed: u64 = packages
+        .values()
+        .flat_map(|versions| versions.values().flatten())
+        .filter_map(|u| u.size)
+        .sum();
+    let savings: u64 = packages
+        .values()
+        .flat_map(|versions| versions.values())
+        .map(|usages| duplicate_size(usages))
+        .sum();
+
+    if json {
+        let output = serde_json::json!({
+            "scan": scan,
+            "projects": projects.len(),
+            "installed_bytes": installed,
+            "shared_store_savings_bytes": savings,
+            "divergent": divergent,
+            "packages": packages
+                .iter()
+                .map(|(name, versions)| {
+                    let versions: BTreeMap<_, _> = versions
+                        .iter()
+                        .map(|(version, usages)| {
+                            (
+                                version,
+                                usages.iter().map(|u| &u.project).collect::<Vec<_>>(),
+                            )
+                        })
+                        .collect();
+                    (name, versions)
+                })
+                .collect::<BTreeMap<_, _>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!("Scanned {} ({} projects)", scan.display(), projects.len());
+    println!();
+
+    if packages.is_empty() {
+        println!("No locked packages found.");
+        println!();
+        println!("Projects need an unrealpm.lock - run `unrealpm install` in each project.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<30} {:<12} {:<8} Projects",
+        "Package", "Version", "Count"
+    );
+    println!("{}", "-".repeat(80));
+
+    for (name, versions) in &packages {
+        let marker = if versions.len() > 1 { "⚠ " } else { "  " };
+        for (i, (version, usages)) in versions.iter().enumerate() {
+            let label = if i == 0 {
+                format!("{}{}", marker, name)
+            } else {
+                String::new()
+            };
+            println!(
+                "{:<30} {:<12} {:<8} {}",
+                label,
+                version,
+                usages.len(),
+                usages
+                    .iter()
+                    .map(|u| u.project.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    println!();
+    if divergent.is_empty() {
+        println!("✓ No version divergence across projects");
+    } else {
+        println!(
+            "⚠ {} package(s) locked at different versions across projects:",
+            divergent.len()
+        );
+        for name in &divergent {
+            let versions: Vec<&str> = packages[*name].keys().map(|v| v.as_str()).collect();
+            println!("  • {} ({})", name, versions.join(", "));
+        }
+    }
+
+    println!();
+    println!("Disk usage:");
+    println!("  Installed plugin copies:     {}", format_size(installed));
+    println!("  Saved with a shared store:   {}", format_size(savings));
+    if savings > 0 {
+        println!();
+        println!("Projects installed with the CAS store already share hard-linked files.");
+    }
+
+    Ok(())
+}
+
+/// Find project directories (containing unrealpm.lock) under `root`, sorted
+fn find_projects(root: &Path) -> Vec<PathBuf> {
+    let mut projects: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || !e.file_type().is_dir()
+                || !SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref())
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == LOCKFILE_NAME)
+        .filter_map(|e| e.path().parent().map(|p| p.to_path_buf()))
+        .collect();
+    projects.sort();
+    projects
+}
+
+/// Bytes taken by duplicate copies of identical content (same checksum)
+fn duplicate_size(usages: &[Usage]) -> u64 {
+    let mut by_checksum: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    for usage in usages {
+        if let Some(size) = usage.size {
+            by_checksum.entry(&usage.checksum).or_default().push(size);
+        }
+    }
+
+    // Keep one copy of each distinct package content, count the rest
+    by_checksum
+        .values()
+        .map(|sizes| sizes.iter().sum::<u64>() - sizes.iter().max().copied().unwrap_or(0))
+        .sum()
+}
@@ -0,0 +1,208 @@
+//! Report-bug command - bundle diagnostics for an issue report
+//!
+//! `unrealpm report-bug` writes `unrealpm-bug-report-<timestamp>.tar.gz` with
+//! what maintainers usually have to ask for:
+//! - report.txt: unrealpm version, OS, and the `unrealpm doctor` results
+//! - config.toml: the user config, with the auth token and URL credentials removed
+//! - commands.log: the most recent commands and how they ended
+//! - panic.log: the last crash, if there was one
+//! - incidents.jsonl: recent checksum incidents
+//! - the current project's manifest, lockfile, and status file
+//!
+//! Every command is appended to `~/.unrealpm/logs/commands.log` (the last
+//! [`MAX_LOGGED_COMMANDS`] are kept). A panic is written to
+//! `~/.unrealpm/logs/panic.log` and the user is asked to run
+//! `unrealpm report-bug`.
+
+use anyhow::Result;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use unrealpm::{
+    outln, Config, StatusFile, LOCKFILE_NAME, MANIFEST_FILE_NAME, TOML_MANIFEST_FILE_NAME,
+};
+
+/// Commands kept in commands.log
+const MAX_LOGGED_COMMANDS: usize = 50;
+
+/// Checksum incidents included in the bundle
+const MAX_INCIDENTS: usize = 20;
+
+const COMMANDS_LOG: &str = "commands.log";
+const PANIC_LOG: &str = "panic.log";
+
+/// Flags whose value is a secret
+const SECRET_FLAGS: &[&str] = &["--token", "--password"];
+
+/// `~/.unrealpm/logs/`
+fn logs_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".unrealpm").join("logs"))
+}
+
+/// The command line with secrets replaced, for logs
+fn sanitize_args(args: &[String]) -> String {
+    let mut sanitized = vec!["unrealpm".to_string()];
+    let mut secret_next = false;
+    for arg in args.iter().skip(1) {
+        let flag = arg.split_once('=').map_or(arg.as_str(), |(flag, _)| flag);
+        sanitized.push(if secret_next || arg.starts_with("urpm_") {
+            "<redacted>".to_string()
+        } else if SECRET_FLAGS.contains(&flag) && arg.contains('=') {
+            format!("{}=<redacted>", flag)
+        } else {
+            arg.clone()
+        });
+        secret_next = SECRET_FLAGS.contains(&arg.as_str());
+    }
+    sanitized.join(" ")
+}
+
+/// Append a command and its outcome to commands.log
+pub fn record_command(args: &[String], outcome: &str) {
+    let Some(dir) = logs_dir() else {
+        return;
+    };
+    let path = dir.join(COMMANDS_LOG);
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let mut lines: Vec<String> = existing.lines().map(str::to_string).collect();
+    lines.push(format!(
+        "{}\t{}\t{}",
+        chrono::Local::now().to_rfc3339(),
+        outcome.lines().next().unwrap_or_default(),
+        sanitize_args(args)
+    ));
+    let start = lines.len().saturating_sub(MAX_LOGGED_COMMANDS);
+    let _ = fs::create_dir_all(&dir);
+    let _ = fs::write(&path, lines[start..].join("\n") + "\n");
+}
+
+/// Write panics to panic.log and point the user at `unrealpm report-bug`
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        let args: Vec<String> = env::args().collect();
+        if let Some(dir) = logs_dir() {
+            let _ = fs::create_dir_all(&dir);
+            let _ = fs::write(
+                dir.join(PANIC_LOG),
+                format!(
+                    "{}\nunrealpm {}\ncommand: {}\n\n{}\n\n{}\n",
+                    chrono::Local::now().to_rfc3339(),
+                    env!("CARGO_PKG_VERSION"),
+                    sanitize_args(&args),
+                    info,
+                    std::backtrace::Backtrace::force_capture()
+                ),
+            );
+        }
+        record_command(&args, "panicked");
+        eprintln!();
+        eprintln!("unrealpm crashed. This is a bug; please report it:");
+        eprintln!("  1. Run `unrealpm report-bug` to collect the details");
+        eprintln!(
+            "  2. Attach the bundle to an issue at {}/issues",
+            env!("CARGO_PKG_REPOSITORY")
+        );
+    }));
+}
+
+pub fn run(output: Option<String>) -> Result<()> {
+    let current_dir = env::current_dir()?;
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let bundle_name = format!("unrealpm-bug-report-{}", timestamp);
+    let output = output
+        .map(PathBuf::from)
+        .unwrap_or_else(|| current_dir.join(format!("{}.tar.gz", bundle_name)));
+
+    outln!("Collecting diagnostics (this runs `unrealpm doctor`)...");
+    outln!();
+
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+    let mut report = format!(
+        "unrealpm {}\nOS: {} ({})\nDirectory: {}\n\n",
+        env!("CARGO_PKG_VERSION"),
+        env::consts::OS,
+        env::consts::ARCH,
+        current_dir.display()
+    );
+    report.push_str("Doctor\n======\n");
+    report.push_str(&crate::commands::doctor::diagnose());
+    files.push(("report.txt".to_string(), report.into_bytes()));
+
+    let config = match Config::load() {
+        Ok(config) => toml::to_string_pretty(&config.redacted())?,
+        Err(e) => format!("# Config failed to load: {}\n", e),
+    };
+    files.push(("config.toml".to_string(), config.into_bytes()));
+
+    if let Some(dir) = logs_dir() {
+        for log in [COMMANDS_LOG, PANIC_LOG] {
+            if let Ok(content) = fs::read(dir.join(log)) {
+                files.push((log.to_string(), content));
+            }
+        }
+    }
+
+    if let Ok(log) = unrealpm::integrity::IncidentLog::open_default() {
+        let incidents: Vec<String> = fs::read_to_string(log.path())
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        if !incidents.is_empty() {
+            let start = incidents.len().saturating_sub(MAX_INCIDENTS);
+            files.push((
+                "incidents.jsonl".to_string(),
+                (incidents[start..].join("\n") + "\n").into_bytes(),
+            ));
+        }
+    }
+
+    let project_files = [
+        current_dir.join(MANIFEST_FILE_NAME),
+        current_dir.join(TOML_MANIFEST_FILE_NAME),
+        current_dir.join(LOCKFILE_NAME),
+        StatusFile::path(&current_dir),
+    ];
+    for path in project_files {
+        if let (Ok(content), Some(name)) = (fs::read(&path), path.file_name()) {
+            files.push((format!("project/{}", name.to_string_lossy()), content));
+        }
+    }
+
+    let mut archive = tar::Builder::new(GzEncoder::new(
+        fs::File::create(&output)?,
+        Compression::default(),
+    ));
+    for (name, content) in &files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+        header.set_cksum();
+        archive.append_data(
+            &mut header,
+            format!("{}/{}", bundle_name, name),
+            content.as_slice(),
+        )?;
+    }
+    archive.into_inner()?.finish()?;
+
+    outln!("✓ Wrote {}", output.display());
+    outln!();
+    outln!("It contains:");
+    for (name, _) in &files {
+        outln!("  - {}", name);
+    }
+    outln!();
+    outln!("Tokens and URL credentials are removed, but the bundle includes your");
+    outln!("project's dependencies and local paths. Review it, then attach it to an");
+    outln!("issue at {}/issues", env!("CARGO_PKG_REPOSITORY"));
+    outln!();
+
+    Ok(())
+}
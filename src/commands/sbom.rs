@@ -0,0 +1,116 @@
+//! SBOM command - export a software bill of materials
+//!
+//! Converts unrealpm.lock into a CycloneDX or SPDX document, adding what the
+//! registry knows about each package: description, download URL,
+//! compatible engines, and the publisher's signature. Registry lookups that
+//! fail are reported on stderr and leave those fields out, so the document
+//! can still be produced offline.
+
+use anyhow::Result;
+use std::path::Path;
+use unrealpm::sbom::{PackageSignature, Sbom, SbomPackage};
+use unrealpm::{outln, Config, Lockfile, Manifest, RegistryClient, UProject, LOCKFILE_NAME};
+
+use crate::SbomFormat;
+
+pub fn run(format: SbomFormat, output: Option<String>) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let Some(lockfile) = Lockfile::load_from(current_dir.join(LOCKFILE_NAME))? else {
+        anyhow::bail!(
+            "No unrealpm.lock found in {}\n\n\
+            • Run 'unrealpm install' first",
+            current_dir.display()
+        );
+    };
+
+    let manifest = Manifest::load(&current_dir).ok();
+    let mut sbom = Sbom::from_lockfile(&project_name(&current_dir, manifest.as_ref()), &lockfile);
+    if let Some(manifest) = &manifest {
+        sbom.project_version = manifest.version.clone();
+        sbom.direct = manifest
+            .dependencies
+            .keys()
+            .chain(manifest.dev_dependencies.keys())
+            .chain(manifest.path_dependencies.keys())
+            .cloned()
+            .collect();
+    }
+
+    let config = Config::load()?;
+    let registry =
+        RegistryClient::from_config(&config)?.with_locked_registries(&lockfile, &config)?;
+    for package in sbom.packages.iter_mut().filter(|p| p.source.is_none()) {
+        if let Err(e) = add_registry_metadata(&registry, package) {
+            eprintln!(
+                "  ⚠ No registry metadata for {}@{}: {}",
+                package.name, package.version, e
+            );
+        }
+    }
+
+    let document = match format {
+        SbomFormat::Cyclonedx => sbom.to_cyclonedx(),
+        SbomFormat::Spdx => sbom.to_spdx(),
+    };
+    let json = serde_json::to_string_pretty(&document)? + "\n";
+
+    match output {
+        None => print!("{}", json),
+        Some(path) => {
+            let path = Path::new(&path);
+            unrealpm::vcs::track(&[path], || Ok(std::fs::write(path, &json)?))?;
+            outln!(
+                "✓ Wrote {} ({} package{})",
+                path.display(),
+                sbom.packages.len(),
+                if sbom.packages.len() == 1 { "" } else { "s" }
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Project name: the .uproject file stem, or the directory name
+fn project_name(project_dir: &Path, manifest: Option<&Manifest>) -> String {
+    let uproject = match manifest {
+        Some(manifest) => manifest.uproject_path(project_dir).ok(),
+        None => UProject::find(project_dir).ok(),
+    };
+    uproject
+        .and_then(|path| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .or_else(|| {
+            project_dir
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+        })
+        .unwrap_or_default()
+}
+
+fn add_registry_metadata(registry: &RegistryClient, package: &mut SbomPackage) -> Result<()> {
+    let metadata = registry.get_package(&package.name)?;
+    package.description = metadata.description;
+    let Some(version) = metadata
+        .versions
+        .into_iter()
+        .find(|v| v.version == package.version)
+    else {
+        anyhow::bail!("version not listed by the registry");
+    };
+
+    if version.tarball.starts_with("https://") || version.tarball.starts_with("http://") {
+        package.download_url = Some(version.tarball);
+    }
+    package.engine_versions = version.engine_versions.unwrap_or_default();
+
+    if let Some(public_key) = version.public_key {
+        let signature = registry
+            .download_signature(&package.name, &package.version)
+            .and_then(|path| Ok(std::fs::read(path)?))?;
+        package.signature = Some(PackageSignature {
+            signature: hex::encode(signature),
+            public_key,
+            signed_at: version.signed_at,
+        });
+    }
+    Ok(())
+}
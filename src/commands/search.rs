@@ -1,40 +1,86 @@
 use anyhow::Result;
-use unrealpm::{Config, RegistryClient};
+use std::io::IsTerminal;
+use unrealpm::{Config, RegistryClient, SearchHit};
 
-pub fn run(query: String) -> Result<()> {
-    println!("Searching for: {}", query);
+pub fn run(query: String, category: Option<String>) -> Result<()> {
+    match &category {
+        Some(category) if query.is_empty() => println!("Packages in category: {}", category),
+        Some(category) => println!("Searching for: {} (category: {})", query, category),
+        None => println!("Searching for: {}", query),
+    }
     println!();
 
     // Get registry client (uses HTTP if configured)
     let config = Config::load()?;
     let registry = RegistryClient::from_config(&config)?;
-    let results = registry.search(&query)?;
+    let results = registry.search_ranked(&query, category.as_deref())?;
 
     if results.is_empty() {
-        println!("No packages found matching '{}'", query);
+        match &category {
+            Some(category) => println!(
+                "No packages found matching '{}' in category '{}'",
+                query, category
+            ),
+            None => println!("No packages found matching '{}'", query),
+        }
         println!();
         println!("Try a different search term or check the registry.");
+        if category.is_some() {
+            println!("See available categories with: unrealpm categories");
+        }
         return Ok(());
     }
 
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+
     println!(
         "Found {} package{}:",
         results.len(),
         if results.len() == 1 { "" } else { "s" }
     );
-    for package_name in &results {
-        // Try to get metadata to show description
-        if let Ok(metadata) = registry.get_package(package_name) {
-            if let Some(desc) = metadata.description {
-                println!("  {} - {}", package_name, desc);
-            } else {
-                println!("  {}", package_name);
-            }
-        } else {
-            println!("  {}", package_name);
-        }
+    for hit in &results {
+        print_hit(hit, color);
     }
     println!();
 
     Ok(())
 }
+
+fn print_hit(hit: &SearchHit, color: bool) {
+    let mut line = format!("  {}", highlight(&hit.name, &hit.name_matches, color));
+    if let Some(version) = &hit.latest_version {
+        line.push_str(&format!(" ({})", version));
+    }
+    if let Some(desc) = &hit.description {
+        line.push_str(&format!(
+            " - {}",
+            highlight(desc, &hit.description_matches, color)
+        ));
+    }
+    if !hit.categories.is_empty() {
+        line.push_str(&format!(" [{}]", hit.categories.join(", ")));
+    }
+    println!("{}", line);
+}
+
+/// Render matched characters in bold yellow (only on a color terminal)
+fn highlight(text: &str, matches: &[usize], color: bool) -> String {
+    if !color || matches.is_empty() {
+        return text.to_string();
+    }
+
+    let mut out = String::new();
+    let mut in_match = false;
+    for (i, c) in text.chars().enumerate() {
+        let matched = matches.binary_search(&i).is_ok();
+        if matched != in_match {
+            out.push_str(if matched { "\x1b[1;33m" } else { "\x1b[0m" });
+            in_match = matched;
+        }
+        out.push(c);
+    }
+    if in_match {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
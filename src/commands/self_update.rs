@@ -0,0 +1,91 @@
+use anyhow::Result;
+use std::env;
+use unrealpm::self_update::{
+    cleanup_previous, download_asset, fetch_release, managed_by, release_url, replace_executable,
+    ReleaseChannel,
+};
+use unrealpm::Config;
+
+pub fn run(channel: Option<ReleaseChannel>, check: bool, force: bool) -> Result<()> {
+    let exe = env::current_exe()?;
+    let exe = exe.canonicalize().unwrap_or(exe);
+    cleanup_previous(&exe);
+
+    let config = Config::load()?;
+    let channel = channel.or(config.self_update.channel).unwrap_or_default();
+    let current = env!("CARGO_PKG_VERSION");
+
+    println!("Checking the {} channel for updates...", channel);
+    let client = reqwest::blocking::Client::new();
+    let url = release_url(&config.self_update.releases_url(&config.registry), channel);
+    let release = fetch_release(&client, &url)?;
+
+    if !release.is_newer_than(current) && !force {
+        println!("✓ unrealpm {} is up to date ({})", current, channel);
+        return Ok(());
+    }
+
+    println!("  Current: {}", current);
+    println!("  Latest:  {} ({})", release.version, channel);
+    if let Some(notes) = &release.notes {
+        println!("  Release notes: {}", notes);
+    }
+    println!();
+
+    if check {
+        println!("Run 'unrealpm self-update' to install it.");
+        return Ok(());
+    }
+
+    if let Some(manager) = managed_by(&exe) {
+        anyhow::bail!(
+            "This copy of unrealpm is managed by {}\n\
+             • Update it with that installer instead of self-update",
+            manager
+        );
+    }
+
+    let platform = unrealpm::platform::detect_platform();
+    let asset = release.asset_for(&platform).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Release {} has no binary for {} (available: {})",
+            release.version,
+            platform,
+            release
+                .assets
+                .iter()
+                .map(|a| a.platform.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+
+    let public_key = config.self_update.public_key();
+    if public_key.is_none() {
+        unrealpm::strict::warn(
+            "No release key configured; only the download's checksum is checked \
+             (set one with: unrealpm config set self_update.public_key <hex>)",
+        )?;
+    }
+
+    println!("  Downloading {}...", asset.url);
+    let binary = download_asset(&client, asset, public_key)?;
+    println!(
+        "  ✓ Verified {}",
+        if public_key.is_some() {
+            "checksum and signature"
+        } else {
+            "checksum"
+        }
+    );
+
+    replace_executable(&exe, &binary)?;
+    println!();
+    println!("✓ Updated unrealpm {} -> {}", current, release.version);
+    if cfg!(windows) {
+        println!("  The previous binary is removed on the next self-update.");
+    }
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! Store commands for the global CAS store shared by all projects
+//!
+//! - `store gc` - Remove store entries no registered project uses
+//!
+//! Projects register themselves in `~/.unrealpm/projects.json` whenever a
+//! command loads their lockfile, so `store gc` can tell which entries any
+//! project's lockfile still refers to. Unlike `cache clean`, it never removes
+//! an entry another registered project depends on. A project that hasn't run
+//! unrealpm since the index was created is unknown to it, so the first
+//! collection, and any with no registered projects, needs `--yes`.
+
+use super::cache::format_size;
+use anyhow::Result;
+use unrealpm::store::{collect_garbage, ProjectIndex, StoreLock};
+use unrealpm::{get_store_dir, DryRun};
+
+/// Remove store entries unreferenced by every registered project
+pub fn run_gc(dry_run: DryRun, assume_yes: bool) -> Result<()> {
+    let _lock = match StoreLock::try_exclusive()? {
+        Some(lock) => lock,
+        None => {
+            println!("Waiting for running installs to finish...");
+            StoreLock::exclusive()?
+        }
+    };
+
+    let store_dir = get_store_dir()?;
+    let mut index = ProjectIndex::open_default()?;
+    println!(
+        "{}Collecting garbage in {} ({} registered projects)...",
+        dry_run.prefix(),
+        store_dir.display(),
+        index.len()
+    );
+    println!();
+
+    if !dry_run.is_enabled() && !assume_yes {
+        if index.is_empty() {
+            anyhow::bail!(
+                "No projects are registered, so every store entry would be removed\n\n\
+                Projects register when unrealpm runs in them. Run `unrealpm list` in each\n\
+                project that uses the store, or pass --yes to remove everything."
+            );
+        }
+        if index.last_collected().is_none() {
+            for project in index.iter() {
+                println!("  {}", project.display());
+            }
+            println!();
+            anyhow::bail!(
+                "This is the first collection, and only the projects above are registered\n\n\
+                Projects register when unrealpm runs in them; ones that haven't since the\n\
+                index was created would lose their packages. Run `unrealpm list` in each\n\
+                project that uses the store, or pass --yes if the list is complete.\n\
+                Preview with: unrealpm store gc --dry-run"
+            );
+        }
+    }
+
+    let report = collect_garbage(&store_dir, &mut index, dry_run)?;
+
+    for project in &report.forgotten {
+        println!(
+            "  {} {} (no lockfile)",
+            dry_run.verb("Forgot", "Would forget"),
+            project.display()
+        );
+    }
+    if !report.forgotten.is_empty() {
+        println!();
+    }
+
+    if report.removed.is_empty() {
+        println!(
+            "No unused packages found; all {} store entries are in use by {} projects.",
+            report.kept, report.projects
+        );
+        return Ok(());
+    }
+
+    for entry in &report.removed {
+        let short_hash = entry.checksum.get(..12).unwrap_or(&entry.checksum);
+        let marker = if entry.incomplete {
+            " (incomplete)"
+        } else {
+            ""
+        };
+        println!(
+            "  {}...  {:>10}{}",
+            short_hash,
+            format_size(entry.size),
+            marker
+        );
+    }
+    println!();
+    println!(
+        "{} {} packages, {} {} ({} kept for {} projects)",
+        dry_run.verb("Removed", "Would remove"),
+        report.removed.len(),
+        dry_run.verb("reclaimed", "reclaiming"),
+        format_size(report.reclaimed),
+        report.kept,
+        report.projects
+    );
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::io::{self, Write};
-use unrealpm::{config::AuthConfig, Config};
+use unrealpm::{config::AuthConfig, Config, Error};
 
 #[derive(Debug, Serialize)]
 struct CreateTokenRequest {
@@ -49,10 +49,8 @@ pub fn run_create(name: String, scopes: Vec<String>, expires_days: Option<i64>)
 
     // Check we're logged in
     let auth_token = config
-        .auth
-        .token
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Not logged in. Run: unrealpm login"))?;
+        .auth_token(std::env::current_dir()?)
+        .ok_or_else(|| Error::Unauthorized("Not logged in. Run: unrealpm login".to_string()))?;
 
     // Default scopes if none provided
     let scopes = if scopes.is_empty() {
@@ -73,7 +71,7 @@ pub fn run_create(name: String, scopes: Vec<String>, expires_days: Option<i64>)
 
     let response = client
         .post(&url)
-        .header("Authorization", AuthConfig::format_auth_header(auth_token))
+        .header("Authorization", AuthConfig::format_auth_header(&auth_token))
         .json(&request_body)
         .send()
         .context("Failed to create token")?;
@@ -127,10 +125,8 @@ pub fn run_list() -> Result<()> {
 
     // Check we're logged in
     let auth_token = config
-        .auth
-        .token
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Not logged in. Run: unrealpm login"))?;
+        .auth_token(std::env::current_dir()?)
+        .ok_or_else(|| Error::Unauthorized("Not logged in. Run: unrealpm login".to_string()))?;
 
     // Send request
     let client = reqwest::blocking::Client::new();
@@ -138,7 +134,7 @@ pub fn run_list() -> Result<()> {
 
     let response = client
         .get(&url)
-        .header("Authorization", AuthConfig::format_auth_header(auth_token))
+        .header("Authorization", AuthConfig::format_auth_header(&auth_token))
         .send()
         .context("Failed to list tokens")?;
 
@@ -199,10 +195,8 @@ pub fn run_revoke(token_id: String) -> Result<()> {
 
     // Check we're logged in
     let auth_token = config
-        .auth
-        .token
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Not logged in. Run: unrealpm login"))?;
+        .auth_token(std::env::current_dir()?)
+        .ok_or_else(|| Error::Unauthorized("Not logged in. Run: unrealpm login".to_string()))?;
 
     // Confirm
     print!("Are you sure you want to revoke this token? (yes/no): ");
@@ -222,7 +216,7 @@ pub fn run_revoke(token_id: String) -> Result<()> {
 
     let response = client
         .delete(&url)
-        .header("Authorization", AuthConfig::format_auth_header(auth_token))
+        .header("Authorization", AuthConfig::format_auth_header(&auth_token))
         .send()
         .context("Failed to revoke token")?;
 
@@ -21,7 +21,10 @@ pub fn run() -> Result<()> {
     let manifest = Manifest::load(&current_dir)?;
     let lockfile = Lockfile::load()?;
 
-    if manifest.dependencies.is_empty() {
+    if manifest.dependencies.is_empty()
+        && manifest.path_dependencies.is_empty()
+        && manifest.dev_dependencies.is_empty()
+    {
         println!("No dependencies to display.");
         println!();
         return Ok(());
@@ -71,6 +74,72 @@ pub fn run() -> Result<()> {
         }
     }
 
+    // Path dependencies are linked working copies, marked with their path
+    let mut linked: Vec<_> = manifest.path_dependencies.iter().collect();
+    linked.sort();
+    for (name, path) in linked {
+        match lockfile.get_package(name) {
+            Some(pkg) if pkg.linked_path().is_some() => print_tree_node(
+                name,
+                &pkg.version,
+                &format!("path: {}", path),
+                &dep_map,
+                0,
+                true,
+                &mut visited,
+                &HashSet::new(),
+            ),
+            _ => println!("├── {} (path: {}, not linked)", name, path),
+        }
+    }
+
+    // Dev dependencies are listed apart, since --production skips them
+    let mut dev: Vec<_> = manifest
+        .dev_dependencies
+        .iter()
+        .filter(|(name, _)| !manifest.dependencies.contains_key(*name))
+        .collect();
+    if !dev.is_empty() {
+        dev.sort();
+        println!();
+        println!("Dev dependencies:");
+        for (name, constraint) in dev {
+            match lockfile.get_package(name) {
+                Some(pkg) => print_tree_node(
+                    name,
+                    &pkg.version,
+                    constraint,
+                    &dep_map,
+                    0,
+                    true,
+                    &mut visited,
+                    &HashSet::new(),
+                ),
+                None => println!("├── {} (not installed)", name),
+            }
+        }
+    }
+
+    // Fab/Marketplace plugins sit outside the dependency graph
+    let mut external: Vec<_> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, pkg)| pkg.is_externally_managed())
+        .collect();
+    if !external.is_empty() {
+        external.sort_by_key(|(name, _)| name.as_str());
+        println!();
+        println!("Externally managed (Fab/Marketplace):");
+        for (name, pkg) in external {
+            println!(
+                "├── {}@{} ({})",
+                name,
+                pkg.version,
+                pkg.source.as_deref().unwrap_or_default()
+            );
+        }
+    }
+
     println!();
     Ok(())
 }
@@ -0,0 +1,95 @@
+//! Trust command - accept a package publisher's new signing key
+//!
+//! Installs pin the key a package is first signed with and refuse other keys
+//! for it afterwards. Once a publisher has confirmed they rotated their key,
+//! `unrealpm trust <package>` pins the key the registry now lists for the
+//! package's latest signed version (or the one given with `--key`).
+//! `unrealpm trust --list` shows every pinned key.
+
+use anyhow::Result;
+use unrealpm::trust::TrustStore;
+use unrealpm::{Config, RegistryClient};
+
+pub fn run(package: Option<String>, key: Option<String>, list: bool) -> Result<()> {
+    let mut store = TrustStore::open_default()?;
+    match package {
+        Some(package) if !list => trust(&mut store, &package, key),
+        _ => {
+            show(&store);
+            Ok(())
+        }
+    }
+}
+
+fn show(store: &TrustStore) {
+    let pinned: Vec<_> = store.iter().collect();
+    if pinned.is_empty() {
+        println!("No publisher keys pinned yet.");
+        println!("Keys are pinned the first time a signed package is installed.");
+        return;
+    }
+    println!("Pinned publisher keys ({}):", store.path().display());
+    println!();
+    for (package, key) in pinned {
+        println!(
+            "  {:<30} {}  (trusted {})",
+            package,
+            key.public_key,
+            key.origin()
+        );
+    }
+}
+
+fn trust(store: &mut TrustStore, package: &str, key: Option<String>) -> Result<()> {
+    let (public_key, version) = match key {
+        Some(key) => (key, String::new()),
+        None => latest_signing_key(package)?,
+    };
+    let valid = public_key.len() == 64 && public_key.chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        anyhow::bail!(
+            "Invalid public key '{}': expected 64 hex characters (an Ed25519 key)",
+            public_key
+        );
+    }
+
+    if store
+        .get(package)
+        .is_some_and(|pinned| pinned.public_key.eq_ignore_ascii_case(&public_key))
+    {
+        println!("✓ {} is already trusted for {}", public_key, package);
+        return Ok(());
+    }
+
+    let replaced = store.pin(package, &public_key, &version);
+    store.save()?;
+
+    println!("✓ Trusting {} for {}", public_key, package);
+    match replaced {
+        Some(old) => println!("  Replaces {} (trusted {})", old.public_key, old.origin()),
+        None => println!("  No key was pinned for {} before", package),
+    }
+    Ok(())
+}
+
+/// Key of the newest signed version the registry lists, and that version
+fn latest_signing_key(package: &str) -> Result<(String, String)> {
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+    let metadata = registry.get_package(package)?;
+    metadata
+        .versions
+        .into_iter()
+        .filter_map(|v| {
+            let parsed = semver::Version::parse(&v.version).ok()?;
+            Some((parsed, v.public_key?, v.version))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, key, version)| (key, version))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No signed version of {} found in the registry; pass the key with --key",
+                package
+            )
+        })
+}
@@ -1,12 +1,24 @@
 use anyhow::Result;
 use std::env;
 use std::fs;
-use unrealpm::{Lockfile, Manifest};
+use std::io::IsTerminal;
+use std::path::Path;
+use unrealpm::{remove_installed_package, Config, DryRun, InstallLayout, Lockfile, Manifest};
 
-pub fn run(package: String) -> Result<()> {
+pub fn run(packages: Vec<String>, all: bool, assume_yes: bool, dry_run: DryRun) -> Result<()> {
     let current_dir = env::current_dir()?;
+    if all {
+        return uninstall_all(&current_dir, assume_yes, dry_run);
+    }
 
-    println!("Uninstalling package: {}", package);
+    match packages.as_slice() {
+        [package] => println!("{}Uninstalling package: {}", dry_run.prefix(), package),
+        _ => println!(
+            "{}Uninstalling packages: {}",
+            dry_run.prefix(),
+            packages.join(", ")
+        ),
+    }
     println!();
 
     // Check if manifest exists
@@ -20,47 +32,156 @@ pub fn run(package: String) -> Result<()> {
     // Load manifest
     let mut manifest = Manifest::load(&current_dir)?;
 
-    // Check if package is in manifest
-    if !manifest.dependencies.contains_key(&package) {
+    // Check if packages are in manifest, as regular or dev dependencies
+    let (packages, unknown): (Vec<String>, Vec<String>) =
+        packages.into_iter().partition(|package| {
+            manifest.dependencies.contains_key(package)
+                || manifest.dev_dependencies.contains_key(package)
+        });
+    for package in &unknown {
         println!("⚠ Package '{}' is not in dependencies", package);
+    }
+    if packages.is_empty() {
         println!();
         println!("Currently installed packages:");
         for (name, version) in &manifest.dependencies {
             println!("  - {}@{}", name, version);
         }
+        for (name, version) in &manifest.dev_dependencies {
+            println!("  - {}@{} (dev)", name, version);
+        }
         return Ok(());
     }
+    if !unknown.is_empty() {
+        println!();
+    }
 
     // Remove from Plugins/ directory
-    let plugin_path = current_dir.join("Plugins").join(&package);
-    if plugin_path.exists() {
+    let removed = dry_run.verb("Removed", "Would remove");
+    for package in &packages {
+        if packages.len() > 1 {
+            println!("  {}:", package);
+        }
         println!("  Removing from Plugins/...");
-        fs::remove_dir_all(&plugin_path)?;
-        println!("  ✓ Removed {}", plugin_path.display());
-    } else {
-        println!(
-            "  ⚠ Plugin directory not found at {}",
-            plugin_path.display()
-        );
-        println!("  (continuing with manifest/lockfile cleanup)");
+        match remove_installed_package(&current_dir, package, dry_run)? {
+            Some(plugin_path) => println!("  ✓ {} {}", removed, plugin_path.display()),
+            None => {
+                println!(
+                    "  ⚠ Plugin directory not found at {}",
+                    unrealpm::plugins_dir(&current_dir).join(package).display()
+                );
+                println!("  (continuing with manifest/lockfile cleanup)");
+            }
+        }
     }
 
     // Remove from manifest
     println!("  Updating manifest...");
-    manifest.dependencies.remove(&package);
-    manifest.save(&current_dir)?;
-    println!("  ✓ Removed from unrealpm.json");
+    for package in &packages {
+        manifest.dependencies.remove(package);
+        manifest.dev_dependencies.remove(package);
+        manifest.dependency_conditions.remove(package);
+        manifest.dependency_integrity.remove(package);
+        manifest.engine_overrides.remove(package);
+        manifest.dependency_features.remove(package);
+    }
+    dry_run.apply(|| manifest.save(&current_dir))?;
+    println!("  ✓ {} from unrealpm.json", removed);
 
     // Remove from lockfile if it exists
     if let Ok(Some(mut lockfile)) = Lockfile::load() {
         println!("  Updating lockfile...");
-        lockfile.remove_package(&package);
-        lockfile.save()?;
-        println!("  ✓ Removed from unrealpm.lock");
+        for package in &packages {
+            lockfile.remove_package(package);
+        }
+        dry_run.apply(|| lockfile.save())?;
+        println!("  ✓ {} from unrealpm.lock", removed);
+    }
+
+    println!();
+    if dry_run.is_enabled() {
+        println!("[DRY RUN] No files were changed");
+        return Ok(());
+    }
+
+    let layout = crate::commands::install::install_layout(&current_dir, &Config::load()?, None);
+    crate::commands::install::sync_store_layout(&current_dir, layout);
+    crate::commands::install::write_status_file(&current_dir, "uninstall");
+    crate::commands::vcs_ignore::sync_configured(&current_dir);
+
+    println!("✓ Successfully uninstalled {}", packages.join(", "));
+    println!();
+
+    Ok(())
+}
+
+/// `uninstall --all`: remove every plugin UnrealPM installed, keeping
+/// unrealpm.json and unrealpm.lock so `unrealpm install` brings back the
+/// same versions
+fn uninstall_all(project_dir: &Path, assume_yes: bool, dry_run: DryRun) -> Result<()> {
+    println!("{}Uninstalling all packages", dry_run.prefix());
+    println!();
+
+    let Some(lockfile) = Lockfile::load_from(project_dir.join(unrealpm::LOCKFILE_NAME))? else {
+        println!("No unrealpm.lock found - nothing to uninstall.");
+        return Ok(());
+    };
+
+    // Fab/Marketplace plugins belong to the Epic Games Launcher
+    let mut packages: Vec<(&String, &unrealpm::LockedPackage)> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, locked)| !locked.is_externally_managed())
+        .collect();
+    packages.sort_by(|a, b| a.0.cmp(b.0));
+
+    println!("This removes {} plugin(s):", packages.len());
+    for (name, locked) in &packages {
+        println!("  - {}@{}", name, locked.version);
+    }
+    println!();
+    println!("unrealpm.json and unrealpm.lock are kept; run `unrealpm install` to reinstall");
+    println!("the same versions.");
+    println!();
+
+    if !dry_run.is_enabled() && !assume_yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to remove every plugin without confirmation; rerun with --yes");
+        }
+        print!("Continue? [y/N]: ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !answer.trim().eq_ignore_ascii_case("y") {
+            println!("Uninstall cancelled; nothing was changed.");
+            return Ok(());
+        }
+        println!();
+    }
+
+    let removed = dry_run.verb("Removed", "Would remove");
+    for (name, _) in &packages {
+        match remove_installed_package(project_dir, name, dry_run)? {
+            Some(plugin_path) => println!("  ✓ {} {}", removed, plugin_path.display()),
+            None => println!("  ✓ {} {} (no copy in Plugins/)", removed, name),
+        }
     }
 
     println!();
-    println!("✓ Successfully uninstalled {}", package);
+    if dry_run.is_enabled() {
+        println!("[DRY RUN] No files were changed");
+        return Ok(());
+    }
+
+    // Store folders registered in the .uproject go too
+    crate::commands::install::sync_store_layout(project_dir, InstallLayout::Copy);
+    let status_path = unrealpm::StatusFile::path(project_dir);
+    if status_path.exists() {
+        fs::remove_file(&status_path)?;
+    }
+    crate::commands::vcs_ignore::sync_configured(project_dir);
+
+    println!("✓ Uninstalled {} plugin(s)", packages.len());
     println!();
 
     Ok(())
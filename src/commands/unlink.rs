@@ -0,0 +1,72 @@
+//! Unlink command - undo `unrealpm link`
+//!
+//! In a game project, `unrealpm unlink <plugin>` removes the link from
+//! Plugins/; `unrealpm install` then restores the locked registry package,
+//! if there is one. In a plugin folder, `unrealpm unlink` removes the plugin from
+//! `~/.unrealpm/links.json`; links already made in projects keep working.
+
+use anyhow::Result;
+use std::env;
+use std::path::Path;
+use unrealpm::link::{self, GlobalLinks};
+use unrealpm::{outln, Lockfile, Manifest, UPlugin, LOCKFILE_NAME};
+
+pub fn run(plugin: Option<String>) -> Result<()> {
+    let current_dir = env::current_dir()?;
+
+    match plugin {
+        None => unregister(&current_dir),
+        Some(name) => unlink_from_project(&current_dir, &name),
+    }
+}
+
+/// Forget the plugin in `dir` in the global links registry
+fn unregister(dir: &Path) -> Result<()> {
+    let Ok(uplugin_path) = UPlugin::find(dir) else {
+        anyhow::bail!(
+            "No .uplugin found in {}\n\n\
+            • Unlink a plugin from a project: unrealpm unlink <plugin>",
+            dir.display()
+        );
+    };
+    let name = UPlugin::name(&uplugin_path)
+        .ok_or_else(|| anyhow::anyhow!("Could not determine plugin name from file"))?;
+
+    let mut links = GlobalLinks::open_default()?;
+    match links.unregister(&name) {
+        Some(registered) => {
+            links.save()?;
+            outln!("✓ Unregistered {} ({})", name, registered.display());
+        }
+        None => outln!("{} was not registered for linking", name),
+    }
+    Ok(())
+}
+
+/// Remove a link from the project's Plugins/ folder
+fn unlink_from_project(project_dir: &Path, name: &str) -> Result<()> {
+    if let Ok(manifest) = Manifest::load(project_dir) {
+        if let Some(path) = manifest.path_dependencies.get(name) {
+            anyhow::bail!(
+                "{} is a path dependency ({})\n\n\
+                • Remove it from {} and run 'unrealpm install'",
+                name,
+                path,
+                Manifest::file_name(project_dir)
+            );
+        }
+    }
+
+    let link_path = unrealpm::plugins_dir(project_dir).join(name);
+    let Some(target) = link::link_target(&link_path) else {
+        anyhow::bail!("{} is not linked into {}", name, link_path.display());
+    };
+    link::remove_link(&link_path)?;
+    outln!("✓ Unlinked {} ({})", name, target.display());
+
+    let lockfile = Lockfile::load_from(project_dir.join(LOCKFILE_NAME))?;
+    if lockfile.is_some_and(|lockfile| lockfile.has_package(name)) {
+        outln!("  • Reinstall the locked registry package: unrealpm install");
+    }
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use anyhow::Result;
-use unrealpm::{Config, RegistryClient};
+use unrealpm::{Config, DryRun, RegistryClient};
 
-pub fn run(package: String, version: Option<String>) -> Result<()> {
-    println!("Unpublishing package...");
+pub fn run(package: String, version: Option<String>, dry_run: DryRun) -> Result<()> {
+    println!("{}Unpublishing package...", dry_run.prefix());
     println!();
 
     // Load config
@@ -12,11 +12,11 @@ pub fn run(package: String, version: Option<String>) -> Result<()> {
     // Determine what to unpublish
     let (package_name, version_to_unpublish) = if let Some(v) = version {
         (package, Some(v))
-    } else if package.contains('@') {
-        let parts: Vec<&str> = package.splitn(2, '@').collect();
-        (parts[0].to_string(), Some(parts[1].to_string()))
     } else {
-        (package, None)
+        match unrealpm::scope::split_spec(&package) {
+            (name, Some(version)) => (name.to_string(), Some(version.to_string())),
+            (_, None) => (package, None),
+        }
     };
 
     // Confirm with user
@@ -33,6 +33,32 @@ pub fn run(package: String, version: Option<String>) -> Result<()> {
         println!("  This action CANNOT be undone!");
     }
     println!();
+
+    let http_client = match &registry {
+        RegistryClient::Http(http_client) => http_client,
+        RegistryClient::File(_) => {
+            anyhow::bail!("Unpublish is only supported for HTTP registries");
+        }
+    };
+
+    if dry_run.is_enabled() {
+        let deleted =
+            http_client.unpublish(&package_name, version_to_unpublish.as_deref(), dry_run)?;
+        println!("Would delete {} version row(s):", deleted.len());
+        for row in &deleted {
+            println!(
+                "  {}@{}  ({} downloads{})",
+                package_name,
+                row.version,
+                row.downloads,
+                if row.yanked { ", yanked" } else { "" }
+            );
+        }
+        println!();
+        println!("[DRY RUN] Nothing was deleted");
+        return Ok(());
+    }
+
     print!("Are you sure? (yes/no): ");
     std::io::Write::flush(&mut std::io::stdout())?;
 
@@ -49,14 +75,7 @@ pub fn run(package: String, version: Option<String>) -> Result<()> {
     println!("Unpublishing...");
 
     // Make HTTP request to registry
-    match &registry {
-        RegistryClient::Http(http_client) => {
-            http_client.unpublish(&package_name, version_to_unpublish.as_deref())?;
-        }
-        RegistryClient::File(_) => {
-            anyhow::bail!("Unpublish is only supported for HTTP registries");
-        }
-    }
+    http_client.unpublish(&package_name, version_to_unpublish.as_deref(), dry_run)?;
 
     if let Some(v) = version_to_unpublish {
         println!("✓ Successfully unpublished {}@{}", package_name, v);
@@ -1,65 +1,172 @@
 use anyhow::Result;
-use indicatif::{ProgressBar, ProgressStyle};
+use semver::{Version, VersionReq};
+use std::collections::HashMap;
 use std::env;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use unrealpm::changelog::{between, default_git_cache_dir, git_log_between, ChangelogSection};
+use unrealpm::metrics::InstallHistory;
+use unrealpm::pipeline::run_concurrent;
 use unrealpm::{
-    find_matching_version, install_package, resolve_dependencies, verify_checksum, Config,
-    Lockfile, Manifest, ProgressCallback, RegistryClient, ResolverConfig,
+    find_matching_version, install_package, install_package_cas, outln, resolve_dependencies,
+    select_artifacts, Config, Event, EventSink, InstallLayout, InstallMode, Lockfile, Manifest,
+    Phase, RegistryClient, ResolvedPackage, ResolverConfig, Timings,
 };
 
-/// Create an indicatif-based progress callback for CLI display
-fn create_spinner_callback() -> ProgressCallback {
-    let spinner = Arc::new(std::sync::Mutex::new(ProgressBar::new_spinner()));
-    {
-        let s = spinner.lock().unwrap();
-        s.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg}")
-                .unwrap()
-                .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
-        );
-        s.enable_steady_tick(std::time::Duration::from_millis(80));
-    }
+use crate::commands::install::{
+    apply_manifest_patches, artifact_selection, batch_result, check_integrity_pins, check_license,
+    inspect_path_dependencies, prepare_package, print_eta, remove_stale_package, report_failures,
+    warn_engine_plugin_conflicts, warn_module_collisions, SignatureSummary, StatusTable,
+    UnmanagedPolicy,
+};
+
+/// Create an indicatif-based progress sink for CLI display
+fn create_spinner_sink() -> EventSink {
+    let spinner = Arc::new(std::sync::Mutex::new(unrealpm::output::spinner("")));
 
     let spinner_clone = spinner.clone();
-    Arc::new(move |msg: &str, current: u64, total: u64| {
+    Arc::new(move |event: &Event| {
+        // The command prints the outcome itself
+        if matches!(event, Event::Completed { .. } | Event::Failed { .. }) {
+            return;
+        }
         let s = spinner_clone.lock().unwrap();
+        let (current, total) = event.progress();
         if current >= total && total > 0 {
-            s.finish_with_message(format!("✓ {}", msg));
+            unrealpm::output::finish_spinner(&s, format!("✓ {}", event.message()));
         } else {
-            s.set_message(msg.to_string());
+            s.set_message(event.message());
         }
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     package: Option<String>,
+    prefer_binary: bool,
+    source_only: bool,
+    binary_only: bool,
     dry_run: bool,
+    latest: bool,
+    workspace: bool,
+    offline: bool,
     verbose_resolve: bool,
     max_depth: Option<usize>,
     resolve_timeout: Option<u64>,
+    jobs: Option<usize>,
+    show_timings: bool,
 ) -> Result<()> {
     let current_dir = env::current_dir()?;
+    if offline {
+        crate::commands::vendor::enable_offline(&current_dir, true)?;
+    }
 
     // Build resolver config from CLI args and loaded config
     let loaded_config = Config::load()?;
+    let jobs = jobs.unwrap_or(loaded_config.network.max_parallel_downloads);
     let resolver_config = ResolverConfig {
         max_depth: max_depth.unwrap_or(loaded_config.resolver.max_depth),
         verbose_conflicts: verbose_resolve || loaded_config.resolver.verbose_conflicts,
         resolution_timeout_seconds: resolve_timeout
             .unwrap_or(loaded_config.resolver.resolution_timeout_seconds),
+        features: HashMap::new(),
+        remote: loaded_config.resolver.remote,
+    };
+
+    // Flags override the project/user config
+    let install_mode = InstallMode::from_flags(prefer_binary, source_only, binary_only)
+        .unwrap_or_else(|| loaded_config.install_mode(&current_dir));
+    let layout = if workspace {
+        InstallLayout::Copy
+    } else {
+        crate::commands::install::install_layout(&current_dir, &loaded_config, None)
+    };
+    unrealpm::installer::set_store_layout(layout == InstallLayout::Store);
+    unrealpm::saved_plugins::set_keep(loaded_config.saved_versions(&current_dir));
+
+    let mut timings = Timings::new();
+
+    let result = match package {
+        _ if workspace => crate::commands::workspace::install(
+            "update",
+            false,
+            None,
+            install_mode,
+            dry_run,
+            UnmanagedPolicy::Refuse,
+            &resolver_config,
+            jobs,
+            &mut timings,
+        ),
+        _ if latest => update_to_latest(
+            package.as_deref(),
+            &current_dir,
+            &loaded_config,
+            install_mode,
+            dry_run,
+            &resolver_config,
+            jobs,
+            &mut timings,
+        ),
+        Some(pkg) => update_single_package(&pkg, &current_dir, install_mode, dry_run, &mut timings),
+        None => update_all_packages(
+            &current_dir,
+            install_mode,
+            dry_run,
+            &resolver_config,
+            jobs,
+            &mut timings,
+            &HashMap::new(),
+        ),
     };
 
-    match package {
-        Some(pkg) => update_single_package(&pkg, &current_dir, dry_run),
-        None => update_all_packages(&current_dir, dry_run, &resolver_config),
+    // Workspace updates refresh each member themselves
+    if result.is_ok() && !dry_run && !workspace {
+        crate::commands::install::sync_store_layout(&current_dir, layout);
+        crate::commands::install::write_status_file(&current_dir, "update");
+        crate::commands::vcs_ignore::sync_configured(&current_dir);
+    }
+    if !dry_run {
+        crate::commands::install::save_install_history(&timings);
+    }
+
+    if show_timings {
+        println!("{}", timings.report());
+        println!();
+    }
+
+    result
+}
+
+/// Install an updated package: into the store when the project uses the
+/// store layout, otherwise as a fresh copy in Plugins/
+fn extract_update(
+    tarball_path: &Path,
+    project_dir: &Path,
+    name: &str,
+    checksum: &str,
+    progress: Option<EventSink>,
+) -> unrealpm::Result<PathBuf> {
+    let project_dir = project_dir.to_path_buf();
+    if unrealpm::installer::store_layout_enabled() {
+        install_package_cas(
+            &tarball_path.to_path_buf(),
+            &project_dir,
+            name,
+            checksum,
+            progress,
+        )
+    } else {
+        install_package(&tarball_path.to_path_buf(), &project_dir, name, progress)
     }
 }
 
 fn update_single_package(
     package_name: &str,
     project_dir: &std::path::Path,
+    install_mode: InstallMode,
     dry_run: bool,
+    timings: &mut Timings,
 ) -> Result<()> {
     if dry_run {
         println!("[DRY RUN] Would update package: {}", package_name);
@@ -87,20 +194,31 @@ fn update_single_package(
 
     println!("  Current constraint: {}", version_constraint);
 
-    // Get engine version
-    let engine_version = manifest.engine_version.as_deref();
-
     // Get registry client (uses HTTP if configured)
     let config = Config::load()?;
-    let registry = RegistryClient::from_config(&config)?;
+
+    // Get engine version (custom associations map through engine aliases)
+    let mapped_engine = manifest
+        .engine_version
+        .as_deref()
+        .map(|engine| config.logical_engine_version(engine));
+    let engine_version = mapped_engine.as_deref();
+    let registry = RegistryClient::from_config(&config)?
+        .with_locked_registries(&Lockfile::load()?.unwrap_or_default(), &config)?;
 
     // Get package metadata
     println!("  Fetching latest version...");
-    let metadata = registry.get_package(package_name)?;
+    let resolved_version = timings.time(Phase::Resolve, || -> Result<_> {
+        let metadata = registry.get_package(package_name)?;
 
-    // Find latest matching version
-    let resolved_version =
-        find_matching_version(&metadata, version_constraint, engine_version, false)?;
+        // Find latest matching version
+        Ok(find_matching_version(
+            &metadata,
+            version_constraint,
+            engine_version,
+            false,
+        )?)
+    })?;
     println!("  ✓ Latest matching version: {}", resolved_version.version);
 
     // Check if already at latest version
@@ -134,13 +252,27 @@ fn update_single_package(
         None
     };
 
+    // Determine which tarball to use (binary or source)
+    let selection = artifact_selection(
+        install_mode,
+        engine_version,
+        &config.build_configuration(project_dir),
+    );
+    let (tarball_path, checksum, install_type, binary) =
+        crate::commands::install::select_installation_source(
+            &resolved_version,
+            &registry,
+            package_name,
+            &selection,
+        )?;
+    if let Some(install_type) = &install_type {
+        println!("  Using: {}", install_type);
+    }
+
     if dry_run {
         // Dry run: show what would happen
-        println!(
-            "  [DRY RUN] Would verify checksum: {}",
-            resolved_version.checksum
-        );
-        if let Some(cur_ver) = current_version {
+        println!("  [DRY RUN] Would verify checksum: {}", checksum);
+        if let Some(cur_ver) = &current_version {
             println!(
                 "  [DRY RUN] Would update from {} to {}",
                 cur_ver, resolved_version.version
@@ -153,6 +285,16 @@ fn update_single_package(
         );
         println!("  [DRY RUN] Would update lockfile (unrealpm.lock)");
         println!();
+        if let Some(from) = current_version {
+            print_changelogs(
+                &registry,
+                &[(
+                    package_name.to_string(),
+                    from,
+                    resolved_version.version.clone(),
+                )],
+            );
+        }
         println!(
             "[DRY RUN] Would successfully update {} to {}",
             package_name, resolved_version.version
@@ -161,22 +303,38 @@ fn update_single_package(
         return Ok(());
     }
 
-    // Get tarball path
-    let tarball_path = registry.get_tarball_path(package_name, &resolved_version.version);
-
     // Verify checksum with progress spinner
-    let progress = Some(create_spinner_callback());
-    verify_checksum(&tarball_path, &resolved_version.checksum, progress)?;
-
-    // Install package with progress spinner (this will overwrite the existing installation)
-    let progress = Some(create_spinner_callback());
-    let installed_path = install_package(
-        &tarball_path,
-        &project_dir.to_path_buf(),
+    let progress = Some(create_spinner_sink());
+    timings.time_package(package_name, Phase::Verify, || {
+        registry.verify_tarball(
+            package_name,
+            &resolved_version.version,
+            &tarball_path,
+            &checksum,
+            progress,
+        )
+    })?;
+    let license = check_license(
+        project_dir,
         package_name,
-        progress,
+        &resolved_version.version,
+        &tarball_path,
     )?;
+
+    // Install package with progress spinner; the installer backs up the old
+    // version and restores it if this one fails to install
+    let progress = Some(create_spinner_sink());
+    let installed_path = timings.time_package(package_name, Phase::Extract, || {
+        extract_update(
+            &tarball_path,
+            project_dir,
+            package_name,
+            &checksum,
+            progress,
+        )
+    })?;
     println!("  ✓ Updated at {}", installed_path.display());
+    let patched_hash = apply_manifest_patches(project_dir, package_name, &installed_path)?;
 
     // Update lockfile
     println!("  Updating lockfile...");
@@ -191,6 +349,12 @@ fn update_single_package(
                 .collect()
         }),
     );
+    lockfile.set_patched_hash(package_name, patched_hash);
+    lockfile.set_binary(package_name, binary);
+    lockfile.set_registry(package_name, registry.lock_source(package_name));
+    lockfile.set_license(package_name, license);
+    lockfile.set_features(&manifest.dependency_features);
+    lockfile.set_engine(engine_version);
     lockfile.save()?;
     println!("  ✓ Lockfile updated");
 
@@ -206,8 +370,12 @@ fn update_single_package(
 
 fn update_all_packages(
     project_dir: &std::path::Path,
+    install_mode: InstallMode,
     dry_run: bool,
     resolver_config: &ResolverConfig,
+    jobs: usize,
+    timings: &mut Timings,
+    overrides: &HashMap<String, String>,
 ) -> Result<()> {
     if dry_run {
         println!("[DRY RUN] Would update all packages...");
@@ -227,57 +395,188 @@ fn update_all_packages(
     // Load manifest
     let manifest = Manifest::load(project_dir)?;
 
-    if manifest.dependencies.is_empty() {
+    if manifest.dependencies.is_empty()
+        && manifest.path_dependencies.is_empty()
+        && manifest.dev_dependencies.is_empty()
+    {
         println!("No dependencies to update.");
         println!();
         return Ok(());
     }
 
-    println!("Found {} dependencies", manifest.dependencies.len());
+    println!(
+        "Found {} dependencies",
+        manifest.dependencies.len()
+            + manifest.path_dependencies.len()
+            + manifest.dev_dependencies.len()
+    );
     println!();
 
-    // Get engine version
-    let engine_version = manifest.engine_version.as_deref();
-
     // Get registry client (uses HTTP if configured)
     let config = Config::load()?;
-    let registry = RegistryClient::from_config(&config)?;
+
+    // Get engine version (custom associations map through engine aliases)
+    let mapped_engine = manifest
+        .engine_version
+        .as_deref()
+        .map(|engine| config.logical_engine_version(engine));
+    let engine_version = mapped_engine.as_deref();
+    let mut registry = RegistryClient::from_config(&config)?
+        .with_locked_registries(&Lockfile::load()?.unwrap_or_default(), &config)?;
+
+    // Skip dependencies whose platform/engine conditions don't hold on this host
+    let platform = unrealpm::detect_platform();
+    let inactive = manifest.inactive_dependencies(&platform, engine_version);
+    for (name, conditions) in &inactive {
+        println!("Skipping {} ({})", name, conditions.describe());
+    }
+    let mut dependencies = manifest.active_dependencies(&platform, engine_version);
+    // `update --latest` resolves against the rewritten constraints
+    for (name, constraint) in overrides {
+        if let Some(current) = dependencies.get_mut(name) {
+            current.clone_from(constraint);
+        }
+    }
+    // Path dependencies stay linked; their own dependencies are updated
+    let linked = inspect_path_dependencies(project_dir, &manifest)?;
+    for plugin in &linked {
+        for (name, constraint) in &plugin.dependencies {
+            dependencies
+                .entry(name.clone())
+                .or_insert_with(|| constraint.clone());
+        }
+    }
+    let runtime_roots: Vec<String> = dependencies.keys().cloned().collect();
+    for (name, constraint) in &manifest.dev_dependencies {
+        dependencies
+            .entry(name.clone())
+            .or_insert_with(|| overrides.get(name).unwrap_or(constraint).clone());
+    }
+
+    let resolver_config = &ResolverConfig {
+        features: manifest.dependency_features.clone(),
+        ..resolver_config.clone()
+    };
 
     // Resolve all dependencies (this will get latest matching versions)
     println!("Resolving latest versions...");
-    let resolved = resolve_dependencies(
-        &manifest.dependencies,
-        &registry,
-        engine_version,
-        false,
-        Some(resolver_config),
-    )?;
+    let mut resolved = timings.time(Phase::Resolve, || {
+        resolve_dependencies(
+            &dependencies,
+            &registry,
+            engine_version,
+            false,
+            Some(resolver_config),
+        )
+    })?;
+    resolved.retain(|name, _| !manifest.path_dependencies.contains_key(name));
+    let dev_only = unrealpm::dev_only_packages(&resolved, runtime_roots.iter().map(String::as_str));
     println!("  ✓ Resolved {} packages", resolved.len());
     println!();
+    warn_module_collisions(&registry, &resolved)?;
+    warn_engine_plugin_conflicts(
+        &config,
+        &registry,
+        &resolved,
+        manifest.engine_version.as_deref(),
+        &manifest.engine_overrides,
+    )?;
 
     // Load existing lockfile to compare
     let old_lockfile = Lockfile::load()?.unwrap_or_default();
+
+    // Keep the locked binary/source choice for packages whose version is unchanged
+    let selection = artifact_selection(
+        install_mode,
+        engine_version,
+        &config.build_configuration(project_dir),
+    );
+    select_artifacts(&mut resolved, &registry, &selection, Some(&old_lockfile))?;
+    check_integrity_pins(&manifest, &mut resolved, &registry.location())?;
     let mut lockfile = Lockfile::new();
     let mut updated_count = 0;
     let mut pending_updates = Vec::new();
 
-    // Install each resolved package
-    for (name, resolved_pkg) in &resolved {
-        // Check if version changed
-        let is_update = if let Some(old_pkg) = old_lockfile.get_package(name) {
-            if old_pkg.version == resolved_pkg.version {
+    let mut packages: Vec<(&String, &ResolvedPackage)> = resolved.iter().collect();
+    packages.sort_by(|a, b| a.0.cmp(b.0));
+    let mut to_install = Vec::new();
+
+    for &(name, resolved_pkg) in &packages {
+        // Packages locked at the resolved artifact and still installed are left alone
+        let is_update = if let Some(target) = unrealpm::link::link_target(
+            &unrealpm::plugins_dir(project_dir).join(unrealpm::scope::unscoped(name)),
+        ) {
+            println!(
+                "  • {} is linked ({}); run 'unrealpm unlink {}' to update it",
+                name,
+                target.display(),
+                name
+            );
+            false
+        } else if old_lockfile.is_locked_at(
+            name,
+            &resolved_pkg.version,
+            resolved_pkg.binary.as_ref(),
+        ) && unrealpm::installed_plugin_dir(
+            project_dir,
+            name,
+            resolved_pkg.artifact_checksum(),
+        )
+        .is_some()
+        {
+            if dry_run {
+                println!(
+                    "  {} already at latest version ({})",
+                    name, resolved_pkg.version
+                );
+            } else {
+                println!(
+                    "  ✓ {} already at latest version ({})",
+                    name, resolved_pkg.version
+                );
+            }
+            false
+        } else if let Some(old_pkg) = old_lockfile.get_package(name) {
+            if old_pkg.version == resolved_pkg.version && old_pkg.binary != resolved_pkg.binary {
+                let artifact = match &resolved_pkg.binary {
+                    Some(binary) => format!("pre-built binary ({})", binary.target()),
+                    None => "source code".to_string(),
+                };
+                if dry_run {
+                    println!(
+                        "  [DRY RUN] Would switch {}@{} to {}",
+                        name, resolved_pkg.version, artifact
+                    );
+                    pending_updates.push((
+                        name.clone(),
+                        old_pkg.version.clone(),
+                        resolved_pkg.version.clone(),
+                    ));
+                } else {
+                    println!(
+                        "  Switching {}@{} to {}",
+                        name, resolved_pkg.version, artifact
+                    );
+                }
+                true
+            } else if old_pkg.version == resolved_pkg.version {
                 if dry_run {
                     println!(
-                        "  {} already at latest version ({})",
+                        "  [DRY RUN] Would reinstall {}@{} (missing from Plugins/)",
                         name, resolved_pkg.version
                     );
+                    pending_updates.push((
+                        name.clone(),
+                        old_pkg.version.clone(),
+                        resolved_pkg.version.clone(),
+                    ));
                 } else {
                     println!(
-                        "  ✓ {} already at latest version ({})",
+                        "  Reinstalling {}@{} (missing from Plugins/)",
                         name, resolved_pkg.version
                     );
                 }
-                false
+                true
             } else {
                 if dry_run {
                     println!(
@@ -291,7 +590,7 @@ fn update_all_packages(
                     ));
                 } else {
                     println!(
-                        "  Updating {}@{} -> {}...",
+                        "  Updating {}@{} -> {}",
                         name, old_pkg.version, resolved_pkg.version
                     );
                 }
@@ -310,55 +609,148 @@ fn update_all_packages(
                 ));
             } else {
                 println!(
-                    "  Installing new dependency {}@{}...",
+                    "  Installing new dependency {}@{}",
                     name, resolved_pkg.version
                 );
             }
             true
         };
 
-        if is_update && !dry_run {
-            // Get tarball path
-            let tarball_path = registry.get_tarball_path(name, &resolved_pkg.version);
-
-            // Verify checksum (no spinner for batch updates)
-            match verify_checksum(&tarball_path, &resolved_pkg.checksum, None) {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("    ✗ Checksum verification failed: {}", e);
-                    eprintln!("    Skipping...");
-                    continue;
-                }
+        if is_update {
+            to_install.push((name, resolved_pkg));
+        }
+    }
+
+    // Unchanged packages keep their patched tree from the previous lockfile
+    let mut patched_hashes: HashMap<String, Option<String>> = old_lockfile
+        .packages
+        .iter()
+        .map(|(name, pkg)| (name.clone(), pkg.patched_hash.clone()))
+        .collect();
+    let mut licenses: HashMap<String, Option<String>> = HashMap::new();
+    let mut failed_names = Vec::new();
+    let mut outcomes = Vec::new();
+
+    if dry_run {
+        updated_count = to_install.len();
+    } else if !to_install.is_empty() {
+        println!();
+
+        // Start the slowest packages first so their downloads overlap the rest
+        let history = InstallHistory::open_default().unwrap_or_default();
+        history.sort_slowest_first(&mut to_install, |(name, _)| name.as_str());
+        print_eta(&history, &to_install, jobs);
+
+        // Download and verify in parallel, extract one package at a time
+        registry.set_quiet(true);
+        let table = StatusTable::new(
+            to_install
+                .iter()
+                .map(|(name, pkg)| format!("{}@{}", name, pkg.version))
+                .collect(),
+        )
+        .with_estimates(
+            to_install
+                .iter()
+                .map(|(name, _)| history.estimate_total(name))
+                .collect(),
+        );
+        let shared_timings = Mutex::new(&mut *timings);
+        let signatures = Mutex::new(SignatureSummary::default());
+
+        outcomes = run_concurrent(
+            &to_install,
+            jobs,
+            |(name, resolved_pkg), report| {
+                prepare_package(
+                    &registry,
+                    name,
+                    resolved_pkg,
+                    &shared_timings,
+                    &signatures,
+                    report,
+                )
+            },
+            |(name, resolved_pkg), tarball_path| {
+                let license =
+                    check_license(project_dir, name, &resolved_pkg.version, &tarball_path)?;
+                licenses.insert((*name).clone(), license);
+                let extract = || {
+                    extract_update(
+                        &tarball_path,
+                        project_dir,
+                        name,
+                        resolved_pkg.artifact_checksum(),
+                        None,
+                    )
+                };
+                let installed_path =
+                    shared_timings
+                        .lock()
+                        .unwrap()
+                        .time_package(name, Phase::Extract, extract)?;
+
+                let patched_hash = apply_manifest_patches(project_dir, name, &installed_path)
+                    .map_err(|e| anyhow::anyhow!("Failed to patch {}: {}", name, e))?;
+                patched_hashes.insert((*name).clone(), patched_hash);
+                Ok(())
+            },
+            |index, state| table.set(index, state),
+        );
+        drop(table);
+        println!();
+
+        report_failures(&to_install, &outcomes);
+        signatures.into_inner().unwrap().print();
+        for ((name, _), outcome) in to_install.iter().zip(&outcomes) {
+            if outcome.is_ok() {
+                updated_count += 1;
+            } else {
+                failed_names.push((*name).clone());
             }
+        }
+    }
 
-            // Install package (no spinner for batch updates)
-            match install_package(&tarball_path, &project_dir.to_path_buf(), name, None) {
-                Ok(installed_path) => {
-                    println!("    ✓ Installed to {}", installed_path.display());
-                    updated_count += 1;
-                }
-                Err(e) => {
-                    eprintln!("    ✗ Failed to install: {}", e);
-                    eprintln!("    Continuing...");
-                }
+    for (name, resolved_pkg) in packages {
+        // Unchanged packages keep their entry as is. A failed update leaves
+        // the previously locked version in place.
+        let unchanged = !to_install.iter().any(|(changed, _)| *changed == name);
+        if unchanged || failed_names.contains(name) {
+            if let Some(old_pkg) = old_lockfile.get_package(name) {
+                lockfile.packages.insert(name.clone(), old_pkg.clone());
             }
-        } else if is_update && dry_run {
-            updated_count += 1;
+            continue;
         }
 
-        // Update lockfile for all packages (whether updated or not)
         lockfile.update_package(
             name.clone(),
             resolved_pkg.version.clone(),
             resolved_pkg.checksum.clone(),
             resolved_pkg.dependencies.clone(),
         );
+        lockfile.set_patched_hash(name, patched_hashes.remove(name).flatten());
+        lockfile.set_binary(name, resolved_pkg.binary.clone());
+        lockfile.set_registry(name, registry.lock_source(name));
+        lockfile.set_license(name, licenses.remove(name).flatten());
     }
 
+    // Locked packages nothing depends on any more are removed from Plugins/
+    let stale = old_lockfile.unreachable_packages(
+        resolved
+            .keys()
+            .map(String::as_str)
+            .chain(inactive.iter().map(|(name, _)| *name))
+            .chain(linked.iter().map(|plugin| plugin.name.as_str())),
+    );
+
     if dry_run {
+        for name in &stale {
+            println!("  [DRY RUN] Would remove {} (no longer required)", name);
+        }
         println!();
         println!("[DRY RUN] Would update lockfile (unrealpm.lock)");
         println!();
+        print_changelogs(&registry, &pending_updates);
         if updated_count > 0 {
             println!("[DRY RUN] Would update {} packages", updated_count);
         } else {
@@ -368,6 +760,22 @@ fn update_all_packages(
         return Ok(());
     }
 
+    // Keep packages the registry doesn't manage (local installs, Fab plugins)
+    for (name, pkg) in &old_lockfile.packages {
+        if pkg.source.is_some() && !lockfile.has_package(name) {
+            lockfile.packages.insert(name.clone(), pkg.clone());
+        }
+    }
+    if !stale.is_empty() {
+        println!();
+    }
+    for name in &stale {
+        remove_stale_package(project_dir, &mut lockfile, name);
+    }
+    lockfile.set_dev_packages(&dev_only);
+    lockfile.set_features(&manifest.dependency_features);
+    lockfile.set_engine(engine_version);
+
     // Save lockfile
     lockfile.save()?;
     println!();
@@ -381,5 +789,259 @@ fn update_all_packages(
     }
     println!();
 
+    batch_result(outcomes)
+}
+
+/// A direct dependency's move to its newest engine-compatible version
+struct LatestBump {
+    name: String,
+    old_constraint: String,
+    new_constraint: String,
+    /// Version in use today (locked, or the best match for the old constraint)
+    from: Option<String>,
+    to: String,
+}
+
+impl LatestBump {
+    /// Whether `to` is newer than the version in use today
+    ///
+    /// The newest engine-compatible version can be older than the locked one
+    /// (e.g. a release dropped the engine); the constraint is kept then.
+    fn is_upgrade(&self) -> bool {
+        match self.from.as_deref().map(Version::parse) {
+            Some(Ok(from)) => Version::parse(&self.to).is_ok_and(|to| to > from),
+            _ => true,
+        }
+    }
+
+    /// Whether the move leaves the semver-compatible range of the current version
+    fn is_breaking(&self) -> bool {
+        let Some(from) = &self.from else {
+            return false;
+        };
+        let compatible = VersionReq::parse(&format!("^{}", from))
+            .ok()
+            .zip(Version::parse(&self.to).ok())
+            .is_some_and(|(req, to)| req.matches(&to));
+        !compatible
+    }
+}
+
+/// `update --latest`: ignore constraints, update to the newest versions that
+/// support the project's engine, and rewrite the constraints to match
+#[allow(clippy::too_many_arguments)]
+fn update_to_latest(
+    package: Option<&str>,
+    project_dir: &std::path::Path,
+    config: &Config,
+    install_mode: InstallMode,
+    dry_run: bool,
+    resolver_config: &ResolverConfig,
+    jobs: usize,
+    timings: &mut Timings,
+) -> Result<()> {
+    if !Manifest::exists(project_dir) {
+        println!("✗ No unrealpm.json found in current directory");
+        println!();
+        println!("Run 'unrealpm init' first to initialize the project.");
+        return Ok(());
+    }
+
+    let mut manifest = Manifest::load(project_dir)?;
+    let mapped_engine = manifest
+        .engine_version
+        .as_deref()
+        .map(|engine| config.logical_engine_version(engine));
+    let engine_version = mapped_engine.as_deref();
+    let lockfile = Lockfile::load()?.unwrap_or_default();
+    let registry =
+        RegistryClient::from_config(config)?.with_locked_registries(&lockfile, config)?;
+    let style = config.save_style(project_dir);
+
+    let mut dependencies: Vec<(String, String)> = manifest
+        .active_dependencies(&unrealpm::detect_platform(), engine_version)
+        .into_iter()
+        .filter(|(name, _)| package.is_none_or(|p| p == name))
+        .collect();
+    if let Some(package) = package {
+        if dependencies.is_empty() {
+            anyhow::bail!(
+                "{} is not a direct dependency of this project\n\n\
+                --latest rewrites constraints in unrealpm.json, so it only applies to direct dependencies.",
+                package
+            );
+        }
+    }
+    dependencies.sort();
+
+    println!(
+        "Finding the newest {} versions...",
+        match engine_version {
+            Some(engine) => format!("UE {} compatible", engine),
+            None => "published".to_string(),
+        }
+    );
+    let mut bumps = Vec::new();
+    for (name, constraint) in dependencies {
+        let metadata = registry.get_package(&name)?;
+        let newest = find_matching_version(&metadata, "*", engine_version, false)
+            .map_err(|e| anyhow::anyhow!("No version of {} supports this project: {}", name, e))?;
+        let from = lockfile
+            .get_package(&name)
+            .map(|p| p.version.clone())
+            .or_else(|| {
+                find_matching_version(&metadata, &constraint, engine_version, false)
+                    .ok()
+                    .map(|v| v.version)
+            });
+        bumps.push(LatestBump {
+            new_constraint: style.constraint(&newest.version),
+            old_constraint: constraint,
+            name,
+            from,
+            to: newest.version,
+        });
+    }
+    println!();
+
+    let changed: Vec<&LatestBump> = bumps
+        .iter()
+        .filter(|b| b.old_constraint != b.new_constraint && b.is_upgrade())
+        .collect();
+    if changed.is_empty() {
+        println!("✓ Constraints already allow the newest versions");
+        println!();
+    } else {
+        let width = changed.iter().map(|b| b.name.len()).max().unwrap_or(0);
+        println!("Constraints:");
+        for bump in &changed {
+            println!(
+                "  {:<width$}  {} → {}{}",
+                bump.name,
+                bump.old_constraint,
+                bump.new_constraint,
+                if bump.is_breaking() {
+                    "  ⚠ breaking"
+                } else {
+                    ""
+                },
+                width = width
+            );
+        }
+        println!();
+    }
+
+    let overrides: HashMap<String, String> = changed
+        .iter()
+        .map(|b| (b.name.clone(), b.new_constraint.clone()))
+        .collect();
+    update_all_packages(
+        project_dir,
+        install_mode,
+        dry_run,
+        resolver_config,
+        jobs,
+        timings,
+        &overrides,
+    )?;
+
+    if !overrides.is_empty() {
+        if dry_run {
+            println!(
+                "[DRY RUN] Would rewrite {} constraint(s) in {}",
+                overrides.len(),
+                Manifest::file_name(project_dir)
+            );
+        } else {
+            for (name, constraint) in &overrides {
+                manifest
+                    .dependencies
+                    .insert(name.clone(), constraint.clone());
+            }
+            manifest.save(project_dir)?;
+            println!(
+                "✓ Rewrote {} constraint(s) in {}",
+                overrides.len(),
+                Manifest::file_name(project_dir)
+            );
+        }
+        println!();
+    }
+
+    let breaking: Vec<&LatestBump> = changed.into_iter().filter(|b| b.is_breaking()).collect();
+    if !breaking.is_empty() {
+        println!("⚠ Breaking changes ({}):", breaking.len());
+        for bump in &breaking {
+            println!(
+                "  {} {} → {}",
+                bump.name,
+                bump.from.as_deref().unwrap_or("?"),
+                bump.to
+            );
+        }
+        println!();
+        println!("These leave the semver-compatible range and may change the plugin's API;");
+        println!("review their changelogs and rebuild before committing.");
+        println!();
+    }
+
     Ok(())
 }
+
+/// Show the changelog entries between the old and new version of each bump
+///
+/// `updates` holds (name, from, to); new installs and reinstalls at the same
+/// version have nothing to show and are skipped.
+fn print_changelogs(registry: &RegistryClient, updates: &[(String, String, String)]) {
+    let bumps: Vec<_> = updates
+        .iter()
+        .filter(|(_, from, to)| from != "none" && from != to)
+        .collect();
+    if bumps.is_empty() {
+        return;
+    }
+
+    outln!("Changes:");
+    outln!();
+    for (name, from, to) in bumps {
+        outln!("  {} {} → {}", name, from, to);
+        match release_notes(registry, name, from, to) {
+            Ok(sections) if !sections.is_empty() => {
+                for section in sections {
+                    outln!("    {}", section.title);
+                    for line in unrealpm::markdown::render(&section.body, 72).lines() {
+                        outln!("      {}", line);
+                    }
+                }
+            }
+            Ok(_) => outln!("    No changelog entries between these versions"),
+            Err(e) => outln!("    ⚠ Could not fetch the changelog: {}", e),
+        }
+        outln!();
+    }
+}
+
+/// Changelog sections between two versions, or the git history between
+/// their tags when the package has no changelog
+fn release_notes(
+    registry: &RegistryClient,
+    name: &str,
+    from: &str,
+    to: &str,
+) -> Result<Vec<ChangelogSection>> {
+    let source = registry.get_changelog(name, to)?;
+    if let Some(changelog) = &source.changelog {
+        let sections = between(changelog, from, to);
+        if !sections.is_empty() {
+            return Ok(sections);
+        }
+    }
+    match &source.git_repository {
+        Some(repository) => Ok(
+            git_log_between(repository, &default_git_cache_dir()?, from, to)?
+                .into_iter()
+                .collect(),
+        ),
+        None => Ok(Vec::new()),
+    }
+}
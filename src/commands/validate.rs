@@ -0,0 +1,53 @@
+//! Validate command - check the project manifest without installing anything
+//!
+//! Lists every dependency whose version constraint doesn't parse (loading
+//! the manifest stops at the first), then any other problem with it.
+
+use anyhow::Result;
+use std::env;
+use unrealpm::manifest::CONSTRAINT_EXAMPLES;
+use unrealpm::Manifest;
+
+pub fn run() -> Result<()> {
+    let current_dir = env::current_dir()?;
+    let Some(format) = Manifest::detect(&current_dir)? else {
+        anyhow::bail!("No unrealpm.json or unrealpm.toml found. Run 'unrealpm init' first.");
+    };
+    let file_name = format.file_name();
+    let content = std::fs::read_to_string(current_dir.join(file_name))?;
+
+    let errors = Manifest::constraint_errors(&content, format)
+        .map_err(|e| anyhow::anyhow!("{} is not valid: {}", file_name, e))?;
+    if !errors.is_empty() {
+        println!(
+            "✗ {} has {} invalid version constraint{}:",
+            file_name,
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" }
+        );
+        for error in &errors {
+            println!(
+                "  • {}: \"{}\" ({})",
+                error.dependency, error.constraint, error.reason
+            );
+        }
+        println!();
+        println!("{}", CONSTRAINT_EXAMPLES);
+        println!();
+        anyhow::bail!("{} is not valid", file_name);
+    }
+
+    let manifest = Manifest::parse(&content, format)
+        .map_err(|e| anyhow::anyhow!("{} is not valid: {}", file_name, e))?;
+    let count = manifest.dependencies.len()
+        + manifest.dev_dependencies.len()
+        + manifest.optional_dependencies.len()
+        + manifest.path_dependencies.len();
+    println!(
+        "✓ {} is valid ({} dependenc{})",
+        file_name,
+        count,
+        if count == 1 { "y" } else { "ies" }
+    );
+    Ok(())
+}
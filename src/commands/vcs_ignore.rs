@@ -0,0 +1,77 @@
+//! vcs-ignore command - keep installed plugins out of version control
+//!
+//! `vcs-ignore sync` writes one entry per installed plugin folder into a
+//! marked block of `.gitignore`, `.p4ignore`, or `.unrealpm-managed`. With
+//! `[vcs] ignore` configured, install, update, and uninstall refresh the
+//! block themselves.
+
+use anyhow::Result;
+use std::path::Path;
+use unrealpm::{ignore, outln, Config, IgnoreFile, Lockfile, Manifest};
+
+pub fn run(action: &crate::VcsIgnoreAction) -> Result<()> {
+    match action {
+        crate::VcsIgnoreAction::Sync { file } => run_sync(*file),
+    }
+}
+
+fn run_sync(file: Option<IgnoreFile>) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    if !Manifest::exists(&current_dir) {
+        anyhow::bail!(
+            "No unrealpm.json found in {}\n\n\
+            • Run this from your project directory",
+            current_dir.display()
+        );
+    }
+    let file = file
+        .or_else(|| Config::load().ok()?.vcs_ignore(&current_dir))
+        .unwrap_or_default();
+
+    let entries = managed_entries(&current_dir)?;
+    let changed = ignore::sync(&current_dir, file, &entries)?;
+
+    if changed {
+        outln!(
+            "✓ Updated {} ({} managed plugin{})",
+            file.file_name(),
+            entries.len(),
+            if entries.len() == 1 { "" } else { "s" }
+        );
+    } else {
+        outln!("✓ {} is up to date", file.file_name());
+    }
+    for entry in &entries {
+        outln!("  {}", entry);
+    }
+    if file == IgnoreFile::List {
+        outln!();
+        outln!("  • Include it from git with: git config core.excludesFile .unrealpm-managed");
+        outln!("  • Or from Perforce with:    p4 set P4IGNORE=.p4ignore;.unrealpm-managed");
+    }
+    Ok(())
+}
+
+/// Refresh the configured ignore file after an install, update, or uninstall
+///
+/// Best-effort: a failure is reported but never fails the command.
+pub fn sync_configured(project_dir: &Path) {
+    let Some(file) = Config::load()
+        .ok()
+        .and_then(|config| config.vcs_ignore(project_dir))
+    else {
+        return;
+    };
+    if let Err(e) = managed_entries(project_dir)
+        .and_then(|entries| Ok(ignore::sync(project_dir, file, &entries)?))
+    {
+        unrealpm::eoutln!("⚠ Failed to update {}: {}", file.file_name(), e);
+    }
+}
+
+fn managed_entries(project_dir: &Path) -> Result<Vec<String>> {
+    let lockfile = Lockfile::load_from(project_dir.join(unrealpm::LOCKFILE_NAME))?;
+    Ok(lockfile
+        .map(|lockfile| ignore::managed_entries(project_dir, &lockfile))
+        .unwrap_or_default())
+}
@@ -0,0 +1,125 @@
+//! Vendor command - copy locked packages into a mirror for offline installs
+//!
+//! `unrealpm vendor` fills `unrealpm-vendor/` (or `UNREALPM_VENDOR_DIR`)
+//! with the tarballs and metadata of everything in unrealpm.lock.
+//! `install --offline` and `update --offline` then resolve and install from
+//! it without network access.
+
+use anyhow::Result;
+use std::path::Path;
+use unrealpm::vendor::{self, VendorIndex};
+use unrealpm::{outln, Config, DryRun, Lockfile, Manifest, RegistryClient, LOCKFILE_NAME};
+
+pub fn run(dry_run: DryRun) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let Some(lockfile) = Lockfile::load_from(current_dir.join(LOCKFILE_NAME))? else {
+        anyhow::bail!(
+            "No unrealpm.lock found in {}\n\n\
+            • Run 'unrealpm install' first; vendor copies the locked versions",
+            current_dir.display()
+        );
+    };
+
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+    let dir = vendor::vendor_dir(&current_dir);
+
+    outln!(
+        "{}Vendoring packages from {} into {}",
+        dry_run.prefix(),
+        registry.location(),
+        dir.display()
+    );
+    outln!();
+
+    let vendored = vendor::vendor_packages(&registry, &lockfile, &dir, dry_run)?;
+    let mut copied = 0;
+    for package in &vendored {
+        let artifact = if package.artifact == package.version {
+            String::new()
+        } else {
+            format!(" ({})", package.artifact)
+        };
+        if package.up_to_date {
+            outln!(
+                "  ✓ {}@{}{} already vendored",
+                package.name,
+                package.version,
+                artifact
+            );
+        } else {
+            copied += 1;
+            outln!(
+                "  ✓ {} {}@{}{}",
+                dry_run.verb("Vendored", "Would vendor"),
+                package.name,
+                package.version,
+                artifact
+            );
+        }
+    }
+    outln!();
+    outln!(
+        "✓ {} package{} vendored ({} {})",
+        vendored.len(),
+        if vendored.len() == 1 { "" } else { "s" },
+        copied,
+        dry_run.verb("copied", "to copy")
+    );
+    if !dry_run.is_enabled() {
+        outln!("  • Install without network access: unrealpm install --offline");
+    }
+    Ok(())
+}
+
+/// Switch the registry to the project's vendored mirror for this run
+///
+/// Returns false when there is no mirror and `required` is false, so the
+/// caller can fall back to its own offline handling. Fails with the list of
+/// packages the mirror lacks, so an air-gapped install stops up front
+/// instead of timing out on the network.
+pub fn enable_offline(project_dir: &Path, required: bool) -> Result<bool> {
+    let dir = vendor::vendor_dir(project_dir);
+    let index = match VendorIndex::load(&dir) {
+        Ok(index) => index,
+        Err(_) if !required => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+
+    // Everything the project asks for directly or has locked must be vendored
+    let manifest = Manifest::load(project_dir).ok();
+    let lockfile = Lockfile::load_from(project_dir.join(LOCKFILE_NAME))?;
+    let names = manifest
+        .iter()
+        .flat_map(|m| m.dependencies.keys())
+        .chain(lockfile.iter().flat_map(|l| {
+            l.packages
+                .iter()
+                .filter(|(_, locked)| locked.source.is_none())
+                .map(|(name, _)| name)
+        }))
+        .map(String::as_str);
+    let missing = index.missing(names);
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "{} package{} missing from the vendored mirror at {}:\n  {}\n\n\
+            • Run 'unrealpm vendor' while online to add {}",
+            missing.len(),
+            if missing.len() == 1 { " is" } else { "s are" },
+            dir.display(),
+            missing.join("\n  "),
+            if missing.len() == 1 { "it" } else { "them" }
+        );
+    }
+
+    outln!(
+        "Offline: using vendored packages in {} ({} package{}, vendored {})",
+        dir.display(),
+        index.packages.len(),
+        if index.packages.len() == 1 { "" } else { "s" },
+        index.generated_at.get(..10).unwrap_or(&index.generated_at)
+    );
+    outln!();
+    vendor::set_offline_dir(Some(dir));
+    Ok(true)
+}
@@ -1,14 +1,273 @@
 use anyhow::Result;
-use unrealpm::{verify_signature, Config, RegistryClient};
+use unrealpm::integrity::{cross_check, report_incident, CrossCheck, Incident, MismatchLayer};
+use unrealpm::pipeline::{run_concurrent, TaskState};
+use unrealpm::registry_http::HttpRegistryClient;
+use unrealpm::trust::{KeyCheck, TrustStore};
+use unrealpm::{verify_checksum, verify_signature, Config, Error, LockedPackage, RegistryClient};
 
-pub fn run(package_spec: String) -> Result<()> {
-    // Parse package spec (e.g., "awesome-plugin" or "awesome-plugin@1.2.0")
-    let (package_name, version_spec) = if let Some(pos) = package_spec.find('@') {
-        let (name, version) = package_spec.split_at(pos);
-        (name.to_string(), Some(version[1..].to_string()))
-    } else {
-        (package_spec.to_string(), None)
+use crate::commands::install::StatusTable;
+
+pub fn run(package_spec: Option<String>, all: bool, jobs: usize, cross: bool) -> Result<()> {
+    if cross {
+        return run_cross_check(package_spec.filter(|_| !all));
+    }
+    match package_spec {
+        Some(package_spec) if !all => run_package(package_spec),
+        _ => run_all(jobs),
+    }
+}
+
+/// Verify the checksum of every locked package's tarball, several at a time
+fn run_all(jobs: usize) -> Result<()> {
+    let lockfile = unrealpm::Lockfile::load()?.ok_or_else(|| {
+        anyhow::anyhow!("No lockfile found (unrealpm.lock). Run `unrealpm install` first.")
+    })?;
+
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+
+    let mut names: Vec<&String> = lockfile.packages.keys().collect();
+    names.sort();
+
+    // Local and externally managed packages have no registry tarball to check
+    let mut packages: Vec<(&String, &LockedPackage)> = Vec::new();
+    let mut not_cached = Vec::new();
+    for name in names {
+        let locked = &lockfile.packages[name];
+        if locked.source.is_some() {
+            continue;
+        }
+        if registry
+            .get_tarball_path(name, locked.artifact_id())
+            .exists()
+        {
+            packages.push((name, locked));
+        } else {
+            not_cached.push(format!("{}@{}", name, locked.version));
+        }
+    }
+
+    if packages.is_empty() && not_cached.is_empty() {
+        println!("No registry packages in unrealpm.lock to verify.");
+        return Ok(());
+    }
+
+    println!(
+        "Verifying checksums of {} packages ({} at a time)...",
+        packages.len(),
+        jobs.max(1)
+    );
+    println!();
+
+    let table = StatusTable::new(
+        packages
+            .iter()
+            .map(|(name, locked)| format!("{}@{}", name, locked.version))
+            .collect(),
+    )
+    .finished_as("verified");
+
+    let outcomes = run_concurrent(
+        &packages,
+        jobs,
+        |(name, locked), report| {
+            report(TaskState::Verifying);
+            let tarball_path = registry.get_tarball_path(name, locked.artifact_id());
+            verify_checksum(&tarball_path, locked.artifact_checksum(), None)
+        },
+        |_, ()| Ok(()),
+        |index, state| table.set(index, state),
+    );
+    drop(table);
+    println!();
+
+    let layer = match registry {
+        RegistryClient::Http(_) => MismatchLayer::Cache,
+        RegistryClient::File(_) => MismatchLayer::Registry,
     };
+    let mut failed = 0;
+    let mut mismatch = None;
+    for ((name, locked), outcome) in packages.iter().zip(&outcomes) {
+        let Err(e) = outcome else { continue };
+        failed += 1;
+        println!("  ✗ {}@{}: {}", name, locked.version, e);
+        println!(
+            "      Tarball: {}",
+            registry
+                .get_tarball_path(name, locked.artifact_id())
+                .display()
+        );
+        if let Error::ChecksumMismatch { expected, actual } = e {
+            report_incident(
+                &Incident::new(name, &locked.version, layer, expected)
+                    .with_actual(actual)
+                    .with_source(&registry.location()),
+            );
+            mismatch.get_or_insert_with(|| Error::ChecksumMismatch {
+                expected: expected.clone(),
+                actual: actual.clone(),
+            });
+        }
+    }
+
+    if !not_cached.is_empty() {
+        println!(
+            "  ⚠ {} packages have no local tarball and were skipped:",
+            not_cached.len()
+        );
+        for label in &not_cached {
+            println!("      {}", label);
+        }
+        println!("    Run `unrealpm install` to download them.");
+    }
+    if failed > 0 || !not_cached.is_empty() {
+        println!();
+    }
+
+    if failed > 0 {
+        let message = format!(
+            "{} of {} packages failed checksum verification\n\n\
+            Delete the affected tarballs and run `unrealpm install` to download them again",
+            failed,
+            packages.len()
+        );
+        // Keep the mismatch as the source so the exit code reflects it
+        return Err(match mismatch {
+            Some(mismatch) => anyhow::Error::new(mismatch).context(message),
+            None => anyhow::anyhow!(message),
+        });
+    }
+    println!("✓ {} packages match unrealpm.lock", packages.len());
+    Ok(())
+}
+
+/// Compare the checksums in unrealpm.lock with what the registry and each
+/// configured mirror publish for the same versions
+///
+/// A registry serving different bytes under a version than its mirrors
+/// points at tampering or a mirror that drifted.
+fn run_cross_check(package: Option<String>) -> Result<()> {
+    let lockfile = unrealpm::Lockfile::load()?.ok_or_else(|| {
+        anyhow::anyhow!("No lockfile found (unrealpm.lock). Run `unrealpm install` first.")
+    })?;
+    let config = Config::load()?;
+    let mirrors = config.mirrors_for(&config.registry.url);
+    if config.registry.registry_type != "http" || mirrors.is_empty() {
+        anyhow::bail!(
+            "--cross-check needs a second registry to compare with\n\n\
+            Add one to registry.mirrors in ~/.unrealpm/config.toml"
+        );
+    }
+
+    // One client per registry, without failover, so each answers for itself
+    let cache_dir = RegistryClient::default_registry_path()?;
+    let project_dir = std::env::current_dir()?;
+    let mut sources = vec![(
+        config.registry.url.clone(),
+        HttpRegistryClient::new(
+            config.registry.url.clone(),
+            cache_dir.clone(),
+            config.auth_token(&project_dir),
+        )?,
+    )];
+    for mirror in &mirrors {
+        let token = unrealpm::config::token_env_var(mirror).and_then(|var| std::env::var(var).ok());
+        sources.push((
+            mirror.clone(),
+            HttpRegistryClient::new(mirror.clone(), cache_dir.clone(), token)?,
+        ));
+    }
+
+    let name = package.map(|spec| unrealpm::scope::split_spec(&spec).0.to_string());
+    let mut packages: Vec<(&String, &LockedPackage)> = lockfile
+        .packages
+        .iter()
+        .filter(|(locked_name, locked)| {
+            locked.source.is_none() && name.as_ref().is_none_or(|name| name == *locked_name)
+        })
+        .collect();
+    packages.sort_by(|a, b| a.0.cmp(b.0));
+    if packages.is_empty() {
+        match name {
+            Some(name) => anyhow::bail!("Package '{}' is not locked in unrealpm.lock", name),
+            None => {
+                println!("No registry packages in unrealpm.lock to cross-check.");
+                return Ok(());
+            }
+        }
+    }
+
+    println!(
+        "Cross-checking {} package(s) against {} registries...",
+        packages.len(),
+        sources.len()
+    );
+    println!();
+
+    let mut mismatched = 0;
+    for (name, locked) in &packages {
+        let label = format!("{}@{}", name, locked.version);
+        let mut problems = Vec::new();
+        let mut mismatch = false;
+        for (url, client) in &sources {
+            match client.get_package(name) {
+                Ok(metadata) => match cross_check(&metadata, locked, url) {
+                    CrossCheck::Match => {}
+                    CrossCheck::Missing => problems.push(format!("⚠ not published on {}", url)),
+                    CrossCheck::Mismatch(incident) => {
+                        mismatch = true;
+                        problems.push(format!(
+                            "✗ {} publishes {}",
+                            url,
+                            incident.actual.as_deref().unwrap_or_default()
+                        ));
+                        report_incident(&incident);
+                    }
+                },
+                Err(e) => problems.push(format!(
+                    "⚠ {} could not be checked: {}",
+                    url,
+                    e.to_string().lines().next().unwrap_or_default()
+                )),
+            }
+        }
+
+        if problems.is_empty() {
+            println!("  ✓ {}", label);
+            continue;
+        }
+        if mismatch {
+            mismatched += 1;
+            println!(
+                "  ✗ {} (unrealpm.lock has {})",
+                label,
+                locked.artifact_checksum()
+            );
+        } else {
+            println!("  ⚠ {}", label);
+        }
+        for problem in problems {
+            println!("      {}", problem);
+        }
+    }
+    println!();
+
+    if mismatched > 0 {
+        anyhow::bail!(
+            "{} of {} packages have different checksums across registries\n\n\
+            Don't install them until the registry operators confirm which copy is genuine",
+            mismatched,
+            packages.len()
+        );
+    }
+    println!("✓ No checksum discrepancies between registries");
+    Ok(())
+}
+
+fn run_package(package_spec: String) -> Result<()> {
+    // Parse package spec (e.g., "awesome-plugin" or "awesome-plugin@1.2.0")
+    let (package_name, version_spec) = unrealpm::scope::split_spec(&package_spec);
+    let (package_name, version_spec) = (package_name.to_string(), version_spec.map(str::to_string));
 
     println!("Verifying package: {}", package_name);
     if let Some(ref ver) = version_spec {
@@ -133,6 +392,40 @@ pub fn run(package_spec: String) -> Result<()> {
     if is_valid {
         println!("  ✓ SIGNATURE VALID");
         println!();
+
+        // A valid signature by a different key than before is not authentic
+        let endorsement = package_version.key_endorsement.as_ref();
+        let store = TrustStore::open_default()?;
+        if let KeyCheck::Changed(pinned) =
+            store.check_endorsed(&package_name, public_key, endorsement)
+        {
+            println!("✗ The publisher key has CHANGED since it was pinned");
+            println!("  Pinned key: {}", pinned.public_key);
+            println!("  Trusted {}", pinned.origin());
+            println!();
+            println!("Confirm the new key with the publisher, then accept it with:");
+            println!("  unrealpm trust {} --key {}", package_name, public_key);
+            println!();
+            return Ok(());
+        }
+        match unrealpm::trust::pin_on_first_use(
+            &package_name,
+            &version_to_verify,
+            public_key,
+            endorsement,
+        )? {
+            KeyCheck::FirstUse => {
+                println!("  ✓ Pinned publisher key (first use)");
+                println!();
+            }
+            KeyCheck::Rotated(pinned) => {
+                println!("  ✓ Accepted rotated publisher key");
+                println!("    Endorsed by the pinned key {}", pinned.public_key);
+                println!();
+            }
+            KeyCheck::Trusted | KeyCheck::Changed(_) => {}
+        }
+
         println!(
             "✓ Package {}@{} is authentic and has not been tampered with",
             package_name, version_to_verify
@@ -153,7 +446,7 @@ pub fn run(package_spec: String) -> Result<()> {
         println!("  • Report this to the UnrealPM team");
         println!("  • Do not use this package in production");
         println!();
-        std::process::exit(1);
+        std::process::exit(unrealpm::exit_code::SIGNATURE_FAILURE);
     }
 
     Ok(())
@@ -0,0 +1,288 @@
+//! Versions command - list and manage the published versions of a package
+//!
+//! Subcommands:
+//! - (none): List every version with downloads, yank status, signature
+//!   status, and engine targeting
+//! - yank / unyank: Yank or un-yank many versions at once (e.g., everything
+//!   older than 1.0.0)
+
+use anyhow::Result;
+use semver::Version;
+use std::io::Write;
+use unrealpm::registry_http::VersionSummary;
+use unrealpm::{Config, DryRun, PackageVersion, RegistryClient};
+
+use crate::VersionsAction;
+
+pub fn run(package: Option<&str>, action: &Option<VersionsAction>) -> Result<()> {
+    match action {
+        None => match package {
+            Some(package) => run_list(package),
+            None => anyhow::bail!("Please specify a package: unrealpm versions <package>"),
+        },
+        Some(VersionsAction::Yank {
+            package,
+            older_than,
+            yes,
+            dry_run,
+        }) => run_batch(package, older_than, false, *yes, *dry_run),
+        Some(VersionsAction::Unyank {
+            package,
+            older_than,
+            yes,
+            dry_run,
+        }) => run_batch(package, older_than, true, *yes, *dry_run),
+    }
+}
+
+fn run_list(package: &str) -> Result<()> {
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+
+    let mut versions = match &registry {
+        RegistryClient::Http(http_client) => http_client.list_versions(package)?,
+        // File registries don't track downloads or yanks
+        RegistryClient::File(_) => registry
+            .get_package(package)?
+            .versions
+            .iter()
+            .map(summary_from_metadata)
+            .collect(),
+    };
+
+    if versions.is_empty() {
+        println!("{} has no published versions.", package);
+        return Ok(());
+    }
+    sort_newest_first(&mut versions);
+
+    println!("Versions of {}:", package);
+    println!();
+
+    let rows: Vec<[String; 6]> = versions
+        .iter()
+        .map(|v| {
+            [
+                v.version.clone(),
+                v.published_at
+                    .get(..10)
+                    .unwrap_or(&v.published_at)
+                    .to_string(),
+                if matches!(registry, RegistryClient::Http(_)) {
+                    v.downloads.to_string()
+                } else {
+                    "-".to_string()
+                },
+                engines(v),
+                if v.signed { "✓" } else { "✗" }.to_string(),
+                if v.yanked { "yanked" } else { "" }.to_string(),
+            ]
+        })
+        .collect();
+    let headers = [
+        "VERSION",
+        "PUBLISHED",
+        "DOWNLOADS",
+        "ENGINES",
+        "SIGNED",
+        "STATUS",
+    ];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let print_row = |cells: Vec<&str>| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("  {}", line.trim_end());
+    };
+    print_row(headers.to_vec());
+    for row in &rows {
+        print_row(row.iter().map(String::as_str).collect());
+    }
+
+    let total_downloads: i64 = versions.iter().map(|v| v.downloads as i64).sum();
+    let yanked = versions.iter().filter(|v| v.yanked).count();
+    println!();
+    print!("{} version row(s)", versions.len());
+    if yanked > 0 {
+        print!(", {} yanked", yanked);
+    }
+    if matches!(registry, RegistryClient::Http(_)) {
+        print!(", {} total downloads", total_downloads);
+    }
+    println!();
+
+    Ok(())
+}
+
+fn run_batch(
+    package: &str,
+    older_than: &str,
+    unyank: bool,
+    yes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let cutoff = Version::parse(older_than).map_err(|e| {
+        anyhow::anyhow!(
+            "Invalid --older-than version '{}': {}\n\nUse a full version like 1.0.0",
+            older_than,
+            e
+        )
+    })?;
+
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+    let http_client = match &registry {
+        RegistryClient::Http(http_client) => http_client,
+        RegistryClient::File(_) => {
+            anyhow::bail!("Yank is only supported for HTTP registries");
+        }
+    };
+
+    // Engine-specific versions share a version string; yank each version once
+    let mut selected: Vec<String> = Vec::new();
+    for summary in http_client.list_versions(package)? {
+        let matches =
+            Version::parse(&summary.version).is_ok_and(|v| v < cutoff) && summary.yanked != unyank;
+        if matches && !selected.contains(&summary.version) {
+            selected.push(summary.version);
+        }
+    }
+    selected.sort_by(|a, b| compare_versions(a, b));
+
+    let action = if unyank { "un-yank" } else { "yank" };
+    if selected.is_empty() {
+        println!(
+            "No {} versions of {} older than {}.",
+            if unyank { "yanked" } else { "unyanked" },
+            package,
+            cutoff
+        );
+        return Ok(());
+    }
+
+    println!(
+        "Will {} {} version(s) of {} older than {}:",
+        action,
+        selected.len(),
+        package,
+        cutoff
+    );
+    for version in &selected {
+        println!("  {}@{}", package, version);
+    }
+    println!();
+    if !unyank {
+        println!("Existing projects with these versions in their lockfile can still install them.");
+        println!();
+    }
+
+    if dry_run {
+        println!("[DRY RUN] No versions were changed");
+        return Ok(());
+    }
+
+    if !yes {
+        print!("Continue? (yes/no): ");
+        std::io::stdout().flush()?;
+        let mut confirmation = String::new();
+        std::io::stdin().read_line(&mut confirmation)?;
+        if confirmation.trim().to_lowercase() != "yes" {
+            println!("Cancelled.");
+            return Ok(());
+        }
+        println!();
+    }
+
+    let mut failed = 0;
+    for version in &selected {
+        match http_client.yank(package, version, unyank, DryRun::OFF) {
+            Ok(_) => println!("  ✓ {}@{}", package, version),
+            Err(e) => {
+                println!("  ✗ {}@{}: {}", package, version, e);
+                failed += 1;
+            }
+        }
+    }
+    println!();
+
+    if failed > 0 {
+        anyhow::bail!(
+            "Failed to {} {} of {} versions",
+            action,
+            failed,
+            selected.len()
+        );
+    }
+    println!(
+        "✓ {} {} version(s) of {}",
+        if unyank { "Un-yanked" } else { "Yanked" },
+        selected.len(),
+        package
+    );
+    Ok(())
+}
+
+/// A file-registry version in the same shape as the HTTP listing
+pub fn summary_from_metadata(version: &PackageVersion) -> VersionSummary {
+    VersionSummary {
+        version: version.version.clone(),
+        published_at: version.signed_at.clone().unwrap_or_default(),
+        downloads: 0,
+        yanked: false,
+        engine_versions: version.engine_versions.clone(),
+        engine_major: version.engine_major,
+        engine_minor: version.engine_minor,
+        is_multi_engine: version.is_multi_engine,
+        package_type: format!("{:?}", version.package_type).to_lowercase(),
+        signed: version.public_key.is_some(),
+        binaries: version.binaries.as_ref().map(|b| b.len()).unwrap_or(0),
+    }
+}
+
+/// Engine targeting for display
+pub fn engines(summary: &VersionSummary) -> String {
+    let targeting = match (
+        summary.is_multi_engine,
+        summary.engine_major,
+        summary.engine_minor,
+    ) {
+        (false, Some(major), Some(minor)) => format!("{}.{} only", major, minor),
+        _ => match &summary.engine_versions {
+            Some(list) if !list.is_empty() => list.join(", "),
+            _ => "any".to_string(),
+        },
+    };
+    if summary.binaries > 0 {
+        format!("{} (+{} binaries)", targeting, summary.binaries)
+    } else {
+        targeting
+    }
+}
+
+fn sort_newest_first(versions: &mut [VersionSummary]) {
+    versions.sort_by(|a, b| {
+        compare_versions(&b.version, &a.version).then(b.engine_minor.cmp(&a.engine_minor))
+    });
+}
+
+/// Semver order, with unparsable versions after valid ones
+pub fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    match (Version::parse(a), Version::parse(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Less,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
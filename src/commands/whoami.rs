@@ -1,6 +1,6 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
-use unrealpm::{config::AuthConfig, Config};
+use unrealpm::{config::AuthConfig, Config, Error};
 
 #[derive(Debug, Deserialize)]
 struct UserInfoResponse {
@@ -24,10 +24,8 @@ pub fn run() -> Result<()> {
 
     // Check we're logged in
     let auth_token = config
-        .auth
-        .token
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Not logged in. Run: unrealpm login"))?;
+        .auth_token(std::env::current_dir()?)
+        .ok_or_else(|| Error::Unauthorized("Not logged in. Run: unrealpm login".to_string()))?;
 
     // Send request
     let client = reqwest::blocking::Client::new();
@@ -35,7 +33,7 @@ pub fn run() -> Result<()> {
 
     let response = client
         .get(&url)
-        .header("Authorization", AuthConfig::format_auth_header(auth_token))
+        .header("Authorization", AuthConfig::format_auth_header(&auth_token))
         .send()
         .context("Failed to get user info")?;
 
@@ -0,0 +1,586 @@
+//! Workspace installs - resolve every member project together
+//!
+//! `install --workspace` and `update --workspace` read the member projects
+//! from `unrealpm.workspace.json`, resolve their dependencies as one graph,
+//! and lock the result in a single `unrealpm.lock` at the workspace root.
+//! A package shared by several members is downloaded and verified once,
+//! then linked from the package store into every member that uses it.
+//! Each member also gets its own `unrealpm.lock` with just its packages, so
+//! per-project commands and the editor status file keep working.
+
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use unrealpm::metrics::InstallHistory;
+use unrealpm::pipeline::run_concurrent;
+use unrealpm::{
+    find_plugin_dir, install_package_cas, is_package_in_store, outln, resolve_dependencies,
+    select_artifacts, Config, DryRun, InstallMode, Lockfile, Manifest, Phase, RegistryClient,
+    ResolvedPackage, ResolverConfig, Timings, Workspace, LOCKFILE_NAME, WORKSPACE_FILE_NAME,
+};
+
+use crate::commands::install::{
+    apply_manifest_patches, artifact_selection, batch_result, check_integrity_pins, check_license,
+    claim_install_targets, lockfile_dependencies, prepare_package, print_eta, remove_stale_package,
+    report_failures, skip_checksum_conflicts, warn_engine_plugin_conflicts, warn_module_collisions,
+    write_status_file, SignatureSummary, StatusTable, UnmanagedPolicy,
+};
+
+/// A member project of the workspace
+struct Member {
+    label: String,
+    dir: PathBuf,
+    manifest: Manifest,
+
+    /// Direct dependencies active on this host
+    dependencies: HashMap<String, String>,
+
+    /// Names of all direct dependencies, including ones skipped on this host
+    roots: Vec<String>,
+
+    /// The member's lockfile before this run
+    lockfile: Lockfile,
+}
+
+impl Member {
+    /// Packages of `lockfile` this member uses, directly or transitively
+    fn packages(&self, lockfile: &Lockfile) -> HashSet<String> {
+        let unreachable = lockfile.unreachable_packages(self.roots.iter().map(String::as_str));
+        lockfile
+            .packages
+            .keys()
+            .filter(|name| !unreachable.contains(name))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Install (or update) the dependencies of every workspace member
+///
+/// `operation` is `install` or `update`, for output and the status file.
+#[allow(clippy::too_many_arguments)]
+pub fn install(
+    operation: &str,
+    force: bool,
+    engine_version_override: Option<String>,
+    install_mode: InstallMode,
+    dry_run: bool,
+    unmanaged: UnmanagedPolicy,
+    resolver_config: &ResolverConfig,
+    jobs: usize,
+    timings: &mut Timings,
+) -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let (root, workspace) = Workspace::find(&current_dir)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No {} found in this directory or any parent.\n\n\
+            Create one listing the member projects:\n\
+              {{ \"members\": [\"Game\", \"DedicatedServer\"] }}",
+            WORKSPACE_FILE_NAME
+        )
+    })?;
+    if workspace.members.is_empty() {
+        anyhow::bail!(
+            "{} lists no members\n\n\
+            • Add the member project directories: {{ \"members\": [\"Game\", \"DedicatedServer\"] }}",
+            root.join(WORKSPACE_FILE_NAME).display()
+        );
+    }
+
+    let (verb, finished) = if operation == "update" {
+        ("Updating", "updating")
+    } else {
+        ("Installing", "installing")
+    };
+    outln!(
+        "{}{} workspace dependencies: {} ({} members)",
+        DryRun::new(dry_run).prefix(),
+        verb,
+        root.display(),
+        workspace.members.len()
+    );
+    outln!();
+
+    let config = Config::load()?;
+    let registry = RegistryClient::from_config(&config)?;
+
+    let mut members = Vec::new();
+    for (label, dir) in workspace.members.iter().zip(workspace.member_dirs(&root)) {
+        if !Manifest::exists(&dir) {
+            anyhow::bail!(
+                "Workspace member '{}' has no unrealpm.json ({})\n\n\
+                • Run 'unrealpm init' in it, or remove it from {}",
+                label,
+                dir.display(),
+                WORKSPACE_FILE_NAME
+            );
+        }
+        members.push(Member {
+            label: label.clone(),
+            manifest: Manifest::load(&dir)?,
+            lockfile: Lockfile::load_from(dir.join(LOCKFILE_NAME))?.unwrap_or_default(),
+            dir,
+            dependencies: HashMap::new(),
+            roots: Vec::new(),
+        });
+    }
+
+    // One resolution needs one engine version
+    let engine_version = match engine_version_override {
+        Some(engine) => {
+            outln!("Engine version: {} (overridden)", engine);
+            Some(engine)
+        }
+        None => workspace_engine(&config, &members)?,
+    };
+    let engine_version = engine_version.as_deref();
+
+    // Skip dependencies whose platform/engine conditions don't hold on this host
+    let platform = unrealpm::detect_platform();
+    for member in &mut members {
+        let inactive = member
+            .manifest
+            .inactive_dependencies(&platform, engine_version);
+        for (name, conditions) in &inactive {
+            outln!(
+                "Skipping {} in {} ({})",
+                name,
+                member.label,
+                conditions.describe()
+            );
+        }
+        member.roots = inactive.iter().map(|(name, _)| name.to_string()).collect();
+        member.dependencies = member
+            .manifest
+            .active_dependencies(&platform, engine_version);
+        for (name, constraint) in &member.manifest.dev_dependencies {
+            member
+                .dependencies
+                .entry(name.clone())
+                .or_insert_with(|| constraint.clone());
+        }
+        member.roots.extend(member.dependencies.keys().cloned());
+        outln!(
+            "  {}: {} direct dependencies",
+            member.label,
+            member.dependencies.len()
+        );
+    }
+    outln!();
+
+    let dependencies = Workspace::merge_dependencies(members.iter().map(|m| &m.dependencies));
+    if dependencies.is_empty() {
+        outln!("No dependencies to {}.", operation);
+        return Ok(());
+    }
+
+    // A feature any member enables is enabled for the whole workspace
+    let mut features: HashMap<String, Vec<String>> = HashMap::new();
+    for member in &members {
+        for (name, enabled) in &member.manifest.dependency_features {
+            let merged = features.entry(name.clone()).or_default();
+            for feature in enabled {
+                if !merged.contains(feature) {
+                    merged.push(feature.clone());
+                }
+            }
+        }
+    }
+    let resolver_config = &ResolverConfig {
+        features: features.clone(),
+        ..resolver_config.clone()
+    };
+
+    let spinner = unrealpm::output::spinner("Resolving workspace dependency tree...");
+    let mut resolved = timings.time(Phase::Resolve, || {
+        resolve_dependencies(
+            &dependencies,
+            &registry,
+            engine_version,
+            force,
+            Some(resolver_config),
+        )
+    })?;
+    if force && engine_version.is_some() {
+        unrealpm::strict::warn("Force installing - engine compatibility not checked")?;
+    }
+    unrealpm::output::finish_spinner(
+        &spinner,
+        format!(
+            "✓ Resolved {} packages for {} members ({} shared by several members)",
+            resolved.len(),
+            members.len(),
+            shared_count(&members)
+        ),
+    );
+    outln!();
+    warn_module_collisions(&registry, &resolved)?;
+    let overrides: HashSet<String> = members
+        .iter()
+        .flat_map(|m| m.manifest.engine_overrides.iter().cloned())
+        .collect();
+    warn_engine_plugin_conflicts(&config, &registry, &resolved, engine_version, &overrides)?;
+
+    // The workspace lockfile keeps the binary/source choice across runs
+    let lockfile_path = root.join(LOCKFILE_NAME);
+    let mut lockfile = Lockfile::load_from(&lockfile_path)?.unwrap_or_default();
+    let selection = artifact_selection(
+        install_mode,
+        engine_version,
+        &config.build_configuration(&root),
+    );
+    select_artifacts(&mut resolved, &registry, &selection, Some(&lockfile))?;
+    for member in &members {
+        check_integrity_pins(&member.manifest, &mut resolved, &registry.location())?;
+    }
+
+    let stale = lockfile.unreachable_packages(
+        resolved.keys().map(String::as_str).chain(
+            members
+                .iter()
+                .flat_map(|m| m.roots.iter().map(String::as_str)),
+        ),
+    );
+
+    // The lockfile as it will be, to work out which members use which package
+    let mut planned = lockfile.clone();
+    for (name, resolved_pkg) in &resolved {
+        let dependencies = resolved_pkg.dependencies.clone().or_else(|| {
+            lockfile
+                .get_package(name)
+                .filter(|locked| locked.version == resolved_pkg.version)
+                .and_then(|locked| locked.dependencies.clone())
+        });
+        planned.update_package(
+            name.clone(),
+            resolved_pkg.version.clone(),
+            resolved_pkg.checksum.clone(),
+            dependencies,
+        );
+    }
+    for name in &stale {
+        planned.remove_package(name);
+    }
+
+    let mut packages: Vec<(&String, &ResolvedPackage)> = resolved.iter().collect();
+    packages.sort_by(|a, b| a.0.cmp(b.0));
+    let conflicts = if dry_run {
+        Vec::new()
+    } else {
+        skip_checksum_conflicts(&lockfile, &mut packages, &registry.location())
+    };
+
+    // Members each package still has to be installed into
+    let mut targets: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, member) in members.iter_mut().enumerate() {
+        let uses = member.packages(&planned);
+        let used: Vec<(&String, &ResolvedPackage)> = packages
+            .iter()
+            .filter(|(name, _)| uses.contains(*name))
+            .copied()
+            .collect();
+        let adopted =
+            claim_install_targets(&member.dir, &used, &mut member.lockfile, unmanaged, dry_run)?;
+        let plugins_dir = unrealpm::plugins_dir(&member.dir);
+        for (name, resolved_pkg) in used {
+            let current = member.lockfile.is_locked_at(
+                name,
+                &resolved_pkg.version,
+                resolved_pkg.binary.as_ref(),
+            ) && find_plugin_dir(&plugins_dir, name).is_some();
+            if !current && !adopted.contains(name) {
+                targets.entry(name.as_str()).or_default().push(index);
+            }
+        }
+    }
+    packages.retain(|(name, _)| targets.contains_key(name.as_str()));
+
+    if dry_run {
+        if packages.is_empty() {
+            outln!("[DRY RUN] Every member is up to date");
+        } else {
+            outln!("[DRY RUN] Would install the following packages:");
+            outln!();
+        }
+        for (name, resolved_pkg) in &packages {
+            let into: Vec<&str> = targets[name.as_str()]
+                .iter()
+                .map(|&index| members[index].label.as_str())
+                .collect();
+            match &resolved_pkg.binary {
+                Some(binary) => outln!(
+                    "  - {}@{} (pre-built binary {}) → {}",
+                    name,
+                    resolved_pkg.version,
+                    binary.target(),
+                    into.join(", ")
+                ),
+                None => outln!(
+                    "  - {}@{} → {}",
+                    name,
+                    resolved_pkg.version,
+                    into.join(", ")
+                ),
+            }
+        }
+        for name in &stale {
+            outln!("  - {} (no longer required, would be removed)", name);
+        }
+        outln!();
+        outln!(
+            "[DRY RUN] Would update {} and each member's lockfile",
+            lockfile_path.display()
+        );
+        outln!();
+        return Ok(());
+    }
+
+    // Start the slowest packages first so their downloads overlap the rest
+    let history = InstallHistory::open_default().unwrap_or_default();
+    history.sort_slowest_first(&mut packages, |(name, _)| name.as_str());
+    print_eta(&history, &packages, jobs);
+
+    // Download and verify each package once, then link it into its members
+    let mut registry = registry;
+    registry.set_quiet(true);
+    let table = StatusTable::new(
+        packages
+            .iter()
+            .map(|(name, pkg)| format!("{}@{}", name, pkg.version))
+            .collect(),
+    )
+    .with_estimates(
+        packages
+            .iter()
+            .map(|(name, _)| history.estimate_total(name))
+            .collect(),
+    );
+    let shared_timings = Mutex::new(&mut *timings);
+    let signatures = Mutex::new(SignatureSummary::default());
+    let mut patched: HashMap<(usize, String), Option<String>> = HashMap::new();
+
+    let outcomes = run_concurrent(
+        &packages,
+        jobs,
+        |(name, resolved_pkg), report| {
+            prepare_package(
+                &registry,
+                name,
+                resolved_pkg,
+                &shared_timings,
+                &signatures,
+                report,
+            )
+        },
+        |(name, resolved_pkg), tarball_path| {
+            // Every member's license policy must allow the package before
+            // it's installed into any of them
+            let mut license = None;
+            for &index in &targets[name.as_str()] {
+                license = check_license(
+                    &members[index].dir,
+                    name,
+                    &resolved_pkg.version,
+                    &tarball_path,
+                )?;
+            }
+            let mut timings = shared_timings.lock().unwrap();
+            timings
+                .store_cache
+                .record(is_package_in_store(resolved_pkg.artifact_checksum()).unwrap_or(false));
+            for &index in &targets[name.as_str()] {
+                let member = &members[index];
+                let installed_path = timings.time_package(name, Phase::Extract, || {
+                    install_package_cas(
+                        &tarball_path,
+                        &member.dir,
+                        name,
+                        resolved_pkg.artifact_checksum(),
+                        None,
+                    )
+                })?;
+                let patched_hash = apply_manifest_patches(&member.dir, name, &installed_path)
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to patch {} in {}: {}", name, member.label, e)
+                    })?;
+                patched.insert((index, (*name).clone()), patched_hash);
+            }
+
+            lockfile.update_package(
+                (*name).clone(),
+                resolved_pkg.version.clone(),
+                resolved_pkg.checksum.clone(),
+                lockfile_dependencies(resolved_pkg, &tarball_path),
+            );
+            lockfile.set_binary(name, resolved_pkg.binary.clone());
+            lockfile.set_license(name, license);
+            Ok(())
+        },
+        |index, state| table.set(index, state),
+    );
+    drop(table);
+    outln!();
+
+    let failed = report_failures(&packages, &outcomes);
+    if failed == 0 {
+        outln!("✓ All packages processed");
+    } else {
+        outln!(
+            "⚠ {} of {} packages failed; continuing with the rest",
+            failed,
+            packages.len()
+        );
+    }
+    signatures.into_inner().unwrap().print();
+    outln!();
+
+    // Packages already installed everywhere (or adopted) are locked as resolved;
+    // failed ones keep their previous entry
+    let attempted: HashSet<&String> = packages.iter().map(|(name, _)| *name).collect();
+    for (name, resolved_pkg) in &resolved {
+        if attempted.contains(name)
+            || lockfile.is_locked_at(name, &resolved_pkg.version, resolved_pkg.binary.as_ref())
+        {
+            continue;
+        }
+        lockfile.update_package(
+            name.clone(),
+            resolved_pkg.version.clone(),
+            resolved_pkg.checksum.clone(),
+            planned
+                .get_package(name)
+                .and_then(|locked| locked.dependencies.clone()),
+        );
+        lockfile.set_binary(name, resolved_pkg.binary.clone());
+    }
+    for name in &stale {
+        lockfile.remove_package(name);
+    }
+    lockfile.set_features(&features);
+    lockfile.save_to(&lockfile_path)?;
+    outln!(
+        "  ✓ Workspace lockfile updated ({})",
+        lockfile_path.display()
+    );
+
+    for (index, member) in members.iter_mut().enumerate() {
+        let uses = member.packages(&lockfile);
+
+        // Packages the member no longer uses leave its Plugins/ folder
+        let mut unused: Vec<String> = member
+            .lockfile
+            .packages
+            .iter()
+            .filter(|(name, locked)| locked.source.is_none() && !uses.contains(*name))
+            .map(|(name, _)| name.clone())
+            .collect();
+        unused.sort();
+        for name in &unused {
+            remove_stale_package(&member.dir, &mut member.lockfile, name);
+        }
+
+        let mut member_lockfile = lockfile.clone();
+        member_lockfile
+            .packages
+            .retain(|name, _| uses.contains(name));
+        // Local and Fab/Marketplace plugins are recorded per member only
+        for (name, locked) in &member.lockfile.packages {
+            if locked.source.is_some() {
+                member_lockfile
+                    .packages
+                    .insert(name.clone(), locked.clone());
+            }
+        }
+        let names: Vec<String> = member_lockfile.packages.keys().cloned().collect();
+        for name in names {
+            let patched_hash = match patched.remove(&(index, name.clone())) {
+                Some(patched_hash) => patched_hash,
+                None => member
+                    .lockfile
+                    .get_package(&name)
+                    .filter(|previous| {
+                        member_lockfile.is_locked_at(
+                            &name,
+                            &previous.version,
+                            previous.binary.as_ref(),
+                        )
+                    })
+                    .and_then(|previous| previous.patched_hash.clone()),
+            };
+            member_lockfile.set_patched_hash(&name, patched_hash);
+        }
+
+        member_lockfile.save_to(member.dir.join(LOCKFILE_NAME))?;
+        write_status_file(&member.dir, operation);
+        crate::commands::vcs_ignore::sync_configured(&member.dir);
+        outln!(
+            "  ✓ {}: {} package{}",
+            member.label,
+            member_lockfile.packages.len(),
+            if member_lockfile.packages.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+    }
+    outln!();
+
+    batch_result(conflicts.into_iter().chain(outcomes).collect())?;
+    outln!("✓ Finished {} workspace dependencies", finished);
+    outln!();
+
+    Ok(())
+}
+
+/// Direct dependencies required by more than one member
+fn shared_count(members: &[Member]) -> usize {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for name in members.iter().flat_map(|m| m.dependencies.keys()) {
+        *seen.entry(name.as_str()).or_default() += 1;
+    }
+    seen.values().filter(|&&count| count > 1).count()
+}
+
+/// The engine version every member targets
+///
+/// Members without an engine association don't constrain it.
+fn workspace_engine(config: &Config, members: &[Member]) -> Result<Option<String>> {
+    let engines: Vec<(&str, &str, String)> = members
+        .iter()
+        .filter_map(|member| {
+            let association = member.manifest.engine_version.as_deref()?;
+            Some((
+                member.label.as_str(),
+                association,
+                config.logical_engine_version(association),
+            ))
+        })
+        .collect();
+
+    let Some((_, association, mapped)) = engines.first() else {
+        return Ok(None);
+    };
+    if engines.iter().any(|(_, _, engine)| engine != mapped) {
+        let list = engines
+            .iter()
+            .map(|(label, association, _)| format!("  {}: {}", label, association))
+            .collect::<Vec<_>>()
+            .join("\n");
+        anyhow::bail!(
+            "Workspace members target different engine versions:\n{}\n\n\
+            A workspace resolves for one engine. Options:\n\
+              • Align the engine versions in the members' unrealpm.json\n\
+              • Resolve for one engine: unrealpm install --workspace --engine-version <version>",
+            list
+        );
+    }
+
+    if *association == mapped.as_str() {
+        outln!("Engine version: {}", mapped);
+    } else {
+        outln!("Engine version: {} (maps to {})", association, mapped);
+    }
+    Ok(Some(mapped.clone()))
+}
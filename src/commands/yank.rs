@@ -1,9 +1,9 @@
 use anyhow::Result;
-use unrealpm::{Config, RegistryClient};
+use unrealpm::{Config, DryRun, RegistryClient};
 
-pub fn run(package: String, unyank: bool) -> Result<()> {
+pub fn run(package: String, unyank: bool, dry_run: DryRun) -> Result<()> {
     let action = if unyank { "Unyanking" } else { "Yanking" };
-    println!("{} package...", action);
+    println!("{}{} package...", dry_run.prefix(), action);
     println!();
 
     // Load config
@@ -11,11 +11,9 @@ pub fn run(package: String, unyank: bool) -> Result<()> {
     let registry = RegistryClient::from_config(&config)?;
 
     // Parse package@version
-    let (package_name, version) = if package.contains('@') {
-        let parts: Vec<&str> = package.splitn(2, '@').collect();
-        (parts[0].to_string(), parts[1].to_string())
-    } else {
-        anyhow::bail!("Please specify version: <package>@<version>");
+    let (package_name, version) = match unrealpm::scope::split_spec(&package) {
+        (name, Some(version)) => (name.to_string(), version.to_string()),
+        (_, None) => anyhow::bail!("Please specify version: <package>@<version>"),
     };
 
     // Explain what yanking means
@@ -39,6 +37,36 @@ pub fn run(package: String, unyank: bool) -> Result<()> {
         println!();
     }
 
+    let http_client = match &registry {
+        RegistryClient::Http(http_client) => http_client,
+        RegistryClient::File(_) => {
+            anyhow::bail!("Yank is only supported for HTTP registries");
+        }
+    };
+
+    if dry_run.is_enabled() {
+        let changed = http_client.yank(&package_name, &version, unyank, dry_run)?;
+        if changed.is_empty() {
+            println!(
+                "{}@{} is already {}",
+                package_name,
+                version,
+                if unyank { "installable" } else { "yanked" }
+            );
+        } else {
+            println!(
+                "Would {} {}@{} ({} version row(s))",
+                if unyank { "un-yank" } else { "yank" },
+                package_name,
+                version,
+                changed.len()
+            );
+        }
+        println!();
+        println!("[DRY RUN] No versions were changed");
+        return Ok(());
+    }
+
     print!("Continue? (yes/no): ");
     std::io::Write::flush(&mut std::io::stdout())?;
 
@@ -55,14 +83,7 @@ pub fn run(package: String, unyank: bool) -> Result<()> {
     println!("{}...", action);
 
     // Make HTTP request to registry
-    match &registry {
-        RegistryClient::Http(http_client) => {
-            http_client.yank(&package_name, &version, unyank)?;
-        }
-        RegistryClient::File(_) => {
-            anyhow::bail!("Yank is only supported for HTTP registries");
-        }
-    }
+    http_client.yank(&package_name, &version, unyank, dry_run)?;
 
     if unyank {
         println!("✓ Successfully un-yanked {}@{}", package_name, version);
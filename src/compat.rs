@@ -0,0 +1,332 @@
+//! Post-resolution compatibility checks
+//!
+//! Unreal Build Tool requires module names to be unique across every plugin
+//! in a project. Two packages that both declare a `Networking` module resolve
+//! and install fine, then fail at build time with a confusing UBT error. These
+//! checks run after dependency resolution and before anything is installed, so
+//! the collision can be reported up front.
+//!
+//! Module names come from the registry's `modules` field for each version
+//! when present, otherwise from the `.uplugin` inside a locally available
+//! tarball. Packages whose modules cannot be determined are skipped.
+//!
+//! The same goes for whole plugins: a package whose plugin the engine
+//! already ships under `Engine/Plugins` (often an older built-in copy)
+//! leaves Unreal with two plugins of one name, and which one loads is
+//! ambiguous. [`check_engine_plugin_conflicts`] finds those against the
+//! project's engine installation.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use unrealpm::compat::find_module_collisions;
+//!
+//! let mut modules = HashMap::new();
+//! modules.insert("net-a".to_string(), vec!["Networking".to_string()]);
+//! modules.insert("net-b".to_string(), vec!["networking".to_string()]);
+//!
+//! let collisions = find_module_collisions(&modules);
+//! assert_eq!(collisions[0].packages, vec!["net-a", "net-b"]);
+//! ```
+
+use crate::{read_tarball_uplugin, RegistryClient, ResolvedPackage, UPlugin};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Plugin subfolders that never hold other plugins, skipped when scanning
+const NON_PLUGIN_DIRS: [&str; 8] = [
+    "Binaries",
+    "Config",
+    "Content",
+    "Intermediate",
+    "Resources",
+    "Saved",
+    "Shaders",
+    "Source",
+];
+
+/// A module name declared by more than one package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleCollision {
+    /// Module name as declared by the first package (sorted by package name)
+    pub module: String,
+
+    /// Packages declaring the module, sorted by name
+    pub packages: Vec<String>,
+}
+
+/// Find module names declared by more than one package
+///
+/// `modules` maps package name to the modules it declares. Names are compared
+/// case-insensitively, since UBT module names collide on case-insensitive
+/// file systems. Results are sorted by module name.
+pub fn find_module_collisions(modules: &HashMap<String, Vec<String>>) -> Vec<ModuleCollision> {
+    let mut packages: Vec<&String> = modules.keys().collect();
+    packages.sort();
+
+    let mut owners: BTreeMap<String, ModuleCollision> = BTreeMap::new();
+    for package in packages {
+        for module in &modules[package] {
+            let entry = owners
+                .entry(module.to_lowercase())
+                .or_insert_with(|| ModuleCollision {
+                    module: module.clone(),
+                    packages: Vec::new(),
+                });
+            if !entry.packages.contains(package) {
+                entry.packages.push(package.clone());
+            }
+        }
+    }
+
+    owners
+        .into_values()
+        .filter(|collision| collision.packages.len() > 1)
+        .collect()
+}
+
+/// Module names for each resolved package, where they can be determined
+///
+/// Uses the registry's `modules` field for the resolved version, falling
+/// back to reading the `.uplugin` from a tarball that is already on disk
+/// (file registries and the HTTP download cache). Never downloads.
+pub fn resolved_modules(
+    registry: &RegistryClient,
+    resolved: &HashMap<String, ResolvedPackage>,
+) -> HashMap<String, Vec<String>> {
+    let mut modules = HashMap::new();
+
+    for (name, package) in resolved {
+        let from_registry = registry.get_package(name).ok().and_then(|metadata| {
+            metadata
+                .versions
+                .into_iter()
+                .find(|v| v.version == package.version)
+                .and_then(|v| v.modules)
+        });
+
+        let found = from_registry.or_else(|| {
+            let tarball = registry.get_tarball_path(name, &package.version);
+            tarball
+                .exists()
+                .then(|| read_tarball_uplugin(&tarball).ok())
+                .flatten()
+                .map(|(_, uplugin)| uplugin.module_names())
+        });
+
+        if let Some(found) = found {
+            modules.insert(name.clone(), found);
+        }
+    }
+
+    modules
+}
+
+/// Module collisions among a set of resolved packages
+pub fn check_module_collisions(
+    registry: &RegistryClient,
+    resolved: &HashMap<String, ResolvedPackage>,
+) -> Vec<ModuleCollision> {
+    find_module_collisions(&resolved_modules(registry, resolved))
+}
+
+/// A package whose plugin the engine already ships
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnginePluginConflict {
+    /// Registry package name
+    pub package: String,
+
+    /// Plugin name (the `.uplugin` file stem) shared with the engine
+    pub plugin: String,
+
+    /// The engine's `.uplugin`, relative to the engine directory
+    pub engine_path: PathBuf,
+
+    /// `VersionName` of the engine's copy, if readable
+    pub engine_version: Option<String>,
+}
+
+/// Plugins shipped with the engine at `engine_dir`, by lowercased name
+///
+/// Maps each name to its `.uplugin` under `Engine/Plugins`.
+pub fn engine_plugins<P: AsRef<Path>>(engine_dir: P) -> HashMap<String, PathBuf> {
+    walkdir::WalkDir::new(engine_dir.as_ref().join("Engine").join("Plugins"))
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.file_type().is_dir()
+                && NON_PLUGIN_DIRS.iter().any(|dir| entry.file_name() == *dir))
+        })
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry.path().extension().is_some_and(|ext| ext == "uplugin")
+        })
+        .filter_map(|entry| {
+            let stem = entry.path().file_stem()?.to_string_lossy().to_lowercase();
+            Some((stem, entry.into_path()))
+        })
+        .collect()
+}
+
+/// Plugin name of each resolved package
+///
+/// Read from the `.uplugin` of a tarball already on disk; otherwise the
+/// package name stands in for it. Never downloads.
+pub fn resolved_plugin_names(
+    registry: &RegistryClient,
+    resolved: &HashMap<String, ResolvedPackage>,
+) -> HashMap<String, String> {
+    resolved
+        .iter()
+        .map(|(name, package)| {
+            let tarball = registry.get_tarball_path(name, &package.version);
+            let plugin = tarball
+                .exists()
+                .then(|| read_tarball_uplugin(&tarball).ok())
+                .flatten()
+                .map(|(plugin, _)| plugin)
+                .unwrap_or_else(|| name.clone());
+            (name.clone(), plugin)
+        })
+        .collect()
+}
+
+/// Packages whose plugin name matches one of `engine_plugins`
+///
+/// `plugin_names` maps package name to plugin name. Names are compared
+/// case-insensitively. Results are sorted by package name.
+pub fn find_engine_plugin_conflicts(
+    plugin_names: &HashMap<String, String>,
+    engine_plugins: &HashMap<String, PathBuf>,
+    engine_dir: &Path,
+) -> Vec<EnginePluginConflict> {
+    let mut conflicts: Vec<EnginePluginConflict> = plugin_names
+        .iter()
+        .filter_map(|(package, plugin)| {
+            let uplugin = engine_plugins.get(&plugin.to_lowercase())?;
+            Some(EnginePluginConflict {
+                package: package.clone(),
+                plugin: plugin.clone(),
+                engine_path: uplugin.strip_prefix(engine_dir).unwrap_or(uplugin).into(),
+                engine_version: UPlugin::load(uplugin)
+                    .ok()
+                    .map(|uplugin| uplugin.version_name)
+                    .filter(|version| !version.is_empty()),
+            })
+        })
+        .collect();
+    conflicts.sort_by(|a, b| a.package.cmp(&b.package));
+    conflicts
+}
+
+/// Resolved packages whose plugin the engine at `engine_dir` already ships
+pub fn check_engine_plugin_conflicts(
+    registry: &RegistryClient,
+    resolved: &HashMap<String, ResolvedPackage>,
+    engine_dir: &Path,
+) -> Vec<EnginePluginConflict> {
+    find_engine_plugin_conflicts(
+        &resolved_plugin_names(registry, resolved),
+        &engine_plugins(engine_dir),
+        engine_dir,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modules(entries: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(package, modules)| {
+                (
+                    package.to_string(),
+                    modules.iter().map(|m| m.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_no_collisions() {
+        let modules = modules(&[
+            ("awesome-plugin", &["AwesomeRuntime", "AwesomeEditor"]),
+            ("other-plugin", &["OtherRuntime"]),
+            ("content-only", &[]),
+        ]);
+        assert!(find_module_collisions(&modules).is_empty());
+    }
+
+    #[test]
+    fn test_collisions_are_case_insensitive_and_sorted() {
+        let modules = modules(&[
+            ("zeta", &["Networking", "Shared"]),
+            ("alpha", &["SHARED"]),
+            ("beta", &["networking", "Beta"]),
+        ]);
+
+        let collisions = find_module_collisions(&modules);
+        assert_eq!(
+            collisions,
+            vec![
+                ModuleCollision {
+                    module: "networking".to_string(),
+                    packages: vec!["beta".to_string(), "zeta".to_string()],
+                },
+                ModuleCollision {
+                    module: "SHARED".to_string(),
+                    packages: vec!["alpha".to_string(), "zeta".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_engine_plugin_conflicts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let engine = temp.path();
+        let plugins = engine.join("Engine").join("Plugins");
+        let built_in = plugins.join("Runtime").join("AwesomePlugin");
+        std::fs::create_dir_all(&built_in).unwrap();
+        std::fs::write(
+            built_in.join("AwesomePlugin.uplugin"),
+            r#"{"FileVersion": 3, "Version": 1, "VersionName": "1.0", "FriendlyName": "Awesome"}"#,
+        )
+        .unwrap();
+        // Plugins nested in a plugin's Content folder aren't loaded by the engine
+        let content = plugins.join("Other").join("Content").join("Nested");
+        std::fs::create_dir_all(&content).unwrap();
+        std::fs::write(content.join("Nested.uplugin"), "{}").unwrap();
+
+        let shipped = engine_plugins(engine);
+        assert_eq!(shipped.len(), 1);
+
+        let names: HashMap<String, String> = [
+            ("awesome-plugin", "AWESOMEPLUGIN"),
+            ("nested", "nested"),
+            ("other-plugin", "OtherPlugin"),
+        ]
+        .iter()
+        .map(|(package, plugin)| (package.to_string(), plugin.to_string()))
+        .collect();
+        assert_eq!(
+            find_engine_plugin_conflicts(&names, &shipped, engine),
+            vec![EnginePluginConflict {
+                package: "awesome-plugin".to_string(),
+                plugin: "AWESOMEPLUGIN".to_string(),
+                engine_path: PathBuf::from(
+                    "Engine/Plugins/Runtime/AwesomePlugin/AwesomePlugin.uplugin"
+                ),
+                engine_version: Some("1.0".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_module_within_one_package_is_not_a_collision() {
+        let modules = modules(&[("awesome-plugin", &["Awesome", "awesome"])]);
+        assert!(find_module_collisions(&modules).is_empty());
+    }
+}
@@ -0,0 +1,268 @@
+//! Disk-space preflight checks
+//!
+//! Downloads, extractions, and plugin builds check the free space on every
+//! volume they are about to write to before starting, so a full disk fails
+//! early with exact numbers instead of dying halfway through with an IO error.
+//!
+//! Set `UNREALPM_SKIP_DISK_CHECK=1` to skip the checks, e.g. on network
+//! filesystems that misreport their free space.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use unrealpm::disk::{ensure_space, SpaceRequirement};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! ensure_space(&[SpaceRequirement::new("Plugins", 512 * 1024 * 1024, "extracted package")])?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Error, Result};
+use flate2::read::GzDecoder;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use tar::Archive;
+
+/// Environment variable that disables the preflight checks
+pub const SKIP_DISK_CHECK_ENV: &str = "UNREALPM_SKIP_DISK_CHECK";
+
+/// Free space kept in reserve on every volume on top of the estimate
+pub const DISK_HEADROOM: u64 = 64 * 1024 * 1024;
+
+/// Plugin builds need roughly this many times the source size
+/// (Intermediate/ plus the packaged output)
+pub const BUILD_SPACE_FACTOR: u64 = 5;
+
+/// Lower bound for a plugin build estimate
+pub const MIN_BUILD_SPACE: u64 = 1024 * 1024 * 1024;
+
+/// Space an operation needs below a directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceRequirement {
+    /// Directory that will be written to (need not exist yet)
+    pub path: PathBuf,
+
+    /// Estimated bytes written
+    pub bytes: u64,
+
+    /// What the space is for, shown in the error message
+    pub purpose: String,
+}
+
+impl SpaceRequirement {
+    pub fn new<P: AsRef<Path>>(path: P, bytes: u64, purpose: &str) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            bytes,
+            purpose: purpose.to_string(),
+        }
+    }
+}
+
+/// Verify every volume touched by `requirements` has room for them
+///
+/// Requirements on the same volume are added together. Fails with
+/// [`Error::InsufficientDiskSpace`] for the first volume that is short.
+pub fn ensure_space(requirements: &[SpaceRequirement]) -> Result<()> {
+    if std::env::var(SKIP_DISK_CHECK_ENV).is_ok_and(|v| !v.is_empty() && v != "0") {
+        return Ok(());
+    }
+
+    // volume -> (directory to report, total bytes, purposes)
+    let mut volumes: BTreeMap<String, (PathBuf, u64, Vec<String>)> = BTreeMap::new();
+    for req in requirements.iter().filter(|r| r.bytes > 0) {
+        let existing = existing_ancestor(&req.path);
+        let entry = volumes
+            .entry(volume_key(&existing))
+            .or_insert_with(|| (existing, 0, Vec::new()));
+        entry.1 += req.bytes;
+        entry
+            .2
+            .push(format!("{} ({})", req.purpose, format_bytes(req.bytes)));
+    }
+
+    for (path, required, purposes) in volumes.into_values() {
+        let available = available_space(&path)?;
+        let required = required + DISK_HEADROOM;
+        if available < required {
+            return Err(Error::InsufficientDiskSpace {
+                path,
+                required,
+                available,
+                purposes: purposes.join(", "),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Free bytes available to the current user on the volume holding `path`
+///
+/// `path` does not need to exist; its nearest existing ancestor is queried.
+pub fn available_space<P: AsRef<Path>>(path: P) -> Result<u64> {
+    let existing = existing_ancestor(path.as_ref());
+    fs2::available_space(&existing).map_err(|e| {
+        Error::Other(format!(
+            "Failed to query free space for {}: {}",
+            existing.display(),
+            e
+        ))
+    })
+}
+
+/// Total size of the files in a package tarball once extracted
+pub fn tarball_unpacked_size<P: AsRef<Path>>(tarball_path: P) -> Result<u64> {
+    let tar_gz = File::open(tarball_path.as_ref())?;
+    let mut archive = Archive::new(GzDecoder::new(tar_gz));
+
+    let mut total = 0u64;
+    for entry in archive.entries()? {
+        total = total.saturating_add(entry?.header().size()?);
+    }
+    Ok(total)
+}
+
+/// Estimated space for building a plugin whose sources take `source_bytes`
+pub fn estimate_build_space(source_bytes: u64) -> u64 {
+    source_bytes
+        .saturating_mul(BUILD_SPACE_FACTOR)
+        .max(MIN_BUILD_SPACE)
+}
+
+/// Whether two paths (or their nearest existing ancestors) share a volume
+pub fn same_volume<P: AsRef<Path>, Q: AsRef<Path>>(a: P, b: Q) -> bool {
+    volume_key(&existing_ancestor(a.as_ref())) == volume_key(&existing_ancestor(b.as_ref()))
+}
+
+/// Format a byte count for messages (e.g. "1.50 GB")
+pub fn format_bytes(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.2} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.2} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.2} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
+
+/// Nearest ancestor of `path` that exists on disk
+fn existing_ancestor(path: &Path) -> PathBuf {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    };
+
+    absolute
+        .ancestors()
+        .find(|p| p.exists())
+        .map(|p| p.to_path_buf())
+        .unwrap_or(absolute)
+}
+
+/// Identifier of the volume holding an existing path
+#[cfg(unix)]
+fn volume_key(path: &Path) -> String {
+    use std::os::unix::fs::MetadataExt;
+    match fs::metadata(path) {
+        Ok(meta) => format!("dev:{}", meta.dev()),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+/// Identifier of the volume holding an existing path
+#[cfg(not(unix))]
+fn volume_key(path: &Path) -> String {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    path.components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().to_uppercase())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tarball_unpacked_size() {
+        let temp = TempDir::new().unwrap();
+        let tarball = temp.path().join("pkg.tar.gz");
+
+        let file = File::create(&tarball).unwrap();
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, len) in [("Pkg/a.txt", 1000usize), ("Pkg/b.bin", 2500)] {
+            let data = vec![7u8; len];
+            let mut header = tar::Header::new_gnu();
+            header.set_size(len as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, &data[..]).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        assert_eq!(tarball_unpacked_size(&tarball).unwrap(), 3500);
+    }
+
+    #[test]
+    fn test_ensure_space_fails_with_exact_numbers() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("Plugins/NotCreatedYet");
+
+        // Nothing on any real disk has this much room
+        let err = ensure_space(&[SpaceRequirement::new(&target, u64::MAX / 2, "extraction")])
+            .unwrap_err();
+        match err {
+            Error::InsufficientDiskSpace {
+                path,
+                required,
+                available,
+                purposes,
+            } => {
+                assert_eq!(path, temp.path());
+                assert_eq!(required, u64::MAX / 2 + DISK_HEADROOM);
+                assert!(available < required);
+                assert!(purposes.starts_with("extraction"));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+
+        // Zero-byte requirements never fail
+        ensure_space(&[SpaceRequirement::new(&target, 0, "nothing")]).unwrap();
+    }
+
+    #[test]
+    fn test_same_volume_for_missing_children() {
+        let temp = TempDir::new().unwrap();
+        assert!(same_volume(temp.path(), temp.path().join("a/b/c")));
+    }
+
+    #[test]
+    fn test_estimate_build_space() {
+        assert_eq!(estimate_build_space(0), MIN_BUILD_SPACE);
+        assert_eq!(
+            estimate_build_space(10 * MIN_BUILD_SPACE),
+            10 * MIN_BUILD_SPACE * BUILD_SPACE_FACTOR
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 bytes");
+        assert_eq!(format_bytes(1536), "1.50 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.00 GB");
+    }
+}
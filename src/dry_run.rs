@@ -0,0 +1,78 @@
+//! Dry runs of mutating commands
+//!
+//! Every command that changes the project, the package store, the
+//! configuration, or the registry accepts `--dry-run`. The flag becomes a
+//! [`DryRun`] that is passed down to the library functions doing the work.
+//! They compute exactly what they would do (which folders, which files,
+//! which versions) and report it, but leave every write to
+//! [`DryRun::apply`], which skips it. Output built from their return values
+//! therefore matches a real run, with [`DryRun::verb`] choosing between
+//! "Removed" and "Would remove".
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::DryRun;
+//!
+//! let dry_run = DryRun::new(true);
+//! let mut written = false;
+//! let result = dry_run.apply(|| {
+//!     written = true;
+//!     Ok(42)
+//! }).unwrap();
+//! assert_eq!(result, None);
+//! assert!(!written);
+//! assert_eq!(dry_run.verb("Removed", "Would remove"), "Would remove");
+//!
+//! assert_eq!(DryRun::OFF.apply(|| Ok(42)).unwrap(), Some(42));
+//! ```
+
+use crate::Result;
+
+/// Whether changes are made or only reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DryRun {
+    enabled: bool,
+}
+
+impl DryRun {
+    /// Changes are made
+    pub const OFF: DryRun = DryRun { enabled: false };
+
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Whether changes are only reported
+    pub fn is_enabled(self) -> bool {
+        self.enabled
+    }
+
+    /// Run `change`, or skip it in a dry run
+    ///
+    /// Returns `None` when the change was skipped.
+    pub fn apply<T>(self, change: impl FnOnce() -> Result<T>) -> Result<Option<T>> {
+        if self.enabled {
+            return Ok(None);
+        }
+        change().map(Some)
+    }
+
+    /// `[DRY RUN] ` in a dry run, for headers
+    pub fn prefix(self) -> &'static str {
+        if self.enabled {
+            "[DRY RUN] "
+        } else {
+            ""
+        }
+    }
+
+    /// `done` normally, `would` in a dry run
+    pub fn verb(self, done: &'static str, would: &'static str) -> &'static str {
+        if self.enabled {
+            would
+        } else {
+            done
+        }
+    }
+}
@@ -31,44 +31,66 @@ pub enum Error {
     #[error("Invalid manifest: {0}")]
     InvalidManifest(String),
 
-    #[error(
-        "No .uproject file found in current directory\n\n\
-             Hint: Make sure you're running this command from your Unreal Engine project root.\n\
-             The project root should contain a .uproject file.\n\n\
-             Example structure:\n\
-             MyProject/\n\
-             ├── MyProject.uproject  ← This file is required\n\
-             ├── Config/\n\
-             ├── Content/\n\
-             └── Source/\n\n\
-             Try: cd /path/to/your/project"
-    )]
+    #[error("{}", crate::i18n::tr("error.no_uproject", &[]))]
     NoUProjectFile,
 
-    #[error("Unreal Engine installation not found{}\n\n\
-             Hint: UnrealPM couldn't detect an Unreal Engine installation on your system.\n\n\
-             Common locations:\n\
-             - Windows: C:\\Program Files\\Epic Games\\UE_5.x\n\
-             - Linux:   ~/UnrealEngine\n\
-             - macOS:   /Users/Shared/Epic Games/UE_5.x\n\n\
-             Solutions:\n\
-             1. Install Unreal Engine from Epic Games Launcher\n\
-             2. Manually configure the engine path:\n\
-                unrealpm config add-engine \"5.3\" \"/path/to/UE_5.3\"\n\
-             3. Verify your .uproject has a valid EngineAssociation field",
-             .0)]
+    #[error("{}", crate::i18n::tr("error.multiple_uproject", &[("files", .0)]))]
+    MultipleUProjectFiles(String),
+
+    #[error("{}", crate::i18n::tr("error.engine_not_found", &[("detail", .0)]))]
     EngineNotFound(String),
 
+    #[error("{}", crate::i18n::tr("error.resolution_failed", &[("details", .0)]))]
+    DependencyResolutionFailed(String),
+
     #[error(
-        "Dependency resolution failed: {0}\n\n\
-             Hint: This usually means conflicting version requirements.\n\n\
-             Possible solutions:\n\
-             1. Check your unrealpm.json for incompatible version constraints\n\
-             2. Update package versions to compatible ranges\n\
-             3. Use --force to bypass version checks (not recommended)\n\n\
-             Need help? Run: unrealpm list --verbose"
+        "{}",
+        crate::i18n::tr(
+            "error.disk_space",
+            &[
+                ("path", &path.display()),
+                ("required", &crate::disk::format_bytes(*required)),
+                ("required_bytes", required),
+                ("available", &crate::disk::format_bytes(*available)),
+                ("available_bytes", available),
+                ("purposes", purposes),
+                (
+                    "shortfall",
+                    &crate::disk::format_bytes(required.saturating_sub(*available))
+                ),
+            ]
+        )
     )]
-    DependencyResolutionFailed(String),
+    InsufficientDiskSpace {
+        path: std::path::PathBuf,
+        required: u64,
+        available: u64,
+        purposes: String,
+    },
+
+    #[error(
+        "{}",
+        crate::i18n::tr(
+            "error.checksum_mismatch",
+            &[("expected", expected), ("actual", actual)]
+        )
+    )]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("{0}")]
+    SignatureVerificationFailed(String),
+
+    #[error("{0}")]
+    Network(String),
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    EngineIncompatible(String),
+
+    #[error("{}", crate::i18n::tr("error.strict", &[("message", .0)]))]
+    Strict(String),
 
     #[error("{0}")]
     Other(String),
@@ -0,0 +1,204 @@
+//! Typed progress events for frontends built on the library
+//!
+//! Long-running operations (resolving, downloading, verifying, extracting)
+//! report what they are doing as [`Event`]s sent to an [`EventSink`]. A GUI
+//! can match on the variants to drive per-package progress bars instead of
+//! parsing messages.
+//!
+//! Installer functions take an `Option<EventSink>`; a [`RegistryClient`]
+//! carries one for downloads and resolution (see
+//! [`RegistryClient::set_event_sink`]).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use unrealpm::events::{self, Event};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let (sink, receiver) = events::channel();
+//! std::thread::spawn(move || {
+//!     unrealpm::install_package_cas(
+//!         "awesome-plugin-1.0.0.tar.gz",
+//!         "MyGame",
+//!         "awesome-plugin",
+//!         "abc123...",
+//!         Some(sink),
+//!     )
+//! });
+//!
+//! for event in receiver {
+//!     match event {
+//!         Event::ExtractProgress { current, total, .. } => println!("{}/{}", current, total),
+//!         Event::Completed { package } => println!("{} installed", package),
+//!         Event::Failed { package, error } => eprintln!("{} failed: {}", package, error),
+//!         _ => {}
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`RegistryClient`]: crate::RegistryClient
+//! [`RegistryClient::set_event_sink`]: crate::RegistryClient::set_event_sink
+
+use crate::ProgressCallback;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+/// Something that happened during a long-running operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Dependency resolution started for this many direct dependencies
+    ResolveStarted { dependencies: usize },
+
+    /// Bytes of a package tarball received so far; `total` is `None` when
+    /// the server sends no Content-Length
+    DownloadProgress {
+        package: String,
+        bytes: u64,
+        total: Option<u64>,
+    },
+
+    /// Bytes of a tarball hashed so far while verifying its checksum
+    VerifyProgress { bytes: u64, total: u64 },
+
+    /// A step of unpacking into the store or installing into a project;
+    /// `current` out of `total` (percent)
+    ExtractProgress {
+        message: String,
+        current: u64,
+        total: u64,
+    },
+
+    /// One line of output from a plugin build
+    BuildOutputLine { package: String, line: String },
+
+    /// A package finished installing
+    Completed { package: String },
+
+    /// A package failed to install
+    Failed { package: String, error: String },
+}
+
+impl Event {
+    /// One-line description, as shown by the CLI
+    pub fn message(&self) -> String {
+        match self {
+            Event::ResolveStarted { dependencies } => {
+                format!("Resolving {} dependencies...", dependencies)
+            }
+            Event::DownloadProgress { package, .. } => format!("Downloading {}...", package),
+            Event::VerifyProgress { bytes, total } if bytes >= total => {
+                "Checksum verified".to_string()
+            }
+            Event::VerifyProgress { .. } => "Verifying checksum...".to_string(),
+            Event::ExtractProgress { message, .. } => message.clone(),
+            Event::BuildOutputLine { line, .. } => line.clone(),
+            Event::Completed { package } => format!("Installed {}", package),
+            Event::Failed { package, error } => format!("Failed to install {}: {}", package, error),
+        }
+    }
+
+    /// An [`Event::ExtractProgress`] step
+    pub(crate) fn extract(message: impl Into<String>, current: u64, total: u64) -> Self {
+        Event::ExtractProgress {
+            message: message.into(),
+            current,
+            total,
+        }
+    }
+
+    /// Progress as `(current, total)`; `(0, 0)` when the event has none
+    pub fn progress(&self) -> (u64, u64) {
+        match self {
+            Event::DownloadProgress { bytes, total, .. } => (*bytes, total.unwrap_or(0)),
+            Event::VerifyProgress { bytes, total } => (*bytes, *total),
+            Event::ExtractProgress { current, total, .. } => (*current, *total),
+            Event::Completed { .. } => (100, 100),
+            _ => (0, 0),
+        }
+    }
+}
+
+/// Receiver for [`Event`]s; called on whichever thread the operation runs on
+pub type EventSink = Arc<dyn Fn(&Event) + Send + Sync>;
+
+/// A sink that forwards events into a channel, for consumers on another thread
+///
+/// Events sent after the receiver is dropped are discarded.
+pub fn channel() -> (EventSink, mpsc::Receiver<Event>) {
+    let (sender, receiver) = mpsc::channel();
+    let sink: EventSink = Arc::new(move |event: &Event| {
+        let _ = sender.send(event.clone());
+    });
+    (sink, receiver)
+}
+
+/// Adapt a message/current/total [`ProgressCallback`] to an [`EventSink`]
+///
+/// Each event is passed on as its [`Event::message`] and [`Event::progress`].
+pub fn from_progress_callback(callback: ProgressCallback) -> EventSink {
+    Arc::new(move |event: &Event| {
+        let (current, total) = event.progress();
+        callback(&event.message(), current, total)
+    })
+}
+
+/// Send `event` to `sink`, if there is one
+pub(crate) fn emit(sink: Option<&EventSink>, event: Event) {
+    if let Some(sink) = sink {
+        sink(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_channel() {
+        let (sink, receiver) = channel();
+        emit(Some(&sink), Event::ResolveStarted { dependencies: 2 });
+        sink(&Event::Completed {
+            package: "alpha".to_string(),
+        });
+        drop(sink);
+
+        let events: Vec<Event> = receiver.iter().collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], Event::ResolveStarted { dependencies: 2 });
+    }
+
+    #[test]
+    fn test_from_progress_callback() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let callback: ProgressCallback = Arc::new(move |message, current, total| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((message.to_string(), current, total));
+        });
+
+        let sink = from_progress_callback(callback);
+        sink(&Event::VerifyProgress {
+            bytes: 10,
+            total: 40,
+        });
+        sink(&Event::VerifyProgress {
+            bytes: 40,
+            total: 40,
+        });
+        sink(&Event::DownloadProgress {
+            package: "alpha".to_string(),
+            bytes: 5,
+            total: None,
+        });
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0], ("Verifying checksum...".to_string(), 10, 40));
+        assert_eq!(calls[1], ("Checksum verified".to_string(), 40, 40));
+        assert_eq!(calls[2], ("Downloading alpha...".to_string(), 5, 0));
+    }
+}
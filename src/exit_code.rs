@@ -0,0 +1,103 @@
+//! Exit codes of the unrealpm CLI
+//!
+//! Wrappers and CI scripts can branch on the class of a failure without
+//! parsing error messages. The codes are a stable contract: a code's meaning
+//! never changes, and new failure classes get new codes.
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0    | Success |
+//! | 1    | Any other failure; also findings from `outdated --exit-code` and `audit --deny` |
+//! | 2    | Invalid command-line usage |
+//! | 10   | Dependency resolution conflict |
+//! | 11   | Checksum mismatch |
+//! | 12   | Signature verification failure, including a changed publisher key |
+//! | 13   | Network failure or registry server error |
+//! | 14   | Authentication or authorization failure |
+//! | 15   | No version compatible with the project's engine |
+//! | 16   | Package not found |
+//! | 17   | Not enough disk space |
+//! | 18   | Warning turned into an error by strict mode |
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::{exit_code, Error};
+//!
+//! let error = Error::ChecksumMismatch {
+//!     expected: "aa".to_string(),
+//!     actual: "bb".to_string(),
+//! };
+//! assert_eq!(exit_code::for_error(&error), exit_code::CHECKSUM_MISMATCH);
+//! ```
+
+use crate::Error;
+
+pub const SUCCESS: i32 = 0;
+pub const FAILURE: i32 = 1;
+pub const USAGE: i32 = 2;
+pub const RESOLUTION_CONFLICT: i32 = 10;
+pub const CHECKSUM_MISMATCH: i32 = 11;
+pub const SIGNATURE_FAILURE: i32 = 12;
+pub const NETWORK: i32 = 13;
+pub const AUTH: i32 = 14;
+pub const ENGINE_INCOMPATIBLE: i32 = 15;
+pub const PACKAGE_NOT_FOUND: i32 = 16;
+pub const DISK_SPACE: i32 = 17;
+pub const STRICT: i32 = 18;
+
+/// Exit code for `error`, or for the first typed error among its sources
+///
+/// Errors that carry no class, such as I/O errors or a command's own
+/// messages, exit with [`FAILURE`].
+pub fn for_error(error: &(dyn std::error::Error + 'static)) -> i32 {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(error) = error.downcast_ref::<Error>() {
+            if let Some(code) = classify(error) {
+                return code;
+            }
+        } else if error.downcast_ref::<reqwest::Error>().is_some() {
+            return NETWORK;
+        }
+        current = error.source();
+    }
+    FAILURE
+}
+
+fn classify(error: &Error) -> Option<i32> {
+    match error {
+        Error::DependencyConflict(_) | Error::DependencyResolutionFailed(_) => {
+            Some(RESOLUTION_CONFLICT)
+        }
+        Error::ChecksumMismatch { .. } => Some(CHECKSUM_MISMATCH),
+        Error::SignatureVerificationFailed(_) => Some(SIGNATURE_FAILURE),
+        Error::Http(_) | Error::Network(_) => Some(NETWORK),
+        Error::Unauthorized(_) => Some(AUTH),
+        Error::EngineIncompatible(_) => Some(ENGINE_INCOMPATIBLE),
+        Error::PackageNotFound(_) => Some(PACKAGE_NOT_FOUND),
+        Error::InsufficientDiskSpace { .. } => Some(DISK_SPACE),
+        Error::Strict(_) => Some(STRICT),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_error_follows_sources() {
+        let error = anyhow::Error::new(Error::Unauthorized("x".into())).context("outer");
+        assert_eq!(for_error(error.as_ref()), AUTH);
+        assert_eq!(for_error(&Error::Other("x".into())), FAILURE);
+        assert_eq!(
+            for_error(&Error::DependencyResolutionFailed("x".into())),
+            RESOLUTION_CONFLICT
+        );
+        assert_eq!(
+            for_error(&Error::EngineIncompatible("x".into())),
+            ENGINE_INCOMPATIBLE
+        );
+    }
+}
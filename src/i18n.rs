@@ -0,0 +1,260 @@
+//! Localizable CLI messages
+//!
+//! User-facing error guidance lives in message catalogs under `locales/`
+//! rather than in format strings, so it can be translated. English
+//! (`locales/en.toml`) is the reference catalog; a translation only needs
+//! the keys it translates, and everything else falls back to English.
+//!
+//! The locale comes from, in order:
+//!
+//! 1. `UNREALPM_LANG` (e.g. `ja`, `ko_KR.UTF-8`)
+//! 2. `[ui] locale` in the user config, which the CLI passes to [`init`]
+//! 3. `LC_ALL`, `LC_MESSAGES`, or `LANG`
+//!
+//! Only the language part of a locale is used, so `ja_JP.UTF-8` selects `ja`.
+//!
+//! # Adding a translation
+//!
+//! Copy `locales/en.toml` to `locales/<lang>.toml`, translate the values,
+//! and add the file to `BUILTIN_CATALOGS`. Placeholders like `{name}` must
+//! be kept. To try a catalog without rebuilding, put it in
+//! `~/.unrealpm/locales/<lang>.toml`; a catalog there is used in place of
+//! the built-in one.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::i18n::Catalog;
+//!
+//! let catalog = Catalog::parse(
+//!     "ja",
+//!     r#"[registry]
+//! not_found = "パッケージ '{name}' はレジストリにありません""#,
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     catalog.message("registry.not_found", &[("name", &"foo")]),
+//!     "パッケージ 'foo' はレジストリにありません"
+//! );
+//! // Untranslated keys fall back to English
+//! assert_eq!(catalog.message("registry.did_you_mean", &[]), "Did you mean one of these?");
+//! ```
+
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Environment variable that selects the message locale
+pub const LOCALE_ENV: &str = "UNREALPM_LANG";
+
+/// Locale of the reference catalog
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Catalogs compiled into the binary
+const BUILTIN_CATALOGS: &[(&str, &str)] = &[("en", include_str!("../locales/en.toml"))];
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Messages for one locale, with English filling any gaps
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    locale: String,
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// The English catalog
+    pub fn english() -> Self {
+        Self {
+            locale: DEFAULT_LOCALE.to_string(),
+            messages: flatten(english_source()).unwrap_or_default(),
+        }
+    }
+
+    /// Layer a translation (catalog TOML) over English
+    pub fn parse(locale: &str, source: &str) -> Result<Self> {
+        let mut catalog = Self::english();
+        catalog.messages.extend(flatten(source)?);
+        catalog.locale = locale.to_string();
+        Ok(catalog)
+    }
+
+    /// Catalog for `locale`, from the user's locales directory or the
+    /// built-in set
+    ///
+    /// Unknown locales and unreadable catalogs fall back to English.
+    pub fn load(locale: &str) -> Self {
+        let user_catalog = locales_dir()
+            .map(|dir| dir.join(format!("{}.toml", locale)))
+            .and_then(|path| std::fs::read_to_string(path).ok());
+        let source = user_catalog.as_deref().or_else(|| {
+            BUILTIN_CATALOGS
+                .iter()
+                .find(|(name, _)| *name == locale)
+                .map(|(_, source)| *source)
+        });
+        source
+            .and_then(|source| Self::parse(locale, source).ok())
+            .unwrap_or_else(Self::english)
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Message for `key` with `{placeholder}`s filled in from `args`
+    ///
+    /// An unknown key returns the key itself, so a typo shows up in the
+    /// output instead of an empty line.
+    pub fn message(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let Some(template) = self.messages.get(key) else {
+            return key.to_string();
+        };
+        args.iter().fold(template.clone(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+    }
+}
+
+/// Select the locale for the rest of the process
+///
+/// `configured` is the `[ui] locale` setting. Has no effect once a message
+/// has been looked up.
+pub fn init(configured: Option<&str>) {
+    CATALOG.get_or_init(|| Catalog::load(&select_locale(configured)));
+}
+
+/// Message for `key` in the current locale (see [`Catalog::message`])
+pub fn tr(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    CATALOG
+        .get_or_init(|| Catalog::load(&select_locale(None)))
+        .message(key, args)
+}
+
+/// Locale selected by the environment and `configured`
+pub fn select_locale(configured: Option<&str>) -> String {
+    let from_env = |var: &str| std::env::var(var).ok().as_deref().and_then(language);
+    from_env(LOCALE_ENV)
+        .or_else(|| configured.and_then(language))
+        .or_else(|| from_env("LC_ALL"))
+        .or_else(|| from_env("LC_MESSAGES"))
+        .or_else(|| from_env("LANG"))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// Locales with a built-in catalog
+pub fn builtin_locales() -> Vec<&'static str> {
+    BUILTIN_CATALOGS.iter().map(|(name, _)| *name).collect()
+}
+
+/// Language part of a locale name: `ja_JP.UTF-8` -> `ja`
+///
+/// `C` and `POSIX` carry no language and yield `None`.
+pub fn language(locale: &str) -> Option<String> {
+    let language = locale
+        .split(['_', '-', '.', '@'])
+        .next()?
+        .trim()
+        .to_ascii_lowercase();
+    if language.is_empty() || language == "c" || language == "posix" {
+        return None;
+    }
+    Some(language)
+}
+
+fn english_source() -> &'static str {
+    BUILTIN_CATALOGS[0].1
+}
+
+/// Directory for user-supplied catalogs, next to the user config
+fn locales_dir() -> Option<PathBuf> {
+    crate::Config::default_path()
+        .ok()
+        .and_then(|path| path.parent().map(|dir| dir.join("locales")))
+}
+
+/// Catalog TOML as `section.key` -> message
+fn flatten(source: &str) -> Result<HashMap<String, String>> {
+    fn walk(prefix: &str, table: &toml::Table, out: &mut HashMap<String, String>) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            match value {
+                toml::Value::String(message) => {
+                    out.insert(key, message.clone());
+                }
+                toml::Value::Table(table) => walk(&key, table, out),
+                _ => {}
+            }
+        }
+    }
+
+    let table: toml::Table = toml::from_str(source)
+        .map_err(|e| Error::Other(format!("Invalid message catalog: {}", e)))?;
+    let mut messages = HashMap::new();
+    walk("", &table, &mut messages);
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(message: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .filter(|name| name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    #[test]
+    fn test_language_from_locale_names() {
+        assert_eq!(language("ja_JP.UTF-8").as_deref(), Some("ja"));
+        assert_eq!(language("ko-KR").as_deref(), Some("ko"));
+        assert_eq!(language("EN").as_deref(), Some("en"));
+        assert_eq!(language("C.UTF-8"), None);
+        assert_eq!(language("POSIX"), None);
+        assert_eq!(language(""), None);
+    }
+
+    #[test]
+    fn test_message_fills_placeholders() {
+        let catalog = Catalog::english();
+        assert_eq!(
+            catalog.message("registry.not_found", &[("name", &"foo")]),
+            "Package 'foo' not found in registry"
+        );
+        assert_eq!(catalog.message("no.such.key", &[]), "no.such.key");
+    }
+
+    #[test]
+    fn test_builtin_catalogs_match_english() {
+        let english = flatten(english_source()).unwrap();
+        assert!(english.contains_key("cli.error"));
+
+        for (locale, source) in BUILTIN_CATALOGS {
+            for (key, message) in flatten(source).unwrap() {
+                let reference = english
+                    .get(&key)
+                    .unwrap_or_else(|| panic!("{}: unknown key {}", locale, key));
+                assert_eq!(
+                    placeholders(&message),
+                    placeholders(reference),
+                    "{}: placeholders differ for {}",
+                    locale,
+                    key
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Ignore-file entries for managed plugins
+//!
+//! Teams that don't commit dependencies want installed plugins kept out of
+//! version control; a multi-GB plugin folder committed by accident is
+//! painful to undo. unrealpm keeps one entry per managed plugin folder in a
+//! marked block of `.gitignore`, `.p4ignore`, or a dedicated
+//! `.unrealpm-managed` list (which a team can point `core.excludesFile` or
+//! `P4IGNORE` at):
+//!
+//! ```text
+//! # >>> unrealpm managed plugins (generated by `unrealpm vcs-ignore sync`, do not edit)
+//! /Plugins/AwesomePlugin/
+//! # <<< unrealpm managed plugins
+//! ```
+//!
+//! Lines outside the block are never touched. With `[vcs] ignore` set, the
+//! block is refreshed after every install, update, and uninstall.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::ignore::update_block;
+//!
+//! let gitignore = "Binaries/\nIntermediate/\n";
+//! let updated = update_block(gitignore, &["/Plugins/AwesomePlugin/".to_string()]);
+//! assert!(updated.starts_with("Binaries/\nIntermediate/\n\n# >>> unrealpm"));
+//! assert!(updated.contains("\n/Plugins/AwesomePlugin/\n"));
+//!
+//! // An empty list removes the block again
+//! assert_eq!(update_block(&updated, &[]), gitignore);
+//! ```
+
+use crate::marketplace::FAB_SOURCE_PREFIX;
+use crate::{find_plugin_dir, Error, Lockfile, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// First line of the managed block
+pub const BLOCK_START: &str =
+    "# >>> unrealpm managed plugins (generated by `unrealpm vcs-ignore sync`, do not edit)";
+
+/// Last line of the managed block
+pub const BLOCK_END: &str = "# <<< unrealpm managed plugins";
+
+/// Ignore file that lists managed plugins
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IgnoreFile {
+    /// `.gitignore`
+    #[default]
+    Gitignore,
+    /// `.p4ignore`
+    P4ignore,
+    /// `.unrealpm-managed`, for teams that include it from their own config
+    List,
+}
+
+impl IgnoreFile {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            IgnoreFile::Gitignore => ".gitignore",
+            IgnoreFile::P4ignore => ".p4ignore",
+            IgnoreFile::List => ".unrealpm-managed",
+        }
+    }
+
+    /// Path of the file in `project_dir`
+    pub fn path<P: AsRef<Path>>(&self, project_dir: P) -> PathBuf {
+        project_dir.as_ref().join(self.file_name())
+    }
+}
+
+impl fmt::Display for IgnoreFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IgnoreFile::Gitignore => "gitignore",
+            IgnoreFile::P4ignore => "p4ignore",
+            IgnoreFile::List => "list",
+        })
+    }
+}
+
+impl FromStr for IgnoreFile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim_start_matches('.').to_ascii_lowercase().as_str() {
+            "gitignore" => Ok(IgnoreFile::Gitignore),
+            "p4ignore" => Ok(IgnoreFile::P4ignore),
+            "list" | "unrealpm-managed" => Ok(IgnoreFile::List),
+            _ => Err(Error::Other(format!(
+                "Unknown ignore file '{}'. Use: gitignore, p4ignore, list",
+                s
+            ))),
+        }
+    }
+}
+
+/// Ignore entries (`/Plugins/<folder>/`) for the plugins unrealpm installed
+///
+/// Fab/Marketplace plugins are managed by the launcher, not unrealpm, and
+/// are left out. Packages whose folder is missing are skipped. Standalone
+/// plugin workspaces get `/Deps/Plugins/<folder>/` entries instead.
+pub fn managed_entries<P: AsRef<Path>>(project_dir: P, lockfile: &Lockfile) -> Vec<String> {
+    let project_dir = project_dir.as_ref();
+    let plugins_dir = crate::installer::plugins_dir(project_dir);
+    let prefix = plugins_dir
+        .strip_prefix(project_dir)
+        .unwrap_or(Path::new("Plugins"))
+        .to_string_lossy()
+        .replace('\\', "/");
+    let mut entries: Vec<String> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, locked)| {
+            !locked
+                .source
+                .as_deref()
+                .is_some_and(|source| source.starts_with(FAB_SOURCE_PREFIX))
+        })
+        .filter_map(|(name, _)| find_plugin_dir(&plugins_dir, name))
+        .filter_map(|dir| {
+            dir.file_name()
+                .map(|folder| format!("/{}/{}/", prefix, folder.to_string_lossy()))
+        })
+        .collect();
+    entries.sort();
+    entries.dedup();
+    entries
+}
+
+/// `content` with the managed block replaced by `entries`
+///
+/// The block is appended if missing and removed when `entries` is empty.
+pub fn update_block(content: &str, entries: &[String]) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|line| line.trim() == BLOCK_START);
+    let end = start.and_then(|start| {
+        lines[start..]
+            .iter()
+            .position(|line| line.trim() == BLOCK_END)
+            .map(|offset| start + offset)
+    });
+
+    let (mut before, after): (Vec<&str>, Vec<&str>) = match (start, end) {
+        (Some(start), Some(end)) => (lines[..start].to_vec(), lines[end + 1..].to_vec()),
+        _ => (lines, Vec::new()),
+    };
+    // The blank line separating the block from the user's entries goes with it
+    while before.last().is_some_and(|line| line.trim().is_empty()) {
+        before.pop();
+    }
+
+    let mut out: Vec<String> = before.iter().map(|line| line.to_string()).collect();
+    if !entries.is_empty() {
+        if !out.is_empty() {
+            out.push(String::new());
+        }
+        out.push(BLOCK_START.to_string());
+        out.extend(entries.iter().cloned());
+        out.push(BLOCK_END.to_string());
+    }
+    out.extend(after.iter().map(|line| line.to_string()));
+
+    if out.is_empty() {
+        return String::new();
+    }
+    out.join("\n") + "\n"
+}
+
+/// Write `entries` into `file` in `project_dir`
+///
+/// Returns whether the file changed. A file that would only hold an empty
+/// block is not created.
+pub fn sync<P: AsRef<Path>>(project_dir: P, file: IgnoreFile, entries: &[String]) -> Result<bool> {
+    let path = file.path(project_dir);
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    let updated = update_block(&existing, entries);
+    if updated == existing {
+        return Ok(false);
+    }
+    crate::vcs::track(&[&path], || Ok(fs::write(&path, &updated)?))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_block_keeps_user_lines() {
+        let entries = vec!["/Plugins/A/".to_string(), "/Plugins/B/".to_string()];
+        let first = update_block("Saved/\n", &entries);
+        assert_eq!(
+            first,
+            format!(
+                "Saved/\n\n{}\n/Plugins/A/\n/Plugins/B/\n{}\n",
+                BLOCK_START, BLOCK_END
+            )
+        );
+
+        // Lines after the block survive, and re-syncing is stable
+        let edited = format!("{}DerivedDataCache/\n", first);
+        let resynced = update_block(&edited, &entries[..1]);
+        assert_eq!(
+            resynced,
+            format!(
+                "Saved/\n\n{}\n/Plugins/A/\n{}\nDerivedDataCache/\n",
+                BLOCK_START, BLOCK_END
+            )
+        );
+        assert_eq!(update_block(&resynced, &entries[..1]), resynced);
+
+        assert_eq!(update_block("", &[]), "");
+        assert_eq!(
+            update_block("", &entries[..1]),
+            format!("{}\n/Plugins/A/\n{}\n", BLOCK_START, BLOCK_END)
+        );
+    }
+
+    #[test]
+    fn test_managed_entries() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let plugins = temp.path().join("Plugins");
+        for (folder, uplugin) in [("Awesome", "awesome"), ("FabTool", "fab-tool")] {
+            fs::create_dir_all(plugins.join(folder)).unwrap();
+            fs::write(
+                plugins.join(folder).join(format!("{}.uplugin", uplugin)),
+                "{}",
+            )
+            .unwrap();
+        }
+
+        let mut lockfile = Lockfile::new();
+        for name in ["awesome", "fab-tool", "missing"] {
+            lockfile.update_package(name.into(), "1.0.0".into(), "abc".into(), None);
+        }
+        lockfile.set_source("fab-tool", Some("fab+a1b2c3".to_string()));
+
+        assert_eq!(
+            managed_entries(temp.path(), &lockfile),
+            vec!["/Plugins/Awesome/".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_sync_writes_only_on_change() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let entries = vec!["/Plugins/A/".to_string()];
+
+        assert!(!sync(temp.path(), IgnoreFile::P4ignore, &[]).unwrap());
+        assert!(!IgnoreFile::P4ignore.path(temp.path()).exists());
+
+        assert!(sync(temp.path(), IgnoreFile::List, &entries).unwrap());
+        assert!(!sync(temp.path(), IgnoreFile::List, &entries).unwrap());
+        let written = fs::read_to_string(temp.path().join(".unrealpm-managed")).unwrap();
+        assert!(written.contains("/Plugins/A/"));
+    }
+}
@@ -20,18 +20,135 @@
 //! # }
 //! ```
 
-use crate::{Error, Result};
+use crate::events::{Event, EventSink};
+use crate::manifest::{ManifestFormat, MANIFEST_FILE_NAME, TOML_MANIFEST_FILE_NAME};
+use crate::{DryRun, Error, Manifest, Result, UPlugin};
 use flate2::read::GzDecoder;
 use sha2::{Digest, Sha256};
 use std::fs::{self, File};
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tar::Archive;
 
 /// Content-Addressable Storage (CAS) version for store layout
 const CAS_VERSION: &str = "v1";
 
+/// Oldest engine whose .uproject files honor `AdditionalPluginDirectories`
+pub const MIN_STORE_LAYOUT_ENGINE: (u32, u32) = (4, 25);
+
+static STORE_LAYOUT: AtomicBool = AtomicBool::new(false);
+
+/// Install packages for this run by leaving them in the store
+///
+/// With the store layout, [`install_package_cas`] returns the package's
+/// folder in the store instead of linking it into Plugins/, and the caller
+/// lists the store folders in the .uproject with
+/// [`crate::UProject::set_store_plugin_directories`]. Packages with local
+/// patches are still copied, since patching would change the shared store.
+pub fn set_store_layout(enabled: bool) {
+    STORE_LAYOUT.store(enabled, Ordering::Relaxed);
+}
+
+/// True if [`set_store_layout`] turned the store layout on for this run
+pub fn store_layout_enabled() -> bool {
+    STORE_LAYOUT.load(Ordering::Relaxed)
+}
+
+static SOURCE_FILES_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Plugin folder left out by [`set_source_files_only`]
+pub const CONTENT_DIR: &str = "Content";
+
+/// Install packages for this run without their Content/ folder
+///
+/// Compile-only CI jobs need the .uplugin and Source/, not the assets.
+/// [`install_package_cas`] then extracts the tarball itself, skipping
+/// Content/ entries, instead of going through the store, so the store only
+/// ever holds complete packages. Callers mark such installs as partial with
+/// [`crate::Lockfile::set_partial`].
+pub fn set_source_files_only(enabled: bool) {
+    SOURCE_FILES_ONLY.store(enabled, Ordering::Relaxed);
+}
+
+/// True if [`set_source_files_only`] turned partial installs on for this run
+pub fn source_files_only() -> bool {
+    SOURCE_FILES_ONLY.load(Ordering::Relaxed)
+}
+
+/// True for tarball entries under a plugin's Content/ folder, with or
+/// without the plugin's root folder
+fn is_content_path(path: &Path) -> bool {
+    path.components()
+        .filter(|component| matches!(component, std::path::Component::Normal(_)))
+        .take(2)
+        .any(|component| component.as_os_str() == CONTENT_DIR)
+}
+
+/// Extract a tarball without its Content/ folder to
+/// `Plugins/<package>.unrealpm_partial/`, which the caller removes
+fn extract_partial(tarball_path: &Path, plugins_dir: &Path, package_name: &str) -> Result<PathBuf> {
+    validate_tarball_entries(tarball_path)?;
+    let dest = plugins_dir.join(format!(
+        "{}.unrealpm_partial",
+        crate::scope::unscoped(package_name)
+    ));
+    if dest.exists() {
+        fs::remove_dir_all(&dest)?;
+    }
+    fs::create_dir_all(&dest)?;
+
+    let mut archive = Archive::new(GzDecoder::new(File::open(tarball_path)?));
+    let result = archive.entries().and_then(|entries| {
+        for entry in entries {
+            let mut entry = entry?;
+            if !is_content_path(&entry.path()?) {
+                entry.unpack_in(&dest)?;
+            }
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&dest);
+        return Err(e.into());
+    }
+    Ok(dest)
+}
+
+/// True if projects for `engine_version` can load plugins from the store
+///
+/// Unknown versions (custom engine builds) are assumed to be recent.
+pub fn supports_store_layout(engine_version: Option<&str>) -> bool {
+    engine_version
+        .and_then(crate::platform::EngineVersion::parse)
+        .is_none_or(|v| (v.major, v.minor) >= MIN_STORE_LAYOUT_ENGINE)
+}
+
+/// Folder a standalone plugin workspace keeps its dependencies in
+pub const STANDALONE_DEPS_DIR: &str = "Deps";
+
+/// True if `dir` is a plugin developed on its own: it has a .uplugin but no
+/// .uproject
+pub fn is_standalone_plugin<P: AsRef<Path>>(dir: P) -> bool {
+    let dir = dir.as_ref();
+    UPlugin::find(dir).is_ok() && crate::UProject::find_all(dir).is_ok_and(|p| p.is_empty())
+}
+
+/// Folder packages for the project in `project_dir` are installed into
+///
+/// `Plugins/` for a game project. A standalone plugin workspace is itself
+/// the plugin, so its dependencies go to `Deps/Plugins/`, which a host
+/// project picks up through its `AdditionalPluginDirectories`.
+pub fn plugins_dir<P: AsRef<Path>>(project_dir: P) -> PathBuf {
+    let project_dir = project_dir.as_ref();
+    if is_standalone_plugin(project_dir) {
+        project_dir.join(STANDALONE_DEPS_DIR).join("Plugins")
+    } else {
+        project_dir.join("Plugins")
+    }
+}
+
 /// Get the global UnrealPM store directory
 ///
 /// Returns `~/.unrealpm/store/v1/packages/` and creates it if it doesn't exist.
@@ -70,7 +187,7 @@ pub fn is_package_in_store(checksum: &str) -> Result<bool> {
 ///
 /// * `tarball_path` - Path to the .tar.gz package file
 /// * `checksum` - SHA256 hash of the tarball (used as content address)
-/// * `progress` - Optional callback for progress updates
+/// * `progress` - Optional sink for progress [`Event`]s
 ///
 /// # Returns
 ///
@@ -78,7 +195,7 @@ pub fn is_package_in_store(checksum: &str) -> Result<bool> {
 pub fn store_package<P: AsRef<Path>>(
     tarball_path: P,
     checksum: &str,
-    progress: Option<ProgressCallback>,
+    progress: Option<EventSink>,
 ) -> Result<PathBuf> {
     let tarball_path = tarball_path.as_ref();
     let store_path = get_package_store_path(checksum)?;
@@ -86,15 +203,24 @@ pub fn store_package<P: AsRef<Path>>(
     // If already in store, return early
     if store_path.exists() {
         if let Some(ref cb) = progress {
-            cb("Package already in store", 100, 100);
+            cb(&Event::extract("Package already in store", 100, 100));
         }
         return Ok(store_path);
     }
 
     if let Some(ref cb) = progress {
-        cb("Extracting to global store...", 0, 100);
+        cb(&Event::extract("Extracting to global store...", 0, 100));
     }
 
+    validate_tarball_entries(tarball_path)?;
+
+    let store_dir = get_store_dir()?;
+    crate::disk::ensure_space(&[crate::disk::SpaceRequirement::new(
+        &store_dir,
+        crate::disk::tarball_unpacked_size(tarball_path)?,
+        "extracting to the package store",
+    )])?;
+
     // Create a temporary directory for extraction (in case of failure)
     // Use a separate temp directory name to avoid path confusion
     let store_parent = store_path
@@ -136,7 +262,7 @@ pub fn store_package<P: AsRef<Path>>(
     })?;
 
     if let Some(ref cb) = progress {
-        cb("Stored in global cache", 100, 100);
+        cb(&Event::extract("Stored in global cache", 100, 100));
     }
 
     Ok(store_path)
@@ -151,14 +277,14 @@ pub fn store_package<P: AsRef<Path>>(
 ///
 /// * `store_path` - Path to the package in the global store
 /// * `target_path` - Destination path in the project's Plugins/ directory
-/// * `progress` - Optional callback for progress updates
+/// * `progress` - Optional sink for progress [`Event`]s
 pub fn link_or_copy_from_store(
     store_path: &Path,
     target_path: &Path,
-    progress: Option<ProgressCallback>,
+    progress: Option<EventSink>,
 ) -> Result<()> {
     if let Some(ref cb) = progress {
-        cb("Linking from store...", 0, 100);
+        cb(&Event::extract("Linking from store...", 0, 100));
     }
 
     // Remove existing target if it exists
@@ -170,18 +296,27 @@ pub fn link_or_copy_from_store(
     match link_directory_recursive(store_path, target_path) {
         Ok(()) => {
             if let Some(ref cb) = progress {
-                cb("Linked from store", 100, 100);
+                cb(&Event::extract("Linked from store", 100, 100));
             }
             Ok(())
         }
         Err(_) => {
-            // Fall back to copying
+            // Fall back to copying. Files linked before the failure share
+            // their contents with the store, so they go first; copying over
+            // them would write into the store.
+            if target_path.exists() {
+                fs::remove_dir_all(target_path)?;
+            }
             if let Some(ref cb) = progress {
-                cb("Copying from store (hard links not supported)...", 50, 100);
+                cb(&Event::extract(
+                    "Copying from store (hard links not supported)...",
+                    50,
+                    100,
+                ));
             }
             copy_directory_recursive(store_path, target_path)?;
             if let Some(ref cb) = progress {
-                cb("Copied from store", 100, 100);
+                cb(&Event::extract("Copied from store", 100, 100));
             }
             Ok(())
         }
@@ -250,7 +385,7 @@ fn copy_directory_recursive(src: &Path, dst: &Path) -> Result<()> {
 /// * `target_dir` - Project root directory
 /// * `package_name` - Name of the package being installed
 /// * `checksum` - SHA256 hash of the tarball
-/// * `progress` - Optional callback for progress updates
+/// * `progress` - Optional sink for progress [`Event`]s
 ///
 /// # Returns
 ///
@@ -260,11 +395,44 @@ pub fn install_package_cas<P: AsRef<Path>>(
     target_dir: P,
     package_name: &str,
     checksum: &str,
-    progress: Option<ProgressCallback>,
+    progress: Option<EventSink>,
 ) -> Result<PathBuf> {
-    let tarball_path = tarball_path.as_ref();
-    let target_dir = target_dir.as_ref();
+    let dirs = plugin_dirs(target_dir.as_ref(), package_name);
+    let paths: Vec<&Path> = dirs.iter().map(PathBuf::as_path).collect();
+    let result = crate::vcs::track(&paths, || {
+        link_package(
+            tarball_path.as_ref(),
+            target_dir.as_ref(),
+            package_name,
+            checksum,
+            progress.clone(),
+        )
+    });
+    report_outcome(progress.as_ref(), package_name, &result);
+    result
+}
+
+/// Send [`Event::Completed`] or [`Event::Failed`] for an install
+fn report_outcome(progress: Option<&EventSink>, package_name: &str, result: &Result<PathBuf>) {
+    let event = match result {
+        Ok(_) => Event::Completed {
+            package: package_name.to_string(),
+        },
+        Err(e) => Event::Failed {
+            package: package_name.to_string(),
+            error: e.to_string(),
+        },
+    };
+    crate::events::emit(progress, event);
+}
 
+fn link_package(
+    tarball_path: &Path,
+    target_dir: &Path,
+    package_name: &str,
+    checksum: &str,
+    progress: Option<EventSink>,
+) -> Result<PathBuf> {
     if !tarball_path.exists() {
         return Err(Error::Other(format!(
             "Package tarball not found: {}",
@@ -272,67 +440,122 @@ pub fn install_package_cas<P: AsRef<Path>>(
         )));
     }
 
-    // Create Plugins directory if it doesn't exist
-    let plugins_dir = target_dir.join("Plugins");
-    fs::create_dir_all(&plugins_dir)?;
+    crate::store::hold_for_install(target_dir)?;
+
+    let plugins_dir = plugins_dir(target_dir);
+    let store_layout = store_layout_enabled()
+        && !Manifest::load(target_dir).is_ok_and(|m| m.patches.contains_key(package_name));
 
-    // Store the package in the global store (if not already there)
-    let store_path = store_package(tarball_path, checksum, progress.clone())?;
+    if !store_layout {
+        // Create Plugins directory if it doesn't exist
+        fs::create_dir_all(&plugins_dir)?;
+
+        // Hard links can't cross volumes, so linking falls back to a full copy
+        if !crate::disk::same_volume(get_store_dir()?, &plugins_dir) {
+            crate::disk::ensure_space(&[crate::disk::SpaceRequirement::new(
+                &plugins_dir,
+                crate::disk::tarball_unpacked_size(tarball_path)?,
+                "copying from the package store",
+            )])?;
+        }
+    }
+
+    // Store the package in the global store (if not already there); partial
+    // installs stay out of it, since it holds complete packages
+    let partial_dir = if source_files_only() {
+        fs::create_dir_all(&plugins_dir)?;
+        Some(extract_partial(tarball_path, &plugins_dir, package_name)?)
+    } else {
+        None
+    };
+    let store_path = match &partial_dir {
+        Some(dir) => dir.clone(),
+        None => store_package(tarball_path, checksum, progress.clone())?,
+    };
 
     // Find the plugin directory within the store
     // The tarball may have a root folder with a different name
     let plugin_store_path =
         find_extracted_plugin_dir(&store_path, package_name).unwrap_or_else(|_| store_path.clone());
 
+    // Descriptor problems would otherwise only surface when the editor loads
+    // the plugin; checking the store copy keeps a strict failure from
+    // touching the project. Partial copies are skipped, since some checks
+    // look in Content/.
+    let uplugin = UPlugin::find(&plugin_store_path)
+        .and_then(UPlugin::load)
+        .ok()
+        .filter(|_| partial_dir.is_none());
+    if let Some(uplugin) = uplugin {
+        for problem in uplugin.validate(&plugin_store_path) {
+            crate::strict::warn(format!("{}: {}", package_name, problem))?;
+        }
+    }
+
+    // The store copy is used in place; a copy left in Plugins/ would shadow it
+    if store_layout {
+        let copy =
+            find_plugin_dir(&plugins_dir, package_name).filter(|dir| !crate::link::is_link(dir));
+        if let Some(copy) = copy {
+            fs::remove_dir_all(copy)?;
+        }
+        if let Some(ref cb) = progress {
+            cb(&Event::extract(
+                format!("Installed {}", package_name),
+                100,
+                100,
+            ));
+        }
+        return Ok(plugin_store_path);
+    }
+
     // Before linking, handle existing installation
-    let installed_path = plugins_dir.join(package_name);
-    let uplugin_name = format!("{}.uplugin", package_name);
-    let mut existing_plugin_dir: Option<PathBuf> = None;
+    let installed_path = plugins_dir.join(crate::scope::unscoped(package_name));
     let mut backup_dir: Option<PathBuf> = None;
 
     // Search for existing plugin by .uplugin file
-    if let Ok(entries) = fs::read_dir(&plugins_dir) {
-        'outer: for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                if let Ok(dir_entries) = fs::read_dir(&path) {
-                    for dir_entry in dir_entries.flatten() {
-                        let file_path = dir_entry.path();
-                        if file_path.is_file() {
-                            if let Some(file_name) = file_path.file_name() {
-                                if file_name
-                                    .to_string_lossy()
-                                    .eq_ignore_ascii_case(&uplugin_name)
-                                {
-                                    existing_plugin_dir = Some(path);
-                                    break 'outer;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let existing_plugin_dir = find_plugin_dir(&plugins_dir, package_name);
 
     // Backup existing installation
     if let Some(ref existing_dir) = existing_plugin_dir {
-        let backup_path = plugins_dir.join(format!("{}.unrealpm_backup", package_name));
+        let backup_path = plugins_dir.join(format!(
+            "{}.unrealpm_backup",
+            crate::scope::unscoped(package_name)
+        ));
         if backup_path.exists() {
             let _ = fs::remove_dir_all(&backup_path);
         }
 
         if let Some(ref cb) = progress {
-            cb(&format!("Backing up existing {}...", package_name), 0, 100);
+            cb(&Event::extract(
+                format!("Backing up existing {}...", package_name),
+                0,
+                100,
+            ));
         }
 
         fs::rename(existing_dir, &backup_path)?;
         backup_dir = Some(backup_path);
     }
 
-    // Link or copy from store to project
-    let link_result =
-        link_or_copy_from_store(&plugin_store_path, &installed_path, progress.clone());
+    // Link or copy from store to project, unless this version was saved
+    // when it was last replaced
+    let link_result = if partial_dir.is_some() {
+        fs::rename(&plugin_store_path, &installed_path).map_err(Error::from)
+    } else if crate::saved_plugins::restore(
+        target_dir,
+        package_name,
+        checksum,
+        &plugin_store_path,
+        &installed_path,
+    ) {
+        Ok(())
+    } else {
+        link_or_copy_from_store(&plugin_store_path, &installed_path, progress.clone())
+    };
+    if let Some(dir) = &partial_dir {
+        let _ = fs::remove_dir_all(dir);
+    }
 
     if let Err(e) = link_result {
         // Restore backup on failure
@@ -344,13 +567,20 @@ pub fn install_package_cas<P: AsRef<Path>>(
         return Err(e);
     }
 
-    // Clean up backup on success
+    // Keep the replaced version for a quick switch back, or clean it up
     if let Some(ref backup_path) = backup_dir {
-        let _ = fs::remove_dir_all(backup_path);
+        if !crate::saved_plugins::stash(target_dir, package_name, backup_path, checksum) {
+            let _ = fs::remove_dir_all(backup_path);
+        }
     }
+    crate::saved_plugins::record_installed(target_dir, package_name, checksum);
 
     if let Some(ref cb) = progress {
-        cb(&format!("Installed {}", package_name), 100, 100);
+        cb(&Event::extract(
+            format!("Installed {}", package_name),
+            100,
+            100,
+        ));
     }
 
     Ok(installed_path)
@@ -366,6 +596,9 @@ pub fn get_store_stats() -> Result<StoreStats> {
             if entry.path().is_dir() {
                 stats.package_count += 1;
                 stats.total_size += calculate_dir_size(&entry.path()).unwrap_or(0);
+                if let Some(saved) = hard_link_savings(&entry.path()) {
+                    *stats.saved_size.get_or_insert(0) += saved;
+                }
             }
         }
     }
@@ -380,6 +613,9 @@ pub struct StoreStats {
     pub package_count: usize,
     /// Total size of the store in bytes
     pub total_size: u64,
+    /// Bytes saved by projects hard linking store files instead of holding
+    /// their own copies; `None` where link counts aren't available
+    pub saved_size: Option<u64>,
 }
 
 /// Calculate the total size of a directory recursively
@@ -400,14 +636,186 @@ fn calculate_dir_size(path: &Path) -> Result<u64> {
     Ok(total)
 }
 
-/// Progress callback for installation/verification operations
+/// Bytes saved by hard links to the files under `path`
+///
+/// A store file linked into `n` projects has `n + 1` links and would
+/// otherwise take `n` copies, so it saves `n - 1` times its size.
+#[cfg(unix)]
+fn hard_link_savings(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut saved = 0;
+    for entry in fs::read_dir(path).ok()?.flatten() {
+        let metadata = entry.metadata().ok()?;
+        if metadata.is_dir() {
+            saved += hard_link_savings(&entry.path())?;
+        } else {
+            saved += metadata.len() * metadata.nlink().saturating_sub(2);
+        }
+    }
+    Some(saved)
+}
+
+#[cfg(not(unix))]
+fn hard_link_savings(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Message-based progress callback, the form progress took before
+/// [`crate::events`]
 ///
 /// Called with:
 /// - `message`: Description of current operation (e.g., "Extracting package...")
 /// - `current`: Current progress (0-100 for percentage, or bytes processed)
 /// - `total`: Total work (100 for percentage, or total bytes)
+///
+/// Pass one where an [`EventSink`] is expected with
+/// [`crate::events::from_progress_callback`].
 pub type ProgressCallback = Arc<dyn Fn(&str, u64, u64) + Send + Sync>;
 
+/// Find the folder in `plugins_dir` that holds a plugin, if any
+///
+/// The `.uplugin` filename is the canonical identifier for a plugin, so the
+/// folder may be named differently; a folder named after the package counts
+/// too. Matching is case-insensitive, and a scoped package is found by its
+/// name without the scope.
+pub fn find_plugin_dir(plugins_dir: &Path, package_name: &str) -> Option<PathBuf> {
+    let package_name = crate::scope::unscoped(package_name);
+    let uplugin_name = format!("{}.uplugin", package_name);
+    let entries = fs::read_dir(plugins_dir).ok()?;
+    let dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    dirs.iter()
+        .find(|dir| {
+            fs::read_dir(dir).is_ok_and(|files| {
+                files.flatten().any(|file| {
+                    file.path().is_file()
+                        && file
+                            .file_name()
+                            .to_string_lossy()
+                            .eq_ignore_ascii_case(&uplugin_name)
+                })
+            })
+        })
+        .or_else(|| {
+            dirs.iter().find(|dir| {
+                dir.file_name()
+                    .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case(package_name))
+            })
+        })
+        .cloned()
+}
+
+/// Folder a package is installed in: its folder in Plugins/, or its store
+/// folder when the project's .uproject lists it (store layout)
+pub fn installed_plugin_dir(
+    project_dir: &Path,
+    package_name: &str,
+    checksum: &str,
+) -> Option<PathBuf> {
+    find_plugin_dir(&plugins_dir(project_dir), package_name).or_else(|| {
+        let store_path = get_package_store_path(checksum).ok()?;
+        let uproject = Manifest::load(project_dir)
+            .and_then(|manifest| manifest.uproject_path(project_dir))
+            .or_else(|_| crate::UProject::find(project_dir))
+            .and_then(crate::UProject::load)
+            .ok()?;
+        uproject
+            .additional_plugin_directories
+            .iter()
+            .any(|dir| Path::new(dir) == store_path)
+            .then(|| find_extracted_plugin_dir(&store_path, package_name).unwrap_or(store_path))
+    })
+}
+
+/// Remove an installed package's folder from `{target_dir}/Plugins/`
+///
+/// Used before installing a different version, so files the new version
+/// dropped don't linger next to it, and by uninstall. Also clears a backup
+/// left behind by an interrupted install. Returns the folder that was (or,
+/// in a dry run, would be) removed, if any.
+pub fn remove_installed_package<P: AsRef<Path>>(
+    target_dir: P,
+    package_name: &str,
+    dry_run: DryRun,
+) -> Result<Option<PathBuf>> {
+    let plugins_dir = plugins_dir(target_dir);
+    let backup_path = plugins_dir.join(format!(
+        "{}.unrealpm_backup",
+        crate::scope::unscoped(package_name)
+    ));
+    if backup_path.exists() {
+        dry_run.apply(|| Ok(fs::remove_dir_all(&backup_path)?))?;
+    }
+
+    let Some(plugin_dir) = find_plugin_dir(&plugins_dir, package_name) else {
+        return Ok(None);
+    };
+    if dry_run.is_enabled() {
+        return Ok(Some(plugin_dir));
+    }
+    crate::vcs::track(&[&plugin_dir], || {
+        fs::remove_dir_all(&plugin_dir).map_err(|e| {
+            Error::Other(format!(
+                "Failed to remove previous version of '{}' at '{}': {}",
+                package_name,
+                plugin_dir.display(),
+                e
+            ))
+        })
+    })?;
+    Ok(Some(plugin_dir))
+}
+
+/// Plugin folders in a project's Plugins/, split by who put them there
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstalledPlugins {
+    /// `(package, folder)` for each package unrealpm.lock records, sorted by
+    /// package name
+    pub managed: Vec<(String, PathBuf)>,
+    /// Folders no locked package claims: hand-placed plugins, Fab plugins
+    /// copied into the project, and anything else unrealpm didn't install
+    pub unmanaged: Vec<PathBuf>,
+}
+
+/// Sort the folders in `{project_dir}/Plugins/` into ones unrealpm installed
+/// (per `lockfile`) and ones it didn't
+///
+/// Fab/Marketplace packages count as unmanaged even when locked, since the
+/// launcher owns them. Locked packages with no folder are left out.
+pub fn installed_plugins<P: AsRef<Path>>(
+    project_dir: P,
+    lockfile: &crate::Lockfile,
+) -> InstalledPlugins {
+    let plugins_dir = plugins_dir(project_dir);
+    let mut managed: Vec<(String, PathBuf)> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, locked)| !locked.is_externally_managed())
+        .filter_map(|(name, _)| find_plugin_dir(&plugins_dir, name).map(|dir| (name.clone(), dir)))
+        .collect();
+    managed.sort();
+
+    let mut unmanaged: Vec<PathBuf> = fs::read_dir(&plugins_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .filter(|path| !managed.iter().any(|(_, dir)| dir == path))
+                .filter(|path| path.extension().is_none_or(|ext| ext != "unrealpm_backup"))
+                .collect()
+        })
+        .unwrap_or_default();
+    unmanaged.sort();
+
+    InstalledPlugins { managed, unmanaged }
+}
+
 /// Install a package from a tarball to the target directory
 ///
 /// Extracts the package tarball to `{target_dir}/Plugins/{package_name}/`.
@@ -421,7 +829,7 @@ pub type ProgressCallback = Arc<dyn Fn(&str, u64, u64) + Send + Sync>;
 /// * `tarball_path` - Path to the .tar.gz package file
 /// * `target_dir` - Project root directory
 /// * `package_name` - Name of the package being installed
-/// * `progress` - Optional callback for progress updates
+/// * `progress` - Optional sink for progress [`Event`]s
 ///
 /// # Returns
 ///
@@ -430,11 +838,28 @@ pub fn install_package<P: AsRef<Path>>(
     tarball_path: P,
     target_dir: P,
     package_name: &str,
-    progress: Option<ProgressCallback>,
+    progress: Option<EventSink>,
 ) -> Result<PathBuf> {
-    let tarball_path = tarball_path.as_ref();
-    let target_dir = target_dir.as_ref();
+    let dirs = plugin_dirs(target_dir.as_ref(), package_name);
+    let paths: Vec<&Path> = dirs.iter().map(PathBuf::as_path).collect();
+    let result = crate::vcs::track(&paths, || {
+        extract_package(
+            tarball_path.as_ref(),
+            target_dir.as_ref(),
+            package_name,
+            progress.clone(),
+        )
+    });
+    report_outcome(progress.as_ref(), package_name, &result);
+    result
+}
 
+fn extract_package(
+    tarball_path: &Path,
+    target_dir: &Path,
+    package_name: &str,
+    progress: Option<EventSink>,
+) -> Result<PathBuf> {
     if !tarball_path.exists() {
         return Err(Error::Other(format!(
             "Package tarball not found: {}",
@@ -442,44 +867,27 @@ pub fn install_package<P: AsRef<Path>>(
         )));
     }
 
+    validate_tarball_entries(tarball_path)?;
+
     // Create Plugins directory if it doesn't exist
-    let plugins_dir = target_dir.join("Plugins");
+    let plugins_dir = plugins_dir(target_dir);
+    crate::disk::ensure_space(&[crate::disk::SpaceRequirement::new(
+        &plugins_dir,
+        crate::disk::tarball_unpacked_size(tarball_path)?,
+        "extracting the package",
+    )])?;
     fs::create_dir_all(&plugins_dir)?;
 
     // Before extracting, check for existing installation by searching for the .uplugin file
-    // The .uplugin filename is the canonical identifier for a plugin
-    let uplugin_name = format!("{}.uplugin", package_name);
-    let mut existing_plugin_dir: Option<PathBuf> = None;
+    let existing_plugin_dir = find_plugin_dir(&plugins_dir, package_name);
     let mut backup_dir: Option<PathBuf> = None;
 
-    if let Ok(entries) = fs::read_dir(&plugins_dir) {
-        'outer: for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                // Check if this directory contains the matching .uplugin file (case-insensitive)
-                if let Ok(dir_entries) = fs::read_dir(&path) {
-                    for dir_entry in dir_entries.flatten() {
-                        let file_path = dir_entry.path();
-                        if file_path.is_file() {
-                            if let Some(file_name) = file_path.file_name() {
-                                if file_name
-                                    .to_string_lossy()
-                                    .eq_ignore_ascii_case(&uplugin_name)
-                                {
-                                    existing_plugin_dir = Some(path);
-                                    break 'outer;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
     // If existing installation found, back it up before installing
     if let Some(ref existing_dir) = existing_plugin_dir {
-        let backup_path = plugins_dir.join(format!("{}.unrealpm_backup", package_name));
+        let backup_path = plugins_dir.join(format!(
+            "{}.unrealpm_backup",
+            crate::scope::unscoped(package_name)
+        ));
 
         // Remove any stale backup from a previous failed install
         if backup_path.exists() {
@@ -487,7 +895,11 @@ pub fn install_package<P: AsRef<Path>>(
         }
 
         if let Some(ref cb) = progress {
-            cb(&format!("Backing up existing {}...", package_name), 0, 100);
+            cb(&Event::extract(
+                format!("Backing up existing {}...", package_name),
+                0,
+                100,
+            ));
         }
 
         fs::rename(existing_dir, &backup_path).map_err(|e| {
@@ -504,7 +916,11 @@ pub fn install_package<P: AsRef<Path>>(
 
     // Report extraction start
     if let Some(ref cb) = progress {
-        cb(&format!("Extracting {}...", package_name), 0, 100);
+        cb(&Event::extract(
+            format!("Extracting {}...", package_name),
+            0,
+            100,
+        ));
     }
 
     // Helper closure to restore backup on failure
@@ -536,10 +952,14 @@ pub fn install_package<P: AsRef<Path>>(
 
     // Report extraction complete
     if let Some(ref cb) = progress {
-        cb(&format!("Extracted {}", package_name), 100, 100);
+        cb(&Event::extract(
+            format!("Extracted {}", package_name),
+            100,
+            100,
+        ));
     }
 
-    let installed_path = plugins_dir.join(package_name);
+    let installed_path = plugins_dir.join(crate::scope::unscoped(package_name));
 
     // Check if the expected path exists
     if installed_path.exists() {
@@ -597,11 +1017,21 @@ pub fn install_package<P: AsRef<Path>>(
     }
 }
 
+/// Folders an install of `package_name` replaces or creates: the one named
+/// after the package, and the existing installation if it lives elsewhere
+fn plugin_dirs(target_dir: &Path, package_name: &str) -> Vec<PathBuf> {
+    let plugins_dir = plugins_dir(target_dir);
+    let mut dirs = vec![plugins_dir.join(crate::scope::unscoped(package_name))];
+    dirs.extend(find_plugin_dir(&plugins_dir, package_name));
+    dirs
+}
+
 /// Find the extracted plugin directory by searching for .uplugin files
 ///
 /// This handles cases where the tarball's root folder name doesn't match
 /// the package name (e.g., tarball contains `chroma-sense/` but package is `ChromaSense`)
 fn find_extracted_plugin_dir(plugins_dir: &Path, package_name: &str) -> Result<PathBuf> {
+    let package_name = crate::scope::unscoped(package_name);
     // First, try case-insensitive match for the package name
     if let Ok(entries) = fs::read_dir(plugins_dir) {
         for entry in entries.flatten() {
@@ -660,11 +1090,11 @@ fn find_extracted_plugin_dir(plugins_dir: &Path, package_name: &str) -> Result<P
 ///
 /// * `tarball_path` - Path to the .tar.gz package file
 /// * `expected_checksum` - Expected SHA256 checksum (hex string)
-/// * `progress` - Optional callback for progress updates
+/// * `progress` - Optional sink for progress [`Event`]s
 pub fn verify_checksum<P: AsRef<Path>>(
     tarball_path: P,
     expected_checksum: &str,
-    progress: Option<ProgressCallback>,
+    progress: Option<EventSink>,
 ) -> Result<()> {
     let tarball_path = tarball_path.as_ref();
 
@@ -672,14 +1102,17 @@ pub fn verify_checksum<P: AsRef<Path>>(
         return Err(Error::Other("Empty checksum".to_string()));
     }
 
+    // Get file size for progress reporting
+    let file_size = fs::metadata(tarball_path)?.len();
+
     // Report verification start
     if let Some(ref cb) = progress {
-        cb("Verifying checksum...", 0, 100);
+        cb(&Event::VerifyProgress {
+            bytes: 0,
+            total: file_size,
+        });
     }
 
-    // Get file size for progress reporting
-    let file_size = fs::metadata(tarball_path)?.len();
-
     // Read the tarball file
     let mut file = File::open(tarball_path)?;
     let mut hasher = Sha256::new();
@@ -697,7 +1130,10 @@ pub fn verify_checksum<P: AsRef<Path>>(
 
         // Report progress
         if let Some(ref cb) = progress {
-            cb("Verifying checksum...", bytes_processed, file_size);
+            cb(&Event::VerifyProgress {
+                bytes: bytes_processed,
+                total: file_size,
+            });
         }
     }
 
@@ -707,17 +1143,267 @@ pub fn verify_checksum<P: AsRef<Path>>(
     // Compare with expected checksum (case-insensitive)
     if computed_hash.eq_ignore_ascii_case(expected_checksum) {
         if let Some(ref cb) = progress {
-            cb("Checksum verified", file_size, file_size);
+            cb(&Event::VerifyProgress {
+                bytes: file_size,
+                total: file_size,
+            });
         }
         Ok(())
     } else {
-        Err(Error::Other(format!(
-            "Checksum mismatch!\nExpected: {}\nComputed: {}",
-            expected_checksum, computed_hash
-        )))
+        Err(Error::ChecksumMismatch {
+            expected: expected_checksum.to_string(),
+            actual: computed_hash,
+        })
     }
 }
 
+/// Calculate the SHA256 checksum of a file as a lowercase hex string
+pub fn calculate_checksum<P: AsRef<Path>>(path: P) -> Result<String> {
+    let mut file = File::open(path.as_ref())?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Check that every entry in a package tarball stays inside the extraction directory
+///
+/// Rejects absolute paths, `..` components, and links pointing outside the
+/// archive (zip-slip). Called before any extraction.
+pub fn validate_tarball_entries<P: AsRef<Path>>(tarball_path: P) -> Result<()> {
+    let tar_gz = File::open(tarball_path.as_ref())?;
+    let mut archive = Archive::new(GzDecoder::new(tar_gz));
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let path = entry.path()?.into_owned();
+        if !is_contained_path(&path) {
+            return Err(Error::Other(format!(
+                "Unsafe path in package tarball: {}",
+                path.display()
+            )));
+        }
+
+        if let Some(link) = entry.link_name()? {
+            // Hard link targets are archive paths; symlinks resolve from the entry's directory
+            let resolved = if entry.header().entry_type().is_hard_link() {
+                link.to_path_buf()
+            } else {
+                path.parent().unwrap_or(Path::new("")).join(&link)
+            };
+            if !is_contained_path(&normalize_relative(&resolved)) {
+                return Err(Error::Other(format!(
+                    "Unsafe link in package tarball: {} -> {}",
+                    path.display(),
+                    link.display()
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// True if a relative archive path cannot escape its extraction root
+fn is_contained_path(path: &Path) -> bool {
+    use std::path::Component;
+
+    let mut depth: i32 = 0;
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return false,
+            Component::ParentDir => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+        }
+    }
+    true
+}
+
+/// Collapse `..` components lexically, keeping any that would escape the root
+fn normalize_relative(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let mut parts: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir if matches!(parts.last(), Some(Component::Normal(_))) => {
+                parts.pop();
+            }
+            Component::CurDir => {}
+            other => parts.push(other),
+        }
+    }
+    parts.iter().collect()
+}
+
+/// Package metadata read from a tarball without extracting it
+#[derive(Debug, Clone)]
+pub struct TarballMetadata {
+    /// Plugin name (the `.uplugin` file stem)
+    pub plugin_name: String,
+
+    /// Parsed `.uplugin` descriptor
+    pub uplugin: UPlugin,
+
+    /// Parsed `unrealpm.json` or `unrealpm.toml`, if the package ships one
+    /// next to the descriptor
+    pub manifest: Option<Manifest>,
+}
+
+/// Read the `.uplugin` and manifest from a package tarball without extracting it
+///
+/// Only these two files are read into memory; every other entry is skipped
+/// while streaming, and reading stops as soon as both have been found. Both
+/// are looked for at the archive root or one directory deep.
+pub fn read_tarball_metadata<P: AsRef<Path>>(tarball_path: P) -> Result<TarballMetadata> {
+    let tarball_path = tarball_path.as_ref();
+    let tar_gz = File::open(tarball_path)?;
+    let mut archive = Archive::new(GzDecoder::new(tar_gz));
+
+    let mut uplugin: Option<(String, UPlugin)> = None;
+    let mut manifest: Option<Manifest> = None;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        if path.components().count() > 2 {
+            continue;
+        }
+
+        let is_uplugin = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("uplugin"));
+        let manifest_format = match path.file_name().and_then(|n| n.to_str()) {
+            Some(MANIFEST_FILE_NAME) => Some(ManifestFormat::Json),
+            Some(TOML_MANIFEST_FILE_NAME) => Some(ManifestFormat::Toml),
+            _ => None,
+        };
+
+        if is_uplugin && uplugin.is_none() {
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .ok_or_else(|| Error::Other("Invalid .uplugin file name".to_string()))?;
+
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents)?;
+            let parsed: UPlugin = serde_json::from_str(&contents).map_err(|e| {
+                Error::InvalidManifest(format!("Failed to parse {}: {}", path.display(), e))
+            })?;
+            uplugin = Some((name, parsed));
+        } else if let (Some(format), None) = (manifest_format, &manifest) {
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents)?;
+            let parsed = Manifest::parse(&contents, format).map_err(|e| {
+                Error::InvalidManifest(format!("Failed to parse {}: {}", path.display(), e))
+            })?;
+            manifest = Some(parsed);
+        }
+
+        if uplugin.is_some() && manifest.is_some() {
+            break;
+        }
+    }
+
+    let (plugin_name, uplugin) = uplugin.ok_or_else(|| {
+        Error::Other(format!(
+            "No .uplugin file found in {}",
+            tarball_path.display()
+        ))
+    })?;
+
+    Ok(TarballMetadata {
+        plugin_name,
+        uplugin,
+        manifest,
+    })
+}
+
+/// Read the `.uplugin` descriptor from a package tarball without extracting it
+///
+/// Looks for a `.uplugin` file at the archive root or one directory deep.
+/// Returns the plugin name (the file stem) and the parsed descriptor.
+pub fn read_tarball_uplugin<P: AsRef<Path>>(tarball_path: P) -> Result<(String, UPlugin)> {
+    let metadata = read_tarball_metadata(tarball_path)?;
+    Ok((metadata.plugin_name, metadata.uplugin))
+}
+
+/// A file stored in a package tarball
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarballEntry {
+    /// Path inside the archive
+    pub path: PathBuf,
+
+    /// Uncompressed size in bytes
+    pub size: u64,
+}
+
+/// List the files in a package tarball without extracting it
+///
+/// Only the entry headers are read; file contents are skipped while
+/// streaming. Directory entries are left out.
+pub fn list_tarball_entries<P: AsRef<Path>>(tarball_path: P) -> Result<Vec<TarballEntry>> {
+    let tar_gz = File::open(tarball_path.as_ref())?;
+    let mut archive = Archive::new(GzDecoder::new(tar_gz));
+
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        entries.push(TarballEntry {
+            path: entry.path()?.into_owned(),
+            size: entry.size(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Read a package's README from its tarball without extracting it
+///
+/// Matches `README`, `README.md`, `README.txt` and so on, in any case, at
+/// the archive root or one directory deep. Returns `None` if there is none.
+pub fn read_tarball_readme<P: AsRef<Path>>(tarball_path: P) -> Result<Option<String>> {
+    read_tarball_text(tarball_path.as_ref(), &["readme"])
+}
+
+/// Read a package's changelog from its tarball without extracting it
+///
+/// Matches the stems in [`crate::changelog::CHANGELOG_STEMS`] the same way
+/// [`read_tarball_readme`] matches README.
+pub fn read_tarball_changelog<P: AsRef<Path>>(tarball_path: P) -> Result<Option<String>> {
+    read_tarball_text(tarball_path.as_ref(), crate::changelog::CHANGELOG_STEMS)
+}
+
+/// First file at the archive root or one directory deep whose stem is one of
+/// `stems` (ignoring case)
+fn read_tarball_text(tarball_path: &Path, stems: &[&str]) -> Result<Option<String>> {
+    let tar_gz = File::open(tarball_path)?;
+    let mut archive = Archive::new(GzDecoder::new(tar_gz));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let matches = path
+            .file_stem()
+            .is_some_and(|stem| stems.iter().any(|wanted| stem.eq_ignore_ascii_case(wanted)));
+        if path.components().count() > 2 || !matches || !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents)?;
+        return Ok(Some(contents));
+    }
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -739,6 +1425,7 @@ mod tests {
         let uplugin_content = format!(
             r#"{{
     "FileVersion": 3,
+    "Version": 1,
     "VersionName": "1.0.0",
     "FriendlyName": "{}",
     "Description": "Test plugin"
@@ -878,7 +1565,8 @@ mod tests {
         let progress_count = Arc::new(AtomicU32::new(0));
         let progress_count_clone = progress_count.clone();
 
-        let progress: ProgressCallback = Arc::new(move |_msg, _current, _total| {
+        let progress: EventSink = Arc::new(move |event| {
+            assert!(matches!(event, Event::VerifyProgress { .. }));
             progress_count_clone.fetch_add(1, Ordering::SeqCst);
         });
 
@@ -894,6 +1582,157 @@ mod tests {
     // install_package tests
     // ============================================================================
 
+    #[test]
+    fn test_plugins_dir_standalone() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        assert_eq!(plugins_dir(dir), dir.join("Plugins"));
+
+        // A plugin without a project installs its dependencies under Deps/
+        fs::write(dir.join("Mine.uplugin"), "{}").unwrap();
+        assert!(is_standalone_plugin(dir));
+        assert_eq!(plugins_dir(dir), dir.join("Deps").join("Plugins"));
+
+        // A project that ships a plugin next to its .uproject is still a project
+        fs::write(dir.join("Game.uproject"), "{}").unwrap();
+        assert!(!is_standalone_plugin(dir));
+        assert_eq!(plugins_dir(dir), dir.join("Plugins"));
+    }
+
+    #[test]
+    fn test_supports_store_layout() {
+        assert!(supports_store_layout(Some("5.3")));
+        assert!(supports_store_layout(Some("4.25")));
+        assert!(!supports_store_layout(Some("4.24")));
+        assert!(!supports_store_layout(Some("4.22.3")));
+        // Custom engine builds and unset versions are assumed to be recent
+        assert!(supports_store_layout(Some("{A1B2C3D4-0000}")));
+        assert!(supports_store_layout(None));
+    }
+
+    #[test]
+    fn test_find_plugin_dir() {
+        let temp = TempDir::new().unwrap();
+        let plugins = temp.path().join("Plugins");
+        fs::create_dir_all(plugins.join("Renamed")).unwrap();
+        fs::write(plugins.join("Renamed").join("AwesomePlugin.uplugin"), "{}").unwrap();
+        fs::create_dir_all(plugins.join("Copied")).unwrap();
+
+        // The .uplugin identifies the plugin regardless of folder name
+        assert_eq!(
+            find_plugin_dir(&plugins, "awesomeplugin"),
+            Some(plugins.join("Renamed"))
+        );
+        // A bare folder named after the package counts too
+        assert_eq!(
+            find_plugin_dir(&plugins, "Copied"),
+            Some(plugins.join("Copied"))
+        );
+        assert_eq!(find_plugin_dir(&plugins, "Missing"), None);
+        assert_eq!(
+            find_plugin_dir(&temp.path().join("NoPlugins"), "Copied"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remove_installed_package() {
+        let temp = TempDir::new().unwrap();
+        let plugins = temp.path().join("Plugins");
+        fs::create_dir_all(plugins.join("Renamed").join("Source")).unwrap();
+        fs::write(plugins.join("Renamed").join("Awesome.uplugin"), "{}").unwrap();
+        fs::create_dir_all(plugins.join("Awesome.unrealpm_backup")).unwrap();
+        fs::create_dir_all(plugins.join("Other")).unwrap();
+
+        let preview = remove_installed_package(temp.path(), "Awesome", DryRun::new(true)).unwrap();
+        assert_eq!(preview, Some(plugins.join("Renamed")));
+        assert!(plugins.join("Renamed").exists());
+        assert!(plugins.join("Awesome.unrealpm_backup").exists());
+
+        let removed = remove_installed_package(temp.path(), "Awesome", DryRun::OFF).unwrap();
+        assert_eq!(removed, Some(plugins.join("Renamed")));
+        assert!(!plugins.join("Renamed").exists());
+        assert!(!plugins.join("Awesome.unrealpm_backup").exists());
+        assert!(plugins.join("Other").exists());
+
+        assert_eq!(
+            remove_installed_package(temp.path(), "Awesome", DryRun::OFF).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_installed_plugins() {
+        let temp = TempDir::new().unwrap();
+        let plugins = temp.path().join("Plugins");
+        for dir in [
+            "Renamed",
+            "HandPlaced",
+            "FabPlugin",
+            "Awesome.unrealpm_backup",
+        ] {
+            fs::create_dir_all(plugins.join(dir)).unwrap();
+        }
+        fs::write(plugins.join("Renamed").join("Awesome.uplugin"), "{}").unwrap();
+
+        let mut lockfile = crate::Lockfile::new();
+        for name in ["Awesome", "FabPlugin", "Missing"] {
+            lockfile.update_package(name.to_string(), "1.0.0".into(), "sha256:x".into(), None);
+        }
+        lockfile.packages.get_mut("FabPlugin").unwrap().source =
+            Some(format!("{}123", crate::marketplace::FAB_SOURCE_PREFIX));
+
+        let installed = installed_plugins(temp.path(), &lockfile);
+        assert_eq!(
+            installed.managed,
+            vec![("Awesome".to_string(), plugins.join("Renamed"))]
+        );
+        assert_eq!(
+            installed.unmanaged,
+            vec![plugins.join("FabPlugin"), plugins.join("HandPlaced")]
+        );
+    }
+
+    #[test]
+    fn test_is_content_path() {
+        assert!(is_content_path(Path::new("Awesome/Content/Maps/Demo.umap")));
+        assert!(is_content_path(Path::new("./Awesome/Content/")));
+        assert!(is_content_path(Path::new("Content/Icon.uasset")));
+        assert!(!is_content_path(Path::new(
+            "Awesome/Source/Content/Loader.cpp"
+        )));
+        assert!(!is_content_path(Path::new("Awesome/Awesome.uplugin")));
+    }
+
+    #[test]
+    fn test_extract_partial() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = temp_dir.path().join("Awesome.tar.gz");
+        let mut builder = Builder::new(GzEncoder::new(
+            File::create(&tarball).unwrap(),
+            Compression::default(),
+        ));
+        for path in [
+            "Awesome/Awesome.uplugin",
+            "Awesome/Source/Awesome.cpp",
+            "Awesome/Content/Maps/Demo.umap",
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(2);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, &b"{}"[..]).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let plugins_dir = temp_dir.path().join("Plugins");
+        let dest = extract_partial(&tarball, &plugins_dir, "Awesome").unwrap();
+        assert_eq!(dest, plugins_dir.join("Awesome.unrealpm_partial"));
+        assert!(dest.join("Awesome/Awesome.uplugin").is_file());
+        assert!(dest.join("Awesome/Source/Awesome.cpp").is_file());
+        assert!(!dest.join("Awesome/Content").exists());
+    }
+
     #[test]
     fn test_install_package_basic() {
         let temp_dir = TempDir::new().unwrap();
@@ -987,22 +1826,25 @@ mod tests {
         let progress_messages = Arc::new(std::sync::Mutex::new(Vec::new()));
         let progress_messages_clone = progress_messages.clone();
 
-        let progress: ProgressCallback = Arc::new(move |msg, _current, _total| {
-            progress_messages_clone
-                .lock()
-                .unwrap()
-                .push(msg.to_string());
+        let progress: EventSink = Arc::new(move |event| {
+            progress_messages_clone.lock().unwrap().push(event.clone());
         });
 
         let result = install_package(&tarball, &project_dir, "ProgressPlugin", Some(progress));
         assert!(result.is_ok());
 
-        let messages = progress_messages.lock().unwrap();
-        assert!(!messages.is_empty(), "Progress should be reported");
+        let events = progress_messages.lock().unwrap();
+        assert!(!events.is_empty(), "Progress should be reported");
         assert!(
-            messages.iter().any(|m| m.contains("Extracting")),
+            events.iter().any(|e| e.message().contains("Extracting")),
             "Should report extraction"
         );
+        assert_eq!(
+            events.last(),
+            Some(&Event::Completed {
+                package: "ProgressPlugin".to_string()
+            })
+        );
     }
 
     // ============================================================================
@@ -1188,11 +2030,11 @@ mod tests {
         let progress_messages = Arc::new(std::sync::Mutex::new(Vec::new()));
         let progress_messages_clone = progress_messages.clone();
 
-        let progress: ProgressCallback = Arc::new(move |msg, _current, _total| {
+        let progress: EventSink = Arc::new(move |event| {
             progress_messages_clone
                 .lock()
                 .unwrap()
-                .push(msg.to_string());
+                .push(event.message());
         });
 
         let result = store_package(&tarball, &checksum, Some(progress));
@@ -1344,8 +2186,8 @@ mod tests {
 
         let progress_messages = Arc::new(std::sync::Mutex::new(Vec::new()));
         let progress_clone = progress_messages.clone();
-        let progress: ProgressCallback = Arc::new(move |msg, _, _| {
-            progress_clone.lock().unwrap().push(msg.to_string());
+        let progress: EventSink = Arc::new(move |event| {
+            progress_clone.lock().unwrap().push(event.message());
         });
 
         let result2 = install_package_cas(
@@ -1452,4 +2294,176 @@ mod tests {
         let size = calculate_dir_size(&test_dir).unwrap();
         assert_eq!(size, 15, "Nested directory size should be 15 bytes");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hard_link_savings() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("store");
+        fs::create_dir_all(store_path.join("sub")).unwrap();
+        fs::write(store_path.join("file1.txt"), "12345").unwrap();
+        fs::write(store_path.join("sub/file2.txt"), "1234567890").unwrap();
+        assert_eq!(hard_link_savings(&store_path), Some(0));
+
+        // One project holds the only other copy; nothing saved yet
+        link_or_copy_from_store(&store_path, &temp_dir.path().join("a"), None).unwrap();
+        assert_eq!(hard_link_savings(&store_path), Some(0));
+
+        // Each further project saves a full copy
+        link_or_copy_from_store(&store_path, &temp_dir.path().join("b"), None).unwrap();
+        link_or_copy_from_store(&store_path, &temp_dir.path().join("c"), None).unwrap();
+        assert_eq!(hard_link_savings(&store_path), Some(30));
+    }
+
+    // ============================================================================
+    // Tarball validation tests
+    // ============================================================================
+
+    /// Create a tarball with a single entry, bypassing tar's own path checks
+    fn create_raw_tarball(dir: &Path, entry_path: &str, link: Option<&str>) -> PathBuf {
+        let tarball_path = dir.join("raw.tar.gz");
+        let enc = GzEncoder::new(File::create(&tarball_path).unwrap(), Compression::default());
+        let mut builder = Builder::new(enc);
+
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..entry_path.len()].copy_from_slice(entry_path.as_bytes());
+        header.set_mode(0o644);
+        match link {
+            Some(target) => {
+                header.set_entry_type(tar::EntryType::Symlink);
+                header.set_link_name(target).unwrap();
+                header.set_size(0);
+                header.set_cksum();
+                builder.append(&header, &[][..]).unwrap();
+            }
+            None => {
+                header.set_size(4);
+                header.set_cksum();
+                builder.append(&header, &b"evil"[..]).unwrap();
+            }
+        }
+
+        builder.finish().unwrap();
+        tarball_path
+    }
+
+    #[test]
+    fn test_validate_tarball_entries_accepts_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_test_tarball(temp_dir.path(), "SafePlugin", "safe");
+        assert!(validate_tarball_entries(&tarball).is_ok());
+    }
+
+    #[test]
+    fn test_validate_tarball_entries_rejects_parent_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_raw_tarball(temp_dir.path(), "Plugin/../../evil.txt", None);
+
+        let err = validate_tarball_entries(&tarball).unwrap_err();
+        assert!(err.to_string().contains("Unsafe path"));
+    }
+
+    #[test]
+    fn test_validate_tarball_entries_rejects_escaping_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_raw_tarball(temp_dir.path(), "Plugin/link", Some("../../etc"));
+
+        let err = validate_tarball_entries(&tarball).unwrap_err();
+        assert!(err.to_string().contains("Unsafe link"));
+    }
+
+    #[test]
+    fn test_validate_tarball_entries_allows_internal_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_raw_tarball(temp_dir.path(), "Plugin/Docs/link", Some("../README"));
+        assert!(validate_tarball_entries(&tarball).is_ok());
+    }
+
+    #[test]
+    fn test_store_package_rejects_unsafe_tarball() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_raw_tarball(temp_dir.path(), "../escape.txt", None);
+        let checksum = compute_sha256(&tarball);
+
+        assert!(store_package(&tarball, &checksum, None).is_err());
+        assert!(!is_package_in_store(&checksum).unwrap());
+    }
+
+    #[test]
+    fn test_read_tarball_uplugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_test_tarball(temp_dir.path(), "MetaPlugin", "meta");
+
+        let (name, uplugin) = read_tarball_uplugin(&tarball).unwrap();
+        assert_eq!(name, "MetaPlugin");
+        assert_eq!(uplugin.version_name, "1.0.0");
+    }
+
+    #[test]
+    fn test_read_tarball_metadata_with_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball_path = temp_dir.path().join("with-manifest.tar.gz");
+        let enc = GzEncoder::new(File::create(&tarball_path).unwrap(), Compression::default());
+        let mut builder = Builder::new(enc);
+
+        let mut append = |path: &str, contents: &str| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        };
+        append("DepPlugin/Content/Big.uasset", "not metadata");
+        append("DepPlugin/Nested/Other.uplugin", "not json");
+        append(
+            "DepPlugin/DepPlugin.uplugin",
+            r#"{"FileVersion": 3, "Version": 2, "VersionName": "2.1.0", "FriendlyName": "Dep"}"#,
+        );
+        append(
+            "DepPlugin/unrealpm.json",
+            r#"{"name": "dep-plugin", "dependencies": {"base-plugin": "^1.0.0"}}"#,
+        );
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let metadata = read_tarball_metadata(&tarball_path).unwrap();
+        assert_eq!(metadata.plugin_name, "DepPlugin");
+        assert_eq!(metadata.uplugin.version_name, "2.1.0");
+        let manifest = metadata.manifest.unwrap();
+        assert_eq!(manifest.dependencies["base-plugin"], "^1.0.0");
+
+        // Packages without unrealpm.json still yield the descriptor
+        let tarball = create_test_tarball(temp_dir.path(), "BarePlugin", "bare");
+        let metadata = read_tarball_metadata(&tarball).unwrap();
+        assert_eq!(metadata.plugin_name, "BarePlugin");
+        assert!(metadata.manifest.is_none());
+    }
+
+    #[test]
+    fn test_list_tarball_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_test_tarball(temp_dir.path(), "ListPlugin", "list");
+
+        let entries = list_tarball_entries(&tarball).unwrap();
+        let paths: Vec<_> = entries.iter().map(|e| e.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("ListPlugin/ListPlugin.uplugin"),
+                PathBuf::from("ListPlugin/Source/ListPlugin.cpp"),
+            ]
+        );
+        assert_eq!(entries[1].size, b"// Test source file\n".len() as u64);
+    }
+
+    #[test]
+    fn test_calculate_checksum_matches_verify() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = create_test_tarball(temp_dir.path(), "SumPlugin", "sum");
+
+        let checksum = calculate_checksum(&tarball).unwrap();
+        assert_eq!(checksum, compute_sha256(&tarball));
+        assert!(verify_checksum(&tarball, &checksum, None).is_ok());
+    }
 }
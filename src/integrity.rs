@@ -0,0 +1,480 @@
+//! Checksum mismatch recovery
+//!
+//! When an artifact does not match its expected checksum, UnrealPM does not
+//! silently overwrite or delete it. The bad file is moved to
+//! `~/.unrealpm/quarantine/` for inspection, the download is retried from the
+//! configured mirrors, and an [`Incident`] naming the layer that mismatched is
+//! appended to `~/.unrealpm/incidents.jsonl` for later auditing.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use unrealpm::integrity::IncidentLog;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! for incident in IncidentLog::open_default()?.load()? {
+//!     println!("{} {}@{}: {}", incident.timestamp, incident.package, incident.version, incident.layer);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Error, LockedPackage, PackageMetadata, ResolvedPackage, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Where in the pipeline a checksum mismatch was detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MismatchLayer {
+    /// A tarball in the local download cache changed after it was verified
+    Cache,
+    /// A registry, mirror, or CDN served bytes that do not match the registry checksum
+    Download,
+    /// A local file registry's tarball does not match its own metadata
+    Registry,
+    /// The registry's checksum for a version differs from the one in unrealpm.lock
+    Lockfile,
+    /// The registry's checksum for a version differs from its `integrity` pin in unrealpm.json
+    Manifest,
+    /// A registry or mirror publishes a different checksum for a version than
+    /// unrealpm.lock records (`verify --cross-check`)
+    CrossCheck,
+}
+
+impl std::fmt::Display for MismatchLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MismatchLayer::Cache => "download cache",
+            MismatchLayer::Download => "download",
+            MismatchLayer::Registry => "registry",
+            MismatchLayer::Lockfile => "lockfile",
+            MismatchLayer::Manifest => "manifest",
+            MismatchLayer::CrossCheck => "cross-check",
+        })
+    }
+}
+
+/// A recorded checksum mismatch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Incident {
+    /// When the mismatch was detected (RFC 3339)
+    pub timestamp: String,
+    pub package: String,
+    pub version: String,
+    pub layer: MismatchLayer,
+
+    /// Checksum the artifact should have had
+    pub expected: String,
+
+    /// Checksum actually found, if the artifact could be hashed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+
+    /// Where the artifact came from (URL or path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Where the bad artifact was moved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quarantined: Option<PathBuf>,
+}
+
+impl Incident {
+    pub fn new(package: &str, version: &str, layer: MismatchLayer, expected: &str) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            package: package.to_string(),
+            version: version.to_string(),
+            layer,
+            expected: expected.to_string(),
+            actual: None,
+            source: None,
+            quarantined: None,
+        }
+    }
+
+    pub fn with_actual(mut self, actual: &str) -> Self {
+        self.actual = Some(actual.to_string());
+        self
+    }
+
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    pub fn with_quarantined(mut self, path: PathBuf) -> Self {
+        self.quarantined = Some(path);
+        self
+    }
+}
+
+/// Append-only log of checksum incidents (JSON lines)
+pub struct IncidentLog {
+    path: PathBuf,
+}
+
+impl IncidentLog {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Log at `~/.unrealpm/incidents.jsonl`
+    pub fn open_default() -> Result<Self> {
+        Ok(Self::new(unrealpm_home()?.join("incidents.jsonl")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an incident
+    pub fn record(&self, incident: &Incident) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(incident)?)?;
+        Ok(())
+    }
+
+    /// All recorded incidents, oldest first (unreadable lines are skipped)
+    pub fn load(&self) -> Result<Vec<Incident>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(fs::read_to_string(&self.path)?
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Incidents recorded at or after `cutoff`, oldest first
+    pub fn since(&self, cutoff: chrono::DateTime<chrono::Utc>) -> Result<Vec<Incident>> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .filter(|incident| {
+                chrono::DateTime::parse_from_rfc3339(&incident.timestamp)
+                    .is_ok_and(|timestamp| timestamp >= cutoff)
+            })
+            .collect())
+    }
+}
+
+/// Directory that bad artifacts are moved into
+pub struct Quarantine {
+    dir: PathBuf,
+}
+
+impl Quarantine {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Quarantine at `~/.unrealpm/quarantine`
+    pub fn open_default() -> Result<Self> {
+        Ok(Self::new(unrealpm_home()?.join("quarantine")))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Move `path` into quarantine as `<package>-<version>-<timestamp>.tar.gz`
+    pub fn isolate(&self, path: &Path, package: &str, version: &str) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+        let dest = self.dir.join(format!(
+            "{}-{}-{}.tar.gz",
+            package.replace('/', "_"),
+            version,
+            stamp
+        ));
+
+        // Rename fails across volumes; fall back to copy + remove
+        if fs::rename(path, &dest).is_err() {
+            fs::copy(path, &dest)?;
+            fs::remove_file(path)?;
+        }
+        Ok(dest)
+    }
+}
+
+/// Record an incident in the default log, warning instead of failing
+///
+/// Recovery should not abort because the incident log is not writable.
+pub fn report_incident(incident: &Incident) {
+    if let Err(e) = IncidentLog::open_default().and_then(|log| log.record(incident)) {
+        eprintln!("  ⚠ Could not record checksum incident: {}", e);
+    }
+}
+
+/// Move an artifact to the default quarantine, returning its new location
+///
+/// Falls back to deleting it if it cannot be moved, so a corrupt file is never
+/// reused.
+pub fn quarantine_artifact(path: &Path, package: &str, version: &str) -> Option<PathBuf> {
+    match Quarantine::open_default().and_then(|q| q.isolate(path, package, version)) {
+        Ok(dest) => Some(dest),
+        Err(_) => {
+            let _ = fs::remove_file(path);
+            None
+        }
+    }
+}
+
+/// Incident for a package whose checksum differs from its `integrity` pin
+///
+/// `pins` is [`Manifest::dependency_integrity`](crate::Manifest::dependency_integrity);
+/// unpinned packages always pass.
+pub fn pin_mismatch(
+    pins: &HashMap<String, String>,
+    package: &str,
+    version: &str,
+    checksum: &str,
+) -> Option<Incident> {
+    let pinned = pins.get(package)?;
+    (!pinned.eq_ignore_ascii_case(checksum)).then(|| {
+        Incident::new(package, version, MismatchLayer::Manifest, pinned).with_actual(checksum)
+    })
+}
+
+/// Check resolved packages against their `integrity` pins
+///
+/// Pins cover the source tarball, so pinned packages are switched to source
+/// installs; the download is then verified against the pinned checksum.
+/// Returns an incident for each package the registry serves with a
+/// different checksum, sorted by name.
+pub fn enforce_pins(
+    pins: &HashMap<String, String>,
+    resolved: &mut HashMap<String, ResolvedPackage>,
+) -> Vec<Incident> {
+    let mut mismatches: Vec<Incident> = resolved
+        .iter_mut()
+        .filter(|(name, _)| pins.contains_key(*name))
+        .filter_map(|(name, package)| {
+            package.binary = None;
+            pin_mismatch(pins, name, &package.version, &package.checksum)
+        })
+        .collect();
+    mismatches.sort_by(|a, b| a.package.cmp(&b.package));
+    mismatches
+}
+
+/// What one registry publishes for a locked artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrossCheck {
+    /// The registry publishes the locked checksum
+    Match,
+    /// The registry doesn't have this version (or pre-built binary)
+    Missing,
+    /// The registry publishes a different checksum
+    Mismatch(Incident),
+}
+
+/// Compare the checksum `metadata` (from the registry at `source`)
+/// publishes for a locked package's artifact with the lockfile's
+///
+/// Engine-specific builds share a version string, so a source tarball
+/// matches if any entry for the version has the locked checksum. A locked
+/// pre-built binary is looked up by its artifact id.
+pub fn cross_check(metadata: &PackageMetadata, locked: &LockedPackage, source: &str) -> CrossCheck {
+    let versions = metadata
+        .versions
+        .iter()
+        .filter(|version| version.version == locked.version);
+    let published: Vec<&str> = match &locked.binary {
+        Some(binary) => versions
+            .flat_map(|version| version.binaries.iter().flatten())
+            .filter(|candidate| candidate.tarball == binary.tarball)
+            .map(|candidate| candidate.checksum.as_str())
+            .collect(),
+        None => versions.map(|version| version.checksum.as_str()).collect(),
+    };
+    let expected = locked.artifact_checksum();
+    if published
+        .iter()
+        .any(|checksum| checksum.eq_ignore_ascii_case(expected))
+    {
+        return CrossCheck::Match;
+    }
+    match published.first() {
+        None => CrossCheck::Missing,
+        Some(actual) => CrossCheck::Mismatch(
+            Incident::new(
+                &metadata.name,
+                locked.artifact_id(),
+                MismatchLayer::CrossCheck,
+                expected,
+            )
+            .with_actual(actual)
+            .with_source(source),
+        ),
+    }
+}
+
+fn unrealpm_home() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| Error::Other("Could not find home directory".to_string()))?;
+    Ok(home.join(".unrealpm"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_incident_log_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let log = IncidentLog::new(temp.path().join("nested/incidents.jsonl"));
+        assert!(log.load().unwrap().is_empty());
+
+        let first = Incident::new("awesome-plugin", "1.0.0", MismatchLayer::Cache, "aaa")
+            .with_actual("bbb")
+            .with_quarantined(PathBuf::from("/q/awesome-plugin-1.0.0.tar.gz"));
+        let second = Incident::new("other", "2.0.0", MismatchLayer::Download, "ccc")
+            .with_source("https://cdn.example.com/other-2.0.0.tar.gz");
+        log.record(&first).unwrap();
+        log.record(&second).unwrap();
+
+        assert_eq!(log.load().unwrap(), vec![first, second]);
+        assert!(fs::read_to_string(log.path())
+            .unwrap()
+            .contains("\"layer\":\"download\""));
+
+        let mut old = Incident::new("old", "0.1.0", MismatchLayer::Lockfile, "eee");
+        old.timestamp = "2020-01-01T00:00:00+00:00".to_string();
+        log.record(&old).unwrap();
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(30);
+        assert_eq!(log.since(cutoff).unwrap().len(), 2);
+        assert!(!log.since(cutoff).unwrap().contains(&old));
+        assert_eq!(
+            log.since(chrono::Utc::now() + chrono::Duration::days(1))
+                .unwrap(),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn test_quarantine_moves_file() {
+        let temp = TempDir::new().unwrap();
+        let artifact = temp.path().join("awesome-plugin-1.0.0.tar.gz");
+        fs::write(&artifact, b"corrupt").unwrap();
+
+        let quarantine = Quarantine::new(temp.path().join("quarantine"));
+        let dest = quarantine
+            .isolate(&artifact, "awesome-plugin", "1.0.0")
+            .unwrap();
+
+        assert!(!artifact.exists());
+        assert!(dest.starts_with(quarantine.dir()));
+        assert_eq!(fs::read(&dest).unwrap(), b"corrupt");
+    }
+
+    #[test]
+    fn test_enforce_pins() {
+        let package = |name: &str, checksum: &str| ResolvedPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            checksum: checksum.to_string(),
+            dependencies: None,
+            binary: Some(crate::PrebuiltBinary {
+                platform: "Win64".to_string(),
+                engine: "5.3".to_string(),
+                tarball: "1.0.0-win64".to_string(),
+                checksum: "bin".to_string(),
+                configuration: None,
+            }),
+        };
+        let mut resolved = HashMap::new();
+        for (name, checksum) in [("pinned", "AAA"), ("tampered", "bbb"), ("free", "ccc")] {
+            resolved.insert(name.to_string(), package(name, checksum));
+        }
+        let pins: HashMap<String, String> = [("pinned", "aaa"), ("tampered", "ddd")]
+            .into_iter()
+            .map(|(name, pin)| (name.to_string(), pin.to_string()))
+            .collect();
+
+        let mismatches = enforce_pins(&pins, &mut resolved);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].package, "tampered");
+        assert_eq!(mismatches[0].layer, MismatchLayer::Manifest);
+        assert_eq!(mismatches[0].expected, "ddd");
+        assert_eq!(mismatches[0].actual.as_deref(), Some("bbb"));
+
+        // Pinned packages install from source; the rest keep their binary
+        assert!(resolved["pinned"].binary.is_none());
+        assert!(resolved["free"].binary.is_some());
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let metadata: PackageMetadata = serde_json::from_value(serde_json::json!({
+            "name": "awesome-plugin",
+            "versions": [
+                {"version": "1.0.0", "tarball": "a.tar.gz", "checksum": "aaa"},
+                {"version": "1.0.0", "tarball": "b.tar.gz", "checksum": "bbb", "binaries": [
+                    {"platform": "Win64", "engine": "5.3", "tarball": "1.0.0-Win64-5.3", "checksum": "ccc"}
+                ]},
+                {"version": "2.0.0", "tarball": "c.tar.gz", "checksum": "ddd"}
+            ]
+        }))
+        .unwrap();
+        let locked = |version: &str, checksum: &str| LockedPackage {
+            version: version.to_string(),
+            checksum: checksum.to_string(),
+            dependencies: None,
+            patched_hash: None,
+            source: None,
+            binary: None,
+            dev: false,
+            features: Vec::new(),
+            partial: false,
+            registry: None,
+            license: None,
+        };
+
+        assert_eq!(
+            cross_check(&metadata, &locked("1.0.0", "BBB"), "m"),
+            CrossCheck::Match
+        );
+        assert_eq!(
+            cross_check(&metadata, &locked("3.0.0", "eee"), "m"),
+            CrossCheck::Missing
+        );
+        let CrossCheck::Mismatch(incident) = cross_check(&metadata, &locked("2.0.0", "eee"), "m")
+        else {
+            panic!("expected a mismatch");
+        };
+        assert_eq!(incident.layer, MismatchLayer::CrossCheck);
+        assert_eq!(incident.expected, "eee");
+        assert_eq!(incident.actual.as_deref(), Some("ddd"));
+        assert_eq!(incident.source.as_deref(), Some("m"));
+
+        let mut binary = locked("1.0.0", "aaa");
+        binary.binary = Some(crate::PrebuiltBinary {
+            platform: "Win64".to_string(),
+            engine: "5.3".to_string(),
+            tarball: "1.0.0-Win64-5.3".to_string(),
+            checksum: "fff".to_string(),
+            configuration: None,
+        });
+        assert!(matches!(
+            cross_check(&metadata, &binary, "m"),
+            CrossCheck::Mismatch(incident) if incident.actual.as_deref() == Some("ccc")
+        ));
+    }
+}
@@ -0,0 +1,702 @@
+//! Format-preserving edits to JSON documents
+//!
+//! `unrealpm.json` is edited by people as well as by `install` and
+//! `uninstall`. Teams keep notes in it as `//` and `/* */` comments (JSONC)
+//! and care about key order and indentation, all of which a full
+//! re-serialization throws away. Manifest writes go through
+//! [`update_document`] instead, which rewrites only the members whose values
+//! changed and leaves every other byte of the file alone.
+//!
+//! Comments and trailing commas are accepted on read via [`strip_comments`].
+//!
+//! # Examples
+//!
+//! ```
+//! use serde_json::json;
+//! use unrealpm::json_edit::update_document;
+//!
+//! let text = r#"{
+//!     // Keep in sync with the build machines
+//!     "engine_version": "5.3",
+//!     "dependencies": {
+//!         "awesome-plugin": "^1.0.0"
+//!     }
+//! }"#;
+//!
+//! let new = json!({
+//!     "engine_version": "5.3",
+//!     "dependencies": { "awesome-plugin": "^1.0.0", "other-plugin": "^2.0.0" }
+//! });
+//! let updated = update_document(text, &new, &[]).unwrap();
+//!
+//! assert!(updated.contains("// Keep in sync with the build machines"));
+//! assert!(updated.contains("        \"other-plugin\": \"^2.0.0\""));
+//! ```
+
+use crate::{Error, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Copy of a JSONC document with comments and trailing commas removed
+///
+/// The result parses with `serde_json`. Text inside strings is untouched.
+pub fn strip_comments(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    // Position in `out` of a comma that is trailing if a closing bracket comes next
+    let mut pending_comma: Option<usize> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(bytes, i);
+                out.push_str(&text[i..end]);
+                pending_comma = None;
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = text[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = text[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |n| i + 2 + n + 2);
+                out.push(' ');
+            }
+            b',' => {
+                pending_comma = Some(out.len());
+                out.push(',');
+                i += 1;
+            }
+            b'}' | b']' => {
+                if let Some(comma) = pending_comma.take() {
+                    out.replace_range(comma..comma + 1, " ");
+                }
+                out.push(bytes[i] as char);
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() => {
+                out.push(b as char);
+                i += 1;
+            }
+            _ => {
+                let ch = text[i..].chars().next().unwrap_or_default();
+                out.push(ch);
+                pending_comma = None;
+                i += ch.len_utf8().max(1);
+            }
+        }
+    }
+
+    out
+}
+
+/// Rewrite `text` so its top-level object has the value `new`
+///
+/// Only members whose values differ are touched: changed values are replaced
+/// in place, new members are appended to their object using the indentation
+/// of their siblings, and removed members are cut out along with their line.
+/// Comments, key order, and whitespace elsewhere are preserved.
+///
+/// Top-level keys missing from `new` are only removed when listed in
+/// `managed`, so keys the caller does not model (such as `$schema`) survive.
+/// Empty objects and arrays in `new` are not added where `text` has no key.
+pub fn update_document(text: &str, new: &Value, managed: &[&str]) -> Result<String> {
+    let old: Value = serde_json::from_str(&strip_comments(text))?;
+    let (Value::Object(old), Value::Object(new)) = (&old, new) else {
+        return Err(Error::Other("Expected a JSON object".to_string()));
+    };
+
+    let mut edits = Vec::new();
+    diff(&[], old, new, managed, &mut edits);
+
+    let mut text = text.to_string();
+    for edit in &edits {
+        text = apply(&text, edit)?;
+    }
+    Ok(text)
+}
+
+/// A change to one member, addressed by its key path from the root
+enum Edit {
+    Set(Vec<String>, Value),
+    Remove(Vec<String>),
+}
+
+fn diff(
+    path: &[String],
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    managed: &[&str],
+    edits: &mut Vec<Edit>,
+) {
+    for (key, new_value) in new {
+        let mut member_path = path.to_vec();
+        member_path.push(key.clone());
+
+        match (old.get(key), new_value) {
+            (Some(old_value), _) if old_value == new_value => {}
+            (Some(Value::Object(old_object)), Value::Object(new_object)) => {
+                diff(&member_path, old_object, new_object, &[], edits)
+            }
+            (None, Value::Object(o)) if o.is_empty() => {}
+            (None, Value::Array(a)) if a.is_empty() => {}
+            _ => edits.push(Edit::Set(member_path, new_value.clone())),
+        }
+    }
+
+    for key in old.keys() {
+        let removable = !path.is_empty() || managed.contains(&key.as_str());
+        if removable && !new.contains_key(key) {
+            let mut member_path = path.to_vec();
+            member_path.push(key.clone());
+            edits.push(Edit::Remove(member_path));
+        }
+    }
+}
+
+fn apply(text: &str, edit: &Edit) -> Result<String> {
+    let root = Scanner::new(text).document()?;
+    let unit = indent_unit(text, &root);
+
+    let (path, value) = match edit {
+        Edit::Set(path, value) => (path, Some(value)),
+        Edit::Remove(path) => (path, None),
+    };
+    let (key, parent_path) = path
+        .split_last()
+        .ok_or_else(|| Error::Other("Empty JSON path".to_string()))?;
+    let parent = object_at(&root, parent_path)
+        .ok_or_else(|| Error::Other(format!("No object at {}", parent_path.join("."))))?;
+    let index = parent.members.iter().position(|m| &m.key == key);
+
+    Ok(match (index, value) {
+        (Some(index), Some(value)) => {
+            let member = &parent.members[index];
+            let rendered = render(value, &unit, &indent_of(text, member.start))?;
+            splice(text, member.value_start..member.value_end, &rendered)
+        }
+        (None, Some(value)) => insert_member(text, parent, key, value, &unit)?,
+        (Some(index), None) => remove_member(text, parent, index),
+        (None, None) => text.to_string(),
+    })
+}
+
+fn insert_member(
+    text: &str,
+    parent: &ObjectSpan,
+    key: &str,
+    value: &Value,
+    unit: &str,
+) -> Result<String> {
+    let key = serde_json::to_string(key)?;
+
+    let Some(last) = parent.members.last() else {
+        let outer = indent_of(text, parent.open);
+        let inner = format!("{}{}", outer, unit);
+        let entry = format!("{}: {}", key, render(value, unit, &inner)?);
+        let inside = &text[parent.open + 1..parent.close];
+        return Ok(if inside.trim().is_empty() {
+            splice(
+                text,
+                parent.open + 1..parent.close,
+                &format!("\n{}{}\n{}", inner, entry, outer),
+            )
+        } else {
+            // Only comments inside; put the member first
+            splice(
+                text,
+                parent.open + 1..parent.open + 1,
+                &format!("\n{}{},", inner, entry),
+            )
+        });
+    };
+
+    let comma = comma_after(text, last.value_end);
+
+    // Compact objects stay on one line
+    if !on_own_line(text, last.start) {
+        let entry = format!("{}: {}", key, render(value, unit, "")?);
+        return Ok(match comma {
+            Some(comma) => splice(text, comma + 1..comma + 1, &format!(" {},", entry)),
+            None => splice(
+                text,
+                last.value_end..last.value_end,
+                &format!(", {}", entry),
+            ),
+        });
+    }
+
+    let indent = indent_of(text, last.start);
+    let entry = format!("{}: {}", key, render(value, unit, &indent)?);
+    Ok(match comma {
+        // Keep the document's trailing-comma style
+        Some(comma) => {
+            let at = line_end(text, comma + 1);
+            splice(text, at..at, &format!("\n{}{},", indent, entry))
+        }
+        None => {
+            let at = line_end(text, last.value_end);
+            let with_entry = splice(text, at..at, &format!("\n{}{}", indent, entry));
+            splice(&with_entry, last.value_end..last.value_end, ",")
+        }
+    })
+}
+
+fn remove_member(text: &str, parent: &ObjectSpan, index: usize) -> String {
+    let member = &parent.members[index];
+    let comma = comma_after(text, member.value_end);
+    let previous_comma = index
+        .checked_sub(1)
+        .and_then(|i| comma_after(text, parent.members[i].value_end));
+
+    if parent.members.len() == 1 {
+        let outside = format!(
+            "{}{}",
+            &text[parent.open + 1..member.start],
+            &text[comma.map_or(member.value_end, |c| c + 1)..parent.close]
+        );
+        if outside.trim().is_empty() {
+            return splice(text, parent.open + 1..parent.close, "");
+        }
+    }
+
+    if !on_own_line(text, member.start) {
+        return match (comma, previous_comma) {
+            (Some(comma), _) => {
+                let end = comma + 1 + whitespace_len(&text[comma + 1..]);
+                splice(text, member.start..end, "")
+            }
+            (None, Some(previous)) => splice(text, previous..member.value_end, ""),
+            (None, None) => splice(text, member.start..member.value_end, ""),
+        };
+    }
+
+    // Remove the member's whole line, including a comment at its end
+    let start = line_start(text, member.start);
+    let mut end = line_end(text, comma.map_or(member.value_end, |c| c + 1));
+    if text[end..].starts_with('\n') {
+        end += 1;
+    }
+    let without = splice(text, start..end, "");
+
+    // The member was last; its predecessor must not keep a dangling comma
+    match (comma, previous_comma) {
+        (None, Some(previous)) => splice(&without, previous..previous + 1, ""),
+        _ => without,
+    }
+}
+
+/// Location of an object and its members in the document
+struct ObjectSpan {
+    open: usize,
+    close: usize,
+    members: Vec<MemberSpan>,
+}
+
+struct MemberSpan {
+    key: String,
+    /// Offset of the key's opening quote
+    start: usize,
+    value_start: usize,
+    value_end: usize,
+    object: Option<ObjectSpan>,
+}
+
+struct Scanner<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            bytes: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn document(mut self) -> Result<ObjectSpan> {
+        self.skip_trivia();
+        if self.peek() != Some(b'{') {
+            return Err(self.error("expected '{'"));
+        }
+        let root = self.object()?;
+        self.skip_trivia();
+        if self.pos < self.bytes.len() {
+            return Err(self.error("unexpected content after the document"));
+        }
+        Ok(root)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Other(format!("Invalid JSON at byte {}: {}", self.pos, message))
+    }
+
+    fn skip_trivia(&mut self) {
+        self.pos += trivia_len(&self.text[self.pos..]);
+    }
+
+    fn value(&mut self) -> Result<(usize, usize, Option<ObjectSpan>)> {
+        self.skip_trivia();
+        let start = self.pos;
+        let object = match self.peek() {
+            Some(b'{') => Some(self.object()?),
+            Some(b'[') => {
+                self.array()?;
+                None
+            }
+            Some(b'"') => {
+                self.pos = string_end(self.bytes, self.pos);
+                None
+            }
+            Some(_) => {
+                while self.peek().is_some_and(|b| {
+                    !matches!(b, b',' | b'}' | b']' | b'/') && !b.is_ascii_whitespace()
+                }) {
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return Err(self.error("expected a value"));
+                }
+                None
+            }
+            None => return Err(self.error("unexpected end of document")),
+        };
+        Ok((start, self.pos, object))
+    }
+
+    fn object(&mut self) -> Result<ObjectSpan> {
+        let open = self.pos;
+        self.pos += 1;
+        let mut members = Vec::new();
+
+        loop {
+            self.skip_trivia();
+            match self.peek() {
+                Some(b'}') => {
+                    let close = self.pos;
+                    self.pos += 1;
+                    return Ok(ObjectSpan {
+                        open,
+                        close,
+                        members,
+                    });
+                }
+                Some(b'"') => {}
+                _ => return Err(self.error("expected a key or '}'")),
+            }
+
+            let start = self.pos;
+            self.pos = string_end(self.bytes, start);
+            let key: String = serde_json::from_str(&self.text[start..self.pos])?;
+
+            self.skip_trivia();
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':'"));
+            }
+            self.pos += 1;
+
+            let (value_start, value_end, object) = self.value()?;
+            members.push(MemberSpan {
+                key,
+                start,
+                value_start,
+                value_end,
+                object,
+            });
+
+            self.skip_trivia();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {}
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<()> {
+        self.pos += 1;
+        loop {
+            self.skip_trivia();
+            if self.peek() == Some(b']') {
+                self.pos += 1;
+                return Ok(());
+            }
+            self.value()?;
+            self.skip_trivia();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {}
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+}
+
+fn object_at<'a>(root: &'a ObjectSpan, path: &[String]) -> Option<&'a ObjectSpan> {
+    path.iter().try_fold(root, |object, key| {
+        object
+            .members
+            .iter()
+            .find(|m| &m.key == key)
+            .and_then(|m| m.object.as_ref())
+    })
+}
+
+/// Offset just past the string literal starting at `start`
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Length of the whitespace and comments at the start of `text`
+fn trivia_len(text: &str) -> usize {
+    let mut i = 0;
+    loop {
+        let rest = &text[i..];
+        let trimmed = rest.trim_start();
+        i += rest.len() - trimmed.len();
+        if trimmed.starts_with("//") {
+            i += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else if let Some(comment) = trimmed.strip_prefix("/*") {
+            i += comment.find("*/").map_or(trimmed.len(), |n| n + 4);
+        } else {
+            return i;
+        }
+    }
+}
+
+fn whitespace_len(text: &str) -> usize {
+    text.len() - text.trim_start_matches([' ', '\t']).len()
+}
+
+/// Offset of the comma following a value, if any
+fn comma_after(text: &str, value_end: usize) -> Option<usize> {
+    let at = value_end + trivia_len(&text[value_end..]);
+    text[at..].starts_with(',').then_some(at)
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |n| n + 1)
+}
+
+/// End of the line containing `pos` if only whitespace or a `//` comment
+/// follows, otherwise `pos` itself
+fn line_end(text: &str, pos: usize) -> usize {
+    let rest = &text[pos..];
+    let len = rest.find('\n').unwrap_or(rest.len());
+    let line = rest[..len].trim();
+    if line.is_empty() || line.starts_with("//") {
+        pos + len
+    } else {
+        pos
+    }
+}
+
+fn on_own_line(text: &str, pos: usize) -> bool {
+    text[line_start(text, pos)..pos].trim().is_empty()
+}
+
+/// Leading whitespace of the line containing `pos`
+fn indent_of(text: &str, pos: usize) -> String {
+    let line = &text[line_start(text, pos)..];
+    line[..whitespace_len(line)].to_string()
+}
+
+/// One level of indentation, as used by the root object's first member
+fn indent_unit(text: &str, root: &ObjectSpan) -> String {
+    root.members
+        .first()
+        .filter(|m| on_own_line(text, m.start))
+        .map(|m| {
+            let indent = indent_of(text, m.start);
+            let outer = indent_of(text, root.open);
+            indent.strip_prefix(&outer).unwrap_or(&indent).to_string()
+        })
+        .filter(|unit| !unit.is_empty())
+        .unwrap_or_else(|| "  ".to_string())
+}
+
+/// Pretty-print `value` for a member whose line is indented by `indent`
+fn render(value: &Value, unit: &str, indent: &str) -> Result<String> {
+    let mut buf = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+    value.serialize(&mut serializer)?;
+    let rendered = String::from_utf8(buf).map_err(|e| Error::Other(e.to_string()))?;
+    Ok(rendered.replace('\n', &format!("\n{}", indent)))
+}
+
+fn splice(text: &str, range: std::ops::Range<usize>, replacement: &str) -> String {
+    let mut out = String::with_capacity(text.len() + replacement.len());
+    out.push_str(&text[..range.start]);
+    out.push_str(replacement);
+    out.push_str(&text[range.end..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MANIFEST: &str = r#"{
+    // Shared by the whole team
+    "name": "MyGame",
+    "engine_version": "5.3",
+    "dependencies": {
+        "awesome-plugin": "^1.0.0", // pinned until the 2.0 migration
+        /* networking */
+        "net-plugin": "~2.1.0"
+    },
+    "$schema": "https://example.com/unrealpm.schema.json"
+}
+"#;
+
+    fn parsed(text: &str) -> Value {
+        serde_json::from_str(&strip_comments(text)).unwrap()
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        let text = r#"{
+            // comment
+            "url": "https://example.com/a//b", /* block */
+            "list": [1, 2,],
+            "nested": { "a": "/* not a comment */", },
+        }"#;
+        assert_eq!(
+            parsed(text),
+            json!({
+                "url": "https://example.com/a//b",
+                "list": [1, 2],
+                "nested": { "a": "/* not a comment */" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_unchanged_document_is_untouched() {
+        let new = parsed(MANIFEST);
+        assert_eq!(
+            update_document(MANIFEST, &new, &["name"]).unwrap(),
+            MANIFEST
+        );
+    }
+
+    #[test]
+    fn test_add_member_keeps_comments_and_indentation() {
+        let mut new = parsed(MANIFEST);
+        new["dependencies"]["other-plugin"] = json!("^3.0.0");
+
+        let updated = update_document(MANIFEST, &new, &[]).unwrap();
+        assert!(updated.contains(
+            "        \"net-plugin\": \"~2.1.0\",\n        \"other-plugin\": \"^3.0.0\"\n    },"
+        ));
+        assert!(updated.contains("// pinned until the 2.0 migration"));
+        assert!(updated.contains("/* networking */"));
+        assert_eq!(parsed(&updated), new);
+    }
+
+    #[test]
+    fn test_change_value_in_place() {
+        let mut new = parsed(MANIFEST);
+        new["dependencies"]["awesome-plugin"] = json!("^1.2.0");
+
+        let updated = update_document(MANIFEST, &new, &[]).unwrap();
+        assert_eq!(
+            updated,
+            MANIFEST.replace("\"^1.0.0\", // pinned", "\"^1.2.0\", // pinned")
+        );
+    }
+
+    #[test]
+    fn test_remove_members() {
+        // Removing the last member drops the comma left on its predecessor
+        let mut new = parsed(MANIFEST);
+        new["dependencies"]
+            .as_object_mut()
+            .unwrap()
+            .remove("net-plugin");
+        let updated = update_document(MANIFEST, &new, &[]).unwrap();
+        assert!(updated.contains("\"awesome-plugin\": \"^1.0.0\" // pinned"));
+        assert!(!updated.contains("net-plugin"));
+        assert_eq!(parsed(&updated), new);
+
+        // Removing a member takes its line and trailing comment with it
+        let mut new = parsed(MANIFEST);
+        new["dependencies"]
+            .as_object_mut()
+            .unwrap()
+            .remove("awesome-plugin");
+        let updated = update_document(MANIFEST, &new, &[]).unwrap();
+        assert!(!updated.contains("pinned"));
+        assert_eq!(parsed(&updated), new);
+    }
+
+    #[test]
+    fn test_unmanaged_top_level_keys_survive() {
+        let mut new = parsed(MANIFEST);
+        let object = new.as_object_mut().unwrap();
+        object.remove("$schema");
+        object.remove("name");
+        object.insert("dev_dependencies".to_string(), json!({}));
+
+        let updated = update_document(MANIFEST, &new, &["name", "dev_dependencies"]).unwrap();
+        assert!(updated.contains("$schema"));
+        assert!(!updated.contains("\"name\""));
+        assert!(!updated.contains("dev_dependencies"));
+        assert!(updated.contains("// Shared by the whole team"));
+    }
+
+    #[test]
+    fn test_insert_into_empty_and_compact_objects() {
+        let text = "{\n  \"dependencies\": {},\n  \"patches\": {\"a\": \"p/a\"}\n}\n";
+        let new = json!({
+            "dependencies": { "awesome-plugin": "^1.0.0" },
+            "patches": { "a": "p/a", "b": "p/b" }
+        });
+
+        let updated = update_document(text, &new, &[]).unwrap();
+        assert_eq!(
+            updated,
+            "{\n  \"dependencies\": {\n    \"awesome-plugin\": \"^1.0.0\"\n  },\n  \
+             \"patches\": {\"a\": \"p/a\", \"b\": \"p/b\"}\n}\n"
+        );
+
+        // Removing the only member collapses the object
+        let removed =
+            update_document(&updated, &json!({ "dependencies": {}, "patches": {} }), &[]).unwrap();
+        assert_eq!(
+            removed,
+            "{\n  \"dependencies\": {},\n  \"patches\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_trailing_comma_style_is_kept() {
+        let text = "{\n  \"dependencies\": {\n    \"a\": \"1\",\n  },\n}\n";
+        let new = json!({ "dependencies": { "a": "1", "b": "2" } });
+
+        let updated = update_document(text, &new, &[]).unwrap();
+        assert_eq!(
+            updated,
+            "{\n  \"dependencies\": {\n    \"a\": \"1\",\n    \"b\": \"2\",\n  },\n}\n"
+        );
+    }
+}
@@ -34,41 +34,144 @@
 //! # Modules
 //!
 //! - [`manifest`] - Parse and manage unrealpm.json and .uproject files
+//! - [`markdown`] - Terminal rendering of package READMEs
 //! - [`registry`] - Interact with the package registry
 //! - [`resolver`] - Resolve package dependencies with semantic versioning
+//! - [`resolve_cache`] - Reuse resolution results while registry metadata is unchanged
+//! - [`changelog`] - Release notes between two versions of a package
+//! - [`compat`] - Post-resolution checks such as duplicate module names
 //! - [`installer`] - Install packages and verify checksums
 //! - [`lockfile`] - Manage unrealpm.lock for reproducible builds
+//! - [`license`] - Package licenses and the license allow-list
+//! - [`link`] - Plugins linked into a project from a local directory
+//! - [`attestation`] - Signed attestations of the dependencies used for a build
+//! - [`advisory`] - Security advisories and yanked versions for `unrealpm audit`
+//! - [`marketplace`] - Discover Fab/Marketplace plugins (externally managed)
+//! - [`patch`] - Apply local source patches to installed packages
+//! - [`pipeline`] - Parallel download/verify with serial installs for multi-package operations
 //! - [`platform`] - Platform detection and Unreal Engine path resolution
 //! - [`config`] - User and project configuration management
+//! - [`disk`] - Disk-space preflight checks
+//! - [`dry_run`] - Dry-run context threaded through functions that make changes
+//! - [`integrity`] - Quarantine and incident log for checksum mismatches
+//! - [`ignore`] - Ignore-file entries (.gitignore, .p4ignore) for managed plugins
+//! - [`i18n`] - Message catalogs and locale selection for CLI messages
+//! - [`mirrors`] - Mirror latency probing, ranking, and failover
+//! - [`notify`] - Desktop and webhook notifications when long operations finish
+//! - [`notices`] - Aggregate third-party LICENSE/NOTICE files into one document
+//! - [`output`] - Plain-text output mode (no spinners, frames, emoji, or colors)
+//! - [`json_edit`] - Format-preserving edits to unrealpm.json (comments, key order)
+//! - [`limits`] - Package size and file-count limits for pack and publish
+//! - [`metrics`] - Per-package install time history for estimates and ordering
+//! - [`sbom`] - Software bills of materials (CycloneDX and SPDX)
+//! - [`saved_plugins`] - Previous plugin versions kept in Saved/ for quick branch switches
+//! - [`scope`] - Scoped package names (`@studio/plugin-name`)
+//! - [`self_update`] - Release checks and atomic replacement of the unrealpm binary
+//! - [`testing`] - Synthetic file registries for integration tests (`testing` feature)
+//! - [`strict`] - Strict mode, which turns warnings into errors
+//! - [`status`] - Editor-visible status file in Plugins/
+//! - [`store`] - Garbage collection of the global package store
+//! - [`trust`] - Trust-on-first-use pinning of package publisher keys
+//! - [`timings`] - Opt-in phase timings and cache hit rates
+//! - [`vcs`] - Perforce/Plastic SCM checkout around file writes
+//! - [`vendor`] - Vendored package mirrors for offline and air-gapped installs
+//! - [`events`] - Typed progress events for frontends built on the library
 //! - [`error`] - Error types and result handling
+//! - [`exit_code`] - Stable exit codes of the CLI, by failure class
 
+pub mod advisory;
+pub mod attestation;
+pub mod changelog;
+pub mod compat;
 pub mod config;
+pub mod disk;
+pub mod dry_run;
 pub mod error;
+pub mod events;
+pub mod exit_code;
+pub mod i18n;
+pub mod ignore;
 pub mod installer;
+pub mod integrity;
+pub mod json_edit;
+pub mod license;
+pub mod limits;
+pub mod link;
 pub mod lockfile;
 pub mod manifest;
+pub mod markdown;
+pub mod marketplace;
+pub mod metrics;
+pub mod mirrors;
+pub mod notices;
+pub mod notify;
+pub mod output;
+pub mod patch;
+pub mod pipeline;
 pub mod platform;
 pub mod pubgrub_resolver;
 pub mod registry;
 pub mod registry_http;
+pub mod resolve_cache;
 pub mod resolver;
+pub mod saved_plugins;
+pub mod sbom;
+pub mod scope;
+pub mod self_update;
 pub mod signing;
+pub mod status;
+pub mod store;
+pub mod strict;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod timings;
+pub mod trust;
+pub mod vcs;
+pub mod vendor;
 
-pub use config::{Config, ResolverConfig};
+pub use attestation::{
+    Attestation, AttestationStatement, AttestedPackage, ATTESTATION_FILE_NAME, ATTESTATION_FORMAT,
+};
+pub use config::{
+    CommandAlias, Config, InstallConfig, InstallLayout, InstallMode, LimitsConfig, NetworkConfig,
+    NotificationsConfig, ProjectBuildConfig, ProjectConfig, ResolverConfig, SaveStyle,
+    SelfUpdateConfig, VcsConfig, PROJECT_CONFIG_PATH,
+};
+pub use dry_run::DryRun;
 pub use error::{Error, Result};
+pub use events::{Event, EventSink};
+pub use ignore::IgnoreFile;
 pub use installer::{
-    get_package_store_path, get_store_dir, get_store_stats, install_package, install_package_cas,
-    is_package_in_store, link_or_copy_from_store, store_package, verify_checksum, ProgressCallback,
-    StoreStats,
+    calculate_checksum, find_plugin_dir, get_package_store_path, get_store_dir, get_store_stats,
+    install_package, install_package_cas, installed_plugin_dir, installed_plugins,
+    is_package_in_store, is_standalone_plugin, link_or_copy_from_store, list_tarball_entries,
+    plugins_dir, read_tarball_metadata, read_tarball_uplugin, remove_installed_package,
+    store_package, validate_tarball_entries, verify_checksum, InstalledPlugins, ProgressCallback,
+    StoreStats, TarballEntry, TarballMetadata, STANDALONE_DEPS_DIR,
+};
+pub use lockfile::{LockChange, LockChangeKind, LockedPackage, Lockfile, LOCKFILE_NAME};
+pub use manifest::{
+    ConstraintError, DependencyConditions, DiscoveryTags, Manifest, ManifestFormat,
+    ProjectSettings, UPlugin, UPluginDependency, UPluginLocalizationTarget, UPluginModule,
+    UProject, Workspace, MANIFEST_FILE_NAME, TOML_MANIFEST_FILE_NAME, WORKSPACE_FILE_NAME,
 };
-pub use lockfile::{LockedPackage, Lockfile, LOCKFILE_NAME};
-pub use manifest::{Manifest, UPlugin, UPluginDependency, UProject};
 pub use platform::{
-    detect_platform, detect_unreal_engines, normalize_engine_version, resolve_engine_association,
-    wsl_to_windows_path,
+    describe_engine_version, detect_engine_platforms, detect_platform, detect_unreal_engines,
+    engine_versions_compatible, is_source_engine_build, is_valid_engine_install,
+    normalize_engine_version, read_engine_build_version, resolve_engine_association,
+    wsl_to_windows_path, EngineBuildVersion, EngineVersion,
 };
 pub use registry::{
-    Dependency, PackageMetadata, PackageType, PackageVersion, PrebuiltBinary, RegistryClient,
+    ArtifactSize, BinaryUpsert, Dependency, KeyEndorsement, LayoutMigration, PackageMetadata,
+    PackageType, PackageVersion, PrebuiltBinary, RegistryClient, SearchHit, TarballLayout,
+};
+pub use resolve_cache::{metadata_digest, resolution_key, CachedResolution, ResolutionCache};
+pub use resolver::{
+    dev_only_packages, find_matching_version, install_order, resolve_dependencies,
+    resolve_with_source, select_artifacts, ArtifactSelection, MetadataSource, ResolvedPackage,
+};
+pub use signing::{
+    load_or_generate_keys, load_public_key_hex, verify_signature, PackageSigningKey,
 };
-pub use resolver::{find_matching_version, resolve_dependencies, ResolvedPackage};
-pub use signing::{load_or_generate_keys, verify_signature, PackageSigningKey};
+pub use status::{PluginState, PluginStatus, StatusFile, STATUS_FILE_NAME};
+pub use timings::{Phase, Timings};
@@ -0,0 +1,452 @@
+//! Package licenses and the `[policy] allowed_licenses` allow-list
+//!
+//! A package's license is the first found of:
+//! 1. `license` in its unrealpm.json or unrealpm.toml (an SPDX expression
+//!    such as `MIT OR Apache-2.0`)
+//! 2. `License` in its .uplugin
+//! 3. A well-known license text (MIT, Apache-2.0, BSD, GPL, ...) in a
+//!    LICENSE/COPYING file at the plugin root
+//!
+//! Install records it in unrealpm.lock and, when the user or project config
+//! has an allow-list, refuses packages whose license is not on it:
+//!
+//! ```toml
+//! [policy]
+//! allowed_licenses = ["MIT", "Apache-2.0", "BSD-3-Clause"]
+//! ```
+//!
+//! Packages without a detectable license are refused while a list is set.
+//! Licenses in `Source/ThirdParty/` are not considered; `unrealpm notices`
+//! collects those.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::license::{identify, is_allowed};
+//!
+//! let allowed = vec!["MIT".to_string(), "BSD-3-Clause".to_string()];
+//! assert!(is_allowed("MIT", &allowed));
+//! assert!(is_allowed("Apache-2.0 OR MIT", &allowed));
+//! assert!(!is_allowed("GPL-3.0-only", &allowed));
+//!
+//! let text = "MIT License\n\nPermission is hereby granted, free of charge, ...";
+//! assert_eq!(identify(text), Some("MIT"));
+//! ```
+
+use crate::json_edit::strip_comments;
+use crate::manifest::ManifestFormat;
+use crate::Result;
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path};
+use tar::Archive;
+
+/// File stems holding a package's own license text
+const LICENSE_STEMS: [&str; 3] = ["LICENSE", "LICENCE", "COPYING"];
+
+/// License of one locked package, as reported by `unrealpm licenses`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageLicense {
+    pub name: String,
+    pub version: String,
+    /// SPDX expression, `None` if it couldn't be determined
+    pub license: Option<String>,
+}
+
+/// SPDX identifier of a well-known license text
+pub fn identify(text: &str) -> Option<&'static str> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let upper = text.to_uppercase();
+    let has = |needle: &str| upper.contains(&needle.to_uppercase());
+    if has("Apache License") && has("Version 2.0") {
+        Some("Apache-2.0")
+    } else if has("GNU Lesser General Public License") {
+        Some(if has("Version 3") {
+            "LGPL-3.0"
+        } else {
+            "LGPL-2.1"
+        })
+    } else if has("GNU General Public License") {
+        Some(if has("Version 3") {
+            "GPL-3.0"
+        } else {
+            "GPL-2.0"
+        })
+    } else if has("Mozilla Public License Version 2.0") {
+        Some("MPL-2.0")
+    } else if has("Boost Software License") {
+        Some("BSL-1.0")
+    } else if has("free and unencumbered software released into the public domain") {
+        Some("Unlicense")
+    } else if has("Permission is hereby granted, free of charge") {
+        Some("MIT")
+    } else if has("Redistribution and use in source and binary forms") {
+        Some(if has("Neither the name") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        })
+    } else if has("This software is provided 'as-is'") {
+        Some("Zlib")
+    } else {
+        None
+    }
+}
+
+/// Whether an SPDX `expression` is permitted by the `allowed` identifiers
+///
+/// `OR` needs one permitted alternative, `AND` needs all of its parts;
+/// `AND` binds tighter. Identifiers compare case-insensitively, and `-only`
+/// matches the bare identifier (`GPL-3.0-only` is `GPL-3.0`).
+pub fn is_allowed(expression: &str, allowed: &[String]) -> bool {
+    let normalize = |id: &str| id.trim().trim_end_matches("-only").to_ascii_lowercase();
+    let expression = expression.replace(['(', ')'], " ");
+    let words: Vec<&str> = expression.split_whitespace().collect();
+    words
+        .split(|word| word.eq_ignore_ascii_case("OR"))
+        .any(|alternative| {
+            !alternative.is_empty()
+                && alternative
+                    .split(|word| word.eq_ignore_ascii_case("AND"))
+                    .all(|part| {
+                        let id = part.join(" ");
+                        allowed
+                            .iter()
+                            .any(|permitted| normalize(permitted) == normalize(&id))
+                    })
+        })
+}
+
+/// License from the contents of a package's manifest, .uplugin, and root
+/// license files
+fn from_files(
+    manifest: Option<(&str, ManifestFormat)>,
+    uplugin: Option<&str>,
+    license_texts: &[String],
+) -> Option<String> {
+    let non_empty = |license: &str| {
+        Some(license.trim())
+            .filter(|license| !license.is_empty())
+            .map(String::from)
+    };
+    // Both JSON files allow comments and trailing commas
+    let json_field = |json: &str, key: &str| -> Option<String> {
+        let value: serde_json::Value =
+            serde_json::from_str(&strip_comments(json.trim_start_matches('\u{feff}'))).ok()?;
+        non_empty(value.get(key)?.as_str()?)
+    };
+    let manifest_license = manifest.and_then(|(text, format)| match format {
+        ManifestFormat::Json => json_field(text, "license"),
+        ManifestFormat::Toml => {
+            non_empty(text.parse::<toml::Table>().ok()?.get("license")?.as_str()?)
+        }
+    });
+    manifest_license
+        .or_else(|| json_field(uplugin?, "License"))
+        .or_else(|| {
+            license_texts
+                .iter()
+                .find_map(|text| identify(text))
+                .map(String::from)
+        })
+}
+
+fn is_license_file(file_name: &str) -> bool {
+    let stem = file_name
+        .split('.')
+        .next()
+        .unwrap_or(file_name)
+        .to_ascii_uppercase();
+    LICENSE_STEMS.iter().any(|license| {
+        stem == *license
+            || stem
+                .strip_prefix(license)
+                .is_some_and(|rest| rest.starts_with(['-', '_']))
+    })
+}
+
+/// License of the plugin in a package tarball, read without extracting it
+///
+/// Tarballs have the plugin files at their root or in a single folder.
+pub fn from_tarball(tarball_path: &Path) -> Result<Option<String>> {
+    let mut manifest = None;
+    let mut uplugin = None;
+    let mut license_texts = Vec::new();
+
+    let mut archive = Archive::new(GzDecoder::new(File::open(tarball_path)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let depth = path
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .count();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()).map(String::from) else {
+            continue;
+        };
+        if depth > 2 || !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let manifest_format = [ManifestFormat::Json, ManifestFormat::Toml]
+            .into_iter()
+            .find(|format| file_name == format.file_name());
+        let wanted = manifest_format.is_some()
+            || file_name.ends_with(".uplugin")
+            || is_license_file(&file_name);
+        if !wanted {
+            continue;
+        }
+        let mut text = String::new();
+        if entry.read_to_string(&mut text).is_err() {
+            continue;
+        }
+        if let Some(format) = manifest_format {
+            manifest = Some((text, format));
+        } else if file_name.ends_with(".uplugin") {
+            uplugin = Some(text);
+        } else {
+            license_texts.push(text);
+        }
+    }
+    Ok(from_files(
+        manifest
+            .as_ref()
+            .map(|(text, format)| (text.as_str(), *format)),
+        uplugin.as_deref(),
+        &license_texts,
+    ))
+}
+
+/// License of an installed plugin
+pub fn from_dir<P: AsRef<Path>>(plugin_dir: P) -> Option<String> {
+    let plugin_dir = plugin_dir.as_ref();
+    let manifest = crate::Manifest::detect(plugin_dir)
+        .ok()
+        .flatten()
+        .and_then(|format| {
+            let text = fs::read_to_string(plugin_dir.join(format.file_name())).ok()?;
+            Some((text, format))
+        });
+    let uplugin = crate::UPlugin::find(plugin_dir)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok());
+    let mut license_files: Vec<_> = fs::read_dir(plugin_dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_name().to_str().is_some_and(is_license_file))
+        .map(|entry| entry.path())
+        .collect();
+    license_files.sort();
+    let license_texts: Vec<String> = license_files
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect();
+    from_files(
+        manifest
+            .as_ref()
+            .map(|(text, format)| (text.as_str(), *format)),
+        uplugin.as_deref(),
+        &license_texts,
+    )
+}
+
+/// Plain-text license report, one aligned row per package
+pub fn render_text(licenses: &[PackageLicense]) -> String {
+    let width = licenses
+        .iter()
+        .map(|entry| entry.name.len() + entry.version.len() + 1)
+        .max()
+        .unwrap_or(0);
+    licenses
+        .iter()
+        .map(|entry| {
+            format!(
+                "{:<width$}  {}\n",
+                format!("{}@{}", entry.name, entry.version),
+                entry.license.as_deref().unwrap_or("unknown"),
+                width = width
+            )
+        })
+        .collect()
+}
+
+/// License report as a JSON array
+pub fn render_json(licenses: &[PackageLicense]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(licenses)?)
+}
+
+/// License report as CSV with a `name,version,license` header
+pub fn render_csv(licenses: &[PackageLicense]) -> String {
+    let quote = |field: &str| {
+        if field.contains([',', '"', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    };
+    let mut csv = String::from("name,version,license\n");
+    for entry in licenses {
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            quote(&entry.name),
+            quote(&entry.version),
+            quote(entry.license.as_deref().unwrap_or(""))
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    #[test]
+    fn test_identify() {
+        assert_eq!(
+            identify("                                 Apache License\n                           Version 2.0, January 2004"),
+            Some("Apache-2.0")
+        );
+        assert_eq!(
+            identify("Copyright (c) 2024 Studio\n\nPermission is hereby granted, free of charge, to any person"),
+            Some("MIT")
+        );
+        assert_eq!(
+            identify("Redistribution and use in source and binary forms, with or without\nmodification... Neither the name of the copyright holder"),
+            Some("BSD-3-Clause")
+        );
+        assert_eq!(
+            identify("GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007"),
+            Some("GPL-3.0")
+        );
+        assert_eq!(identify("All rights reserved."), None);
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        let allowed = vec![
+            "MIT".to_string(),
+            "apache-2.0".to_string(),
+            "GPL-3.0".to_string(),
+        ];
+        assert!(is_allowed("MIT", &allowed));
+        assert!(is_allowed("Apache-2.0", &allowed));
+        assert!(is_allowed("GPL-3.0-only", &allowed));
+        assert!(is_allowed("BSD-3-Clause OR MIT", &allowed));
+        assert!(is_allowed("(MIT AND Apache-2.0)", &allowed));
+        assert!(!is_allowed("MIT AND BSD-3-Clause", &allowed));
+        assert!(!is_allowed("Proprietary", &allowed));
+        assert!(!is_allowed("", &allowed));
+    }
+
+    #[test]
+    fn test_license_precedence() {
+        let mit = ["Permission is hereby granted, free of charge".to_string()];
+        assert_eq!(
+            from_files(
+                Some((r#"{"license": "Apache-2.0"}"#, ManifestFormat::Json)),
+                Some(r#"{"License": "BSD-2-Clause"}"#),
+                &mit
+            ),
+            Some("Apache-2.0".to_string())
+        );
+        assert_eq!(
+            from_files(None, Some(r#"{"License": "BSD-2-Clause"}"#), &mit),
+            Some("BSD-2-Clause".to_string())
+        );
+        assert_eq!(
+            from_files(Some(("{}", ManifestFormat::Json)), None, &mit),
+            Some("MIT".to_string())
+        );
+        // Manifests may be JSONC or TOML
+        assert_eq!(
+            from_files(
+                Some((
+                    "{\n  // SPDX\n  \"license\": \"MPL-2.0\",\n}",
+                    ManifestFormat::Json
+                )),
+                None,
+                &mit
+            ),
+            Some("MPL-2.0".to_string())
+        );
+        assert_eq!(
+            from_files(
+                Some(("license = \"ISC\"\n", ManifestFormat::Toml)),
+                None,
+                &mit
+            ),
+            Some("ISC".to_string())
+        );
+        assert_eq!(
+            from_files(
+                Some((r#"{"license": " "}"#, ManifestFormat::Json)),
+                None,
+                &mit
+            ),
+            Some("MIT".to_string())
+        );
+        assert_eq!(from_files(None, None, &[]), None);
+    }
+
+    #[test]
+    fn test_from_tarball() {
+        let dir = tempfile::tempdir().unwrap();
+        let tarball = dir.path().join("plugin.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&tarball).unwrap(),
+            Compression::default(),
+        ));
+        let mut add = |path: &str, contents: &str| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        };
+        add("Awesome/Awesome.uplugin", r#"{"FriendlyName": "Awesome"}"#);
+        add(
+            "Awesome/LICENSE.md",
+            "Permission is hereby granted, free of charge",
+        );
+        add(
+            "Awesome/Source/ThirdParty/zlib/LICENSE",
+            "This software is provided 'as-is'",
+        );
+        builder.into_inner().unwrap().finish().unwrap();
+
+        assert_eq!(from_tarball(&tarball).unwrap(), Some("MIT".to_string()));
+    }
+
+    #[test]
+    fn test_render() {
+        let licenses = vec![
+            PackageLicense {
+                name: "awesome".to_string(),
+                version: "1.0.0".to_string(),
+                license: Some("MIT OR Apache-2.0".to_string()),
+            },
+            PackageLicense {
+                name: "core".to_string(),
+                version: "2.1.0".to_string(),
+                license: None,
+            },
+        ];
+        assert_eq!(
+            render_text(&licenses),
+            "awesome@1.0.0  MIT OR Apache-2.0\ncore@2.1.0     unknown\n"
+        );
+        assert_eq!(
+            render_csv(&licenses),
+            "name,version,license\nawesome,1.0.0,MIT OR Apache-2.0\ncore,2.1.0,\n"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_json(&licenses).unwrap()).unwrap();
+        assert_eq!(json[1]["license"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,297 @@
+//! Package size and file-count limits for pack and publish
+//!
+//! `pack` and `publish` scan the tarball they just built and check it against
+//! the `[limits]` config, so a stray `Saved/` folder, a DerivedDataCache, or
+//! a multi-gigabyte debug symbol file is caught before anyone downloads it.
+//! The scan also reports the largest files and folders, which is usually
+//! enough to see what slipped in.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use unrealpm::limits::PackageContents;
+//! use unrealpm::LimitsConfig;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let contents = PackageContents::scan("MyPlugin-1.0.0.tar.gz")?;
+//! for violation in contents.check(&LimitsConfig::default()) {
+//!     println!("{}", violation);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::disk::format_bytes;
+use crate::{LimitsConfig, Result};
+use flate2::read::GzDecoder;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use tar::Archive;
+
+const MB: u64 = 1024 * 1024;
+
+/// One file inside a package tarball
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackedEntry {
+    /// Path inside the tarball, e.g. `MyPlugin/Content/Big.uasset`
+    pub path: String,
+    /// Uncompressed size in bytes
+    pub size: u64,
+}
+
+/// A limit the package exceeds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitViolation {
+    /// The compressed tarball is larger than `limits.max_package_mb`
+    PackageTooLarge { size: u64, max: u64 },
+    /// A single file is larger than `limits.max_file_mb`
+    FileTooLarge { path: String, size: u64, max: u64 },
+    /// The package has more files than `limits.max_files`
+    TooManyFiles { count: usize, max: usize },
+}
+
+impl std::fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PackageTooLarge { size, max } => write!(
+                f,
+                "Package is {} (limit {}, limits.max_package_mb)",
+                format_bytes(*size),
+                format_bytes(*max)
+            ),
+            Self::FileTooLarge { path, size, max } => write!(
+                f,
+                "{} is {} (limit {}, limits.max_file_mb)",
+                path,
+                format_bytes(*size),
+                format_bytes(*max)
+            ),
+            Self::TooManyFiles { count, max } => write!(
+                f,
+                "Package has {} files (limit {}, limits.max_files)",
+                count, max
+            ),
+        }
+    }
+}
+
+/// What a package tarball contains
+#[derive(Debug, Clone, Default)]
+pub struct PackageContents {
+    pub entries: Vec<PackedEntry>,
+    /// Size of the compressed tarball
+    pub compressed_size: u64,
+}
+
+impl PackageContents {
+    /// List the files in a `.tar.gz`
+    pub fn scan<P: AsRef<Path>>(tarball: P) -> Result<Self> {
+        let tarball = tarball.as_ref();
+        let compressed_size = std::fs::metadata(tarball)?.len();
+        let mut archive = Archive::new(GzDecoder::new(File::open(tarball)?));
+        let mut entries = Vec::new();
+        for entry in archive.entries()? {
+            let entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            entries.push(PackedEntry {
+                path: entry.path()?.to_string_lossy().into_owned(),
+                size: entry.size(),
+            });
+        }
+        Ok(Self {
+            entries,
+            compressed_size,
+        })
+    }
+
+    /// Total uncompressed size
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    /// Every limit the package exceeds; empty when it fits
+    pub fn check(&self, limits: &LimitsConfig) -> Vec<LimitViolation> {
+        let mut violations = Vec::new();
+        let max_package = limits.max_package_mb.saturating_mul(MB);
+        if max_package > 0 && self.compressed_size > max_package {
+            violations.push(LimitViolation::PackageTooLarge {
+                size: self.compressed_size,
+                max: max_package,
+            });
+        }
+        if limits.max_files > 0 && self.entries.len() > limits.max_files {
+            violations.push(LimitViolation::TooManyFiles {
+                count: self.entries.len(),
+                max: limits.max_files,
+            });
+        }
+        let max_file = limits.max_file_mb.saturating_mul(MB);
+        if max_file > 0 {
+            let mut large: Vec<&PackedEntry> =
+                self.entries.iter().filter(|e| e.size > max_file).collect();
+            large.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+            violations.extend(large.into_iter().map(|e| LimitViolation::FileTooLarge {
+                path: e.path.clone(),
+                size: e.size,
+                max: max_file,
+            }));
+        }
+        violations
+    }
+
+    /// The `n` largest files, largest first
+    pub fn largest_files(&self, n: usize) -> Vec<&PackedEntry> {
+        let mut files: Vec<&PackedEntry> = self.entries.iter().collect();
+        files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        files.truncate(n);
+        files
+    }
+
+    /// Size and file count per folder directly under the plugin root,
+    /// largest first (`n` entries)
+    ///
+    /// Files at the root are grouped under `.`.
+    pub fn largest_folders(&self, n: usize) -> Vec<(String, u64, usize)> {
+        let mut folders: HashMap<String, (u64, usize)> = HashMap::new();
+        for entry in &self.entries {
+            // Paths are rooted at the plugin folder: <Plugin>/<folder>/...
+            let mut parts = entry.path.split('/').skip(1);
+            let folder = match (parts.next(), parts.next()) {
+                (Some(folder), Some(_)) => folder.to_string(),
+                _ => ".".to_string(),
+            };
+            let total = folders.entry(folder).or_default();
+            total.0 += entry.size;
+            total.1 += 1;
+        }
+        let mut folders: Vec<(String, u64, usize)> = folders
+            .into_iter()
+            .map(|(folder, (size, count))| (folder, size, count))
+            .collect();
+        folders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        folders.truncate(n);
+        folders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(files: &[(&str, u64)], compressed_size: u64) -> PackageContents {
+        PackageContents {
+            entries: files
+                .iter()
+                .map(|(path, size)| PackedEntry {
+                    path: path.to_string(),
+                    size: *size,
+                })
+                .collect(),
+            compressed_size,
+        }
+    }
+
+    #[test]
+    fn test_check_reports_every_exceeded_limit() {
+        let limits = LimitsConfig {
+            max_package_mb: 1,
+            max_file_mb: 1,
+            max_files: 2,
+        };
+        let package = contents(
+            &[
+                ("Kit/Kit.uplugin", 100),
+                ("Kit/Saved/Crash.dmp", 3 * MB),
+                ("Kit/Binaries/Win64/Kit.pdb", 5 * MB),
+            ],
+            2 * MB,
+        );
+
+        let violations = package.check(&limits);
+        assert_eq!(violations.len(), 4);
+        assert!(matches!(
+            violations[0],
+            LimitViolation::PackageTooLarge { .. }
+        ));
+        assert_eq!(
+            violations[1],
+            LimitViolation::TooManyFiles { count: 3, max: 2 }
+        );
+        // Largest offender first
+        assert!(
+            matches!(&violations[2], LimitViolation::FileTooLarge { path, .. } if path.ends_with(".pdb"))
+        );
+        assert!(violations[3].to_string().contains("limits.max_file_mb"));
+
+        // 0 disables a limit
+        let unlimited = LimitsConfig {
+            max_package_mb: 0,
+            max_file_mb: 0,
+            max_files: 0,
+        };
+        assert!(package.check(&unlimited).is_empty());
+
+        // Huge limits saturate rather than overflow
+        let huge = LimitsConfig {
+            max_package_mb: u64::MAX,
+            max_file_mb: u64::MAX,
+            max_files: 0,
+        };
+        assert!(package.check(&huge).is_empty());
+    }
+
+    #[test]
+    fn test_breakdown_by_file_and_folder() {
+        let package = contents(
+            &[
+                ("Kit/Kit.uplugin", 10),
+                ("Kit/Content/A.uasset", 300),
+                ("Kit/Content/Maps/B.umap", 500),
+                ("Kit/Source/Kit.cpp", 200),
+            ],
+            100,
+        );
+        assert_eq!(package.total_size(), 1010);
+        assert_eq!(package.largest_files(1)[0].path, "Kit/Content/Maps/B.umap");
+        assert_eq!(
+            package.largest_folders(10),
+            vec![
+                ("Content".to_string(), 800, 2),
+                ("Source".to_string(), 200, 1),
+                (".".to_string(), 10, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_tarball() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let tarball = temp.path().join("kit.tar.gz");
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            File::create(&tarball).unwrap(),
+            flate2::Compression::default(),
+        ));
+        let data = vec![7u8; 4096];
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "Kit/Content/Big.uasset", data.as_slice())
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let package = PackageContents::scan(&tarball).unwrap();
+        assert_eq!(
+            package.entries,
+            vec![PackedEntry {
+                path: "Kit/Content/Big.uasset".to_string(),
+                size: 4096
+            }]
+        );
+        assert!(package.compressed_size > 0 && package.compressed_size < 4096);
+    }
+}
@@ -0,0 +1,329 @@
+//! Plugins linked into a project from a local directory
+//!
+//! A path dependency (`"my-plugin": { "path": "../MyPlugin" }`) is not
+//! installed from the registry. `Plugins/<name>` becomes a link to the
+//! plugin's working copy instead: a symlink on Linux and macOS, and a
+//! directory symlink or, without the privilege to create one, a junction on
+//! Windows. Edits in the working copy show up in the project immediately.
+//!
+//! The lockfile records linked plugins with a `link+<path>` source and no
+//! checksum, since their contents change as they are developed.
+//!
+//! `unrealpm link` does the same without touching unrealpm.json: a plugin
+//! author registers a working copy once in [`GlobalLinks`], then links it
+//! into any project by name. Installs and updates leave such links in place
+//! and keep locking the registry version, so teammates are unaffected.
+
+use crate::{Config, Error, Manifest, Result, UPlugin};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lockfile source prefix for plugins linked from a local directory
+pub const LINK_SOURCE_PREFIX: &str = "link+";
+
+/// A path dependency checked against its directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedPlugin {
+    /// Dependency name, which is also the folder name under Plugins/
+    pub name: String,
+    /// Path as written in unrealpm.json
+    pub path: String,
+    /// Absolute plugin directory
+    pub dir: PathBuf,
+    /// `VersionName` from the plugin's .uplugin
+    pub version: String,
+    /// Runtime dependencies from the plugin's own manifest, if it has one
+    pub dependencies: HashMap<String, String>,
+}
+
+impl LinkedPlugin {
+    /// Check a path dependency of the project in `project_dir`
+    ///
+    /// The directory must exist and contain a .uplugin. Its unrealpm.json
+    /// dependencies (if any) are read so they can be installed from the
+    /// registry alongside the project's own.
+    pub fn inspect(project_dir: &Path, name: &str, path: &str) -> Result<Self> {
+        let dir = project_dir.join(path);
+        if !dir.is_dir() {
+            return Err(Error::Other(format!(
+                "Path dependency {} points to {}, which is not a directory",
+                name,
+                dir.display()
+            )));
+        }
+        let dir = dir.canonicalize()?;
+        let uplugin = UPlugin::load(UPlugin::find(&dir)?)?;
+        let dependencies = if Manifest::exists(&dir) {
+            Manifest::load(&dir)?.dependencies
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            path: path.to_string(),
+            dir,
+            version: uplugin.version_name,
+            dependencies,
+        })
+    }
+
+    /// Lockfile source string for this plugin (`link+<path>`)
+    pub fn source(&self) -> String {
+        format!("{}{}", LINK_SOURCE_PREFIX, self.path)
+    }
+}
+
+/// Plugin working copies registered with `unrealpm link`, shared by every
+/// project on the machine
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GlobalLinks {
+    #[serde(skip)]
+    path: PathBuf,
+
+    /// Plugin name -> absolute plugin directory
+    links: BTreeMap<String, PathBuf>,
+}
+
+impl GlobalLinks {
+    /// Empty registry that saves to `path`
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            links: BTreeMap::new(),
+        }
+    }
+
+    /// Registry stored at `path`; empty if the file doesn't exist yet
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut links = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str::<Self>(&content)
+                .map_err(|e| Error::Other(format!("Failed to parse {}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+        links.path = path.to_path_buf();
+        Ok(links)
+    }
+
+    /// Registry next to the user config (`~/.unrealpm/links.json`)
+    pub fn open_default() -> Result<Self> {
+        let config_path = Config::default_path()?;
+        let dir = config_path.parent().unwrap_or(Path::new("."));
+        Self::load(dir.join("links.json"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize links: {}", e)))?;
+        fs::write(&self.path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Register `dir` under `name`, returning the directory it replaces
+    pub fn register(&mut self, name: &str, dir: PathBuf) -> Option<PathBuf> {
+        self.links.insert(name.to_string(), dir)
+    }
+
+    /// Forget `name`, returning its directory if it was registered
+    pub fn unregister(&mut self, name: &str) -> Option<PathBuf> {
+        self.links.remove(name)
+    }
+
+    /// Directory registered under `name`; names match case-insensitively,
+    /// like Unreal plugin names
+    pub fn get(&self, name: &str) -> Option<(&str, &Path)> {
+        self.links
+            .get_key_value(name)
+            .or_else(|| {
+                self.links
+                    .iter()
+                    .find(|(registered, _)| registered.eq_ignore_ascii_case(name))
+            })
+            .map(|(name, dir)| (name.as_str(), dir.as_path()))
+    }
+
+    /// Registered plugins, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Path)> {
+        self.links
+            .iter()
+            .map(|(name, dir)| (name.as_str(), dir.as_path()))
+    }
+}
+
+/// Where `link` points, if it is a link
+pub fn link_target(link: &Path) -> Option<PathBuf> {
+    is_link(link).then(|| fs::read_link(link).ok()).flatten()
+}
+
+/// True if `path` is a symlink or junction (whether or not its target exists)
+pub fn is_link(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// True if `link` is a link that resolves to `target`
+pub fn points_to(link: &Path, target: &Path) -> bool {
+    is_link(link)
+        && match (link.canonicalize(), target.canonicalize()) {
+            (Ok(link), Ok(target)) => link == target,
+            _ => false,
+        }
+}
+
+/// Make `link` a directory link to `target`
+///
+/// An existing link at `link` is replaced; a real directory or file is not,
+/// so a plugin folder is never deleted to make room for a link.
+pub fn link_dir(target: &Path, link: &Path) -> Result<()> {
+    if is_link(link) {
+        remove_link(link)?;
+    } else if link.exists() {
+        return Err(Error::Other(format!(
+            "{} already exists and is not a link; move it away to link {}",
+            link.display(),
+            target.display()
+        )));
+    }
+    if let Some(parent) = link.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    create_link(target, link)
+}
+
+/// Remove a link without touching the directory it points to
+pub fn remove_link(link: &Path) -> Result<()> {
+    if !is_link(link) {
+        return Err(Error::Other(format!("{} is not a link", link.display())));
+    }
+    // Directory symlinks and junctions are directories to Windows
+    #[cfg(windows)]
+    fs::remove_dir(link)?;
+    #[cfg(not(windows))]
+    fs::remove_file(link)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_link(target: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn create_link(target: &Path, link: &Path) -> Result<()> {
+    if std::os::windows::fs::symlink_dir(target, link).is_ok() {
+        return Ok(());
+    }
+    // Symlinks need Developer Mode or admin rights; junctions don't
+    let status = std::process::Command::new("cmd")
+        .args(["/C", "mklink", "/J"])
+        .arg(link)
+        .arg(target)
+        .stdout(std::process::Stdio::null())
+        .status()?;
+    if !status.success() {
+        return Err(Error::Other(format!(
+            "Failed to create a junction from {} to {}",
+            link.display(),
+            target.display()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_plugin(dir: &Path, name: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join(format!("{}.uplugin", name)),
+            r#"{"FileVersion": 3, "Version": 3, "VersionName": "0.3.0", "FriendlyName": "Mine"}"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_inspect() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Game");
+        fs::create_dir_all(&project).unwrap();
+        let plugin = temp.path().join("MyPlugin");
+        write_plugin(&plugin, "MyPlugin");
+        fs::write(
+            plugin.join("unrealpm.json"),
+            r#"{"dependencies": {"alpha": "^1.0"}}"#,
+        )
+        .unwrap();
+
+        let linked = LinkedPlugin::inspect(&project, "my-plugin", "../MyPlugin").unwrap();
+        assert_eq!(linked.version, "0.3.0");
+        assert_eq!(linked.dir, plugin.canonicalize().unwrap());
+        assert_eq!(linked.dependencies["alpha"], "^1.0");
+        assert_eq!(linked.source(), "link+../MyPlugin");
+
+        assert!(LinkedPlugin::inspect(&project, "missing", "../Missing").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_dir() {
+        let temp = TempDir::new().unwrap();
+        let plugin = temp.path().join("MyPlugin");
+        write_plugin(&plugin, "MyPlugin");
+        let link = temp.path().join("Game/Plugins/my-plugin");
+
+        link_dir(&plugin, &link).unwrap();
+        assert!(is_link(&link));
+        assert!(points_to(&link, &plugin));
+        assert!(link.join("MyPlugin.uplugin").exists());
+
+        // Relinking replaces the link; a real directory is left alone
+        link_dir(&plugin, &link).unwrap();
+        let real = temp.path().join("Game/Plugins/real");
+        fs::create_dir_all(&real).unwrap();
+        assert!(link_dir(&plugin, &real).is_err());
+        assert!(remove_link(&real).is_err());
+
+        remove_link(&link).unwrap();
+        assert!(!link.exists());
+        assert!(plugin.join("MyPlugin.uplugin").exists());
+    }
+
+    #[test]
+    fn test_global_links() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("links.json");
+
+        let mut links = GlobalLinks::load(&path).unwrap();
+        assert_eq!(links.iter().count(), 0);
+        assert!(links
+            .register("MyPlugin", temp.path().join("MyPlugin"))
+            .is_none());
+        links.save().unwrap();
+
+        let mut links = GlobalLinks::load(&path).unwrap();
+        let (name, dir) = links.get("myplugin").unwrap();
+        assert_eq!(name, "MyPlugin");
+        assert_eq!(dir, temp.path().join("MyPlugin"));
+        assert!(links.get("Other").is_none());
+
+        assert!(links.unregister("MyPlugin").is_some());
+        assert!(links.unregister("MyPlugin").is_none());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(GlobalLinks::load(&path).is_err());
+    }
+}
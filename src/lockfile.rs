@@ -24,6 +24,14 @@
 //!     version: "1.2.0".to_string(),
 //!     checksum: "sha256:abc123...".to_string(),
 //!     dependencies: Some(HashMap::new()),
+//!     patched_hash: None,
+//!     source: None,
+//!     binary: None,
+//!     dev: false,
+//!     features: Vec::new(),
+//!     partial: false,
+//!     registry: None,
+//!     license: None,
 //! });
 //! lockfile.packages = packages;
 //! lockfile.save()?;
@@ -31,9 +39,9 @@
 //! # }
 //! ```
 
-use crate::{Error, Result};
+use crate::{Error, PrebuiltBinary, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
@@ -63,20 +71,94 @@ pub struct LockfileMetadata {
 
     /// Timestamp when the lockfile was generated (ISO 8601 format)
     pub generated_at: String,
+
+    /// Engine version (e.g. "5.3") the pre-built binaries were chosen for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
 }
 
 /// Information about a locked package
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LockedPackage {
     /// Exact version installed
     pub version: String,
 
-    /// SHA256 checksum of the tarball
+    /// SHA256 checksum of the tarball; empty for linked path dependencies
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub checksum: String,
 
     /// Dependencies of this package (name -> version constraint)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dependencies: Option<HashMap<String, String>>,
+
+    /// Tree hash of the installed package after local patches were applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patched_hash: Option<String>,
+
+    /// Source for packages not installed from the registry: `path+<dir>` or
+    /// `tarball+<file>` for local installs, `link+<dir>` for linked path
+    /// dependencies, `fab+<id>` for externally managed Fab/Marketplace plugins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Pre-built binary installed instead of the source tarball; `None` for source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<PrebuiltBinary>,
+
+    /// Only needed by development dependencies; skipped by
+    /// `install --production`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dev: bool,
+
+    /// Features enabled for this package, whose optional dependencies were
+    /// resolved with it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+
+    /// Installed without its Content/ folder by `install --source-only-files`;
+    /// the next normal install replaces it with the complete package
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+
+    /// Registry the package was downloaded from when it isn't the configured
+    /// one (`--registry`); later installs fetch it from there again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+
+    /// SPDX license expression found in the package when it was installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+}
+
+impl LockedPackage {
+    /// Identifier of the installed tarball: the binary id, or the version for source
+    pub fn artifact_id(&self) -> &str {
+        self.binary
+            .as_ref()
+            .map_or(self.version.as_str(), |b| b.tarball.as_str())
+    }
+
+    /// Checksum of the installed tarball
+    pub fn artifact_checksum(&self) -> &str {
+        self.binary
+            .as_ref()
+            .map_or(self.checksum.as_str(), |b| b.checksum.as_str())
+    }
+
+    /// True for Fab/Marketplace plugins that UnrealPM records but does not install
+    pub fn is_externally_managed(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|s| s.starts_with(crate::marketplace::FAB_SOURCE_PREFIX))
+    }
+
+    /// Directory of a path dependency linked into Plugins/, as written in
+    /// unrealpm.json
+    pub fn linked_path(&self) -> Option<&str> {
+        self.source
+            .as_deref()
+            .and_then(|s| s.strip_prefix(crate::link::LINK_SOURCE_PREFIX))
+    }
 }
 
 impl Lockfile {
@@ -86,14 +168,22 @@ impl Lockfile {
             metadata: LockfileMetadata {
                 unrealpm_version: env!("CARGO_PKG_VERSION").to_string(),
                 generated_at: chrono::Utc::now().to_rfc3339(),
+                engine: None,
             },
             packages: HashMap::new(),
         }
     }
 
     /// Load lockfile from the current directory
+    ///
+    /// A directory with a lockfile is registered in the project index, so
+    /// `unrealpm store gc` keeps the store entries it refers to.
     pub fn load() -> Result<Option<Self>> {
-        Self::load_from(LOCKFILE_NAME)
+        let lockfile = Self::load_from(LOCKFILE_NAME)?;
+        if lockfile.is_some() {
+            crate::store::register_current_project();
+        }
+        Ok(lockfile)
     }
 
     /// Load lockfile from a specific path
@@ -105,10 +195,13 @@ impl Lockfile {
         }
 
         let contents = fs::read_to_string(path)?;
-        let lockfile: Lockfile = toml::from_str(&contents)
-            .map_err(|e| Error::Other(format!("Failed to parse lockfile: {}", e)))?;
+        Self::parse(&contents).map(Some)
+    }
 
-        Ok(Some(lockfile))
+    /// Parse lockfile contents (e.g., read from a git revision)
+    pub fn parse(contents: &str) -> Result<Self> {
+        toml::from_str(contents)
+            .map_err(|e| Error::Other(format!("Failed to parse lockfile: {}", e)))
     }
 
     /// Save lockfile to the current directory
@@ -121,8 +214,9 @@ impl Lockfile {
         let toml_string = toml::to_string_pretty(self)
             .map_err(|e| Error::Other(format!("Failed to serialize lockfile: {}", e)))?;
 
-        fs::write(path.as_ref(), toml_string)?;
-        Ok(())
+        crate::vcs::track(&[path.as_ref()], || {
+            Ok(fs::write(path.as_ref(), toml_string)?)
+        })
     }
 
     /// Add or update a package in the lockfile
@@ -139,6 +233,14 @@ impl Lockfile {
                 version,
                 checksum,
                 dependencies,
+                patched_hash: None,
+                source: None,
+                binary: None,
+                dev: false,
+                features: Vec::new(),
+                partial: false,
+                registry: None,
+                license: None,
             },
         );
 
@@ -146,6 +248,141 @@ impl Lockfile {
         self.metadata.generated_at = chrono::Utc::now().to_rfc3339();
     }
 
+    /// Record the patched tree hash for a package (`None` if unpatched)
+    ///
+    /// Returns false if the package is not in the lockfile.
+    pub fn set_patched_hash(&mut self, name: &str, patched_hash: Option<String>) -> bool {
+        match self.packages.get_mut(name) {
+            Some(package) => {
+                package.patched_hash = patched_hash;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record the local source of a package (`None` for registry packages)
+    ///
+    /// Returns false if the package is not in the lockfile.
+    pub fn set_source(&mut self, name: &str, source: Option<String>) -> bool {
+        match self.packages.get_mut(name) {
+            Some(package) => {
+                package.source = source;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record the pre-built binary installed for a package (`None` for source)
+    ///
+    /// Returns false if the package is not in the lockfile.
+    pub fn set_binary(&mut self, name: &str, binary: Option<PrebuiltBinary>) -> bool {
+        match self.packages.get_mut(name) {
+            Some(package) => {
+                package.binary = binary;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record whether a package was installed without its Content/ folder
+    ///
+    /// Returns false if the package is not in the lockfile.
+    pub fn set_partial(&mut self, name: &str, partial: bool) -> bool {
+        match self.packages.get_mut(name) {
+            Some(package) => {
+                package.partial = partial;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record the registry a package was downloaded from (`None` for the
+    /// configured registry)
+    ///
+    /// Returns false if the package is not in the lockfile.
+    pub fn set_registry(&mut self, name: &str, registry: Option<String>) -> bool {
+        match self.packages.get_mut(name) {
+            Some(package) => {
+                package.registry = registry;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record the license found in a package (`None` if there was none)
+    ///
+    /// Returns false if the package is not in the lockfile.
+    pub fn set_license(&mut self, name: &str, license: Option<String>) -> bool {
+        match self.packages.get_mut(name) {
+            Some(package) => {
+                package.license = license;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Packages installed without their Content/ folder, sorted by name
+    pub fn partial_packages(&self) -> Vec<&String> {
+        let mut names: Vec<&String> = self
+            .packages
+            .iter()
+            .filter(|(_, package)| package.partial)
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Record the engine version artifacts were selected for; `None` (no
+    /// engine known) keeps the previous one
+    pub fn set_engine(&mut self, engine: Option<&str>) {
+        if let Some(engine) = engine {
+            self.metadata.engine = Some(engine.to_string());
+        }
+    }
+
+    /// Locked pre-built binaries that weren't built for `engine`, sorted by name
+    ///
+    /// After the project switches engines these need a binary for the new
+    /// engine or a source build (`unrealpm rebuild --stale`).
+    pub fn stale_binaries(&self, engine: &str) -> Vec<(&String, &PrebuiltBinary)> {
+        let mut stale: Vec<_> = self
+            .packages
+            .iter()
+            .filter_map(|(name, package)| Some((name, package.binary.as_ref()?)))
+            .filter(|(_, binary)| {
+                !crate::platform::engine_versions_compatible(&binary.engine, engine)
+            })
+            .collect();
+        stale.sort_by(|a, b| a.0.cmp(b.0));
+        stale
+    }
+
+    /// Mark the packages in `dev_only` as development-only and every other
+    /// package as a runtime package
+    pub fn set_dev_packages(&mut self, dev_only: &[String]) {
+        for (name, package) in &mut self.packages {
+            package.dev = dev_only.contains(name);
+        }
+    }
+
+    /// Record the features enabled per package (name -> feature names);
+    /// packages not in `features` have none
+    pub fn set_features(&mut self, features: &HashMap<String, Vec<String>>) {
+        for (name, package) in &mut self.packages {
+            let mut enabled = features.get(name).cloned().unwrap_or_default();
+            enabled.sort();
+            enabled.dedup();
+            package.features = enabled;
+        }
+    }
+
     /// Remove a package from the lockfile
     pub fn remove_package(&mut self, name: &str) -> Option<LockedPackage> {
         let removed = self.packages.remove(name);
@@ -168,10 +405,224 @@ impl Lockfile {
         self.packages.contains_key(name)
     }
 
+    /// Locked checksum for `name`, if it is locked at `version` with a different checksum
+    ///
+    /// A hit means the registry now serves different contents for a version
+    /// that was already locked.
+    pub fn checksum_conflict(&self, name: &str, version: &str, checksum: &str) -> Option<&str> {
+        self.packages
+            .get(name)
+            .filter(|locked| locked.version == version)
+            .filter(|locked| !locked.checksum.eq_ignore_ascii_case(checksum))
+            .map(|locked| locked.checksum.as_str())
+    }
+
+    /// Whether `name` is locked at exactly this version and artifact
+    ///
+    /// A package locked at the same version but a different binary (or
+    /// source instead of a binary) is not.
+    pub fn is_locked_at(&self, name: &str, version: &str, binary: Option<&PrebuiltBinary>) -> bool {
+        self.packages
+            .get(name)
+            .is_some_and(|locked| locked.version == version && locked.binary.as_ref() == binary)
+    }
+
     /// Get the number of packages in the lockfile
     pub fn package_count(&self) -> usize {
         self.packages.len()
     }
+
+    /// Registry packages that no package in `roots` reaches, sorted by name
+    ///
+    /// Reachability follows the locked dependency lists. Packages from other
+    /// sources (local paths, Fab) are never reported, since a registry
+    /// resolution doesn't know about them.
+    pub fn unreachable_packages<'a>(
+        &self,
+        roots: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<String> {
+        let mut reached: HashSet<&str> = HashSet::new();
+        let mut pending: Vec<&str> = roots.into_iter().collect();
+        while let Some(name) = pending.pop() {
+            if !reached.insert(name) {
+                continue;
+            }
+            if let Some(deps) = self
+                .packages
+                .get(name)
+                .and_then(|p| p.dependencies.as_ref())
+            {
+                pending.extend(deps.keys().map(String::as_str));
+            }
+        }
+
+        let mut unreachable: Vec<String> = self
+            .packages
+            .iter()
+            .filter(|(name, package)| package.source.is_none() && !reached.contains(name.as_str()))
+            .map(|(name, _)| name.clone())
+            .collect();
+        unreachable.sort();
+        unreachable
+    }
+
+    /// Ways this lockfile is out of sync with a manifest, sorted
+    ///
+    /// `dependencies` are the manifest's direct dependencies (name ->
+    /// constraint) and `features` the features it enables on them. Empty
+    /// when installing from the lockfile gives exactly what the manifest asks
+    /// for: every dependency is locked at a version its constraint allows,
+    /// with the same features, and nothing else is locked.
+    pub fn manifest_mismatches(
+        &self,
+        dependencies: &HashMap<String, String>,
+        features: &HashMap<String, Vec<String>>,
+    ) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        for (name, constraint) in dependencies {
+            let Some(locked) = self.packages.get(name) else {
+                mismatches.push(format!("{} is in the manifest but not locked", name));
+                continue;
+            };
+            let allowed = match (
+                semver::VersionReq::parse(constraint),
+                semver::Version::parse(&locked.version),
+            ) {
+                (Ok(req), Ok(version)) => req.matches(&version),
+                _ => true,
+            };
+            if !allowed {
+                mismatches.push(format!(
+                    "{} is locked at {}, which does not satisfy {}",
+                    name, locked.version, constraint
+                ));
+            }
+        }
+
+        for (name, locked) in &self.packages {
+            let mut wanted = features.get(name).cloned().unwrap_or_default();
+            wanted.sort();
+            wanted.dedup();
+            if locked.features != wanted {
+                mismatches.push(format!(
+                    "{} is locked with features [{}] but the manifest enables [{}]",
+                    name,
+                    locked.features.join(", "),
+                    wanted.join(", ")
+                ));
+            }
+        }
+
+        for name in self.unreachable_packages(dependencies.keys().map(String::as_str)) {
+            mismatches.push(format!(
+                "{} is locked but nothing in the manifest needs it",
+                name
+            ));
+        }
+
+        mismatches.sort();
+        mismatches
+    }
+
+    /// Package changes from this lockfile to `newer`, sorted by name
+    pub fn diff(&self, newer: &Lockfile) -> Vec<LockChange> {
+        let mut names: Vec<&String> = self.packages.keys().chain(newer.packages.keys()).collect();
+        names.sort();
+        names.dedup();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let old = self.packages.get(name);
+                let new = newer.packages.get(name);
+                let kind = match (old, new) {
+                    (None, Some(_)) => LockChangeKind::Added,
+                    (Some(_), None) => LockChangeKind::Removed,
+                    (Some(old), Some(new)) if old.version != new.version => {
+                        match (
+                            semver::Version::parse(&old.version),
+                            semver::Version::parse(&new.version),
+                        ) {
+                            (Ok(o), Ok(n)) if n < o => LockChangeKind::Downgraded,
+                            (Ok(o), Ok(n)) if n > o => LockChangeKind::Upgraded,
+                            _ => LockChangeKind::Changed,
+                        }
+                    }
+                    (Some(old), Some(new))
+                        if old.checksum != new.checksum
+                            || old.source != new.source
+                            || old.registry != new.registry
+                            || old.patched_hash != new.patched_hash
+                            || old.binary != new.binary =>
+                    {
+                        LockChangeKind::Changed
+                    }
+                    _ => return None,
+                };
+
+                Some(LockChange {
+                    name: name.clone(),
+                    kind,
+                    old: old.cloned(),
+                    new: new.cloned(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// How a package changed between two lockfiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockChangeKind {
+    Added,
+    Removed,
+    Upgraded,
+    Downgraded,
+    /// Same version (or incomparable versions) with a different checksum,
+    /// source, patch, or pre-built binary
+    Changed,
+}
+
+impl std::fmt::Display for LockChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LockChangeKind::Added => "added",
+            LockChangeKind::Removed => "removed",
+            LockChangeKind::Upgraded => "upgraded",
+            LockChangeKind::Downgraded => "downgraded",
+            LockChangeKind::Changed => "changed",
+        })
+    }
+}
+
+/// One package entry that differs between two lockfiles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockChange {
+    pub name: String,
+    pub kind: LockChangeKind,
+
+    /// Entry in the older lockfile (`None` if added)
+    pub old: Option<LockedPackage>,
+
+    /// Entry in the newer lockfile (`None` if removed)
+    pub new: Option<LockedPackage>,
+}
+
+impl LockChange {
+    /// True when both lockfiles have the package but its checksum differs
+    pub fn checksum_changed(&self) -> bool {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => old.checksum != new.checksum,
+            _ => false,
+        }
+    }
+
+    /// True when the checksum changed while the version stayed the same,
+    /// i.e. the same release now has different contents
+    pub fn is_suspicious(&self) -> bool {
+        self.checksum_changed()
+            && self.old.as_ref().map(|p| &p.version) == self.new.as_ref().map(|p| &p.version)
+    }
 }
 
 impl Default for Lockfile {
@@ -232,6 +683,31 @@ mod tests {
         assert_eq!(lockfile.package_count(), 0);
     }
 
+    #[test]
+    fn test_unreachable_packages() {
+        let mut lockfile = Lockfile::new();
+        let deps = |names: &[&str]| {
+            Some(
+                names
+                    .iter()
+                    .map(|n| (n.to_string(), "^1.0.0".to_string()))
+                    .collect(),
+            )
+        };
+        lockfile.update_package("app".into(), "1.0.0".into(), "a".into(), deps(&["util"]));
+        lockfile.update_package("util".into(), "1.0.0".into(), "u".into(), deps(&[]));
+        lockfile.update_package("old".into(), "1.0.0".into(), "o".into(), deps(&["gone"]));
+        lockfile.update_package("gone".into(), "1.0.0".into(), "g".into(), None);
+        lockfile.update_package("local".into(), "0.1.0".into(), "l".into(), None);
+        lockfile.set_source("local", Some("path+../Local".to_string()));
+
+        assert_eq!(lockfile.unreachable_packages(["app"]), ["gone", "old"]);
+        assert_eq!(
+            lockfile.unreachable_packages(["app", "old"]),
+            Vec::<String>::new()
+        );
+    }
+
     #[test]
     fn test_lockfile_serialization() {
         let mut lockfile = Lockfile::new();
@@ -248,4 +724,423 @@ mod tests {
         assert!(toml_string.contains("1.0.0"));
         assert!(toml_string.contains("abc123"));
     }
+
+    #[test]
+    fn test_lockfile_patched_hash() {
+        let mut lockfile = Lockfile::new();
+        assert!(!lockfile.set_patched_hash("test-package", Some("def456".to_string())));
+
+        lockfile.update_package(
+            "test-package".to_string(),
+            "1.0.0".to_string(),
+            "abc123".to_string(),
+            None,
+        );
+        assert!(lockfile.set_patched_hash("test-package", Some("def456".to_string())));
+
+        let toml_string = toml::to_string(&lockfile).unwrap();
+        assert!(toml_string.contains("patched_hash = \"def456\""));
+
+        let parsed: Lockfile = toml::from_str(&toml_string).unwrap();
+        assert_eq!(
+            parsed.get_package("test-package").unwrap().patched_hash,
+            Some("def456".to_string())
+        );
+
+        // Re-locking a package clears the patched hash until patches are re-applied
+        lockfile.update_package(
+            "test-package".to_string(),
+            "1.0.1".to_string(),
+            "abc124".to_string(),
+            None,
+        );
+        assert_eq!(
+            lockfile.get_package("test-package").unwrap().patched_hash,
+            None
+        );
+    }
+
+    #[test]
+    fn test_lockfile_source() {
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package(
+            "local-plugin".to_string(),
+            "0.1.0".to_string(),
+            "abc123".to_string(),
+            None,
+        );
+        assert!(lockfile.set_source("local-plugin", Some("path+../LocalPlugin".to_string())));
+
+        let toml_string = toml::to_string(&lockfile).unwrap();
+        assert!(toml_string.contains("source = \"path+../LocalPlugin\""));
+
+        let parsed: Lockfile = toml::from_str(&toml_string).unwrap();
+        assert_eq!(
+            parsed
+                .get_package("local-plugin")
+                .unwrap()
+                .source
+                .as_deref(),
+            Some("path+../LocalPlugin")
+        );
+
+        assert!(!parsed
+            .get_package("local-plugin")
+            .unwrap()
+            .is_externally_managed());
+        lockfile.set_source("local-plugin", Some("fab+abc123".to_string()));
+        assert!(lockfile
+            .get_package("local-plugin")
+            .unwrap()
+            .is_externally_managed());
+
+        // Registry packages omit the field entirely
+        lockfile.set_source("local-plugin", None);
+        assert!(!toml::to_string(&lockfile).unwrap().contains("source"));
+    }
+
+    #[test]
+    fn test_lockfile_linked_path() {
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package(
+            "my-plugin".to_string(),
+            "0.3.0".to_string(),
+            String::new(),
+            None,
+        );
+        lockfile.set_source("my-plugin", Some("link+../MyPlugin".to_string()));
+
+        // Linked plugins are locked without a checksum
+        let toml_string = toml::to_string(&lockfile).unwrap();
+        assert!(!toml_string.contains("checksum"));
+        let parsed: Lockfile = toml::from_str(&toml_string).unwrap();
+        let locked = parsed.get_package("my-plugin").unwrap();
+        assert_eq!(locked.checksum, "");
+        assert_eq!(locked.linked_path(), Some("../MyPlugin"));
+        assert!(!locked.is_externally_managed());
+    }
+
+    #[test]
+    fn test_lockfile_dev_packages() {
+        let mut lockfile = Lockfile::new();
+        for name in ["runtime", "test-framework"] {
+            lockfile.update_package(
+                name.to_string(),
+                "1.0.0".to_string(),
+                "abc".to_string(),
+                None,
+            );
+        }
+        lockfile.set_dev_packages(&["test-framework".to_string()]);
+
+        // Only dev packages carry the flag
+        let toml_string = toml::to_string(&lockfile).unwrap();
+        assert_eq!(toml_string.matches("dev = true").count(), 1);
+        let parsed: Lockfile = toml::from_str(&toml_string).unwrap();
+        assert!(parsed.get_package("test-framework").unwrap().dev);
+        assert!(!parsed.get_package("runtime").unwrap().dev);
+    }
+
+    #[test]
+    fn test_lockfile_features() {
+        let mut lockfile = Lockfile::new();
+        for name in ["vfx-toolkit", "niagara-helpers"] {
+            lockfile.update_package(
+                name.to_string(),
+                "1.0.0".to_string(),
+                "abc".to_string(),
+                None,
+            );
+        }
+        let features = HashMap::from([(
+            "vfx-toolkit".to_string(),
+            vec!["niagara-support".to_string(), "editor".to_string()],
+        )]);
+        lockfile.set_features(&features);
+
+        let toml_string = toml::to_string(&lockfile).unwrap();
+        assert_eq!(toml_string.matches("features = ").count(), 1);
+        let parsed: Lockfile = toml::from_str(&toml_string).unwrap();
+        assert_eq!(
+            parsed.get_package("vfx-toolkit").unwrap().features,
+            vec!["editor".to_string(), "niagara-support".to_string()]
+        );
+        assert!(parsed
+            .get_package("niagara-helpers")
+            .unwrap()
+            .features
+            .is_empty());
+    }
+
+    #[test]
+    fn test_manifest_mismatches() {
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package(
+            "app".to_string(),
+            "1.2.0".to_string(),
+            "abc".to_string(),
+            Some(HashMap::from([("core".to_string(), "^1.0".to_string())])),
+        );
+        lockfile.update_package(
+            "core".to_string(),
+            "1.0.0".to_string(),
+            "def".to_string(),
+            None,
+        );
+        lockfile.update_package(
+            "old".to_string(),
+            "0.1.0".to_string(),
+            "123".to_string(),
+            None,
+        );
+
+        let manifest = HashMap::from([("app".to_string(), "^1.0".to_string())]);
+        assert_eq!(
+            lockfile.manifest_mismatches(&manifest, &HashMap::new()),
+            vec!["old is locked but nothing in the manifest needs it"]
+        );
+
+        lockfile.remove_package("old");
+        assert!(lockfile
+            .manifest_mismatches(&manifest, &HashMap::new())
+            .is_empty());
+
+        let manifest = HashMap::from([
+            ("app".to_string(), "^2.0".to_string()),
+            ("new".to_string(), "*".to_string()),
+        ]);
+        let features = HashMap::from([("app".to_string(), vec!["extra".to_string()])]);
+        assert_eq!(
+            lockfile.manifest_mismatches(&manifest, &features),
+            vec![
+                "app is locked at 1.2.0, which does not satisfy ^2.0",
+                "app is locked with features [] but the manifest enables [extra]",
+                "new is in the manifest but not locked",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lockfile_binary() {
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package(
+            "awesome-plugin".to_string(),
+            "1.0.0".to_string(),
+            "source123".to_string(),
+            None,
+        );
+        let binary = PrebuiltBinary {
+            platform: "Win64".to_string(),
+            engine: "5.3".to_string(),
+            tarball: "1.0.0-Win64-5.3".to_string(),
+            checksum: "binary456".to_string(),
+            configuration: Some("Development".to_string()),
+        };
+        assert!(lockfile.is_locked_at("awesome-plugin", "1.0.0", None));
+        assert!(lockfile.set_binary("awesome-plugin", Some(binary.clone())));
+        assert!(lockfile.is_locked_at("awesome-plugin", "1.0.0", Some(&binary)));
+        assert!(!lockfile.is_locked_at("awesome-plugin", "1.0.0", None));
+        assert!(!lockfile.is_locked_at("awesome-plugin", "1.0.1", Some(&binary)));
+
+        let toml_string = toml::to_string(&lockfile).unwrap();
+        assert!(toml_string.contains("[package.awesome-plugin.binary]"));
+
+        let parsed: Lockfile = toml::from_str(&toml_string).unwrap();
+        let locked = parsed.get_package("awesome-plugin").unwrap();
+        assert_eq!(locked.binary.as_ref(), Some(&binary));
+        assert_eq!(locked.artifact_id(), "1.0.0-Win64-5.3");
+        assert_eq!(locked.artifact_checksum(), "binary456");
+
+        // Source installs omit the table
+        lockfile.set_binary("awesome-plugin", None);
+        assert!(!toml::to_string(&lockfile).unwrap().contains("binary"));
+        let locked = lockfile.get_package("awesome-plugin").unwrap();
+        assert_eq!(locked.artifact_id(), "1.0.0");
+        assert_eq!(locked.artifact_checksum(), "source123");
+    }
+
+    #[test]
+    fn test_lockfile_partial() {
+        let mut lockfile = Lockfile::new();
+        for name in ["core", "awesome"] {
+            lockfile.update_package(
+                name.to_string(),
+                "1.0.0".to_string(),
+                "abc".to_string(),
+                None,
+            );
+        }
+        assert!(lockfile.set_partial("core", true));
+        assert!(lockfile.set_partial("awesome", true));
+        assert!(!lockfile.set_partial("missing", true));
+        assert_eq!(lockfile.partial_packages(), vec!["awesome", "core"]);
+
+        let parsed: Lockfile = toml::from_str(&toml::to_string(&lockfile).unwrap()).unwrap();
+        assert!(parsed.get_package("core").unwrap().partial);
+
+        // A normal install replaces the entry with a complete one
+        lockfile.update_package(
+            "core".to_string(),
+            "1.0.0".to_string(),
+            "abc".to_string(),
+            None,
+        );
+        assert_eq!(lockfile.partial_packages(), vec!["awesome"]);
+    }
+
+    #[test]
+    fn test_lockfile_registry() {
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package(
+            "core".to_string(),
+            "1.0.0".to_string(),
+            "abc".to_string(),
+            None,
+        );
+        assert!(lockfile.set_registry("core", Some("https://partner.example.com".to_string())));
+        assert!(!lockfile.set_registry("missing", None));
+
+        let toml_string = toml::to_string(&lockfile).unwrap();
+        assert!(toml_string.contains("registry = \"https://partner.example.com\""));
+        let parsed: Lockfile = toml::from_str(&toml_string).unwrap();
+        assert_eq!(
+            parsed.get_package("core").unwrap().registry.as_deref(),
+            Some("https://partner.example.com")
+        );
+
+        lockfile.set_registry("core", None);
+        assert!(!toml::to_string(&lockfile).unwrap().contains("registry"));
+
+        assert!(lockfile.set_license("core", Some("MIT OR Apache-2.0".to_string())));
+        assert!(toml::to_string(&lockfile)
+            .unwrap()
+            .contains("license = \"MIT OR Apache-2.0\""));
+    }
+
+    #[test]
+    fn test_stale_binaries() {
+        let mut lockfile = Lockfile::new();
+        for (name, engine) in [
+            ("b-plugin", "5.3"),
+            ("a-plugin", "5.3.2"),
+            ("c-plugin", "5.4"),
+        ] {
+            lockfile.update_package(name.into(), "1.0.0".into(), "sha256:src".into(), None);
+            lockfile.set_binary(
+                name,
+                Some(PrebuiltBinary {
+                    platform: "Win64".to_string(),
+                    engine: engine.to_string(),
+                    tarball: format!("1.0.0-Win64-{}", engine),
+                    checksum: "sha256:bin".to_string(),
+                    configuration: None,
+                }),
+            );
+        }
+        lockfile.update_package("source".into(), "1.0.0".into(), "sha256:src".into(), None);
+
+        let stale: Vec<&str> = lockfile
+            .stale_binaries("5.4")
+            .into_iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(stale, ["a-plugin", "b-plugin"]);
+        assert!(lockfile.stale_binaries("5.4.1").len() == 2);
+
+        lockfile.set_engine(Some("5.4"));
+        lockfile.set_engine(None);
+        let parsed: Lockfile = toml::from_str(&toml::to_string(&lockfile).unwrap()).unwrap();
+        assert_eq!(parsed.metadata.engine.as_deref(), Some("5.4"));
+    }
+
+    #[test]
+    fn test_lockfile_diff() {
+        let mut old = Lockfile::new();
+        old.update_package("kept".into(), "1.0.0".into(), "sha256:aaa".into(), None);
+        old.update_package("removed".into(), "0.3.0".into(), "sha256:bbb".into(), None);
+        old.update_package("upgraded".into(), "1.2.0".into(), "sha256:ccc".into(), None);
+        old.update_package(
+            "downgraded".into(),
+            "2.0.0".into(),
+            "sha256:ddd".into(),
+            None,
+        );
+        old.update_package("repacked".into(), "1.0.0".into(), "sha256:eee".into(), None);
+
+        let mut new = old.clone();
+        new.remove_package("removed");
+        new.update_package("added".into(), "0.1.0".into(), "sha256:fff".into(), None);
+        new.update_package("upgraded".into(), "1.3.0".into(), "sha256:ggg".into(), None);
+        new.update_package(
+            "downgraded".into(),
+            "1.9.0".into(),
+            "sha256:hhh".into(),
+            None,
+        );
+        new.update_package("repacked".into(), "1.0.0".into(), "sha256:iii".into(), None);
+        old.update_package("rebuilt".into(), "1.0.0".into(), "sha256:jjj".into(), None);
+        new.update_package("rebuilt".into(), "1.0.0".into(), "sha256:jjj".into(), None);
+        new.set_binary(
+            "rebuilt",
+            Some(PrebuiltBinary {
+                platform: "Win64".to_string(),
+                engine: "5.3".to_string(),
+                tarball: "1.0.0-Win64-5.3".to_string(),
+                checksum: "sha256:kkk".to_string(),
+                configuration: None,
+            }),
+        );
+
+        let changes = old.diff(&new);
+        let summary: Vec<(&str, LockChangeKind)> =
+            changes.iter().map(|c| (c.name.as_str(), c.kind)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("added", LockChangeKind::Added),
+                ("downgraded", LockChangeKind::Downgraded),
+                ("rebuilt", LockChangeKind::Changed),
+                ("removed", LockChangeKind::Removed),
+                ("repacked", LockChangeKind::Changed),
+                ("upgraded", LockChangeKind::Upgraded),
+            ]
+        );
+
+        let repacked = changes.iter().find(|c| c.name == "repacked").unwrap();
+        assert!(repacked.checksum_changed());
+        assert!(repacked.is_suspicious());
+
+        let upgraded = changes.iter().find(|c| c.name == "upgraded").unwrap();
+        assert!(upgraded.checksum_changed());
+        assert!(!upgraded.is_suspicious());
+
+        assert!(new.diff(&new).is_empty());
+
+        assert_eq!(
+            old.checksum_conflict("repacked", "1.0.0", "sha256:iii"),
+            Some("sha256:eee")
+        );
+        assert_eq!(
+            old.checksum_conflict("repacked", "1.0.0", "SHA256:EEE"),
+            None
+        );
+        assert_eq!(
+            old.checksum_conflict("repacked", "1.1.0", "sha256:iii"),
+            None
+        );
+        assert_eq!(
+            old.checksum_conflict("unknown", "1.0.0", "sha256:iii"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_lockfile_parse() {
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package("a".into(), "1.0.0".into(), "sha256:aaa".into(), None);
+        let parsed = Lockfile::parse(&toml::to_string(&lockfile).unwrap()).unwrap();
+        assert_eq!(parsed.get_package("a"), lockfile.get_package("a"));
+
+        assert!(Lockfile::parse("not = [valid").is_err());
+    }
 }
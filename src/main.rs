@@ -1,4 +1,4 @@
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::{generate, Shell};
 
 mod commands;
@@ -8,6 +8,21 @@ mod commands;
 #[command(name = "unrealpm")]
 #[command(author, version, about, long_about = None)]
 struct Cli {
+    /// Treat warnings (unsigned packages, skipped engine checks, missing
+    /// lockfile entries) as errors; also enabled by [ui] strict = true
+    #[arg(long, global = true)]
+    strict: bool,
+
+    /// Plain text output: no spinners, box drawing, emoji, or colors (automatic
+    /// when stdout is not a terminal or TERM=dumb)
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// Use the HTTP registry at this URL instead of the configured one for
+    /// this command; packages installed from it record it in unrealpm.lock
+    #[arg(long, global = true, value_name = "URL")]
+    registry: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -15,12 +30,35 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize a new UnrealPM project
-    Init,
+    Init {
+        /// .uproject file to use when the directory contains several
+        #[arg(long, value_name = "FILE")]
+        uproject: Option<String>,
+
+        /// Create unrealpm.toml instead of unrealpm.json
+        #[arg(long)]
+        toml: bool,
+    },
 
     /// Install a package
+    #[command(visible_alias = "i")]
     Install {
-        /// Package name (e.g., awesome-plugin@1.2.0)
-        package: Option<String>,
+        /// Packages to install, resolved together (e.g., awesome-plugin@1.2.0 other-plugin)
+        #[arg(value_name = "PACKAGE")]
+        package: Vec<String>,
+
+        /// Install from a local plugin directory, bypassing the registry
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["package", "offline", "tarball"])]
+        path: Option<String>,
+
+        /// Install from a local package tarball, bypassing the registry
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["package", "offline"])]
+        tarball: Option<String>,
+
+        /// Enable optional features of the package (e.g., --features niagara-support);
+        /// saved to unrealpm.json
+        #[arg(long, value_delimiter = ',', requires = "package")]
+        features: Vec<String>,
 
         /// Force install even if engine version is incompatible
         #[arg(short, long)]
@@ -42,14 +80,32 @@ enum Commands {
         #[arg(long, conflicts_with = "source_only")]
         binary_only: bool,
 
+        /// Leave out plugins' Content/ folders, for compile-only CI jobs;
+        /// unrealpm.lock marks these installs as partial
+        #[arg(long, conflicts_with_all = ["path", "tarball", "offline", "workspace"])]
+        source_only_files: bool,
+
         /// Show what would be installed without actually installing
         #[arg(long)]
         dry_run: bool,
 
-        /// Offline mode - install from lockfile and cache only, no network requests
+        /// Offline mode - resolve and install from the vendored mirror (unrealpm vendor),
+        /// or from lockfile and cache only when there is none; no network requests
         #[arg(long)]
         offline: bool,
 
+        /// Take over plugin folders in Plugins/ that unrealpm.lock doesn't list, keeping their files
+        #[arg(long, conflicts_with = "overwrite")]
+        adopt: bool,
+
+        /// Replace plugin folders in Plugins/ that unrealpm.lock doesn't list
+        #[arg(long)]
+        overwrite: bool,
+
+        /// Apply the install plan without asking, however many packages it changes
+        #[arg(short, long)]
+        yes: bool,
+
         /// Show verbose conflict information during dependency resolution
         #[arg(long)]
         verbose_resolve: bool,
@@ -61,12 +117,81 @@ enum Commands {
         /// Resolution timeout in seconds (0 = no timeout)
         #[arg(long)]
         resolve_timeout: Option<u64>,
+
+        /// Always resolve from scratch instead of reusing a cached resolution
+        #[arg(long)]
+        no_resolve_cache: bool,
+
+        /// Skip dev_dependencies and the packages only they need
+        #[arg(long, conflicts_with_all = ["package", "path", "tarball"])]
+        production: bool,
+
+        /// Resolve as usual, but fail if unrealpm.lock would change
+        #[arg(long, conflicts_with_all = ["package", "path", "tarball", "offline", "workspace"])]
+        locked: bool,
+
+        /// Install exactly what unrealpm.lock records without resolving; fail
+        /// if it is out of sync with unrealpm.json
+        #[arg(long, conflicts_with_all = ["package", "path", "tarball", "offline", "workspace"])]
+        frozen: bool,
+
+        /// Install all members of the workspace (unrealpm.workspace.json) together
+        #[arg(long, conflicts_with_all = ["package", "path", "tarball", "offline", "production"])]
+        workspace: bool,
+
+        /// Number of packages to download and verify at once
+        /// (default: network.max_parallel_downloads, or 4)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Print phase durations (resolve/download/verify/extract/build) and cache hit rates
+        #[arg(long)]
+        timings: bool,
+    },
+
+    /// Clean install of exactly what unrealpm.lock records (for CI)
+    ///
+    /// Removes the plugins unrealpm installed and reinstalls them from the
+    /// lockfile without resolving. Fails if the lockfile is missing or out of
+    /// sync with unrealpm.json.
+    Ci {
+        /// Skip dev_dependencies and the packages only they need
+        #[arg(long)]
+        production: bool,
+
+        /// Leave out plugins' Content/ folders, for compile-only CI jobs;
+        /// unrealpm.lock marks these installs as partial
+        #[arg(long)]
+        source_only_files: bool,
+
+        /// Number of packages to download and verify at once
+        /// (default: network.max_parallel_downloads, or 4)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Print phase durations (resolve/download/verify/extract/build) and cache hit rates
+        #[arg(long)]
+        timings: bool,
     },
 
     /// Uninstall a package
+    #[command(visible_alias = "rm")]
     Uninstall {
-        /// Package name
-        package: String,
+        /// Packages to remove
+        #[arg(value_name = "PACKAGE", required_unless_present = "all")]
+        package: Vec<String>,
+
+        /// Remove every installed plugin, keeping unrealpm.json and unrealpm.lock
+        #[arg(long, conflicts_with = "package")]
+        all: bool,
+
+        /// Don't ask for confirmation (with --all)
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Update packages
@@ -74,10 +199,35 @@ enum Commands {
         /// Specific package to update (optional)
         package: Option<String>,
 
+        /// Prefer pre-built binaries, fall back to source if not available
+        #[arg(long)]
+        prefer_binary: bool,
+
+        /// Only install from source (skip pre-built binaries)
+        #[arg(long)]
+        source_only: bool,
+
+        /// Only install pre-built binaries (fail if not available)
+        #[arg(long, conflicts_with = "source_only")]
+        binary_only: bool,
+
         /// Show what would be updated without actually updating
         #[arg(long)]
         dry_run: bool,
 
+        /// Update to the newest engine-compatible versions regardless of constraints,
+        /// rewriting them in unrealpm.json (style from install.save_style)
+        #[arg(long)]
+        latest: bool,
+
+        /// Update all members of the workspace (unrealpm.workspace.json) together
+        #[arg(long, conflicts_with_all = ["package", "latest"])]
+        workspace: bool,
+
+        /// Resolve and install from the vendored mirror (unrealpm vendor), no network requests
+        #[arg(long)]
+        offline: bool,
+
         /// Show verbose conflict information during dependency resolution
         #[arg(long)]
         verbose_resolve: bool,
@@ -89,13 +239,88 @@ enum Commands {
         /// Resolution timeout in seconds (0 = no timeout)
         #[arg(long)]
         resolve_timeout: Option<u64>,
+
+        /// Number of packages to download and verify at once
+        /// (default: network.max_parallel_downloads, or 4)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Print phase durations (resolve/download/verify/extract/build) and cache hit rates
+        #[arg(long)]
+        timings: bool,
+    },
+
+    /// Link a plugin's working copy into this project instead of installing it
+    ///
+    /// Run without a name in a plugin folder to register it; run with a name
+    /// in a project to link the registered plugin into Plugins/.
+    Link {
+        /// Registered plugin to link into the current project
+        plugin: Option<String>,
+    },
+
+    /// Remove a link made with `unrealpm link`
+    ///
+    /// Run without a name in a plugin folder to unregister it.
+    Unlink {
+        /// Linked plugin to remove from the current project
+        plugin: Option<String>,
+    },
+
+    /// Copy locked packages into unrealpm-vendor/ for offline installs
+    Vendor {
+        /// Show what would be vendored without copying anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// List installed packages
-    List,
+    #[command(visible_alias = "ls")]
+    List {
+        /// Show disk usage per plugin and subfolder, largest first
+        #[arg(long)]
+        tree_size: bool,
+    },
+
+    /// Report locked versions with security advisories or that were yanked
+    Audit {
+        /// Exit with a non-zero code if any finding is at this level or above
+        /// (`warnings` includes yanked versions and recent checksum incidents)
+        #[arg(long, value_enum, value_name = "LEVEL")]
+        deny: Option<AuditLevel>,
+    },
 
     /// Check for outdated packages
-    Outdated,
+    Outdated {
+        /// Check all members of the workspace (unrealpm.workspace.json)
+        #[arg(long)]
+        workspace: bool,
+
+        /// Exit with a non-zero code if any packages are outdated
+        #[arg(long)]
+        exit_code: bool,
+    },
+
+    /// Check which dependencies support a new engine version and what to bump
+    MigrateEngine {
+        /// Target engine version (e.g., 5.4)
+        engine: String,
+
+        /// Bump constraints in unrealpm.json where the new version stays in the same major
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Report plugin versions and duplicate installs across many projects
+    Report {
+        /// Directory tree to scan for projects with unrealpm.lock
+        #[arg(long, value_name = "DIR")]
+        scan: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Create a package tarball without publishing
     Pack {
@@ -118,18 +343,96 @@ enum Commands {
     /// Show dependency tree
     Tree,
 
+    /// Check installed packages (and applied patches) against the lockfile
+    Check,
+
+    /// Check unrealpm.json for invalid version constraints and other errors
+    Validate,
+
+    /// Inspect and compare lockfiles
+    Lock {
+        #[command(subcommand)]
+        action: LockAction,
+    },
+
+    /// Print the managed plugin environment for custom build pipelines
+    Env {
+        /// Emit UnrealBuildTool/UAT arguments (-Project=, -EnablePlugin=)
+        #[arg(long, conflicts_with = "json")]
+        ubt: bool,
+
+        /// Emit JSON for Build.cs/Target.cs scripts
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Write the license and notice files of installed packages to ThirdPartyNotices.txt
+    Notices {
+        /// Output file (default: ThirdPartyNotices.txt in the project)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Print to stdout instead of writing a file
+        #[arg(long, conflicts_with = "output")]
+        stdout: bool,
+    },
+
+    /// Print the license of every locked package
+    Licenses {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = LicenseFormat::Text)]
+        format: LicenseFormat,
+    },
+
+    /// Export a software bill of materials (SBOM) for the locked packages
+    Sbom {
+        /// SBOM standard to write
+        #[arg(long, value_enum, default_value_t = SbomFormat::Cyclonedx)]
+        format: SbomFormat,
+
+        /// Output file (default: print to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
     /// Explain why a package is installed
     Why {
         /// Package name
         package: String,
     },
 
+    /// Show a package's details from the registry, or render its README
+    Info {
+        /// Package name with optional version (e.g., awesome-plugin@1.0.0)
+        package: String,
+
+        /// Render the package's README in the terminal
+        #[arg(long)]
+        readme: bool,
+
+        /// Print the README directly instead of through $PAGER
+        #[arg(long, requires = "readme")]
+        no_pager: bool,
+
+        /// List the files in the package tarball (downloads it if not cached)
+        #[arg(long, conflicts_with = "readme")]
+        files: bool,
+    },
+
     /// Search for packages in the registry
     Search {
-        /// Search query
+        /// Search query (optional with --category)
+        #[arg(default_value = "")]
         query: String,
+
+        /// Only show packages in this category (e.g., "Networking")
+        #[arg(short, long)]
+        category: Option<String>,
     },
 
+    /// List package categories in the registry
+    Categories,
+
     /// Publish a package to the registry
     Publish {
         /// Path to plugin directory (defaults to current directory)
@@ -143,9 +446,13 @@ enum Commands {
         #[arg(long)]
         include_binaries: bool,
 
-        /// Target engine version (e.g., 4.27, 5.3) - for engine-specific builds
+        /// Target engine version (e.g., 4.27, 5.3) - for engine-specific builds; repeat to publish for several engines at once
         #[arg(long)]
-        engine: Option<String>,
+        engine: Vec<String>,
+
+        /// Publish the --engine values as one multi-engine version instead of one version per engine
+        #[arg(long, requires = "engine")]
+        multi_engine: bool,
 
         /// Git repository URL (for automatic updates)
         #[arg(long)]
@@ -156,6 +463,32 @@ enum Commands {
         git_ref: Option<String>,
     },
 
+    /// Attach a pre-built binary for one platform to a published version
+    PublishBinary {
+        /// Path to plugin directory (defaults to current directory)
+        path: Option<String>,
+
+        /// Platform of the binaries to attach (Win64, Linux, Mac)
+        #[arg(short, long)]
+        platform: String,
+
+        /// Engine version the binaries were built with (e.g., 5.3)
+        #[arg(short, long)]
+        engine: String,
+
+        /// Build configuration of the binaries (e.g., Shipping); omit for a single unspecified build
+        #[arg(long = "config", value_name = "CONFIGURATION")]
+        configuration: Option<String>,
+
+        /// Overwrite a different binary already attached for this platform/engine
+        #[arg(long)]
+        replace_binary: bool,
+
+        /// Show what would be published without actually publishing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Build plugin binaries for specified engine/platform
     Build {
         /// Path to plugin directory (defaults to current directory)
@@ -172,6 +505,25 @@ enum Commands {
         /// Build all configured platforms
         #[arg(long)]
         all_platforms: bool,
+
+        /// Build configurations (e.g., Shipping or Development,Shipping); defaults to build.configuration
+        #[arg(long = "config", value_name = "CONFIGURATION", value_delimiter = ',')]
+        configurations: Vec<String>,
+    },
+
+    /// Reinstall pre-built plugin binaries for the engine the .uproject uses
+    ///
+    /// Installs the binary for the project's engine where one is published,
+    /// otherwise the source. Updates the engine in unrealpm.json to match
+    /// the .uproject.
+    Rebuild {
+        /// Only packages whose binaries were built for another engine
+        #[arg(long)]
+        stale: bool,
+
+        /// Show what would be reinstalled without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Manage the package cache
@@ -180,12 +532,42 @@ enum Commands {
         action: CacheAction,
     },
 
+    /// Manage the global package store shared by all projects
+    Store {
+        #[command(subcommand)]
+        action: StoreAction,
+    },
+
+    /// Manage the local file registry
+    Registry {
+        #[command(subcommand)]
+        action: RegistryAction,
+    },
+
+    /// Keep installed plugins out of version control
+    VcsIgnore {
+        #[command(subcommand)]
+        action: VcsIgnoreAction,
+    },
+
     /// Manage configuration
     Config {
         #[command(subcommand)]
         action: ConfigAction,
     },
 
+    /// Register, verify, and set up Unreal Engine installations
+    Engine {
+        #[command(subcommand)]
+        action: EngineAction,
+    },
+
+    /// List and pin Fab/Marketplace plugins (externally managed)
+    Fab {
+        #[command(subcommand)]
+        action: FabAction,
+    },
+
     /// Diagnose setup issues
     Doctor {
         /// Show detailed output
@@ -197,6 +579,13 @@ enum Commands {
         fix: bool,
     },
 
+    /// Bundle diagnostics, sanitized config, and recent logs for a bug report
+    ReportBug {
+        /// Where to write the bundle (default: ./unrealpm-bug-report-<timestamp>.tar.gz)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
     /// Manage signing keys
     Keys {
         #[command(subcommand)]
@@ -206,11 +595,69 @@ enum Commands {
     /// Verify package signature
     Verify {
         /// Package name with optional version (e.g., awesome-plugin@1.0.0)
-        package: String,
+        #[arg(
+            required_unless_present_any = ["all", "cross_check"],
+            conflicts_with = "all"
+        )]
+        package: Option<String>,
+
+        /// Verify the checksums of every package in unrealpm.lock
+        #[arg(long)]
+        all: bool,
+
+        /// Number of packages to verify at once (with --all)
+        #[arg(short, long, default_value_t = unrealpm::pipeline::DEFAULT_JOBS)]
+        jobs: usize,
+
+        /// Compare the locked checksums with what the registry and each
+        /// mirror publish, to detect tampering or mirror drift
+        #[arg(long)]
+        cross_check: bool,
+    },
+
+    /// Accept a new publisher key for a package, or list pinned publisher keys
+    Trust {
+        /// Package whose publisher key to trust
+        #[arg(required_unless_present = "list")]
+        package: Option<String>,
+
+        /// Hex public key to trust (default: the key of the latest signed version)
+        #[arg(long, conflicts_with = "list")]
+        key: Option<String>,
+
+        /// List the publisher keys pinned on this machine
+        #[arg(long, conflicts_with = "package")]
+        list: bool,
+    },
+
+    /// Write a signed attestation of the dependencies and engine used for a build
+    Attest {
+        #[command(subcommand)]
+        action: Option<AttestAction>,
+
+        /// Attestation file to write (defaults to unrealpm-attestation.json)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Build identifier to record (e.g., CI build number or changelist)
+        #[arg(long)]
+        build_id: Option<String>,
     },
 
     /// Register for UnrealPM registry
-    Register,
+    Register {
+        /// Sign up with GitHub (creates the account on first sign-in)
+        #[arg(long, conflicts_with = "email")]
+        github: bool,
+
+        /// Sign up with email/password
+        #[arg(long, conflicts_with = "github")]
+        email: bool,
+
+        /// Don't wait for the email address to be verified
+        #[arg(long)]
+        no_wait: bool,
+    },
 
     /// Login to UnrealPM registry
     Login {
@@ -229,6 +676,21 @@ enum Commands {
     /// Show current logged-in user
     Whoami,
 
+    /// Update unrealpm itself to the latest release
+    SelfUpdate {
+        /// Release channel to follow (default: [self_update] channel, or stable)
+        #[arg(long, value_name = "stable|beta")]
+        channel: Option<unrealpm::self_update::ReleaseChannel>,
+
+        /// Only report whether an update is available
+        #[arg(long)]
+        check: bool,
+
+        /// Reinstall even when already on the latest release
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Unpublish a package version (or entire package)
     Unpublish {
         /// Package name with optional version (e.g., my-plugin@1.0.0 or my-plugin)
@@ -237,18 +699,46 @@ enum Commands {
         /// Specific version to unpublish (alternative to package@version syntax)
         #[arg(short, long)]
         version: Option<String>,
+
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List the published versions of a package, or yank/un-yank them in bulk
+    #[command(args_conflicts_with_subcommands = true)]
+    Versions {
+        /// Package name
+        package: Option<String>,
+
+        #[command(subcommand)]
+        action: Option<VersionsAction>,
     },
 
     /// Yank a package version (prevent new installs)
     Yank {
         /// Package name with version (e.g., my-plugin@1.0.0)
         package: String,
+
+        /// Show what would be yanked without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Un-yank a package version (allow installs again)
     Unyank {
         /// Package name with version (e.g., my-plugin@1.0.0)
         package: String,
+
+        /// Show what would be un-yanked without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Verify your email or reset your password without a browser
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
     },
 
     /// Manage API tokens
@@ -265,6 +755,66 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum VersionsAction {
+    /// Yank every version older than a given version
+    Yank {
+        /// Package name
+        package: String,
+
+        /// Select versions lower than this one (e.g., 1.0.0)
+        #[arg(long, value_name = "VERSION")]
+        older_than: String,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show which versions would be yanked without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Un-yank every yanked version older than a given version
+    Unyank {
+        /// Package name
+        package: String,
+
+        /// Select versions lower than this one (e.g., 1.0.0)
+        #[arg(long, value_name = "VERSION")]
+        older_than: String,
+
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show which versions would be un-yanked without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthAction {
+    /// Show verification status or submit the code from the verification email
+    VerifyEmail {
+        /// Code (or full link) from the verification email
+        #[arg(conflicts_with = "resend")]
+        code: Option<String>,
+
+        /// Send the verification email again
+        #[arg(long)]
+        resend: bool,
+    },
+
+    /// Reset a forgotten password using a code sent by email
+    ResetPassword {
+        /// Code (or full link) from an earlier reset email; skips requesting a new one
+        #[arg(long)]
+        code: Option<String>,
+    },
+}
+
 #[derive(Subcommand)]
 enum TokensAction {
     /// Create a new API token
@@ -302,6 +852,10 @@ enum ConfigAction {
         key: String,
         /// Configuration value
         value: String,
+
+        /// Show the change without saving it
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Add an Unreal Engine installation
@@ -322,13 +876,161 @@ enum ConfigAction {
     ListEngines,
 }
 
+#[derive(Subcommand)]
+enum EngineAction {
+    /// List configured and auto-detected engines
+    List,
+
+    /// Register an engine installation (version read from Build.version)
+    Add {
+        /// Path to engine installation
+        path: String,
+
+        /// Engine version (defaults to the version in Engine/Build/Build.version)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Target platforms (defaults to those detected in Engine/Platforms)
+        #[arg(long, value_delimiter = ',')]
+        platforms: Vec<String>,
+    },
+
+    /// Set the target platforms for a configured engine
+    Platforms {
+        /// Engine version
+        version: String,
+
+        /// Target platforms (e.g., Win64,Android); omit to use detected platforms
+        #[arg(value_delimiter = ',')]
+        platforms: Vec<String>,
+    },
+
+    /// Verify engine installations (path, UnrealBuildTool, Build.version)
+    Verify {
+        /// Engine version to verify (defaults to all)
+        version: Option<String>,
+    },
+
+    /// Run Setup and GenerateProjectFiles for a source-built engine
+    Setup {
+        /// Engine version to set up
+        version: String,
+
+        /// Only run Setup, skip GenerateProjectFiles
+        #[arg(long)]
+        skip_project_files: bool,
+
+        /// Show what would be run without running it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Map a custom EngineAssociation (GUID, fork name) to an engine version
+    Alias {
+        /// EngineAssociation from the .uproject (e.g., "MyStudio-5.3-fork")
+        association: String,
+
+        /// Engine version packages are matched against (e.g., 5.3)
+        version: String,
+
+        /// Engine installation for this association
+        #[arg(long)]
+        path: Option<String>,
+    },
+
+    /// Remove an engine association mapping
+    Unalias {
+        /// EngineAssociation to remove
+        association: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum FabAction {
+    /// List Fab/Marketplace plugins in the engine and Plugins/
+    List {
+        /// Engine version to scan (defaults to the project's engine version)
+        #[arg(long)]
+        engine: Option<String>,
+    },
+
+    /// Record Fab plugins in unrealpm.lock as externally managed
+    Pin {
+        /// Plugin names to pin
+        plugins: Vec<String>,
+
+        /// Pin every discovered Fab plugin
+        #[arg(long, conflicts_with = "plugins")]
+        all: bool,
+
+        /// Engine version to scan (defaults to the project's engine version)
+        #[arg(long)]
+        engine: Option<String>,
+    },
+
+    /// Remove a pinned Fab plugin from unrealpm.lock
+    Unpin {
+        /// Plugin name
+        plugin: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum KeysAction {
     /// Generate new signing keys
-    Generate,
+    Generate {
+        /// Generate an additional named key instead of the active one
+        #[arg(long)]
+        name: Option<String>,
+    },
 
     /// Show public key
     Show,
+
+    /// List the signing keys on this machine
+    List,
+
+    /// Sign packages with the named key from now on
+    Use {
+        /// Key name (see `keys list`)
+        name: String,
+    },
+
+    /// Replace the active key: endorse and register a new key, then re-sign
+    /// the latest version of the given packages with it
+    Rotate {
+        /// Packages whose latest version to re-sign
+        packages: Vec<String>,
+
+        /// Name of the new key (default: key-<date>)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Export a key encrypted with a passphrase (set UNREALPM_KEY_PASSPHRASE
+    /// to skip the prompt)
+    Export {
+        /// Key to export (default: the active key)
+        name: Option<String>,
+
+        /// File to write (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Import a key written by `keys export`
+    Import {
+        /// Exported key file
+        file: String,
+
+        /// Name to store the key under (default: its exported name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Also sign with the imported key from now on
+        #[arg(long = "use")]
+        activate: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -361,52 +1063,287 @@ enum CacheAction {
     Verify,
 }
 
+#[derive(Subcommand)]
+enum StoreAction {
+    /// Remove store entries no project on this machine uses
+    Gc {
+        /// Show what would be removed without actually removing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Collect even if the project index may be missing projects
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RegistryAction {
+    /// Show or change how the file registry lays out tarballs on disk
+    Layout {
+        /// New layout: flat or sharded (omit to show the current layout)
+        layout: Option<String>,
+
+        /// Show what would be moved without moving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum VcsIgnoreAction {
+    /// Write the installed plugins into the ignore file's managed block
+    Sync {
+        /// Ignore file: gitignore, p4ignore, or list (default: [vcs] ignore, or gitignore)
+        #[arg(long, value_name = "gitignore|p4ignore|list")]
+        file: Option<unrealpm::IgnoreFile>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AttestAction {
+    /// Verify an attestation's signature and show what it records
+    Verify {
+        /// Attestation file
+        file: String,
+
+        /// Trusted signer public key (hex or public key file)
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Also check that this lockfile matches the attested packages
+        #[arg(long)]
+        lockfile: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum LockAction {
+    /// Show packages added, removed, and changed between two lockfiles
+    Diff {
+        /// Older lockfile (defaults to unrealpm.lock at the git revision --rev)
+        old: Option<String>,
+
+        /// Newer lockfile (defaults to ./unrealpm.lock)
+        new: Option<String>,
+
+        /// Git revision to compare the working lockfile against
+        #[arg(long, default_value = "HEAD", conflicts_with = "old")]
+        rev: String,
+
+        /// Output format (markdown is suitable for PR comments)
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiffFormat {
+    Text,
+    Markdown,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LicenseFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SbomFormat {
+    /// CycloneDX 1.5 JSON
+    Cyclonedx,
+    /// SPDX 2.3 JSON
+    Spdx,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum AuditLevel {
+    Warnings,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl From<AuditLevel> for unrealpm::advisory::Severity {
+    fn from(level: AuditLevel) -> Self {
+        match level {
+            AuditLevel::Warnings => Self::Warning,
+            AuditLevel::Low => Self::Low,
+            AuditLevel::Medium => Self::Medium,
+            AuditLevel::High => Self::High,
+            AuditLevel::Critical => Self::Critical,
+        }
+    }
+}
+
+/// True if `name` is a built-in command's own name
+///
+/// Short aliases like `i` and `ls` don't count, so `[alias]` can redefine
+/// them; [`clap::Command::find_subcommand`] would match those too.
+fn is_builtin_command(command: &clap::Command, name: &str) -> bool {
+    command.get_subcommands().any(|c| c.get_name() == name)
+}
+
+/// Global options that take a separate value, e.g. `--registry <URL>`
+fn global_value_options(command: &clap::Command) -> Vec<String> {
+    command
+        .get_arguments()
+        .filter(|arg| arg.get_action().takes_values())
+        .flat_map(|arg| {
+            arg.get_long()
+                .map(|long| format!("--{}", long))
+                .into_iter()
+                .chain(arg.get_short().map(|short| format!("-{}", short)))
+        })
+        .collect()
+}
+
 fn main() {
-    let cli = Cli::parse();
+    commands::report_bug::install_panic_hook();
+    let config = unrealpm::Config::load().ok();
+    let args: Vec<String> = std::env::args().collect();
+    let command = Cli::command();
+    let value_options = global_value_options(&command);
+    let args = match &config {
+        Some(config) => config
+            .expand_alias(args, &value_options, |name| {
+                is_builtin_command(&command, name)
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(unrealpm::exit_code::USAGE);
+            }),
+        None => args,
+    };
+    let command_name = unrealpm::config::command_index(&args, &value_options)
+        .map(|index| args[index].clone())
+        .unwrap_or_default();
+    let cli = Cli::parse_from(args);
+    let started = std::time::Instant::now();
+    if let Err(e) = unrealpm::config::set_registry_override(cli.registry.as_deref()) {
+        eprintln!("Error: {}", e);
+        std::process::exit(unrealpm::exit_code::USAGE);
+    }
+    unrealpm::strict::set_enabled(cli.strict || config.as_ref().is_some_and(|c| c.ui.strict));
+    unrealpm::i18n::init(config.as_ref().and_then(|c| c.ui.locale.as_deref()));
+    unrealpm::output::set_plain(unrealpm::output::plain_requested(cli.plain));
+    unrealpm::vcs::init(std::env::current_dir().ok().and_then(|dir| match &config {
+        Some(config) => config.vcs_kind(dir),
+        None => unrealpm::Config::default().vcs_kind(dir),
+    }));
+
+    if !matches!(cli.command, Commands::Rebuild { .. }) {
+        commands::rebuild::warn_if_stale(config.as_ref());
+    }
 
     let result = match cli.command {
-        Commands::Init => commands::init::run(),
+        Commands::Init { uproject, toml } => commands::init::run(uproject, toml),
         Commands::Install {
             package,
+            path,
+            tarball,
             force,
             engine_version,
             prefer_binary,
             source_only,
             binary_only,
+            source_only_files,
             dry_run,
             offline,
+            adopt,
+            overwrite,
+            yes,
             verbose_resolve,
             max_depth,
             resolve_timeout,
+            no_resolve_cache,
+            features,
+            production,
+            locked,
+            frozen,
+            workspace,
+            jobs,
+            timings,
         } => commands::install::run(
             package,
+            features,
+            path,
+            tarball,
             force,
             engine_version,
             prefer_binary,
             source_only,
             binary_only,
+            source_only_files,
             dry_run,
             offline,
+            commands::install::UnmanagedPolicy::from_flags(adopt, overwrite),
+            yes,
             verbose_resolve,
             max_depth,
             resolve_timeout,
+            no_resolve_cache,
+            production,
+            commands::install::LockfileMode::from_flags(locked, frozen),
+            workspace,
+            jobs,
+            timings,
         ),
-        Commands::Uninstall { package } => commands::uninstall::run(package),
+        Commands::Ci {
+            production,
+            source_only_files,
+            jobs,
+            timings,
+        } => commands::ci::run(production, source_only_files, jobs, timings),
+        Commands::Uninstall {
+            package,
+            all,
+            yes,
+            dry_run,
+        } => commands::uninstall::run(package, all, yes, unrealpm::DryRun::new(dry_run)),
         Commands::Update {
             package,
+            prefer_binary,
+            source_only,
+            binary_only,
             dry_run,
+            latest,
+            workspace,
+            offline,
             verbose_resolve,
             max_depth,
             resolve_timeout,
+            jobs,
+            timings,
         } => commands::update::run(
             package,
+            prefer_binary,
+            source_only,
+            binary_only,
             dry_run,
+            latest,
+            workspace,
+            offline,
             verbose_resolve,
             max_depth,
             resolve_timeout,
+            jobs,
+            timings,
         ),
-        Commands::List => commands::list::run(),
-        Commands::Outdated => commands::outdated::run(),
+        Commands::Link { plugin } => commands::link::run(plugin),
+        Commands::Unlink { plugin } => commands::unlink::run(plugin),
+        Commands::Vendor { dry_run } => commands::vendor::run(unrealpm::DryRun::new(dry_run)),
+        Commands::List { tree_size } => commands::list::run(tree_size),
+        Commands::Audit { deny } => commands::audit::run(deny.map(Into::into)),
+        Commands::Outdated {
+            workspace,
+            exit_code,
+        } => commands::outdated::run(workspace, exit_code),
+        Commands::MigrateEngine { engine, apply } => commands::migrate_engine::run(&engine, apply),
+        Commands::Report { scan, json } => commands::report::run(scan, json),
         Commands::Pack {
             path,
             output,
@@ -414,40 +1351,124 @@ fn main() {
             dry_run,
         } => commands::pack::run(path, output, include_binaries, dry_run),
         Commands::Tree => commands::tree::run(),
+        Commands::Check => commands::check::run(),
+        Commands::Validate => commands::validate::run(),
+        Commands::Lock { action } => commands::lock::run(&action),
+        Commands::Env { ubt, json } => commands::env::run(ubt, json),
+        Commands::Notices { output, stdout } => commands::notices::run(output, stdout),
+        Commands::Licenses { format } => commands::licenses::run(format),
+        Commands::Sbom { format, output } => commands::sbom::run(format, output),
         Commands::Why { package } => commands::why::run(package),
-        Commands::Search { query } => commands::search::run(query),
+        Commands::Info {
+            package,
+            readme,
+            no_pager,
+            files,
+        } => commands::info::run(&package, readme, no_pager, files),
+        Commands::Search { query, category } => commands::search::run(query, category),
+        Commands::Categories => commands::categories::run(),
         Commands::Publish {
             path,
             dry_run,
             include_binaries,
             engine,
+            multi_engine,
             git_repo,
             git_ref,
-        } => commands::publish::run(path, dry_run, include_binaries, engine, git_repo, git_ref),
+        } => commands::publish::run(
+            path,
+            dry_run,
+            include_binaries,
+            engine,
+            multi_engine,
+            git_repo,
+            git_ref,
+        ),
+        Commands::PublishBinary {
+            path,
+            platform,
+            engine,
+            configuration,
+            replace_binary,
+            dry_run,
+        } => commands::publish::run_binary(
+            path,
+            platform,
+            engine,
+            configuration,
+            replace_binary,
+            dry_run,
+        ),
         Commands::Build {
             path,
             engine,
             platform,
             all_platforms,
-        } => commands::build::run(path, engine, platform, all_platforms),
+            configurations,
+        } => commands::build::run(path, engine, platform, all_platforms, configurations),
+        Commands::Rebuild { stale, dry_run } => commands::rebuild::run(stale, dry_run),
         Commands::Cache { action } => match action {
             CacheAction::List { verbose } => commands::cache::run_list(verbose),
             CacheAction::Info => commands::cache::run_info(),
             CacheAction::Path => commands::cache::run_path(),
-            CacheAction::Clean { all, dry_run } => commands::cache::run_clean(all, dry_run),
+            CacheAction::Clean { all, dry_run } => {
+                commands::cache::run_clean(all, unrealpm::DryRun::new(dry_run))
+            }
             CacheAction::Verify => commands::cache::run_verify(),
         },
+        Commands::Store { action } => match action {
+            StoreAction::Gc { dry_run, yes } => {
+                commands::store::run_gc(unrealpm::DryRun::new(dry_run), yes)
+            }
+        },
+        Commands::Registry { action } => commands::registry::run(&action),
+        Commands::VcsIgnore { action } => commands::vcs_ignore::run(&action),
         Commands::Config { action } => commands::config::run(&action),
+        Commands::Engine { action } => commands::engine::run(&action),
+        Commands::Fab { action } => commands::fab::run(&action),
         Commands::Doctor { verbose, fix } => commands::doctor::run(verbose, fix),
+        Commands::ReportBug { output } => commands::report_bug::run(output),
         Commands::Keys { action } => commands::keys::run(&action),
-        Commands::Verify { package } => commands::verify::run(package),
-        Commands::Register => commands::register::run(),
+        Commands::Verify {
+            package,
+            all,
+            jobs,
+            cross_check,
+        } => commands::verify::run(package, all, jobs, cross_check),
+        Commands::Trust { package, key, list } => commands::trust::run(package, key, list),
+        Commands::Attest {
+            action,
+            output,
+            build_id,
+        } => commands::attest::run(&action, output.as_deref(), build_id.as_deref()),
+        Commands::Register {
+            github,
+            email,
+            no_wait,
+        } => commands::register::run(github, email, no_wait),
         Commands::Login { github, email } => commands::login::run(github, email),
         Commands::Logout => commands::login::run_logout(),
         Commands::Whoami => commands::whoami::run(),
-        Commands::Unpublish { package, version } => commands::unpublish::run(package, version),
-        Commands::Yank { package } => commands::yank::run(package, false),
-        Commands::Unyank { package } => commands::yank::run(package, true),
+        Commands::SelfUpdate {
+            channel,
+            check,
+            force,
+        } => commands::self_update::run(channel, check, force),
+        Commands::Unpublish {
+            package,
+            version,
+            dry_run,
+        } => commands::unpublish::run(package, version, unrealpm::DryRun::new(dry_run)),
+        Commands::Versions { package, action } => {
+            commands::versions::run(package.as_deref(), &action)
+        }
+        Commands::Yank { package, dry_run } => {
+            commands::yank::run(package, false, unrealpm::DryRun::new(dry_run))
+        }
+        Commands::Unyank { package, dry_run } => {
+            commands::yank::run(package, true, unrealpm::DryRun::new(dry_run))
+        }
+        Commands::Auth { action } => commands::auth::run(&action),
         Commands::Tokens { action } => match action {
             TokensAction::Create {
                 name,
@@ -464,8 +1485,74 @@ fn main() {
         }
     };
 
+    // Changes are opened in the workspace even when the command fails midway
+    if let Some(summary) = unrealpm::vcs::finish() {
+        unrealpm::outln!("{}", summary);
+        unrealpm::outln!();
+    }
+
+    if let Some(config) = &config {
+        let current_dir = std::env::current_dir().ok();
+        unrealpm::notify::send(
+            &config.notifications,
+            &unrealpm::notify::Notification {
+                command: command_name,
+                project: current_dir
+                    .filter(|dir| unrealpm::Manifest::exists(dir))
+                    .and_then(|dir| dir.file_name().map(|name| name.to_string_lossy().into())),
+                elapsed: started.elapsed(),
+                error: result.as_ref().err().map(|e| e.to_string()),
+            },
+        );
+    }
+
+    commands::report_bug::record_command(
+        &std::env::args().collect::<Vec<_>>(),
+        &result
+            .as_ref()
+            .map_or_else(|e| format!("error: {}", e), |_| "ok".to_string()),
+    );
     if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+        unrealpm::eoutln!("{}", unrealpm::i18n::tr("cli.error", &[("message", &e)]));
+        std::process::exit(unrealpm::exit_code::for_error(e.as_ref()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_alias_overrides_builtin_short_alias() {
+        let config: unrealpm::Config = toml::from_str(
+            r#"
+            [alias]
+            i = "install --prefer-binary"
+            install = "install --offline"
+            "#,
+        )
+        .unwrap();
+        let command = Cli::command();
+        let value_options = global_value_options(&command);
+        let args = |line: &str| line.split(' ').map(String::from).collect::<Vec<_>>();
+        let expand = |line: &str| {
+            config
+                .expand_alias(args(line), &value_options, |name| {
+                    is_builtin_command(&command, name)
+                })
+                .unwrap()
+        };
+
+        assert_eq!(
+            expand("unrealpm i foo"),
+            args("unrealpm install --prefer-binary foo")
+        );
+        assert_eq!(expand("unrealpm install"), args("unrealpm install"));
+        // `--registry` takes a value, which is not the command
+        assert!(value_options.contains(&"--registry".to_string()));
+        assert_eq!(
+            expand("unrealpm --registry http://x i"),
+            args("unrealpm --registry http://x install --prefer-binary")
+        );
     }
 }
@@ -3,6 +3,15 @@
 //! This module provides types and functions for working with UnrealPM manifests
 //! and Unreal Engine project files.
 //!
+//! A project's manifest is either `unrealpm.json` or `unrealpm.toml`, never
+//! both. The format is detected on load and kept on save.
+//!
+//! [`UPlugin`] models the .uplugin schema, including sealing
+//! (`IsSealed`/`DisallowedPlugins`), per-module platform allow and deny lists
+//! (and their pre-5.1 `WhitelistPlatforms`/`BlacklistPlatforms` spellings),
+//! and localization targets. Keys it does not know are kept as-is, so a
+//! loaded descriptor saves back without losing anything.
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -20,9 +29,10 @@
 //! # }
 //! ```
 
+use crate::json_edit::{strip_comments, update_document};
 use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -51,31 +61,607 @@ use std::path::{Path, PathBuf};
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Dependencies may also be written as objects with conditions, which are
+/// split into [`Manifest::dependency_conditions`] when loaded:
+///
+/// ```json
+/// "dependencies": {
+///   "win-only-plugin": { "version": "^1.0", "platforms": ["Win64"] },
+///   "new-api-plugin": { "version": "^2.0", "engines": ">=5.3" }
+/// }
+/// ```
+///
+/// An exact version can also be pinned to the SHA-256 of its source tarball,
+/// kept in [`Manifest::dependency_integrity`]. The pin is checked on every
+/// install, with or without a lockfile:
+///
+/// ```json
+/// "dependencies": {
+///   "audited-plugin": { "version": "1.2.3", "integrity": "sha256-9f86d0…" }
+/// }
+/// ```
+///
+/// A dependency meant to replace a plugin the engine already ships sets
+/// `engine_override`, kept in [`Manifest::engine_overrides`], which silences
+/// the engine plugin conflict check for it:
+///
+/// ```json
+/// "dependencies": {
+///   "newer-built-in": { "version": "^2.0", "engine_override": true }
+/// }
+/// ```
+///
+/// Optional dependencies of a package are enabled per dependency with
+/// `features`, kept in [`Manifest::dependency_features`]:
+///
+/// ```json
+/// "dependencies": {
+///   "vfx-toolkit": { "version": "^1.0", "features": ["niagara-support"] }
+/// }
+/// ```
+///
+/// A plugin declares those features by grouping its
+/// [`Manifest::optional_dependencies`] under [`Manifest::features`]:
+///
+/// ```json
+/// "optional_dependencies": { "NiagaraHelpers": "^1.2" },
+/// "features": { "niagara-support": ["NiagaraHelpers"] }
+/// ```
+///
+/// A plugin developed alongside the project can be linked from a local
+/// directory instead of installed from the registry. Its entry has only a
+/// `path`, relative to the project, and is kept in
+/// [`Manifest::path_dependencies`] rather than [`Manifest::dependencies`]:
+///
+/// ```json
+/// "dependencies": {
+///   "my-plugin": { "path": "../MyPlugin" }
+/// }
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ManifestFile", into = "ManifestFile")]
 pub struct Manifest {
     /// Package name (optional for projects, required for plugins)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// Package version (optional for projects, required for plugins)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 
     /// Package description
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
     /// Unreal Engine version (e.g., "5.3", "5.4")
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub engine_version: Option<String>,
 
-    /// Runtime dependencies
-    #[serde(default)]
+    /// Runtime dependencies (name -> version constraint)
     pub dependencies: HashMap<String, String>,
 
-    /// Development dependencies (not installed with --production)
-    #[serde(default)]
+    /// Platform/engine conditions for runtime dependencies that have them
+    pub dependency_conditions: HashMap<String, DependencyConditions>,
+
+    /// Pinned source tarball checksums (name -> lowercase hex SHA-256) for
+    /// runtime dependencies written with `integrity`
+    pub dependency_integrity: HashMap<String, String>,
+
+    /// Runtime dependencies that replace a plugin the engine ships, written
+    /// with `engine_override`
+    pub engine_overrides: HashSet<String>,
+
+    /// Plugins linked from a local directory (name -> path relative to the
+    /// project), written as `{ "path": "../MyPlugin" }`
+    pub path_dependencies: HashMap<String, String>,
+
+    /// Features enabled on runtime dependencies written with `features`
+    /// (name -> feature names)
+    pub dependency_features: HashMap<String, Vec<String>>,
+
+    /// Dependencies this plugin only needs for some features
+    /// (name -> version constraint); published with [`Manifest::features`]
+    pub optional_dependencies: HashMap<String, String>,
+
+    /// Features this plugin offers (feature -> names from
+    /// [`Manifest::optional_dependencies`] it pulls in)
+    pub features: HashMap<String, Vec<String>>,
+
+    /// Development dependencies such as test frameworks and editor-only
+    /// tools (name -> version constraint)
+    ///
+    /// Installed alongside runtime dependencies unless `install --production`
+    /// is used, and never published as dependencies of the package. Also
+    /// accepted as `devDependencies`.
     pub dev_dependencies: HashMap<String, String>,
+
+    /// Local patch directories applied after install (package -> directory,
+    /// e.g., "awesome-plugin" -> "patches/awesome-plugin")
+    pub patches: HashMap<String, String>,
+
+    /// Project settings (e.g., which .uproject to use)
+    pub project: Option<ProjectSettings>,
+
+    /// Registry categories; overrides the .uplugin's Category when publishing
+    pub categories: Vec<String>,
+
+    /// Registry search keywords
+    pub keywords: Vec<String>,
+}
+
+/// Project settings in unrealpm.json (`"project": { ... }`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    /// .uproject file to use when the directory contains several, relative to
+    /// the project root (e.g., "MyGame.uproject")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uproject: Option<String>,
+}
+
+/// JSON manifest file name
+pub const MANIFEST_FILE_NAME: &str = "unrealpm.json";
+
+/// TOML manifest file name, an alternative to [`MANIFEST_FILE_NAME`]
+pub const TOML_MANIFEST_FILE_NAME: &str = "unrealpm.toml";
+
+/// On-disk format of a project manifest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// `unrealpm.json` (comments and trailing commas allowed)
+    #[default]
+    Json,
+    /// `unrealpm.toml`
+    Toml,
+}
+
+impl ManifestFormat {
+    /// File name used for this format
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ManifestFormat::Json => MANIFEST_FILE_NAME,
+            ManifestFormat::Toml => TOML_MANIFEST_FILE_NAME,
+        }
+    }
+}
+
+/// Top-level keys written by [`Manifest::save`]; other keys in an existing
+/// manifest file are left alone
+const MANIFEST_KEYS: &[&str] = &[
+    "name",
+    "version",
+    "description",
+    "engine_version",
+    "dependencies",
+    "dev_dependencies",
+    "devDependencies",
+    "optional_dependencies",
+    "features",
+    "patches",
+    "project",
+    "categories",
+    "keywords",
+];
+
+/// On-disk form of [`Manifest`], where dependencies may carry conditions
+#[derive(Serialize, Deserialize)]
+struct ManifestFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    engine_version: Option<String>,
+
+    #[serde(default)]
+    dependencies: HashMap<String, DependencyEntry>,
+
+    #[serde(default, alias = "devDependencies")]
+    dev_dependencies: HashMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    optional_dependencies: HashMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    features: HashMap<String, Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    patches: HashMap<String, String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<ProjectSettings>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    categories: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    keywords: Vec<String>,
+}
+
+/// A dependency as written in unrealpm.json: a bare constraint or an object
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum DependencyEntry {
+    Version(String),
+    Detailed {
+        version: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        integrity: Option<String>,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        engine_override: bool,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        features: Vec<String>,
+        #[serde(flatten)]
+        conditions: DependencyConditions,
+    },
+    Path {
+        path: String,
+    },
+}
+
+/// Prefix of an `integrity` value
+const INTEGRITY_PREFIX: &str = "sha256-";
+
+/// Hex checksum in an `integrity` value (`sha256-<64 hex digits>`)
+fn parse_integrity(
+    name: &str,
+    version: &str,
+    integrity: &str,
+) -> std::result::Result<String, String> {
+    let hex = integrity
+        .strip_prefix(INTEGRITY_PREFIX)
+        .filter(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| {
+            format!(
+                "invalid integrity '{}' for {}: expected sha256-<64 hex digits>",
+                integrity, name
+            )
+        })?;
+    if semver::Version::parse(version.trim_start_matches('=')).is_err() {
+        return Err(format!(
+            "integrity for {} requires an exact version, not '{}'",
+            name, version
+        ));
+    }
+    Ok(hex.to_ascii_lowercase())
+}
+
+/// Valid version constraint forms, shown with constraint errors
+pub const CONSTRAINT_EXAMPLES: &str = "\
+Valid constraints look like:
+  \"^1.2.0\"       1.2.0 or newer, below 2.0.0
+  \"~1.2.0\"       1.2.0 or newer, below 1.3.0
+  \"=1.2.3\"       exactly 1.2.3
+  \">=1.2, <2.0\"  any version in the range
+  \"*\"            any version";
+
+/// A dependency whose version constraint doesn't parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintError {
+    /// Dependency name
+    pub dependency: String,
+    /// The constraint as written
+    pub constraint: String,
+    /// Why it doesn't parse
+    pub reason: String,
+}
+
+impl std::fmt::Display for ConstraintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid version constraint '{}' for {}: {}\n\n{}",
+            self.constraint, self.dependency, self.reason, CONSTRAINT_EXAMPLES
+        )
+    }
+}
+
+/// Check that `constraint` is a valid version constraint for `dependency`
+pub fn check_constraint(
+    dependency: &str,
+    constraint: &str,
+) -> std::result::Result<(), ConstraintError> {
+    let reason = if constraint.trim().is_empty() {
+        "the constraint is empty".to_string()
+    } else {
+        match semver::VersionReq::parse(constraint) {
+            Ok(_) => return Ok(()),
+            Err(e) => e.to_string(),
+        }
+    };
+    Err(ConstraintError {
+        dependency: dependency.to_string(),
+        constraint: constraint.to_string(),
+        reason,
+    })
+}
+
+impl ManifestFile {
+    /// Every dependency with an invalid version constraint, sorted by name
+    fn constraint_errors(&self) -> Vec<ConstraintError> {
+        let versioned =
+            self.dependencies
+                .iter()
+                .filter_map(|(name, entry)| match entry {
+                    DependencyEntry::Version(version)
+                    | DependencyEntry::Detailed { version, .. } => Some((name, version)),
+                    DependencyEntry::Path { .. } => None,
+                });
+        let mut errors: Vec<ConstraintError> = versioned
+            .chain(&self.dev_dependencies)
+            .chain(&self.optional_dependencies)
+            .filter_map(|(name, constraint)| check_constraint(name, constraint).err())
+            .collect();
+        errors.sort_by(|a, b| a.dependency.cmp(&b.dependency));
+        errors
+    }
+}
+
+impl TryFrom<ManifestFile> for Manifest {
+    type Error = String;
+
+    fn try_from(file: ManifestFile) -> std::result::Result<Self, Self::Error> {
+        let mut dependencies = HashMap::new();
+        let mut dependency_conditions = HashMap::new();
+        let mut dependency_integrity = HashMap::new();
+        let mut engine_overrides = HashSet::new();
+        let mut path_dependencies = HashMap::new();
+        let mut dependency_features = HashMap::new();
+
+        // Dependency names end up in registry URLs and file names
+        let names = file
+            .dependencies
+            .keys()
+            .chain(file.dev_dependencies.keys())
+            .chain(file.optional_dependencies.keys());
+        for name in names {
+            crate::scope::validate(name).map_err(|e| e.to_string())?;
+        }
+
+        // Bad constraints would otherwise only fail deep inside resolution
+        if let Some(error) = file.constraint_errors().into_iter().next() {
+            return Err(error.to_string());
+        }
+
+        for (name, entry) in file.dependencies {
+            match entry {
+                DependencyEntry::Version(version) => {
+                    dependencies.insert(name, version);
+                }
+                DependencyEntry::Detailed {
+                    version,
+                    integrity,
+                    engine_override,
+                    features,
+                    conditions,
+                } => {
+                    if features.iter().any(|f| f.trim().is_empty()) {
+                        return Err(format!("empty feature name for {}", name));
+                    }
+                    if !features.is_empty() {
+                        dependency_features.insert(name.clone(), features);
+                    }
+                    if engine_override {
+                        engine_overrides.insert(name.clone());
+                    }
+                    if let Some(integrity) = integrity {
+                        let checksum = parse_integrity(&name, &version, &integrity)?;
+                        dependency_integrity.insert(name.clone(), checksum);
+                    }
+                    if let Some(engines) = &conditions.engines {
+                        semver::VersionReq::parse(engines).map_err(|e| {
+                            format!(
+                                "invalid engines requirement '{}' for {}: {}",
+                                engines, name, e
+                            )
+                        })?;
+                    }
+                    dependencies.insert(name.clone(), version);
+                    if !conditions.is_empty() {
+                        dependency_conditions.insert(name, conditions);
+                    }
+                }
+                DependencyEntry::Path { path } => {
+                    if path.trim().is_empty() {
+                        return Err(format!("empty path for {}", name));
+                    }
+                    path_dependencies.insert(name, path);
+                }
+            }
+        }
+
+        for (feature, names) in &file.features {
+            if let Some(missing) = names
+                .iter()
+                .find(|n| !file.optional_dependencies.contains_key(*n))
+            {
+                return Err(format!(
+                    "feature '{}' lists {}, which is not in optional_dependencies",
+                    feature, missing
+                ));
+            }
+        }
+
+        Ok(Self {
+            name: file.name,
+            version: file.version,
+            description: file.description,
+            engine_version: file.engine_version,
+            dependencies,
+            dependency_conditions,
+            dependency_integrity,
+            engine_overrides,
+            path_dependencies,
+            dependency_features,
+            optional_dependencies: file.optional_dependencies,
+            features: file.features,
+            dev_dependencies: file.dev_dependencies,
+            patches: file.patches,
+            project: file.project,
+            categories: file.categories,
+            keywords: file.keywords,
+        })
+    }
+}
+
+impl From<Manifest> for ManifestFile {
+    fn from(manifest: Manifest) -> Self {
+        let mut conditions = manifest.dependency_conditions;
+        let mut integrity = manifest.dependency_integrity;
+        let mut features = manifest.dependency_features;
+        let dependencies = manifest
+            .dependencies
+            .into_iter()
+            .map(|(name, version)| {
+                let conditions = conditions.remove(&name).unwrap_or_default();
+                let integrity = integrity
+                    .remove(&name)
+                    .map(|hex| format!("{}{}", INTEGRITY_PREFIX, hex));
+                let engine_override = manifest.engine_overrides.contains(&name);
+                let features = features.remove(&name).unwrap_or_default();
+                let entry = if conditions.is_empty()
+                    && integrity.is_none()
+                    && !engine_override
+                    && features.is_empty()
+                {
+                    DependencyEntry::Version(version)
+                } else {
+                    DependencyEntry::Detailed {
+                        version,
+                        integrity,
+                        engine_override,
+                        features,
+                        conditions,
+                    }
+                };
+                (name, entry)
+            })
+            .chain(
+                manifest
+                    .path_dependencies
+                    .into_iter()
+                    .map(|(name, path)| (name, DependencyEntry::Path { path })),
+            )
+            .collect();
+
+        Self {
+            name: manifest.name,
+            version: manifest.version,
+            description: manifest.description,
+            engine_version: manifest.engine_version,
+            dependencies,
+            dev_dependencies: manifest.dev_dependencies,
+            optional_dependencies: manifest.optional_dependencies,
+            features: manifest.features,
+            patches: manifest.patches,
+            project: manifest.project,
+            categories: manifest.categories,
+            keywords: manifest.keywords,
+        }
+    }
+}
+
+/// Categories and keywords a plugin is published with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryTags {
+    pub categories: Vec<String>,
+    pub keywords: Vec<String>,
+}
+
+impl DiscoveryTags {
+    /// Tags for a plugin: the manifest's `categories` if set, otherwise the
+    /// .uplugin's Category, plus the manifest's `keywords`
+    ///
+    /// Entries are trimmed and de-duplicated case-insensitively.
+    pub fn from_plugin(uplugin: &UPlugin, manifest: Option<&Manifest>) -> Self {
+        let categories = match manifest.filter(|m| !m.categories.is_empty()) {
+            Some(manifest) => manifest.categories.clone(),
+            None => uplugin.category.iter().cloned().collect(),
+        };
+        let keywords = manifest.map(|m| m.keywords.clone()).unwrap_or_default();
+
+        Self {
+            categories: normalize_tags(categories),
+            keywords: normalize_tags(keywords),
+        }
+    }
+
+    /// True if there are no categories or keywords
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty() && self.keywords.is_empty()
+    }
+}
+
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    tags.into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty() && seen.insert(t.to_lowercase()))
+        .collect()
+}
+
+/// Conditions under which a dependency is installed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyConditions {
+    /// Host platforms the dependency applies to (e.g., `["Win64"]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platforms: Option<Vec<String>>,
+
+    /// Engine version requirement (e.g., `">=5.3"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engines: Option<String>,
+}
+
+impl DependencyConditions {
+    /// True if no conditions are set
+    pub fn is_empty(&self) -> bool {
+        self.platforms.is_none() && self.engines.is_none()
+    }
+
+    /// Check the conditions against a host platform and engine version
+    ///
+    /// An unknown engine version satisfies any engine requirement.
+    pub fn matches(&self, platform: &str, engine_version: Option<&str>) -> bool {
+        if let Some(platforms) = &self.platforms {
+            if !platforms.iter().any(|p| p.eq_ignore_ascii_case(platform)) {
+                return false;
+            }
+        }
+
+        if let (Some(engines), Some(engine)) = (&self.engines, engine_version) {
+            if let (Ok(req), Some(version)) =
+                (semver::VersionReq::parse(engines), engine_semver(engine))
+            {
+                return req.matches(&version);
+            }
+        }
+
+        true
+    }
+
+    /// Human-readable summary (e.g., "platforms: Win64, engines: >=5.3")
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(platforms) = &self.platforms {
+            parts.push(format!("platforms: {}", platforms.join("/")));
+        }
+        if let Some(engines) = &self.engines {
+            parts.push(format!("engines: {}", engines));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Parse an engine version like "5.3", "5.3.2", or "5.4.0-preview" as a
+/// semver version (suffixes are ignored)
+fn engine_semver(engine_version: &str) -> Option<semver::Version> {
+    let parsed = crate::platform::EngineVersion::parse(engine_version)?;
+    Some(semver::Version::new(
+        parsed.major.into(),
+        parsed.minor.into(),
+        parsed.patch.unwrap_or(0).into(),
+    ))
 }
 
 impl Manifest {
@@ -87,46 +673,367 @@ impl Manifest {
             description: None,
             engine_version: None,
             dependencies: HashMap::new(),
+            dependency_conditions: HashMap::new(),
+            dependency_integrity: HashMap::new(),
+            engine_overrides: HashSet::new(),
+            path_dependencies: HashMap::new(),
+            dependency_features: HashMap::new(),
+            optional_dependencies: HashMap::new(),
+            features: HashMap::new(),
             dev_dependencies: HashMap::new(),
+            patches: HashMap::new(),
+            project: None,
+            categories: Vec::new(),
+            keywords: Vec::new(),
         }
     }
 
-    /// Load manifest from unrealpm.json in the given directory
-    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
-        let manifest_path = dir.as_ref().join("unrealpm.json");
+    /// This plugin's features with the optional dependencies each pulls in,
+    /// as published to the registry
+    pub fn feature_dependencies(&self) -> HashMap<String, Vec<crate::Dependency>> {
+        self.features
+            .iter()
+            .map(|(feature, names)| {
+                let deps = names
+                    .iter()
+                    .filter_map(|name| {
+                        self.optional_dependencies
+                            .get(name)
+                            .map(|version| crate::Dependency {
+                                name: name.clone(),
+                                version: version.clone(),
+                            })
+                    })
+                    .collect();
+                (feature.clone(), deps)
+            })
+            .collect()
+    }
 
-        if !manifest_path.exists() {
-            return Err(Error::InvalidManifest(
-                "unrealpm.json not found. Run 'unrealpm init' first.".to_string(),
-            ));
+    /// Path of the project's .uproject file in `dir`
+    ///
+    /// Uses the `project.uproject` setting when present, otherwise the single
+    /// .uproject in `dir` (see [`UProject::select`]).
+    pub fn uproject_path<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf> {
+        let preferred = self.project.as_ref().and_then(|p| p.uproject.as_deref());
+        UProject::select(dir, preferred)
+    }
+
+    /// Runtime dependencies whose conditions hold for the given platform and engine
+    pub fn active_dependencies(
+        &self,
+        platform: &str,
+        engine_version: Option<&str>,
+    ) -> HashMap<String, String> {
+        self.dependencies
+            .iter()
+            .filter(|(name, _)| {
+                self.dependency_conditions
+                    .get(*name)
+                    .is_none_or(|c| c.matches(platform, engine_version))
+            })
+            .map(|(name, version)| (name.clone(), version.clone()))
+            .collect()
+    }
+
+    /// Runtime dependencies skipped because their conditions do not hold, sorted by name
+    pub fn inactive_dependencies(
+        &self,
+        platform: &str,
+        engine_version: Option<&str>,
+    ) -> Vec<(&str, &DependencyConditions)> {
+        let mut inactive: Vec<_> = self
+            .dependency_conditions
+            .iter()
+            .filter(|(name, c)| {
+                self.dependencies.contains_key(*name) && !c.matches(platform, engine_version)
+            })
+            .map(|(name, c)| (name.as_str(), c))
+            .collect();
+        inactive.sort_by_key(|(name, _)| *name);
+        inactive
+    }
+
+    /// Format of the manifest in the given directory, if there is one
+    ///
+    /// Fails when both unrealpm.json and unrealpm.toml exist, since it is
+    /// unclear which one is meant.
+    pub fn detect<P: AsRef<Path>>(dir: P) -> Result<Option<ManifestFormat>> {
+        let dir = dir.as_ref();
+        let json = dir.join(MANIFEST_FILE_NAME).exists();
+        let toml = dir.join(TOML_MANIFEST_FILE_NAME).exists();
+
+        match (json, toml) {
+            (true, true) => Err(Error::InvalidManifest(format!(
+                "Both {} and {} found in {}. Keep only one of them.",
+                MANIFEST_FILE_NAME,
+                TOML_MANIFEST_FILE_NAME,
+                dir.display()
+            ))),
+            (true, false) => Ok(Some(ManifestFormat::Json)),
+            (false, true) => Ok(Some(ManifestFormat::Toml)),
+            (false, false) => Ok(None),
         }
+    }
+
+    /// Load the manifest (unrealpm.json or unrealpm.toml) in the given directory
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let format = Self::detect(dir)?.ok_or_else(|| {
+            Error::InvalidManifest(
+                "No unrealpm.json or unrealpm.toml found. Run 'unrealpm init' first.".to_string(),
+            )
+        })?;
+
+        let content = fs::read_to_string(dir.join(format.file_name()))?;
+        Self::parse(&content, format)
+    }
 
-        let content = fs::read_to_string(&manifest_path)?;
-        let manifest: Manifest = serde_json::from_str(&content)?;
+    /// Every dependency in manifest file contents whose version constraint
+    /// doesn't parse, where [`Manifest::parse`] stops at the first
+    pub fn constraint_errors(
+        content: &str,
+        format: ManifestFormat,
+    ) -> Result<Vec<ConstraintError>> {
+        let file: ManifestFile = match format {
+            ManifestFormat::Json => serde_json::from_str(&strip_comments(content))?,
+            ManifestFormat::Toml => toml::from_str(content)?,
+        };
+        Ok(file.constraint_errors())
+    }
 
-        Ok(manifest)
+    /// Parse manifest file contents in the given format
+    pub fn parse(content: &str, format: ManifestFormat) -> Result<Self> {
+        Ok(match format {
+            // Comments and trailing commas (JSONC) are allowed
+            ManifestFormat::Json => serde_json::from_str(&strip_comments(content))?,
+            ManifestFormat::Toml => toml::from_str(content)?,
+        })
     }
 
-    /// Save manifest to unrealpm.json in the given directory
+    /// Save the manifest in the given directory, keeping its existing format
+    ///
+    /// An existing file is edited in place: only changed entries are
+    /// rewritten, so comments, key order, and formatting survive. Without an
+    /// existing manifest, unrealpm.json is created.
     pub fn save<P: AsRef<Path>>(&self, dir: P) -> Result<()> {
-        let manifest_path = dir.as_ref().join("unrealpm.json");
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&manifest_path, content)?;
-        Ok(())
+        let format = Self::detect(&dir)?.unwrap_or_default();
+        self.save_as(dir, format)
     }
 
-    /// Check if unrealpm.json exists in the given directory
+    /// Save the manifest in the given directory in a specific format
+    pub fn save_as<P: AsRef<Path>>(&self, dir: P, format: ManifestFormat) -> Result<()> {
+        let manifest_path = dir.as_ref().join(format.file_name());
+        let existing = fs::read_to_string(&manifest_path).ok();
+
+        let content = match format {
+            ManifestFormat::Json => {
+                let mut value = serde_json::to_value(self)?;
+                // Keep the `devDependencies` spelling when the file uses it
+                let camel_case = existing.as_deref().is_some_and(|existing| {
+                    serde_json::from_str::<serde_json::Value>(&strip_comments(existing))
+                        .is_ok_and(|old| old.get("devDependencies").is_some())
+                });
+                if let (true, Some(object)) = (camel_case, value.as_object_mut()) {
+                    if let Some(dev) = object.remove("dev_dependencies") {
+                        object.insert("devDependencies".to_string(), dev);
+                    }
+                }
+                let edited = existing
+                    .and_then(|existing| update_document(&existing, &value, MANIFEST_KEYS).ok());
+                match edited {
+                    Some(content) => content,
+                    None => serde_json::to_string_pretty(&value)?,
+                }
+            }
+            ManifestFormat::Toml => {
+                let fresh = toml::to_string_pretty(self)?;
+                match existing.and_then(|existing| update_toml_document(&existing, &fresh).ok()) {
+                    Some(content) => content,
+                    None => fresh,
+                }
+            }
+        };
+
+        crate::vcs::track(&[&manifest_path], || {
+            Ok(fs::write(&manifest_path, content)?)
+        })
+    }
+
+    /// Check if unrealpm.json or unrealpm.toml exists in the given directory
     pub fn exists<P: AsRef<Path>>(dir: P) -> bool {
-        dir.as_ref().join("unrealpm.json").exists()
+        let dir = dir.as_ref();
+        dir.join(MANIFEST_FILE_NAME).exists() || dir.join(TOML_MANIFEST_FILE_NAME).exists()
+    }
+
+    /// File name of the manifest in the given directory (unrealpm.json if none)
+    pub fn file_name<P: AsRef<Path>>(dir: P) -> &'static str {
+        Self::detect(dir)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .file_name()
     }
 }
 
+/// Apply the managed keys of `fresh` to an existing TOML manifest in place
+///
+/// The TOML counterpart of [`update_document`]: only changed entries are
+/// replaced, so comments and layout in the rest of the file survive.
+fn update_toml_document(existing: &str, fresh: &str) -> Result<String> {
+    let invalid = |e: toml_edit::TomlError| Error::InvalidManifest(e.to_string());
+    let mut document: toml_edit::DocumentMut = existing.parse().map_err(invalid)?;
+    let fresh_document: toml_edit::DocumentMut = fresh.parse().map_err(invalid)?;
+    let old: toml::Table = toml::from_str(existing)?;
+    let new: toml::Table = toml::from_str(fresh)?;
+
+    for key in MANIFEST_KEYS {
+        match (old.get(*key), new.get(*key)) {
+            (old_value, new_value) if old_value == new_value => {}
+            (None, Some(toml::Value::Table(t))) if t.is_empty() => {}
+            (None, Some(toml::Value::Array(a))) if a.is_empty() => {}
+            (Some(toml::Value::Table(old_table)), Some(toml::Value::Table(new_table))) => {
+                let Some(table) = document[*key].as_table_like_mut() else {
+                    document[*key] = fresh_document[*key].clone();
+                    continue;
+                };
+                for (member, value) in new_table {
+                    if old_table.get(member) != Some(value) {
+                        table.insert(member, fresh_document[*key][member.as_str()].clone());
+                    }
+                }
+                for member in old_table.keys() {
+                    if !new_table.contains_key(member) {
+                        table.remove(member);
+                    }
+                }
+            }
+            (_, Some(_)) => document[*key] = fresh_document[*key].clone(),
+            (Some(_), None) => {
+                document.remove(key);
+            }
+            (None, None) => {}
+        }
+    }
+
+    Ok(document.to_string())
+}
+
 impl Default for Manifest {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// The workspace file name
+pub const WORKSPACE_FILE_NAME: &str = "unrealpm.workspace.json";
+
+/// Workspace file (unrealpm.workspace.json)
+///
+/// Lists member projects of a monorepo. Each member is a directory (relative
+/// to the workspace root) containing its own unrealpm.json.
+///
+/// `install --workspace` and `update --workspace` resolve all members
+/// together into one `unrealpm.lock` at the workspace root, so a package
+/// shared by several members is locked (and downloaded) once. Each member
+/// still gets its own lockfile holding the packages it uses.
+///
+/// # Examples
+///
+/// ```no_run
+/// use unrealpm::Workspace;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// if let Some((root, workspace)) = Workspace::find(".")? {
+///     for member in workspace.member_dirs(&root) {
+///         println!("Member: {}", member.display());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Workspace {
+    /// Member project directories, relative to the workspace root
+    #[serde(default)]
+    pub members: Vec<String>,
+}
+
+impl Workspace {
+    /// Load unrealpm.workspace.json from the given directory
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let path = dir.as_ref().join(WORKSPACE_FILE_NAME);
+
+        if !path.exists() {
+            return Err(Error::InvalidManifest(format!(
+                "{} not found in {}",
+                WORKSPACE_FILE_NAME,
+                dir.as_ref().display()
+            )));
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let workspace: Workspace = serde_json::from_str(&content)?;
+        Ok(workspace)
+    }
+
+    /// Find the nearest workspace file in `start` or any of its ancestors
+    ///
+    /// Returns the workspace root directory and the parsed workspace.
+    pub fn find<P: AsRef<Path>>(start: P) -> Result<Option<(PathBuf, Self)>> {
+        let start = start.as_ref();
+        let start = if start.is_absolute() {
+            start.to_path_buf()
+        } else {
+            std::env::current_dir()?.join(start)
+        };
+
+        for dir in start.ancestors() {
+            if dir.join(WORKSPACE_FILE_NAME).exists() {
+                return Ok(Some((dir.to_path_buf(), Self::load(dir)?)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Absolute member directories for a workspace rooted at `root`
+    pub fn member_dirs<P: AsRef<Path>>(&self, root: P) -> Vec<PathBuf> {
+        self.members.iter().map(|m| root.as_ref().join(m)).collect()
+    }
+
+    /// Combine the direct dependencies of several members
+    ///
+    /// A package required with different constraints gets all of them,
+    /// comma-separated, so the resolution satisfies every member. `*` adds
+    /// nothing to another constraint and is dropped.
+    pub fn merge_dependencies<'a>(
+        members: impl IntoIterator<Item = &'a HashMap<String, String>>,
+    ) -> HashMap<String, String> {
+        let mut constraints: HashMap<String, Vec<&str>> = HashMap::new();
+        for dependencies in members {
+            for (name, constraint) in dependencies {
+                let entry = constraints.entry(name.clone()).or_default();
+                let constraint = constraint.trim();
+                if !entry.contains(&constraint) {
+                    entry.push(constraint);
+                }
+            }
+        }
+
+        constraints
+            .into_iter()
+            .map(|(name, mut list)| {
+                if list.len() > 1 {
+                    list.retain(|constraint| *constraint != "*");
+                }
+                list.sort_unstable();
+                (name, list.join(", "))
+            })
+            .collect()
+    }
+}
+
 /// Unreal Engine project file (.uproject)
 ///
 /// Represents the structure of an Unreal Engine .uproject file.
@@ -164,6 +1071,14 @@ pub struct UProject {
 
     #[serde(rename = "Plugins", default, skip_serializing_if = "Vec::is_empty")]
     pub plugins: Vec<UProjectPlugin>,
+
+    /// Extra folders the engine searches for plugins
+    #[serde(
+        rename = "AdditionalPluginDirectories",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub additional_plugin_directories: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -179,20 +1094,62 @@ pub struct UProjectPlugin {
 }
 
 impl UProject {
-    /// Find .uproject file in the given directory
+    /// Find the .uproject file in the given directory
+    ///
+    /// Fails with [`Error::MultipleUProjectFiles`] if there is more than one;
+    /// use [`UProject::select`] or [`Manifest::uproject_path`] to pick one.
     pub fn find<P: AsRef<Path>>(dir: P) -> Result<PathBuf> {
-        let dir = dir.as_ref();
+        let mut candidates = Self::find_all(dir)?;
+
+        match candidates.len() {
+            0 => Err(Error::NoUProjectFile),
+            1 => Ok(candidates.remove(0)),
+            _ => Err(Error::MultipleUProjectFiles(
+                candidates
+                    .iter()
+                    .filter_map(|p| p.file_name())
+                    .map(|n| format!("  - {}", n.to_string_lossy()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )),
+        }
+    }
 
-        for entry in fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
+    /// Find all .uproject files in the given directory, sorted by name
+    pub fn find_all<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>> {
+        let mut found = Vec::new();
 
-            if path.extension().and_then(|s| s.to_str()) == Some("uproject") {
-                return Ok(path);
+        for entry in fs::read_dir(dir.as_ref())? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("uproject") {
+                found.push(path);
             }
         }
 
-        Err(Error::NoUProjectFile)
+        found.sort();
+        Ok(found)
+    }
+
+    /// Select the .uproject file in `dir`, preferring `preferred` if given
+    ///
+    /// `preferred` is relative to `dir` (the `project.uproject` manifest setting).
+    pub fn select<P: AsRef<Path>>(dir: P, preferred: Option<&str>) -> Result<PathBuf> {
+        let dir = dir.as_ref();
+
+        match preferred {
+            Some(file) => {
+                let path = dir.join(file);
+                if !path.is_file() {
+                    return Err(Error::InvalidManifest(format!(
+                        "project.uproject '{}' not found in {}",
+                        file,
+                        dir.display()
+                    )));
+                }
+                Ok(path)
+            }
+            None => Self::find(dir),
+        }
     }
 
     /// Load .uproject file
@@ -202,6 +1159,62 @@ impl UProject {
         Ok(uproject)
     }
 
+    /// Point a .uproject's `AdditionalPluginDirectories` at package store
+    /// folders
+    ///
+    /// Entries inside `store_dir` belong to unrealpm and are replaced by
+    /// `dirs`; other entries and the file's formatting are kept. Returns
+    /// whether the file changed.
+    pub fn set_store_plugin_directories<P: AsRef<Path>>(
+        path: P,
+        store_dir: &Path,
+        dirs: &[PathBuf],
+    ) -> Result<bool> {
+        const KEY: &str = "AdditionalPluginDirectories";
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let mut document: serde_json::Value =
+            serde_json::from_str(&crate::json_edit::strip_comments(&text))?;
+        let Some(object) = document.as_object_mut() else {
+            return Err(Error::InvalidManifest(format!(
+                "{} is not a JSON object",
+                path.display()
+            )));
+        };
+
+        let existing: Vec<String> = object
+            .get(KEY)
+            .and_then(|value| value.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| entry.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut wanted: Vec<String> = existing
+            .iter()
+            .filter(|entry| !Path::new(entry.as_str()).starts_with(store_dir))
+            .cloned()
+            .collect();
+        wanted.extend(
+            dirs.iter()
+                .map(|dir| dir.to_string_lossy().replace('\\', "/")),
+        );
+        if wanted == existing {
+            return Ok(false);
+        }
+
+        if wanted.is_empty() {
+            object.remove(KEY);
+        } else {
+            object.insert(KEY.to_string(), serde_json::json!(wanted));
+        }
+        let updated = crate::json_edit::update_document(&text, &document, &[KEY])?;
+        crate::vcs::track(&[path], || Ok(fs::write(path, &updated)?))?;
+        Ok(true)
+    }
+
     /// Get project name from filename
     pub fn name<P: AsRef<Path>>(path: P) -> Option<String> {
         path.as_ref()
@@ -256,8 +1269,88 @@ pub struct UPlugin {
     #[serde(rename = "IsBetaVersion", skip_serializing_if = "Option::is_none")]
     pub is_beta_version: Option<bool>,
 
+    /// Other plugins may not depend on a sealed plugin
+    #[serde(rename = "IsSealed", skip_serializing_if = "Option::is_none")]
+    pub is_sealed: Option<bool>,
+
+    /// Plugins this plugin promises never to depend on
+    #[serde(
+        rename = "DisallowedPlugins",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub disallowed_plugins: Vec<String>,
+
+    #[serde(
+        rename = "SupportedTargetPlatforms",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub supported_target_platforms: Vec<String>,
+
+    #[serde(
+        rename = "LocalizationTargets",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub localization_targets: Vec<UPluginLocalizationTarget>,
+
     #[serde(rename = "Plugins", default, skip_serializing_if = "Vec::is_empty")]
     pub plugins: Vec<UPluginDependency>,
+
+    #[serde(rename = "Modules", default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<UPluginModule>,
+
+    /// Keys not modelled above, kept so they survive a save
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A module declared in a .uplugin's `Modules` array
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UPluginModule {
+    #[serde(rename = "Name")]
+    pub name: String,
+
+    #[serde(rename = "Type", skip_serializing_if = "Option::is_none")]
+    pub module_type: Option<String>,
+
+    #[serde(rename = "LoadingPhase", skip_serializing_if = "Option::is_none")]
+    pub loading_phase: Option<String>,
+
+    #[serde(rename = "PlatformAllowList", skip_serializing_if = "Option::is_none")]
+    pub platform_allow_list: Option<Vec<String>>,
+
+    #[serde(rename = "PlatformDenyList", skip_serializing_if = "Option::is_none")]
+    pub platform_deny_list: Option<Vec<String>>,
+
+    /// Pre-5.1 spelling of `PlatformAllowList`, kept as written
+    #[serde(rename = "WhitelistPlatforms", skip_serializing_if = "Option::is_none")]
+    pub whitelist_platforms: Option<Vec<String>>,
+
+    /// Pre-5.1 spelling of `PlatformDenyList`, kept as written
+    #[serde(rename = "BlacklistPlatforms", skip_serializing_if = "Option::is_none")]
+    pub blacklist_platforms: Option<Vec<String>>,
+
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl UPluginModule {
+    /// Platforms the module is limited to, under either spelling (`None` = all)
+    pub fn allowed_platforms(&self) -> Option<&[String]> {
+        self.platform_allow_list
+            .as_deref()
+            .or(self.whitelist_platforms.as_deref())
+    }
+
+    /// Platforms the module is excluded from, under either spelling
+    pub fn denied_platforms(&self) -> &[String] {
+        self.platform_deny_list
+            .as_deref()
+            .or(self.blacklist_platforms.as_deref())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -267,6 +1360,27 @@ pub struct UPluginDependency {
 
     #[serde(rename = "Enabled")]
     pub enabled: bool,
+
+    #[serde(rename = "Optional", skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A localization target declared in a .uplugin's `LocalizationTargets` array
+///
+/// Its text lives in `Content/Localization/<Name>` inside the plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UPluginLocalizationTarget {
+    #[serde(rename = "Name")]
+    pub name: String,
+
+    #[serde(rename = "LoadingPolicy", skip_serializing_if = "Option::is_none")]
+    pub loading_policy: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl UPlugin {
@@ -295,6 +1409,107 @@ impl UPlugin {
         Ok(uplugin)
     }
 
+    /// Save .uplugin file, editing an existing one in place
+    ///
+    /// Only values that changed are rewritten, so formatting, key order and
+    /// keys this type does not model are preserved. A `Version` written as a
+    /// float (e.g. `5.3`) is kept as long as it still reads as the same value.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut value = serde_json::to_value(self)?;
+        let existing = fs::read_to_string(path).ok();
+        let existing_value = existing
+            .as_deref()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&strip_comments(text)).ok());
+
+        let content = match (existing, existing_value) {
+            (Some(text), Some(existing_value)) => {
+                let same_version = serde_json::from_value::<UPlugin>(existing_value.clone())
+                    .is_ok_and(|old| old.version == self.version);
+                if let (true, Some(raw)) = (same_version, existing_value.get("Version")) {
+                    value["Version"] = raw.clone();
+                }
+
+                // Keys with empty arrays are skipped on serialization, so
+                // only remove keys that held something
+                let managed: Vec<&str> = existing_value
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter(|(_, v)| !v.as_array().is_some_and(|a| a.is_empty()))
+                    .map(|(k, _)| k.as_str())
+                    .collect();
+                update_document(&text, &value, &managed)?
+            }
+            _ => serde_json::to_string_pretty(&value)?,
+        };
+
+        crate::vcs::track(&[path], || Ok(fs::write(path, content)?))
+    }
+
+    /// Problems the engine would report when loading this plugin from `plugin_dir`
+    ///
+    /// Checks that the plugin does not depend on anything in its own
+    /// `DisallowedPlugins`, that no module both allows and denies a platform
+    /// or mixes the current and pre-5.1 list spellings, and that every
+    /// localization target has its `Content/Localization/<Name>` folder.
+    pub fn validate(&self, plugin_dir: &Path) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for dependency in &self.plugins {
+            if self
+                .disallowed_plugins
+                .iter()
+                .any(|d| d.eq_ignore_ascii_case(&dependency.name))
+            {
+                problems.push(format!(
+                    "depends on {}, which its DisallowedPlugins forbids",
+                    dependency.name
+                ));
+            }
+        }
+
+        for module in &self.modules {
+            if module.platform_allow_list.is_some() && module.whitelist_platforms.is_some()
+                || module.platform_deny_list.is_some() && module.blacklist_platforms.is_some()
+            {
+                problems.push(format!(
+                    "module {} mixes PlatformAllowList/PlatformDenyList with the pre-5.1 \
+                     WhitelistPlatforms/BlacklistPlatforms; the engine reads only one",
+                    module.name
+                ));
+            }
+            for platform in module.allowed_platforms().unwrap_or_default() {
+                if module.denied_platforms().contains(platform) {
+                    problems.push(format!(
+                        "module {} both allows and denies platform {}",
+                        module.name, platform
+                    ));
+                }
+            }
+        }
+
+        for target in &self.localization_targets {
+            let dir = plugin_dir
+                .join("Content")
+                .join("Localization")
+                .join(&target.name);
+            if !dir.is_dir() {
+                problems.push(format!(
+                    "localization target {} has no Content/Localization/{} folder",
+                    target.name, target.name
+                ));
+            }
+        }
+
+        problems
+    }
+
+    /// Names of the modules this plugin declares
+    pub fn module_names(&self) -> Vec<String> {
+        self.modules.iter().map(|m| m.name.clone()).collect()
+    }
+
     /// Get plugin name from filename
     pub fn name<P: AsRef<Path>>(path: P) -> Option<String> {
         path.as_ref()
@@ -353,6 +1568,227 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_save_preserves_comments_and_formatting() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let original = r#"{
+    // Engine pinned for the whole team
+    "engine_version": "5.3",
+    "dependencies": {
+        "awesome-plugin": "^1.0.0", // waiting on 2.0
+    },
+    "$schema": "https://example.com/unrealpm.schema.json",
+}
+"#;
+        fs::write(temp.path().join("unrealpm.json"), original).unwrap();
+
+        let mut manifest = Manifest::load(temp.path()).unwrap();
+        assert_eq!(manifest.dependencies["awesome-plugin"], "^1.0.0");
+
+        manifest
+            .dependencies
+            .insert("other-plugin".to_string(), "^2.0.0".to_string());
+        manifest.save(temp.path()).unwrap();
+
+        let saved = fs::read_to_string(temp.path().join("unrealpm.json")).unwrap();
+        assert_eq!(
+            saved,
+            original.replace(
+                "// waiting on 2.0\n",
+                "// waiting on 2.0\n        \"other-plugin\": \"^2.0.0\",\n"
+            )
+        );
+
+        manifest.dependencies.remove("other-plugin");
+        manifest.save(temp.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(temp.path().join("unrealpm.json")).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_save_keeps_dev_dependencies_spelling() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let original = r#"{
+    "dependencies": { "awesome-plugin": "^1.0.0" },
+    "devDependencies": { "test-kit": "*", "bench-kit": "*" }
+}
+"#;
+        fs::write(temp.path().join("unrealpm.json"), original).unwrap();
+
+        let mut manifest = Manifest::load(temp.path()).unwrap();
+        manifest.dev_dependencies.remove("bench-kit");
+        manifest.save(temp.path()).unwrap();
+
+        let saved = fs::read_to_string(temp.path().join("unrealpm.json")).unwrap();
+        assert!(!saved.contains("bench-kit"));
+        assert!(!saved.contains("dev_dependencies"));
+        assert_eq!(
+            Manifest::load(temp.path()).unwrap().dev_dependencies.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_toml_manifest_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let original = r#"# Engine pinned for the whole team
+engine_version = "5.3"
+
+[dependencies]
+awesome-plugin = "^1.0.0" # waiting on 2.0
+win-only-plugin = { version = "^1.0", platforms = ["Win64"] }
+"#;
+        fs::write(temp.path().join(TOML_MANIFEST_FILE_NAME), original).unwrap();
+
+        assert_eq!(
+            Manifest::detect(temp.path()).unwrap(),
+            Some(ManifestFormat::Toml)
+        );
+        let mut manifest = Manifest::load(temp.path()).unwrap();
+        assert_eq!(manifest.dependencies["awesome-plugin"], "^1.0.0");
+        assert_eq!(
+            manifest.dependency_conditions["win-only-plugin"].platforms,
+            Some(vec!["Win64".to_string()])
+        );
+
+        // Saving keeps the format and only touches the changed entry
+        manifest
+            .dependencies
+            .insert("other-plugin".to_string(), "^2.0.0".to_string());
+        manifest.save(temp.path()).unwrap();
+        assert!(!temp.path().join(MANIFEST_FILE_NAME).exists());
+
+        let saved = fs::read_to_string(temp.path().join(TOML_MANIFEST_FILE_NAME)).unwrap();
+        assert!(saved.starts_with(original));
+        assert!(saved.contains("other-plugin = \"^2.0.0\""));
+        assert_eq!(
+            Manifest::load(temp.path()).unwrap().dependencies["other-plugin"],
+            "^2.0.0"
+        );
+
+        manifest.dependencies.remove("other-plugin");
+        manifest.save(temp.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(temp.path().join(TOML_MANIFEST_FILE_NAME)).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_json_and_toml_manifests_are_exclusive() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(Manifest::detect(temp.path()).unwrap(), None);
+
+        Manifest::new()
+            .save_as(temp.path(), ManifestFormat::Toml)
+            .unwrap();
+        assert!(Manifest::exists(temp.path()));
+        assert_eq!(Manifest::file_name(temp.path()), TOML_MANIFEST_FILE_NAME);
+
+        fs::write(temp.path().join(MANIFEST_FILE_NAME), "{}").unwrap();
+        assert!(Manifest::detect(temp.path()).is_err());
+        assert!(Manifest::load(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_workspace_find() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::write(
+            temp.path().join(WORKSPACE_FILE_NAME),
+            r#"{"members": ["Game", "Tools/Editor"]}"#,
+        )
+        .unwrap();
+        let nested = temp.path().join("Game/Source");
+        fs::create_dir_all(&nested).unwrap();
+
+        let (root, workspace) = Workspace::find(&nested).unwrap().unwrap();
+        assert_eq!(root, temp.path());
+        assert_eq!(
+            workspace.member_dirs(&root),
+            vec![temp.path().join("Game"), temp.path().join("Tools/Editor")]
+        );
+    }
+
+    #[test]
+    fn test_workspace_merge_dependencies() {
+        let game = HashMap::from([
+            ("shared".to_string(), "^1.2.0".to_string()),
+            ("game-only".to_string(), "^2.0.0".to_string()),
+        ]);
+        let server = HashMap::from([
+            ("shared".to_string(), "<1.5.0".to_string()),
+            ("any".to_string(), "*".to_string()),
+        ]);
+        let tools = HashMap::from([
+            ("shared".to_string(), "^1.2.0".to_string()),
+            ("any".to_string(), "^3.0.0".to_string()),
+        ]);
+
+        let merged = Workspace::merge_dependencies([&game, &server, &tools]);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged["shared"], "<1.5.0, ^1.2.0");
+        assert_eq!(merged["game-only"], "^2.0.0");
+        assert_eq!(merged["any"], "^3.0.0");
+    }
+
+    #[test]
+    fn test_workspace_load_missing() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert!(Workspace::load(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_uproject_find_single() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::write(temp.path().join("Game.uproject"), "{}").unwrap();
+
+        let path = UProject::find(temp.path()).unwrap();
+        assert_eq!(path, temp.path().join("Game.uproject"));
+    }
+
+    #[test]
+    fn test_uproject_find_multiple() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::write(temp.path().join("Game.uproject"), "{}").unwrap();
+        fs::write(temp.path().join("GameServer.uproject"), "{}").unwrap();
+
+        match UProject::find(temp.path()) {
+            Err(Error::MultipleUProjectFiles(candidates)) => {
+                assert_eq!(candidates, "  - Game.uproject\n  - GameServer.uproject");
+            }
+            other => panic!("expected MultipleUProjectFiles, got {:?}", other),
+        }
+        assert!(matches!(
+            UProject::find(tempfile::TempDir::new().unwrap().path()),
+            Err(Error::NoUProjectFile)
+        ));
+    }
+
+    #[test]
+    fn test_manifest_uproject_setting() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::write(temp.path().join("Game.uproject"), "{}").unwrap();
+        fs::write(temp.path().join("GameServer.uproject"), "{}").unwrap();
+
+        let json = r#"{"project": {"uproject": "GameServer.uproject"}}"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            manifest.uproject_path(temp.path()).unwrap(),
+            temp.path().join("GameServer.uproject")
+        );
+
+        let serialized = serde_json::to_string(&manifest).unwrap();
+        assert!(serialized.contains(r#""uproject":"GameServer.uproject""#));
+
+        let mut missing = manifest.clone();
+        missing.project = Some(ProjectSettings {
+            uproject: Some("Other.uproject".to_string()),
+        });
+        assert!(missing.uproject_path(temp.path()).is_err());
+    }
+
     #[test]
     fn test_manifest_new() {
         let manifest = Manifest::new();
@@ -386,6 +1822,219 @@ mod tests {
         assert_eq!(deserialized.dependencies.len(), 1);
     }
 
+    #[test]
+    fn test_scoped_dependency_names() {
+        let json = r#"{
+            "dependencies": { "@mystudio/core-utils": "^1.0.0" },
+            "dev_dependencies": { "test-kit": "*" }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.dependencies["@mystudio/core-utils"], "^1.0.0");
+
+        let toml = toml::to_string(&ManifestFile::from(manifest)).unwrap();
+        assert!(toml.contains("\"@mystudio/core-utils\""));
+
+        let json = r#"{ "dependencies": { "../core-utils": "^1.0.0" } }"#;
+        let error = serde_json::from_str::<Manifest>(json).unwrap_err();
+        assert!(error.to_string().contains("Invalid package name"));
+    }
+
+    #[test]
+    fn test_constraint_validation() {
+        let json = r#"{ "dependencies": { "awesome": "^1.x.2" } }"#;
+        let error = serde_json::from_str::<Manifest>(json)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("invalid version constraint '^1.x.2' for awesome"));
+        assert!(error.contains("Valid constraints look like"));
+
+        let json = r#"{
+            "dependencies": {
+                "good": ">=1.2, <2.0",
+                "bad": { "version": "1..0" },
+                "local": { "path": "../Local" }
+            },
+            "dev_dependencies": { "empty": "" },
+            "optional_dependencies": { "wild": "*" }
+        }"#;
+        let errors = Manifest::constraint_errors(json, ManifestFormat::Json).unwrap();
+        let names: Vec<&str> = errors.iter().map(|e| e.dependency.as_str()).collect();
+        assert_eq!(names, vec!["bad", "empty"]);
+        assert_eq!(errors[1].reason, "the constraint is empty");
+
+        assert!(check_constraint("awesome", "~5.3").is_ok());
+    }
+
+    #[test]
+    fn test_conditional_dependencies_roundtrip() {
+        let json = r#"{
+            "dependencies": {
+                "core-plugin": "^1.0.0",
+                "win-only-plugin": { "version": "^1.0", "platforms": ["Win64"] },
+                "new-api-plugin": { "version": "^2.0", "engines": ">=5.3" },
+                "plain-object": { "version": "^3.0" }
+            }
+        }"#;
+
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.dependencies.len(), 4);
+        assert_eq!(manifest.dependencies["win-only-plugin"], "^1.0");
+        assert_eq!(manifest.dependency_conditions.len(), 2);
+        assert_eq!(
+            manifest.dependency_conditions["win-only-plugin"].platforms,
+            Some(vec!["Win64".to_string()])
+        );
+
+        // Conditions are written back inline; unconditional entries stay strings
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(value["dependencies"]["core-plugin"], "^1.0.0");
+        assert_eq!(value["dependencies"]["plain-object"], "^3.0");
+        assert_eq!(value["dependencies"]["new-api-plugin"]["engines"], ">=5.3");
+        assert_eq!(value["dependencies"]["new-api-plugin"]["version"], "^2.0");
+    }
+
+    #[test]
+    fn test_dependency_integrity() {
+        let checksum = "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08";
+        let json = format!(
+            r#"{{"dependencies": {{"audited": {{"version": "1.2.3", "integrity": "sha256-{}"}}}}}}"#,
+            checksum
+        );
+        let manifest: Manifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(manifest.dependencies["audited"], "1.2.3");
+        assert_eq!(
+            manifest.dependency_integrity["audited"],
+            checksum.to_lowercase()
+        );
+        assert!(manifest.dependency_conditions.is_empty());
+
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            value["dependencies"]["audited"]["integrity"],
+            format!("sha256-{}", checksum.to_lowercase())
+        );
+
+        let range = json.replace("1.2.3", "^1.2.3");
+        let err = serde_json::from_str::<Manifest>(&range).unwrap_err();
+        assert!(err.to_string().contains("requires an exact version"));
+
+        let short =
+            r#"{"dependencies": {"audited": {"version": "1.2.3", "integrity": "sha256-abc"}}}"#;
+        let err = serde_json::from_str::<Manifest>(short).unwrap_err();
+        assert!(err.to_string().contains("invalid integrity"));
+    }
+
+    #[test]
+    fn test_engine_override() {
+        let json = r#"{"dependencies": {"built-in": {"version": "^2.0", "engine_override": true}, "plain": "^1.0"}}"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert!(manifest.engine_overrides.contains("built-in"));
+        assert!(!manifest.engine_overrides.contains("plain"));
+
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(value["dependencies"]["built-in"]["engine_override"], true);
+        assert_eq!(value["dependencies"]["plain"], "^1.0");
+    }
+
+    #[test]
+    fn test_path_dependencies() {
+        let json = r#"{"dependencies": {"my-plugin": {"path": "../MyPlugin"}, "plain": "^1.0"}}"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.path_dependencies["my-plugin"], "../MyPlugin");
+        // Path dependencies are never resolved against the registry
+        assert!(!manifest.dependencies.contains_key("my-plugin"));
+        assert_eq!(manifest.dependencies["plain"], "^1.0");
+
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            value["dependencies"]["my-plugin"],
+            serde_json::json!({"path": "../MyPlugin"})
+        );
+        assert_eq!(value["dependencies"]["plain"], "^1.0");
+
+        let json = r#"{"dependencies": {"my-plugin": {"path": " "}}}"#;
+        assert!(serde_json::from_str::<Manifest>(json).is_err());
+    }
+
+    #[test]
+    fn test_dev_dependencies() {
+        let json = r#"{"dependencies": {"core": "^1.0"}, "devDependencies": {"test-kit": "^2.0"}}"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.dev_dependencies["test-kit"], "^2.0");
+        assert!(!manifest.dependencies.contains_key("test-kit"));
+
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(value["dev_dependencies"]["test-kit"], "^2.0");
+    }
+
+    #[test]
+    fn test_features() {
+        let json = r#"{
+            "dependencies": {
+                "vfx-toolkit": { "version": "^1.0", "features": ["niagara-support"] }
+            },
+            "optional_dependencies": { "NiagaraHelpers": "^1.2" },
+            "features": { "niagara-support": ["NiagaraHelpers"] }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+        assert_eq!(manifest.dependencies["vfx-toolkit"], "^1.0");
+        assert_eq!(
+            manifest.dependency_features["vfx-toolkit"],
+            vec!["niagara-support".to_string()]
+        );
+
+        let offered = manifest.feature_dependencies();
+        assert_eq!(offered["niagara-support"][0].name, "NiagaraHelpers");
+        assert_eq!(offered["niagara-support"][0].version, "^1.2");
+
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            value["dependencies"]["vfx-toolkit"]["features"][0],
+            "niagara-support"
+        );
+        assert_eq!(value["features"]["niagara-support"][0], "NiagaraHelpers");
+
+        let json = r#"{"features": {"extra": ["Missing"]}}"#;
+        let err = serde_json::from_str::<Manifest>(json).unwrap_err();
+        assert!(err.to_string().contains("not in optional_dependencies"));
+    }
+
+    #[test]
+    fn test_conditional_dependencies_invalid_engines() {
+        let json = r#"{"dependencies": {"bad": {"version": "^1.0", "engines": "five"}}}"#;
+        let err = serde_json::from_str::<Manifest>(json).unwrap_err();
+        assert!(err.to_string().contains("invalid engines requirement"));
+    }
+
+    #[test]
+    fn test_active_dependencies() {
+        let json = r#"{
+            "dependencies": {
+                "core-plugin": "^1.0.0",
+                "win-only-plugin": { "version": "^1.0", "platforms": ["Win64"] },
+                "new-api-plugin": { "version": "^2.0", "engines": ">=5.3" }
+            }
+        }"#;
+        let manifest: Manifest = serde_json::from_str(json).unwrap();
+
+        let linux_54 = manifest.active_dependencies("Linux", Some("5.4"));
+        assert!(linux_54.contains_key("core-plugin"));
+        assert!(linux_54.contains_key("new-api-plugin"));
+        assert!(!linux_54.contains_key("win-only-plugin"));
+
+        let win_52 = manifest.active_dependencies("win64", Some("5.2"));
+        assert!(win_52.contains_key("win-only-plugin"));
+        assert!(!win_52.contains_key("new-api-plugin"));
+
+        // Unknown engine version does not exclude anything engine-gated
+        assert_eq!(manifest.active_dependencies("Win64", None).len(), 3);
+
+        let inactive = manifest.inactive_dependencies("Linux", Some("5.2"));
+        let names: Vec<_> = inactive.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["new-api-plugin", "win-only-plugin"]);
+        assert_eq!(inactive[1].1.describe(), "platforms: Win64");
+    }
+
     #[test]
     fn test_uproject_name() {
         let path = std::path::Path::new("/path/to/MyProject.uproject");
@@ -414,6 +2063,43 @@ mod tests {
         assert_eq!(uproject.description, Some("Test project".to_string()));
     }
 
+    #[test]
+    fn test_set_store_plugin_directories() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Game.uproject");
+        let store = temp.path().join("store");
+        let text = "{\n\t\"FileVersion\": 3,\n\t\"EngineAssociation\": \"5.3\",\n\t\"AdditionalPluginDirectories\": [\"D:/Shared\"]\n}\n";
+        fs::write(&path, text).unwrap();
+
+        // Store folders are added after the user's own entries
+        let dirs = vec![store.join("abc"), store.join("def")];
+        assert!(UProject::set_store_plugin_directories(&path, &store, &dirs).unwrap());
+        assert!(!UProject::set_store_plugin_directories(&path, &store, &dirs).unwrap());
+        let uproject = UProject::load(&path).unwrap();
+        assert_eq!(uproject.additional_plugin_directories.len(), 3);
+        assert_eq!(uproject.additional_plugin_directories[0], "D:/Shared");
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("\t\"EngineAssociation\""));
+
+        // Dropping the store folders leaves the user's entry alone
+        assert!(UProject::set_store_plugin_directories(&path, &store, &[]).unwrap());
+        let uproject = UProject::load(&path).unwrap();
+        assert_eq!(uproject.additional_plugin_directories, vec!["D:/Shared"]);
+
+        // The key goes away once nothing is left in it
+        fs::write(
+            &path,
+            "{\"FileVersion\": 3, \"EngineAssociation\": \"5.3\"}",
+        )
+        .unwrap();
+        assert!(UProject::set_store_plugin_directories(&path, &store, &dirs).unwrap());
+        assert!(UProject::set_store_plugin_directories(&path, &store, &[]).unwrap());
+        assert!(!fs::read_to_string(&path)
+            .unwrap()
+            .contains("AdditionalPluginDirectories"));
+    }
+
     #[test]
     fn test_uplugin_parse() {
         let json = r#"{
@@ -443,4 +2129,106 @@ mod tests {
         assert_eq!(uplugin.friendly_name, "My Plugin");
         assert_eq!(uplugin.category, Some("Gameplay".to_string()));
     }
+
+    #[test]
+    fn test_uplugin_modules() {
+        let uplugin: UPlugin = serde_json::from_str(
+            r#"{"FileVersion": 3, "Version": 1, "VersionName": "1.0", "FriendlyName": "P",
+                "Modules": [
+                    {"Name": "AwesomeRuntime", "Type": "Runtime", "LoadingPhase": "Default"},
+                    {"Name": "AwesomeEditor", "Type": "Editor"}
+                ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            uplugin.module_names(),
+            vec!["AwesomeRuntime", "AwesomeEditor"]
+        );
+        assert_eq!(uplugin.modules[1].loading_phase, None);
+    }
+
+    #[test]
+    fn test_uplugin_save_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Sealed.uplugin");
+        let original = "{\n\t\"FileVersion\": 3,\n\t\"Version\": 5.3,\n\t\"VersionName\": \"1.0\",\n\t\"FriendlyName\": \"Sealed\",\n\t\"IsSealed\": true,\n\t\"DisallowedPlugins\": [\"OnlineSubsystem\"],\n\t\"Modules\": [\n\t\t{\n\t\t\t\"Name\": \"SealedRuntime\",\n\t\t\t\"Type\": \"Runtime\",\n\t\t\t\"WhitelistPlatforms\": [\"Win64\"],\n\t\t\t\"HasExplicitPlatforms\": true\n\t\t}\n\t],\n\t\"PreBuildSteps\": {\"Win64\": [\"echo hi\"]}\n}\n";
+        fs::write(&path, original).unwrap();
+
+        let mut uplugin = UPlugin::load(&path).unwrap();
+        assert_eq!(uplugin.is_sealed, Some(true));
+        assert_eq!(uplugin.disallowed_plugins, vec!["OnlineSubsystem"]);
+        assert_eq!(
+            uplugin.modules[0].allowed_platforms(),
+            Some(&["Win64".to_string()][..])
+        );
+        assert!(uplugin.extra.contains_key("PreBuildSteps"));
+        assert!(uplugin.modules[0]
+            .extra
+            .contains_key("HasExplicitPlatforms"));
+
+        // Saving unchanged leaves the file byte-for-byte identical
+        uplugin.save(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        // Editing one value keeps legacy spellings and unknown keys
+        uplugin.version_name = "1.1".to_string();
+        uplugin.save(&path).unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("\"VersionName\": \"1.1\""));
+        assert!(saved.contains("\"Version\": 5.3"));
+        assert!(saved.contains("WhitelistPlatforms"));
+        assert!(!saved.contains("PlatformAllowList"));
+        assert!(saved.contains("PreBuildSteps"));
+    }
+
+    #[test]
+    fn test_uplugin_validate() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let uplugin: UPlugin = serde_json::from_str(
+            r#"{"FileVersion": 3, "Version": 1, "VersionName": "1.0", "FriendlyName": "P",
+                "DisallowedPlugins": ["Paper2D"],
+                "Plugins": [{"Name": "Paper2D", "Enabled": true}, {"Name": "EnhancedInput", "Enabled": true}],
+                "LocalizationTargets": [{"Name": "PText", "LoadingPolicy": "Always"}],
+                "Modules": [
+                    {"Name": "PRuntime", "Type": "Runtime",
+                     "PlatformAllowList": ["Win64", "Linux"], "PlatformDenyList": ["Linux"]},
+                    {"Name": "PEditor", "Type": "Editor",
+                     "PlatformAllowList": ["Win64"], "WhitelistPlatforms": ["Win64"]}
+                ]}"#,
+        )
+        .unwrap();
+
+        let problems = uplugin.validate(temp.path());
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[0].contains("Paper2D"));
+        assert!(problems[1].contains("PEditor") || problems[1].contains("PRuntime"));
+        assert!(problems.iter().any(|p| p.contains("denies platform Linux")));
+        assert!(problems.iter().any(|p| p.contains("PText")));
+
+        fs::create_dir_all(temp.path().join("Content/Localization/PText")).unwrap();
+        assert_eq!(uplugin.validate(temp.path()).len(), 3);
+    }
+
+    #[test]
+    fn test_discovery_tags() {
+        let uplugin: UPlugin = serde_json::from_str(
+            r#"{"FileVersion": 3, "Version": 1, "VersionName": "1.0", "FriendlyName": "P", "Category": "Networking"}"#,
+        )
+        .unwrap();
+
+        // Without a manifest, the .uplugin Category is used
+        let tags = DiscoveryTags::from_plugin(&uplugin, None);
+        assert_eq!(tags.categories, vec!["Networking"]);
+        assert!(tags.keywords.is_empty());
+
+        // Manifest categories override; entries are trimmed and de-duplicated
+        let manifest: Manifest = serde_json::from_str(
+            r#"{"categories": ["AI", " Editor Tools ", "ai"], "keywords": ["npc", ""]}"#,
+        )
+        .unwrap();
+        let tags = DiscoveryTags::from_plugin(&uplugin, Some(&manifest));
+        assert_eq!(tags.categories, vec!["AI", "Editor Tools"]);
+        assert_eq!(tags.keywords, vec!["npc"]);
+    }
 }
@@ -0,0 +1,484 @@
+//! Terminal rendering of package READMEs
+//!
+//! A small Markdown renderer for reading a package's README in the terminal:
+//! headings are bold (and underlined for the top two levels), fenced and
+//! indented code blocks are indented and colored without wrapping, list
+//! bullets become `•`, block quotes get a `│` bar, and links show their
+//! target after the text. Paragraphs are re-wrapped to the given width.
+//! Tables and raw HTML lines pass through untouched.
+//!
+//! The output uses ANSI escapes; in plain mode [`outln!`](crate::outln)
+//! strips them like any other color.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::markdown::render;
+//! use unrealpm::output::plain_text;
+//!
+//! let text = render("# Awesome\n\nSee [docs](https://example.com).\n", 80);
+//! let lines: Vec<String> = text.lines().map(plain_text).collect();
+//! assert_eq!(lines, vec!["Awesome", "", "See docs (https://example.com)."]);
+//! ```
+
+const BOLD: &str = "\x1b[1m";
+const ITALIC: &str = "\x1b[3m";
+const UNDERLINE: &str = "\x1b[4m";
+const DIM: &str = "\x1b[2m";
+const CODE: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// Render `markdown` for a terminal `width` columns wide
+pub fn render(markdown: &str, width: usize) -> String {
+    let width = width.max(20);
+    let mut out: Vec<String> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut lines = markdown.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            } else {
+                out.push(format!("    {}{}{}", CODE, line, RESET));
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            flush(&mut paragraph, &mut out, width);
+            fence = Some(marker);
+            continue;
+        }
+
+        // Indented code needs a blank line (or another code line) before it
+        if line.starts_with("    ") && paragraph.is_empty() && !trimmed.is_empty() {
+            out.push(format!("    {}{}{}", CODE, &line[4..], RESET));
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut out, width);
+            if out.last().is_some_and(|l| !l.is_empty()) {
+                out.push(String::new());
+            }
+            continue;
+        }
+
+        if let Some((level, text)) = heading(trimmed) {
+            flush(&mut paragraph, &mut out, width);
+            out.push(styled_heading(level, text));
+            continue;
+        }
+
+        // Setext headings underline the paragraph's last line
+        if !paragraph.is_empty() && is_underline(trimmed) {
+            let level = if trimmed.starts_with('=') { 1 } else { 2 };
+            let text = paragraph.pop().unwrap_or_default();
+            flush(&mut paragraph, &mut out, width);
+            out.push(styled_heading(level, text.trim()));
+            continue;
+        }
+
+        if is_rule(trimmed) {
+            flush(&mut paragraph, &mut out, width);
+            out.push(format!("{}{}{}", DIM, "─".repeat(width.min(80)), RESET));
+            continue;
+        }
+
+        if trimmed.starts_with('|') || trimmed.starts_with('<') {
+            flush(&mut paragraph, &mut out, width);
+            out.push(line.to_string());
+            continue;
+        }
+
+        if let Some(quoted) = trimmed.strip_prefix('>') {
+            flush(&mut paragraph, &mut out, width);
+            let prefix = format!("{}│{} ", DIM, RESET);
+            wrap_into(&inline(quoted.trim()), &prefix, &prefix, width, &mut out);
+            continue;
+        }
+
+        if let Some((marker, item)) = list_item(trimmed) {
+            flush(&mut paragraph, &mut out, width);
+            let indent = " ".repeat(line.len() - trimmed.len());
+            let first = format!("{}  {} ", indent, marker);
+            let rest = format!("{}{}", indent, " ".repeat(marker.chars().count() + 3));
+            // Continuation lines belong to the item
+            let mut text = item.to_string();
+            while let Some(next) = lines.peek() {
+                let next_trimmed = next.trim_start();
+                if next_trimmed.is_empty()
+                    || !next.starts_with(' ')
+                    || list_item(next_trimmed).is_some()
+                {
+                    break;
+                }
+                text.push(' ');
+                text.push_str(next_trimmed);
+                lines.next();
+            }
+            wrap_into(&inline(&text), &first, &rest, width, &mut out);
+            continue;
+        }
+
+        paragraph.push(trimmed);
+    }
+    flush(&mut paragraph, &mut out, width);
+
+    while out.last().is_some_and(|l| l.is_empty()) {
+        out.pop();
+    }
+    out.join("\n")
+}
+
+/// Heading level and text of an ATX heading (`## Title`)
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+fn styled_heading(level: usize, text: &str) -> String {
+    let style = if level <= 2 {
+        format!("{}{}", BOLD, UNDERLINE)
+    } else {
+        BOLD.to_string()
+    };
+    format!("{}{}{}", style, strip_inline(text), RESET)
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= 2 && (line.chars().all(|c| c == '=') || line.chars().all(|c| c == '-'))
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ["-", "*", "_"]
+            .iter()
+            .any(|m| compact.chars().all(|c| c.to_string() == *m))
+}
+
+/// Bullet to show and text of a list item (`- item`, `1. item`)
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            // "- [ ] task" and "- [x] task"
+            if let Some(task) = item.strip_prefix("[ ] ") {
+                return Some(("[ ]".to_string(), task));
+            }
+            if let Some(task) = item
+                .strip_prefix("[x] ")
+                .or_else(|| item.strip_prefix("[X] "))
+            {
+                return Some(("[x]".to_string(), task));
+            }
+            return Some(("•".to_string(), item));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(item) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return Some((format!("{}.", &line[..digits]), item));
+        }
+    }
+    None
+}
+
+fn flush(paragraph: &mut Vec<&str>, out: &mut Vec<String>, width: usize) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let text = inline(&paragraph.join(" "));
+    wrap_into(&text, "", "", width, out);
+    paragraph.clear();
+}
+
+/// Apply inline styles: code spans, emphasis, links, and images
+fn inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    let mut bold = false;
+    let mut italic = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() => {
+                out.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                let ticks = chars[i..].iter().take_while(|&&c| c == '`').count();
+                let fence: String = "`".repeat(ticks);
+                let body: String = chars[i + ticks..].iter().collect();
+                match body.find(&fence) {
+                    Some(end) => {
+                        out.push_str(CODE);
+                        out.push_str(body[..end].trim());
+                        out.push_str(RESET);
+                        out.push_str(restore(bold, italic));
+                        i += ticks + body[..end].chars().count() + ticks;
+                    }
+                    None => {
+                        out.push_str(&fence);
+                        i += ticks;
+                    }
+                }
+            }
+            '*' | '_' => {
+                let run = chars[i..].iter().take_while(|&&r| r == c).count();
+                // Underscores inside words are literal (snake_case)
+                let in_word = c == '_'
+                    && i > 0
+                    && chars[i - 1].is_alphanumeric()
+                    && chars.get(i + run).is_some_and(|n| n.is_alphanumeric());
+                if in_word {
+                    out.extend(std::iter::repeat_n(c, run));
+                } else if run >= 2 {
+                    bold = !bold;
+                    out.push_str(if bold { BOLD } else { RESET });
+                    if !bold {
+                        out.push_str(restore(false, italic));
+                    }
+                } else {
+                    italic = !italic;
+                    out.push_str(if italic { ITALIC } else { RESET });
+                    if !italic {
+                        out.push_str(restore(bold, false));
+                    }
+                }
+                i += if in_word { run } else { run.min(2) };
+            }
+            '!' | '[' => {
+                let image = c == '!';
+                let start = if image { i + 1 } else { i };
+                match link_at(&chars, start) {
+                    Some((label, target, end)) if !image || chars.get(start) == Some(&'[') => {
+                        if image {
+                            out.push_str(&format!("{}[image: {}]{}", DIM, label, RESET));
+                        } else if label == target || target.is_empty() {
+                            out.push_str(&format!("{}{}{}", UNDERLINE, label, RESET));
+                        } else {
+                            out.push_str(&format!(
+                                "{}{}{} {}({}){}",
+                                UNDERLINE, label, RESET, DIM, target, RESET
+                            ));
+                        }
+                        out.push_str(restore(bold, italic));
+                        i = end;
+                    }
+                    _ => {
+                        out.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            '<' => {
+                // Autolinks (<https://...>) keep their text; other tags are dropped
+                let rest: String = chars[i + 1..].iter().collect();
+                match rest.find('>') {
+                    Some(end) if rest[..end].contains("://") => {
+                        out.push_str(&format!("{}{}{}", UNDERLINE, &rest[..end], RESET));
+                        i += end + 2;
+                    }
+                    Some(end) if is_tag(&rest[..end]) => i += end + 2,
+                    _ => {
+                        out.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    if bold || italic {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// Styles still open after a reset
+fn restore(bold: bool, italic: bool) -> &'static str {
+    match (bold, italic) {
+        (true, true) => "\x1b[1;3m",
+        (true, false) => BOLD,
+        (false, true) => ITALIC,
+        (false, false) => "",
+    }
+}
+
+/// `[label](target)` starting at `start`: label, target, and the index after it
+fn link_at(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    if chars.get(start) != Some(&'[') {
+        return None;
+    }
+    let close = start + chars[start..].iter().position(|&c| c == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = close + 1 + chars[close + 1..].iter().position(|&c| c == ')')?;
+    let label: String = chars[start + 1..close].iter().collect();
+    let target: String = chars[close + 2..end].iter().collect();
+    // Drop a link title: [text](url "title")
+    let target = target.split_whitespace().next().unwrap_or_default();
+    Some((strip_inline(&label), target.to_string(), end + 1))
+}
+
+fn is_tag(text: &str) -> bool {
+    let name = text.trim_start_matches('/');
+    name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && (!name.contains(' ') || name.contains('='))
+}
+
+/// Text with inline markers removed, for headings and link labels
+fn strip_inline(text: &str) -> String {
+    crate::output::plain_text(&inline(text))
+}
+
+/// Word-wrap styled `text` by visible width, prefixing each output line
+fn wrap_into(text: &str, first: &str, rest: &str, width: usize, out: &mut Vec<String>) {
+    let mut line = first.to_string();
+    let mut line_width = visible_width(first);
+    let mut empty = true;
+    let indent = visible_width(rest);
+
+    for word in text.split(' ').filter(|w| !w.is_empty()) {
+        let word_width = visible_width(word);
+        if !empty && line_width + 1 + word_width > width {
+            out.push(std::mem::replace(&mut line, rest.to_string()));
+            line_width = indent;
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(word);
+        line_width += word_width;
+        empty = false;
+    }
+    if !empty {
+        out.push(line);
+    }
+}
+
+/// Columns `text` takes up, ignoring ANSI escapes
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::plain_text;
+
+    fn plain(markdown: &str, width: usize) -> Vec<String> {
+        render(markdown, width).lines().map(plain_text).collect()
+    }
+
+    #[test]
+    fn test_blocks() {
+        let markdown = "\
+Awesome Plugin
+==============
+
+Install it:
+
+```bash
+unrealpm install awesome-plugin
+```
+
+## Features
+
+- Fast **replication**
+- Uses `FAwesomeComponent`
+  across modules
+1. First
+
+> Note: requires 5.3
+
+---
+| a | b |
+";
+        assert_eq!(
+            plain(markdown, 80),
+            vec![
+                "Awesome Plugin",
+                "",
+                "Install it:",
+                "",
+                "    unrealpm install awesome-plugin",
+                "",
+                "Features",
+                "",
+                "  - Fast replication",
+                "  - Uses FAwesomeComponent across modules",
+                "  1. First",
+                "",
+                "| Note: requires 5.3",
+                "",
+                "-".repeat(80).as_str(),
+                "| a | b |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inline_styles() {
+        let rendered = render("Some **bold** and `code` with snake_case_name", 80);
+        assert!(rendered.contains(&format!("{}bold{}", BOLD, RESET)));
+        assert!(rendered.contains(&format!("{}code{}", CODE, RESET)));
+        assert!(rendered.contains("snake_case_name"));
+
+        assert_eq!(
+            plain("![logo](logo.png) <br> <https://unrealpm.dev>", 80),
+            vec!["[image: logo] https://unrealpm.dev"]
+        );
+    }
+
+    #[test]
+    fn test_wraps_by_visible_width() {
+        let lines = plain(
+            "one two three four five six seven eight nine ten **eleven** twelve",
+            24,
+        );
+        assert!(lines.iter().all(|l| l.chars().count() <= 24), "{:?}", lines);
+        assert_eq!(
+            lines.join(" "),
+            "one two three four five six seven eight nine ten eleven twelve"
+        );
+
+        // Code blocks are never wrapped
+        let code = format!("```\n{}\n```", "x".repeat(60));
+        assert_eq!(plain(&code, 24), vec![format!("    {}", "x".repeat(60))]);
+    }
+}
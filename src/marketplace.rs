@@ -0,0 +1,208 @@
+//! Read-only metadata for Fab/Marketplace plugins
+//!
+//! Plugins acquired through Fab (formerly the Unreal Engine Marketplace) are
+//! installed by the Epic Games Launcher into `Engine/Plugins/Marketplace`, or
+//! copied into a project's Plugins/ directory by hand. UnrealPM cannot
+//! download them, but it can discover them and pin their metadata in the
+//! lockfile with an externally managed source (`fab+<marketplace id>`), so
+//! commands like `list` and `tree` account for every plugin the project uses.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use unrealpm::marketplace::scan_engine;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! for plugin in scan_engine("C:/Program Files/Epic Games/UE_5.4")? {
+//!     println!("{}@{} ({})", plugin.name, plugin.version, plugin.marketplace_id);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Result, UPlugin};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lockfile source prefix for externally managed Fab/Marketplace plugins
+pub const FAB_SOURCE_PREFIX: &str = "fab+";
+
+/// A plugin acquired through Fab/Marketplace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketplacePlugin {
+    /// Plugin name (the .uplugin file stem)
+    pub name: String,
+
+    /// VersionName from the .uplugin
+    pub version: String,
+
+    /// Marketplace/Fab listing identifier
+    pub marketplace_id: String,
+
+    /// MarketplaceURL from the .uplugin, if set
+    pub marketplace_url: Option<String>,
+
+    /// Directory containing the .uplugin
+    pub path: PathBuf,
+}
+
+impl MarketplacePlugin {
+    /// Lockfile source string for this plugin (`fab+<marketplace id>`)
+    pub fn source(&self) -> String {
+        format!("{}{}", FAB_SOURCE_PREFIX, self.marketplace_id)
+    }
+}
+
+/// Extract the listing identifier from a Marketplace or Fab URL
+///
+/// Handles launcher URLs (`com.epicgames.launcher://ue/marketplace/product/<id>`)
+/// and Fab listings (`https://www.fab.com/listings/<id>`).
+pub fn marketplace_id(url: &str) -> Option<String> {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    url.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|id| !id.is_empty() && !id.contains(':'))
+        .map(|id| id.to_string())
+}
+
+/// Find Marketplace plugins installed into an engine by the Epic Games Launcher
+pub fn scan_engine<P: AsRef<Path>>(engine_path: P) -> Result<Vec<MarketplacePlugin>> {
+    scan_dir(
+        &engine_path
+            .as_ref()
+            .join("Engine")
+            .join("Plugins")
+            .join("Marketplace"),
+        true,
+    )
+}
+
+/// Find Fab/Marketplace plugins copied into a project's Plugins/ directory
+///
+/// Only plugins whose .uplugin declares a `MarketplaceURL` are reported.
+pub fn scan_project<P: AsRef<Path>>(project_dir: P) -> Result<Vec<MarketplacePlugin>> {
+    scan_dir(&project_dir.as_ref().join("Plugins"), false)
+}
+
+/// Scan plugin directories one level below `dir`
+///
+/// With `assume_marketplace`, plugins without a MarketplaceURL are included and
+/// identified by their directory name.
+fn scan_dir(dir: &Path, assume_marketplace: bool) -> Result<Vec<MarketplacePlugin>> {
+    let mut plugins = Vec::new();
+    if !dir.is_dir() {
+        return Ok(plugins);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let Ok(uplugin_path) = UPlugin::find(&path) else {
+            continue;
+        };
+        let Ok(uplugin) = UPlugin::load(&uplugin_path) else {
+            continue;
+        };
+
+        let marketplace_url = uplugin.marketplace_url.filter(|url| !url.trim().is_empty());
+        let id = match marketplace_url.as_deref().and_then(marketplace_id) {
+            Some(id) => id,
+            None if assume_marketplace => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            None => continue,
+        };
+
+        plugins.push(MarketplacePlugin {
+            name: UPlugin::name(&uplugin_path).unwrap_or_else(|| id.clone()),
+            version: uplugin.version_name,
+            marketplace_id: id,
+            marketplace_url,
+            path,
+        });
+    }
+
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(plugins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_uplugin(dir: &Path, name: &str, marketplace_url: &str) {
+        let plugin_dir = dir.join(name);
+        fs::create_dir_all(&plugin_dir).unwrap();
+        fs::write(
+            plugin_dir.join(format!("{}.uplugin", name)),
+            format!(
+                r#"{{"FileVersion": 3, "Version": 1, "VersionName": "2.1", "FriendlyName": "{}", "MarketplaceURL": "{}"}}"#,
+                name, marketplace_url
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_marketplace_id() {
+        assert_eq!(
+            marketplace_id("com.epicgames.launcher://ue/marketplace/product/a1b2c3"),
+            Some("a1b2c3".to_string())
+        );
+        assert_eq!(
+            marketplace_id("https://www.fab.com/listings/0f1e2d3c-aaaa/?ref=lib"),
+            Some("0f1e2d3c-aaaa".to_string())
+        );
+        assert_eq!(marketplace_id(""), None);
+        assert_eq!(marketplace_id("https://"), None);
+    }
+
+    #[test]
+    fn test_scan_engine_includes_all_marketplace_plugins() {
+        let temp = TempDir::new().unwrap();
+        let marketplace = temp.path().join("Engine/Plugins/Marketplace");
+        write_uplugin(
+            &marketplace,
+            "FabTool",
+            "com.epicgames.launcher://ue/marketplace/product/fab123",
+        );
+        write_uplugin(&marketplace, "LegacyTool", "");
+
+        let plugins = scan_engine(temp.path()).unwrap();
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[0].name, "FabTool");
+        assert_eq!(plugins[0].marketplace_id, "fab123");
+        assert_eq!(plugins[0].source(), "fab+fab123");
+        assert_eq!(plugins[0].version, "2.1");
+        assert_eq!(plugins[1].marketplace_id, "LegacyTool");
+        assert_eq!(plugins[1].marketplace_url, None);
+    }
+
+    #[test]
+    fn test_scan_project_requires_marketplace_url() {
+        let temp = TempDir::new().unwrap();
+        let plugins_dir = temp.path().join("Plugins");
+        write_uplugin(
+            &plugins_dir,
+            "CopiedFromFab",
+            "https://www.fab.com/listings/xyz",
+        );
+        write_uplugin(&plugins_dir, "OwnPlugin", "");
+
+        let plugins = scan_project(temp.path()).unwrap();
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].name, "CopiedFromFab");
+        assert_eq!(plugins[0].marketplace_id, "xyz");
+    }
+
+    #[test]
+    fn test_scan_missing_dir() {
+        let temp = TempDir::new().unwrap();
+        assert!(scan_engine(temp.path()).unwrap().is_empty());
+    }
+}
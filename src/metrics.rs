@@ -0,0 +1,288 @@
+//! Per-package install time history
+//!
+//! Every install records how long each package spent downloading,
+//! verifying, extracting, and building (see [`Timings::time_package`]). The
+//! history is kept in `~/.unrealpm/metrics.json` and used by later installs
+//! to:
+//!
+//! - show an estimated time before a multi-package install, and per package
+//!   in the status table
+//! - queue the slowest packages first, so a long download is not the last
+//!   one to start
+//!
+//! Each phase keeps a running average in which the newest install counts for
+//! half, so estimates follow a package as it grows without swinging wildly
+//! on one slow download.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use unrealpm::metrics::InstallHistory;
+//! use unrealpm::timings::{Phase, Timings};
+//!
+//! let mut timings = Timings::new();
+//! timings.record_package("big-plugin", Phase::Download, Duration::from_secs(30));
+//! timings.record_package("small-plugin", Phase::Download, Duration::from_secs(2));
+//!
+//! let mut history = InstallHistory::new("metrics.json");
+//! history.record(&timings);
+//!
+//! let mut queue = vec!["small-plugin", "new-plugin", "big-plugin"];
+//! history.sort_slowest_first(&mut queue, |name| name);
+//! assert_eq!(queue, vec!["big-plugin", "small-plugin", "new-plugin"]);
+//!
+//! let eta = history.eta(&["big-plugin", "small-plugin"], 2).unwrap();
+//! assert_eq!(eta.duration, Duration::from_secs(30));
+//! ```
+
+use crate::timings::{Phase, Timings};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Phases that run on the download workers of a concurrent install
+const PREPARE_PHASES: [Phase; 2] = [Phase::Download, Phase::Verify];
+
+/// Phases that run one package at a time after downloading
+const INSTALL_PHASES: [Phase; 2] = [Phase::Extract, Phase::Build];
+
+/// Estimated duration of a multi-package install
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eta {
+    pub duration: Duration,
+
+    /// Packages the estimate is based on (the rest have no history)
+    pub known: usize,
+
+    /// Packages in the install
+    pub total: usize,
+}
+
+/// Average phase durations per package, persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstallHistory {
+    #[serde(skip)]
+    path: PathBuf,
+
+    /// Package name -> phase -> average duration in milliseconds
+    packages: BTreeMap<String, BTreeMap<Phase, u64>>,
+}
+
+impl InstallHistory {
+    /// Empty history that saves to `path`
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            packages: BTreeMap::new(),
+        }
+    }
+
+    /// History stored at `path`
+    ///
+    /// A missing or unreadable file yields an empty history: estimates are
+    /// a convenience and never block an install.
+    pub fn load<P: AsRef<Path>>(path: P) -> Self {
+        let mut history = fs::read_to_string(path.as_ref())
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .unwrap_or_default();
+        history.path = path.as_ref().to_path_buf();
+        history
+    }
+
+    /// History at `~/.unrealpm/metrics.json`
+    pub fn open_default() -> Result<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| Error::Other("Could not find home directory".to_string()))?;
+        Ok(Self::load(home.join(".unrealpm").join("metrics.json")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize install metrics: {}", e)))?;
+        fs::write(&self.path, json)?;
+        Ok(())
+    }
+
+    /// Fold the per-package phases of an install into the averages
+    pub fn record(&mut self, timings: &Timings) {
+        for (name, phases) in timings.packages() {
+            let averages = self.packages.entry(name.clone()).or_default();
+            for (phase, duration) in phases {
+                let sample = duration.as_millis() as u64;
+                averages
+                    .entry(*phase)
+                    .and_modify(|average| *average = (*average + sample) / 2)
+                    .or_insert(sample);
+            }
+        }
+    }
+
+    /// Average time `package` spends in `phases`, or `None` without history
+    pub fn estimate(&self, package: &str, phases: &[Phase]) -> Option<Duration> {
+        let averages = self.packages.get(package)?;
+        let recorded: Vec<u64> = phases
+            .iter()
+            .filter_map(|phase| averages.get(phase).copied())
+            .collect();
+        if recorded.is_empty() {
+            return None;
+        }
+        Some(Duration::from_millis(recorded.iter().sum()))
+    }
+
+    /// Average total install time of `package` across all phases
+    pub fn estimate_total(&self, package: &str) -> Option<Duration> {
+        self.estimate(package, &Phase::ALL)
+    }
+
+    /// Order `items` by how long their package took last time, slowest first
+    ///
+    /// Packages without history go last, keeping their relative order.
+    pub fn sort_slowest_first<T>(&self, items: &mut [T], name: impl Fn(&T) -> &str) {
+        items.sort_by_key(|item| Reverse(self.estimate_total(name(item))));
+    }
+
+    /// Estimated wall time to install `packages`, in this order, with `jobs`
+    /// download workers
+    ///
+    /// Mirrors the install pipeline: downloads and verification run on
+    /// `jobs` workers, extraction and builds run one package at a time as
+    /// downloads finish. Packages without history count as instant, so the
+    /// estimate is a lower bound when `known < total`. Returns `None` if no
+    /// package has history.
+    pub fn eta(&self, packages: &[&str], jobs: usize) -> Option<Eta> {
+        let known = packages
+            .iter()
+            .filter(|name| self.packages.contains_key(**name))
+            .count();
+        if known == 0 {
+            return None;
+        }
+
+        let mut workers = vec![Duration::ZERO; jobs.clamp(1, packages.len())];
+        let mut prepared: Vec<(Duration, Duration)> = packages
+            .iter()
+            .map(|name| {
+                let worker = workers.iter_mut().min().expect("at least one worker");
+                *worker += self.estimate(name, &PREPARE_PHASES).unwrap_or_default();
+                (
+                    *worker,
+                    self.estimate(name, &INSTALL_PHASES).unwrap_or_default(),
+                )
+            })
+            .collect();
+        prepared.sort();
+
+        let duration = prepared
+            .into_iter()
+            .fold(Duration::ZERO, |done, (ready, install)| {
+                done.max(ready) + install
+            });
+        Some(Eta {
+            duration,
+            known,
+            total: packages.len(),
+        })
+    }
+}
+
+/// Short human-readable duration: `45s`, `3m 05s`, `1h 02m`
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64().round() as u64;
+    match secs {
+        0..=59 => format!("{}s", secs.max(1)),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    fn history(samples: &[(&str, Phase, u64)]) -> InstallHistory {
+        let mut timings = Timings::new();
+        for (name, phase, seconds) in samples {
+            timings.record_package(name, *phase, secs(*seconds));
+        }
+        let mut history = InstallHistory::new("metrics.json");
+        history.record(&timings);
+        history
+    }
+
+    #[test]
+    fn test_record_averages_and_roundtrips() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("metrics.json");
+
+        let mut history = InstallHistory::load(&path);
+        assert_eq!(history.estimate_total("a"), None);
+
+        let mut timings = Timings::new();
+        timings.record_package("a", Phase::Download, secs(10));
+        history.record(&timings);
+        let mut timings = Timings::new();
+        timings.record_package("a", Phase::Download, secs(20));
+        timings.record_package("a", Phase::Build, secs(60));
+        history.record(&timings);
+        history.save().unwrap();
+
+        let loaded = InstallHistory::load(&path);
+        assert_eq!(loaded.estimate("a", &[Phase::Download]), Some(secs(15)));
+        assert_eq!(loaded.estimate_total("a"), Some(secs(75)));
+        assert_eq!(loaded.estimate("a", &[Phase::Extract]), None);
+
+        fs::write(&path, "not json").unwrap();
+        assert_eq!(InstallHistory::load(&path).estimate_total("a"), None);
+    }
+
+    #[test]
+    fn test_eta_models_parallel_downloads_and_serial_extraction() {
+        let history = history(&[
+            ("a", Phase::Download, 10),
+            ("a", Phase::Extract, 5),
+            ("b", Phase::Download, 4),
+            ("b", Phase::Extract, 3),
+            ("c", Phase::Download, 4),
+            ("c", Phase::Extract, 3),
+        ]);
+
+        // One worker: each extraction overlaps the next download
+        assert_eq!(history.eta(&["a", "b", "c"], 1).unwrap().duration, secs(21));
+
+        // Two workers: b and c download while a does, and are extracted
+        // before a finishes downloading
+        let eta = history.eta(&["a", "b", "c"], 2).unwrap();
+        assert_eq!(eta.duration, secs(16));
+
+        let eta = history.eta(&["a", "new"], 4).unwrap();
+        assert_eq!((eta.known, eta.total), (1, 2));
+        assert_eq!(history.eta(&["new"], 4), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(200)), "1s");
+        assert_eq!(format_duration(secs(45)), "45s");
+        assert_eq!(format_duration(secs(185)), "3m 05s");
+        assert_eq!(format_duration(secs(3720)), "1h 02m");
+    }
+}
@@ -0,0 +1,285 @@
+//! Mirror health: latency probing, ranking, and failover bookkeeping
+//!
+//! When the registry has mirrors (`registry.mirrors`, or its entry in the
+//! `[mirrors]` table; see [`Config::mirrors_for`](crate::Config::mirrors_for)),
+//! the HTTP registry client keeps a [`MirrorPool`] of the registry and its
+//! mirrors. On first use every entry is probed once and ranked by latency; entries that return a 5xx or time
+//! out are marked dead and skipped for the rest of the process, so one
+//! failing mirror costs a single timeout instead of one per request.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use unrealpm::mirrors::{rank, MirrorHealth};
+//!
+//! let ranked = rank(vec![
+//!     MirrorHealth::up("https://slow.example.com", Duration::from_millis(300)),
+//!     MirrorHealth::down("https://dead.example.com", "HTTP 503"),
+//!     MirrorHealth::up("https://fast.example.com", Duration::from_millis(40)),
+//! ]);
+//! assert_eq!(ranked[0].url, "https://fast.example.com");
+//! assert_eq!(ranked[2].url, "https://dead.example.com");
+//! ```
+
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How long a probe waits before counting the mirror as down
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Result of probing one registry or mirror
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MirrorHealth {
+    pub url: String,
+    /// Round-trip time of the probe, if the mirror answered
+    pub latency: Option<Duration>,
+    /// Why the mirror is considered down
+    pub error: Option<String>,
+}
+
+impl MirrorHealth {
+    pub fn up(url: impl Into<String>, latency: Duration) -> Self {
+        Self {
+            url: url.into(),
+            latency: Some(latency),
+            error: None,
+        }
+    }
+
+    pub fn down(url: impl Into<String>, error: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            latency: None,
+            error: Some(error.into()),
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Probe a registry base URL
+///
+/// Any answer below 500 counts as healthy; the probe only measures whether
+/// the server is reachable and how quickly it responds.
+pub fn probe(client: &reqwest::blocking::Client, base_url: &str) -> MirrorHealth {
+    let start = Instant::now();
+    match client
+        .head(format!("{}/api/v1/packages", base_url))
+        .timeout(PROBE_TIMEOUT)
+        .send()
+    {
+        Ok(response) if response.status().is_server_error() => {
+            MirrorHealth::down(base_url, format!("HTTP {}", response.status().as_u16()))
+        }
+        Ok(_) => MirrorHealth::up(base_url, start.elapsed()),
+        Err(e) if e.is_timeout() => MirrorHealth::down(
+            base_url,
+            format!("timed out after {}s", PROBE_TIMEOUT.as_secs()),
+        ),
+        Err(e) if e.is_connect() => MirrorHealth::down(base_url, "connection failed"),
+        Err(e) => MirrorHealth::down(base_url, e.to_string()),
+    }
+}
+
+/// Probe several base URLs in parallel, in input order
+pub fn probe_all(client: &reqwest::blocking::Client, urls: &[String]) -> Vec<MirrorHealth> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = urls
+            .iter()
+            .map(|url| scope.spawn(move || probe(client, url)))
+            .collect();
+        handles
+            .into_iter()
+            .zip(urls)
+            .map(|(handle, url)| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| MirrorHealth::down(url, "probe panicked"))
+            })
+            .collect()
+    })
+}
+
+/// Order healthy entries by latency, then the ones that are down
+///
+/// The sort is stable, so entries that tie keep their configured order.
+pub fn rank(mut health: Vec<MirrorHealth>) -> Vec<MirrorHealth> {
+    health.sort_by_key(|h| (!h.is_up(), h.latency.unwrap_or(Duration::MAX)));
+    health
+}
+
+/// Whether a failed request should move on to the next mirror
+///
+/// Server errors and network failures usually clear up on another host;
+/// 4xx answers would be the same everywhere.
+pub fn should_fail_over(result: &reqwest::Result<reqwest::blocking::Response>) -> bool {
+    match result {
+        Ok(response) => response.status().is_server_error(),
+        Err(e) => e.is_timeout() || e.is_connect(),
+    }
+}
+
+/// The registry and its mirrors, with per-session health
+pub struct MirrorPool {
+    /// Registry first, then mirrors in configured order
+    urls: Vec<String>,
+    ranked: OnceLock<Vec<MirrorHealth>>,
+    dead: Mutex<HashSet<String>>,
+}
+
+impl MirrorPool {
+    pub fn new(registry: &str, mirrors: &[String]) -> Self {
+        let mut urls = vec![registry.trim_end_matches('/').to_string()];
+        for mirror in mirrors {
+            let mirror = mirror.trim_end_matches('/').to_string();
+            if !urls.contains(&mirror) {
+                urls.push(mirror);
+            }
+        }
+        Self {
+            urls,
+            ranked: OnceLock::new(),
+            dead: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// The registry's own base URL
+    pub fn registry(&self) -> &str {
+        &self.urls[0]
+    }
+
+    /// Configured mirrors, excluding the registry itself
+    pub fn mirrors(&self) -> &[String] {
+        &self.urls[1..]
+    }
+
+    /// Probe results, taken once on first use
+    ///
+    /// A pool without mirrors is never probed: there is nothing to rank.
+    pub fn health(&self, client: &reqwest::blocking::Client) -> &[MirrorHealth] {
+        self.ranked.get_or_init(|| {
+            if self.urls.len() == 1 {
+                return Vec::new();
+            }
+            let health = probe_all(client, &self.urls);
+            for h in health.iter().filter(|h| !h.is_up()) {
+                self.mark_dead(&h.url);
+            }
+            rank(health)
+        })
+    }
+
+    /// Base URLs to try for a download, fastest first
+    ///
+    /// Downloads are checked against the registry checksum, so any mirror
+    /// may serve them. Dead entries come last rather than being dropped, so
+    /// a request still has somewhere to go when every host failed once.
+    pub fn download_order(&self, client: &reqwest::blocking::Client) -> Vec<String> {
+        let ranked: Vec<String> = match self.health(client) {
+            [] => self.urls.clone(),
+            health => health.iter().map(|h| h.url.clone()).collect(),
+        };
+        self.live_first(ranked)
+    }
+
+    /// Base URLs to try for API requests
+    ///
+    /// The registry is authoritative for metadata, so it stays first while
+    /// it is alive even if a mirror answers faster; mirrors may lag behind
+    /// a fresh publish.
+    pub fn api_order(&self, client: &reqwest::blocking::Client) -> Vec<String> {
+        let mut order = self.download_order(client);
+        if !self.is_dead(&self.urls[0]) {
+            order.retain(|url| url != &self.urls[0]);
+            order.insert(0, self.urls[0].clone());
+        }
+        order
+    }
+
+    /// Skip `url` for the rest of the session
+    pub fn mark_dead(&self, url: &str) {
+        self.dead
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(url.to_string());
+    }
+
+    pub fn is_dead(&self, url: &str) -> bool {
+        self.dead
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(url)
+    }
+
+    fn live_first(&self, urls: Vec<String>) -> Vec<String> {
+        let (live, dead): (Vec<String>, Vec<String>) =
+            urls.into_iter().partition(|url| !self.is_dead(url));
+        live.into_iter().chain(dead).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranked_pool(health: Vec<MirrorHealth>) -> MirrorPool {
+        let urls: Vec<String> = health.iter().map(|h| h.url.clone()).collect();
+        let pool = MirrorPool::new(&urls[0], &urls[1..]);
+        for h in health.iter().filter(|h| !h.is_up()) {
+            pool.mark_dead(&h.url);
+        }
+        pool.ranked.set(rank(health)).unwrap();
+        pool
+    }
+
+    #[test]
+    fn test_rank_orders_by_latency_and_keeps_ties_stable() {
+        let ranked = rank(vec![
+            MirrorHealth::down("https://a", "HTTP 502"),
+            MirrorHealth::up("https://b", Duration::from_millis(50)),
+            MirrorHealth::up("https://c", Duration::from_millis(10)),
+            MirrorHealth::up("https://d", Duration::from_millis(50)),
+        ]);
+        let urls: Vec<&str> = ranked.iter().map(|h| h.url.as_str()).collect();
+        assert_eq!(urls, ["https://c", "https://b", "https://d", "https://a"]);
+    }
+
+    #[test]
+    fn test_pool_orders_and_remembers_dead_mirrors() {
+        let client = reqwest::blocking::Client::new();
+        let pool = ranked_pool(vec![
+            MirrorHealth::up("https://registry", Duration::from_millis(200)),
+            MirrorHealth::up("https://fast", Duration::from_millis(20)),
+            MirrorHealth::down("https://dead", "connection failed"),
+        ]);
+
+        assert_eq!(
+            pool.download_order(&client),
+            ["https://fast", "https://registry", "https://dead"]
+        );
+        assert_eq!(
+            pool.api_order(&client),
+            ["https://registry", "https://fast", "https://dead"]
+        );
+
+        // A registry that fails mid-session drops behind the mirrors
+        pool.mark_dead("https://registry");
+        assert_eq!(
+            pool.api_order(&client),
+            ["https://fast", "https://registry", "https://dead"]
+        );
+    }
+
+    #[test]
+    fn test_pool_without_mirrors_is_not_probed() {
+        let client = reqwest::blocking::Client::new();
+        let pool = MirrorPool::new("https://registry/", &["https://registry".to_string()]);
+        assert!(pool.mirrors().is_empty());
+        assert!(pool.health(&client).is_empty());
+        assert_eq!(pool.download_order(&client), ["https://registry"]);
+    }
+}
@@ -0,0 +1,232 @@
+//! Third-party license and notice aggregation
+//!
+//! Console and store submissions usually require a single file crediting
+//! every third-party component a game ships. [`collect`] gathers the
+//! license and notice files (`LICENSE`, `LICENCE`, `COPYING`, `NOTICE`,
+//! with or without an extension or suffix such as `LICENSE-MIT.txt`) from
+//! each installed package, including the ones vendored under
+//! `Source/ThirdParty/`, and [`render`] concatenates them under a
+//! `name version` header per package:
+//!
+//! ```text
+//! ================================================================================
+//! awesome-plugin 1.2.0
+//! ================================================================================
+//!
+//! --- LICENSE ---
+//! MIT License
+//! ...
+//! ```
+//!
+//! Externally managed (Fab/Marketplace) plugins are covered by the Fab EULA
+//! and left out.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::notices::is_notice_file;
+//!
+//! assert!(is_notice_file("LICENSE"));
+//! assert!(is_notice_file("License.md"));
+//! assert!(is_notice_file("LICENSE-APACHE.txt"));
+//! assert!(is_notice_file("NOTICE"));
+//! assert!(!is_notice_file("LicenseManager.cpp"));
+//! assert!(!is_notice_file("README.md"));
+//! ```
+
+use crate::{Lockfile, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default output file, in the project directory
+pub const NOTICES_FILE_NAME: &str = "ThirdPartyNotices.txt";
+
+/// File stems that hold license or notice text
+const NOTICE_STEMS: [&str; 4] = ["LICENSE", "LICENCE", "COPYING", "NOTICE"];
+
+/// Extensions a notice file may have
+const NOTICE_EXTENSIONS: [&str; 4] = ["", "txt", "md", "rst"];
+
+/// Build output and assets, which never hold notices and can be large
+const SKIPPED_DIRS: [&str; 5] = ["Binaries", "Intermediate", "Content", "Saved", "Resources"];
+
+/// Separator line above and below each package header
+const RULE: &str =
+    "================================================================================";
+
+/// License and notice files of one installed package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageNotices {
+    pub name: String,
+    pub version: String,
+
+    /// Path relative to the plugin folder (with `/` separators) and contents
+    pub files: Vec<(String, String)>,
+}
+
+/// Whether `file_name` looks like a license or notice file
+pub fn is_notice_file(file_name: &str) -> bool {
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) => (stem, extension.to_ascii_lowercase()),
+        None => (file_name, String::new()),
+    };
+    let stem = stem.to_ascii_uppercase();
+    NOTICE_EXTENSIONS.contains(&extension.as_str())
+        && NOTICE_STEMS.iter().any(|notice| {
+            stem == *notice
+                || stem
+                    .strip_prefix(notice)
+                    .is_some_and(|rest| rest.starts_with(['-', '_', '.']))
+        })
+}
+
+/// License and notice files under `plugin_dir`, sorted, top level first
+pub fn find_notice_files<P: AsRef<Path>>(plugin_dir: P) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(plugin_dir.as_ref())
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.depth() == 1
+                && entry.file_type().is_dir()
+                && SKIPPED_DIRS.iter().any(|dir| entry.file_name() == *dir))
+        })
+        .flatten()
+        .filter(|entry| {
+            entry.file_type().is_file() && is_notice_file(&entry.file_name().to_string_lossy())
+        })
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort_by_key(|path| (path.components().count(), path.clone()));
+    files
+}
+
+/// Notices of every installed package in the lockfile, sorted by name
+///
+/// Packages whose folder is not in `Plugins/` and externally managed
+/// plugins are skipped. A package without notice files is included with an
+/// empty `files` list, so callers can flag it.
+pub fn collect<P: AsRef<Path>>(project_dir: P, lockfile: &Lockfile) -> Result<Vec<PackageNotices>> {
+    let project_dir = project_dir.as_ref();
+    let mut names: Vec<&String> = lockfile.packages.keys().collect();
+    names.sort();
+
+    let mut notices = Vec::new();
+    for name in names {
+        let locked = &lockfile.packages[name];
+        if locked.is_externally_managed() {
+            continue;
+        }
+        let Some(plugin_dir) =
+            crate::installer::installed_plugin_dir(project_dir, name, locked.artifact_checksum())
+        else {
+            continue;
+        };
+
+        let mut files = Vec::new();
+        for path in find_notice_files(&plugin_dir) {
+            let bytes = fs::read(&path)?;
+            let relative = path
+                .strip_prefix(&plugin_dir)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative, String::from_utf8_lossy(&bytes).into_owned()));
+        }
+        notices.push(PackageNotices {
+            name: name.clone(),
+            version: locked.version.clone(),
+            files,
+        });
+    }
+    Ok(notices)
+}
+
+/// Contents of the aggregated notices file
+pub fn render(packages: &[PackageNotices]) -> String {
+    let mut out = String::from(
+        "THIRD-PARTY SOFTWARE NOTICES\n\n\
+        This project includes the following third-party Unreal Engine plugins.\n\
+        Generated by unrealpm; regenerate with `unrealpm notices`.\n",
+    );
+    for package in packages {
+        out.push_str(&format!(
+            "\n{}\n{} {}\n{}\n",
+            RULE, package.name, package.version, RULE
+        ));
+        if package.files.is_empty() {
+            out.push_str("\nNo license or notice file found in this package.\n");
+        }
+        for (path, content) in &package.files {
+            out.push_str(&format!("\n--- {} ---\n{}", path, content.trim_end()));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_notice_files_skips_build_output() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let plugin = temp.path();
+        let third_party = plugin.join("Source").join("ThirdParty").join("zlib");
+        fs::create_dir_all(&third_party).unwrap();
+        fs::create_dir_all(plugin.join("Binaries")).unwrap();
+        fs::write(plugin.join("LICENSE.md"), "MIT").unwrap();
+        fs::write(plugin.join("NOTICE"), "Notice").unwrap();
+        fs::write(third_party.join("LICENSE.txt"), "zlib").unwrap();
+        fs::write(third_party.join("zlib.h"), "").unwrap();
+        fs::write(plugin.join("Binaries").join("LICENSE"), "stale").unwrap();
+
+        assert_eq!(
+            find_notice_files(plugin),
+            vec![
+                plugin.join("LICENSE.md"),
+                plugin.join("NOTICE"),
+                third_party.join("LICENSE.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collect_and_render() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let plugins = temp.path().join("Plugins");
+        for (folder, name, license) in [
+            ("Alpha", "alpha", Some("Alpha license\n")),
+            ("Beta", "beta", None),
+        ] {
+            fs::create_dir_all(plugins.join(folder)).unwrap();
+            fs::write(plugins.join(folder).join(format!("{}.uplugin", name)), "{}").unwrap();
+            if let Some(license) = license {
+                fs::write(plugins.join(folder).join("LICENSE"), license).unwrap();
+            }
+        }
+
+        let mut lockfile = Lockfile::new();
+        for name in ["beta", "alpha", "not-installed"] {
+            lockfile.update_package(name.into(), "1.0.0".into(), "abc".into(), None);
+        }
+
+        let notices = collect(temp.path(), &lockfile).unwrap();
+        assert_eq!(notices.len(), 2);
+        assert_eq!(notices[0].name, "alpha");
+        assert_eq!(
+            notices[0].files,
+            vec![("LICENSE".to_string(), "Alpha license\n".to_string())]
+        );
+        assert!(notices[1].files.is_empty());
+
+        let text = render(&notices);
+        assert!(text.contains(&format!(
+            "{}\nalpha 1.0.0\n{}\n\n--- LICENSE ---\nAlpha license\n",
+            RULE, RULE
+        )));
+        assert!(text.contains("beta 1.0.0\n"));
+        assert!(text.contains("No license or notice file found"));
+    }
+}
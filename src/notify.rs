@@ -0,0 +1,183 @@
+//! Desktop and webhook notifications when long operations finish
+//!
+//! Installs and builds can run for minutes; with `[notifications]` set,
+//! unrealpm reports when a command that ran longer than
+//! `min_duration_seconds` finishes or fails:
+//!
+//! ```toml
+//! [notifications]
+//! desktop = true
+//! webhook = "https://hooks.slack.com/services/..."
+//! min_duration_seconds = 120
+//! ```
+//!
+//! Desktop notifications use the platform's own tool (`notify-send`,
+//! `osascript`, PowerShell). The webhook gets a Slack-compatible
+//! `{"text": ...}` payload, which Teams and Discord (`/slack` endpoints)
+//! accept as well. Failing to notify never fails the command.
+
+use crate::config::NotificationsConfig;
+use crate::metrics::format_duration;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Time allowed for the webhook request
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A finished command to report
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// Subcommand, e.g. `install`
+    pub command: String,
+    /// Project the command ran in, if any
+    pub project: Option<String>,
+    /// How long the command ran
+    pub elapsed: Duration,
+    /// Error message if the command failed
+    pub error: Option<String>,
+}
+
+impl Notification {
+    /// One-line summary, e.g. `unrealpm install finished in MyGame after 3m 05s`
+    pub fn message(&self) -> String {
+        let project = self
+            .project
+            .as_ref()
+            .map(|project| format!(" in {}", project))
+            .unwrap_or_default();
+        let elapsed = format_duration(self.elapsed);
+        match &self.error {
+            None => format!(
+                "unrealpm {} finished{} after {}",
+                self.command, project, elapsed
+            ),
+            Some(error) => format!(
+                "unrealpm {} failed{} after {}: {}",
+                self.command,
+                project,
+                elapsed,
+                error.lines().next().unwrap_or_default()
+            ),
+        }
+    }
+
+    /// Slack-compatible webhook body
+    pub fn payload(&self) -> serde_json::Value {
+        serde_json::json!({ "text": self.message() })
+    }
+}
+
+/// True if `config` asks for a notification after a command this long
+pub fn should_notify(config: &NotificationsConfig, elapsed: Duration) -> bool {
+    (config.desktop || config.webhook.is_some())
+        && elapsed >= Duration::from_secs(config.min_duration_seconds)
+}
+
+/// Send `notification` to every configured target, if it ran long enough
+///
+/// Errors are printed as warnings.
+pub fn send(config: &NotificationsConfig, notification: &Notification) {
+    if !should_notify(config, notification.elapsed) {
+        return;
+    }
+    if config.desktop {
+        if let Err(e) = desktop(&notification.message()) {
+            crate::eoutln!("⚠ Desktop notification failed: {}", e);
+        }
+    }
+    if let Some(url) = &config.webhook {
+        if let Err(e) = webhook(url, notification) {
+            crate::eoutln!("⚠ Webhook notification failed: {}", e);
+        }
+    }
+}
+
+fn webhook(url: &str, notification: &Notification) -> Result<(), String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(url)
+        .json(&notification.payload())
+        .send()
+        // The URL itself is the secret for Slack-style webhooks
+        .map_err(|e| e.without_url().to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Start the platform's notification tool; it is not waited for
+fn desktop(message: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification \"{}\" with title \"unrealpm\"",
+            message.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+        command
+    } else if cfg!(windows) {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-WindowStyle", "Hidden", "-Command"]);
+        command.arg(format!(
+            "Add-Type -AssemblyName System.Windows.Forms; \
+             $n = New-Object System.Windows.Forms.NotifyIcon; \
+             $n.Icon = [System.Drawing.SystemIcons]::Information; \
+             $n.Visible = $true; \
+             $n.ShowBalloonTip(10000, 'unrealpm', '{}', 'Info'); \
+             Start-Sleep -Seconds 10; $n.Dispose()",
+            message.replace('\'', "''")
+        ));
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=unrealpm", "unrealpm", message]);
+        command
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("{} ({:?})", e, command.get_program()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_message() {
+        let mut notification = Notification {
+            command: "install".to_string(),
+            project: Some("MyGame".to_string()),
+            elapsed: Duration::from_secs(185),
+            error: None,
+        };
+        assert_eq!(
+            notification.message(),
+            "unrealpm install finished in MyGame after 3m 05s"
+        );
+
+        notification.project = None;
+        notification.error = Some("Checksum mismatch\n\nDetails".to_string());
+        assert_eq!(
+            notification.payload(),
+            serde_json::json!({"text": "unrealpm install failed after 3m 05s: Checksum mismatch"})
+        );
+    }
+
+    #[test]
+    fn test_should_notify() {
+        let mut config = NotificationsConfig::default();
+        let long = Duration::from_secs(config.min_duration_seconds);
+        assert!(!should_notify(&config, long));
+
+        config.webhook = Some("https://hooks.example.com/x".to_string());
+        assert!(should_notify(&config, long));
+        assert!(!should_notify(&config, long - Duration::from_secs(1)));
+    }
+}
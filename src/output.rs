@@ -0,0 +1,220 @@
+//! Plain output mode for screen readers and log collectors
+//!
+//! By default the CLI draws spinners, box-drawing frames, emoji, and ANSI
+//! colors. Plain mode replaces all of them with ASCII: status glyphs become
+//! words (`✓` -> `OK`, `⚠` -> `WARNING`), boxes are dropped, tree branches
+//! become `+--`, and spinners print their final message as a normal line. The CLI turns
+//! it on with `--plain`, and automatically when stdout is not a terminal or
+//! `TERM=dumb`.
+//!
+//! Commands print through [`outln!`](crate::outln), which applies
+//! [`render`] to each line.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::output::plain_text;
+//!
+//! assert_eq!(plain_text("  ✓ Resolved 3 packages"), "  OK Resolved 3 packages");
+//! assert_eq!(plain_text("│  Registry: local  │"), "Registry: local");
+//! assert_eq!(plain_text("└── leaf-plugin"), "+-- leaf-plugin");
+//! assert_eq!(plain_text("💡 Modify settings:"), "Modify settings:");
+//! ```
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::borrow::Cow;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Print a line, converted to plain text in plain mode
+#[macro_export]
+macro_rules! outln {
+    () => {
+        println!()
+    };
+    ($($arg:tt)*) => {
+        if let Some(line) = $crate::output::line(&format!($($arg)*)) {
+            println!("{}", line);
+        }
+    };
+}
+
+/// [`outln!`](crate::outln) for stderr
+#[macro_export]
+macro_rules! eoutln {
+    () => {
+        eprintln!()
+    };
+    ($($arg:tt)*) => {
+        if let Some(line) = $crate::output::line(&format!($($arg)*)) {
+            eprintln!("{}", line);
+        }
+    };
+}
+
+/// Turn plain mode on or off for the rest of the process
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether output is currently plain text
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Whether to use plain mode: requested, or the terminal can't show more
+pub fn plain_requested(flag: bool) -> bool {
+    flag || !std::io::stdout().is_terminal()
+        || std::env::var("TERM").is_ok_and(|term| term == "dumb")
+}
+
+/// `text` as it should be printed in the current mode
+pub fn render(text: &str) -> Cow<'_, str> {
+    if is_plain() {
+        Cow::Owned(plain_text(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// A line to print for `text`, or `None` if it was pure decoration
+///
+/// Box borders and blank box rows vanish in plain mode rather than leaving
+/// empty lines behind.
+pub fn line(text: &str) -> Option<Cow<'_, str>> {
+    let rendered = render(text);
+    if rendered.trim().is_empty() && !text.trim().is_empty() {
+        return None;
+    }
+    Some(rendered)
+}
+
+/// ASCII-only version of `text`
+///
+/// Status glyphs become words, arrows and tree branches become ASCII, and
+/// emoji, spinner frames, and ANSI color codes are dropped. A line that is
+/// part of a box (it starts and ends with a frame character) loses its frame
+/// entirely, so `│ Name: foo │` reads as `Name: foo`.
+pub fn plain_text(text: &str) -> String {
+    let trimmed = text.trim();
+    let text = if trimmed.starts_with(is_frame) && trimmed.ends_with(is_frame) {
+        trimmed.trim_matches(|c: char| is_frame(c) || c == ' ')
+    } else {
+        text
+    };
+
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let replacement = match c {
+            '\x1b' => {
+                // Skip a CSI sequence such as "\x1b[32m"
+                if chars.next_if_eq(&'[').is_some() {
+                    while chars.next().is_some_and(|c| !c.is_ascii_alphabetic()) {}
+                }
+                continue;
+            }
+            '✓' | '✔' | '✅' => "OK",
+            '✗' | '✘' | '❌' => "ERROR",
+            '⚠' => "WARNING",
+            'ℹ' => "INFO",
+            '↑' => "UP",
+            '↓' => "DOWN",
+            '→' => "->",
+            '←' => "<-",
+            '•' | '—' | '–' => "-",
+            '…' => "...",
+            '─' | '━' | '═' => "-",
+            '│' | '┃' | '║' => "|",
+            '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╔' | '╗' | '╚' | '╝' => {
+                "+"
+            }
+            c if is_decoration(c) => {
+                // Drop the emoji, and the space that separated it from the text
+                while chars.next_if(|c| is_decoration(*c)).is_some() {}
+                if out.is_empty() || out.ends_with(char::is_whitespace) {
+                    chars.next_if_eq(&' ');
+                }
+                continue;
+            }
+            c => {
+                out.push(c);
+                continue;
+            }
+        };
+        out.push_str(replacement);
+    }
+    out
+}
+
+/// Box-drawing characters
+fn is_frame(c: char) -> bool {
+    matches!(c as u32, 0x2500..=0x257F)
+}
+
+/// Emoji, variation selectors, and braille spinner frames
+fn is_decoration(c: char) -> bool {
+    matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2800..=0x28FF | 0xFE0F)
+}
+
+/// A spinner showing `message`, hidden in plain mode
+pub fn spinner(message: impl Into<Cow<'static, str>>) -> ProgressBar {
+    let spinner = ProgressBar::new_spinner();
+    if is_plain() {
+        spinner.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.blue} {msg}")
+            .unwrap()
+            .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"),
+    );
+    spinner.set_message(message);
+    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    spinner
+}
+
+/// Stop a spinner, leaving `message` in its place
+///
+/// In plain mode the spinner was never drawn, so the message is printed
+/// as a line.
+pub fn finish_spinner(spinner: &ProgressBar, message: impl Into<Cow<'static, str>>) {
+    if spinner.is_hidden() {
+        spinner.finish_and_clear();
+        println!("{}", render(&message.into()));
+    } else {
+        spinner.finish_with_message(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_replaces_glyphs_and_frames() {
+        assert_eq!(
+            plain_text("│  ⚠ Cache is large  │"),
+            "WARNING Cache is large"
+        );
+        assert_eq!(plain_text("┌─ Build Settings ────┐"), "Build Settings");
+        assert_eq!(plain_text("└──────┘"), "");
+        assert_eq!(
+            plain_text("  ↑ beta 1.0.0 -> 1.1.0"),
+            "  UP beta 1.0.0 -> 1.1.0"
+        );
+        assert_eq!(plain_text("  • install.mode"), "  - install.mode");
+        assert_eq!(plain_text("└── leaf"), "+-- leaf");
+    }
+
+    #[test]
+    fn test_plain_text_drops_emoji_and_colors() {
+        assert_eq!(plain_text("\x1b[32m✓\x1b[0m Registry"), "OK Registry");
+        assert_eq!(plain_text("🔍 Auto-detected:"), "Auto-detected:");
+        assert_eq!(plain_text("⚠️ careful"), "WARNING careful");
+        assert_eq!(plain_text("⠋ Resolving"), "Resolving");
+        assert_eq!(plain_text("plain ascii"), "plain ascii");
+    }
+}
@@ -0,0 +1,581 @@
+//! Source patching for installed packages
+//!
+//! Projects sometimes need a small fix to a third-party plugin (e.g., for an
+//! engine fork). Patches live in `patches/<package>/*.patch` and are referenced
+//! from the `patches` section of unrealpm.json. After a package is extracted,
+//! its patches are applied in order (quilt-style: a `series` file if present,
+//! otherwise filename order) and the resulting tree hash is recorded in the
+//! lockfile so `unrealpm check` can detect drift.
+//!
+//! Patches are unified diffs applied with `-p1` semantics (the `a/` and `b/`
+//! prefixes produced by `git diff` and quilt are stripped).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use unrealpm::patch::{apply_patches, hash_directory};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let tree_hash = apply_patches("patches/awesome-plugin", "Plugins/awesome-plugin")?;
+//! assert_eq!(tree_hash, hash_directory("Plugins/awesome-plugin")?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Error, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A single line within a hunk
+#[derive(Debug, Clone, PartialEq)]
+enum HunkLine {
+    Context(String),
+    Remove(String),
+    Add(String),
+}
+
+/// A hunk of a unified diff
+#[derive(Debug, Clone)]
+struct Hunk {
+    /// 1-based start line in the original file
+    old_start: usize,
+    lines: Vec<HunkLine>,
+}
+
+/// All hunks targeting one file
+#[derive(Debug, Clone)]
+struct FilePatch {
+    /// Original path (`None` for newly created files)
+    old_path: Option<String>,
+    /// New path (`None` for deleted files)
+    new_path: Option<String>,
+    hunks: Vec<Hunk>,
+}
+
+/// List patch files in a patch directory, in application order
+///
+/// Uses the quilt `series` file if present, otherwise all `*.patch` files
+/// sorted by name.
+pub fn list_patches<P: AsRef<Path>>(patch_dir: P) -> Result<Vec<PathBuf>> {
+    let patch_dir = patch_dir.as_ref();
+
+    if !patch_dir.is_dir() {
+        return Err(Error::Other(format!(
+            "Patch directory not found: {}",
+            patch_dir.display()
+        )));
+    }
+
+    let series = patch_dir.join("series");
+    if series.exists() {
+        let content = fs::read_to_string(&series)?;
+        return Ok(content
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or("").trim())
+            .filter(|l| !l.is_empty())
+            // quilt allows per-patch options after the name (e.g., "-p1")
+            .filter_map(|l| l.split_whitespace().next())
+            .map(|name| patch_dir.join(name))
+            .collect());
+    }
+
+    let mut patches: Vec<PathBuf> = fs::read_dir(patch_dir)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "patch"))
+        .collect();
+    patches.sort();
+    Ok(patches)
+}
+
+/// Apply all patches in `patch_dir` to `target_dir`
+///
+/// Returns the tree hash of `target_dir` after patching.
+pub fn apply_patches<P: AsRef<Path>, T: AsRef<Path>>(
+    patch_dir: P,
+    target_dir: T,
+) -> Result<String> {
+    let target_dir = target_dir.as_ref();
+
+    for patch in list_patches(patch_dir)? {
+        apply_patch_file(&patch, target_dir)?;
+    }
+
+    hash_directory(target_dir)
+}
+
+/// Apply a single unified diff file to `target_dir`
+pub fn apply_patch_file<P: AsRef<Path>, T: AsRef<Path>>(
+    patch_path: P,
+    target_dir: T,
+) -> Result<()> {
+    let patch_path = patch_path.as_ref();
+    let content = fs::read_to_string(patch_path)?;
+    let patch_name = patch_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let file_patches = parse_patch(&content)
+        .map_err(|e| Error::Other(format!("Invalid patch {}: {}", patch_name, e)))?;
+
+    for file_patch in &file_patches {
+        apply_file_patch(file_patch, target_dir.as_ref())
+            .map_err(|e| Error::Other(format!("Patch {} failed: {}", patch_name, e)))?;
+    }
+
+    Ok(())
+}
+
+/// Compute a stable SHA256 hash over all files in a directory tree
+///
+/// The hash covers relative paths and file contents, so it changes if any
+/// file is added, removed, renamed, or modified.
+pub fn hash_directory<P: AsRef<Path>>(dir: P) -> Result<String> {
+    let dir = dir.as_ref();
+    let mut hasher = Sha256::new();
+
+    for entry in walkdir::WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.map_err(|e| Error::Other(format!("Failed to walk directory: {}", e)))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let relative = relative.to_string_lossy().replace('\\', "/");
+
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(fs::read(entry.path())?);
+        hasher.update([0]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn parse_patch(content: &str) -> std::result::Result<Vec<FilePatch>, String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut patches = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let Some(old_header) = lines[i].strip_prefix("--- ") else {
+            i += 1;
+            continue;
+        };
+        let new_header = lines
+            .get(i + 1)
+            .and_then(|l| l.strip_prefix("+++ "))
+            .ok_or_else(|| format!("expected '+++' after line {}", i + 1))?;
+        i += 2;
+
+        let mut file_patch = FilePatch {
+            old_path: parse_header_path(old_header),
+            new_path: parse_header_path(new_header),
+            hunks: Vec::new(),
+        };
+
+        while i < lines.len() && lines[i].starts_with("@@") {
+            let (old_start, old_count, new_count) = parse_hunk_header(lines[i])
+                .ok_or_else(|| format!("invalid hunk header: {}", lines[i]))?;
+            i += 1;
+
+            let mut hunk = Hunk {
+                old_start,
+                lines: Vec::new(),
+            };
+            let (mut old_seen, mut new_seen) = (0, 0);
+
+            while (old_seen < old_count || new_seen < new_count) && i < lines.len() {
+                let line = lines[i];
+                i += 1;
+
+                if line.starts_with('\\') {
+                    // "\ No newline at end of file"
+                    continue;
+                }
+
+                match line.chars().next() {
+                    Some('+') => {
+                        hunk.lines.push(HunkLine::Add(line[1..].to_string()));
+                        new_seen += 1;
+                    }
+                    Some('-') => {
+                        hunk.lines.push(HunkLine::Remove(line[1..].to_string()));
+                        old_seen += 1;
+                    }
+                    Some(' ') => {
+                        hunk.lines.push(HunkLine::Context(line[1..].to_string()));
+                        old_seen += 1;
+                        new_seen += 1;
+                    }
+                    // Some editors strip the leading space from empty context lines
+                    None => {
+                        hunk.lines.push(HunkLine::Context(String::new()));
+                        old_seen += 1;
+                        new_seen += 1;
+                    }
+                    _ => return Err(format!("unexpected line in hunk: {}", line)),
+                }
+            }
+
+            while i < lines.len() && lines[i].starts_with('\\') {
+                i += 1;
+            }
+
+            file_patch.hunks.push(hunk);
+        }
+
+        patches.push(file_patch);
+    }
+
+    if patches.is_empty() {
+        return Err("no file changes found".to_string());
+    }
+
+    Ok(patches)
+}
+
+/// Parse a `---`/`+++` header path, stripping timestamps and the `a/`/`b/` prefix
+fn parse_header_path(header: &str) -> Option<String> {
+    let path = header.split('\t').next().unwrap_or(header).trim();
+    if path == "/dev/null" {
+        return None;
+    }
+
+    // -p1: strip the first path component
+    Some(match path.split_once('/') {
+        Some((_, rest)) => rest.to_string(),
+        None => path.to_string(),
+    })
+}
+
+/// Parse "@@ -l,s +l,s @@" into (old_start, old_count, new_count)
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize)> {
+    let mut parts = line.split_whitespace().skip(1);
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+
+    let (old_start, old_count) = parse_range(old)?;
+    let (_, new_count) = parse_range(new)?;
+    Some((old_start, old_count, new_count))
+}
+
+fn apply_file_patch(file_patch: &FilePatch, target_dir: &Path) -> std::result::Result<(), String> {
+    match (&file_patch.old_path, &file_patch.new_path) {
+        // New file
+        (None, Some(new_path)) => {
+            let content: Vec<String> = file_patch
+                .hunks
+                .iter()
+                .flat_map(|h| h.lines.iter())
+                .filter_map(|l| match l {
+                    HunkLine::Add(s) => Some(s.clone()),
+                    _ => None,
+                })
+                .collect();
+            let path = target_dir.join(new_path);
+            if path.exists() {
+                return Err(format!("{} already exists", new_path));
+            }
+            write_lines(&path, &content, false, true)
+        }
+        // Deleted file
+        (Some(old_path), None) => {
+            let path = target_dir.join(old_path);
+            fs::remove_file(&path).map_err(|e| format!("{}: {}", old_path, e))
+        }
+        (Some(old_path), Some(new_path)) => {
+            let path = target_dir.join(old_path);
+            let original = fs::read_to_string(&path).map_err(|e| format!("{}: {}", old_path, e))?;
+
+            let crlf = original.contains("\r\n");
+            let trailing_newline = original.ends_with('\n');
+            let mut lines: Vec<String> = original
+                .lines()
+                .map(|l| l.trim_end_matches('\r').to_string())
+                .collect();
+
+            let mut offset: isize = 0;
+            for (index, hunk) in file_patch.hunks.iter().enumerate() {
+                offset = apply_hunk(&mut lines, hunk, offset)
+                    .ok_or_else(|| format!("hunk #{} does not apply to {}", index + 1, old_path))?;
+            }
+
+            if old_path != new_path {
+                fs::remove_file(&path).map_err(|e| format!("{}: {}", old_path, e))?;
+            }
+            write_lines(&target_dir.join(new_path), &lines, crlf, trailing_newline)
+        }
+        (None, None) => Err("patch header has no file paths".to_string()),
+    }
+}
+
+/// Apply one hunk, returning the updated line offset
+///
+/// The hunk is tried at its recorded position first, then at increasing
+/// distances from it (like `patch` without fuzz).
+fn apply_hunk(lines: &mut Vec<String>, hunk: &Hunk, offset: isize) -> Option<isize> {
+    let old: Vec<&str> = hunk
+        .lines
+        .iter()
+        .filter_map(|l| match l {
+            HunkLine::Context(s) | HunkLine::Remove(s) => Some(s.as_str()),
+            HunkLine::Add(_) => None,
+        })
+        .collect();
+    let new: Vec<String> = hunk
+        .lines
+        .iter()
+        .filter_map(|l| match l {
+            HunkLine::Context(s) | HunkLine::Add(s) => Some(s.clone()),
+            HunkLine::Remove(_) => None,
+        })
+        .collect();
+
+    // A hunk for an empty range ("-0,0") inserts after line old_start
+    let expected = if old.is_empty() {
+        hunk.old_start as isize + offset
+    } else {
+        hunk.old_start as isize - 1 + offset
+    };
+    let max_start = lines.len().checked_sub(old.len())? as isize;
+
+    let matches_at = |start: isize| -> bool {
+        start >= 0
+            && start <= max_start
+            && old
+                .iter()
+                .enumerate()
+                .all(|(i, l)| lines[start as usize + i] == *l)
+    };
+
+    let found = (0..=lines.len() as isize).find_map(|distance| {
+        [expected - distance, expected + distance]
+            .into_iter()
+            .find(|&start| matches_at(start))
+    })?;
+
+    let start = found as usize;
+    lines.splice(start..start + old.len(), new.iter().cloned());
+
+    Some(offset + (found - expected) + new.len() as isize - old.len() as isize)
+}
+
+/// Write lines to a file, replacing it rather than writing in place
+///
+/// Installed packages may be hard links into the global store, so the file
+/// is written to a temporary path and renamed over the original.
+fn write_lines(
+    path: &Path,
+    lines: &[String],
+    crlf: bool,
+    trailing_newline: bool,
+) -> std::result::Result<(), String> {
+    let newline = if crlf { "\r\n" } else { "\n" };
+    let mut content = lines.join(newline);
+    if trailing_newline && !lines.is_empty() {
+        content.push_str(newline);
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let temp_path = path.with_extension("unrealpm-patch-tmp");
+    fs::write(&temp_path, content).map_err(|e| e.to_string())?;
+    fs::rename(&temp_path, path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SIMPLE_PATCH: &str = "\
+diff --git a/Source/Foo.cpp b/Source/Foo.cpp
+--- a/Source/Foo.cpp
++++ b/Source/Foo.cpp
+@@ -1,3 +1,3 @@
+ #include \"Foo.h\"
+-int Value = 1;
++int Value = 2;
+ // end
+";
+
+    fn setup_plugin() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("Source")).unwrap();
+        fs::write(
+            temp.path().join("Source/Foo.cpp"),
+            "#include \"Foo.h\"\nint Value = 1;\n// end\n",
+        )
+        .unwrap();
+        temp
+    }
+
+    // ========================================================================
+    // Parsing
+    // ========================================================================
+
+    #[test]
+    fn test_parse_hunk_header() {
+        assert_eq!(parse_hunk_header("@@ -1,3 +1,4 @@"), Some((1, 3, 4)));
+        assert_eq!(parse_hunk_header("@@ -5 +5 @@ fn foo()"), Some((5, 1, 1)));
+        assert_eq!(parse_hunk_header("@@ garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_header_path() {
+        assert_eq!(
+            parse_header_path("a/Source/Foo.cpp\t2024-01-01"),
+            Some("Source/Foo.cpp".to_string())
+        );
+        assert_eq!(parse_header_path("/dev/null"), None);
+    }
+
+    #[test]
+    fn test_parse_patch_rejects_empty() {
+        assert!(parse_patch("just some text\n").is_err());
+    }
+
+    // ========================================================================
+    // Applying
+    // ========================================================================
+
+    #[test]
+    fn test_apply_simple_patch() {
+        let plugin = setup_plugin();
+        let patches = TempDir::new().unwrap();
+        fs::write(patches.path().join("0001-fix.patch"), SIMPLE_PATCH).unwrap();
+
+        apply_patches(patches.path(), plugin.path()).unwrap();
+
+        let content = fs::read_to_string(plugin.path().join("Source/Foo.cpp")).unwrap();
+        assert_eq!(content, "#include \"Foo.h\"\nint Value = 2;\n// end\n");
+    }
+
+    #[test]
+    fn test_apply_patch_with_offset_and_crlf() {
+        let plugin = TempDir::new().unwrap();
+        fs::create_dir_all(plugin.path().join("Source")).unwrap();
+        fs::write(
+            plugin.path().join("Source/Foo.cpp"),
+            "// header\r\n// more\r\n#include \"Foo.h\"\r\nint Value = 1;\r\n// end\r\n",
+        )
+        .unwrap();
+
+        let patch_file = plugin.path().join("fix.patch");
+        fs::write(&patch_file, SIMPLE_PATCH).unwrap();
+        apply_patch_file(&patch_file, plugin.path()).unwrap();
+
+        let content = fs::read_to_string(plugin.path().join("Source/Foo.cpp")).unwrap();
+        assert_eq!(
+            content,
+            "// header\r\n// more\r\n#include \"Foo.h\"\r\nint Value = 2;\r\n// end\r\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_mismatch_fails() {
+        let plugin = setup_plugin();
+        fs::write(plugin.path().join("Source/Foo.cpp"), "something else\n").unwrap();
+
+        let patch_file = plugin.path().join("fix.patch");
+        fs::write(&patch_file, SIMPLE_PATCH).unwrap();
+
+        let err = apply_patch_file(&patch_file, plugin.path()).unwrap_err();
+        assert!(err.to_string().contains("hunk #1"));
+    }
+
+    #[test]
+    fn test_apply_patch_new_and_deleted_file() {
+        let plugin = setup_plugin();
+        let patch = "\
+--- /dev/null
++++ b/Source/New.h
+@@ -0,0 +1,2 @@
++#pragma once
++int NewValue();
+--- a/Source/Foo.cpp
++++ /dev/null
+@@ -1,3 +0,0 @@
+-#include \"Foo.h\"
+-int Value = 1;
+-// end
+";
+        let patch_file = plugin.path().join("fix.patch");
+        fs::write(&patch_file, patch).unwrap();
+        apply_patch_file(&patch_file, plugin.path()).unwrap();
+
+        assert!(!plugin.path().join("Source/Foo.cpp").exists());
+        assert_eq!(
+            fs::read_to_string(plugin.path().join("Source/New.h")).unwrap(),
+            "#pragma once\nint NewValue();\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_patch_breaks_hard_link() {
+        let store = setup_plugin();
+        let plugin = TempDir::new().unwrap();
+        fs::create_dir_all(plugin.path().join("Source")).unwrap();
+        fs::hard_link(
+            store.path().join("Source/Foo.cpp"),
+            plugin.path().join("Source/Foo.cpp"),
+        )
+        .unwrap();
+
+        let patch_file = plugin.path().join("fix.patch");
+        fs::write(&patch_file, SIMPLE_PATCH).unwrap();
+        apply_patch_file(&patch_file, plugin.path()).unwrap();
+
+        // The store copy must be untouched
+        let stored = fs::read_to_string(store.path().join("Source/Foo.cpp")).unwrap();
+        assert!(stored.contains("int Value = 1;"));
+    }
+
+    #[test]
+    fn test_list_patches_uses_series_order() {
+        let patches = TempDir::new().unwrap();
+        fs::write(patches.path().join("a.patch"), "").unwrap();
+        fs::write(patches.path().join("b.patch"), "").unwrap();
+
+        let sorted = list_patches(patches.path()).unwrap();
+        assert_eq!(sorted[0].file_name().unwrap(), "a.patch");
+
+        fs::write(
+            patches.path().join("series"),
+            "# order\nb.patch -p1\na.patch\n",
+        )
+        .unwrap();
+        let series = list_patches(patches.path()).unwrap();
+        assert_eq!(series[0].file_name().unwrap(), "b.patch");
+        assert_eq!(series[1].file_name().unwrap(), "a.patch");
+    }
+
+    // ========================================================================
+    // Tree hashing
+    // ========================================================================
+
+    #[test]
+    fn test_hash_directory_detects_changes() {
+        let plugin = setup_plugin();
+        let before = hash_directory(plugin.path()).unwrap();
+        assert_eq!(before, hash_directory(plugin.path()).unwrap());
+
+        fs::write(plugin.path().join("Source/Foo.cpp"), "changed\n").unwrap();
+        assert_ne!(before, hash_directory(plugin.path()).unwrap());
+    }
+}
@@ -0,0 +1,259 @@
+//! Concurrent processing of multi-package operations
+//!
+//! Installing many packages is mostly waiting on the network and hashing
+//! tarballs, so `install` and `update` download and verify packages on a pool
+//! of worker threads. Extraction writes into the project and the global store,
+//! so it stays serial: prepared packages are installed one at a time on the
+//! calling thread, in the order they finish downloading.
+//!
+//! Every package moves through [`TaskState`]s, reported to a callback that the
+//! CLI renders as a live status table.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::pipeline::{run_concurrent, TaskState};
+//!
+//! let packages = vec!["a-plugin", "b-plugin", "c-plugin"];
+//! let mut installed = Vec::new();
+//!
+//! let outcomes = run_concurrent(
+//!     &packages,
+//!     2,
+//!     |name, report| {
+//!         report(TaskState::Downloading);
+//!         Ok::<_, String>(format!("{}.tar.gz", name))
+//!     },
+//!     |_, tarball| {
+//!         installed.push(tarball);
+//!         Ok(())
+//!     },
+//!     |index, state| println!("{}: {}", packages[index], state),
+//! );
+//!
+//! assert!(outcomes.iter().all(|o| o.is_ok()));
+//! assert_eq!(installed.len(), 3);
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Default number of packages downloaded and verified at once
+pub const DEFAULT_JOBS: usize = 4;
+
+/// Where a package is in a multi-package operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    /// Waiting for a free worker
+    Queued,
+    /// Fetching the tarball (or locating it in the cache)
+    Downloading,
+    /// Checking the tarball's checksum
+    Verifying,
+    /// Extracting into the store and linking into the project
+    Installing,
+    /// Finished successfully
+    Installed,
+    /// Finished with an error
+    Failed,
+}
+
+impl TaskState {
+    /// Whether the package has finished (successfully or not)
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TaskState::Installed | TaskState::Failed)
+    }
+}
+
+impl std::fmt::Display for TaskState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TaskState::Queued => "queued",
+            TaskState::Downloading => "downloading",
+            TaskState::Verifying => "verifying",
+            TaskState::Installing => "installing",
+            TaskState::Installed => "installed",
+            TaskState::Failed => "failed",
+        })
+    }
+}
+
+/// Prepare `tasks` on up to `jobs` threads, then install them serially
+///
+/// `prepare` runs on worker threads and receives a callback for reporting
+/// intermediate states (downloading, verifying). `install` runs on the calling
+/// thread for each successfully prepared task, in completion order. `on_state`
+/// is called with each task's index whenever its state changes.
+///
+/// Returns one outcome per task, in the same order as `tasks`. A failed task
+/// never stops the others.
+pub fn run_concurrent<T, R, E, P, I, S>(
+    tasks: &[T],
+    jobs: usize,
+    prepare: P,
+    mut install: I,
+    on_state: S,
+) -> Vec<Result<(), E>>
+where
+    T: Sync,
+    R: Send,
+    E: Send,
+    P: Fn(&T, &dyn Fn(TaskState)) -> Result<R, E> + Sync,
+    I: FnMut(&T, R) -> Result<(), E>,
+    S: Fn(usize, TaskState) + Sync,
+{
+    let mut outcomes: Vec<Option<Result<(), E>>> = tasks.iter().map(|_| None).collect();
+    for index in 0..tasks.len() {
+        on_state(index, TaskState::Queued);
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        let workers = jobs.clamp(1, tasks.len().max(1));
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, prepare, on_state) = (&next, &prepare, &on_state);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= tasks.len() {
+                    break;
+                }
+                let report = |state| on_state(index, state);
+                let prepared = prepare(&tasks[index], &report);
+                if tx.send((index, prepared)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        // Extraction happens here, one package at a time
+        for (index, prepared) in rx {
+            let outcome = prepared.and_then(|prepared| {
+                on_state(index, TaskState::Installing);
+                install(&tasks[index], prepared)
+            });
+            on_state(
+                index,
+                if outcome.is_ok() {
+                    TaskState::Installed
+                } else {
+                    TaskState::Failed
+                },
+            );
+            outcomes[index] = Some(outcome);
+        }
+    });
+
+    outcomes
+        .into_iter()
+        .map(|outcome| outcome.expect("every task reports an outcome"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[test]
+    fn test_outcomes_keep_task_order() {
+        let tasks: Vec<u64> = (0..8).collect();
+        let mut install_order = Vec::new();
+
+        let outcomes = run_concurrent(
+            &tasks,
+            4,
+            |task, _| {
+                // Later tasks finish first
+                thread::sleep(Duration::from_millis(40 - task * 5));
+                if task % 3 == 0 {
+                    Err(format!("task {} failed", task))
+                } else {
+                    Ok(task * 10)
+                }
+            },
+            |_, value| {
+                install_order.push(value);
+                Ok(())
+            },
+            |_, _| {},
+        );
+
+        assert_eq!(outcomes.len(), 8);
+        for (task, outcome) in tasks.iter().zip(&outcomes) {
+            assert_eq!(outcome.is_err(), task % 3 == 0);
+        }
+        assert_eq!(install_order.len(), 5);
+        install_order.sort();
+        assert_eq!(install_order, vec![10, 20, 40, 50, 70]);
+    }
+
+    #[test]
+    fn test_state_transitions() {
+        let tasks = vec!["ok", "bad-download", "bad-install"];
+        let states = Mutex::new(Vec::new());
+
+        let outcomes = run_concurrent(
+            &tasks,
+            2,
+            |task, report| {
+                report(TaskState::Downloading);
+                if *task == "bad-download" {
+                    return Err("download failed");
+                }
+                report(TaskState::Verifying);
+                Ok(())
+            },
+            |task, _| {
+                if *task == "bad-install" {
+                    Err("extract failed")
+                } else {
+                    Ok(())
+                }
+            },
+            |index, state| states.lock().unwrap().push((index, state)),
+        );
+
+        assert_eq!(
+            outcomes,
+            vec![Ok(()), Err("download failed"), Err("extract failed")]
+        );
+
+        let states = states.into_inner().unwrap();
+        let history = |i: usize| -> Vec<TaskState> {
+            states.iter().filter(|s| s.0 == i).map(|s| s.1).collect()
+        };
+        assert_eq!(
+            history(0),
+            vec![
+                TaskState::Queued,
+                TaskState::Downloading,
+                TaskState::Verifying,
+                TaskState::Installing,
+                TaskState::Installed
+            ]
+        );
+        assert_eq!(
+            history(1),
+            vec![TaskState::Queued, TaskState::Downloading, TaskState::Failed]
+        );
+        assert_eq!(history(2).last(), Some(&TaskState::Failed));
+        assert!(history(2).contains(&TaskState::Installing));
+    }
+
+    #[test]
+    fn test_empty_and_single_job() {
+        let none: Vec<u32> = Vec::new();
+        let outcomes = run_concurrent(&none, 4, |_, _| Ok::<_, ()>(()), |_, _| Ok(()), |_, _| {});
+        assert!(outcomes.is_empty());
+
+        let tasks = vec![1, 2, 3];
+        let outcomes = run_concurrent(&tasks, 0, |_, _| Ok::<_, ()>(()), |_, _| Ok(()), |_, _| {});
+        assert_eq!(outcomes, vec![Ok(()), Ok(()), Ok(())]);
+    }
+}
@@ -53,15 +53,117 @@ pub fn detect_platform() -> String {
     }
 }
 
+/// A parsed Unreal Engine version string
+///
+/// Accepts launcher versions ("5.3", "5.3.2"), preview builds
+/// ("5.4.0-preview", "5.4.0-preview-1"), custom/source builds with a suffix
+/// ("4.27Chaos", "5.3.2+release"), and a leading "UE"/"UE_" prefix
+/// ("UE_5.3"). Engines are compatible when major.minor match; the suffix is
+/// kept for diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: Option<u32>,
+
+    /// Anything after the numeric part (e.g., "preview", "Chaos")
+    pub suffix: Option<String>,
+
+    /// The string as written
+    pub raw: String,
+}
+
+impl EngineVersion {
+    /// Parse an engine version, or `None` if it has no major.minor
+    /// (e.g., a source-build GUID association)
+    pub fn parse(version: &str) -> Option<Self> {
+        let raw = version.trim();
+        let mut rest = raw;
+        for prefix in ["UE_", "UE-", "UE"] {
+            if rest
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            {
+                rest = rest[prefix.len()..].trim_start();
+                break;
+            }
+        }
+
+        let (major, rest) = leading_number(rest)?;
+        let (minor, rest) = leading_number(rest.strip_prefix('.')?)?;
+        let (patch, rest) = match rest.strip_prefix('.').and_then(leading_number) {
+            Some((patch, rest)) => (Some(patch), rest),
+            None => (None, rest),
+        };
+
+        let suffix = rest.trim_start_matches(['-', '+', '_', '.', ' ']);
+        Some(Self {
+            major,
+            minor,
+            patch,
+            suffix: (!suffix.is_empty()).then(|| suffix.to_string()),
+            raw: raw.to_string(),
+        })
+    }
+
+    /// "major.minor" form used for engine matching (e.g., "5.4")
+    pub fn short(&self) -> String {
+        format!("{}.{}", self.major, self.minor)
+    }
+
+    /// Whether two engine versions can share plugin builds (same major.minor)
+    pub fn is_compatible(&self, other: &EngineVersion) -> bool {
+        self.major == other.major && self.minor == other.minor
+    }
+
+    /// Whether this is a preview build
+    pub fn is_preview(&self) -> bool {
+        self.suffix
+            .as_deref()
+            .is_some_and(|s| s.to_lowercase().starts_with("preview"))
+    }
+}
+
+impl std::fmt::Display for EngineVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// Split a leading run of ASCII digits off `s`
+fn leading_number(s: &str) -> Option<(u32, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    Some((s[..end].parse().ok()?, &s[end..]))
+}
+
 /// Normalize engine version for comparison
-/// Converts "5.2.0" -> "5.2", "5.3" -> "5.3"
+/// Converts "5.2.0" -> "5.2", "5.4.0-preview" -> "5.4", "4.27Chaos" -> "4.27"
+///
+/// Strings that don't parse (see [`EngineVersion::parse`]) are returned trimmed.
 pub fn normalize_engine_version(version: &str) -> String {
-    // Take only major.minor
-    let parts: Vec<&str> = version.split('.').collect();
-    if parts.len() >= 2 {
-        format!("{}.{}", parts[0], parts[1])
-    } else {
-        version.to_string()
+    EngineVersion::parse(version)
+        .map(|v| v.short())
+        .unwrap_or_else(|| version.trim().to_string())
+}
+
+/// Whether two engine version strings are compatible (same major.minor)
+///
+/// Unparseable strings only match themselves exactly.
+pub fn engine_versions_compatible(a: &str, b: &str) -> bool {
+    match (EngineVersion::parse(a), EngineVersion::parse(b)) {
+        (Some(a), Some(b)) => a.is_compatible(&b),
+        _ => a.trim() == b.trim(),
+    }
+}
+
+/// Engine version for diagnostics: the exact string, plus what it's compared as
+///
+/// "5.3" -> "5.3", "5.4.0-preview" -> "5.4.0-preview (compared as 5.4)"
+pub fn describe_engine_version(version: &str) -> String {
+    match EngineVersion::parse(version) {
+        Some(parsed) if parsed.short() == version.trim() => parsed.short(),
+        Some(parsed) => format!("{} (compared as {})", version.trim(), parsed.short()),
+        None => format!("{} (unrecognized engine version)", version.trim()),
     }
 }
 
@@ -165,7 +267,7 @@ pub fn detect_unreal_engines() -> Vec<(String, PathBuf)> {
 }
 
 /// Check if a path is a valid Unreal Engine installation
-fn is_valid_engine_install(path: &Path) -> bool {
+pub fn is_valid_engine_install(path: &Path) -> bool {
     // Check for Engine directory and UnrealBuildTool
     path.join("Engine").exists()
         && (path.join("Engine/Binaries/DotNET/UnrealBuildTool").exists()
@@ -180,15 +282,8 @@ fn is_valid_engine_install(path: &Path) -> bool {
 /// Extract engine version from installation path
 fn extract_engine_version(path: &Path) -> Option<String> {
     // Try to read version from Engine/Build/Build.version
-    let version_file = path.join("Engine/Build/Build.version");
-    if let Ok(content) = fs::read_to_string(&version_file) {
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-            if let Some(major) = json["MajorVersion"].as_u64() {
-                if let Some(minor) = json["MinorVersion"].as_u64() {
-                    return Some(format!("{}.{}", major, minor));
-                }
-            }
-        }
+    if let Some(build_version) = read_engine_build_version(path) {
+        return Some(build_version.short());
     }
 
     // Fallback: try to extract from directory name
@@ -201,9 +296,107 @@ fn extract_engine_version(path: &Path) -> Option<String> {
     None
 }
 
+/// Contents of an engine's `Engine/Build/Build.version` file
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EngineBuildVersion {
+    pub major_version: u32,
+    pub minor_version: u32,
+    #[serde(default)]
+    pub patch_version: u32,
+    #[serde(default)]
+    pub changelist: u64,
+    #[serde(default)]
+    pub branch_name: Option<String>,
+}
+
+impl EngineBuildVersion {
+    /// Short "major.minor" form used for engine matching (e.g., "5.3")
+    pub fn short(&self) -> String {
+        format!("{}.{}", self.major_version, self.minor_version)
+    }
+
+    /// Full "major.minor.patch" form (e.g., "5.3.2")
+    pub fn full(&self) -> String {
+        format!(
+            "{}.{}.{}",
+            self.major_version, self.minor_version, self.patch_version
+        )
+    }
+}
+
+/// Read and parse `Engine/Build/Build.version` from an engine installation
+pub fn read_engine_build_version(path: &Path) -> Option<EngineBuildVersion> {
+    let content = fs::read_to_string(path.join("Engine/Build/Build.version")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Check if an engine installation is a source build (GitHub checkout)
+///
+/// Source builds ship Setup and GenerateProjectFiles scripts at the root,
+/// launcher builds do not.
+pub fn is_source_engine_build(path: &Path) -> bool {
+    let has_setup = path.join("Setup.bat").exists() || path.join("Setup.sh").exists();
+    let has_generate = path.join("GenerateProjectFiles.bat").exists()
+        || path.join("GenerateProjectFiles.sh").exists();
+    has_setup && has_generate
+}
+
+/// Detect the target platforms an engine installation supports
+///
+/// Reads the folder names under `Engine/Platforms` (UE5 platform extensions)
+/// and `Engine/Binaries`, mapping them to UBT platform names (e.g., "Windows"
+/// becomes "Win64").
+pub fn detect_engine_platforms(path: &Path) -> Vec<String> {
+    let mut platforms = Vec::new();
+
+    for dir in ["Engine/Platforms", "Engine/Binaries"] {
+        let Ok(entries) = fs::read_dir(path.join(dir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            let platform = match name.as_str() {
+                "Windows" => "Win64".to_string(),
+                // Tooling folders under Engine/Binaries, not target platforms
+                "DotNET" | "ThirdParty" => continue,
+                _ => name,
+            };
+            if !platforms.contains(&platform) {
+                platforms.push(platform);
+            }
+        }
+    }
+
+    platforms.sort();
+    platforms
+}
+
 /// Resolve engine path from EngineAssociation (e.g., "5.6", "{GUID}")
-/// Uses Epic Games Launcher associations on Windows, config files on Linux
+/// Consults `[engine_aliases]` in the global config first, then uses Epic Games
+/// Launcher associations on Windows, config files on Linux
 pub fn resolve_engine_association(engine_association: &str) -> Option<PathBuf> {
+    // Studio forks and source builds mapped in config
+    if let Some(alias) = crate::Config::load()
+        .ok()
+        .and_then(|config| config.engine_alias(engine_association).cloned())
+    {
+        if let Some(path) = alias.path {
+            return Some(path);
+        }
+        let mut detected = detect_unreal_engines();
+        detected.sort_by_key(|(v, _)| *v != alias.version);
+        if let Some((_, path)) = detected
+            .into_iter()
+            .find(|(v, _)| engine_versions_compatible(v, &alias.version))
+        {
+            return Some(path);
+        }
+    }
+
     // If it's a version string (e.g., "5.6"), try to find it
     if !engine_association.starts_with('{') {
         // Try auto-detection first
@@ -321,6 +514,65 @@ mod tests {
         assert_eq!(normalize_engine_version("5.2.0"), "5.2");
         assert_eq!(normalize_engine_version("5.3"), "5.3");
         assert_eq!(normalize_engine_version("5.4.1"), "5.4");
+        assert_eq!(normalize_engine_version("5.4.0-preview"), "5.4");
+        assert_eq!(normalize_engine_version("4.27Chaos"), "4.27");
+        assert_eq!(normalize_engine_version("UE_5.3"), "5.3");
+        assert_eq!(
+            normalize_engine_version(" {A1B2C3D4-0000} "),
+            "{A1B2C3D4-0000}"
+        );
+    }
+
+    #[test]
+    fn test_engine_version_parse() {
+        let preview = EngineVersion::parse("5.4.0-preview-1").unwrap();
+        assert_eq!(
+            (preview.major, preview.minor, preview.patch),
+            (5, 4, Some(0))
+        );
+        assert_eq!(preview.suffix.as_deref(), Some("preview-1"));
+        assert!(preview.is_preview());
+        assert_eq!(preview.to_string(), "5.4.0-preview-1");
+
+        let custom = EngineVersion::parse("4.27Chaos").unwrap();
+        assert_eq!((custom.major, custom.minor, custom.patch), (4, 27, None));
+        assert_eq!(custom.suffix.as_deref(), Some("Chaos"));
+        assert!(!custom.is_preview());
+
+        let source = EngineVersion::parse("5.3.2+release").unwrap();
+        assert_eq!(source.patch, Some(2));
+        assert_eq!(source.suffix.as_deref(), Some("release"));
+
+        assert!(EngineVersion::parse("5").is_none());
+        assert!(EngineVersion::parse("{GUID}").is_none());
+        assert!(EngineVersion::parse("").is_none());
+        // Non-ASCII input is rejected rather than sliced mid-character
+        assert!(EngineVersion::parse("Ü5.4").is_none());
+        assert!(EngineVersion::parse("UÉ5.4").is_none());
+    }
+
+    #[test]
+    fn test_engine_versions_compatible() {
+        assert!(engine_versions_compatible("5.3", "5.3.2"));
+        assert!(engine_versions_compatible("5.4", "5.4.0-preview"));
+        assert!(engine_versions_compatible("4.27", "4.27Chaos"));
+        assert!(!engine_versions_compatible("5.3", "5.4"));
+        assert!(!engine_versions_compatible("5.3", "4.3"));
+        assert!(engine_versions_compatible("{GUID}", "{GUID}"));
+        assert!(!engine_versions_compatible("{GUID}", "5.3"));
+    }
+
+    #[test]
+    fn test_describe_engine_version() {
+        assert_eq!(describe_engine_version("5.3"), "5.3");
+        assert_eq!(
+            describe_engine_version("5.4.0-preview"),
+            "5.4.0-preview (compared as 5.4)"
+        );
+        assert_eq!(
+            describe_engine_version("custom"),
+            "custom (unrecognized engine version)"
+        );
     }
 
     #[test]
@@ -329,4 +581,57 @@ mod tests {
         // Just make sure it returns something
         assert!(!platform.is_empty());
     }
+
+    #[test]
+    fn test_read_engine_build_version() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("Engine/Build")).unwrap();
+        fs::write(
+            temp.path().join("Engine/Build/Build.version"),
+            r#"{"MajorVersion": 5, "MinorVersion": 3, "PatchVersion": 2, "Changelist": 0, "BranchName": "++UE5+Release-5.3"}"#,
+        )
+        .unwrap();
+
+        let version = read_engine_build_version(temp.path()).unwrap();
+        assert_eq!(version.short(), "5.3");
+        assert_eq!(version.full(), "5.3.2");
+        assert_eq!(version.branch_name.as_deref(), Some("++UE5+Release-5.3"));
+        assert_eq!(extract_engine_version(temp.path()), Some("5.3".to_string()));
+    }
+
+    #[test]
+    fn test_read_engine_build_version_missing() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert!(read_engine_build_version(temp.path()).is_none());
+    }
+
+    #[test]
+    fn test_detect_engine_platforms() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert!(detect_engine_platforms(temp.path()).is_empty());
+
+        fs::create_dir_all(temp.path().join("Engine/Platforms/Windows")).unwrap();
+        fs::create_dir_all(temp.path().join("Engine/Platforms/Android")).unwrap();
+        fs::create_dir_all(temp.path().join("Engine/Binaries/Win64")).unwrap();
+        fs::create_dir_all(temp.path().join("Engine/Binaries/Linux")).unwrap();
+        fs::create_dir_all(temp.path().join("Engine/Binaries/DotNET")).unwrap();
+        fs::create_dir_all(temp.path().join("Engine/Binaries/ThirdParty")).unwrap();
+
+        assert_eq!(
+            detect_engine_platforms(temp.path()),
+            vec!["Android", "Linux", "Win64"]
+        );
+    }
+
+    #[test]
+    fn test_is_source_engine_build() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert!(!is_source_engine_build(temp.path()));
+
+        fs::write(temp.path().join("Setup.sh"), "").unwrap();
+        assert!(!is_source_engine_build(temp.path()));
+
+        fs::write(temp.path().join("GenerateProjectFiles.sh"), "").unwrap();
+        assert!(is_source_engine_build(temp.path()));
+    }
 }
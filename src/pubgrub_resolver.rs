@@ -21,7 +21,10 @@
 //! # }
 //! ```
 
-use crate::{Error, PackageMetadata, PackageVersion, RegistryClient, ResolverConfig, Result};
+use crate::{
+    Dependency, Error, PackageMetadata, PackageVersion, PrebuiltBinary, RegistryClient,
+    ResolverConfig, Result,
+};
 use pubgrub::{
     DefaultStringReporter, Dependencies, DependencyConstraints, DependencyProvider,
     PackageResolutionStatistics, PubGrubError, Ranges, Reporter,
@@ -97,19 +100,111 @@ impl From<&SemVersion> for SemVersion {
 pub type VersionRange = Ranges<SemVersion>;
 
 /// Resolved package with exact version and metadata
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ResolvedPackage {
     pub name: String,
     pub version: String,
     pub checksum: String,
     pub dependencies: Option<HashMap<String, String>>,
+
+    /// Pre-built binary to install instead of the source tarball
+    ///
+    /// Resolution always yields `None`; [`crate::resolver::select_artifacts`]
+    /// fills it in for the host platform and install mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary: Option<PrebuiltBinary>,
+}
+
+impl ResolvedPackage {
+    /// Identifier of the tarball to install: the binary id, or the version for source
+    ///
+    /// Used wherever a registry method takes a version to locate a tarball.
+    pub fn artifact_id(&self) -> &str {
+        self.binary
+            .as_ref()
+            .map_or(self.version.as_str(), |b| b.tarball.as_str())
+    }
+
+    /// Checksum of the tarball to install
+    pub fn artifact_checksum(&self) -> &str {
+        self.binary
+            .as_ref()
+            .map_or(self.checksum.as_str(), |b| b.checksum.as_str())
+    }
+}
+
+/// Source of package metadata for dependency resolution
+///
+/// [`RegistryClient`] is the default implementation. Implement this trait to
+/// resolve against other backends (a git index, an S3 bucket, or an in-memory
+/// set of packages in tests and GUIs) without a full registry client.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use unrealpm::{resolve_with_source, PackageMetadata};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // A map of package name to metadata is itself a metadata source
+/// let source: HashMap<String, PackageMetadata> = HashMap::new();
+/// let resolved = resolve_with_source(&HashMap::new(), &source, Some("5.3"), false, None)?;
+/// assert!(resolved.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub trait MetadataSource {
+    /// Get metadata (all versions) for a package
+    fn get_package(&self, name: &str) -> Result<PackageMetadata>;
+
+    /// Get dependencies for a specific package version
+    ///
+    /// Only called when the version in [`get_package`](Self::get_package)
+    /// carries no dependency list. The default looks the version up in the
+    /// package metadata.
+    fn get_version_dependencies(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<Vec<Dependency>>> {
+        let metadata = self.get_package(name)?;
+        Ok(metadata
+            .versions
+            .into_iter()
+            .find(|v| v.version == version)
+            .and_then(|v| v.dependencies))
+    }
+}
+
+impl MetadataSource for RegistryClient {
+    fn get_package(&self, name: &str) -> Result<PackageMetadata> {
+        RegistryClient::get_package(self, name)
+    }
+
+    fn get_version_dependencies(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<Vec<Dependency>>> {
+        RegistryClient::get_version_dependencies(self, name, version)
+    }
+}
+
+impl MetadataSource for HashMap<String, PackageMetadata> {
+    fn get_package(&self, name: &str) -> Result<PackageMetadata> {
+        self.get(name)
+            .cloned()
+            .ok_or_else(|| Error::PackageNotFound(name.to_string()))
+    }
 }
 
-/// Dependency provider that fetches package information from the registry
+/// Dependency provider that fetches package information from a [`MetadataSource`]
 pub struct UnrealPmDependencyProvider<'a> {
-    registry: &'a RegistryClient,
+    source: &'a dyn MetadataSource,
     engine_version: Option<String>,
     force: bool,
+    /// Enabled features per package, whose optional dependencies are added
+    features: HashMap<String, Vec<String>>,
     /// Cache of package metadata
     package_cache: std::cell::RefCell<HashMap<String, PackageMetadata>>,
     /// Cache of available versions per package (filtered by engine)
@@ -117,16 +212,48 @@ pub struct UnrealPmDependencyProvider<'a> {
 }
 
 impl<'a> UnrealPmDependencyProvider<'a> {
-    pub fn new(registry: &'a RegistryClient, engine_version: Option<&str>, force: bool) -> Self {
+    pub fn new(source: &'a dyn MetadataSource, engine_version: Option<&str>, force: bool) -> Self {
         Self {
-            registry,
+            source,
             engine_version: engine_version.map(|s| s.to_string()),
             force,
+            features: HashMap::new(),
             package_cache: std::cell::RefCell::new(HashMap::new()),
             versions_cache: std::cell::RefCell::new(HashMap::new()),
         }
     }
 
+    /// Enable features (package -> feature names) so their optional
+    /// dependencies are resolved
+    pub fn with_features(mut self, features: HashMap<String, Vec<String>>) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Optional dependencies pulled in by the features enabled for `package`
+    ///
+    /// Fails if `pkg_ver` does not offer one of them, which rules that
+    /// version out.
+    fn feature_dependencies(
+        &self,
+        package: &str,
+        pkg_ver: &PackageVersion,
+    ) -> std::result::Result<Vec<Dependency>, String> {
+        let mut deps = Vec::new();
+        for feature in self.features.get(package).into_iter().flatten() {
+            match pkg_ver.features.as_ref().and_then(|f| f.get(feature)) {
+                Some(optional) => deps.extend(optional.iter().cloned()),
+                None => {
+                    return Err(format!(
+                        "{}@{} has no feature '{}'",
+                        package, pkg_ver.version, feature
+                    ))
+                }
+            }
+        }
+        Ok(deps)
+    }
+
     /// Get package metadata, using cache
     fn get_package_metadata(&self, name: &str) -> Result<PackageMetadata> {
         // Check cache first
@@ -134,8 +261,8 @@ impl<'a> UnrealPmDependencyProvider<'a> {
             return Ok(meta.clone());
         }
 
-        // Fetch from registry
-        let meta = self.registry.get_package(name)?;
+        // Fetch from the metadata source
+        let meta = self.source.get_package(name)?;
         self.package_cache
             .borrow_mut()
             .insert(name.to_string(), meta.clone());
@@ -162,33 +289,7 @@ impl<'a> UnrealPmDependencyProvider<'a> {
             // Check engine compatibility if not forcing
             if !self.force {
                 if let Some(ref required_engine) = self.engine_version {
-                    let req_parts: Vec<&str> = required_engine.split('.').collect();
-                    let req_major = req_parts.first().and_then(|s| s.parse::<i32>().ok());
-                    let req_minor = req_parts.get(1).and_then(|s| s.parse::<i32>().ok());
-
-                    let mut matches = false;
-
-                    if !pkg_ver.is_multi_engine {
-                        // Engine-specific: Must match major.minor
-                        if let (Some(pkg_major), Some(pkg_minor), Some(rm), Some(rmi)) = (
-                            pkg_ver.engine_major,
-                            pkg_ver.engine_minor,
-                            req_major,
-                            req_minor,
-                        ) {
-                            matches = pkg_major == rm && pkg_minor == rmi;
-                        }
-                    } else {
-                        // Multi-engine: Check if in array
-                        if let Some(ref compatible_engines) = pkg_ver.engine_versions {
-                            matches = compatible_engines.iter().any(|e| e == required_engine);
-                        } else {
-                            // If no engine_versions specified, assume compatible with all
-                            matches = true;
-                        }
-                    }
-
-                    if !matches {
+                    if !pkg_ver.supports_engine(required_engine) {
                         continue;
                     }
                 }
@@ -239,6 +340,15 @@ fn version_constraint_to_ranges(original: &str) -> Result<VersionRange> {
     // isn't directly accessible in a useful way
     let trimmed = original.trim();
 
+    // Handle comma-separated comparators like "^1.2.0, <1.5.0" (AND of all)
+    if trimmed.contains(',') {
+        let mut range = Ranges::full();
+        for part in trimmed.split(',') {
+            range = range.intersection(&version_constraint_to_ranges(part.trim())?);
+        }
+        return Ok(range);
+    }
+
     // Handle caret (^) - compatible with version
     if let Some(ver_str) = trimmed.strip_prefix('^') {
         if let Some(base) = SemVersion::parse(ver_str) {
@@ -429,29 +539,37 @@ impl<'a> DependencyProvider for UnrealPmDependencyProvider<'a> {
         let deps = if pkg_ver.dependencies.is_some() {
             pkg_ver.dependencies.clone()
         } else {
-            // Try to fetch from registry (for HTTP registry)
-            self.registry
+            // Try to fetch from the source (e.g. HTTP registry version detail)
+            self.source
                 .get_version_dependencies(package, &version.to_string())
                 .ok()
                 .flatten()
         };
 
+        let optional = match self.feature_dependencies(package, pkg_ver) {
+            Ok(optional) => optional,
+            Err(e) => return Ok(Dependencies::Unavailable(e)),
+        };
+
         // Convert to DependencyConstraints
         let mut constraints: DependencyConstraints<String, VersionRange> =
             DependencyConstraints::default();
 
-        if let Some(deps) = deps {
-            for dep in deps {
-                match self.parse_version_constraint(&dep.version) {
-                    Ok(range) => {
-                        constraints.insert(dep.name, range);
-                    }
-                    Err(e) => {
-                        return Ok(Dependencies::Unavailable(format!(
-                            "Invalid dependency constraint for {}: {}",
-                            dep.name, e
-                        )));
-                    }
+        for dep in deps.into_iter().flatten().chain(optional) {
+            match self.parse_version_constraint(&dep.version) {
+                Ok(range) => {
+                    // A feature may narrow a dependency the package already has
+                    let range = match constraints.get(&dep.name) {
+                        Some(existing) => existing.intersection(&range),
+                        None => range,
+                    };
+                    constraints.insert(dep.name, range);
+                }
+                Err(e) => {
+                    return Ok(Dependencies::Unavailable(format!(
+                        "Invalid dependency constraint for {}: {}",
+                        dep.name, e
+                    )));
                 }
             }
         }
@@ -477,6 +595,19 @@ pub fn resolve_dependencies(
     engine_version: Option<&str>,
     force: bool,
     config: Option<&ResolverConfig>,
+) -> Result<HashMap<String, ResolvedPackage>> {
+    resolve_with_source(direct_deps, registry, engine_version, force, config)
+}
+
+/// Resolve all transitive dependencies against any [`MetadataSource`]
+///
+/// Same as [`resolve_dependencies`], but without requiring a [`RegistryClient`].
+pub fn resolve_with_source(
+    direct_deps: &HashMap<String, String>,
+    source: &dyn MetadataSource,
+    engine_version: Option<&str>,
+    force: bool,
+    config: Option<&ResolverConfig>,
 ) -> Result<HashMap<String, ResolvedPackage>> {
     if direct_deps.is_empty() {
         return Ok(HashMap::new());
@@ -492,7 +623,8 @@ pub fn resolve_dependencies(
     }
 
     // Create a virtual root package that depends on all direct dependencies
-    let provider = UnrealPmDependencyProvider::new(registry, engine_version, force);
+    let provider = UnrealPmDependencyProvider::new(source, engine_version, force)
+        .with_features(resolver_config.features.clone());
 
     // Build the root dependencies
     let mut root_deps: DependencyConstraints<String, VersionRange> =
@@ -532,11 +664,20 @@ pub fn resolve_dependencies(
 
         // Get the PackageVersion for metadata
         if let Some(pkg_ver) = root_provider.inner.get_package_version(&name, &version) {
-            let deps = pkg_ver.dependencies.as_ref().map(|deps| {
-                deps.iter()
-                    .map(|d| (d.name.clone(), d.version.clone()))
-                    .collect()
-            });
+            let optional = root_provider
+                .inner
+                .feature_dependencies(&name, &pkg_ver)
+                .unwrap_or_default();
+            let deps = match (&pkg_ver.dependencies, optional.is_empty()) {
+                (None, true) => None,
+                (deps, _) => Some(
+                    deps.iter()
+                        .flatten()
+                        .chain(&optional)
+                        .map(|d| (d.name.clone(), d.version.clone()))
+                        .collect(),
+                ),
+            };
 
             resolved.insert(
                 name.clone(),
@@ -545,6 +686,7 @@ pub fn resolve_dependencies(
                     version: version.to_string(),
                     checksum: pkg_ver.checksum.clone(),
                     dependencies: deps,
+                    binary: None,
                 },
             );
         }
@@ -648,14 +790,9 @@ where
                 .replace("__root__", "your project")
                 .replace(" 0.0.0", "");
 
-            Error::DependencyResolutionFailed(format!(
-                "Dependency resolution failed:\n\n{}\n\n\
-                 Suggestions:\n\
-                 • Check if all packages exist and have compatible versions\n\
-                 • Try loosening version constraints\n\
-                 • Check engine version compatibility\n\
-                 • Run 'unrealpm search <package>' to see available versions",
-                cleaned_report
+            Error::DependencyResolutionFailed(crate::i18n::tr(
+                "resolve.conflict",
+                &[("report", &cleaned_report)],
             ))
         }
         PubGrubError::ErrorChoosingVersion { package, source } => {
@@ -695,7 +832,8 @@ pub fn find_matching_version(
         ))
     })?;
 
-    // Find all matching versions
+    // Find all matching versions, noting whether the engine ruled any out
+    let mut engine_excluded = false;
     let mut matching_versions: Vec<(SemVersion, PackageVersion)> = Vec::new();
 
     for pkg_ver in &package_metadata.versions {
@@ -712,28 +850,8 @@ pub fn find_matching_version(
         // Check engine version compatibility if specified (unless force is enabled)
         if !force {
             if let Some(required_engine) = engine_version {
-                let req_parts: Vec<&str> = required_engine.split('.').collect();
-                let req_major = req_parts.first().and_then(|s| s.parse::<i32>().ok());
-                let req_minor = req_parts.get(1).and_then(|s| s.parse::<i32>().ok());
-
-                let mut matches = false;
-
-                if !pkg_ver.is_multi_engine {
-                    if let (Some(pkg_major), Some(pkg_minor), Some(rm), Some(rmi)) = (
-                        pkg_ver.engine_major,
-                        pkg_ver.engine_minor,
-                        req_major,
-                        req_minor,
-                    ) {
-                        matches = pkg_major == rm && pkg_minor == rmi;
-                    }
-                } else if let Some(ref compatible_engines) = pkg_ver.engine_versions {
-                    matches = compatible_engines.iter().any(|e| e == required_engine);
-                } else {
-                    matches = true;
-                }
-
-                if !matches {
+                if !pkg_ver.supports_engine(required_engine) {
+                    engine_excluded = true;
                     continue;
                 }
             }
@@ -761,32 +879,34 @@ pub fn find_matching_version(
             })
             .collect();
 
+        let versions = available_versions.join("\n  ");
         let error_msg = if let Some(engine) = engine_version {
-            format!(
-                "No version of '{}' matches constraint '{}' for Unreal Engine {}\n\n\
-                Available versions:\n  {}\n\n\
-                Suggestions:\n\
-                  • Check if the package supports Unreal Engine {}\n\
-                  • Try a different version constraint\n\
-                  • Update your engine version in the .uproject file",
-                package_metadata.name,
-                constraint,
-                engine,
-                available_versions.join("\n  "),
-                engine
+            crate::i18n::tr(
+                "resolve.no_match_for_engine",
+                &[
+                    ("name", &package_metadata.name),
+                    ("constraint", &constraint),
+                    ("engine", &crate::platform::describe_engine_version(engine)),
+                    ("versions", &versions),
+                    (
+                        "engine_short",
+                        &crate::platform::normalize_engine_version(engine),
+                    ),
+                ],
             )
         } else {
-            format!(
-                "No version of '{}' matches constraint '{}'\n\n\
-                Available versions:\n  {}\n\n\
-                Suggestions:\n\
-                  • Try a different version constraint\n\
-                  • Check the package name spelling",
-                package_metadata.name,
-                constraint,
-                available_versions.join("\n  ")
+            crate::i18n::tr(
+                "resolve.no_match",
+                &[
+                    ("name", &package_metadata.name),
+                    ("constraint", &constraint),
+                    ("versions", &versions),
+                ],
             )
         };
+        if engine_excluded {
+            return Err(Error::EngineIncompatible(error_msg));
+        }
         return Err(Error::DependencyResolutionFailed(error_msg));
     }
 
@@ -987,6 +1107,15 @@ mod tests {
         assert!(!range.contains(&SemVersion::new(2, 0, 0)));
     }
 
+    #[test]
+    fn test_constraint_comma_separated() {
+        let range = version_constraint_to_ranges("^1.2.0, <1.5.0").unwrap();
+        assert!(!range.contains(&SemVersion::new(1, 1, 9)));
+        assert!(range.contains(&SemVersion::new(1, 2, 0)));
+        assert!(range.contains(&SemVersion::new(1, 4, 9)));
+        assert!(!range.contains(&SemVersion::new(1, 5, 0)));
+    }
+
     #[test]
     fn test_constraint_invalid() {
         let result = version_constraint_to_ranges("not-a-version");
@@ -1059,4 +1188,166 @@ mod tests {
         assert!(range.contains(&SemVersion::new(0, 0, 1)));
         assert!(range.contains(&SemVersion::new(999, 999, 999)));
     }
+
+    // ========================================================================
+    // MetadataSource Tests
+    // ========================================================================
+
+    fn make_package(name: &str, versions: &[(&str, &[(&str, &str)])]) -> PackageMetadata {
+        PackageMetadata {
+            name: name.to_string(),
+            description: None,
+            categories: Vec::new(),
+            keywords: Vec::new(),
+            versions: versions
+                .iter()
+                .map(|(version, deps)| PackageVersion {
+                    version: version.to_string(),
+                    tarball: format!("{}-{}.tar.gz", name, version),
+                    checksum: format!("{}-{}", name, version),
+                    dependencies: Some(
+                        deps.iter()
+                            .map(|(n, v)| Dependency {
+                                name: n.to_string(),
+                                version: v.to_string(),
+                            })
+                            .collect(),
+                    ),
+                    engine_versions: None,
+                    engine_major: None,
+                    engine_minor: None,
+                    is_multi_engine: true,
+                    package_type: crate::registry::PackageType::Source,
+                    binaries: None,
+                    public_key: None,
+                    signed_at: None,
+                    key_endorsement: None,
+                    modules: None,
+                    features: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_with_in_memory_source() {
+        let mut source = HashMap::new();
+        source.insert(
+            "app".to_string(),
+            make_package(
+                "app",
+                &[
+                    ("1.0.0", &[("core", "^1.0.0")]),
+                    ("1.1.0", &[("core", "^2.0.0")]),
+                ],
+            ),
+        );
+        source.insert(
+            "core".to_string(),
+            make_package("core", &[("1.2.0", &[]), ("2.0.0", &[])]),
+        );
+
+        let mut deps = HashMap::new();
+        deps.insert("app".to_string(), "^1.0.0".to_string());
+
+        let resolved = resolve_with_source(&deps, &source, None, false, None).unwrap();
+        assert_eq!(resolved["app"].version, "1.1.0");
+        assert_eq!(resolved["core"].version, "2.0.0");
+        assert_eq!(resolved["core"].checksum, "core-2.0.0");
+    }
+
+    #[test]
+    fn test_resolve_with_features() {
+        let mut source = HashMap::new();
+        let mut vfx = make_package("vfx", &[("1.0.0", &[]), ("1.1.0", &[])]);
+        vfx.versions[0].features = Some(HashMap::from([(
+            "niagara".to_string(),
+            vec![Dependency {
+                name: "niagara-helpers".to_string(),
+                version: "^1.0.0".to_string(),
+            }],
+        )]));
+        source.insert("vfx".to_string(), vfx);
+        source.insert(
+            "niagara-helpers".to_string(),
+            make_package("niagara-helpers", &[("1.4.0", &[])]),
+        );
+
+        let mut deps = HashMap::new();
+        deps.insert("vfx".to_string(), "^1.0.0".to_string());
+
+        // Optional dependencies stay out unless the feature is enabled
+        let resolved = resolve_with_source(&deps, &source, None, false, None).unwrap();
+        assert_eq!(resolved["vfx"].version, "1.1.0");
+        assert!(!resolved.contains_key("niagara-helpers"));
+
+        // Only 1.0.0 offers the feature
+        let config = ResolverConfig {
+            features: HashMap::from([("vfx".to_string(), vec!["niagara".to_string()])]),
+            ..ResolverConfig::default()
+        };
+        let resolved = resolve_with_source(&deps, &source, None, false, Some(&config)).unwrap();
+        assert_eq!(resolved["vfx"].version, "1.0.0");
+        assert_eq!(resolved["niagara-helpers"].version, "1.4.0");
+        assert_eq!(
+            resolved["vfx"].dependencies.as_ref().unwrap()["niagara-helpers"],
+            "^1.0.0"
+        );
+
+        let config = ResolverConfig {
+            features: HashMap::from([("vfx".to_string(), vec!["missing".to_string()])]),
+            ..ResolverConfig::default()
+        };
+        assert!(resolve_with_source(&deps, &source, None, false, Some(&config)).is_err());
+    }
+
+    #[test]
+    fn test_resolve_with_source_missing_package() {
+        let source: HashMap<String, PackageMetadata> = HashMap::new();
+        let mut deps = HashMap::new();
+        deps.insert("ghost".to_string(), "^1.0.0".to_string());
+
+        assert!(resolve_with_source(&deps, &source, None, false, None).is_err());
+    }
+
+    /// Source that only reports dependencies through get_version_dependencies
+    struct LazyDepsSource(HashMap<String, PackageMetadata>);
+
+    impl MetadataSource for LazyDepsSource {
+        fn get_package(&self, name: &str) -> Result<PackageMetadata> {
+            let mut metadata = self.0.get_package(name)?;
+            for version in &mut metadata.versions {
+                version.dependencies = None;
+            }
+            Ok(metadata)
+        }
+
+        fn get_version_dependencies(
+            &self,
+            name: &str,
+            version: &str,
+        ) -> Result<Option<Vec<Dependency>>> {
+            self.0.get_version_dependencies(name, version)
+        }
+    }
+
+    #[test]
+    fn test_resolve_uses_version_dependencies_fallback() {
+        let mut packages = HashMap::new();
+        packages.insert(
+            "app".to_string(),
+            make_package("app", &[("1.0.0", &[("core", "=1.2.0")])]),
+        );
+        packages.insert(
+            "core".to_string(),
+            make_package("core", &[("1.2.0", &[]), ("1.3.0", &[])]),
+        );
+
+        let mut deps = HashMap::new();
+        deps.insert("app".to_string(), "*".to_string());
+
+        let resolved =
+            resolve_with_source(&deps, &LazyDepsSource(packages), None, false, None).unwrap();
+        assert_eq!(resolved["core"].version, "1.2.0");
+    }
 }
@@ -27,8 +27,9 @@
 //! # }
 //! ```
 
-use crate::{Error, Result};
+use crate::{DryRun, Error, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -41,6 +42,14 @@ pub struct PackageMetadata {
     pub name: String,
     pub description: Option<String>,
     pub versions: Vec<PackageVersion>,
+
+    /// Discovery categories (e.g., "AI", "Networking", "Editor Tools")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+
+    /// Search keywords
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
 }
 
 /// Package type indicating what's included in the package
@@ -89,6 +98,17 @@ pub struct PackageVersion {
     /// Timestamp when package was signed (ISO 8601)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signed_at: Option<String>,
+    /// Endorsement of `public_key` by the key it replaced, for versions
+    /// re-signed after a key rotation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_endorsement: Option<KeyEndorsement>,
+    /// Module names declared in the package's .uplugin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modules: Option<Vec<String>>,
+    /// Optional dependencies grouped by feature name; only resolved for
+    /// consumers that enable the feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<HashMap<String, Vec<Dependency>>>,
 }
 
 fn default_multi_engine() -> bool {
@@ -99,12 +119,174 @@ fn default_package_type() -> PackageType {
     PackageType::Source
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A retired publisher key's endorsement of the key that replaced it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEndorsement {
+    /// Hex-encoded public key that was rotated out
+    pub previous_key: String,
+    /// Hex-encoded signature by `previous_key` of the rotation
+    /// (see [`crate::signing::rotation_message`])
+    pub signature: String,
+}
+
+impl KeyEndorsement {
+    /// Whether this is `previous_key`'s endorsement of `public_key`
+    pub fn endorses(&self, public_key: &str) -> bool {
+        hex::decode(&self.signature).is_ok_and(|signature| {
+            crate::signing::verify_key_endorsement(&self.previous_key, public_key, &signature)
+                .unwrap_or(false)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PrebuiltBinary {
     pub platform: String,
     pub engine: String,
     pub tarball: String,
     pub checksum: String,
+    /// Build configuration (Development, Shipping, ...); `None` if unspecified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub configuration: Option<String>,
+}
+
+impl PrebuiltBinary {
+    /// True if this binary targets the same platform, engine (major.minor), and configuration
+    pub fn same_target(&self, platform: &str, engine: &str, configuration: Option<&str>) -> bool {
+        self.platform.eq_ignore_ascii_case(platform)
+            && crate::platform::engine_versions_compatible(&self.engine, engine)
+            && match (self.configuration.as_deref(), configuration) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    /// Artifact id used to name this binary's tarball in a file registry
+    ///
+    /// `<version>-<platform>-<engine>[-<configuration>]`, so the file becomes
+    /// `<name>-1.0.0-Win64-5.3-Shipping.tar.gz`.
+    pub fn artifact_id(&self, version: &str) -> String {
+        match &self.configuration {
+            Some(configuration) => format!(
+                "{}-{}-{}-{}",
+                version, self.platform, self.engine, configuration
+            ),
+            None => format!("{}-{}-{}", version, self.platform, self.engine),
+        }
+    }
+
+    /// Display label, e.g. "Win64/5.3" or "Win64/5.3/Shipping"
+    pub fn target(&self) -> String {
+        match &self.configuration {
+            Some(configuration) => format!("{}/{}/{}", self.platform, self.engine, configuration),
+            None => format!("{}/{}", self.platform, self.engine),
+        }
+    }
+
+    /// Pick the best binary for a platform/engine and intended build configuration
+    ///
+    /// An exact configuration match wins; a binary without a configuration is
+    /// accepted as a fallback. Binaries built for another configuration are not.
+    pub fn select<'a>(
+        binaries: &'a [PrebuiltBinary],
+        platform: &str,
+        engine: &str,
+        configuration: &str,
+    ) -> Option<&'a PrebuiltBinary> {
+        binaries
+            .iter()
+            .find(|b| b.same_target(platform, engine, Some(configuration)))
+            .or_else(|| {
+                binaries
+                    .iter()
+                    .find(|b| b.same_target(platform, engine, None))
+            })
+    }
+}
+
+/// Outcome of attaching a binary to a package version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryUpsert {
+    /// No binary existed for the platform/engine
+    Added,
+    /// An identical binary (same checksum) was already attached
+    Unchanged,
+    /// A different binary was overwritten (`replace` was set)
+    Replaced,
+}
+
+impl PackageVersion {
+    /// Whether this version can be installed into `engine`
+    ///
+    /// Engine-specific versions must match major.minor; multi-engine versions
+    /// must list a compatible engine, or list none to support all of them.
+    /// Suffixes like "-preview" or custom-build names are ignored.
+    pub fn supports_engine(&self, engine: &str) -> bool {
+        if !self.is_multi_engine {
+            return match (
+                self.engine_major,
+                self.engine_minor,
+                crate::platform::EngineVersion::parse(engine),
+            ) {
+                (Some(major), Some(minor), Some(required)) => {
+                    i64::from(major) == i64::from(required.major)
+                        && i64::from(minor) == i64::from(required.minor)
+                }
+                _ => false,
+            };
+        }
+
+        match &self.engine_versions {
+            Some(engines) => engines
+                .iter()
+                .any(|e| crate::platform::engine_versions_compatible(e, engine)),
+            None => true,
+        }
+    }
+
+    /// Attach a pre-built binary, keyed by platform + engine
+    ///
+    /// Re-attaching the same artifact is a no-op, so publishing from CI can be
+    /// retried safely. A different artifact for the same key is a conflict
+    /// unless `replace` is set.
+    pub fn upsert_binary(&mut self, binary: PrebuiltBinary, replace: bool) -> Result<BinaryUpsert> {
+        let binaries = self.binaries.get_or_insert_with(Vec::new);
+
+        let outcome = match binaries.iter_mut().find(|b| {
+            b.same_target(
+                &binary.platform,
+                &binary.engine,
+                binary.configuration.as_deref(),
+            )
+        }) {
+            Some(existing) if existing.checksum.eq_ignore_ascii_case(&binary.checksum) => {
+                BinaryUpsert::Unchanged
+            }
+            Some(existing) if replace => {
+                *existing = binary;
+                BinaryUpsert::Replaced
+            }
+            Some(existing) => {
+                return Err(Error::Other(format!(
+                    "A different {} binary is already attached to {} (checksum {}).\n\
+                     Use --replace-binary to overwrite it.",
+                    existing.target(),
+                    self.version,
+                    existing.checksum
+                )));
+            }
+            None => {
+                binaries.push(binary);
+                BinaryUpsert::Added
+            }
+        };
+
+        if self.package_type == PackageType::Source {
+            self.package_type = PackageType::Hybrid;
+        }
+        Ok(outcome)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,22 +295,238 @@ pub struct Dependency {
     pub version: String,
 }
 
+/// A ranked search result with the matched character positions for highlighting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub name: String,
+    pub description: Option<String>,
+    pub latest_version: Option<String>,
+
+    /// Relevance; higher ranks first
+    pub score: i64,
+
+    /// Char indices in `name` matched by the query
+    pub name_matches: Vec<usize>,
+
+    /// Char indices in `description` matched by the query
+    pub description_matches: Vec<usize>,
+
+    pub categories: Vec<String>,
+    pub keywords: Vec<String>,
+}
+
+impl SearchHit {
+    /// Whether the package is in `category` (case-insensitive)
+    pub fn in_category(&self, category: &str) -> bool {
+        self.categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(category.trim()))
+    }
+
+    /// Score a package against `query`, or `None` if it doesn't match
+    ///
+    /// Names are fuzzy-matched (`mpl` finds `MultiPlayerLobby`), with exact,
+    /// prefix, and substring matches ranked above scattered ones. Keywords
+    /// match when a query word equals one. Descriptions match when every query
+    /// word appears in them, and rank below name and keyword hits.
+    pub fn rank(
+        query: &str,
+        name: &str,
+        description: Option<&str>,
+        keywords: &[String],
+    ) -> Option<Self> {
+        use fuzzy_matcher::skim::SkimMatcherV2;
+        use fuzzy_matcher::FuzzyMatcher;
+
+        let mut hit = Self {
+            name: name.to_string(),
+            description: description.map(|d| d.to_string()),
+            latest_version: None,
+            score: 0,
+            name_matches: Vec::new(),
+            description_matches: Vec::new(),
+            categories: Vec::new(),
+            keywords: keywords.to_vec(),
+        };
+
+        let query = query.trim();
+        if query.is_empty() {
+            return Some(hit);
+        }
+
+        let mut matched = false;
+        let compact: String = query.split_whitespace().collect();
+        if let Some((score, indices)) = SkimMatcherV2::default()
+            .ignore_case()
+            .fuzzy_indices(name, &compact)
+        {
+            let lower_name = name.to_lowercase();
+            let lower_query = compact.to_lowercase();
+            hit.score += score
+                + if lower_name == lower_query {
+                    1000
+                } else if lower_name.starts_with(&lower_query) {
+                    500
+                } else if lower_name.contains(&lower_query) {
+                    250
+                } else {
+                    0
+                };
+            hit.name_matches = indices;
+            matched = true;
+        }
+
+        let keyword_hits = query
+            .split_whitespace()
+            .filter(|word| keywords.iter().any(|k| k.eq_ignore_ascii_case(word)))
+            .count();
+        if keyword_hits > 0 {
+            hit.score += 100 * keyword_hits as i64;
+            matched = true;
+        }
+
+        if let Some(description) = description {
+            if let Some(indices) = word_matches(description, query) {
+                hit.score += 10 * query.split_whitespace().count() as i64;
+                hit.description_matches = indices;
+                matched = true;
+            }
+        }
+
+        matched.then_some(hit)
+    }
+}
+
+/// Char indices of every query word in `text` (ASCII case-insensitive),
+/// or `None` if any word is missing
+fn word_matches(text: &str, query: &str) -> Option<Vec<usize>> {
+    let haystack: Vec<char> = text.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let mut indices = Vec::new();
+
+    for word in query.split_whitespace() {
+        let needle: Vec<char> = word.chars().map(|c| c.to_ascii_lowercase()).collect();
+        let start = haystack
+            .windows(needle.len())
+            .position(|window| window == needle.as_slice())?;
+        indices.extend(start..start + needle.len());
+    }
+
+    indices.sort_unstable();
+    indices.dedup();
+    Some(indices)
+}
+
 pub enum RegistryClient {
     File(FileRegistryClient),
     Http(crate::registry_http::HttpRegistryClient),
 }
 
+/// Size of a package tarball, as reported by [`RegistryClient::artifact_size`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactSize {
+    /// Already on this machine, in bytes
+    Local(u64),
+    /// Has to be downloaded, in bytes
+    Download(u64),
+    /// The registry didn't say
+    Unknown,
+}
+
 pub struct FileRegistryClient {
     registry_path: PathBuf,
+    layout: TarballLayout,
+    /// Receives resolution events
+    events: Option<crate::EventSink>,
+}
+
+/// Settings file at the root of a file registry
+pub const FILE_REGISTRY_SETTINGS: &str = "registry.json";
+
+/// How a file registry arranges tarballs and signatures on disk
+///
+/// File names are always `<name>-<artifact>.tar.gz` (or `.sig`), where the
+/// artifact is the version or a binary's [`PrebuiltBinary::artifact_id`].
+/// Registries without a settings file use the flat layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TarballLayout {
+    /// `tarballs/<file>`
+    #[default]
+    Flat,
+    /// `tarballs/ab/cd/<file>`, where `abcd` starts the SHA-256 of the
+    /// lowercased package name, so all versions of a package share a folder
+    Sharded,
+}
+
+impl TarballLayout {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "flat" => Some(Self::Flat),
+            "sharded" => Some(Self::Sharded),
+            _ => None,
+        }
+    }
+
+    /// Location of a package's file relative to the tarballs or signatures directory
+    pub fn relative_path(&self, name: &str, file_name: &str) -> PathBuf {
+        match self {
+            Self::Flat => PathBuf::from(file_name),
+            Self::Sharded => {
+                use sha2::{Digest, Sha256};
+                let hash = hex::encode(Sha256::digest(name.to_lowercase().as_bytes()));
+                PathBuf::from(&hash[0..2]).join(&hash[2..4]).join(file_name)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for TarballLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Flat => write!(f, "flat"),
+            Self::Sharded => write!(f, "sharded"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FileRegistrySettings {
+    #[serde(default)]
+    layout: TarballLayout,
+}
+
+/// Files moved (or to be moved) by [`FileRegistryClient::migrate_layout`]
+#[derive(Debug, Default)]
+pub struct LayoutMigration {
+    /// `(from, to)` for each tarball and signature
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    /// Tarballs listed in package metadata but found in neither layout
+    pub missing: Vec<PathBuf>,
 }
 
 impl FileRegistryClient {
     /// Create a new file registry client
+    ///
+    /// The layout is read from the registry's `registry.json`; an unreadable
+    /// settings file falls back to the flat layout.
     pub fn new<P: AsRef<Path>>(registry_path: P) -> Self {
+        let registry_path = registry_path.as_ref().to_path_buf();
+        let layout = fs::read_to_string(registry_path.join(FILE_REGISTRY_SETTINGS))
+            .ok()
+            .and_then(|s| serde_json::from_str::<FileRegistrySettings>(&s).ok())
+            .unwrap_or_default()
+            .layout;
         Self {
-            registry_path: registry_path.as_ref().to_path_buf(),
+            registry_path,
+            layout,
+            events: None,
         }
     }
+
+    /// Tarball layout this registry uses
+    pub fn layout(&self) -> TarballLayout {
+        self.layout
+    }
 }
 
 impl RegistryClient {
@@ -143,14 +541,24 @@ impl RegistryClient {
 
     /// Create a registry client using configuration
     pub fn from_config(config: &crate::Config) -> Result<Self> {
+        // Offline mode reads the vendored mirror, never the network
+        if let Some(dir) = crate::vendor::offline_dir() {
+            return Ok(RegistryClient::File(FileRegistryClient::new(dir)));
+        }
         match config.registry.registry_type.as_str() {
             "http" => {
                 let cache_dir = Self::default_registry_path()?;
-                let http_client = crate::registry_http::HttpRegistryClient::new(
+                let mut http_client = crate::registry_http::HttpRegistryClient::new(
                     config.registry.url.clone(),
-                    cache_dir,
-                    config.auth.token.clone(),
-                )?;
+                    cache_dir.clone(),
+                    config.auth_token(std::env::current_dir().unwrap_or_default()),
+                )?
+                .with_download_hosts(config.registry.download_hosts.clone())
+                .with_mirrors(config.mirrors_for(&config.registry.url));
+                for (scope, url) in config.scope_registries() {
+                    let registry = Self::other_registry(config, &url, cache_dir.clone())?;
+                    http_client = http_client.with_scope_registry(&scope, registry);
+                }
                 Ok(RegistryClient::Http(http_client))
             }
             _ => {
@@ -161,12 +569,74 @@ impl RegistryClient {
         }
     }
 
+    /// Client for a registry other than the configured one (scope registries,
+    /// lockfile sources), which authenticates with its per-host token variable
+    fn other_registry(
+        config: &crate::Config,
+        url: &str,
+        cache_dir: PathBuf,
+    ) -> Result<crate::registry_http::HttpRegistryClient> {
+        let token = crate::config::token_env_var(url)
+            .and_then(|name| std::env::var(name).ok())
+            .filter(|token| !token.is_empty());
+        Ok(
+            crate::registry_http::HttpRegistryClient::new(url.to_string(), cache_dir, token)?
+                .with_download_hosts(config.registry.download_hosts.clone())
+                .with_mirrors(config.mirrors_for(url)),
+        )
+    }
+
+    /// Fetch packages the lockfile records from another registry
+    /// (`install --registry`) from that registry again
+    ///
+    /// Packages locked to the registry in use, and file or offline registries,
+    /// are left alone.
+    pub fn with_locked_registries(
+        self,
+        lockfile: &crate::Lockfile,
+        config: &crate::Config,
+    ) -> Result<Self> {
+        let RegistryClient::Http(mut http_client) = self else {
+            return Ok(self);
+        };
+        let cache_dir = Self::default_registry_path()?;
+        let mut locked: Vec<(&String, &String)> = lockfile
+            .packages
+            .iter()
+            .filter_map(|(name, package)| Some((name, package.registry.as_ref()?)))
+            .filter(|(_, url)| url.as_str() != http_client.base_url())
+            .collect();
+        locked.sort();
+        for (name, url) in locked {
+            let registry = Self::other_registry(config, url, cache_dir.clone())?;
+            http_client = http_client.with_package_registry(name, registry);
+        }
+        Ok(RegistryClient::Http(http_client))
+    }
+
+    /// Registry to record in the lockfile for `name` (see
+    /// [`LockedPackage::registry`](crate::LockedPackage::registry))
+    pub fn lock_source(&self, name: &str) -> Option<String> {
+        match self {
+            RegistryClient::File(_) => None,
+            RegistryClient::Http(client) => client.lock_source(name),
+        }
+    }
+
     /// Create a registry client using the default (file-based for backward compat)
     pub fn new_default() -> Result<Self> {
         let path = Self::default_registry_path()?;
         Ok(RegistryClient::File(FileRegistryClient::new(path)))
     }
 
+    /// Registry URL or local registry path, identifying where metadata comes from
+    pub fn location(&self) -> String {
+        match self {
+            RegistryClient::File(client) => client.registry_path.display().to_string(),
+            RegistryClient::Http(client) => client.base_url().to_string(),
+        }
+    }
+
     /// Get package metadata from registry
     pub fn get_package(&self, name: &str) -> Result<PackageMetadata> {
         match self {
@@ -175,6 +645,59 @@ impl RegistryClient {
         }
     }
 
+    /// Verify a tarball from this registry against its expected checksum
+    ///
+    /// A mismatch is recorded as an incident naming the layer it came from:
+    /// the local file registry itself (left untouched), or the HTTP download
+    /// cache (quarantined, so the next install downloads it again).
+    pub fn verify_tarball(
+        &self,
+        name: &str,
+        version: &str,
+        tarball_path: &Path,
+        checksum: &str,
+        progress: Option<crate::EventSink>,
+    ) -> Result<()> {
+        use crate::integrity::{quarantine_artifact, report_incident, Incident, MismatchLayer};
+
+        let (expected, actual) = match crate::verify_checksum(tarball_path, checksum, progress) {
+            Err(Error::ChecksumMismatch { expected, actual }) => (expected, actual),
+            other => return other,
+        };
+
+        let mut incident = Incident::new(name, version, MismatchLayer::Registry, &expected)
+            .with_actual(&actual)
+            .with_source(&tarball_path.display().to_string());
+        let hint = match self {
+            RegistryClient::File(_) => {
+                "The registry's tarball was modified or corrupted after publishing.\n\
+                 Republish the package or restore the registry from a backup."
+                    .to_string()
+            }
+            RegistryClient::Http(_) => {
+                incident.layer = MismatchLayer::Cache;
+                match quarantine_artifact(tarball_path, name, version) {
+                    Some(dest) => {
+                        let hint = format!(
+                            "The cached tarball was quarantined to {}.\n\
+                             Run the command again to download a fresh copy.",
+                            dest.display()
+                        );
+                        incident = incident.with_quarantined(dest);
+                        hint
+                    }
+                    None => "Run the command again to download a fresh copy.".to_string(),
+                }
+            }
+        };
+        report_incident(&incident);
+
+        Err(Error::Other(format!(
+            "Checksum mismatch for {}@{} ({} layer)\nExpected: {}\nComputed: {}\n\n{}",
+            name, version, incident.layer, expected, actual, hint
+        )))
+    }
+
     /// Get path to package tarball
     pub fn get_tarball_path(&self, name: &str, version: &str) -> PathBuf {
         match self {
@@ -183,6 +706,146 @@ impl RegistryClient {
         }
     }
 
+    /// Local path to a package tarball, downloading it first for HTTP registries
+    ///
+    /// HTTP downloads are cache-first and checksum-verified; file registries
+    /// already hold the tarball locally.
+    pub fn fetch_tarball(&self, name: &str, version: &str, checksum: &str) -> Result<PathBuf> {
+        match self {
+            RegistryClient::File(client) => Ok(client.get_tarball_path(name, version)),
+            RegistryClient::Http(client) => client.download_if_needed(name, version, checksum),
+        }
+    }
+
+    /// README stored for a version, if it has one
+    ///
+    /// HTTP registries keep the README uploaded at publish time; a version
+    /// published without one falls back to the README in an already
+    /// downloaded tarball. File registries read it from the tarball.
+    pub fn get_readme(&self, name: &str, version: &str) -> Result<Option<String>> {
+        let from_tarball = |path: PathBuf| {
+            if path.exists() {
+                crate::installer::read_tarball_readme(path)
+            } else {
+                Ok(None)
+            }
+        };
+        match self {
+            RegistryClient::File(client) => from_tarball(client.get_tarball_path(name, version)),
+            RegistryClient::Http(client) => match client.get_readme(name, version)? {
+                Some(readme) => Ok(Some(readme)),
+                None => from_tarball(client.get_tarball_path(name, version)),
+            },
+        }
+    }
+
+    /// Where to find the release notes for a version
+    ///
+    /// Like [`get_readme`](Self::get_readme): the changelog uploaded at
+    /// publish time, otherwise the one in a local tarball. HTTP registries
+    /// also report the git repository the version was published from.
+    pub fn get_changelog(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> Result<crate::changelog::ChangelogSource> {
+        let from_tarball = |path: PathBuf| {
+            if path.exists() {
+                crate::installer::read_tarball_changelog(path)
+            } else {
+                Ok(None)
+            }
+        };
+        match self {
+            RegistryClient::File(client) => Ok(crate::changelog::ChangelogSource {
+                changelog: from_tarball(client.get_tarball_path(name, version))?,
+                git_repository: None,
+            }),
+            RegistryClient::Http(client) => {
+                let mut source = client.get_changelog(name, version)?;
+                if source.changelog.is_none() {
+                    source.changelog = from_tarball(client.get_tarball_path(name, version))?;
+                }
+                Ok(source)
+            }
+        }
+    }
+
+    /// Whether a tarball is already in the local download cache (HTTP only)
+    pub fn is_tarball_cached(&self, name: &str, version: &str) -> bool {
+        match self {
+            RegistryClient::File(_) => false,
+            RegistryClient::Http(client) => client.get_tarball_path(name, version).exists(),
+        }
+    }
+
+    /// How much installing a tarball would fetch
+    ///
+    /// File registries and the HTTP download cache report the local file;
+    /// otherwise the registry is asked for the download's size.
+    pub fn artifact_size(&self, name: &str, version: &str) -> ArtifactSize {
+        let local = |path: PathBuf| std::fs::metadata(path).ok().map(|m| m.len());
+        match self {
+            RegistryClient::File(client) => local(client.get_tarball_path(name, version))
+                .map_or(ArtifactSize::Unknown, ArtifactSize::Local),
+            RegistryClient::Http(client) => match local(client.get_tarball_path(name, version)) {
+                Some(size) => ArtifactSize::Local(size),
+                None => client
+                    .download_size(name, version)
+                    .map_or(ArtifactSize::Unknown, ArtifactSize::Download),
+            },
+        }
+    }
+
+    /// Send resolution and download progress to `sink`
+    ///
+    /// File registries have nothing to download, so they only report
+    /// [`crate::Event::ResolveStarted`].
+    pub fn set_event_sink(&mut self, sink: Option<crate::EventSink>) {
+        match self {
+            RegistryClient::File(client) => client.events = sink,
+            RegistryClient::Http(client) => client.set_event_sink(sink),
+        }
+    }
+
+    /// Send `event` to the client's event sink, if it has one
+    pub fn emit(&self, event: crate::Event) {
+        let sink = match self {
+            RegistryClient::File(client) => client.events.as_ref(),
+            RegistryClient::Http(client) => client.event_sink(),
+        };
+        crate::events::emit(sink, event);
+    }
+
+    /// Resolve `direct_deps` on the registry, if it offers that (HTTP only)
+    ///
+    /// `None` means resolve locally: the registry has no resolve endpoint,
+    /// the request failed, or the answer didn't satisfy the constraints.
+    pub fn resolve_on_registry(
+        &self,
+        direct_deps: &HashMap<String, String>,
+        engine_version: Option<&str>,
+        force: bool,
+        features: &HashMap<String, Vec<String>>,
+    ) -> Option<HashMap<String, crate::ResolvedPackage>> {
+        match self {
+            RegistryClient::File(_) => None,
+            RegistryClient::Http(client) => {
+                client.resolve(direct_deps, engine_version, force, features)
+            }
+        }
+    }
+
+    /// Suppress per-download progress output (HTTP only)
+    ///
+    /// Used when several packages download at once and the caller renders
+    /// its own status for each.
+    pub fn set_quiet(&mut self, quiet: bool) {
+        if let RegistryClient::Http(client) = self {
+            client.set_quiet(quiet);
+        }
+    }
+
     /// Get path to signature file
     pub fn get_signature_path(&self, name: &str, version: &str) -> PathBuf {
         match self {
@@ -205,6 +868,28 @@ impl RegistryClient {
         }
     }
 
+    /// Replace the signature of `name@version` with one made by `public_key`
+    ///
+    /// Used to re-sign published versions after a publisher rotates keys;
+    /// `endorsement` lets clients that pinned the old key accept the new one.
+    pub fn replace_signature(
+        &self,
+        name: &str,
+        version: &str,
+        signature: &[u8],
+        public_key: &str,
+        endorsement: Option<&KeyEndorsement>,
+    ) -> Result<()> {
+        match self {
+            RegistryClient::File(client) => {
+                client.replace_signature(name, version, signature, public_key, endorsement)
+            }
+            RegistryClient::Http(client) => {
+                client.replace_signature(name, version, signature, public_key, endorsement)
+            }
+        }
+    }
+
     /// Get tarballs directory
     pub fn get_tarballs_dir(&self) -> PathBuf {
         match self {
@@ -237,10 +922,53 @@ impl RegistryClient {
         }
     }
 
+    /// Search for packages, best matches first, with match positions,
+    /// optionally only those in `category`
+    ///
+    /// The file registry is ranked locally. HTTP results keep the server's
+    /// order; match positions are computed locally for highlighting. HTTP
+    /// registries filter by category themselves; the results are checked
+    /// again in case one ignores the filter.
+    pub fn search_ranked(&self, query: &str, category: Option<&str>) -> Result<Vec<SearchHit>> {
+        let mut hits = match self {
+            RegistryClient::File(client) => client.search_ranked(query)?,
+            RegistryClient::Http(client) => client
+                .search_packages(query, category)?
+                .into_iter()
+                .map(|pkg| {
+                    let mut hit = SearchHit::rank(
+                        query,
+                        &pkg.name,
+                        pkg.description.as_deref(),
+                        &pkg.keywords,
+                    )
+                    .unwrap_or_else(|| SearchHit {
+                        name: pkg.name.clone(),
+                        description: pkg.description.clone(),
+                        latest_version: None,
+                        score: 0,
+                        name_matches: Vec::new(),
+                        description_matches: Vec::new(),
+                        categories: Vec::new(),
+                        keywords: pkg.keywords.clone(),
+                    });
+                    hit.latest_version = pkg.latest_version;
+                    hit.categories = pkg.categories;
+                    hit
+                })
+                .collect(),
+        };
+        if let Some(category) = category {
+            hits.retain(|hit| hit.in_category(category));
+        }
+        Ok(hits)
+    }
+
     /// Search for packages with full metadata
     pub fn search_packages(
         &self,
         query: &str,
+        category: Option<&str>,
     ) -> Result<Vec<crate::registry_http::ApiPackageInfo>> {
         match self {
             RegistryClient::File(client) => {
@@ -249,16 +977,25 @@ impl RegistryClient {
                 let mut results = Vec::new();
                 for name in names {
                     if let Ok(pkg) = client.get_package(&name) {
+                        if category.is_some_and(|category| {
+                            !pkg.categories
+                                .iter()
+                                .any(|c| c.eq_ignore_ascii_case(category.trim()))
+                        }) {
+                            continue;
+                        }
                         results.push(crate::registry_http::ApiPackageInfo {
                             name: pkg.name,
                             description: pkg.description,
                             latest_version: pkg.versions.last().map(|v| v.version.clone()),
+                            categories: pkg.categories,
+                            keywords: pkg.keywords,
                         });
                     }
                 }
                 Ok(results)
             }
-            RegistryClient::Http(client) => client.search_packages(query),
+            RegistryClient::Http(client) => client.search_packages(query, category),
         }
     }
 
@@ -291,23 +1028,23 @@ impl FileRegistryClient {
         let package_file = self
             .registry_path
             .join("packages")
-            .join(format!("{}.json", name));
+            .join(format!("{}.json", crate::scope::encode(name)));
 
         if !package_file.exists() {
             // Try to find similar package names for suggestions
             let similar = self.find_similar_packages(name);
 
-            let mut error_msg = format!("Package '{}' not found in registry", name);
+            let mut error_msg = crate::i18n::tr("registry.not_found", &[("name", &name)]);
 
             if !similar.is_empty() {
-                error_msg.push_str("\n\nDid you mean one of these?\n  ");
+                error_msg.push_str("\n\n");
+                error_msg.push_str(&crate::i18n::tr("registry.did_you_mean", &[]));
+                error_msg.push_str("\n  ");
                 error_msg.push_str(&similar.join("\n  "));
             }
 
-            error_msg.push_str("\n\nSuggestions:\n");
-            error_msg.push_str("  • Check the package name spelling\n");
-            error_msg.push_str("  • Search for packages: unrealpm search <query>\n");
-            error_msg.push_str("  • Visit the package registry for available packages");
+            error_msg.push_str("\n\n");
+            error_msg.push_str(&crate::i18n::tr("registry.not_found_suggestions", &[]));
 
             return Err(Error::PackageNotFound(error_msg));
         }
@@ -332,7 +1069,12 @@ impl FileRegistryClient {
             for entry in entries.flatten() {
                 let path = entry.path();
                 if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                    if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    if let Some(name) = path
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .map(crate::scope::decode)
+                    {
+                        let name = name.as_str();
                         // Simple similarity check: substring match or low edit distance
                         if name.contains(query)
                             || query.contains(name)
@@ -377,10 +1119,14 @@ impl FileRegistryClient {
     }
 
     /// Get path to package tarball
+    ///
+    /// `version` is the artifact id: a version, or a binary's
+    /// [`PrebuiltBinary::artifact_id`].
     pub fn get_tarball_path(&self, name: &str, version: &str) -> PathBuf {
-        self.registry_path
-            .join("tarballs")
-            .join(format!("{}-{}.tar.gz", name, version))
+        self.get_tarballs_dir().join(self.layout.relative_path(
+            name,
+            &format!("{}-{}.tar.gz", crate::scope::encode(name), version),
+        ))
     }
 
     /// Get the tarballs directory path
@@ -400,13 +1146,144 @@ impl FileRegistryClient {
 
     /// Get path to package signature file
     pub fn get_signature_path(&self, name: &str, version: &str) -> PathBuf {
-        self.registry_path
-            .join("signatures")
-            .join(format!("{}-{}.sig", name, version))
+        self.get_signatures_dir().join(self.layout.relative_path(
+            name,
+            &format!("{}-{}.sig", crate::scope::encode(name), version),
+        ))
     }
 
-    /// Search for packages (simple substring search for MVP)
+    /// Write a new signature for `name@version` and record its key in the
+    /// package metadata
+    pub fn replace_signature(
+        &self,
+        name: &str,
+        version: &str,
+        signature: &[u8],
+        public_key: &str,
+        endorsement: Option<&KeyEndorsement>,
+    ) -> Result<()> {
+        let mut metadata = self.get_package(name)?;
+        let mut found = false;
+        for entry in metadata
+            .versions
+            .iter_mut()
+            .filter(|v| v.version == version)
+        {
+            entry.public_key = Some(public_key.to_string());
+            entry.signed_at = Some(chrono::Utc::now().to_rfc3339());
+            entry.key_endorsement = endorsement.cloned();
+            found = true;
+        }
+        if !found {
+            return Err(Error::Other(format!(
+                "{}@{} is not in the registry",
+                name, version
+            )));
+        }
+
+        let signature_path = self.get_signature_path(name, version);
+        if let Some(parent) = signature_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&signature_path, signature)?;
+        let metadata_path = self
+            .get_packages_dir()
+            .join(format!("{}.json", crate::scope::encode(name)));
+        std::fs::write(metadata_path, serde_json::to_string_pretty(&metadata)?)?;
+        Ok(())
+    }
+
+    /// Move every tarball and signature into `layout` and record it
+    ///
+    /// Files are found through package metadata, so only artifacts the
+    /// registry knows about are moved. Files already in the new location are
+    /// left alone, which makes an interrupted migration safe to re-run. The
+    /// settings file is written last; in a dry run nothing is changed.
+    pub fn migrate_layout(
+        &mut self,
+        layout: TarballLayout,
+        dry_run: DryRun,
+    ) -> Result<LayoutMigration> {
+        let mut migration = LayoutMigration::default();
+        let packages_dir = self.get_packages_dir();
+        let mut entries: Vec<PathBuf> = match fs::read_dir(&packages_dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("json"))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        entries.sort();
+
+        for path in entries {
+            let metadata: PackageMetadata = serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| Error::Other(format!("Invalid metadata {}: {}", path.display(), e)))?;
+            let name = &metadata.name;
+            for version in &metadata.versions {
+                let artifacts = std::iter::once(version.version.clone())
+                    .chain(version.binaries.iter().flatten().map(|b| b.tarball.clone()));
+                for artifact in artifacts {
+                    for (dir, file_name, required) in [
+                        (
+                            self.get_tarballs_dir(),
+                            format!("{}-{}.tar.gz", crate::scope::encode(name), artifact),
+                            true,
+                        ),
+                        (
+                            self.get_signatures_dir(),
+                            format!("{}-{}.sig", crate::scope::encode(name), artifact),
+                            false,
+                        ),
+                    ] {
+                        let from = dir.join(self.layout.relative_path(name, &file_name));
+                        let to = dir.join(layout.relative_path(name, &file_name));
+                        if from == to || to.exists() {
+                            continue;
+                        }
+                        if !from.exists() {
+                            if required {
+                                migration.missing.push(from);
+                            }
+                            continue;
+                        }
+                        dry_run.apply(|| {
+                            if let Some(parent) = to.parent() {
+                                fs::create_dir_all(parent)?;
+                            }
+                            Ok(fs::rename(&from, &to)?)
+                        })?;
+                        migration.moved.push((from, to));
+                    }
+                }
+            }
+        }
+
+        if !dry_run.is_enabled() {
+            for dir in [self.get_tarballs_dir(), self.get_signatures_dir()] {
+                remove_empty_dirs(&dir);
+            }
+            fs::write(
+                self.registry_path.join(FILE_REGISTRY_SETTINGS),
+                serde_json::to_string_pretty(&FileRegistrySettings { layout })?,
+            )?;
+            self.layout = layout;
+        }
+        Ok(migration)
+    }
+
+    /// Search for packages, returning names best match first
     pub fn search(&self, query: &str) -> Result<Vec<String>> {
+        Ok(self
+            .search_ranked(query)?
+            .into_iter()
+            .map(|hit| hit.name)
+            .collect())
+    }
+
+    /// Fuzzy-search package names and descriptions, best match first
+    ///
+    /// See [`SearchHit::rank`] for how results are scored. Ties sort by name.
+    pub fn search_ranked(&self, query: &str) -> Result<Vec<SearchHit>> {
         let packages_dir = self.registry_path.join("packages");
 
         if !packages_dir.exists() {
@@ -419,15 +1296,36 @@ impl FileRegistryClient {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
-                    if name.to_lowercase().contains(&query.to_lowercase()) {
-                        results.push(name.to_string());
-                    }
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(name) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .map(crate::scope::decode)
+            else {
+                continue;
+            };
+            let name = name.as_str();
+
+            // A broken metadata file still shows up by name
+            let metadata = self.get_package(name).ok();
+            let description = metadata.as_ref().and_then(|m| m.description.as_deref());
+            let keywords = metadata
+                .as_ref()
+                .map(|m| m.keywords.as_slice())
+                .unwrap_or(&[]);
+
+            if let Some(mut hit) = SearchHit::rank(query, name, description, keywords) {
+                if let Some(metadata) = &metadata {
+                    hit.latest_version = metadata.versions.last().map(|v| v.version.clone());
+                    hit.categories = metadata.categories.clone();
                 }
+                results.push(hit);
             }
         }
 
+        results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
         Ok(results)
     }
 
@@ -439,6 +1337,21 @@ impl FileRegistryClient {
     }
 }
 
+/// Remove empty subdirectories left behind by a layout change (keeps `dir`)
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            // Fails, harmlessly, when the directory still has files
+            let _ = fs::remove_dir(&path);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -520,6 +1433,95 @@ mod tests {
         assert_eq!(binary.engine, "5.3");
         assert_eq!(binary.tarball, "awesome-plugin-win64-5.3.tar.gz");
         assert_eq!(binary.checksum, "sha256:xyz789");
+        assert_eq!(binary.configuration, None);
+    }
+
+    fn binary(platform: &str, engine: &str, checksum: &str) -> PrebuiltBinary {
+        PrebuiltBinary {
+            platform: platform.to_string(),
+            engine: engine.to_string(),
+            tarball: format!("1.0.0-{}-{}", platform, engine),
+            checksum: checksum.to_string(),
+            configuration: None,
+        }
+    }
+
+    #[test]
+    fn test_select_binary_by_configuration() {
+        let mut shipping = binary("Win64", "5.3", "ship");
+        shipping.configuration = Some("Shipping".to_string());
+        let mut debug = binary("Win64", "5.3", "dbg");
+        debug.configuration = Some("Debug".to_string());
+        let binaries = vec![debug, shipping, binary("Win64", "5.3", "any")];
+
+        let pick = |config| PrebuiltBinary::select(&binaries, "Win64", "5.3.1", config);
+        assert_eq!(pick("shipping").unwrap().checksum, "ship");
+        assert_eq!(pick("Debug").unwrap().checksum, "dbg");
+        // No Development build: fall back to the unspecified one
+        assert_eq!(pick("Development").unwrap().checksum, "any");
+        assert!(PrebuiltBinary::select(&binaries[..2], "Win64", "5.3", "Development").is_none());
+    }
+
+    #[test]
+    fn test_upsert_binary_keys_on_configuration() {
+        let json = r#"{"version": "1.0.0", "tarball": "t", "checksum": "c", "dependencies": null}"#;
+        let mut version: PackageVersion = serde_json::from_str(json).unwrap();
+        let mut shipping = binary("Win64", "5.3", "bbb");
+        shipping.configuration = Some("Shipping".to_string());
+
+        version
+            .upsert_binary(binary("Win64", "5.3", "aaa"), false)
+            .unwrap();
+        assert_eq!(
+            version.upsert_binary(shipping, false).unwrap(),
+            BinaryUpsert::Added
+        );
+        assert_eq!(version.binaries.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_upsert_binary() {
+        let json = r#"{"version": "1.0.0", "tarball": "t", "checksum": "c", "dependencies": null}"#;
+        let mut version: PackageVersion = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            version
+                .upsert_binary(binary("Win64", "5.3", "aaa"), false)
+                .unwrap(),
+            BinaryUpsert::Added
+        );
+        assert_eq!(version.package_type, PackageType::Hybrid);
+
+        // Another machine attaches a different platform
+        assert_eq!(
+            version
+                .upsert_binary(binary("Mac", "5.3", "bbb"), false)
+                .unwrap(),
+            BinaryUpsert::Added
+        );
+
+        // Retrying the same upload is idempotent
+        assert_eq!(
+            version
+                .upsert_binary(binary("win64", "5.3.2", "AAA"), false)
+                .unwrap(),
+            BinaryUpsert::Unchanged
+        );
+
+        // A different artifact for the same key conflicts unless replaced
+        assert!(version
+            .upsert_binary(binary("Win64", "5.3", "ccc"), false)
+            .is_err());
+        assert_eq!(
+            version
+                .upsert_binary(binary("Win64", "5.3", "ccc"), true)
+                .unwrap(),
+            BinaryUpsert::Replaced
+        );
+
+        let binaries = version.binaries.unwrap();
+        assert_eq!(binaries.len(), 2);
+        assert_eq!(binaries[0].checksum, "ccc");
     }
 
     #[test]
@@ -527,4 +1529,199 @@ mod tests {
         let pkg_type = default_package_type();
         assert_eq!(pkg_type, PackageType::Source);
     }
+
+    #[test]
+    fn test_search_hit_rank_fuzzy_name() {
+        let hit = SearchHit::rank("mpl", "MultiPlayerLobby", None, &[]).unwrap();
+        assert_eq!(hit.name_matches.len(), 3);
+        assert!(SearchHit::rank("xyz", "MultiPlayerLobby", None, &[]).is_none());
+
+        // Empty queries match everything
+        assert!(SearchHit::rank("", "Anything", None, &[]).is_some());
+    }
+
+    #[test]
+    fn test_search_hit_rank_prefers_exact_and_prefix() {
+        let exact = SearchHit::rank("lobby", "Lobby", None, &[]).unwrap();
+        let prefix = SearchHit::rank("lobby", "LobbyTools", None, &[]).unwrap();
+        let inner = SearchHit::rank("lobby", "MultiplayerLobby", None, &[]).unwrap();
+        assert!(exact.score > prefix.score);
+        assert!(prefix.score > inner.score);
+    }
+
+    #[test]
+    fn test_search_hit_rank_description() {
+        let hit = SearchHit::rank(
+            "steam sessions",
+            "OnlineKit",
+            Some("Steam and EOS sessions for UE5"),
+            &[],
+        )
+        .unwrap();
+        assert!(hit.name_matches.is_empty());
+        assert_eq!(
+            hit.description_matches,
+            vec![0, 1, 2, 3, 4, 14, 15, 16, 17, 18, 19, 20, 21]
+        );
+
+        // Every word must appear
+        assert!(SearchHit::rank("steam voice", "OnlineKit", Some("Steam sessions"), &[]).is_none());
+    }
+
+    #[test]
+    fn test_file_registry_search_ranked() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let packages = temp.path().join("packages");
+        fs::create_dir_all(&packages).unwrap();
+        for (name, description) in [
+            ("awesome-lobby", "Lobby screens"),
+            ("lobby", "Session lobby"),
+            ("net-utils", "Helpers for lobby replication"),
+            ("audio-kit", "Sound helpers"),
+        ] {
+            let metadata = PackageMetadata {
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                versions: Vec::new(),
+                categories: Vec::new(),
+                keywords: Vec::new(),
+            };
+            fs::write(
+                packages.join(format!("{}.json", name)),
+                serde_json::to_string(&metadata).unwrap(),
+            )
+            .unwrap();
+        }
+        // Unparseable metadata is still found by name
+        fs::write(packages.join("lobby-broken.json"), "{").unwrap();
+
+        let client = FileRegistryClient::new(temp.path());
+        let names = client.search("lobby").unwrap();
+        assert_eq!(names[0], "lobby");
+        assert!(names.contains(&"net-utils".to_string()));
+        assert!(names.contains(&"lobby-broken".to_string()));
+        assert!(!names.contains(&"audio-kit".to_string()));
+        // Name matches rank above description-only matches
+        assert_eq!(names.last().unwrap(), "net-utils");
+    }
+
+    #[test]
+    fn test_file_registry_search_keywords_and_categories() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let packages = temp.path().join("packages");
+        fs::create_dir_all(&packages).unwrap();
+        let metadata = PackageMetadata {
+            name: "behavior-kit".to_string(),
+            description: Some("Utilities for trees".to_string()),
+            versions: Vec::new(),
+            categories: vec!["AI".to_string()],
+            keywords: vec!["npc".to_string()],
+        };
+        fs::write(
+            packages.join("behavior-kit.json"),
+            serde_json::to_string(&metadata).unwrap(),
+        )
+        .unwrap();
+
+        let client = FileRegistryClient::new(temp.path());
+        let hits = client.search_ranked("NPC").unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].in_category("ai"));
+        assert!(!hits[0].in_category("Networking"));
+
+        // Older metadata files without the fields still parse
+        let legacy: PackageMetadata =
+            serde_json::from_str(r#"{"name": "x", "description": null, "versions": []}"#).unwrap();
+        assert!(legacy.categories.is_empty());
+    }
+
+    #[test]
+    fn test_tarball_layout_paths() {
+        let flat = TarballLayout::Flat.relative_path("Awesome", "Awesome-1.0.0.tar.gz");
+        assert_eq!(flat, PathBuf::from("Awesome-1.0.0.tar.gz"));
+
+        let sharded = TarballLayout::Sharded.relative_path("Awesome", "Awesome-1.0.0.tar.gz");
+        let parts: Vec<_> = sharded.iter().map(|p| p.to_str().unwrap()).collect();
+        assert_eq!(parts.len(), 3);
+        assert!(parts[..2].iter().all(|p| p.len() == 2));
+        // Shards depend only on the name, case-insensitively
+        assert_eq!(
+            sharded.parent(),
+            TarballLayout::Sharded
+                .relative_path("awesome", "x.sig")
+                .parent()
+        );
+
+        assert_eq!(
+            TarballLayout::parse("Sharded"),
+            Some(TarballLayout::Sharded)
+        );
+        assert_eq!(TarballLayout::parse("nested"), None);
+        assert_eq!(
+            binary("Win64", "5.3", "c").artifact_id("1.0.0"),
+            "1.0.0-Win64-5.3"
+        );
+    }
+
+    #[test]
+    fn test_migrate_layout_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut client = FileRegistryClient::new(temp.path());
+        client.init_registry().unwrap();
+        let mut metadata: PackageMetadata = serde_json::from_str(
+            r#"{"name": "kit", "description": null, "versions": [
+                {"version": "1.0.0", "tarball": "kit-1.0.0.tar.gz", "checksum": "c", "dependencies": null},
+                {"version": "2.0.0", "tarball": "kit-2.0.0.tar.gz", "checksum": "c", "dependencies": null}
+            ]}"#,
+        )
+        .unwrap();
+        let mut win64 = binary("Win64", "5.3", "b");
+        win64.tarball = win64.artifact_id("1.0.0");
+        metadata.versions[0].binaries = Some(vec![win64.clone()]);
+        fs::write(
+            client.get_packages_dir().join("kit.json"),
+            serde_json::to_string(&metadata).unwrap(),
+        )
+        .unwrap();
+        fs::write(client.get_tarball_path("kit", "1.0.0"), "source").unwrap();
+        fs::write(client.get_tarball_path("kit", &win64.tarball), "binary").unwrap();
+        fs::create_dir_all(client.get_signatures_dir()).unwrap();
+        fs::write(client.get_signature_path("kit", "1.0.0"), "sig").unwrap();
+
+        let preview = client
+            .migrate_layout(TarballLayout::Sharded, DryRun::new(true))
+            .unwrap();
+        assert_eq!(preview.moved.len(), 3);
+        assert_eq!(client.layout(), TarballLayout::Flat);
+        assert!(client.get_tarball_path("kit", "1.0.0").exists());
+
+        let migration = client
+            .migrate_layout(TarballLayout::Sharded, DryRun::OFF)
+            .unwrap();
+        assert_eq!(migration.moved.len(), 3);
+        // 2.0.0 was never uploaded
+        assert_eq!(migration.missing.len(), 1);
+
+        // The layout is persisted for new clients
+        let reopened = FileRegistryClient::new(temp.path());
+        assert_eq!(reopened.layout(), TarballLayout::Sharded);
+        let tarball = reopened.get_tarball_path("kit", &win64.tarball);
+        assert_eq!(fs::read_to_string(&tarball).unwrap(), "binary");
+        assert!(tarball.starts_with(reopened.get_tarballs_dir()));
+        assert_ne!(
+            tarball.parent(),
+            Some(reopened.get_tarballs_dir().as_path())
+        );
+        assert!(reopened.get_signature_path("kit", "1.0.0").exists());
+
+        client
+            .migrate_layout(TarballLayout::Flat, DryRun::OFF)
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(client.get_tarball_path("kit", "1.0.0")).unwrap(),
+            "source"
+        );
+        // Empty shard directories are cleaned up
+        assert_eq!(fs::read_dir(client.get_tarballs_dir()).unwrap().count(), 2);
+    }
 }
@@ -0,0 +1,364 @@
+//! Cached dependency resolution results
+//!
+//! `unrealpm install` resolves the same manifest over and over. The full
+//! resolution output is cached under `~/.unrealpm/cache/resolutions/`, keyed
+//! by a hash of the active dependencies, enabled features, engine version,
+//! force flag, and registry location. Alongside the result, each entry records a digest of
+//! the registry metadata of every package the resolver looked at.
+//!
+//! A cached result is reused only if all of those digests still match the
+//! registry, so publishing, yanking, or editing any involved package
+//! invalidates it automatically. Checking the digests fetches metadata but
+//! skips the version solver entirely.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::collections::HashMap;
+//! use unrealpm::{RegistryClient, ResolutionCache};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let registry = RegistryClient::new_default()?;
+//! let cache = ResolutionCache::open_default()?;
+//!
+//! let mut dependencies = HashMap::new();
+//! dependencies.insert("awesome-plugin".to_string(), "^1.0.0".to_string());
+//!
+//! let (resolved, hit) =
+//!     cache.resolve(&dependencies, &registry, &registry.location(), Some("5.3"), false, None)?;
+//! println!("{} packages ({})", resolved.len(), if hit { "cached" } else { "resolved" });
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    resolve_with_source, Dependency, DryRun, Error, MetadataSource, PackageMetadata,
+    ResolvedPackage, ResolverConfig, Result,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A cached resolution result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResolution {
+    /// When the resolution was computed (RFC 3339)
+    pub resolved_at: String,
+
+    /// Package name -> digest of its registry metadata at resolution time
+    pub metadata_digests: BTreeMap<String, String>,
+
+    /// The resolved packages
+    pub packages: BTreeMap<String, ResolvedPackage>,
+}
+
+impl CachedResolution {
+    /// Whether the registry still serves the metadata this result was built from
+    pub fn is_fresh(&self, source: &dyn MetadataSource) -> bool {
+        self.metadata_digests.iter().all(|(name, digest)| {
+            source
+                .get_package(name)
+                .is_ok_and(|metadata| metadata_digest(&metadata) == *digest)
+        })
+    }
+}
+
+/// On-disk cache of resolution results
+pub struct ResolutionCache {
+    dir: PathBuf,
+}
+
+impl ResolutionCache {
+    /// Cache stored in `dir`
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Cache in `~/.unrealpm/cache/resolutions`
+    pub fn open_default() -> Result<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| Error::Other("Could not find home directory".to_string()))?;
+        Ok(Self::new(
+            home.join(".unrealpm").join("cache").join("resolutions"),
+        ))
+    }
+
+    /// Directory holding the cache entries
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Resolve `dependencies`, reusing a cached result if it is still fresh
+    ///
+    /// Returns the resolved packages and whether they came from the cache.
+    /// Failures to read or write the cache fall back to a normal resolution.
+    pub fn resolve(
+        &self,
+        dependencies: &HashMap<String, String>,
+        source: &dyn MetadataSource,
+        registry: &str,
+        engine_version: Option<&str>,
+        force: bool,
+        config: Option<&ResolverConfig>,
+    ) -> Result<(HashMap<String, ResolvedPackage>, bool)> {
+        let no_features = HashMap::new();
+        let features = config.map_or(&no_features, |c| &c.features);
+        let key = resolution_key(dependencies, features, registry, engine_version, force);
+
+        if let Some(cached) = self.load(&key) {
+            if cached.is_fresh(source) {
+                return Ok((cached.packages.into_iter().collect(), true));
+            }
+        }
+
+        let recording = RecordingSource::new(source);
+        let resolved =
+            resolve_with_source(dependencies, &recording, engine_version, force, config)?;
+
+        let entry = CachedResolution {
+            resolved_at: chrono::Utc::now().to_rfc3339(),
+            metadata_digests: recording.digests.into_inner(),
+            packages: resolved
+                .iter()
+                .map(|(name, package)| (name.clone(), package.clone()))
+                .collect(),
+        };
+        let _ = self.store(&key, &entry);
+
+        Ok((resolved, false))
+    }
+
+    /// Load the entry for a key, if present and readable
+    pub fn load(&self, key: &str) -> Option<CachedResolution> {
+        let contents = fs::read_to_string(self.entry_path(key)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Write the entry for a key
+    pub fn store(&self, key: &str, entry: &CachedResolution) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string(entry)
+            .map_err(|e| Error::Other(format!("Failed to serialize resolution: {}", e)))?;
+
+        // Write then rename so concurrent installs never read a partial entry
+        let path = self.entry_path(key);
+        let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Remove all entries, returning how many were (or would be) removed
+    pub fn clear(&self, dry_run: DryRun) -> Result<usize> {
+        let mut removed = 0;
+        if let Ok(entries) = fs::read_dir(&self.dir) {
+            for entry in entries.flatten() {
+                if entry.path().extension().is_some_and(|ext| ext == "json")
+                    && dry_run.apply(|| Ok(fs::remove_file(entry.path())?)).is_ok()
+                {
+                    removed += 1;
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+/// Cache key for a resolution request
+///
+/// Covers everything that changes the result besides registry contents,
+/// including the UnrealPM version so resolver changes never reuse old output.
+pub fn resolution_key(
+    dependencies: &HashMap<String, String>,
+    features: &HashMap<String, Vec<String>>,
+    registry: &str,
+    engine_version: Option<&str>,
+    force: bool,
+) -> String {
+    let sorted: BTreeMap<&String, &String> = dependencies.iter().collect();
+    let features: BTreeMap<&String, Vec<&String>> = features
+        .iter()
+        .filter(|(_, enabled)| !enabled.is_empty())
+        .map(|(name, enabled)| {
+            let mut enabled: Vec<&String> = enabled.iter().collect();
+            enabled.sort();
+            enabled.dedup();
+            (name, enabled)
+        })
+        .collect();
+    let input = serde_json::json!({
+        "unrealpm_version": env!("CARGO_PKG_VERSION"),
+        "registry": registry,
+        "engine_version": engine_version,
+        "force": force,
+        "dependencies": sorted,
+        "features": features,
+    });
+    hex::encode(Sha256::digest(input.to_string().as_bytes()))
+}
+
+/// Stable digest of a package's registry metadata
+pub fn metadata_digest(metadata: &PackageMetadata) -> String {
+    // Going through Value sorts object keys, so HashMap order does not matter
+    let value = serde_json::to_value(metadata).unwrap_or_default();
+    hex::encode(Sha256::digest(value.to_string().as_bytes()))
+}
+
+/// Metadata source that remembers a digest of everything it served
+struct RecordingSource<'a> {
+    inner: &'a dyn MetadataSource,
+    digests: RefCell<BTreeMap<String, String>>,
+}
+
+impl<'a> RecordingSource<'a> {
+    fn new(inner: &'a dyn MetadataSource) -> Self {
+        Self {
+            inner,
+            digests: RefCell::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl MetadataSource for RecordingSource<'_> {
+    fn get_package(&self, name: &str) -> Result<PackageMetadata> {
+        let metadata = self.inner.get_package(name)?;
+        self.digests
+            .borrow_mut()
+            .insert(name.to_string(), metadata_digest(&metadata));
+        Ok(metadata)
+    }
+
+    fn get_version_dependencies(
+        &self,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<Vec<Dependency>>> {
+        self.inner.get_version_dependencies(name, version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PackageType, PackageVersion};
+    use tempfile::TempDir;
+
+    fn package(name: &str, versions: &[&str]) -> PackageMetadata {
+        PackageMetadata {
+            name: name.to_string(),
+            description: None,
+            versions: versions
+                .iter()
+                .map(|v| PackageVersion {
+                    version: v.to_string(),
+                    tarball: format!("{}-{}.tar.gz", name, v),
+                    checksum: format!("sha256:{}{}", name, v),
+                    dependencies: Some(Vec::new()),
+                    engine_versions: None,
+                    engine_major: None,
+                    engine_minor: None,
+                    is_multi_engine: true,
+                    package_type: PackageType::Source,
+                    binaries: None,
+                    public_key: None,
+                    signed_at: None,
+                    key_endorsement: None,
+                    modules: None,
+                    features: None,
+                })
+                .collect(),
+            categories: Vec::new(),
+            keywords: Vec::new(),
+        }
+    }
+
+    fn deps() -> HashMap<String, String> {
+        HashMap::from([("awesome-plugin".to_string(), "^1.0.0".to_string())])
+    }
+
+    #[test]
+    fn test_resolution_key_is_order_independent() {
+        let a = HashMap::from([
+            ("a".to_string(), "^1".to_string()),
+            ("b".to_string(), "^2".to_string()),
+        ]);
+        let mut b = HashMap::new();
+        b.insert("b".to_string(), "^2".to_string());
+        b.insert("a".to_string(), "^1".to_string());
+
+        assert_eq!(
+            resolution_key(&a, &HashMap::new(), "file", Some("5.3"), false),
+            resolution_key(&b, &HashMap::new(), "file", Some("5.3"), false)
+        );
+        assert_ne!(
+            resolution_key(&a, &HashMap::new(), "file", Some("5.3"), false),
+            resolution_key(&a, &HashMap::new(), "file", Some("5.4"), false)
+        );
+        assert_ne!(
+            resolution_key(&a, &HashMap::new(), "file", Some("5.3"), false),
+            resolution_key(&a, &HashMap::new(), "file", Some("5.3"), true)
+        );
+
+        let features = HashMap::from([("a".to_string(), vec!["extra".to_string()])]);
+        assert_ne!(
+            resolution_key(&a, &HashMap::new(), "file", Some("5.3"), false),
+            resolution_key(&a, &features, "file", Some("5.3"), false)
+        );
+    }
+
+    #[test]
+    fn test_cache_hit_and_invalidation() {
+        let temp = TempDir::new().unwrap();
+        let cache = ResolutionCache::new(temp.path());
+
+        let mut source: HashMap<String, PackageMetadata> = HashMap::new();
+        source.insert(
+            "awesome-plugin".to_string(),
+            package("awesome-plugin", &["1.0.0"]),
+        );
+
+        let (resolved, hit) = cache
+            .resolve(&deps(), &source, "file", Some("5.3"), false, None)
+            .unwrap();
+        assert!(!hit);
+        assert_eq!(resolved["awesome-plugin"].version, "1.0.0");
+
+        let (resolved, hit) = cache
+            .resolve(&deps(), &source, "file", Some("5.3"), false, None)
+            .unwrap();
+        assert!(hit);
+        assert_eq!(resolved["awesome-plugin"].version, "1.0.0");
+
+        // Publishing a new version invalidates the cached result
+        source.insert(
+            "awesome-plugin".to_string(),
+            package("awesome-plugin", &["1.0.0", "1.1.0"]),
+        );
+        let (resolved, hit) = cache
+            .resolve(&deps(), &source, "file", Some("5.3"), false, None)
+            .unwrap();
+        assert!(!hit);
+        assert_eq!(resolved["awesome-plugin"].version, "1.1.0");
+
+        assert_eq!(cache.clear(DryRun::new(true)).unwrap(), 1);
+        assert_eq!(cache.clear(DryRun::OFF).unwrap(), 1);
+        assert!(cache
+            .load(&resolution_key(
+                &deps(),
+                &HashMap::new(),
+                "file",
+                Some("5.3"),
+                false
+            ))
+            .is_none());
+    }
+}
@@ -20,13 +20,17 @@
 //! # }
 //! ```
 
-use crate::{Error, PackageMetadata, PackageVersion, RegistryClient, ResolverConfig, Result};
+use crate::{
+    Error, InstallMode, Lockfile, PackageMetadata, PackageVersion, PrebuiltBinary, RegistryClient,
+    ResolverConfig, Result,
+};
 use semver::{Version, VersionReq};
 use std::collections::{HashMap, HashSet};
 
 // Re-export the PubGrub-based resolver
 pub use crate::pubgrub_resolver::{
-    resolve_dependencies as pubgrub_resolve_dependencies, ResolvedPackage,
+    resolve_dependencies as pubgrub_resolve_dependencies, resolve_with_source, MetadataSource,
+    ResolvedPackage,
 };
 
 /// Find the best matching version for a version constraint
@@ -69,7 +73,8 @@ pub fn find_matching_version(
         ))
     })?;
 
-    // Find all matching versions
+    // Find all matching versions, noting whether the engine ruled any out
+    let mut engine_excluded = false;
     let mut matching_versions: Vec<_> = package_metadata
         .versions
         .iter()
@@ -93,35 +98,8 @@ pub fn find_matching_version(
             // Check engine version compatibility if specified (unless force is enabled)
             if !force {
                 if let Some(required_engine) = engine_version {
-                    // Parse required engine (e.g., "5.3" -> major=5, minor=3)
-                    let req_parts: Vec<&str> = required_engine.split('.').collect();
-                    let req_major = req_parts.first().and_then(|s| s.parse::<i32>().ok());
-                    let req_minor = req_parts.get(1).and_then(|s| s.parse::<i32>().ok());
-
-                    let mut matches = false;
-
-                    // Check engine-specific version
-                    if !pkg_ver.is_multi_engine {
-                        // Engine-specific: Must match major.minor
-                        if let (Some(pkg_major), Some(pkg_minor), Some(rm), Some(rmi)) = (
-                            pkg_ver.engine_major,
-                            pkg_ver.engine_minor,
-                            req_major,
-                            req_minor,
-                        ) {
-                            matches = pkg_major == rm && pkg_minor == rmi;
-                        }
-                    } else {
-                        // Multi-engine: Check if in array
-                        if let Some(compatible_engines) = &pkg_ver.engine_versions {
-                            matches = compatible_engines.iter().any(|e| e == required_engine);
-                        } else {
-                            // If no engine_versions specified, assume compatible with all
-                            matches = true;
-                        }
-                    }
-
-                    if !matches {
+                    if !pkg_ver.supports_engine(required_engine) {
+                        engine_excluded = true;
                         return None;
                     }
                 }
@@ -156,32 +134,34 @@ pub fn find_matching_version(
             })
             .collect();
 
+        let versions = available_versions.join("\n  ");
         let error_msg = if let Some(engine) = engine_version {
-            format!(
-                "No version of '{}' matches constraint '{}' for Unreal Engine {}\n\n\
-                Available versions:\n  {}\n\n\
-                Suggestions:\n\
-                  • Check if the package supports Unreal Engine {}\n\
-                  • Try a different version constraint\n\
-                  • Update your engine version in the .uproject file",
-                package_metadata.name,
-                constraint,
-                engine,
-                available_versions.join("\n  "),
-                engine
+            crate::i18n::tr(
+                "resolve.no_match_for_engine",
+                &[
+                    ("name", &package_metadata.name),
+                    ("constraint", &constraint),
+                    ("engine", &crate::platform::describe_engine_version(engine)),
+                    ("versions", &versions),
+                    (
+                        "engine_short",
+                        &crate::platform::normalize_engine_version(engine),
+                    ),
+                ],
             )
         } else {
-            format!(
-                "No version of '{}' matches constraint '{}'\n\n\
-                Available versions:\n  {}\n\n\
-                Suggestions:\n\
-                  • Try a different version constraint\n\
-                  • Check the package name spelling",
-                package_metadata.name,
-                constraint,
-                available_versions.join("\n  ")
+            crate::i18n::tr(
+                "resolve.no_match",
+                &[
+                    ("name", &package_metadata.name),
+                    ("constraint", &constraint),
+                    ("versions", &versions),
+                ],
             )
         };
+        if engine_excluded {
+            return Err(Error::EngineIncompatible(error_msg));
+        }
         return Err(Error::DependencyResolutionFailed(error_msg));
     }
 
@@ -218,10 +198,175 @@ pub fn resolve_dependencies(
     force: bool,
     config: Option<&ResolverConfig>,
 ) -> Result<HashMap<String, ResolvedPackage>> {
+    registry.emit(crate::Event::ResolveStarted {
+        dependencies: direct_deps.len(),
+    });
+    if config.is_some_and(|config| config.remote) {
+        let features = config
+            .map(|config| &config.features)
+            .cloned()
+            .unwrap_or_default();
+        if let Some(resolved) =
+            registry.resolve_on_registry(direct_deps, engine_version, force, &features)
+        {
+            return Ok(resolved);
+        }
+    }
     // Delegate to PubGrub-based resolver
     pubgrub_resolve_dependencies(direct_deps, registry, engine_version, force, config)
 }
 
+/// Check a resolution computed elsewhere (by the registry) before using it
+///
+/// Every direct dependency must be resolved to a version its constraint
+/// allows, and every dependency a resolved package lists must be resolved
+/// too. Returns what is wrong otherwise.
+pub(crate) fn check_resolution(
+    direct_deps: &HashMap<String, String>,
+    resolved: &HashMap<String, ResolvedPackage>,
+) -> std::result::Result<(), String> {
+    let satisfies = |name: &str, constraint: &str| {
+        let pkg = resolved
+            .get(name)
+            .ok_or_else(|| format!("{} is missing", name))?;
+        match (VersionReq::parse(constraint), Version::parse(&pkg.version)) {
+            (Ok(req), Ok(version)) if req.matches(&version) => Ok(()),
+            _ => Err(format!(
+                "{}@{} does not satisfy {}",
+                name, pkg.version, constraint
+            )),
+        }
+    };
+    for (name, constraint) in direct_deps {
+        satisfies(name, constraint)?;
+    }
+    for pkg in resolved.values() {
+        for (name, constraint) in pkg.dependencies.iter().flatten() {
+            satisfies(name, constraint).map_err(|e| format!("{} (needed by {})", e, pkg.name))?;
+        }
+    }
+    Ok(())
+}
+
+/// How to choose between source and pre-built binaries for resolved packages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactSelection {
+    /// Install mode (source vs binary preference)
+    pub mode: InstallMode,
+
+    /// Platforms whose binaries are acceptable, most preferred first
+    pub platforms: Vec<String>,
+
+    /// Engine version the binaries must target; without one, source is used
+    pub engine: Option<String>,
+
+    /// Build configuration to prefer (e.g., "Development")
+    pub configuration: String,
+}
+
+impl ArtifactSelection {
+    /// Binary to install for a package version, or `None` to install from source
+    ///
+    /// Fails only in [`InstallMode::BinaryOnly`] when no binary matches.
+    pub fn select(&self, name: &str, version: &PackageVersion) -> Result<Option<PrebuiltBinary>> {
+        if matches!(
+            self.mode,
+            InstallMode::SourceOnly | InstallMode::PreferSource
+        ) {
+            return Ok(None);
+        }
+
+        let binaries = version.binaries.as_deref().unwrap_or_default();
+        let selected = self.engine.as_deref().and_then(|engine| {
+            self.platforms.iter().find_map(|platform| {
+                PrebuiltBinary::select(binaries, platform, engine, &self.configuration)
+            })
+        });
+        if selected.is_none() && self.mode == InstallMode::BinaryOnly {
+            let available = if binaries.is_empty() {
+                "  None".to_string()
+            } else {
+                binaries
+                    .iter()
+                    .map(|b| format!("  - {}", b.target()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            return Err(Error::Other(crate::i18n::tr(
+                "resolve.binary_unavailable",
+                &[
+                    ("name", &name),
+                    ("platforms", &self.platforms.join("/")),
+                    ("engine", &self.engine.as_deref().unwrap_or("unknown")),
+                    ("configuration", &self.configuration),
+                    ("binaries", &available),
+                ],
+            )));
+        }
+        Ok(selected.cloned())
+    }
+
+    /// Whether a previously recorded choice is still valid for this selection
+    pub fn accepts(&self, binary: Option<&PrebuiltBinary>) -> bool {
+        match (self.mode, binary) {
+            (InstallMode::SourceOnly, Some(_)) | (InstallMode::BinaryOnly, None) => false,
+            (_, None) => true,
+            (_, Some(binary)) => {
+                self.platforms
+                    .iter()
+                    .any(|p| p.eq_ignore_ascii_case(&binary.platform))
+                    && self.engine.as_deref().is_some_and(|engine| {
+                        crate::platform::engine_versions_compatible(&binary.engine, engine)
+                    })
+            }
+        }
+    }
+}
+
+/// Choose the artifact (source or pre-built binary) for each resolved package
+///
+/// A choice recorded in `lockfile` for the same version and checksum is kept
+/// as long as `selection` still accepts it, so every machine installs the
+/// same artifacts. Other packages get a fresh [`ArtifactSelection::select`].
+pub fn select_artifacts(
+    resolved: &mut HashMap<String, ResolvedPackage>,
+    source: &impl MetadataSource,
+    selection: &ArtifactSelection,
+    lockfile: Option<&Lockfile>,
+) -> Result<()> {
+    for (name, package) in resolved.iter_mut() {
+        let locked = lockfile
+            .and_then(|lockfile| lockfile.get_package(name))
+            .filter(|locked| {
+                locked.version == package.version && locked.checksum == package.checksum
+            });
+        if let Some(locked) = locked {
+            if selection.accepts(locked.binary.as_ref()) {
+                package.binary = locked.binary.clone();
+                continue;
+            }
+        }
+
+        // Engine-specific builds share a version string; the checksum tells them apart
+        let metadata = source.get_package(name)?;
+        let version = metadata
+            .versions
+            .iter()
+            .find(|v| v.version == package.version && v.checksum == package.checksum)
+            .or_else(|| {
+                metadata
+                    .versions
+                    .iter()
+                    .find(|v| v.version == package.version)
+            });
+        package.binary = match version {
+            Some(version) => selection.select(name, version)?,
+            None => None,
+        };
+    }
+    Ok(())
+}
+
 /// Detect circular dependencies in a dependency graph
 ///
 /// Returns an error if a circular dependency is found
@@ -264,6 +409,71 @@ pub fn detect_circular_deps(
     Ok(())
 }
 
+/// Resolved packages that only development dependencies need, sorted by name
+///
+/// Reachability follows the resolved dependency lists from `runtime_roots`,
+/// the project's runtime dependencies; everything else in `resolved` came in
+/// through a dev dependency.
+pub fn dev_only_packages<'a>(
+    resolved: &HashMap<String, ResolvedPackage>,
+    runtime_roots: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let mut reached: HashSet<&str> = HashSet::new();
+    let mut pending: Vec<&str> = runtime_roots.into_iter().collect();
+    while let Some(name) = pending.pop() {
+        if !reached.insert(name) {
+            continue;
+        }
+        if let Some(deps) = resolved.get(name).and_then(|p| p.dependencies.as_ref()) {
+            pending.extend(deps.keys().map(String::as_str));
+        }
+    }
+
+    let mut dev_only: Vec<String> = resolved
+        .keys()
+        .filter(|name| !reached.contains(name.as_str()))
+        .cloned()
+        .collect();
+    dev_only.sort();
+    dev_only
+}
+
+/// Names in `resolved` ordered so each package comes after the packages it
+/// depends on
+///
+/// Ties are broken by name, and so are dependency cycles, so the order is
+/// stable from run to run.
+pub fn install_order(resolved: &HashMap<String, ResolvedPackage>) -> Vec<String> {
+    fn visit<'a>(
+        name: &'a str,
+        resolved: &'a HashMap<String, ResolvedPackage>,
+        visited: &mut HashSet<&'a str>,
+        order: &mut Vec<String>,
+    ) {
+        if !visited.insert(name) {
+            return;
+        }
+        if let Some(deps) = resolved.get(name).and_then(|p| p.dependencies.as_ref()) {
+            let mut deps: Vec<&String> =
+                deps.keys().filter(|d| resolved.contains_key(*d)).collect();
+            deps.sort();
+            for dep in deps {
+                visit(dep, resolved, visited, order);
+            }
+        }
+        order.push(name.to_string());
+    }
+
+    let mut names: Vec<&String> = resolved.keys().collect();
+    names.sort();
+    let mut visited = HashSet::new();
+    let mut order = Vec::with_capacity(resolved.len());
+    for name in names {
+        visit(name, resolved, &mut visited, &mut order);
+    }
+    order
+}
+
 /// Version resolver using PubGrub algorithm
 ///
 /// Note: This struct is kept for API compatibility but the actual resolution
@@ -308,6 +518,9 @@ mod tests {
             binaries: None,
             public_key: None,
             signed_at: None,
+            key_endorsement: None,
+            modules: None,
+            features: None,
         }
     }
 
@@ -317,6 +530,8 @@ mod tests {
             name: name.to_string(),
             description: Some("Test package".to_string()),
             versions,
+            categories: Vec::new(),
+            keywords: Vec::new(),
         }
     }
 
@@ -516,6 +731,151 @@ mod tests {
         assert_eq!(result.unwrap().version, "1.2.0");
     }
 
+    #[test]
+    fn test_find_matching_version_engine_suffixes() {
+        let metadata = make_metadata(
+            "test-pkg",
+            vec![
+                make_version("1.0.0", None, None, true, Some(vec!["5.3", "5.4"])),
+                make_version("1.1.0", Some(4), Some(27), false, None),
+            ],
+        );
+
+        // Preview and patch builds match by major.minor
+        let result = find_matching_version(&metadata, "*", Some("5.4.0-preview"), false).unwrap();
+        assert_eq!(result.version, "1.0.0");
+        let result = find_matching_version(&metadata, "*", Some("5.3.2"), false).unwrap();
+        assert_eq!(result.version, "1.0.0");
+
+        // Custom source builds match engine-specific versions
+        let result = find_matching_version(&metadata, "*", Some("4.27Chaos"), false).unwrap();
+        assert_eq!(result.version, "1.1.0");
+
+        // Diagnostics keep the exact engine string
+        let err = find_matching_version(&metadata, "*", Some("5.5.0-preview"), false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("5.5.0-preview (compared as 5.5)"));
+    }
+
+    // ============================================================================
+    // dev_only_packages tests
+    // ============================================================================
+
+    #[test]
+    fn test_dev_only_packages() {
+        // runtime -> shared; test-framework -> shared, mocks
+        let package = |name: &str, deps: &[&str]| {
+            (
+                name.to_string(),
+                ResolvedPackage {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    checksum: "abc".to_string(),
+                    binary: None,
+                    dependencies: Some(
+                        deps.iter()
+                            .map(|dep| (dep.to_string(), "^1.0.0".to_string()))
+                            .collect(),
+                    ),
+                },
+            )
+        };
+        let resolved: HashMap<String, ResolvedPackage> = [
+            package("runtime", &["shared"]),
+            package("shared", &[]),
+            package("test-framework", &["shared", "mocks"]),
+            package("mocks", &[]),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            dev_only_packages(&resolved, ["runtime"]),
+            vec!["mocks", "test-framework"]
+        );
+        // A package both groups need is a runtime package
+        assert_eq!(
+            dev_only_packages(&resolved, ["runtime", "test-framework"]),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_install_order() {
+        let package = |name: &str, deps: &[&str]| {
+            (
+                name.to_string(),
+                ResolvedPackage {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    checksum: "abc".to_string(),
+                    binary: None,
+                    dependencies: Some(
+                        deps.iter()
+                            .map(|dep| (dep.to_string(), "^1.0.0".to_string()))
+                            .collect(),
+                    ),
+                },
+            )
+        };
+        let resolved: HashMap<String, ResolvedPackage> = [
+            package("app", &["ui", "core"]),
+            package("ui", &["core"]),
+            package("core", &[]),
+            package("bench", &[]),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(install_order(&resolved), vec!["core", "ui", "app", "bench"]);
+
+        // A cycle still yields every package once
+        let cyclic: HashMap<String, ResolvedPackage> = [package("a", &["b"]), package("b", &["a"])]
+            .into_iter()
+            .collect();
+        assert_eq!(install_order(&cyclic), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_check_resolution() {
+        let package = |name: &str, version: &str, deps: &[(&str, &str)]| {
+            (
+                name.to_string(),
+                ResolvedPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    checksum: "abc".to_string(),
+                    binary: None,
+                    dependencies: Some(
+                        deps.iter()
+                            .map(|(dep, constraint)| (dep.to_string(), constraint.to_string()))
+                            .collect(),
+                    ),
+                },
+            )
+        };
+        let direct = HashMap::from([("app".to_string(), "^1.0.0".to_string())]);
+        let mut resolved: HashMap<String, ResolvedPackage> = [
+            package("app", "1.2.0", &[("core", "^2.0.0")]),
+            package("core", "2.1.0", &[]),
+        ]
+        .into_iter()
+        .collect();
+        assert!(check_resolution(&direct, &resolved).is_ok());
+
+        resolved.extend([package("core", "1.9.0", &[])]);
+        assert_eq!(
+            check_resolution(&direct, &resolved).unwrap_err(),
+            "core@1.9.0 does not satisfy ^2.0.0 (needed by app)"
+        );
+
+        resolved.remove("app");
+        assert_eq!(
+            check_resolution(&direct, &resolved).unwrap_err(),
+            "app is missing"
+        );
+    }
+
     // ============================================================================
     // detect_circular_deps tests
     // ============================================================================
@@ -531,6 +891,7 @@ mod tests {
                 name: "A".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "abc".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("B".to_string(), "^1.0.0".to_string());
@@ -544,6 +905,7 @@ mod tests {
                 name: "B".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "def".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("C".to_string(), "^1.0.0".to_string());
@@ -557,6 +919,7 @@ mod tests {
                 name: "C".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "ghi".to_string(),
+                binary: None,
                 dependencies: None,
             },
         );
@@ -579,6 +942,7 @@ mod tests {
                 name: "A".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "abc".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("B".to_string(), "^1.0.0".to_string());
@@ -592,6 +956,7 @@ mod tests {
                 name: "B".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "def".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("A".to_string(), "^1.0.0".to_string());
@@ -623,6 +988,7 @@ mod tests {
                 name: "A".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "abc".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("B".to_string(), "^1.0.0".to_string());
@@ -636,6 +1002,7 @@ mod tests {
                 name: "B".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "def".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("C".to_string(), "^1.0.0".to_string());
@@ -649,6 +1016,7 @@ mod tests {
                 name: "C".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "ghi".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("A".to_string(), "^1.0.0".to_string());
@@ -678,6 +1046,7 @@ mod tests {
                 name: "A".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "abc".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("B".to_string(), "^1.0.0".to_string());
@@ -692,6 +1061,7 @@ mod tests {
                 name: "B".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "def".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("D".to_string(), "^1.0.0".to_string());
@@ -705,6 +1075,7 @@ mod tests {
                 name: "C".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "ghi".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("D".to_string(), "^1.0.0".to_string());
@@ -718,6 +1089,7 @@ mod tests {
                 name: "D".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "jkl".to_string(),
+                binary: None,
                 dependencies: None,
             },
         );
@@ -751,6 +1123,7 @@ mod tests {
                 name: "A".to_string(),
                 version: "1.0.0".to_string(),
                 checksum: "abc".to_string(),
+                binary: None,
                 dependencies: Some({
                     let mut d = HashMap::new();
                     d.insert("A".to_string(), "^1.0.0".to_string());
@@ -770,6 +1143,110 @@ mod tests {
         assert!(err_msg.contains("A → A"));
     }
 
+    // ============================================================================
+    // Artifact selection tests
+    // ============================================================================
+
+    fn make_binary(platform: &str, engine: &str) -> PrebuiltBinary {
+        PrebuiltBinary {
+            platform: platform.to_string(),
+            engine: engine.to_string(),
+            tarball: format!("1.0.0-{}-{}", platform, engine),
+            checksum: format!("bin-{}-{}", platform, engine),
+            configuration: None,
+        }
+    }
+
+    fn make_selection(mode: InstallMode) -> ArtifactSelection {
+        ArtifactSelection {
+            mode,
+            platforms: vec!["Win64".to_string()],
+            engine: Some("5.3".to_string()),
+            configuration: "Development".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_artifact_selection_modes() {
+        let mut version = make_version("1.0.0", None, None, true, Some(vec!["5.3"]));
+        version.binaries = Some(vec![
+            make_binary("Linux", "5.3"),
+            make_binary("Win64", "5.3"),
+        ]);
+
+        let selected = make_selection(InstallMode::PreferBinary)
+            .select("pkg", &version)
+            .unwrap();
+        assert_eq!(selected, Some(make_binary("Win64", "5.3")));
+        assert_eq!(
+            make_selection(InstallMode::PreferSource)
+                .select("pkg", &version)
+                .unwrap(),
+            None
+        );
+
+        // No binary for the engine: fall back to source, or fail for binary-only
+        let mut selection = make_selection(InstallMode::PreferBinary);
+        selection.engine = Some("5.4".to_string());
+        assert_eq!(selection.select("pkg", &version).unwrap(), None);
+        selection.mode = InstallMode::BinaryOnly;
+        let err = selection.select("pkg", &version).unwrap_err().to_string();
+        assert!(err.contains("No pre-built binary available for pkg"));
+        assert!(err.contains("Win64/5.3"));
+    }
+
+    #[test]
+    fn test_select_artifacts_keeps_locked_choice() {
+        let mut version = make_version("1.0.0", None, None, true, Some(vec!["5.3"]));
+        version.binaries = Some(vec![make_binary("Win64", "5.3")]);
+        let mut packages = HashMap::new();
+        packages.insert("pkg".to_string(), make_metadata("pkg", vec![version]));
+
+        let resolved = || {
+            let mut resolved = HashMap::new();
+            resolved.insert(
+                "pkg".to_string(),
+                ResolvedPackage {
+                    name: "pkg".to_string(),
+                    version: "1.0.0".to_string(),
+                    checksum: "abc123".to_string(),
+                    dependencies: None,
+                    binary: None,
+                },
+            );
+            resolved
+        };
+
+        // Locked as source: prefer-binary keeps installing source
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package(
+            "pkg".to_string(),
+            "1.0.0".to_string(),
+            "abc123".to_string(),
+            None,
+        );
+        let mut from_lock = resolved();
+        let selection = make_selection(InstallMode::PreferBinary);
+        select_artifacts(&mut from_lock, &packages, &selection, Some(&lockfile)).unwrap();
+        assert_eq!(from_lock["pkg"].binary, None);
+        assert_eq!(from_lock["pkg"].artifact_checksum(), "abc123");
+
+        // Without a lockfile the binary is selected
+        let mut fresh = resolved();
+        select_artifacts(&mut fresh, &packages, &selection, None).unwrap();
+        assert_eq!(fresh["pkg"].binary, Some(make_binary("Win64", "5.3")));
+        assert_eq!(fresh["pkg"].artifact_id(), "1.0.0-Win64-5.3");
+
+        // A locked binary for another platform is not reused
+        lockfile.set_binary("pkg", Some(make_binary("Mac", "5.3")));
+        let mut other_platform = resolved();
+        select_artifacts(&mut other_platform, &packages, &selection, Some(&lockfile)).unwrap();
+        assert_eq!(
+            other_platform["pkg"].binary,
+            Some(make_binary("Win64", "5.3"))
+        );
+    }
+
     // ============================================================================
     // Resolver struct tests
     // ============================================================================
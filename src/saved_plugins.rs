@@ -0,0 +1,282 @@
+//! Previous plugin versions kept in the project's Saved/ folder
+//!
+//! Switching between branches whose lockfiles pin different versions makes
+//! every install replace the same plugins back and forth. When an install
+//! replaces a plugin, its old folder is moved to
+//! `Saved/UnrealPM/Plugins/<package>-<hash>/` instead of being deleted, and
+//! installing that version again renames it back into Plugins/ rather than
+//! linking or copying it from the store. Renames within a volume are
+//! instant, even where the store is on another drive and linking would mean
+//! a full copy.
+//!
+//! `Saved/UnrealPM/Plugins/index.json` records which tarball checksum each
+//! installed and saved folder came from. A saved folder is only reused if it
+//! still has every file of the store copy at the same size; files the editor
+//! added (Binaries/, Intermediate/) are kept. Only the last few versions of
+//! each package are kept (see [`set_keep`]).
+
+use crate::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Folder of saved plugin versions, relative to the project
+pub const SAVED_PLUGINS_DIR: &str = "Saved/UnrealPM/Plugins";
+
+/// Versions of each package kept unless configured otherwise
+pub const DEFAULT_SAVED_VERSIONS: usize = 2;
+
+const INDEX_FILE_NAME: &str = "index.json";
+
+static KEEP: AtomicUsize = AtomicUsize::new(DEFAULT_SAVED_VERSIONS);
+
+/// Keep this many previous versions of each package for the rest of the
+/// run; 0 deletes replaced plugins as before
+pub fn set_keep(count: usize) {
+    KEEP.store(count, Ordering::Relaxed);
+}
+
+/// Previous versions of each package kept, as set by [`set_keep`]
+pub fn keep() -> usize {
+    KEEP.load(Ordering::Relaxed)
+}
+
+/// Which tarball each installed and saved plugin folder came from
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedIndex {
+    /// Package -> checksum of the version in Plugins/
+    #[serde(default)]
+    installed: BTreeMap<String, String>,
+    /// Package -> checksums of its saved versions, oldest first
+    #[serde(default)]
+    saved: BTreeMap<String, Vec<String>>,
+}
+
+impl SavedIndex {
+    fn path(project_dir: &Path) -> PathBuf {
+        project_dir.join(SAVED_PLUGINS_DIR).join(INDEX_FILE_NAME)
+    }
+
+    fn load(project_dir: &Path) -> Self {
+        fs::read_to_string(Self::path(project_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, project_dir: &Path) -> Result<()> {
+        let path = Self::path(project_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Folder a saved version of `package` is kept in
+fn saved_dir(project_dir: &Path, package: &str, checksum: &str) -> PathBuf {
+    let hash: String = checksum
+        .trim_start_matches("sha256:")
+        .chars()
+        .take(16)
+        .collect();
+    project_dir
+        .join(SAVED_PLUGINS_DIR)
+        .join(format!("{}-{}", crate::scope::encode(package), hash))
+}
+
+/// Record that Plugins/ now holds `package` as installed from `checksum`
+pub(crate) fn record_installed(project_dir: &Path, package: &str, checksum: &str) {
+    let mut index = SavedIndex::load(project_dir);
+    index
+        .installed
+        .insert(package.to_string(), checksum.to_string());
+    let _ = index.save(project_dir);
+}
+
+/// Move a replaced plugin folder into Saved/ instead of deleting it
+///
+/// `folder` is the old copy of `package`, being replaced by the version
+/// with checksum `replaced_by`. Returns false (leaving `folder` alone) when
+/// keeping versions is off, the folder's origin isn't known, or it is the
+/// same version. Older saved versions beyond [`keep`] are deleted.
+pub(crate) fn stash(project_dir: &Path, package: &str, folder: &Path, replaced_by: &str) -> bool {
+    let keep = keep();
+    let mut index = SavedIndex::load(project_dir);
+    let Some(checksum) = index.installed.get(package).cloned() else {
+        return false;
+    };
+    if keep == 0 || checksum == replaced_by {
+        return false;
+    }
+
+    let dest = saved_dir(project_dir, package, &checksum);
+    if dest.exists() && fs::remove_dir_all(&dest).is_err() {
+        return false;
+    }
+    if dest
+        .parent()
+        .is_some_and(|parent| fs::create_dir_all(parent).is_err())
+        || fs::rename(folder, &dest).is_err()
+    {
+        return false;
+    }
+
+    let versions = index.saved.entry(package.to_string()).or_default();
+    versions.retain(|saved| *saved != checksum);
+    versions.push(checksum);
+    let evicted = versions.len().saturating_sub(keep);
+    for old in versions.drain(..evicted) {
+        let _ = fs::remove_dir_all(saved_dir(project_dir, package, &old));
+    }
+    index.installed.remove(package);
+    let _ = index.save(project_dir);
+    true
+}
+
+/// Move a saved version of `package` back to `target`
+///
+/// `store_copy` is the package's verified folder in the store; the saved
+/// folder must still have each of its files at the same size, or it is
+/// discarded. Returns false if nothing was restored.
+pub(crate) fn restore(
+    project_dir: &Path,
+    package: &str,
+    checksum: &str,
+    store_copy: &Path,
+    target: &Path,
+) -> bool {
+    let mut index = SavedIndex::load(project_dir);
+    let Some(versions) = index.saved.get_mut(package) else {
+        return false;
+    };
+    let Some(position) = versions.iter().position(|saved| saved == checksum) else {
+        return false;
+    };
+    versions.remove(position);
+
+    let dir = saved_dir(project_dir, package, checksum);
+    let restored = dir.is_dir()
+        && has_files_of(&dir, store_copy)
+        && !target.exists()
+        && fs::rename(&dir, target).is_ok();
+    if !restored {
+        let _ = fs::remove_dir_all(&dir);
+    }
+    let _ = index.save(project_dir);
+    restored
+}
+
+/// True if every file under `original` exists under `dir` with the same size
+fn has_files_of(dir: &Path, original: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(original) else {
+        return false;
+    };
+    entries.flatten().all(|entry| {
+        let path = dir.join(entry.file_name());
+        match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => has_files_of(&path, &entry.path()),
+            Ok(metadata) => fs::metadata(&path).is_ok_and(|saved| saved.len() == metadata.len()),
+            Err(_) => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn plugin(dir: &Path, content: &str) {
+        fs::create_dir_all(dir.join("Source")).unwrap();
+        fs::write(dir.join("Awesome.uplugin"), "{}").unwrap();
+        fs::write(dir.join("Source").join("Awesome.cpp"), content).unwrap();
+    }
+
+    #[test]
+    fn test_stash_and_restore() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Game");
+        let installed = project.join("Plugins").join("Awesome");
+        let store_v1 = temp.path().join("store-v1");
+        plugin(&installed, "v1");
+        plugin(&store_v1, "v1");
+
+        // Nothing is known about a folder that wasn't recorded
+        assert!(!stash(&project, "Awesome", &installed, "sha256:bbb"));
+
+        record_installed(&project, "Awesome", "sha256:aaa");
+        assert!(!stash(&project, "Awesome", &installed, "sha256:aaa"));
+        assert!(stash(&project, "Awesome", &installed, "sha256:bbb"));
+        assert!(!installed.exists());
+        assert!(saved_dir(&project, "Awesome", "sha256:aaa").is_dir());
+
+        // Editor output added to the folder doesn't stop it being reused
+        let saved = saved_dir(&project, "Awesome", "sha256:aaa");
+        fs::create_dir_all(saved.join("Binaries")).unwrap();
+        assert!(restore(
+            &project,
+            "Awesome",
+            "sha256:aaa",
+            &store_v1,
+            &installed
+        ));
+        assert!(installed.join("Binaries").is_dir());
+        assert!(!restore(
+            &project,
+            "Awesome",
+            "sha256:aaa",
+            &store_v1,
+            &installed
+        ));
+    }
+
+    #[test]
+    fn test_restore_discards_modified_copy() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Game");
+        let installed = project.join("Plugins").join("Awesome");
+        let store_v1 = temp.path().join("store-v1");
+        plugin(&installed, "v1");
+        plugin(&store_v1, "v1");
+
+        record_installed(&project, "Awesome", "sha256:aaa");
+        assert!(stash(&project, "Awesome", &installed, "sha256:bbb"));
+        let saved = saved_dir(&project, "Awesome", "sha256:aaa");
+        fs::write(saved.join("Source").join("Awesome.cpp"), "edited").unwrap();
+
+        assert!(!restore(
+            &project,
+            "Awesome",
+            "sha256:aaa",
+            &store_v1,
+            &installed
+        ));
+        assert!(!saved.exists());
+        assert!(!installed.exists());
+    }
+
+    #[test]
+    fn test_stash_keeps_latest_versions() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("Game");
+        let installed = project.join("Plugins").join("Awesome");
+
+        for checksum in ["sha256:aaa", "sha256:bbb", "sha256:ccc"] {
+            plugin(&installed, checksum);
+            record_installed(&project, "Awesome", checksum);
+            assert!(stash(&project, "Awesome", &installed, "sha256:new"));
+        }
+
+        assert_eq!(keep(), DEFAULT_SAVED_VERSIONS);
+        assert!(!saved_dir(&project, "Awesome", "sha256:aaa").exists());
+        assert!(saved_dir(&project, "Awesome", "sha256:bbb").exists());
+        assert!(saved_dir(&project, "Awesome", "sha256:ccc").exists());
+        let index = SavedIndex::load(&project);
+        assert_eq!(index.saved["Awesome"], vec!["sha256:bbb", "sha256:ccc"]);
+        assert!(index.installed.is_empty());
+    }
+}
@@ -0,0 +1,617 @@
+//! Software bills of materials (CycloneDX and SPDX)
+//!
+//! Publishers increasingly ask studios for an SBOM with every build they
+//! deliver. `unrealpm sbom` builds one from unrealpm.lock: every locked
+//! package with its exact version, SHA-256 checksum, license, and
+//! dependencies, plus what the registry knows about it (description,
+//! download URL, compatible engines, and the publisher's Ed25519 signature).
+//! The resolved engine version is recorded for the project and for each
+//! pre-built binary.
+//!
+//! [`Sbom::to_cyclonedx`] writes CycloneDX 1.5 JSON and [`Sbom::to_spdx`]
+//! writes SPDX 2.3 JSON. Packages are identified by a package URL such as
+//! `pkg:unrealpm/%40studio/core@1.2.0`.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::sbom::Sbom;
+//! use unrealpm::Lockfile;
+//!
+//! let mut lockfile = Lockfile::new();
+//! lockfile.update_package(
+//!     "awesome-plugin".to_string(),
+//!     "1.2.0".to_string(),
+//!     "9f86d081884c7d65".to_string(),
+//!     None,
+//! );
+//!
+//! let sbom = Sbom::from_lockfile("MyGame", &lockfile);
+//! let bom = sbom.to_cyclonedx();
+//! assert_eq!(bom["bomFormat"], "CycloneDX");
+//! assert_eq!(bom["components"][0]["purl"], "pkg:unrealpm/awesome-plugin@1.2.0");
+//! assert_eq!(sbom.to_spdx()["spdxVersion"], "SPDX-2.3");
+//! ```
+
+use crate::Lockfile;
+use serde_json::{json, Value};
+
+/// CycloneDX specification version written by [`Sbom::to_cyclonedx`]
+pub const CYCLONEDX_SPEC_VERSION: &str = "1.5";
+
+/// SPDX specification version written by [`Sbom::to_spdx`]
+pub const SPDX_VERSION: &str = "SPDX-2.3";
+
+/// Publisher signature of a package version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageSignature {
+    /// Hex-encoded Ed25519 signature of the tarball
+    pub signature: String,
+    /// Hex-encoded Ed25519 public key of the publisher
+    pub public_key: String,
+    /// When the package was signed (ISO 8601)
+    pub signed_at: Option<String>,
+}
+
+/// One locked package in an [`Sbom`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SbomPackage {
+    pub name: String,
+    pub version: String,
+    /// Lowercase hex SHA-256 of the installed tarball; `None` for packages
+    /// without one (linked and externally managed plugins)
+    pub checksum: Option<String>,
+    /// SPDX license expression recorded at install
+    pub license: Option<String>,
+    pub description: Option<String>,
+    /// Where the tarball was downloaded from, if the registry serves it
+    /// from a URL
+    pub download_url: Option<String>,
+    /// Lockfile source of packages not installed from the registry
+    pub source: Option<String>,
+    /// Engine version the package was resolved for
+    pub engine: Option<String>,
+    /// Engines the registry lists the version as compatible with
+    pub engine_versions: Vec<String>,
+    /// Platform of the pre-built binary installed instead of the source
+    pub binary_platform: Option<String>,
+    pub signature: Option<PackageSignature>,
+    /// Names of the locked packages this one depends on, sorted
+    pub dependencies: Vec<String>,
+    /// Only needed by development dependencies
+    pub dev: bool,
+}
+
+impl SbomPackage {
+    /// Package URL, e.g. `pkg:unrealpm/%40studio/core@1.2.0`
+    pub fn purl(&self) -> String {
+        format!(
+            "pkg:unrealpm/{}@{}",
+            self.name.replace('@', "%40"),
+            self.version
+        )
+    }
+
+    /// Identifier of the package within an SPDX document
+    fn spdx_id(&self) -> String {
+        format!("SPDXRef-Package-{}", spdx_id_part(&self.name))
+    }
+}
+
+/// Bill of materials for one project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sbom {
+    /// Project name (.uproject file stem)
+    pub project: String,
+    /// Project version from unrealpm.json, if it has one
+    pub project_version: Option<String>,
+    /// Engine version the lockfile was resolved for
+    pub engine: Option<String>,
+    /// When the SBOM was created (RFC 3339)
+    pub created_at: String,
+    /// Dependencies the project itself declares; all packages nothing else
+    /// depends on when empty
+    pub direct: Vec<String>,
+    /// Locked packages, sorted by name
+    pub packages: Vec<SbomPackage>,
+}
+
+impl Sbom {
+    /// SBOM of everything in `lockfile`, without registry metadata
+    pub fn from_lockfile(project: &str, lockfile: &Lockfile) -> Self {
+        let engine = lockfile.metadata.engine.clone();
+        let mut packages: Vec<SbomPackage> = lockfile
+            .packages
+            .iter()
+            .map(|(name, locked)| {
+                let checksum = locked.artifact_checksum();
+                let mut dependencies: Vec<String> = locked
+                    .dependencies
+                    .iter()
+                    .flatten()
+                    .map(|(dep, _)| dep.clone())
+                    .filter(|dep| lockfile.packages.contains_key(dep))
+                    .collect();
+                dependencies.sort();
+                SbomPackage {
+                    name: name.clone(),
+                    version: locked.version.clone(),
+                    checksum: (!checksum.is_empty())
+                        .then(|| checksum.trim_start_matches("sha256:").to_lowercase()),
+                    license: locked.license.clone(),
+                    source: locked.source.clone(),
+                    engine: locked
+                        .binary
+                        .as_ref()
+                        .map(|binary| binary.engine.clone())
+                        .or_else(|| engine.clone()),
+                    binary_platform: locked.binary.as_ref().map(|b| b.platform.clone()),
+                    dependencies,
+                    dev: locked.dev,
+                    ..SbomPackage::default()
+                }
+            })
+            .collect();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            project: project.to_string(),
+            project_version: None,
+            engine,
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            direct: Vec::new(),
+            packages,
+        }
+    }
+
+    /// Packages the project depends on directly
+    fn direct_dependencies(&self) -> Vec<&SbomPackage> {
+        self.packages
+            .iter()
+            .filter(|package| {
+                if self.direct.is_empty() {
+                    !self
+                        .packages
+                        .iter()
+                        .any(|other| other.dependencies.contains(&package.name))
+                } else {
+                    self.direct.contains(&package.name)
+                }
+            })
+            .collect()
+    }
+
+    fn tool(&self) -> String {
+        format!("unrealpm-{}", env!("CARGO_PKG_VERSION"))
+    }
+
+    /// CycloneDX 1.5 JSON document
+    pub fn to_cyclonedx(&self) -> Value {
+        let project_ref = format!("project:{}", self.project);
+        let mut project = json!({
+            "type": "application",
+            "bom-ref": project_ref,
+            "name": self.project,
+        });
+        if let Some(version) = &self.project_version {
+            project["version"] = json!(version);
+        }
+
+        let mut metadata = json!({
+            "timestamp": self.created_at,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "unrealpm",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }]
+            },
+            "component": project,
+        });
+        if let Some(engine) = &self.engine {
+            metadata["properties"] = json!([{ "name": "unrealpm:engine", "value": engine }]);
+        }
+
+        let components: Vec<Value> = self.packages.iter().map(cyclonedx_component).collect();
+
+        let mut dependencies = vec![json!({
+            "ref": project_ref,
+            "dependsOn": self
+                .direct_dependencies()
+                .iter()
+                .map(|package| package.purl())
+                .collect::<Vec<_>>(),
+        })];
+        for package in &self.packages {
+            dependencies.push(json!({
+                "ref": package.purl(),
+                "dependsOn": self.purls(&package.dependencies),
+            }));
+        }
+
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": CYCLONEDX_SPEC_VERSION,
+            "serialNumber": format!("urn:uuid:{}", uuid_v4()),
+            "version": 1,
+            "metadata": metadata,
+            "components": components,
+            "dependencies": dependencies,
+        })
+    }
+
+    /// SPDX 2.3 JSON document
+    pub fn to_spdx(&self) -> Value {
+        let project_id = format!("SPDXRef-Project-{}", spdx_id_part(&self.project));
+        let creator = format!("Tool: {}", self.tool());
+
+        let mut packages = vec![json!({
+            "name": self.project,
+            "SPDXID": project_id,
+            "versionInfo": self.project_version.as_deref().unwrap_or("NOASSERTION"),
+            "downloadLocation": "NOASSERTION",
+            "filesAnalyzed": false,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": "NOASSERTION",
+            "copyrightText": "NOASSERTION",
+            "primaryPackagePurpose": "APPLICATION",
+        })];
+        if let Some(engine) = &self.engine {
+            packages[0]["comment"] = json!(format!("Resolved for Unreal Engine {}", engine));
+        }
+        for package in &self.packages {
+            packages.push(self.spdx_package(package, &creator));
+        }
+
+        let mut relationships = vec![json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": project_id,
+        })];
+        for package in self.direct_dependencies() {
+            let relationship = if package.dev {
+                "DEV_DEPENDENCY_OF"
+            } else {
+                "DEPENDS_ON"
+            };
+            relationships.push(if package.dev {
+                json!({
+                    "spdxElementId": package.spdx_id(),
+                    "relationshipType": relationship,
+                    "relatedSpdxElement": project_id,
+                })
+            } else {
+                json!({
+                    "spdxElementId": project_id,
+                    "relationshipType": relationship,
+                    "relatedSpdxElement": package.spdx_id(),
+                })
+            });
+        }
+        for package in &self.packages {
+            for dependency in self
+                .packages
+                .iter()
+                .filter(|other| package.dependencies.contains(&other.name))
+            {
+                relationships.push(json!({
+                    "spdxElementId": package.spdx_id(),
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": dependency.spdx_id(),
+                }));
+            }
+        }
+
+        json!({
+            "spdxVersion": SPDX_VERSION,
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": format!("{}-sbom", self.project),
+            "documentNamespace": format!(
+                "https://spdx.org/spdxdocs/{}-{}",
+                spdx_id_part(&self.project),
+                uuid_v4()
+            ),
+            "creationInfo": {
+                "created": self.created_at,
+                "creators": [creator],
+            },
+            "packages": packages,
+            "relationships": relationships,
+        })
+    }
+
+    fn spdx_package(&self, package: &SbomPackage, creator: &str) -> Value {
+        let license = package.license.as_deref().unwrap_or("NOASSERTION");
+        let mut value = json!({
+            "name": package.name,
+            "SPDXID": package.spdx_id(),
+            "versionInfo": package.version,
+            "downloadLocation": package.download_url.as_deref().unwrap_or("NOASSERTION"),
+            "filesAnalyzed": false,
+            "licenseConcluded": license,
+            "licenseDeclared": license,
+            "copyrightText": "NOASSERTION",
+            "primaryPackagePurpose": "LIBRARY",
+            "externalRefs": [{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": package.purl(),
+            }],
+        });
+        if let Some(checksum) = &package.checksum {
+            value["checksums"] = json!([{ "algorithm": "SHA256", "checksumValue": checksum }]);
+        }
+        if let Some(description) = &package.description {
+            value["description"] = json!(description);
+        }
+        if let Some(engine) = engine_note(package) {
+            value["comment"] = json!(engine);
+        }
+        if let Some(signature) = &package.signature {
+            value["annotations"] = json!([{
+                "annotationDate": signature.signed_at.as_deref().unwrap_or(&self.created_at),
+                "annotationType": "OTHER",
+                "annotator": creator,
+                "comment": format!(
+                    "Ed25519 signature {} by public key {}",
+                    signature.signature, signature.public_key
+                ),
+            }]);
+        }
+        value
+    }
+
+    fn purls(&self, names: &[String]) -> Vec<String> {
+        self.packages
+            .iter()
+            .filter(|package| names.contains(&package.name))
+            .map(SbomPackage::purl)
+            .collect()
+    }
+}
+
+fn cyclonedx_component(package: &SbomPackage) -> Value {
+    let mut component = json!({
+        "type": "library",
+        "bom-ref": package.purl(),
+        "name": package.name,
+        "version": package.version,
+        "purl": package.purl(),
+        "scope": if package.dev { "optional" } else { "required" },
+    });
+    if let Some(scope) = crate::scope::scope(&package.name) {
+        component["group"] = json!(scope);
+        component["name"] = json!(crate::scope::unscoped(&package.name));
+    }
+    if let Some(description) = &package.description {
+        component["description"] = json!(description);
+    }
+    if let Some(checksum) = &package.checksum {
+        component["hashes"] = json!([{ "alg": "SHA-256", "content": checksum }]);
+    }
+    if let Some(license) = &package.license {
+        component["licenses"] = json!([{ "expression": license }]);
+    }
+    if let Some(url) = &package.download_url {
+        component["externalReferences"] = json!([{ "type": "distribution", "url": url }]);
+    }
+
+    let mut properties = Vec::new();
+    let mut property = |name: &str, value: &str| {
+        properties.push(json!({ "name": format!("unrealpm:{}", name), "value": value }));
+    };
+    if let Some(engine) = &package.engine {
+        property("engine", engine);
+    }
+    if !package.engine_versions.is_empty() {
+        property("engine_versions", &package.engine_versions.join(", "));
+    }
+    if let Some(platform) = &package.binary_platform {
+        property("binary_platform", platform);
+    }
+    if let Some(source) = &package.source {
+        property("source", source);
+    }
+    if let Some(signature) = &package.signature {
+        property("signature", &signature.signature);
+        property("public_key", &signature.public_key);
+        if let Some(signed_at) = &signature.signed_at {
+            property("signed_at", signed_at);
+        }
+    }
+    if !properties.is_empty() {
+        component["properties"] = json!(properties);
+    }
+    component
+}
+
+/// Engine details of a package for an SPDX comment
+fn engine_note(package: &SbomPackage) -> Option<String> {
+    let mut notes = Vec::new();
+    match (&package.engine, &package.binary_platform) {
+        (Some(engine), Some(platform)) => notes.push(format!(
+            "Pre-built {} binary for Unreal Engine {}",
+            platform, engine
+        )),
+        (Some(engine), None) => notes.push(format!("Resolved for Unreal Engine {}", engine)),
+        _ => {}
+    }
+    if !package.engine_versions.is_empty() {
+        notes.push(format!(
+            "compatible with Unreal Engine {}",
+            package.engine_versions.join(", ")
+        ));
+    }
+    (!notes.is_empty()).then(|| notes.join("; "))
+}
+
+/// `name` with every character SPDX identifiers don't allow replaced by `-`
+fn spdx_id_part(name: &str) -> String {
+    name.trim_start_matches('@')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Random (version 4) UUID for document serial numbers
+fn uuid_v4() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrebuiltBinary;
+    use std::collections::HashMap;
+
+    fn sample() -> Sbom {
+        let mut lockfile = Lockfile::new();
+        lockfile.metadata.engine = Some("5.4".to_string());
+        lockfile.update_package(
+            "@studio/core".to_string(),
+            "1.0.0".to_string(),
+            "sha256:AAAA".to_string(),
+            None,
+        );
+        lockfile.update_package(
+            "ui-kit".to_string(),
+            "2.1.0".to_string(),
+            "bbbb".to_string(),
+            Some(HashMap::from([(
+                "@studio/core".to_string(),
+                "^1.0".to_string(),
+            )])),
+        );
+        let ui_kit = lockfile.packages.get_mut("ui-kit").unwrap();
+        ui_kit.license = Some("MIT".to_string());
+        ui_kit.binary = Some(PrebuiltBinary {
+            platform: "Win64".to_string(),
+            engine: "5.4".to_string(),
+            tarball: "ui-kit-2.1.0-Win64.tar.gz".to_string(),
+            checksum: "cccc".to_string(),
+            configuration: None,
+        });
+
+        let mut sbom = Sbom::from_lockfile("My Game", &lockfile);
+        sbom.packages[0].signature = Some(PackageSignature {
+            signature: "5151".to_string(),
+            public_key: "abab".to_string(),
+            signed_at: Some("2026-01-01T00:00:00Z".to_string()),
+        });
+        sbom
+    }
+
+    #[test]
+    fn test_from_lockfile() {
+        let sbom = sample();
+        let names: Vec<&str> = sbom.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["@studio/core", "ui-kit"]);
+
+        let core = &sbom.packages[0];
+        assert_eq!(core.checksum.as_deref(), Some("aaaa"));
+        assert_eq!(core.engine.as_deref(), Some("5.4"));
+        assert_eq!(core.purl(), "pkg:unrealpm/%40studio/core@1.0.0");
+
+        // Binaries are identified by the tarball that was installed
+        let ui_kit = &sbom.packages[1];
+        assert_eq!(ui_kit.checksum.as_deref(), Some("cccc"));
+        assert_eq!(ui_kit.binary_platform.as_deref(), Some("Win64"));
+        assert_eq!(ui_kit.dependencies, vec!["@studio/core"]);
+
+        let direct: Vec<&str> = sbom
+            .direct_dependencies()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(direct, vec!["ui-kit"]);
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let bom = sample().to_cyclonedx();
+        assert_eq!(bom["specVersion"], CYCLONEDX_SPEC_VERSION);
+        assert!(bom["serialNumber"]
+            .as_str()
+            .unwrap()
+            .starts_with("urn:uuid:"));
+
+        let core = &bom["components"][0];
+        assert_eq!(core["group"], "@studio");
+        assert_eq!(core["name"], "core");
+        assert_eq!(core["hashes"][0]["content"], "aaaa");
+        assert!(core["properties"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({ "name": "unrealpm:signature", "value": "5151" })));
+        assert_eq!(bom["components"][1]["licenses"][0]["expression"], "MIT");
+
+        assert_eq!(
+            bom["dependencies"][0]["dependsOn"],
+            json!(["pkg:unrealpm/ui-kit@2.1.0"])
+        );
+        assert_eq!(
+            bom["dependencies"][2]["dependsOn"],
+            json!(["pkg:unrealpm/%40studio/core@1.0.0"])
+        );
+    }
+
+    #[test]
+    fn test_spdx() {
+        let doc = sample().to_spdx();
+        assert_eq!(doc["packages"][0]["SPDXID"], "SPDXRef-Project-My-Game");
+
+        let core = &doc["packages"][1];
+        assert_eq!(core["SPDXID"], "SPDXRef-Package-studio-core");
+        assert_eq!(core["checksums"][0]["checksumValue"], "aaaa");
+        assert_eq!(core["licenseDeclared"], "NOASSERTION");
+        assert_eq!(
+            core["annotations"][0]["annotationDate"],
+            "2026-01-01T00:00:00Z"
+        );
+
+        let ui_kit = &doc["packages"][2];
+        assert_eq!(ui_kit["licenseDeclared"], "MIT");
+        assert_eq!(
+            ui_kit["comment"],
+            "Pre-built Win64 binary for Unreal Engine 5.4"
+        );
+
+        let relationships: Vec<(&str, &str)> = doc["relationships"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                (
+                    r["spdxElementId"].as_str().unwrap(),
+                    r["relatedSpdxElement"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            relationships,
+            vec![
+                ("SPDXRef-DOCUMENT", "SPDXRef-Project-My-Game"),
+                ("SPDXRef-Project-My-Game", "SPDXRef-Package-ui-kit"),
+                ("SPDXRef-Package-ui-kit", "SPDXRef-Package-studio-core"),
+            ]
+        );
+    }
+}
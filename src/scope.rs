@@ -0,0 +1,160 @@
+//! Scoped package names (`@studio/plugin-name`)
+//!
+//! A scope groups one studio's or organization's packages, as in npm. Only
+//! accounts the registry lists as members of a scope can publish under it;
+//! a plugin is published under a scope by setting its `name` in
+//! unrealpm.json to `@scope/<PluginName>`.
+//!
+//! The part after the scope is the plugin's name in the engine, so scoped
+//! packages are installed in `Plugins/<PluginName>/`. In registry URLs and
+//! file names (download cache, file registries) the `/` is encoded as `%2F`.
+//!
+//! `[scopes]` in the user config sends all of a scope's packages to another
+//! registry:
+//!
+//! ```toml
+//! [scopes]
+//! "@mystudio" = "https://registry.mystudio.internal"
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::scope;
+//!
+//! assert_eq!(scope::split_spec("@studio/core@^1.2"), ("@studio/core", Some("^1.2")));
+//! assert_eq!(scope::scope("@studio/core"), Some("@studio"));
+//! assert_eq!(scope::unscoped("@studio/core"), "core");
+//! assert_eq!(scope::encode("@studio/core"), "@studio%2Fcore");
+//! ```
+
+use crate::{Error, Result};
+use std::borrow::Cow;
+
+/// Split `name@version` into the name and the version, if any
+///
+/// A leading `@` starts a scope, not a version.
+pub fn split_spec(spec: &str) -> (&str, Option<&str>) {
+    match spec.get(1..).and_then(|rest| rest.find('@')) {
+        Some(pos) => (&spec[..=pos], Some(&spec[pos + 2..])),
+        None => (spec, None),
+    }
+}
+
+/// Scope of a package name including its `@`, e.g. `@studio`
+pub fn scope(name: &str) -> Option<&str> {
+    if !name.starts_with('@') {
+        return None;
+    }
+    name.split_once('/').map(|(scope, _)| scope)
+}
+
+/// Name without its scope: the plugin's name in the engine
+pub fn unscoped(name: &str) -> &str {
+    match scope(name) {
+        Some(scope) => &name[scope.len() + 1..],
+        None => name,
+    }
+}
+
+/// Form of `name` usable as a URL path segment or a file name
+pub fn encode(name: &str) -> Cow<'_, str> {
+    if name.contains('/') {
+        Cow::Owned(name.replace('/', "%2F"))
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// Name a file or URL path segment made with [`encode`] stands for
+pub fn decode(encoded: &str) -> String {
+    encoded.replace("%2F", "/").replace("%2f", "/")
+}
+
+/// `@scope` as written in a scope, with or without the `@`
+pub fn normalize(scope: &str) -> String {
+    format!("@{}", scope.trim_start_matches('@'))
+}
+
+/// Check that `name` is a plain name or `@scope/name`
+///
+/// Scopes are lower case letters, digits, `-`, and `_`. Neither part may
+/// contain another `/` or `@`.
+pub fn validate(name: &str) -> Result<()> {
+    let invalid = |reason: &str| {
+        Err(Error::Other(format!(
+            "Invalid package name '{}': {}",
+            name, reason
+        )))
+    };
+    let bare = match name.strip_prefix('@') {
+        Some(scoped) => {
+            let Some((scope, bare)) = scoped.split_once('/') else {
+                return invalid("a scoped name looks like @scope/name");
+            };
+            let scope_char =
+                |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_".contains(c);
+            if scope.is_empty() || !scope.chars().all(scope_char) {
+                return invalid("scopes use lower case letters, digits, '-' and '_'");
+            }
+            bare
+        }
+        None => name,
+    };
+    if bare.is_empty() {
+        return invalid("the name is empty");
+    }
+    if bare.contains(['/', '\\', '@']) {
+        return invalid("names can't contain '/', '\\' or '@' (besides a leading @scope/)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_spec() {
+        assert_eq!(split_spec("awesome"), ("awesome", None));
+        assert_eq!(split_spec("awesome@1.0.0"), ("awesome", Some("1.0.0")));
+        assert_eq!(split_spec("@studio/awesome"), ("@studio/awesome", None));
+        assert_eq!(
+            split_spec("@studio/awesome@>=1.0"),
+            ("@studio/awesome", Some(">=1.0"))
+        );
+        assert_eq!(split_spec("awesome@"), ("awesome", Some("")));
+    }
+
+    #[test]
+    fn test_scope_parts() {
+        assert_eq!(scope("awesome"), None);
+        assert_eq!(scope("@studio/awesome"), Some("@studio"));
+        assert_eq!(unscoped("@studio/awesome"), "awesome");
+        assert_eq!(unscoped("awesome"), "awesome");
+        assert_eq!(encode("awesome"), "awesome");
+        assert_eq!(decode(&encode("@studio/awesome")), "@studio/awesome");
+        assert_eq!(normalize("studio"), "@studio");
+        assert_eq!(normalize("@studio"), "@studio");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("awesome-plugin").is_ok());
+        assert!(validate("ChromaSense").is_ok());
+        assert!(validate("@my-studio/ChromaSense").is_ok());
+
+        for name in [
+            "",
+            "@studio",
+            "@/awesome",
+            "@Studio/awesome",
+            "@studio/",
+            "@studio/a/b",
+            "../awesome",
+            "awesome@1.0",
+        ] {
+            assert!(validate(name).is_err(), "{} should be invalid", name);
+        }
+    }
+}
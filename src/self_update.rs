@@ -0,0 +1,383 @@
+//! Updating the unrealpm binary itself
+//!
+//! Releases are described by a small JSON document per channel, served at
+//! `<releases url>/<channel>` (by default under the registry, at
+//! `/api/v1/cli/releases/stable` and `/api/v1/cli/releases/beta`):
+//!
+//! ```json
+//! {
+//!   "version": "0.5.0",
+//!   "notes": "https://unrealpm.dev/changelog#0.5.0",
+//!   "assets": [
+//!     {
+//!       "platform": "Win64",
+//!       "url": "https://downloads.unrealpm.dev/0.5.0/unrealpm.exe",
+//!       "checksum": "<sha256 of the binary>",
+//!       "signature": "<hex Ed25519 signature of the binary>"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Downloads are checked against the checksum and, when a release key is
+//! known (`[self_update] public_key`, or built in through the
+//! `UNREALPM_RELEASE_PUBLIC_KEY` build-time variable), the signature. The
+//! new binary is written next to the current one and swapped in with a
+//! rename, so an interrupted update leaves the old binary in place. Windows
+//! can't overwrite a running executable, but it can rename one: the old
+//! binary is moved aside to `<name>.old` and removed on the next update.
+//!
+//! Installs managed by an installer (MSI, winget, a package manager) carry
+//! a `unrealpm.managed` file next to the binary; self-update refuses to
+//! touch those and points at the installer instead.
+
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Marker file that hands updates over to an installer
+pub const MANAGED_MARKER: &str = "unrealpm.managed";
+
+/// Release key compiled into official builds, if any
+pub const BUILTIN_PUBLIC_KEY: Option<&str> = option_env!("UNREALPM_RELEASE_PUBLIC_KEY");
+
+/// Release track to follow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseChannel {
+    #[default]
+    Stable,
+    /// Pre-releases, published ahead of stable
+    Beta,
+}
+
+impl std::str::FromStr for ReleaseChannel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            _ => Err(Error::Other(format!(
+                "Invalid release channel '{}'. Use: stable, beta",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for ReleaseChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Stable => "stable",
+            Self::Beta => "beta",
+        })
+    }
+}
+
+/// Latest release on a channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Release {
+    pub version: String,
+
+    /// Release notes URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// Binary for one platform
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    /// Platform name as returned by [`crate::platform::detect_platform`]
+    pub platform: String,
+    pub url: String,
+    /// SHA-256 of the binary (hex, optionally prefixed with `sha256:`)
+    pub checksum: String,
+    /// Hex Ed25519 signature of the binary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl Release {
+    /// Binary for `platform` (case-insensitive)
+    pub fn asset_for(&self, platform: &str) -> Option<&ReleaseAsset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.platform.eq_ignore_ascii_case(platform))
+    }
+
+    /// Whether this release is newer than `current`
+    ///
+    /// Versions that don't parse are compared as unequal strings, so a
+    /// release is only offered when it differs.
+    pub fn is_newer_than(&self, current: &str) -> bool {
+        match (
+            semver::Version::parse(&self.version),
+            semver::Version::parse(current),
+        ) {
+            (Ok(release), Ok(current)) => release > current,
+            _ => self.version != current,
+        }
+    }
+}
+
+/// URL of the release document for `channel`
+pub fn release_url(base: &str, channel: ReleaseChannel) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), channel)
+}
+
+/// Fetch the latest release on a channel
+pub fn fetch_release(client: &reqwest::blocking::Client, url: &str) -> Result<Release> {
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|e| Error::Other(format!("Failed to check for updates: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Other(format!(
+            "Failed to check for updates: HTTP {} from {}",
+            response.status(),
+            url
+        )));
+    }
+    response
+        .json()
+        .map_err(|e| Error::Other(format!("Invalid release information from {}: {}", url, e)))
+}
+
+/// Download a release binary and check it
+pub fn download_asset(
+    client: &reqwest::blocking::Client,
+    asset: &ReleaseAsset,
+    public_key: Option<&str>,
+) -> Result<Vec<u8>> {
+    let response = client
+        .get(&asset.url)
+        .send()
+        .map_err(|e| Error::Other(format!("Failed to download {}: {}", asset.url, e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Other(format!(
+            "Failed to download {}: HTTP {}",
+            asset.url,
+            response.status()
+        )));
+    }
+    let bytes = response.bytes()?.to_vec();
+    verify_asset(&bytes, asset, public_key)?;
+    Ok(bytes)
+}
+
+/// Check downloaded bytes against the asset's checksum and signature
+///
+/// With a `public_key`, the asset must be signed by it. Without one only
+/// the checksum is checked.
+pub fn verify_asset(bytes: &[u8], asset: &ReleaseAsset, public_key: Option<&str>) -> Result<()> {
+    let expected = asset.checksum.trim_start_matches("sha256:");
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(Error::ChecksumMismatch {
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+
+    let Some(public_key) = public_key else {
+        return Ok(());
+    };
+    let signature = asset.signature.as_deref().ok_or_else(|| {
+        Error::SignatureVerificationFailed(format!(
+            "Release binary for {} is not signed; refusing to install it",
+            asset.platform
+        ))
+    })?;
+    let signature = hex::decode(signature.trim())
+        .map_err(|e| Error::Other(format!("Invalid release signature: {}", e)))?;
+    let valid = crate::signing::verify_signature(bytes, &signature, public_key)
+        .map_err(|e| Error::Other(format!("Could not verify release signature: {}", e)))?;
+    if !valid {
+        return Err(Error::SignatureVerificationFailed(format!(
+            "Release signature for {} does not match the release key",
+            asset.platform
+        )));
+    }
+    Ok(())
+}
+
+/// Who manages this install, if an installer left a [`MANAGED_MARKER`]
+///
+/// Returns the marker's contents (e.g. "the UnrealPM MSI installer"), or a
+/// generic description when it's empty.
+pub fn managed_by(exe: &Path) -> Option<String> {
+    let marker = exe.parent()?.join(MANAGED_MARKER);
+    let contents = fs::read_to_string(marker).ok()?;
+    let contents = contents.trim();
+    Some(if contents.is_empty() {
+        "an installer".to_string()
+    } else {
+        contents.to_string()
+    })
+}
+
+/// Swap `exe` for `new_binary`
+///
+/// The new binary is staged next to `exe` and renamed over it, so `exe` is
+/// never half-written. On Windows the running binary is first renamed aside
+/// (see [`cleanup_previous`]).
+pub fn replace_executable(exe: &Path, new_binary: &[u8]) -> Result<()> {
+    let dir = exe
+        .parent()
+        .ok_or_else(|| Error::Other(format!("Cannot update {}", exe.display())))?;
+    let name = file_name(exe)?;
+    let staged = dir.join(format!(".{}.new", name));
+
+    fs::write(&staged, new_binary).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            Error::Other(format!(
+                "No permission to write to {}\n\n\
+                 unrealpm appears to be installed in a protected location (e.g. by the MSI installer).\n\
+                 • Rerun from an elevated (administrator) prompt: unrealpm self-update\n\
+                 • Or update with the installer you used",
+                dir.display()
+            ))
+        } else {
+            Error::Io(e)
+        }
+    })?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(exe)
+            .map(|m| m.permissions().mode())
+            .unwrap_or(0o755);
+        fs::set_permissions(&staged, fs::Permissions::from_mode(mode | 0o111))?;
+    }
+
+    let swapped = swap_into_place(exe, &staged, dir, name);
+    if swapped.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    swapped
+}
+
+#[cfg(windows)]
+fn swap_into_place(exe: &Path, staged: &Path, dir: &Path, name: &str) -> Result<()> {
+    // A running executable can't be replaced, but it can be renamed. An
+    // older copy may still be running too, so fall back to a unique name.
+    let mut aside = dir.join(format!("{}.old", name));
+    if aside.exists() && fs::remove_file(&aside).is_err() {
+        aside = dir.join(format!("{}.{}.old", name, std::process::id()));
+    }
+    fs::rename(exe, &aside)?;
+    if let Err(e) = fs::rename(staged, exe) {
+        let _ = fs::rename(&aside, exe);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn swap_into_place(exe: &Path, staged: &Path, _dir: &Path, _name: &str) -> Result<()> {
+    fs::rename(staged, exe)?;
+    Ok(())
+}
+
+/// Remove binaries left behind by earlier updates
+///
+/// On Windows the replaced binary can't be deleted while it runs, so it is
+/// cleaned up the next time an update runs. Files still in use are skipped.
+pub fn cleanup_previous(exe: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Ok(name)) = (exe.parent(), file_name(exe)) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|f| f.to_str())
+                .is_some_and(|f| f.starts_with(name) && f.ends_with(".old"))
+        })
+        .filter(|path| fs::remove_file(path).is_ok())
+        .collect()
+}
+
+fn file_name(exe: &Path) -> Result<&str> {
+    exe.file_name()
+        .and_then(|f| f.to_str())
+        .ok_or_else(|| Error::Other(format!("Cannot update {}", exe.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(bytes: &[u8]) -> ReleaseAsset {
+        ReleaseAsset {
+            platform: "Linux".to_string(),
+            url: "https://example.com/unrealpm".to_string(),
+            checksum: format!("sha256:{:x}", Sha256::digest(bytes)),
+            signature: None,
+        }
+    }
+
+    #[test]
+    fn test_release_selection() {
+        let release: Release = serde_json::from_str(
+            r#"{"version": "0.5.0-beta.1", "assets": [
+                {"platform": "Win64", "url": "https://e/win", "checksum": "aa"},
+                {"platform": "Linux", "url": "https://e/linux", "checksum": "bb"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(release.asset_for("linux").unwrap().url, "https://e/linux");
+        assert!(release.asset_for("Mac").is_none());
+        assert!(release.is_newer_than("0.4.7"));
+        assert!(!release.is_newer_than("0.5.0"));
+        assert_eq!(
+            release_url("https://r/api/v1/cli/releases/", ReleaseChannel::Beta),
+            "https://r/api/v1/cli/releases/beta"
+        );
+    }
+
+    #[test]
+    fn test_verify_asset_checks_checksum_and_signature() {
+        let binary = b"new unrealpm binary";
+        let key = crate::signing::PackageSigningKey::generate().unwrap();
+        let mut signed = asset(binary);
+        signed.signature = Some(hex::encode(key.sign(binary).to_bytes()));
+
+        assert!(verify_asset(binary, &signed, Some(&key.public_key_hex())).is_ok());
+        assert!(verify_asset(binary, &asset(binary), None).is_ok());
+
+        // Tampered bytes, unsigned assets under a key, and foreign keys are rejected
+        assert!(verify_asset(b"tampered", &signed, None).is_err());
+        assert!(verify_asset(binary, &asset(binary), Some(&key.public_key_hex())).is_err());
+        let other = crate::signing::PackageSigningKey::generate().unwrap();
+        assert!(verify_asset(binary, &signed, Some(&other.public_key_hex())).is_err());
+    }
+
+    #[test]
+    fn test_replace_executable() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let exe = temp.path().join("unrealpm");
+        fs::write(&exe, b"old").unwrap();
+        fs::write(temp.path().join("unrealpm.old"), b"left over").unwrap();
+
+        replace_executable(&exe, b"new").unwrap();
+        assert_eq!(fs::read(&exe).unwrap(), b"new");
+        assert!(!temp.path().join(".unrealpm.new").exists());
+
+        assert_eq!(cleanup_previous(&exe).len(), 1);
+        assert!(managed_by(&exe).is_none());
+        fs::write(temp.path().join(MANAGED_MARKER), "").unwrap();
+        assert_eq!(managed_by(&exe).as_deref(), Some("an installer"));
+    }
+}
@@ -1,8 +1,25 @@
 use anyhow::{Context, Result};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use rand::rngs::OsRng;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use std::num::NonZeroU32;
 use std::path::Path;
 
+/// Name of the key kept at `signing.private_key_path`/`public_key_path`
+pub const DEFAULT_KEY_NAME: &str = "default";
+
+/// PEM tag of a passphrase-encrypted key from `unrealpm keys export`
+const EXPORT_PEM_TAG: &str = "UNREALPM ENCRYPTED SIGNING KEY";
+
+/// PBKDF2-HMAC-SHA256 rounds for export passphrases
+const EXPORT_KDF_ITERATIONS: u32 = 600_000;
+
+/// PBKDF2 rounds an imported key may ask for; fewer make the passphrase
+/// cheap to brute-force, more only stall the import
+const EXPORT_KDF_ITERATION_RANGE: std::ops::RangeInclusive<u32> = 100_000..=10_000_000;
+
+const EXPORT_SALT_LEN: usize = 16;
+
 /// Keypair for signing packages
 pub struct PackageSigningKey {
     signing_key: SigningKey,
@@ -114,6 +131,185 @@ impl PackageSigningKey {
     pub fn public_key_bytes(&self) -> [u8; 32] {
         self.verifying_key.to_bytes()
     }
+
+    /// Sign the statement that `new_public_key_hex` replaces this key
+    ///
+    /// Registries keep the endorsement with the new key, so anyone who
+    /// trusted this key can check the rotation came from its owner.
+    pub fn endorse_key(&self, new_public_key_hex: &str) -> Signature {
+        self.sign(&rotation_message(
+            &self.public_key_hex(),
+            new_public_key_hex,
+        ))
+    }
+
+    /// Encrypt the private key with a passphrase for moving it to another machine
+    ///
+    /// The key is sealed with ChaCha20-Poly1305 under a key derived from
+    /// the passphrase with PBKDF2-HMAC-SHA256. The key name travels in a PEM
+    /// header and is authenticated along with the key.
+    pub fn export_encrypted(&self, name: &str, passphrase: &str) -> Result<String> {
+        self.export_encrypted_with(name, passphrase, EXPORT_KDF_ITERATIONS)
+    }
+
+    fn export_encrypted_with(
+        &self,
+        name: &str,
+        passphrase: &str,
+        iterations: u32,
+    ) -> Result<String> {
+        validate_key_name(name)?;
+        let mut salt = [0u8; EXPORT_SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::RngCore::fill_bytes(&mut OsRng, &mut salt);
+        rand::RngCore::fill_bytes(&mut OsRng, &mut nonce);
+
+        let mut sealed = self.signing_key.to_bytes().to_vec();
+        export_cipher(passphrase, &salt, iterations)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(name.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt signing key"))?;
+
+        let mut contents = Vec::with_capacity(salt.len() + nonce.len() + sealed.len());
+        contents.extend_from_slice(&salt);
+        contents.extend_from_slice(&nonce);
+        contents.extend_from_slice(&sealed);
+        let mut pem = pem::Pem::new(EXPORT_PEM_TAG, contents);
+        pem.headers_mut().add("Key-Name", name)?;
+        pem.headers_mut()
+            .add("Iterations", &iterations.to_string())?;
+        pem.headers_mut()
+            .add("Public-Key", &self.public_key_hex())?;
+        Ok(pem::encode(&pem))
+    }
+
+    /// Decrypt a key written by [`PackageSigningKey::export_encrypted`]
+    ///
+    /// Returns the key's name along with the key.
+    pub fn import_encrypted(exported: &str, passphrase: &str) -> Result<(String, Self)> {
+        Self::import_encrypted_with(exported, passphrase, EXPORT_KDF_ITERATION_RANGE)
+    }
+
+    fn import_encrypted_with(
+        exported: &str,
+        passphrase: &str,
+        allowed_iterations: std::ops::RangeInclusive<u32>,
+    ) -> Result<(String, Self)> {
+        let pem = pem::parse(exported).context("Failed to parse exported key")?;
+        if pem.tag() != EXPORT_PEM_TAG {
+            anyhow::bail!(
+                "Not an exported unrealpm signing key (found a {} block)",
+                pem.tag()
+            );
+        }
+        let name = pem
+            .headers()
+            .get("Key-Name")
+            .context("Exported key has no Key-Name header")?
+            .to_string();
+        validate_key_name(&name)?;
+        let iterations: u32 = pem
+            .headers()
+            .get("Iterations")
+            .and_then(|i| i.parse().ok())
+            .context("Exported key has no valid Iterations header")?;
+        if !allowed_iterations.contains(&iterations) {
+            anyhow::bail!(
+                "Exported key asks for {} passphrase rounds; only {} to {} are accepted",
+                iterations,
+                allowed_iterations.start(),
+                allowed_iterations.end()
+            );
+        }
+
+        let contents = pem.contents();
+        if contents.len() != EXPORT_SALT_LEN + NONCE_LEN + 32 + CHACHA20_POLY1305.tag_len() {
+            anyhow::bail!("Exported key is truncated or corrupted");
+        }
+        let (salt, rest) = contents.split_at(EXPORT_SALT_LEN);
+        let (nonce, sealed) = rest.split_at(NONCE_LEN);
+        let mut sealed = sealed.to_vec();
+        let secret = export_cipher(passphrase, salt, iterations)?
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(nonce)
+                    .map_err(|_| anyhow::anyhow!("Invalid nonce in exported key"))?,
+                Aad::from(name.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| anyhow::anyhow!("Wrong passphrase, or the exported key was modified"))?;
+
+        let signing_key = SigningKey::from_bytes(
+            (&*secret)
+                .try_into()
+                .context("Failed to convert private key")?,
+        );
+        let verifying_key = signing_key.verifying_key();
+        Ok((
+            name,
+            Self {
+                signing_key,
+                verifying_key,
+            },
+        ))
+    }
+}
+
+/// Cipher for exported keys, keyed from `passphrase`
+fn export_cipher(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).context("Invalid KDF iteration count")?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &key)
+        .map_err(|_| anyhow::anyhow!("Failed to derive export key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Check that `name` can name a signing key (letters, digits, `-`, `_`)
+pub fn validate_key_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!(
+            "Invalid key name '{}': use letters, digits, '-' and '_' (at most 64)",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// The statement an old key signs to endorse its replacement
+pub fn rotation_message(old_public_key_hex: &str, new_public_key_hex: &str) -> Vec<u8> {
+    format!(
+        "unrealpm key rotation\nold: {}\nnew: {}\n",
+        old_public_key_hex.to_lowercase(),
+        new_public_key_hex.to_lowercase()
+    )
+    .into_bytes()
+}
+
+/// Whether `endorsement` is `old`'s signature endorsing `new` as its replacement
+pub fn verify_key_endorsement(
+    old_public_key_hex: &str,
+    new_public_key_hex: &str,
+    endorsement: &[u8],
+) -> Result<bool> {
+    verify_signature(
+        &rotation_message(old_public_key_hex, new_public_key_hex),
+        endorsement,
+        old_public_key_hex,
+    )
 }
 
 /// Verify a signature against data using a public key (hex-encoded)
@@ -158,6 +354,22 @@ pub fn verify_signature(data: &[u8], signature_bytes: &[u8], public_key_hex: &st
     }
 }
 
+/// Read a hex-encoded public key from a PEM file
+///
+/// Used to verify locally signed packages without loading the private key.
+pub fn load_public_key_hex(public_path: &Path) -> Result<String> {
+    let public_pem =
+        std::fs::read_to_string(public_path).context("Failed to read public key file")?;
+
+    let public_parsed = pem::parse(&public_pem).context("Failed to parse public key PEM")?;
+
+    if public_parsed.contents().len() != 32 {
+        anyhow::bail!("Invalid public key length (expected 32 bytes)");
+    }
+
+    Ok(hex::encode(public_parsed.contents()))
+}
+
 /// Load or generate signing keys
 ///
 /// If keys exist, load them. Otherwise, generate new keys and save them.
@@ -253,6 +465,71 @@ mod tests {
         assert_eq!(original_sig.to_bytes(), loaded_sig.to_bytes());
     }
 
+    #[test]
+    fn test_load_public_key_hex() {
+        let temp_dir = TempDir::new().unwrap();
+        let private_path = temp_dir.path().join("private.pem");
+        let public_path = temp_dir.path().join("public.pem");
+
+        let keys = PackageSigningKey::generate().unwrap();
+        keys.save_to_files(&private_path, &public_path).unwrap();
+
+        assert_eq!(
+            load_public_key_hex(&public_path).unwrap(),
+            keys.public_key_hex()
+        );
+    }
+
+    #[test]
+    fn test_export_and_import_encrypted() {
+        let keys = PackageSigningKey::generate().unwrap();
+        // Few KDF rounds keep the test fast; the count travels with the key
+        let exported = keys
+            .export_encrypted_with("laptop", "correct horse", 1_000)
+            .unwrap();
+        assert!(exported.contains("Key-Name: laptop"));
+        assert!(!exported.contains(&hex::encode(keys.signing_key.to_bytes())));
+
+        let import = |exported: &str, passphrase: &str| {
+            PackageSigningKey::import_encrypted_with(exported, passphrase, 1_000..=1_000)
+        };
+        let (name, imported) = import(&exported, "correct horse").unwrap();
+        assert_eq!(name, "laptop");
+        assert_eq!(imported.public_key_hex(), keys.public_key_hex());
+
+        assert!(import(&exported, "wrong").is_err());
+        // The name is authenticated with the key
+        let renamed = exported.replace("Key-Name: laptop", "Key-Name: other");
+        assert!(import(&renamed, "correct horse").is_err());
+        // Too few rounds to protect the passphrase
+        let Err(error) = PackageSigningKey::import_encrypted(&exported, "correct horse") else {
+            panic!("expected too few rounds to be refused");
+        };
+        assert!(error.to_string().contains("1000 passphrase rounds"));
+    }
+
+    #[test]
+    fn test_key_endorsement() {
+        let old = PackageSigningKey::generate().unwrap();
+        let new = PackageSigningKey::generate().unwrap();
+        let endorsement = old.endorse_key(&new.public_key_hex()).to_bytes();
+
+        assert!(
+            verify_key_endorsement(&old.public_key_hex(), &new.public_key_hex(), &endorsement)
+                .unwrap()
+        );
+        let other = PackageSigningKey::generate().unwrap();
+        assert!(!verify_key_endorsement(
+            &old.public_key_hex(),
+            &other.public_key_hex(),
+            &endorsement
+        )
+        .unwrap());
+        assert!(validate_key_name("ci-2026_a").is_ok());
+        assert!(validate_key_name("../x").is_err());
+        assert!(validate_key_name("").is_err());
+    }
+
     #[test]
     fn test_tampered_file_detected() {
         let keys = PackageSigningKey::generate().unwrap();
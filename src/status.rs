@@ -0,0 +1,399 @@
+//! Editor-visible status file
+//!
+//! After each operation that changes installed packages, UnrealPM writes
+//! `Plugins/.unrealpm-status.json` summarizing the managed plugins and any
+//! drift between `unrealpm.json`, `unrealpm.lock`, and what is on disk. A
+//! companion editor plugin can read it to show a "dependencies out of date"
+//! notification without running the CLI.
+//!
+//! # Schema (version 1)
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "unrealpm_version": "0.4.7",
+//!   "generated_at": "2025-01-01T12:00:00+00:00",
+//!   "operation": "install",
+//!   "engine_version": "5.4",
+//!   "up_to_date": false,
+//!   "plugins": [
+//!     {
+//!       "name": "awesome-plugin",
+//!       "constraint": "^1.2.0",
+//!       "locked_version": "1.2.0",
+//!       "installed_version": "1.2.0",
+//!       "latest_version": "1.3.0",
+//!       "state": "outdated"
+//!     },
+//!     {
+//!       "name": "FabTool",
+//!       "locked_version": "2.1",
+//!       "source": "fab+a1b2c3",
+//!       "state": "ok"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `schema_version` is bumped only for breaking changes; readers should ignore
+//! unknown fields and treat unknown `state` values as drift. Optional fields
+//! are omitted when unknown.
+
+use crate::{Lockfile, Manifest, Result, UPlugin, LOCKFILE_NAME};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The status filename, written inside the project's Plugins/ directory
+pub const STATUS_FILE_NAME: &str = ".unrealpm-status.json";
+
+/// Current status file schema version
+pub const STATUS_SCHEMA_VERSION: u32 = 1;
+
+/// Drift state of a single managed plugin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginState {
+    /// Installed and matching the lockfile
+    Ok,
+    /// A newer version matching the constraint is available
+    Outdated,
+    /// Declared in unrealpm.json but not in the lockfile
+    NotLocked,
+    /// Locked version no longer satisfies the unrealpm.json constraint
+    ConstraintMismatch,
+    /// Locked but missing from Plugins/
+    Missing,
+    /// Installed .uplugin version differs from the locked version
+    VersionMismatch,
+}
+
+impl PluginState {
+    /// True if the editor should prompt the user to run `unrealpm install`/`update`
+    pub fn is_drift(&self) -> bool {
+        *self != PluginState::Ok
+    }
+}
+
+/// Status of a single managed plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginStatus {
+    /// Package name
+    pub name: String,
+
+    /// Version constraint from unrealpm.json (absent for transitive or local packages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+
+    /// Version recorded in unrealpm.lock
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_version: Option<String>,
+
+    /// VersionName from the installed .uplugin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+
+    /// Lockfile source for packages not installed from the registry
+    /// (e.g., `path+../MyPlugin`, `fab+<id>` for externally managed plugins)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Latest matching registry version, if known from the last `outdated` check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+
+    /// Drift state
+    pub state: PluginState,
+}
+
+/// Contents of `Plugins/.unrealpm-status.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusFile {
+    /// Schema version (see [`STATUS_SCHEMA_VERSION`])
+    pub schema_version: u32,
+
+    /// Version of UnrealPM that wrote the file
+    pub unrealpm_version: String,
+
+    /// Timestamp when the file was written (ISO 8601 format)
+    pub generated_at: String,
+
+    /// Operation that produced the file (e.g., "install", "update")
+    pub operation: String,
+
+    /// Engine version from unrealpm.json
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_version: Option<String>,
+
+    /// True if no plugin has drifted
+    pub up_to_date: bool,
+
+    /// Managed plugins, sorted by name
+    pub plugins: Vec<PluginStatus>,
+}
+
+impl StatusFile {
+    /// Compute the status of a project from its manifest, lockfile, and Plugins/ directory
+    pub fn compute<P: AsRef<Path>>(project_dir: P, operation: &str) -> Result<Self> {
+        let project_dir = project_dir.as_ref();
+        let manifest = Manifest::load(project_dir)?;
+        let lockfile = Lockfile::load_from(project_dir.join(LOCKFILE_NAME))?.unwrap_or_default();
+
+        let mut names: Vec<&String> = manifest
+            .dependencies
+            .keys()
+            .chain(lockfile.packages.keys())
+            .collect();
+        names.sort();
+        names.dedup();
+
+        let plugins = names
+            .into_iter()
+            .map(|name| {
+                let constraint = manifest.dependencies.get(name).cloned();
+                let locked = lockfile.get_package(name);
+                let locked_version = locked.map(|p| p.version.clone());
+                let external = locked.is_some_and(|p| p.is_externally_managed());
+                let installed_version = locked
+                    .and_then(|p| {
+                        crate::installer::installed_plugin_dir(
+                            project_dir,
+                            name,
+                            p.artifact_checksum(),
+                        )
+                    })
+                    .and_then(|dir| UPlugin::find(dir).and_then(UPlugin::load).ok())
+                    .map(|u| u.version_name);
+
+                let state = match (&locked_version, &installed_version) {
+                    (None, _) => PluginState::NotLocked,
+                    (Some(locked), _) if !satisfies(constraint.as_deref(), locked) => {
+                        PluginState::ConstraintMismatch
+                    }
+                    (Some(_), None) if !external => PluginState::Missing,
+                    (Some(locked), Some(installed)) if locked != installed => {
+                        PluginState::VersionMismatch
+                    }
+                    _ => PluginState::Ok,
+                };
+
+                PluginStatus {
+                    name: name.clone(),
+                    constraint,
+                    locked_version,
+                    installed_version,
+                    source: locked.and_then(|p| p.source.clone()),
+                    latest_version: None,
+                    state,
+                }
+            })
+            .collect();
+
+        let mut status = Self {
+            schema_version: STATUS_SCHEMA_VERSION,
+            unrealpm_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            operation: operation.to_string(),
+            engine_version: manifest.engine_version.clone(),
+            up_to_date: true,
+            plugins,
+        };
+        status.refresh_up_to_date();
+        Ok(status)
+    }
+
+    /// Record the latest available version of a plugin
+    ///
+    /// Marks an otherwise healthy plugin as outdated if it is behind.
+    pub fn set_latest(&mut self, name: &str, latest: &str) {
+        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.name == name) {
+            plugin.latest_version = Some(latest.to_string());
+            if plugin.state == PluginState::Ok && plugin.locked_version.as_deref() != Some(latest) {
+                plugin.state = PluginState::Outdated;
+            }
+        }
+        self.refresh_up_to_date();
+    }
+
+    /// Load the status file from a project, if present
+    pub fn load<P: AsRef<Path>>(project_dir: P) -> Result<Option<Self>> {
+        let path = Self::path(project_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&contents)?))
+    }
+
+    /// Write the status file into the project's Plugins/ directory
+    pub fn save<P: AsRef<Path>>(&self, project_dir: P) -> Result<()> {
+        let path = Self::path(project_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Path of the status file for a project
+    pub fn path<P: AsRef<Path>>(project_dir: P) -> std::path::PathBuf {
+        crate::installer::plugins_dir(project_dir).join(STATUS_FILE_NAME)
+    }
+
+    fn refresh_up_to_date(&mut self) {
+        self.up_to_date = !self.plugins.iter().any(|p| p.state.is_drift());
+    }
+}
+
+/// True if `version` satisfies `constraint` (or either cannot be parsed)
+fn satisfies(constraint: Option<&str>, version: &str) -> bool {
+    let Some(constraint) = constraint else {
+        return true;
+    };
+    match (
+        semver::VersionReq::parse(constraint),
+        semver::Version::parse(version),
+    ) {
+        (Ok(req), Ok(ver)) => req.matches(&ver),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_plugin(project_dir: &Path, name: &str, version: &str) {
+        let dir = project_dir.join("Plugins").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(format!("{}.uplugin", name)),
+            format!(
+                r#"{{"FileVersion": 3, "Version": 1, "VersionName": "{}", "FriendlyName": "{}"}}"#,
+                version, name
+            ),
+        )
+        .unwrap();
+    }
+
+    fn setup_project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let mut manifest = Manifest::new();
+        manifest.engine_version = Some("5.4".to_string());
+        for (name, constraint) in [
+            ("healthy", "^1.0.0"),
+            ("missing", "^1.0.0"),
+            ("unlocked", "^1.0.0"),
+            ("stale", "^2.0.0"),
+            ("tampered", "^1.0.0"),
+        ] {
+            manifest
+                .dependencies
+                .insert(name.to_string(), constraint.to_string());
+        }
+        manifest.save(temp.path()).unwrap();
+
+        let mut lockfile = Lockfile::new();
+        for (name, version) in [
+            ("healthy", "1.0.0"),
+            ("missing", "1.0.0"),
+            ("stale", "1.5.0"),
+            ("tampered", "1.0.0"),
+            ("transitive", "0.3.0"),
+            ("fab-tool", "2.1"),
+        ] {
+            lockfile.update_package(
+                name.to_string(),
+                version.to_string(),
+                "abc".to_string(),
+                None,
+            );
+        }
+        lockfile.set_source("fab-tool", Some("fab+a1b2c3".to_string()));
+        lockfile.save_to(temp.path().join(LOCKFILE_NAME)).unwrap();
+
+        write_plugin(temp.path(), "healthy", "1.0.0");
+        write_plugin(temp.path(), "stale", "1.5.0");
+        write_plugin(temp.path(), "tampered", "1.0.1");
+        write_plugin(temp.path(), "transitive", "0.3.0");
+        temp
+    }
+
+    fn state_of(status: &StatusFile, name: &str) -> PluginState {
+        status
+            .plugins
+            .iter()
+            .find(|p| p.name == name)
+            .unwrap()
+            .state
+    }
+
+    #[test]
+    fn test_compute_detects_drift() {
+        let temp = setup_project();
+        let status = StatusFile::compute(temp.path(), "install").unwrap();
+
+        assert_eq!(status.schema_version, STATUS_SCHEMA_VERSION);
+        assert_eq!(status.engine_version.as_deref(), Some("5.4"));
+        assert_eq!(state_of(&status, "healthy"), PluginState::Ok);
+        assert_eq!(state_of(&status, "missing"), PluginState::Missing);
+        assert_eq!(state_of(&status, "unlocked"), PluginState::NotLocked);
+        assert_eq!(state_of(&status, "stale"), PluginState::ConstraintMismatch);
+        assert_eq!(state_of(&status, "tampered"), PluginState::VersionMismatch);
+        assert_eq!(state_of(&status, "transitive"), PluginState::Ok);
+        assert_eq!(state_of(&status, "fab-tool"), PluginState::Ok);
+        assert!(!status.up_to_date);
+
+        let names: Vec<_> = status.plugins.iter().map(|p| p.name.as_str()).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+    }
+
+    #[test]
+    fn test_set_latest_marks_outdated() {
+        let temp = TempDir::new().unwrap();
+        let mut manifest = Manifest::new();
+        manifest
+            .dependencies
+            .insert("healthy".to_string(), "^1.0.0".to_string());
+        manifest.save(temp.path()).unwrap();
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package(
+            "healthy".to_string(),
+            "1.0.0".to_string(),
+            "abc".to_string(),
+            None,
+        );
+        lockfile.save_to(temp.path().join(LOCKFILE_NAME)).unwrap();
+        write_plugin(temp.path(), "healthy", "1.0.0");
+
+        let mut status = StatusFile::compute(temp.path(), "outdated").unwrap();
+        assert!(status.up_to_date);
+
+        status.set_latest("healthy", "1.0.0");
+        assert!(status.up_to_date);
+
+        status.set_latest("healthy", "1.1.0");
+        assert_eq!(state_of(&status, "healthy"), PluginState::Outdated);
+        assert!(!status.up_to_date);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp = setup_project();
+        let status = StatusFile::compute(temp.path(), "update").unwrap();
+        status.save(temp.path()).unwrap();
+
+        assert!(temp.path().join("Plugins").join(STATUS_FILE_NAME).exists());
+
+        let loaded = StatusFile::load(temp.path()).unwrap().unwrap();
+        assert_eq!(loaded.operation, "update");
+        assert_eq!(loaded.plugins.len(), status.plugins.len());
+        assert_eq!(state_of(&loaded, "missing"), PluginState::Missing);
+
+        let raw = fs::read_to_string(StatusFile::path(temp.path())).unwrap();
+        assert!(raw.contains("\"state\": \"constraint_mismatch\""));
+    }
+}
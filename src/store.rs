@@ -0,0 +1,456 @@
+//! Garbage collection of the global package store
+//!
+//! Every project installs from the shared store in
+//! `~/.unrealpm/store/v1/packages/`, so no single project knows which store
+//! entries are still needed. Every command that loads a project's
+//! unrealpm.lock therefore registers the project in a machine-local index
+//! (`~/.unrealpm/projects.json`), and [`collect_garbage`] keeps exactly the
+//! entries some registered project's unrealpm.lock refers to. Projects that
+//! were deleted, or no longer have a lockfile, are dropped from the index.
+//!
+//! Installs and garbage collection coordinate through a lock file next to
+//! the store. An install holds a [`StoreLock::shared`] lock from the first
+//! package it takes from the store until the process exits, which covers
+//! writing its lockfile; `unrealpm store gc` takes the lock exclusively, so
+//! it waits for running installs, and installs started during a collection
+//! wait for it to finish.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use unrealpm::store::{collect_garbage, ProjectIndex, StoreLock};
+//! use unrealpm::{get_store_dir, DryRun};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let _lock = StoreLock::exclusive()?;
+//! let mut index = ProjectIndex::open_default()?;
+//! let report = collect_garbage(&get_store_dir()?, &mut index, DryRun::OFF)?;
+//! println!("Reclaimed {} bytes", report.reclaimed);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{get_store_dir, Config, DryRun, Error, Lockfile, Result, LOCKFILE_NAME};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once};
+
+/// File name of the project index, next to the user config
+pub const PROJECT_INDEX_FILE_NAME: &str = "projects.json";
+
+/// Lock file shared by installs and taken exclusively by garbage collection
+const STORE_LOCK_FILE_NAME: &str = "store.lock";
+
+/// Suffix of folders a package is extracted into before moving into place
+const EXTRACTING_SUFFIX: &str = "-extracting";
+
+/// Projects that have installed packages from the store on this machine
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectIndex {
+    #[serde(skip)]
+    path: PathBuf,
+
+    /// Absolute project directories
+    projects: BTreeSet<PathBuf>,
+
+    /// When garbage was last collected with this index (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_collected: Option<String>,
+}
+
+impl ProjectIndex {
+    /// Empty index that saves to `path`
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            projects: BTreeSet::new(),
+            last_collected: None,
+        }
+    }
+
+    /// Index stored at `path`; empty if the file doesn't exist yet
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut index = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str::<Self>(&content)
+                .map_err(|e| Error::Other(format!("Failed to parse {}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+        index.path = path.to_path_buf();
+        Ok(index)
+    }
+
+    /// Index next to the user config (`~/.unrealpm/projects.json`)
+    pub fn open_default() -> Result<Self> {
+        Self::load(default_index_path()?)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize project index: {}", e)))?;
+        fs::write(&self.path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Add `project_dir`, returning false if it was already registered
+    pub fn register(&mut self, project_dir: PathBuf) -> bool {
+        self.projects.insert(project_dir)
+    }
+
+    /// Remove `project_dir`, returning false if it wasn't registered
+    pub fn unregister(&mut self, project_dir: &Path) -> bool {
+        self.projects.remove(project_dir)
+    }
+
+    /// Registered projects, sorted by path
+    pub fn iter(&self) -> impl Iterator<Item = &Path> {
+        self.projects.iter().map(PathBuf::as_path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.projects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.projects.is_empty()
+    }
+
+    /// When garbage was last collected with this index; `None` until the
+    /// first collection, when projects that haven't run unrealpm since the
+    /// index was created are still missing from it
+    pub fn last_collected(&self) -> Option<&str> {
+        self.last_collected.as_deref()
+    }
+}
+
+fn default_index_path() -> Result<PathBuf> {
+    let config_path = Config::default_path()?;
+    let dir = config_path.parent().unwrap_or(Path::new("."));
+    Ok(dir.join(PROJECT_INDEX_FILE_NAME))
+}
+
+/// Add `project_dir` to the default project index
+///
+/// Concurrent installs of different projects update the index under a lock
+/// of its own, so neither registration is lost.
+pub fn register_project(project_dir: &Path) -> Result<()> {
+    let project_dir = project_dir.canonicalize()?;
+    let path = default_index_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock = open_lock_file(&path.with_extension("lock"))?;
+    lock.lock_exclusive()?;
+
+    let mut index = ProjectIndex::load(&path)?;
+    if index.register(project_dir) {
+        index.save()?;
+    }
+    Ok(())
+}
+
+/// Register the current directory, whose unrealpm.lock was just loaded
+///
+/// Called by [`Lockfile::load`], once per process. Best effort: a project
+/// that can't be registered is still registered by its next install.
+pub fn register_current_project() {
+    static REGISTERED: Once = Once::new();
+    REGISTERED.call_once(|| {
+        if let Ok(dir) = std::env::current_dir() {
+            let _ = register_project(&dir);
+        }
+    });
+}
+
+/// Lock coordinating installs with garbage collection of the store
+///
+/// Released when dropped.
+#[derive(Debug)]
+pub struct StoreLock {
+    file: File,
+}
+
+impl StoreLock {
+    /// Lock held while installing; waits for a running garbage collection
+    pub fn shared() -> Result<Self> {
+        let file = open_lock_file(&store_lock_path()?)?;
+        file.lock_shared()?;
+        Ok(Self { file })
+    }
+
+    /// Lock held while collecting garbage; waits for running installs
+    pub fn exclusive() -> Result<Self> {
+        let file = open_lock_file(&store_lock_path()?)?;
+        file.lock_exclusive()?;
+        Ok(Self { file })
+    }
+
+    /// Exclusive lock, or `None` if an install or collection holds the lock
+    pub fn try_exclusive() -> Result<Option<Self>> {
+        let file = open_lock_file(&store_lock_path()?)?;
+        match file.try_lock_exclusive() {
+            Ok(()) => Ok(Some(Self { file })),
+            Err(e) if e.kind() == fs2::lock_contended_error().kind() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl Drop for StoreLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+fn store_lock_path() -> Result<PathBuf> {
+    let store_dir = get_store_dir()?;
+    let dir = store_dir.parent().unwrap_or(&store_dir);
+    Ok(dir.join(STORE_LOCK_FILE_NAME))
+}
+
+fn open_lock_file(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| Error::Other(format!("Failed to open {}: {}", path.display(), e)))
+}
+
+/// Shared lock and registered projects of this process's install
+static INSTALL_HOLD: Mutex<Option<(StoreLock, HashSet<PathBuf>)>> = Mutex::new(None);
+
+/// Keep garbage collection away while this process installs into
+/// `project_dir`
+///
+/// Takes a shared [`StoreLock`] the first time it's called and holds it
+/// until the process exits, so store entries stay put until the project's
+/// lockfile refers to them. Each project is registered in the project index
+/// once.
+pub fn hold_for_install(project_dir: &Path) -> Result<()> {
+    let mut hold = INSTALL_HOLD.lock().unwrap_or_else(|e| e.into_inner());
+    if hold.is_none() {
+        *hold = Some((StoreLock::shared()?, HashSet::new()));
+    }
+    if let Some((_, registered)) = hold.as_mut() {
+        if !registered.contains(project_dir) {
+            register_project(project_dir)?;
+            registered.insert(project_dir.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// A store entry removed (or, in a dry run, to be removed) by
+/// [`collect_garbage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreEntry {
+    /// Content hash the entry is stored under
+    pub checksum: String,
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    /// Left behind by an interrupted extraction
+    pub incomplete: bool,
+}
+
+/// Outcome of [`collect_garbage`]
+#[derive(Debug, Clone, Default)]
+pub struct GcReport {
+    /// Registered projects whose lockfiles were read
+    pub projects: usize,
+    /// Projects dropped from the index because they or their lockfiles are gone
+    pub forgotten: Vec<PathBuf>,
+    /// Entries still referenced
+    pub kept: usize,
+    pub removed: Vec<StoreEntry>,
+    /// Bytes freed by the removals
+    pub reclaimed: u64,
+}
+
+/// Remove the entries of `store_dir` that no project in `index` refers to
+///
+/// The caller holds [`StoreLock::exclusive`], so no install is extracting
+/// into the store; unfinished extractions are removed too. A lockfile that
+/// can't be read stops the collection before anything is removed, since the
+/// entries it refers to are unknown.
+pub fn collect_garbage(
+    store_dir: &Path,
+    index: &mut ProjectIndex,
+    dry_run: DryRun,
+) -> Result<GcReport> {
+    let mut report = GcReport::default();
+    let mut referenced = HashSet::new();
+
+    for project_dir in index.iter() {
+        let lockfile_path = project_dir.join(LOCKFILE_NAME);
+        let lockfile = Lockfile::load_from(&lockfile_path).map_err(|e| {
+            Error::Other(format!(
+                "Could not read {}: {}; fix it or remove the project before collecting garbage",
+                lockfile_path.display(),
+                e
+            ))
+        })?;
+        match lockfile {
+            Some(lockfile) => {
+                report.projects += 1;
+                for package in lockfile.packages.values() {
+                    referenced.insert(package.checksum.clone());
+                    referenced.insert(package.artifact_checksum().to_string());
+                }
+            }
+            None => report.forgotten.push(project_dir.to_path_buf()),
+        }
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(store_dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let incomplete = name.ends_with(EXTRACTING_SUFFIX);
+        if !incomplete && referenced.contains(&name) {
+            report.kept += 1;
+            continue;
+        }
+        report.removed.push(StoreEntry {
+            checksum: name.trim_end_matches(EXTRACTING_SUFFIX).to_string(),
+            size: dir_size(&path),
+            path,
+            incomplete,
+        });
+    }
+
+    for entry in &report.removed {
+        dry_run.apply(|| Ok(fs::remove_dir_all(&entry.path)?))?;
+        report.reclaimed += entry.size;
+    }
+
+    dry_run.apply(|| {
+        for project_dir in &report.forgotten {
+            index.unregister(project_dir);
+        }
+        index.last_collected = Some(chrono::Utc::now().to_rfc3339());
+        index.save()
+    })?;
+
+    Ok(report)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn lock(dir: &Path, checksums: &[&str]) {
+        let mut lockfile = Lockfile::new();
+        for (i, checksum) in checksums.iter().enumerate() {
+            lockfile.update_package(
+                format!("pkg{}", i),
+                "1.0.0".to_string(),
+                checksum.to_string(),
+                None,
+            );
+        }
+        lockfile.save_to(dir.join(LOCKFILE_NAME)).unwrap();
+    }
+
+    fn entry(store: &Path, name: &str) {
+        let dir = store.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Plugin.uplugin"), "{}").unwrap();
+    }
+
+    #[test]
+    fn test_collect_garbage() {
+        let temp = TempDir::new().unwrap();
+        let store = temp.path().join("store");
+        let game = temp.path().join("Game");
+        let other = temp.path().join("Other");
+        fs::create_dir_all(&game).unwrap();
+        fs::create_dir_all(&other).unwrap();
+        lock(&game, &["aaa"]);
+        lock(&other, &["bbb"]);
+
+        for name in ["aaa", "bbb", "ccc", "ddd-extracting"] {
+            entry(&store, name);
+        }
+
+        let mut index = ProjectIndex::new(temp.path().join(PROJECT_INDEX_FILE_NAME));
+        index.register(game.clone());
+        index.register(other.clone());
+        index.register(temp.path().join("Deleted"));
+        index.save().unwrap();
+
+        let report = collect_garbage(&store, &mut index, DryRun::new(true)).unwrap();
+        assert_eq!(report.projects, 2);
+        assert_eq!(report.kept, 2);
+        let removed: Vec<&str> = report.removed.iter().map(|e| e.checksum.as_str()).collect();
+        assert_eq!(removed, vec!["ccc", "ddd"]);
+        assert!(report.removed[1].incomplete);
+        assert_eq!(report.reclaimed, 4);
+        assert!(store.join("ccc").exists());
+        let saved = ProjectIndex::load(index.path()).unwrap();
+        assert_eq!(saved.len(), 3);
+        assert!(saved.last_collected().is_none());
+
+        // Once a project is gone, its entries go too
+        fs::remove_dir_all(&other).unwrap();
+        let report = collect_garbage(&store, &mut index, DryRun::OFF).unwrap();
+        assert_eq!(report.forgotten.len(), 2);
+        assert_eq!(report.removed.len(), 3);
+        assert!(store.join("aaa").exists());
+        assert!(!store.join("bbb").exists());
+        assert!(!store.join("ddd-extracting").exists());
+
+        let saved = ProjectIndex::load(index.path()).unwrap();
+        assert_eq!(saved.iter().collect::<Vec<_>>(), vec![game.as_path()]);
+        assert!(saved.last_collected().is_some());
+    }
+
+    #[test]
+    fn test_unreadable_lockfile_stops_collection() {
+        let temp = TempDir::new().unwrap();
+        let store = temp.path().join("store");
+        entry(&store, "aaa");
+        fs::write(temp.path().join(LOCKFILE_NAME), "not = [valid").unwrap();
+
+        let mut index = ProjectIndex::new(temp.path().join(PROJECT_INDEX_FILE_NAME));
+        index.register(temp.path().to_path_buf());
+        assert!(collect_garbage(&store, &mut index, DryRun::OFF).is_err());
+        assert!(store.join("aaa").exists());
+    }
+}
@@ -0,0 +1,72 @@
+//! Strict mode: treat warnings as errors
+//!
+//! Teams that want maximum rigor in CI enable strict mode with `--strict` or
+//! `[ui] strict = true` in the user config. Code paths that would normally
+//! print a warning and carry on (unsigned packages, engine checks skipped by
+//! `--force`, packages missing from the lockfile) report it through [`warn`],
+//! which fails with [`Error::Strict`] instead while strict mode is on.
+//!
+//! Strict mode is process-wide: the CLI enables it once at startup.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::strict;
+//!
+//! // Off by default: the warning is printed and execution continues
+//! assert!(strict::warn("Package is not signed").is_ok());
+//!
+//! strict::set_enabled(true);
+//! assert!(strict::warn("Package is not signed").is_err());
+//! # strict::set_enabled(false);
+//! ```
+
+use crate::{Error, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn strict mode on or off for the rest of the process
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether warnings are currently treated as errors
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Print a warning, or fail with it in strict mode
+pub fn warn(message: impl Into<String>) -> Result<()> {
+    let message = message.into();
+    check(&message)?;
+    println!("  ⚠ {}", message);
+    Ok(())
+}
+
+/// Fail in strict mode for a warning the caller has already reported
+pub fn check(message: impl Into<String>) -> Result<()> {
+    if is_enabled() {
+        return Err(Error::Strict(message.into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strict_mode_turns_warnings_into_errors() {
+        set_enabled(true);
+        let result = warn("Force installing - engine compatibility not checked");
+        let checked = check("Module name collisions");
+        set_enabled(false);
+
+        let message = result.unwrap_err().to_string();
+        assert!(message.starts_with("Force installing - engine compatibility not checked"));
+        assert!(message.contains("--strict"));
+        assert!(matches!(checked, Err(Error::Strict(_))));
+        assert!(warn("not strict any more").is_ok());
+    }
+}
@@ -0,0 +1,294 @@
+//! Synthetic file registries for integration tests (`testing` feature)
+//!
+//! [`TestRegistry`] is a file registry in a temporary directory, deleted
+//! when it is dropped. [`MockPlugin`] describes a minimal plugin (a
+//! `.uplugin`, one runtime module, Build.cs); adding it packs a real tarball
+//! with its checksum, so the registry can be resolved against and installed
+//! from like a published one.
+//!
+//! These are test fixtures: they panic instead of returning errors.
+//!
+//! ```toml
+//! [dev-dependencies]
+//! unrealpm = { version = "0.4", features = ["testing"] }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::testing::{MockPlugin, TestRegistry};
+//!
+//! let registry = TestRegistry::new();
+//! registry.add_package(&MockPlugin::new("core-utils", "1.2.0"));
+//! registry.add_package(&MockPlugin::new("awesome", "2.0.0").with_dependency("core-utils", "^1.0.0"));
+//!
+//! let metadata = registry.client().get_package("awesome").unwrap();
+//! assert_eq!(metadata.versions[0].version, "2.0.0");
+//! ```
+
+use crate::registry::{
+    Dependency, FileRegistryClient, PackageMetadata, PackageType, PackageVersion, RegistryClient,
+};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A minimal plugin to add to a [`TestRegistry`] or write into a project
+#[derive(Debug, Clone)]
+pub struct MockPlugin {
+    pub name: String,
+    pub version: String,
+    pub engine_versions: Vec<String>,
+    pub dependencies: Vec<(String, String)>,
+}
+
+impl MockPlugin {
+    /// A plugin compatible with UE 5.3 and 5.4, without dependencies
+    pub fn new(name: &str, version: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            engine_versions: vec!["5.3".to_string(), "5.4".to_string()],
+            dependencies: vec![],
+        }
+    }
+
+    pub fn with_engine_versions(mut self, versions: Vec<&str>) -> Self {
+        self.engine_versions = versions.into_iter().map(String::from).collect();
+        self
+    }
+
+    pub fn with_dependency(mut self, name: &str, version: &str) -> Self {
+        self.dependencies
+            .push((name.to_string(), version.to_string()));
+        self
+    }
+
+    /// Create .uplugin content
+    pub fn uplugin_content(&self) -> String {
+        format!(
+            r#"{{
+    "FileVersion": 3,
+    "Version": 1,
+    "VersionName": "{}",
+    "FriendlyName": "{}",
+    "Description": "Test plugin",
+    "Category": "Testing",
+    "CreatedBy": "UnrealPM Tests",
+    "CanContainContent": true,
+    "IsBetaVersion": false,
+    "IsExperimentalVersion": false,
+    "Installed": false,
+    "Modules": [
+        {{
+            "Name": "{}",
+            "Type": "Runtime",
+            "LoadingPhase": "Default"
+        }}
+    ]
+}}"#,
+            self.version, self.name, self.name
+        )
+    }
+
+    /// Write the plugin to `dir/<name>/`, returning that folder
+    pub fn create_in(&self, dir: &Path) -> PathBuf {
+        let plugin_dir = dir.join(&self.name);
+        fs::create_dir_all(&plugin_dir).expect("Failed to create plugin directory");
+
+        // Create .uplugin
+        fs::write(
+            plugin_dir.join(format!("{}.uplugin", self.name)),
+            self.uplugin_content(),
+        )
+        .expect("Failed to write .uplugin");
+
+        // Create Source directory
+        let source_dir = plugin_dir.join("Source").join(&self.name);
+        fs::create_dir_all(&source_dir).expect("Failed to create source directory");
+
+        // Create a minimal module file
+        let module_cpp = format!(
+            r#"#include "{}.h"
+#include "Modules/ModuleManager.h"
+
+IMPLEMENT_MODULE(FDefaultModuleImpl, {})
+"#,
+            self.name, self.name
+        );
+        fs::write(source_dir.join(format!("{}.cpp", self.name)), module_cpp)
+            .expect("Failed to write module cpp");
+
+        let module_h = r#"#pragma once
+
+#include "CoreMinimal.h"
+"#;
+        fs::write(source_dir.join(format!("{}.h", self.name)), module_h)
+            .expect("Failed to write module header");
+
+        // Create Build.cs
+        let build_cs = format!(
+            r#"using UnrealBuildTool;
+
+public class {} : ModuleRules
+{{
+    public {}(ReadOnlyTargetRules Target) : base(Target)
+    {{
+        PCHUsage = ModuleRules.PCHUsageMode.UseExplicitOrSharedPCHs;
+        PublicDependencyModuleNames.AddRange(new string[] {{ "Core", "CoreUObject", "Engine" }});
+    }}
+}}
+"#,
+            self.name, self.name
+        );
+        fs::write(source_dir.join(format!("{}.Build.cs", self.name)), build_cs)
+            .expect("Failed to write Build.cs");
+
+        plugin_dir
+    }
+
+    /// Registry entry for this plugin's tarball
+    fn package_version(&self, checksum: String) -> PackageVersion {
+        PackageVersion {
+            version: self.version.clone(),
+            tarball: format!("{}-{}.tar.gz", self.name, self.version),
+            checksum,
+            dependencies: Some(
+                self.dependencies
+                    .iter()
+                    .map(|(name, version)| Dependency {
+                        name: name.clone(),
+                        version: version.clone(),
+                    })
+                    .collect(),
+            ),
+            engine_versions: Some(self.engine_versions.clone()),
+            engine_major: None,
+            engine_minor: None,
+            is_multi_engine: true,
+            package_type: PackageType::Source,
+            binaries: None,
+            public_key: None,
+            signed_at: None,
+            key_endorsement: None,
+            modules: Some(vec![self.name.clone()]),
+            features: None,
+        }
+    }
+}
+
+/// File registry in a temporary directory
+pub struct TestRegistry {
+    pub temp_dir: TempDir,
+    pub packages_dir: PathBuf,
+    pub tarballs_dir: PathBuf,
+    pub signatures_dir: PathBuf,
+}
+
+impl TestRegistry {
+    pub fn new() -> Self {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let root = temp_dir.path();
+
+        let packages_dir = root.join("packages");
+        let tarballs_dir = root.join("tarballs");
+        let signatures_dir = root.join("signatures");
+
+        fs::create_dir_all(&packages_dir).expect("Failed to create packages dir");
+        fs::create_dir_all(&tarballs_dir).expect("Failed to create tarballs dir");
+        fs::create_dir_all(&signatures_dir).expect("Failed to create signatures dir");
+
+        Self {
+            temp_dir,
+            packages_dir,
+            tarballs_dir,
+            signatures_dir,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        self.temp_dir.path()
+    }
+
+    /// Client reading this registry
+    pub fn client(&self) -> RegistryClient {
+        RegistryClient::File(FileRegistryClient::new(self.path()))
+    }
+
+    /// Publish a plugin version: pack its tarball and add it to the
+    /// package's metadata, replacing the same version if present
+    pub fn add_package(&self, plugin: &MockPlugin) -> PathBuf {
+        let staging = TempDir::new().expect("Failed to create temp directory");
+        let plugin_dir = plugin.create_in(staging.path());
+        let tarball = self
+            .tarballs_dir
+            .join(format!("{}-{}.tar.gz", plugin.name, plugin.version));
+        let file = fs::File::create(&tarball).expect("Failed to create tarball");
+        let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        archive
+            .append_dir_all(&plugin.name, &plugin_dir)
+            .expect("Failed to pack plugin");
+        archive
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .expect("Failed to write tarball");
+        let checksum =
+            crate::installer::calculate_checksum(&tarball).expect("Failed to hash tarball");
+
+        let metadata_path = self.packages_dir.join(format!("{}.json", plugin.name));
+        let mut metadata = fs::read_to_string(&metadata_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<PackageMetadata>(&content).ok())
+            .unwrap_or_else(|| PackageMetadata {
+                name: plugin.name.clone(),
+                description: Some("Test package".to_string()),
+                versions: Vec::new(),
+                categories: Vec::new(),
+                keywords: Vec::new(),
+            });
+        metadata.versions.retain(|v| v.version != plugin.version);
+        metadata.versions.push(plugin.package_version(checksum));
+        fs::write(
+            &metadata_path,
+            serde_json::to_string_pretty(&metadata).expect("Failed to serialize metadata"),
+        )
+        .expect("Failed to write package metadata");
+
+        tarball
+    }
+}
+
+impl Default for TestRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_serves_packages() {
+        let registry = TestRegistry::new();
+        registry.add_package(&MockPlugin::new("core-utils", "1.0.0"));
+        registry.add_package(&MockPlugin::new("core-utils", "1.1.0"));
+        registry.add_package(
+            &MockPlugin::new("awesome", "2.0.0").with_dependency("core-utils", "^1.0.0"),
+        );
+
+        let client = registry.client();
+        let core = client.get_package("core-utils").unwrap();
+        assert_eq!(core.versions.len(), 2);
+
+        let awesome = &client.get_package("awesome").unwrap().versions[0];
+        assert_eq!(awesome.dependencies.as_ref().unwrap()[0].name, "core-utils");
+        let tarball = client
+            .fetch_tarball("awesome", "2.0.0", &awesome.checksum)
+            .unwrap();
+        crate::installer::verify_checksum(&tarball, &awesome.checksum, None).unwrap();
+        crate::installer::validate_tarball_entries(&tarball).unwrap();
+    }
+}
@@ -0,0 +1,282 @@
+//! Opt-in timing telemetry for install and update operations
+//!
+//! Commands record how long each phase takes (resolution, download, checksum
+//! verification, extraction, build) along with download-cache and CAS store
+//! hit rates. The CLI prints the report with `--timings`; library users can
+//! read the same numbers from [`Timings`] directly.
+//!
+//! Phases timed per package (with [`Timings::time_package`]) are also kept
+//! by package name, which is what [`InstallHistory`](crate::metrics::InstallHistory)
+//! persists for estimates on later installs.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::timings::{Phase, Timings};
+//!
+//! let mut timings = Timings::new();
+//! let sum = timings.time(Phase::Resolve, || 2 + 2);
+//! timings.store_cache.record(true);
+//!
+//! assert_eq!(sum, 4);
+//! assert_eq!(timings.count(Phase::Resolve), 1);
+//! assert_eq!(timings.store_cache.hit_rate(), Some(1.0));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// A phase of an install/update operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    /// Fetching metadata and resolving dependency versions
+    Resolve,
+    /// Downloading tarballs from the registry
+    Download,
+    /// Verifying tarball checksums and signatures
+    Verify,
+    /// Extracting packages into the store and linking into Plugins/
+    Extract,
+    /// Building plugin binaries
+    Build,
+}
+
+impl Phase {
+    /// All phases, in the order they run
+    pub const ALL: [Phase; 5] = [
+        Phase::Resolve,
+        Phase::Download,
+        Phase::Verify,
+        Phase::Extract,
+        Phase::Build,
+    ];
+
+    /// Human-readable phase name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Phase::Resolve => "Resolve",
+            Phase::Download => "Download",
+            Phase::Verify => "Verify",
+            Phase::Extract => "Extract",
+            Phase::Build => "Build",
+        }
+    }
+}
+
+/// Hit/miss counters for a cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u32,
+    pub misses: u32,
+}
+
+impl CacheStats {
+    /// Record a single cache lookup
+    pub fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+
+    /// Fraction of lookups that were hits, or `None` if there were no lookups
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            None
+        } else {
+            Some(self.hits as f64 / total as f64)
+        }
+    }
+}
+
+/// Collected phase durations and cache statistics
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    /// Total duration and number of occurrences per phase
+    phases: BTreeMap<Phase, (Duration, u32)>,
+
+    /// Per-package durations, for phases timed with `time_package`
+    packages: BTreeMap<String, BTreeMap<Phase, Duration>>,
+
+    /// Registry download cache (cached tarballs)
+    pub download_cache: CacheStats,
+
+    /// Global CAS store (already-extracted packages)
+    pub store_cache: CacheStats,
+
+    /// Cached resolution results reused without running the resolver
+    pub resolve_cache: CacheStats,
+}
+
+impl Timings {
+    /// Create an empty set of timings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a duration to a phase
+    pub fn record(&mut self, phase: Phase, duration: Duration) {
+        let entry = self.phases.entry(phase).or_default();
+        entry.0 += duration;
+        entry.1 += 1;
+    }
+
+    /// Run `f`, recording its duration under `phase`
+    pub fn time<T>(&mut self, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    /// Add a duration to a phase, and to `package`'s own record of it
+    pub fn record_package(&mut self, package: &str, phase: Phase, duration: Duration) {
+        self.record(phase, duration);
+        *self
+            .packages
+            .entry(package.to_string())
+            .or_default()
+            .entry(phase)
+            .or_default() += duration;
+    }
+
+    /// Run `f`, recording its duration under `phase` for `package`
+    pub fn time_package<T>(&mut self, package: &str, phase: Phase, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record_package(package, phase, start.elapsed());
+        result
+    }
+
+    /// Per-package phase durations recorded with `record_package`
+    pub fn packages(&self) -> &BTreeMap<String, BTreeMap<Phase, Duration>> {
+        &self.packages
+    }
+
+    /// Total time spent in a phase
+    pub fn duration(&self, phase: Phase) -> Duration {
+        self.phases.get(&phase).map(|p| p.0).unwrap_or_default()
+    }
+
+    /// Number of times a phase was recorded
+    pub fn count(&self, phase: Phase) -> u32 {
+        self.phases.get(&phase).map(|p| p.1).unwrap_or_default()
+    }
+
+    /// Total time across all phases
+    pub fn total(&self) -> Duration {
+        self.phases.values().map(|p| p.0).sum()
+    }
+
+    /// Format a human-readable report of all recorded phases and cache rates
+    pub fn report(&self) -> String {
+        let mut lines = vec!["Timings:".to_string()];
+
+        for phase in Phase::ALL {
+            let count = self.count(phase);
+            if count == 0 {
+                continue;
+            }
+            lines.push(format!(
+                "  {:<10} {:>9.2}s  ({} {})",
+                phase.label(),
+                self.duration(phase).as_secs_f64(),
+                count,
+                if count == 1 { "step" } else { "steps" }
+            ));
+        }
+        lines.push(format!(
+            "  {:<10} {:>9.2}s",
+            "Total",
+            self.total().as_secs_f64()
+        ));
+
+        for (name, stats) in [
+            ("Resolution cache", &self.resolve_cache),
+            ("Download cache", &self.download_cache),
+            ("Package store", &self.store_cache),
+        ] {
+            if let Some(rate) = stats.hit_rate() {
+                lines.push(format!(
+                    "  {}: {}/{} hits ({:.0}%)",
+                    name,
+                    stats.hits,
+                    stats.hits + stats.misses,
+                    rate * 100.0
+                ));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_accumulates() {
+        let mut timings = Timings::new();
+        timings.record(Phase::Download, Duration::from_millis(100));
+        timings.record(Phase::Download, Duration::from_millis(50));
+        timings.record(Phase::Extract, Duration::from_millis(25));
+
+        assert_eq!(
+            timings.duration(Phase::Download),
+            Duration::from_millis(150)
+        );
+        assert_eq!(timings.count(Phase::Download), 2);
+        assert_eq!(timings.count(Phase::Build), 0);
+        assert_eq!(timings.total(), Duration::from_millis(175));
+    }
+
+    #[test]
+    fn test_record_package_counts_toward_phase() {
+        let mut timings = Timings::new();
+        timings.record_package("a", Phase::Download, Duration::from_millis(100));
+        timings.record_package("a", Phase::Download, Duration::from_millis(20));
+        timings.record_package("b", Phase::Extract, Duration::from_millis(30));
+        timings.record(Phase::Extract, Duration::from_millis(5));
+
+        assert_eq!(
+            timings.duration(Phase::Download),
+            Duration::from_millis(120)
+        );
+        assert_eq!(timings.duration(Phase::Extract), Duration::from_millis(35));
+        assert_eq!(
+            timings.packages()["a"][&Phase::Download],
+            Duration::from_millis(120)
+        );
+        assert_eq!(timings.packages().len(), 2);
+    }
+
+    #[test]
+    fn test_cache_hit_rate() {
+        let mut stats = CacheStats::default();
+        assert_eq!(stats.hit_rate(), None);
+
+        stats.record(true);
+        stats.record(false);
+        stats.record(true);
+        stats.record(true);
+        assert_eq!(stats.hit_rate(), Some(0.75));
+    }
+
+    #[test]
+    fn test_report_skips_empty_phases() {
+        let mut timings = Timings::new();
+        timings.record(Phase::Resolve, Duration::from_millis(1200));
+        timings.download_cache.record(false);
+
+        let report = timings.report();
+        assert!(report.contains("Resolve"));
+        assert!(!report.contains("Build"));
+        assert!(report.contains("Download cache: 0/1 hits (0%)"));
+        assert!(!report.contains("Package store"));
+    }
+}
@@ -0,0 +1,398 @@
+//! Trust-on-first-use pinning of package publisher keys
+//!
+//! A valid signature only proves a package was signed by the key the
+//! registry lists for it. If that key is replaced, whether by a publisher
+//! rotating keys or by someone substituting a package, the new signatures
+//! verify just as well. So the first time a package's key is seen it is
+//! pinned in a machine-local trust store (`~/.unrealpm/trusted_keys.json`),
+//! and a different key for the same package is refused until it is
+//! accepted with `unrealpm trust <package>`. A key the pinned one endorsed
+//! with `unrealpm keys rotate` is accepted and pinned in its place.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::trust::{KeyCheck, TrustStore};
+//!
+//! let mut store = TrustStore::new("trusted_keys.json");
+//! assert_eq!(store.check("awesome-plugin", "aa11"), KeyCheck::FirstUse);
+//!
+//! store.pin("awesome-plugin", "aa11", "1.0.0");
+//! assert_eq!(store.check("awesome-plugin", "AA11"), KeyCheck::Trusted);
+//! assert!(matches!(
+//!     store.check("awesome-plugin", "bb22"),
+//!     KeyCheck::Changed(_)
+//! ));
+//! ```
+
+use crate::{Config, Error, KeyEndorsement, Result};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+/// File name of the trust store, next to the user config
+pub const TRUST_STORE_FILE_NAME: &str = "trusted_keys.json";
+
+/// The publisher key pinned for one package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedKey {
+    /// Hex-encoded Ed25519 public key
+    pub public_key: String,
+    /// Version the key was first seen (or accepted) with; empty for keys
+    /// accepted with `unrealpm trust --key`
+    pub version: String,
+    /// When the key was pinned (RFC 3339)
+    pub pinned_at: String,
+}
+
+impl PinnedKey {
+    /// How the key came to be trusted, e.g. `with 1.0.0 on 2026-01-01T...`
+    pub fn origin(&self) -> String {
+        if self.version.is_empty() {
+            format!("by hand on {}", self.pinned_at)
+        } else {
+            format!("with {} on {}", self.version, self.pinned_at)
+        }
+    }
+}
+
+/// How a package's key compares to the pinned one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyCheck {
+    /// No key is pinned for the package yet
+    FirstUse,
+    /// The key is the pinned one
+    Trusted,
+    /// Another key is pinned
+    Changed(PinnedKey),
+    /// Another key is pinned, and it endorsed this one as its replacement
+    Rotated(PinnedKey),
+}
+
+/// Publisher keys pinned on this machine, by package name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrustStore {
+    #[serde(skip)]
+    path: PathBuf,
+
+    keys: BTreeMap<String, PinnedKey>,
+}
+
+impl TrustStore {
+    /// Empty store that saves to `path`
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            keys: BTreeMap::new(),
+        }
+    }
+
+    /// Store at `path`; empty if the file doesn't exist yet
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let mut store = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str::<Self>(&content)
+                .map_err(|e| Error::Other(format!("Failed to parse {}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+        store.path = path.to_path_buf();
+        Ok(store)
+    }
+
+    /// Store next to the user config (`~/.unrealpm/trusted_keys.json`)
+    pub fn open_default() -> Result<Self> {
+        Self::load(default_path()?)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::Other(format!("Failed to serialize trusted keys: {}", e)))?;
+        fs::write(&self.path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Compare `public_key` with the key pinned for `package`
+    pub fn check(&self, package: &str, public_key: &str) -> KeyCheck {
+        match self.keys.get(package) {
+            None => KeyCheck::FirstUse,
+            Some(pinned) if pinned.public_key.eq_ignore_ascii_case(public_key) => KeyCheck::Trusted,
+            Some(pinned) => KeyCheck::Changed(pinned.clone()),
+        }
+    }
+
+    /// Like [`TrustStore::check`], but a changed key that `endorsement`
+    /// shows the pinned key endorsed is a [`KeyCheck::Rotated`] key
+    pub fn check_endorsed(
+        &self,
+        package: &str,
+        public_key: &str,
+        endorsement: Option<&KeyEndorsement>,
+    ) -> KeyCheck {
+        match self.check(package, public_key) {
+            KeyCheck::Changed(pinned)
+                if endorsement.is_some_and(|endorsement| {
+                    endorsement
+                        .previous_key
+                        .eq_ignore_ascii_case(&pinned.public_key)
+                        && endorsement.endorses(public_key)
+                }) =>
+            {
+                KeyCheck::Rotated(pinned)
+            }
+            check => check,
+        }
+    }
+
+    /// Fail if a key is pinned for `package`
+    ///
+    /// Called for unsigned versions: once a publisher's key is pinned, a
+    /// version without a signature can't be told apart from one someone
+    /// else published.
+    pub fn check_unsigned(&self, package: &str, version: &str) -> Result<()> {
+        match self.keys.get(package) {
+            Some(pinned) => Err(Error::SignatureVerificationFailed(unsigned_message(
+                package, version, pinned,
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Pin `public_key` for `package`, returning the key it replaces
+    pub fn pin(&mut self, package: &str, public_key: &str, version: &str) -> Option<PinnedKey> {
+        self.keys.insert(
+            package.to_string(),
+            PinnedKey {
+                public_key: public_key.to_lowercase(),
+                version: version.to_string(),
+                pinned_at: chrono::Utc::now().to_rfc3339(),
+            },
+        )
+    }
+
+    /// Forget the key pinned for `package`, returning it
+    pub fn unpin(&mut self, package: &str) -> Option<PinnedKey> {
+        self.keys.remove(package)
+    }
+
+    /// Key pinned for `package`
+    pub fn get(&self, package: &str) -> Option<&PinnedKey> {
+        self.keys.get(package)
+    }
+
+    /// Pinned keys, sorted by package name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &PinnedKey)> {
+        self.keys.iter().map(|(name, key)| (name.as_str(), key))
+    }
+}
+
+fn default_path() -> Result<PathBuf> {
+    let config_path = Config::default_path()?;
+    let dir = config_path.parent().unwrap_or(Path::new("."));
+    Ok(dir.join(TRUST_STORE_FILE_NAME))
+}
+
+/// Fail if `package`'s publisher key differs from the one pinned for it,
+/// unless the pinned key endorsed it
+pub fn check_publisher_key(
+    package: &str,
+    version: &str,
+    public_key: &str,
+    endorsement: Option<&KeyEndorsement>,
+) -> Result<()> {
+    match TrustStore::open_default()?.check_endorsed(package, public_key, endorsement) {
+        KeyCheck::Changed(pinned) => Err(Error::SignatureVerificationFailed(key_changed_message(
+            package, version, public_key, &pinned,
+        ))),
+        KeyCheck::FirstUse | KeyCheck::Trusted | KeyCheck::Rotated(_) => Ok(()),
+    }
+}
+
+/// Fail if `package@version` is unsigned but a publisher key is pinned for it
+pub fn check_unsigned_package(package: &str, version: &str) -> Result<()> {
+    TrustStore::open_default()?.check_unsigned(package, version)
+}
+
+/// Pin `public_key` for `package` if no key is pinned yet, or the pinned
+/// key endorsed it as its replacement
+///
+/// Called once a signature made with the key has verified. Returns how the
+/// key compared to the pinned one: [`KeyCheck::FirstUse`] and
+/// [`KeyCheck::Rotated`] keys were pinned now. Concurrent installs update the
+/// store under a lock file, so neither pin is lost.
+pub fn pin_on_first_use(
+    package: &str,
+    version: &str,
+    public_key: &str,
+    endorsement: Option<&KeyEndorsement>,
+) -> Result<KeyCheck> {
+    let path = default_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path.with_extension("lock"))?;
+    lock.lock_exclusive()?;
+
+    let mut store = TrustStore::load(&path)?;
+    match store.check_endorsed(package, public_key, endorsement) {
+        check @ (KeyCheck::FirstUse | KeyCheck::Rotated(_)) => {
+            store.pin(package, public_key, version);
+            store.save()?;
+            Ok(check)
+        }
+        KeyCheck::Trusted => Ok(KeyCheck::Trusted),
+        KeyCheck::Changed(pinned) => Err(Error::SignatureVerificationFailed(key_changed_message(
+            package, version, public_key, &pinned,
+        ))),
+    }
+}
+
+fn key_changed_message(
+    package: &str,
+    version: &str,
+    public_key: &str,
+    pinned: &PinnedKey,
+) -> String {
+    format!(
+        "The publisher key of {} has CHANGED\n\n\
+        Pinned key:   {} (trusted {})\n\
+        {}@{} key: {}\n\n\
+        Publishers change keys when they rotate them, but a new key can also mean\n\
+        someone else published or is serving this package. Installation has been\n\
+        aborted.\n\n\
+        Confirm the new key with the publisher, then accept it with:\n  \
+        unrealpm trust {} --key {}",
+        package,
+        pinned.public_key,
+        pinned.origin(),
+        package,
+        version,
+        public_key,
+        package,
+        public_key
+    )
+}
+
+fn unsigned_message(package: &str, version: &str, pinned: &PinnedKey) -> String {
+    format!(
+        "{}@{} is NOT SIGNED, but a publisher key is pinned for {}\n\n\
+        Pinned key: {} (trusted {})\n\n\
+        An unsigned version can mean someone else published or is serving this\n\
+        package. Installation has been aborted.\n\n\
+        Ask the publisher why this version is unsigned, or install a signed version.",
+        package,
+        version,
+        package,
+        pinned.public_key,
+        pinned.origin()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pin_and_check() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(TRUST_STORE_FILE_NAME);
+
+        let mut store = TrustStore::load(&path).unwrap();
+        assert_eq!(store.check("core", "aa11"), KeyCheck::FirstUse);
+        assert!(store.pin("core", "AA11", "1.0.0").is_none());
+        store.save().unwrap();
+
+        let mut store = TrustStore::load(&path).unwrap();
+        assert_eq!(store.check("core", "aa11"), KeyCheck::Trusted);
+        assert_eq!(store.check("other", "aa11"), KeyCheck::FirstUse);
+        let KeyCheck::Changed(pinned) = store.check("core", "bb22") else {
+            panic!("expected a changed key");
+        };
+        assert_eq!(pinned.public_key, "aa11");
+        assert_eq!(pinned.version, "1.0.0");
+
+        // Accepting the new key replaces the pin
+        let replaced = store.pin("core", "bb22", "2.0.0").unwrap();
+        assert_eq!(replaced.public_key, "aa11");
+        assert_eq!(store.check("core", "bb22"), KeyCheck::Trusted);
+        assert!(store.unpin("core").is_some());
+        assert_eq!(store.check("core", "bb22"), KeyCheck::FirstUse);
+    }
+
+    #[test]
+    fn test_endorsed_rotation() {
+        let old = crate::PackageSigningKey::generate().unwrap();
+        let new = crate::PackageSigningKey::generate().unwrap();
+        let endorsement = KeyEndorsement {
+            previous_key: old.public_key_hex(),
+            signature: hex::encode(old.endorse_key(&new.public_key_hex()).to_bytes()),
+        };
+
+        let mut store = TrustStore::new("trusted_keys.json");
+        store.pin("core", &old.public_key_hex(), "1.0.0");
+        assert!(matches!(
+            store.check_endorsed("core", &new.public_key_hex(), Some(&endorsement)),
+            KeyCheck::Rotated(pinned) if pinned.version == "1.0.0"
+        ));
+        assert!(matches!(
+            store.check_endorsed("core", &new.public_key_hex(), None),
+            KeyCheck::Changed(_)
+        ));
+
+        // An endorsement by some other key proves nothing
+        let other = crate::PackageSigningKey::generate().unwrap();
+        let forged = KeyEndorsement {
+            previous_key: other.public_key_hex(),
+            signature: hex::encode(other.endorse_key(&new.public_key_hex()).to_bytes()),
+        };
+        assert!(matches!(
+            store.check_endorsed("core", &new.public_key_hex(), Some(&forged)),
+            KeyCheck::Changed(_)
+        ));
+        // Nor does the endorsement of a different key
+        assert!(matches!(
+            store.check_endorsed("core", &other.public_key_hex(), Some(&endorsement)),
+            KeyCheck::Changed(_)
+        ));
+    }
+
+    #[test]
+    fn test_unsigned_with_pinned_key() {
+        let mut store = TrustStore::new("trusted_keys.json");
+        assert!(store.check_unsigned("core", "1.0.0").is_ok());
+
+        store.pin("core", "aa11", "1.0.0");
+        let error = store.check_unsigned("core", "1.1.0").unwrap_err();
+        assert!(matches!(error, Error::SignatureVerificationFailed(_)));
+        assert!(error.to_string().contains("core@1.1.0 is NOT SIGNED"));
+        assert!(store.check_unsigned("other", "1.0.0").is_ok());
+    }
+
+    #[test]
+    fn test_key_changed_message() {
+        let pinned = PinnedKey {
+            public_key: "aa11".to_string(),
+            version: "1.0.0".to_string(),
+            pinned_at: "2026-01-01T00:00:00+00:00".to_string(),
+        };
+        let message = key_changed_message("core", "1.1.0", "bb22", &pinned);
+        assert!(message.contains("aa11 (trusted with 1.0.0 on 2026-01-01"));
+        assert!(message.contains("core@1.1.0 key: bb22"));
+        assert!(message.contains("unrealpm trust core --key bb22"));
+    }
+}
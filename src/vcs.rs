@@ -0,0 +1,305 @@
+//! Perforce and Plastic SCM workspaces
+//!
+//! Many Unreal teams keep `Plugins/` under Perforce or Plastic SCM, where
+//! files are read-only until they are checked out. With `[vcs] type =
+//! "perforce"` (or `"plastic"`) in the project or user config, every file
+//! unrealpm writes goes through [`track`]:
+//!
+//! 1. existing files are opened for edit (`p4 edit` / `cm checkout`)
+//! 2. the write runs
+//! 3. new files are opened for add (`p4 add` / `cm add`) and files that
+//!    disappeared for delete (`p4 delete` / `cm remove`)
+//!
+//! Installs, updates, and uninstalls then leave a pending changelist behind
+//! instead of failing on read-only files. [`finish`] summarizes it.
+//!
+//! The workspace is process-wide: the CLI selects it once at startup with
+//! [`init`]. Without one, [`track`] just runs the write.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::Path;
+//! use unrealpm::vcs;
+//!
+//! // No workspace configured: the write runs untouched
+//! let written = vcs::track(&[Path::new("unrealpm.lock")], || Ok(42)).unwrap();
+//! assert_eq!(written, 42);
+//! assert!(vcs::finish().is_none());
+//! ```
+
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Files passed to a single VCS command, to stay under command-line limits
+const BATCH_SIZE: usize = 100;
+
+static WORKSPACE: Mutex<Option<Workspace>> = Mutex::new(None);
+
+/// Version control system that owns the project's files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VcsKind {
+    Perforce,
+    Plastic,
+}
+
+impl VcsKind {
+    /// Name of the command-line client
+    pub fn program(&self) -> &'static str {
+        match self {
+            VcsKind::Perforce => "p4",
+            VcsKind::Plastic => "cm",
+        }
+    }
+
+    /// Human-readable product name
+    pub fn label(&self) -> &'static str {
+        match self {
+            VcsKind::Perforce => "Perforce",
+            VcsKind::Plastic => "Plastic SCM",
+        }
+    }
+
+    /// Command that lists the pending changes
+    pub fn review_command(&self) -> &'static str {
+        match self {
+            VcsKind::Perforce => "p4 opened",
+            VcsKind::Plastic => "cm status",
+        }
+    }
+
+    /// Client commands (each is run with a batch of files appended) for an
+    /// operation
+    fn commands(&self, operation: Operation) -> &'static [&'static [&'static str]] {
+        match (self, operation) {
+            (VcsKind::Perforce, Operation::Edit) => &[&["edit"]],
+            (VcsKind::Perforce, Operation::Add) => &[&["add"]],
+            // The files were opened for edit before they were removed; drop
+            // that without restoring them, then open them for delete
+            (VcsKind::Perforce, Operation::Delete) => &[&["revert", "-k"], &["delete", "-k"]],
+            (VcsKind::Plastic, Operation::Edit) => &[&["checkout"]],
+            (VcsKind::Plastic, Operation::Add) => &[&["add"]],
+            (VcsKind::Plastic, Operation::Delete) => &[&["remove"]],
+        }
+    }
+}
+
+impl fmt::Display for VcsKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VcsKind::Perforce => "perforce",
+            VcsKind::Plastic => "plastic",
+        })
+    }
+}
+
+impl FromStr for VcsKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "perforce" | "p4" => Ok(VcsKind::Perforce),
+            "plastic" | "cm" => Ok(VcsKind::Plastic),
+            _ => Err(Error::Other(format!(
+                "Unknown version control system '{}'. Use: perforce, plastic",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Edit,
+    Add,
+    Delete,
+}
+
+/// Files opened in the workspace during this run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PendingChanges {
+    pub edited: usize,
+    pub added: usize,
+    pub deleted: usize,
+}
+
+impl PendingChanges {
+    pub fn is_empty(&self) -> bool {
+        self.edited + self.added + self.deleted == 0
+    }
+}
+
+/// Summary of what a run left in the workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub kind: VcsKind,
+    pub changes: PendingChanges,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} pending changelist: {} edited, {} added, {} deleted\n  Review with: {}",
+            self.kind.label(),
+            self.changes.edited,
+            self.changes.added,
+            self.changes.deleted,
+            self.kind.review_command()
+        )
+    }
+}
+
+#[derive(Debug)]
+struct Workspace {
+    kind: VcsKind,
+    changes: PendingChanges,
+}
+
+/// Select the workspace for the rest of the process (`None` turns it off)
+pub fn init(kind: Option<VcsKind>) {
+    *lock() = kind.map(|kind| Workspace {
+        kind,
+        changes: PendingChanges::default(),
+    });
+}
+
+/// Version control system in use, if any
+pub fn kind() -> Option<VcsKind> {
+    lock().as_ref().map(|workspace| workspace.kind)
+}
+
+/// Run `write`, which creates, changes, or removes files under `paths`
+///
+/// `paths` may be files or directories. With a workspace configured, the
+/// files already there are checked out first and the changes are opened in
+/// the pending changelist afterwards. A failed write still records the
+/// files it changed.
+pub fn track<T>(paths: &[&Path], write: impl FnOnce() -> Result<T>) -> Result<T> {
+    let Some(kind) = kind() else {
+        return write();
+    };
+
+    let before = files_under(paths);
+    run(kind, Operation::Edit, before.iter())?;
+
+    let result = write();
+
+    let after = files_under(paths);
+    run(kind, Operation::Add, after.difference(&before))?;
+    run(kind, Operation::Delete, before.difference(&after))?;
+
+    if let Some(workspace) = lock().as_mut() {
+        workspace.changes.edited += before.intersection(&after).count();
+        workspace.changes.added += after.difference(&before).count();
+        workspace.changes.deleted += before.difference(&after).count();
+    }
+    result
+}
+
+/// What this run left in the pending changelist, if anything
+pub fn finish() -> Option<Summary> {
+    let mut workspace = lock();
+    let workspace = workspace.as_mut()?;
+    let changes = std::mem::take(&mut workspace.changes);
+    (!changes.is_empty()).then_some(Summary {
+        kind: workspace.kind,
+        changes,
+    })
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<Workspace>> {
+    WORKSPACE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Every file under `paths`, recursing into directories
+fn files_under(paths: &[&Path]) -> BTreeSet<PathBuf> {
+    paths
+        .iter()
+        .flat_map(|path| walkdir::WalkDir::new(path).into_iter().flatten())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// Run the client commands for `operation` on `files`
+fn run<'a>(
+    kind: VcsKind,
+    operation: Operation,
+    files: impl Iterator<Item = &'a PathBuf>,
+) -> Result<()> {
+    let files: Vec<&PathBuf> = files.collect();
+    for batch in files.chunks(BATCH_SIZE) {
+        for args in kind.commands(operation) {
+            let output = Command::new(kind.program())
+                .args(*args)
+                .args(batch)
+                .output()
+                .map_err(|e| {
+                    Error::Other(format!(
+                        "{} is configured ([vcs] type = \"{}\") but '{}' could not be run: {}\n\n\
+                        • Install the {} command-line client and make sure it is on PATH\n\
+                        • Or turn the integration off: unrealpm config set vcs.type none",
+                        kind.label(),
+                        kind,
+                        kind.program(),
+                        e,
+                        kind.label()
+                    ))
+                })?;
+            if !output.status.success() {
+                return Err(Error::Other(format!(
+                    "'{} {}' failed: {}\n\n\
+                    • Check that the project is inside your {} workspace",
+                    kind.program(),
+                    args.join(" "),
+                    String::from_utf8_lossy(&output.stderr).trim(),
+                    kind.label()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vcs_kind_parse() {
+        assert_eq!("perforce".parse::<VcsKind>().unwrap(), VcsKind::Perforce);
+        assert_eq!("P4".parse::<VcsKind>().unwrap(), VcsKind::Perforce);
+        assert_eq!("plastic".parse::<VcsKind>().unwrap(), VcsKind::Plastic);
+        assert!("git".parse::<VcsKind>().is_err());
+        assert_eq!(VcsKind::Plastic.to_string(), "plastic");
+    }
+
+    #[test]
+    fn test_files_under_walks_directories() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let plugin = temp.path().join("Plugins").join("Awesome");
+        std::fs::create_dir_all(plugin.join("Source")).unwrap();
+        std::fs::write(plugin.join("Awesome.uplugin"), "{}").unwrap();
+        std::fs::write(plugin.join("Source").join("Awesome.cpp"), "").unwrap();
+        let lockfile = temp.path().join("unrealpm.lock");
+        std::fs::write(&lockfile, "").unwrap();
+
+        let files = files_under(&[&plugin, &lockfile, &temp.path().join("missing")]);
+        assert_eq!(
+            files.into_iter().collect::<Vec<_>>(),
+            vec![
+                plugin.join("Awesome.uplugin"),
+                plugin.join("Source").join("Awesome.cpp"),
+                lockfile,
+            ]
+        );
+    }
+}
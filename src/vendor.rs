@@ -0,0 +1,348 @@
+//! Vendored package mirrors for offline and air-gapped installs
+//!
+//! `unrealpm vendor` copies every locked package's tarball (and signature,
+//! when the registry has one) plus a snapshot of its registry metadata into
+//! a directory laid out like a file registry:
+//!
+//! ```text
+//! unrealpm-vendor/
+//!   index.json              what is vendored, and from where
+//!   packages/<name>.json    metadata, limited to the vendored versions
+//!   tarballs/<name>-<version>.tar.gz
+//!   signatures/<name>-<version>.sig
+//! ```
+//!
+//! With offline mode on ([`set_offline_dir`]), [`RegistryClient::from_config`]
+//! reads from that directory instead of the configured registry, so
+//! resolution, downloads, and verification never touch the network. A
+//! package missing from the mirror fails with the list of what is missing
+//! rather than a connection timeout.
+//!
+//! The mirror lives in `unrealpm-vendor/` next to the manifest, or wherever
+//! `UNREALPM_VENDOR_DIR` points. It can be committed or copied to machines
+//! without network access.
+//!
+//! # Examples
+//!
+//! ```
+//! use unrealpm::vendor::VendorIndex;
+//!
+//! let mut index = VendorIndex::new("https://registry.example.com");
+//! index.add("awesome-plugin", "1.2.0");
+//!
+//! assert!(index.contains("awesome-plugin"));
+//! assert_eq!(index.missing(["awesome-plugin", "other-plugin"]), vec!["other-plugin"]);
+//! ```
+//!
+//! [`RegistryClient::from_config`]: crate::RegistryClient::from_config
+
+use crate::registry::FileRegistryClient;
+use crate::{calculate_checksum, DryRun, Error, Lockfile, PackageMetadata, RegistryClient, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Default mirror directory, next to the manifest
+pub const VENDOR_DIR_NAME: &str = "unrealpm-vendor";
+
+/// Index file at the root of a mirror
+pub const INDEX_FILE_NAME: &str = "index.json";
+
+/// Mirror that replaces the registry while offline mode is on
+static OFFLINE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Use the mirror at `dir` instead of the registry for the rest of the
+/// process (`None` turns offline mode off)
+pub fn set_offline_dir(dir: Option<PathBuf>) {
+    *OFFLINE_DIR.lock().unwrap_or_else(|e| e.into_inner()) = dir;
+}
+
+/// The mirror in use, if offline mode is on
+pub fn offline_dir() -> Option<PathBuf> {
+    OFFLINE_DIR
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Mirror directory for the project in `project_dir`
+///
+/// `UNREALPM_VENDOR_DIR` overrides the default `unrealpm-vendor/`.
+pub fn vendor_dir<P: AsRef<Path>>(project_dir: P) -> PathBuf {
+    match std::env::var_os("UNREALPM_VENDOR_DIR") {
+        Some(dir) if !dir.is_empty() => project_dir.as_ref().join(dir),
+        _ => project_dir.as_ref().join(VENDOR_DIR_NAME),
+    }
+}
+
+/// What a mirror holds (`index.json`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VendorIndex {
+    /// Registry the packages were copied from
+    pub source: String,
+
+    /// When the mirror was last written (RFC 3339)
+    pub generated_at: String,
+
+    /// Vendored artifacts per package: versions, or binary artifact ids
+    pub packages: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl VendorIndex {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            packages: BTreeMap::new(),
+        }
+    }
+
+    /// Load the index of the mirror at `dir`
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let path = dir.as_ref().join(INDEX_FILE_NAME);
+        let content = fs::read_to_string(&path).map_err(|e| {
+            Error::Other(format!(
+                "No vendored packages at {} ({})\n\n\
+                • Run 'unrealpm vendor' while online to create the mirror",
+                dir.as_ref().display(),
+                e
+            ))
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the index into the mirror at `dir`
+    pub fn save<P: AsRef<Path>>(&self, dir: P) -> Result<()> {
+        let path = dir.as_ref().join(INDEX_FILE_NAME);
+        let content = serde_json::to_string_pretty(self)?;
+        crate::vcs::track(&[&path], || Ok(fs::write(&path, content)?))
+    }
+
+    /// Record an artifact of `name`
+    pub fn add(&mut self, name: &str, artifact: &str) {
+        self.packages
+            .entry(name.to_string())
+            .or_default()
+            .insert(artifact.to_string());
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.packages.contains_key(name)
+    }
+
+    /// Names from `names` that the mirror doesn't hold, sorted and deduplicated
+    pub fn missing<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+        let missing: BTreeSet<&str> = names
+            .into_iter()
+            .filter(|name| !self.contains(name))
+            .collect();
+        missing.into_iter().collect()
+    }
+}
+
+/// A package copied into a mirror
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendoredPackage {
+    pub name: String,
+    pub version: String,
+
+    /// Version, or the binary artifact id for pre-built binaries
+    pub artifact: String,
+
+    /// Whether the tarball was already in the mirror
+    pub up_to_date: bool,
+}
+
+/// Copy every locked registry package from `registry` into the mirror at `dir`
+///
+/// Packages installed from a local path or tarball, and Fab/Marketplace
+/// plugins, have nothing to copy and are skipped. Metadata snapshots keep
+/// only the vendored versions, so offline resolution can't pick a version
+/// the mirror lacks; versions vendored by an earlier run are kept. Tarballs
+/// are checksum-verified before they are copied. In a dry run nothing is
+/// downloaded or written.
+pub fn vendor_packages<P: AsRef<Path>>(
+    registry: &RegistryClient,
+    lockfile: &Lockfile,
+    dir: P,
+    dry_run: DryRun,
+) -> Result<Vec<VendoredPackage>> {
+    let dir = dir.as_ref();
+    let mirror = FileRegistryClient::new(dir);
+    let mut index = VendorIndex::load(dir).unwrap_or_else(|_| VendorIndex::new(""));
+    index.source = registry.location();
+    index.generated_at = chrono::Utc::now().to_rfc3339();
+
+    let mut names: Vec<&String> = lockfile
+        .packages
+        .iter()
+        .filter(|(_, locked)| locked.source.is_none())
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+
+    let mut vendored = Vec::new();
+    for name in names {
+        let locked = &lockfile.packages[name];
+        let artifact = locked.artifact_id();
+        let tarball_path = mirror.get_tarball_path(name, artifact);
+        let up_to_date = tarball_path.exists()
+            && calculate_checksum(&tarball_path)
+                .is_ok_and(|checksum| checksum == locked.artifact_checksum());
+
+        let mut metadata = registry.get_package(name)?;
+        metadata.versions.retain(|v| v.version == locked.version);
+        if metadata.versions.is_empty() {
+            return Err(Error::Other(format!(
+                "{}@{} is locked but no longer listed by {}",
+                name,
+                locked.version,
+                registry.location()
+            )));
+        }
+
+        dry_run.apply(|| {
+            if !up_to_date {
+                let source = registry.fetch_tarball(name, artifact, locked.artifact_checksum())?;
+                registry.verify_tarball(
+                    name,
+                    artifact,
+                    &source,
+                    locked.artifact_checksum(),
+                    None,
+                )?;
+                copy_into(&source, &tarball_path)?;
+            }
+            // Unsigned packages have no signature to copy
+            if let Ok(signature) = registry.download_signature(name, artifact) {
+                if signature.exists() {
+                    copy_into(&signature, &mirror.get_signature_path(name, artifact))?;
+                }
+            }
+            write_metadata(&mirror, merge_metadata(&mirror, metadata))
+        })?;
+
+        index.add(name, artifact);
+        vendored.push(VendoredPackage {
+            name: name.clone(),
+            version: locked.version.clone(),
+            artifact: artifact.to_string(),
+            up_to_date,
+        });
+    }
+
+    dry_run.apply(|| index.save(dir))?;
+    Ok(vendored)
+}
+
+/// `metadata` plus the versions an earlier run already vendored
+fn merge_metadata(mirror: &FileRegistryClient, mut metadata: PackageMetadata) -> PackageMetadata {
+    if let Ok(existing) = mirror.get_package(&metadata.name) {
+        for version in existing.versions {
+            if !metadata
+                .versions
+                .iter()
+                .any(|v| v.version == version.version)
+            {
+                metadata.versions.push(version);
+            }
+        }
+    }
+    metadata
+}
+
+fn write_metadata(mirror: &FileRegistryClient, metadata: PackageMetadata) -> Result<()> {
+    let path = mirror
+        .get_packages_dir()
+        .join(format!("{}.json", crate::scope::encode(&metadata.name)));
+    let content = serde_json::to_string_pretty(&metadata)?;
+    fs::create_dir_all(mirror.get_packages_dir())?;
+    crate::vcs::track(&[&path], || Ok(fs::write(&path, content)?))
+}
+
+fn copy_into(source: &Path, dest: &Path) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    crate::vcs::track(&[dest], || {
+        fs::copy(source, dest)?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert!(VendorIndex::load(temp.path()).is_err());
+
+        let mut index = VendorIndex::new("https://registry.example.com");
+        index.add("beta", "1.0.0");
+        index.add("alpha", "2.0.0");
+        index.add("alpha", "2.0.0");
+        index.save(temp.path()).unwrap();
+
+        let loaded = VendorIndex::load(temp.path()).unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(loaded.packages["alpha"].len(), 1);
+        assert_eq!(
+            loaded.missing(["gamma", "alpha", "gamma", "delta"]),
+            vec!["delta", "gamma"]
+        );
+    }
+
+    #[test]
+    fn test_vendor_from_file_registry() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let registry_dir = temp.path().join("registry");
+        let source = FileRegistryClient::new(&registry_dir);
+        fs::create_dir_all(source.get_packages_dir()).unwrap();
+        fs::create_dir_all(source.get_tarballs_dir()).unwrap();
+
+        fs::write(source.get_tarball_path("alpha", "1.0.0"), b"alpha tarball").unwrap();
+        let checksum = calculate_checksum(source.get_tarball_path("alpha", "1.0.0")).unwrap();
+        fs::write(
+            source.get_packages_dir().join("alpha.json"),
+            format!(
+                r#"{{"name": "alpha", "description": null, "versions": [
+                    {{"version": "1.0.0", "tarball": "alpha-1.0.0.tar.gz", "checksum": "{}", "dependencies": null}},
+                    {{"version": "2.0.0", "tarball": "alpha-2.0.0.tar.gz", "checksum": "0", "dependencies": null}}
+                ]}}"#,
+                checksum
+            ),
+        )
+        .unwrap();
+        let registry = RegistryClient::File(source);
+
+        let mut lockfile = Lockfile::new();
+        lockfile.update_package("alpha".into(), "1.0.0".into(), checksum.clone(), None);
+        lockfile.update_package("local".into(), "1.0.0".into(), "abc".into(), None);
+        lockfile.set_source("local", Some("path+../Local".to_string()));
+
+        let mirror_dir = temp.path().join("vendor");
+        let dry = vendor_packages(&registry, &lockfile, &mirror_dir, DryRun::new(true)).unwrap();
+        assert_eq!(dry.len(), 1);
+        assert!(!mirror_dir.exists());
+
+        let vendored = vendor_packages(&registry, &lockfile, &mirror_dir, DryRun::OFF).unwrap();
+        assert_eq!(vendored[0].name, "alpha");
+        assert!(!vendored[0].up_to_date);
+
+        let mirror = FileRegistryClient::new(&mirror_dir);
+        let metadata = mirror.get_package("alpha").unwrap();
+        assert_eq!(metadata.versions.len(), 1);
+        assert_eq!(
+            calculate_checksum(mirror.get_tarball_path("alpha", "1.0.0")).unwrap(),
+            checksum
+        );
+        assert!(VendorIndex::load(&mirror_dir).unwrap().contains("alpha"));
+
+        let again = vendor_packages(&registry, &lockfile, &mirror_dir, DryRun::OFF).unwrap();
+        assert!(again[0].up_to_date);
+    }
+}
@@ -9,6 +9,8 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
+pub use unrealpm::testing::{MockPlugin, TestRegistry};
+
 /// Production registry URL
 pub const PRODUCTION_REGISTRY: &str = "https://registry.unreal.dev";
 
@@ -172,183 +174,6 @@ impl Default for TestProject {
     }
 }
 
-/// Test fixture for a mock plugin
-pub struct MockPlugin {
-    pub name: String,
-    pub version: String,
-    pub engine_versions: Vec<String>,
-    pub dependencies: Vec<(String, String)>,
-}
-
-impl MockPlugin {
-    pub fn new(name: &str, version: &str) -> Self {
-        Self {
-            name: name.to_string(),
-            version: version.to_string(),
-            engine_versions: vec!["5.3".to_string(), "5.4".to_string()],
-            dependencies: vec![],
-        }
-    }
-
-    pub fn with_engine_versions(mut self, versions: Vec<&str>) -> Self {
-        self.engine_versions = versions.into_iter().map(String::from).collect();
-        self
-    }
-
-    pub fn with_dependency(mut self, name: &str, version: &str) -> Self {
-        self.dependencies
-            .push((name.to_string(), version.to_string()));
-        self
-    }
-
-    /// Create .uplugin content
-    pub fn uplugin_content(&self) -> String {
-        format!(
-            r#"{{
-    "FileVersion": 3,
-    "Version": 1,
-    "VersionName": "{}",
-    "FriendlyName": "{}",
-    "Description": "Test plugin",
-    "Category": "Testing",
-    "CreatedBy": "UnrealPM Tests",
-    "CanContainContent": true,
-    "IsBetaVersion": false,
-    "IsExperimentalVersion": false,
-    "Installed": false,
-    "Modules": [
-        {{
-            "Name": "{}",
-            "Type": "Runtime",
-            "LoadingPhase": "Default"
-        }}
-    ]
-}}"#,
-            self.version, self.name, self.name
-        )
-    }
-
-    /// Create the plugin in a directory
-    pub fn create_in(&self, dir: &Path) {
-        let plugin_dir = dir.join(&self.name);
-        fs::create_dir_all(&plugin_dir).expect("Failed to create plugin directory");
-
-        // Create .uplugin
-        fs::write(
-            plugin_dir.join(format!("{}.uplugin", self.name)),
-            self.uplugin_content(),
-        )
-        .expect("Failed to write .uplugin");
-
-        // Create Source directory
-        let source_dir = plugin_dir.join("Source").join(&self.name);
-        fs::create_dir_all(&source_dir).expect("Failed to create source directory");
-
-        // Create a minimal module file
-        let module_cpp = format!(
-            r#"#include "{}.h"
-#include "Modules/ModuleManager.h"
-
-IMPLEMENT_MODULE(FDefaultModuleImpl, {})
-"#,
-            self.name, self.name
-        );
-        fs::write(source_dir.join(format!("{}.cpp", self.name)), module_cpp)
-            .expect("Failed to write module cpp");
-
-        let module_h = r#"#pragma once
-
-#include "CoreMinimal.h"
-"#;
-        fs::write(source_dir.join(format!("{}.h", self.name)), module_h)
-            .expect("Failed to write module header");
-
-        // Create Build.cs
-        let build_cs = format!(
-            r#"using UnrealBuildTool;
-
-public class {} : ModuleRules
-{{
-    public {}(ReadOnlyTargetRules Target) : base(Target)
-    {{
-        PCHUsage = ModuleRules.PCHUsageMode.UseExplicitOrSharedPCHs;
-        PublicDependencyModuleNames.AddRange(new string[] {{ "Core", "CoreUObject", "Engine" }});
-    }}
-}}
-"#,
-            self.name, self.name
-        );
-        fs::write(source_dir.join(format!("{}.Build.cs", self.name)), build_cs)
-            .expect("Failed to write Build.cs");
-    }
-}
-
-/// Helper to create a file-based test registry
-pub struct TestRegistry {
-    pub temp_dir: TempDir,
-    pub packages_dir: PathBuf,
-    pub tarballs_dir: PathBuf,
-    pub signatures_dir: PathBuf,
-}
-
-impl TestRegistry {
-    pub fn new() -> Self {
-        let temp_dir = TempDir::new().expect("Failed to create temp directory");
-        let root = temp_dir.path();
-
-        let packages_dir = root.join("packages");
-        let tarballs_dir = root.join("tarballs");
-        let signatures_dir = root.join("signatures");
-
-        fs::create_dir_all(&packages_dir).expect("Failed to create packages dir");
-        fs::create_dir_all(&tarballs_dir).expect("Failed to create tarballs dir");
-        fs::create_dir_all(&signatures_dir).expect("Failed to create signatures dir");
-
-        Self {
-            temp_dir,
-            packages_dir,
-            tarballs_dir,
-            signatures_dir,
-        }
-    }
-
-    pub fn path(&self) -> &Path {
-        self.temp_dir.path()
-    }
-
-    /// Add a package to the test registry
-    pub fn add_package(&self, plugin: &MockPlugin) {
-        let metadata = format!(
-            r#"{{
-    "name": "{}",
-    "description": "Test package",
-    "versions": [
-        {{
-            "version": "{}",
-            "tarball": "{}-{}.tar.gz",
-            "checksum": "0000000000000000000000000000000000000000000000000000000000000000",
-            "engine_versions": {:?},
-            "package_type": "source"
-        }}
-    ]
-}}"#,
-            plugin.name, plugin.version, plugin.name, plugin.version, plugin.engine_versions
-        );
-
-        fs::write(
-            self.packages_dir.join(format!("{}.json", plugin.name)),
-            metadata,
-        )
-        .expect("Failed to write package metadata");
-    }
-}
-
-impl Default for TestRegistry {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 /// Assertions for test results
 pub mod assertions {
     use std::path::Path;